@@ -12,6 +12,11 @@ fn mermaid_escape_label(value: &str) -> String {
     value.replace('"', "#quot;")
 }
 
+/// Mermaid statements end at a line break, so a multi-line description is folded onto one line.
+fn single_line(value: &str) -> String {
+    value.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
 pub struct MermaidERDiagramGenerator {
     database_model: Rc<DatabaseModel>,
 }
@@ -37,6 +42,14 @@ impl DiagramGenerator for MermaidERDiagramGenerator {
 
         for (table, raw_table_name) in tables.iter().zip(table_names.iter()) {
             let table_id = &table_id_map[raw_table_name.as_str()];
+            // erDiagram has no note or label for an entity as a whole, so a table's
+            // description travels with the diagram source as a comment.
+            if let Some(description) = table.description() {
+                output.push_str(&format!("    %% {}\n", single_line(description)));
+            }
+            if let Some(partitioning) = table.partitioning() {
+                output.push_str(&format!("    %% partitioned by {}\n", partitioning));
+            }
             if table_id == raw_table_name {
                 output.push_str(&format!("    {} {{\n", table_id));
             } else {
@@ -72,7 +85,12 @@ impl DiagramGenerator for MermaidERDiagramGenerator {
                     ""
                 };
 
-                output.push_str(&format!("        {} {}{}\n", type_label, col_token, annotation));
+                let comment = col
+                    .description()
+                    .map(|d| format!(" \"{}\"", mermaid_escape_label(&single_line(d))))
+                    .unwrap_or_default();
+
+                output.push_str(&format!("        {} {}{}{}\n", type_label, col_token, annotation, comment));
             }
 
             output.push_str("    }\n");
@@ -95,7 +113,8 @@ impl DiagramGenerator for MermaidERDiagramGenerator {
                     RelationType::Enforce | RelationType::Cascade => "}o--||",
                     RelationType::SetNull | RelationType::DoNothing => "}o--o|",
                 };
-                let from_col = mermaid_escape_label(relation.from_column_name());
+                // A composite relation is labelled with all of its columns, in order.
+                let from_col = mermaid_escape_label(&relation.from_column_names().join(", "));
                 output.push_str(&format!(
                     "    {} {} {} : \"{}\"\n",
                     from_table, cardinality, to_table, from_col
@@ -113,8 +132,9 @@ mod tests {
     use schema_model::builder::{ColumnBuilder, KeyBuilder, SchemaBuilder, TableBuilder};
     use schema_model::model::column_type::ColumnType;
     use schema_model::model::database_model::DatabaseModel;
-    use schema_model::model::relation::Relation;
-    use schema_model::model::types::{BooleanMode, ForeignKeyMode, KeyType, RelationType};
+    use schema_model::model::relation::{Relation, RelationColumn};
+    use schema_model::model::partition::{PartitionTemplate, Partitioning};
+    use schema_model::model::types::{BooleanMode, ForeignKeyMode, KeyType, PartitionStrategy, RelationType};
     use std::rc::Rc;
 
     fn build_test_model() -> DatabaseModel {
@@ -279,4 +299,75 @@ mod tests {
 
         assert!(output.contains("\"id#quot;quoted\""));
     }
+
+    #[test]
+    fn composite_relation_labelled_with_every_column() {
+        let customer = TableBuilder::new(None::<&str>, "customer")
+            .add_column(ColumnBuilder::new(None::<&str>, "tenant_id", ColumnType::Int).required(true).build())
+            .add_column(ColumnBuilder::new(None::<&str>, "id", ColumnType::Int).required(true).build())
+            .add_key(KeyBuilder::new(KeyType::Primary).add_column("tenant_id").add_column("id").build())
+            .build();
+        let invoice = TableBuilder::new(None::<&str>, "invoice")
+            .add_column(ColumnBuilder::new(None::<&str>, "id", ColumnType::Int).required(true).build())
+            .add_column(ColumnBuilder::new(None::<&str>, "tenant_id", ColumnType::Int).required(true).build())
+            .add_column(ColumnBuilder::new(None::<&str>, "customer_id", ColumnType::Int).required(true).build())
+            .add_key(KeyBuilder::new(KeyType::Primary).add_column("id").build())
+            .add_relation(Relation::new_composite(
+                "customer",
+                "invoice",
+                vec![
+                    RelationColumn::new("tenant_id", "tenant_id"),
+                    RelationColumn::new("customer_id", "id"),
+                ],
+                RelationType::Enforce,
+                false,
+            ))
+            .build();
+        let schema = SchemaBuilder::new(None::<&str>).add_table(customer).add_table(invoice).build();
+        let model = Rc::new(DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema]));
+
+        let generator = MermaidERDiagramGenerator::new(model);
+        let output = generator.generate();
+
+        assert!(output.contains("INVOICE }o--|| CUSTOMER : \"tenant_id, customer_id\""));
+        assert!(output.contains("int tenant_id FK"));
+        assert!(output.contains("int customer_id FK"));
+    }
+
+    #[test]
+    fn descriptions_become_table_and_attribute_comments() {
+        let table = TableBuilder::new(None::<&str>, "customer")
+            .description(Some("People who\nbuy things".to_string()))
+            .add_column(ColumnBuilder::new(None::<&str>, "id", ColumnType::Sequence).required(true).description(Some("Surrogate key".to_string())).build())
+            .add_column(ColumnBuilder::new(None::<&str>, "name", ColumnType::Varchar).description(Some("Full \"legal\" name".to_string())).build())
+            .add_key(KeyBuilder::new(KeyType::Primary).add_column("id").build())
+            .build();
+        let schema = SchemaBuilder::new(None::<&str>).add_table(table).build();
+        let model = DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema]);
+
+        let output = MermaidERDiagramGenerator::new(Rc::new(model)).generate();
+
+        assert!(output.contains("    %% People who buy things\n    CUSTOMER {"));
+        assert!(output.contains(" id PK \"Surrogate key\"\n"));
+        assert!(output.contains(" name \"Full #quot;legal#quot; name\"\n"));
+    }
+
+    #[test]
+    fn partitioned_table_gets_a_comment() {
+        let table = TableBuilder::new(None::<&str>, "events")
+            .add_column(ColumnBuilder::new(None::<&str>, "id", ColumnType::Long).required(true).build())
+            .partitioning(Some(Partitioning::new(
+                PartitionStrategy::Hash,
+                vec!["id".to_string()],
+                vec![],
+                Some(PartitionTemplate::Hash { modulus: 4 }),
+            )))
+            .build();
+        let schema = SchemaBuilder::new(None::<&str>).add_table(table).build();
+        let model = DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema]);
+
+        let output = MermaidERDiagramGenerator::new(Rc::new(model)).generate();
+
+        assert!(output.contains("    %% partitioned by hash (id)\n    EVENTS {"));
+    }
 }
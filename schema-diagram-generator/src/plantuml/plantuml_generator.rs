@@ -1,6 +1,7 @@
 use crate::common::column_type_label::column_type_label;
 use crate::common::diagram_generator::DiagramGenerator;
 use crate::common::safe_identifier::{build_safe_identifier_map, sanitize_token};
+use schema_model::model::column::Column;
 use schema_model::model::database_model::DatabaseModel;
 use schema_model::model::types::RelationType;
 use std::rc::Rc;
@@ -11,6 +12,14 @@ fn plantuml_escape_quoted(value: &str) -> String {
     value.replace('"', "\\\"")
 }
 
+/// A column's description, shown in italics after its type on the same (single) line.
+fn column_description(column: &Column) -> String {
+    column
+        .description()
+        .map(|d| format!(" //{}//", d.split_whitespace().collect::<Vec<_>>().join(" ")))
+        .unwrap_or_default()
+}
+
 pub struct PlantUMLERDiagramGenerator {
     database_model: Rc<DatabaseModel>,
 }
@@ -38,16 +47,22 @@ impl DiagramGenerator for PlantUMLERDiagramGenerator {
 
         for (i, (table, raw_table_name)) in tables.iter().zip(table_names.iter()).enumerate() {
             let table_alias = &table_id_map[raw_table_name.as_str()];
+            // A partitioned table is marked with a stereotype naming its partition key.
+            let stereotype = table
+                .partitioning()
+                .map(|partitioning| format!(" <<partitioned by {}>>", partitioning))
+                .unwrap_or_default();
             if table_alias == raw_table_name {
-                output.push_str(&format!("entity {} {{\n", table_alias));
+                output.push_str(&format!("entity {}{} {{\n", table_alias, stereotype));
             } else {
                 // The name needed sanitizing (and/or disambiguating) to stay a safe,
                 // unique token; keep it legible via PlantUML's
                 // `entity "display name" as Alias` syntax rather than silently losing it.
                 output.push_str(&format!(
-                    "entity \"{}\" as {} {{\n",
+                    "entity \"{}\" as {}{} {{\n",
                     plantuml_escape_quoted(raw_table_name),
-                    table_alias
+                    table_alias,
+                    stereotype
                 ));
             }
 
@@ -75,7 +90,7 @@ impl DiagramGenerator for PlantUMLERDiagramGenerator {
 
             for col in &pk_cols {
                 let type_label = column_type_label(col.column_type());
-                output.push_str(&format!("  * {} : {} <<PK>>\n", column_id_map[col.name()], type_label));
+                output.push_str(&format!("  * {} : {} <<PK>>{}\n", column_id_map[col.name()], type_label, column_description(col)));
             }
 
             if !pk_cols.is_empty() {
@@ -86,14 +101,22 @@ impl DiagramGenerator for PlantUMLERDiagramGenerator {
                 let type_label = column_type_label(col.column_type());
                 let col_token = &column_id_map[col.name()];
                 if table.column_relation(col).is_some() {
-                    output.push_str(&format!("  {} : {} <<FK>>\n", col_token, type_label));
+                    output.push_str(&format!("  {} : {} <<FK>>{}\n", col_token, type_label, column_description(col)));
                 } else {
-                    output.push_str(&format!("  {} : {}\n", col_token, type_label));
+                    output.push_str(&format!("  {} : {}{}\n", col_token, type_label, column_description(col)));
                 }
             }
 
             output.push_str("}\n");
 
+            if let Some(description) = table.description() {
+                output.push_str(&format!("note top of {}\n", table_alias));
+                for line in description.lines() {
+                    output.push_str(&format!("  {}\n", line.trim()));
+                }
+                output.push_str("end note\n");
+            }
+
             if i < table_count - 1 {
                 output.push('\n');
             }
@@ -121,7 +144,13 @@ impl DiagramGenerator for PlantUMLERDiagramGenerator {
                     RelationType::Enforce | RelationType::Cascade => "}o--||",
                     RelationType::SetNull | RelationType::DoNothing => "}o--o|",
                 };
-                let from_col = sanitize_token(relation.from_column_name());
+                // A composite relation is labelled with all of its columns, in order.
+                let from_col = relation
+                    .from_column_names()
+                    .iter()
+                    .map(|c| sanitize_token(c))
+                    .collect::<Vec<_>>()
+                    .join(", ");
                 output.push_str(&format!(
                     "{} {} {} : {}\n",
                     from_table, cardinality, to_table, from_col
@@ -140,8 +169,9 @@ mod tests {
     use schema_model::builder::{ColumnBuilder, KeyBuilder, SchemaBuilder, TableBuilder};
     use schema_model::model::column_type::ColumnType;
     use schema_model::model::database_model::DatabaseModel;
-    use schema_model::model::relation::Relation;
-    use schema_model::model::types::{BooleanMode, ForeignKeyMode, KeyType, RelationType};
+    use schema_model::model::relation::{Relation, RelationColumn};
+    use schema_model::model::partition::Partitioning;
+    use schema_model::model::types::{BooleanMode, ForeignKeyMode, KeyType, PartitionStrategy, RelationType};
     use std::rc::Rc;
 
     fn build_test_model() -> DatabaseModel {
@@ -287,4 +317,71 @@ mod tests {
 
         assert!(output.contains("  display_name : varchar"));
     }
+
+    #[test]
+    fn composite_relation_labelled_with_every_column() {
+        let customer = TableBuilder::new(None::<&str>, "customer")
+            .add_column(ColumnBuilder::new(None::<&str>, "tenant_id", ColumnType::Int).required(true).build())
+            .add_column(ColumnBuilder::new(None::<&str>, "id", ColumnType::Int).required(true).build())
+            .add_key(KeyBuilder::new(KeyType::Primary).add_column("tenant_id").add_column("id").build())
+            .build();
+        let invoice = TableBuilder::new(None::<&str>, "invoice")
+            .add_column(ColumnBuilder::new(None::<&str>, "id", ColumnType::Int).required(true).build())
+            .add_column(ColumnBuilder::new(None::<&str>, "tenant_id", ColumnType::Int).required(true).build())
+            .add_column(ColumnBuilder::new(None::<&str>, "customer_id", ColumnType::Int).required(true).build())
+            .add_key(KeyBuilder::new(KeyType::Primary).add_column("id").build())
+            .add_relation(Relation::new_composite(
+                "customer",
+                "invoice",
+                vec![
+                    RelationColumn::new("tenant_id", "tenant_id"),
+                    RelationColumn::new("customer_id", "id"),
+                ],
+                RelationType::Enforce,
+                false,
+            ))
+            .build();
+        let schema = SchemaBuilder::new(None::<&str>).add_table(customer).add_table(invoice).build();
+        let model = Rc::new(DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema]));
+
+        let generator = PlantUMLERDiagramGenerator::new(model);
+        let output = generator.generate();
+
+        assert!(output.contains("INVOICE }o--|| CUSTOMER : tenant_id, customer_id"));
+        assert!(output.contains("  tenant_id : int <<FK>>"));
+        assert!(output.contains("  customer_id : int <<FK>>"));
+    }
+
+    #[test]
+    fn descriptions_become_a_note_and_column_text() {
+        let table = TableBuilder::new(None::<&str>, "customer")
+            .description(Some("People who\nbuy things".to_string()))
+            .add_column(ColumnBuilder::new(None::<&str>, "id", ColumnType::Sequence).required(true).description(Some("Surrogate key".to_string())).build())
+            .add_column(ColumnBuilder::new(None::<&str>, "name", ColumnType::Varchar).description(Some("Full name".to_string())).build())
+            .add_key(KeyBuilder::new(KeyType::Primary).add_column("id").build())
+            .build();
+        let schema = SchemaBuilder::new(None::<&str>).add_table(table).build();
+        let model = DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema]);
+
+        let output = PlantUMLERDiagramGenerator::new(Rc::new(model)).generate();
+
+        assert!(output.contains("  * id : "));
+        assert!(output.contains(" <<PK>> //Surrogate key//\n"));
+        assert!(output.contains("  name : varchar //Full name//\n"));
+        assert!(output.contains("}\nnote top of CUSTOMER\n  People who\n  buy things\nend note\n"));
+    }
+
+    #[test]
+    fn partitioned_table_gets_a_stereotype() {
+        let table = TableBuilder::new(None::<&str>, "events")
+            .add_column(ColumnBuilder::new(None::<&str>, "created_at", ColumnType::Date).required(true).build())
+            .partitioning(Some(Partitioning::new(PartitionStrategy::Range, vec!["created_at".to_string()], vec![], None)))
+            .build();
+        let schema = SchemaBuilder::new(None::<&str>).add_table(table).build();
+        let model = DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema]);
+
+        let output = PlantUMLERDiagramGenerator::new(Rc::new(model)).generate();
+
+        assert!(output.contains("entity EVENTS <<partitioned by range (created_at)>> {\n"));
+    }
 }
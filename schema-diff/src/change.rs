@@ -1,21 +1,49 @@
+use std::fmt;
+
+use schema_model::model::aggregation::Aggregation;
 use schema_model::model::column::Column;
 use schema_model::model::constraint::Constraint;
+use schema_model::model::enum_type::EnumType;
+use schema_model::model::function::Function;
 use schema_model::model::key::Key;
+use schema_model::model::other_sql::OtherSql;
+use schema_model::model::partition::{Partition, Partitioning};
+use schema_model::model::procedure::Procedure;
 use schema_model::model::relation::Relation;
+use schema_model::model::sequence::Sequence;
+use schema_model::model::trigger::Trigger;
+use schema_model::model::types::{LockEscalation, TableOption};
 use schema_model::model::view::View;
 
+/// One step of a migration. Table, view and sequence names are qualified with their
+/// schema (`sales.orders`) when the schema has a name; a `Relation`'s own table names are
+/// left as the model wrote them.
 #[derive(Debug, Clone)]
+#[allow(clippy::large_enum_variant)]
 pub enum SchemaChange {
+    AddSchema {
+        schema_name: String,
+    },
+    DropSchema {
+        schema_name: String,
+    },
     AddTable {
         table_name: String,
     },
     DropTable {
         table_name: String,
     },
+    /// The table `old_name` takes the name `new_name`, staying in its schema.
     RenameTable {
         old_name: String,
         new_name: String,
     },
+    /// The table `table_name` moves, keeping its name, to `new_schema_name` (`None` being
+    /// the default schema).
+    MoveTable {
+        table_name: String,
+        new_schema_name: Option<String>,
+    },
     AddColumn {
         table_name: String,
         column: Column,
@@ -30,18 +58,68 @@ pub enum SchemaChange {
         old_name: String,
         new_name: String,
     },
+    /// The storage options of a table kept across the migration changed: its compression,
+    /// its SQLite `strict` and `without rowid` options, or its lock escalation. The options
+    /// listed are the ones among those the table has; `columns` are the table's columns,
+    /// for dialects that compress column by column.
+    ModifyTableOptions {
+        table_name: String,
+        old_options: Vec<TableOption>,
+        new_options: Vec<TableOption>,
+        old_lock_escalation: LockEscalation,
+        new_lock_escalation: LockEscalation,
+        columns: Vec<Column>,
+    },
+    /// Attributes of a column kept across the migration changed; `changes` lists which,
+    /// in the order `ColumnAttribute` declares them. `enum_type` is the enum type
+    /// `new_column` is of, for dialects that spell out its values in the column's type or
+    /// check constraint.
     ModifyColumn {
         table_name: String,
         old_column: Column,
         new_column: Column,
+        changes: Vec<ColumnAttribute>,
+        enum_type: Option<EnumType>,
     },
+    /// A table's (`column_name` is `None`) or a column's description was added, changed,
+    /// or removed (`new_description` is `None`).
+    ModifyDescription {
+        table_name: String,
+        column_name: Option<String>,
+        old_description: Option<String>,
+        new_description: Option<String>,
+    },
+    /// `position` is the key's place among its table's keys of its type, which the name
+    /// `ConstraintNamer::key_name` gives it is numbered by. A key whose position changed
+    /// is dropped and added again under its new name.
     AddKey {
         table_name: String,
         key: Key,
+        position: usize,
     },
+    /// `position` is the key's place in the old table, where its name was numbered.
     DropKey {
         table_name: String,
         key: Key,
+        position: usize,
+    },
+    /// A key kept on the same columns and at the same position changed its options:
+    /// uniqueness, method, filter, compression, included columns, clustering, or the
+    /// ordering of its columns. Its table and columns keep their names, so the name the
+    /// create script gave `old_key` is the one `new_key` is given.
+    ModifyKey {
+        table_name: String,
+        old_key: Key,
+        new_key: Key,
+        position: usize,
+    },
+    /// The index `old_name` takes the name the create script gives `key`, the `position`th
+    /// index of its table; it already indexes the right columns.
+    RenameIndex {
+        table_name: String,
+        old_name: String,
+        key: Key,
+        position: usize,
     },
     AddConstraint {
         table_name: String,
@@ -51,16 +129,200 @@ pub enum SchemaChange {
         table_name: String,
         constraint_name: String,
     },
+    RenameConstraint {
+        table_name: String,
+        old_name: String,
+        new_name: String,
+    },
+    /// `position` is the relation's place among its table's relations, which the name
+    /// `ConstraintNamer::foreign_key_name` gives its foreign key is numbered by. A relation
+    /// whose position changed is dropped and added again under its new name.
     AddRelation {
+        table_name: String,
         relation: Relation,
+        position: usize,
     },
+    /// `position` is the relation's place in the old table, where its name was numbered.
     DropRelation {
+        table_name: String,
         relation: Relation,
+        position: usize,
+    },
+    /// A relation kept between the same columns and at the same position changed what a
+    /// delete does to the rows referencing the deleted one.
+    ModifyRelation {
+        table_name: String,
+        old_relation: Relation,
+        new_relation: Relation,
+        position: usize,
     },
     AddView {
         view: View,
     },
     DropView {
         view_name: String,
+        materialized: bool,
+    },
+    AddPartition {
+        table_name: String,
+        partition: Partition,
+    },
+    DropPartition {
+        table_name: String,
+        partition_name: String,
+    },
+    /// A table became partitioned, stopped being partitioned, or changed its strategy or
+    /// partition key; none of these can be altered in place.
+    ModifyPartitioning {
+        table_name: String,
+        old_partitioning: Option<Partitioning>,
+        new_partitioning: Option<Partitioning>,
+    },
+    AddSequence {
+        sequence: Sequence,
     },
+    DropSequence {
+        sequence_name: String,
+    },
+    /// A sequence's options changed; its current value is left where it is.
+    ModifySequence {
+        old_sequence: Sequence,
+        new_sequence: Sequence,
+    },
+    AddEnumType {
+        enum_type: EnumType,
+    },
+    DropEnumType {
+        enum_type_name: String,
+    },
+    /// An enum type's values (their codes) were added, removed or reordered. `columns` are
+    /// the columns of the type, each with its qualified table name, for dialects that
+    /// have to rebuild the type or restate the columns.
+    ModifyEnumType {
+        old_enum_type: EnumType,
+        new_enum_type: EnumType,
+        columns: Vec<(String, Column)>,
+    },
+    AddFunction {
+        function: Function,
+    },
+    DropFunction {
+        function: Function,
+    },
+    ModifyFunction {
+        old_function: Function,
+        new_function: Function,
+    },
+    AddProcedure {
+        procedure: Procedure,
+    },
+    DropProcedure {
+        procedure: Procedure,
+    },
+    ModifyProcedure {
+        old_procedure: Procedure,
+        new_procedure: Procedure,
+    },
+    /// Custom trigger code was added to a table. Triggers have no name of their own; the
+    /// code runs inside the table's generated delete or update trigger.
+    AddTrigger {
+        table_name: String,
+        trigger: Trigger,
+    },
+    DropTrigger {
+        table_name: String,
+        trigger: Trigger,
+    },
+    /// An aggregation was added to a table. Like custom trigger code, it is kept up to date
+    /// by the table's generated delete and update triggers.
+    AddAggregation {
+        table_name: String,
+        aggregation: Aggregation,
+    },
+    DropAggregation {
+        table_name: String,
+        aggregation: Aggregation,
+    },
+    /// Free-form SQL was added to the model. Changed SQL is dropped and added again, since
+    /// it has nothing else to be matched on.
+    AddOtherSql {
+        other_sql: OtherSql,
+    },
+    DropOtherSql {
+        other_sql: OtherSql,
+    },
+}
+
+/// An attribute of a column that a `ModifyColumn` changes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnAttribute {
+    Type,
+    Length,
+    Scale,
+    Required,
+    Default,
+    Check,
+    /// The expression of a generated column, or whether the column is generated at all.
+    Generated,
+    EnumType,
+    ElementType,
+    /// The lower bound of the check constraint generated from `minValue`.
+    MinValue,
+    /// The upper bound of the check constraint generated from `maxValue`.
+    MaxValue,
+    Sequence,
+    IdentityMode,
+    IdentityStart,
+    IdentityIncrement,
+}
+
+impl ColumnAttribute {
+    /// Whether the attribute is part of the column's SQL type.
+    pub fn is_type(self) -> bool {
+        matches!(
+            self,
+            ColumnAttribute::Type
+                | ColumnAttribute::Length
+                | ColumnAttribute::Scale
+                | ColumnAttribute::EnumType
+                | ColumnAttribute::ElementType
+        )
+    }
+
+    /// Whether the attribute feeds the column's check constraint: its own check, or the
+    /// one generated from its min and max values.
+    pub fn is_check(self) -> bool {
+        matches!(self, ColumnAttribute::Check | ColumnAttribute::MinValue | ColumnAttribute::MaxValue)
+    }
+
+    /// Whether the attribute is an option of an identity column.
+    pub fn is_identity(self) -> bool {
+        matches!(
+            self,
+            ColumnAttribute::IdentityMode | ColumnAttribute::IdentityStart | ColumnAttribute::IdentityIncrement
+        )
+    }
+}
+
+impl fmt::Display for ColumnAttribute {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ColumnAttribute::Type => "type",
+            ColumnAttribute::Length => "length",
+            ColumnAttribute::Scale => "scale",
+            ColumnAttribute::Required => "required",
+            ColumnAttribute::Default => "default",
+            ColumnAttribute::Check => "check",
+            ColumnAttribute::Generated => "generated",
+            ColumnAttribute::EnumType => "enum type",
+            ColumnAttribute::ElementType => "element type",
+            ColumnAttribute::MinValue => "min value",
+            ColumnAttribute::MaxValue => "max value",
+            ColumnAttribute::Sequence => "sequence",
+            ColumnAttribute::IdentityMode => "identity mode",
+            ColumnAttribute::IdentityStart => "identity start",
+            ColumnAttribute::IdentityIncrement => "identity increment",
+        };
+        f.write_str(name)
+    }
 }
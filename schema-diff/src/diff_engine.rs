@@ -1,264 +1,1282 @@
 use schema_model::model::column::Column;
 use schema_model::model::constraint::Constraint;
+use schema_model::model::constraint_naming::numbered_keys;
+use schema_model::model::database_model::DatabaseModel;
+use schema_model::model::enum_type::EnumType;
 use schema_model::model::key::Key;
+use schema_model::model::other_sql::OtherSql;
+use schema_model::model::partition::Partitioning;
 use schema_model::model::relation::Relation;
 use schema_model::model::schema::Schema;
+use schema_model::model::sequence::Sequence;
+use schema_model::model::table::Table;
+use schema_model::model::trigger::Trigger;
+use schema_model::model::types::{OtherSqlOrder, TableOption};
 use schema_model::model::view::View;
 
-use crate::change::SchemaChange;
+use crate::change::{ColumnAttribute, SchemaChange};
 use crate::change_set::ChangeSet;
+use crate::error::DiffError;
 
 pub struct SchemaDiffEngine;
 
 impl SchemaDiffEngine {
-    pub fn diff(old: &Schema, new: &Schema) -> ChangeSet {
+    /// Diffs two versions of one schema. Names in the changes are qualified with the new
+    /// schema's name, if it has one. A table, column, constraint or index declaring a
+    /// previous name is renamed rather than dropped and created again; the diff fails when
+    /// the old schema has nothing of that name.
+    pub fn diff(old: &Schema, new: &Schema) -> Result<ChangeSet, DiffError> {
+        let schemas = vec![SchemaPair {
+            schema_name: new.schema_name(),
+            old,
+            new,
+        }];
+        let renames = find_renamed_tables(&schemas)?;
         let mut change_set = ChangeSet::new();
+        diff_schema_pairs(&schemas, &[], &renames, &mut change_set)?;
+        Ok(change_set)
+    }
+
+    /// Diffs two models, pairing their schemas by name. A schema only in `new` is created
+    /// before anything else and one only in `old` is dropped after everything in it; a
+    /// table that left one schema for another under the same name is moved rather than
+    /// dropped and created again. Previous names are followed within a schema, as by `diff`.
+    pub fn diff_models(old: &DatabaseModel, new: &DatabaseModel) -> Result<ChangeSet, DiffError> {
+        let empty_schemas: Vec<Schema> = old
+            .schemas()
+            .iter()
+            .chain(new.schemas())
+            .map(|schema| Schema::new(schema.schema_name()))
+            .collect();
+        let empty_schema = |schema_name: Option<&str>| {
+            empty_schemas
+                .iter()
+                .find(|schema| optional_names_equal(schema.schema_name(), schema_name))
+                .expect("an empty schema is made for every schema name")
+        };
+
+        let mut schemas: Vec<SchemaPair> = Vec::new();
+        for old_schema in old.schemas() {
+            let new_schema = find_schema(new, old_schema.schema_name());
+            schemas.push(SchemaPair {
+                schema_name: new_schema.map_or(old_schema.schema_name(), |s| s.schema_name()),
+                old: old_schema,
+                new: new_schema.unwrap_or_else(|| empty_schema(old_schema.schema_name())),
+            });
+        }
+        for new_schema in new.schemas() {
+            if find_schema(old, new_schema.schema_name()).is_none() {
+                schemas.push(SchemaPair {
+                    schema_name: new_schema.schema_name(),
+                    old: empty_schema(new_schema.schema_name()),
+                    new: new_schema,
+                });
+            }
+        }
+
+        let mut change_set = ChangeSet::new();
+        for new_schema in new.schemas() {
+            if let Some(schema_name) = new_schema.schema_name()
+                && find_schema(old, Some(schema_name)).is_none()
+            {
+                change_set.add_change(SchemaChange::AddSchema {
+                    schema_name: schema_name.to_string(),
+                });
+            }
+        }
 
-        // Drop phase (order matters: views → relations → keys → constraints → columns → tables)
-        diff_drop_views(old, new, &mut change_set);
-        diff_drop_relations(old, new, &mut change_set);
-        diff_drop_keys(old, new, &mut change_set);
-        diff_drop_constraints(old, new, &mut change_set);
-        diff_drop_columns(old, new, &mut change_set);
-        diff_drop_tables(old, new, &mut change_set);
+        let renames = find_renamed_tables(&schemas)?;
+        let moves = find_moved_tables(&schemas, &renames);
+        for moved in &moves {
+            change_set.add_change(SchemaChange::MoveTable {
+                table_name: qualified_name(moved.old_schema_name, moved.old_table.name()),
+                new_schema_name: moved.new_schema_name.map(str::to_string),
+            });
+        }
 
-        // Add phase (order matters: tables → columns → modify columns → keys → constraints → relations → views)
-        diff_add_tables(old, new, &mut change_set);
-        diff_add_columns(old, new, &mut change_set);
-        diff_modify_columns(old, new, &mut change_set);
-        diff_add_keys(old, new, &mut change_set);
-        diff_add_constraints(old, new, &mut change_set);
-        diff_add_relations(old, new, &mut change_set);
-        diff_add_views(old, new, &mut change_set);
+        diff_schema_pairs(&schemas, &moves, &renames, &mut change_set)?;
 
-        change_set
+        for old_schema in old.schemas() {
+            if let Some(schema_name) = old_schema.schema_name()
+                && find_schema(new, Some(schema_name)).is_none()
+            {
+                change_set.add_change(SchemaChange::DropSchema {
+                    schema_name: schema_name.to_string(),
+                });
+            }
+        }
+
+        Ok(change_set)
     }
 }
 
-fn diff_drop_tables(old: &Schema, new: &Schema, cs: &mut ChangeSet) {
-    for old_table in old.tables() {
-        if new.get_optional_table(old_table.name()).is_none() {
-            cs.add_change(SchemaChange::DropTable {
-                table_name: old_table.name().to_string(),
-            });
+/// The old and new version of a schema; a schema that only exists on one side is paired
+/// with an empty one.
+struct SchemaPair<'a> {
+    schema_name: Option<&'a str>,
+    old: &'a Schema,
+    new: &'a Schema,
+}
+
+/// A table that is in a different schema in the new model than in the old one, or that was
+/// renamed within its schema.
+#[derive(Clone, Copy)]
+struct MovedTable<'a> {
+    old_schema_name: Option<&'a str>,
+    old_table: &'a Table,
+    new_schema_name: Option<&'a str>,
+    new_table: &'a Table,
+}
+
+/// A table on both sides of the diff, under the qualified name it has once the
+/// migration has run and the one it has until its renames run.
+struct TablePair<'a> {
+    name: String,
+    old_name: String,
+    schema_name: Option<&'a str>,
+    old: &'a Table,
+    new: &'a Table,
+    renamed_columns: Vec<(&'a Column, &'a Column)>,
+    renamed_constraints: Vec<(&'a Constraint, &'a Constraint)>,
+}
+
+impl<'a> TablePair<'a> {
+    /// The old version of `new_column`, found by its name or the one it was renamed from.
+    fn old_column(&self, new_column: &Column) -> Option<&'a Column> {
+        self.old.column_checked(new_column.name()).or_else(|| {
+            self.renamed_columns
+                .iter()
+                .find(|(_, new)| new.name().eq_ignore_ascii_case(new_column.name()))
+                .map(|(old, _)| *old)
+        })
+    }
+
+    fn has_new_column(&self, old_column: &Column) -> bool {
+        self.new.has_column(old_column.name())
+            || self.renamed_columns.iter().any(|(old, _)| old.name().eq_ignore_ascii_case(old_column.name()))
+    }
+
+    /// The name an old column has once the columns are renamed.
+    fn renamed_column_name<'b>(&'b self, old_name: &'b str) -> &'b str {
+        self.renamed_columns
+            .iter()
+            .find(|(old, _)| old.name().eq_ignore_ascii_case(old_name))
+            .map_or(old_name, |(_, new)| new.name())
+    }
+
+    fn old_keys(&self) -> impl Iterator<Item = &'a Key> {
+        self.old.keys().iter().chain(self.old.indexes())
+    }
+}
+
+fn find_schema<'a>(model: &'a DatabaseModel, schema_name: Option<&str>) -> Option<&'a Schema> {
+    model
+        .schemas()
+        .iter()
+        .find(|schema| optional_names_equal(schema.schema_name(), schema_name))
+}
+
+fn qualified_name(schema_name: Option<&str>, name: &str) -> String {
+    match schema_name {
+        Some(schema_name) => format!("{}.{}", schema_name, name),
+        None => name.to_string(),
+    }
+}
+
+fn schema_description(schema_name: Option<&str>) -> String {
+    match schema_name {
+        Some(schema_name) => format!("schema {}", schema_name),
+        None => "the default schema".to_string(),
+    }
+}
+
+/// Pairs every new item declaring a previous name with the old item of that name. A hint is
+/// ignored once `is_known` finds the item under its new name, as it does in every diff
+/// after the one that renamed it; items sharing a name (a constraint per database) share
+/// their rename.
+#[allow(clippy::too_many_arguments)]
+fn find_renamed<'a, T>(
+    kind: &str,
+    owner: &str,
+    old_items: &'a [T],
+    new_items: &'a [T],
+    name: impl Fn(&T) -> &str,
+    previous_name: impl Fn(&T) -> Option<&str>,
+    is_known: impl Fn(&T) -> bool,
+) -> Result<Vec<(&'a T, &'a T)>, DiffError> {
+    let find = |items: &'a [T], wanted: &str| items.iter().find(|item| name(item).eq_ignore_ascii_case(wanted));
+    let mut renamed: Vec<(&'a T, &'a T)> = Vec::new();
+    for new_item in new_items {
+        let Some(previous) = previous_name(new_item) else {
+            continue;
+        };
+        if is_known(new_item) || renamed.iter().any(|(_, new)| name(new).eq_ignore_ascii_case(name(new_item))) {
+            continue;
+        }
+        let Some(old_item) = find(old_items, previous) else {
+            return Err(DiffError::UnknownPreviousName(format!(
+                "{} {} in {} was previously named {}, but the old {} has no {} of either name",
+                kind,
+                name(new_item),
+                owner,
+                previous,
+                owner,
+                kind
+            )));
+        };
+        if find(new_items, previous).is_some() || renamed.iter().any(|(old, _)| name(old).eq_ignore_ascii_case(previous)) {
+            return Err(DiffError::ConflictingPreviousName(format!(
+                "{} {} in {} was previously named {}, but that {} is still in use",
+                kind,
+                name(new_item),
+                owner,
+                previous,
+                kind
+            )));
         }
+        renamed.push((old_item, new_item));
+    }
+    Ok(renamed)
+}
+
+/// Tables renamed within their schema. A table whose name is found in some other old schema
+/// has moved there, and is left to `find_moved_tables`.
+fn find_renamed_tables<'a>(schemas: &[SchemaPair<'a>]) -> Result<Vec<MovedTable<'a>>, DiffError> {
+    let mut renames = Vec::new();
+    for pair in schemas {
+        let renamed = find_renamed(
+            "table",
+            &schema_description(pair.schema_name),
+            pair.old.tables(),
+            pair.new.tables(),
+            Table::name,
+            Table::previous_name,
+            |table| schemas.iter().any(|other| other.old.get_optional_table(table.name()).is_some()),
+        )?;
+        renames.extend(renamed.into_iter().map(|(old_table, new_table)| MovedTable {
+            old_schema_name: pair.schema_name,
+            old_table,
+            new_schema_name: pair.schema_name,
+            new_table,
+        }));
     }
+    Ok(renames)
 }
 
-fn diff_add_tables(old: &Schema, new: &Schema, cs: &mut ChangeSet) {
-    for new_table in new.tables() {
-        if old.get_optional_table(new_table.name()).is_none() {
-            cs.add_change(SchemaChange::AddTable {
-                table_name: new_table.name().to_string(),
+/// A table is taken to have moved when it's the only one of its name to leave a schema and
+/// the only one of that name to arrive in another. Renamed tables have neither left nor
+/// arrived.
+fn find_moved_tables<'a>(schemas: &[SchemaPair<'a>], renames: &[MovedTable<'a>]) -> Vec<MovedTable<'a>> {
+    let departed: Vec<(Option<&str>, &Table)> = schemas
+        .iter()
+        .flat_map(|pair| {
+            pair.old
+                .tables()
+                .iter()
+                .filter(|table| {
+                    pair.new.get_optional_table(table.name()).is_none() && !is_moved_from(renames, pair.schema_name, table)
+                })
+                .map(|table| (pair.schema_name, table))
+        })
+        .collect();
+    let arrived: Vec<(Option<&str>, &Table)> = schemas
+        .iter()
+        .flat_map(|pair| {
+            pair.new
+                .tables()
+                .iter()
+                .filter(|table| {
+                    pair.old.get_optional_table(table.name()).is_none() && !is_moved_to(renames, pair.schema_name, table)
+                })
+                .map(|table| (pair.schema_name, table))
+        })
+        .collect();
+
+    let mut moves = Vec::new();
+    for &(old_schema_name, old_table) in &departed {
+        let named = |tables: &[(Option<&'a str>, &'a Table)]| -> Vec<(Option<&'a str>, &'a Table)> {
+            tables
+                .iter()
+                .filter(|(_, table)| table.name().eq_ignore_ascii_case(old_table.name()))
+                .copied()
+                .collect()
+        };
+        if let ([_], [(new_schema_name, new_table)]) = (named(&departed).as_slice(), named(&arrived).as_slice()) {
+            moves.push(MovedTable {
+                old_schema_name,
+                old_table,
+                new_schema_name: *new_schema_name,
+                new_table,
             });
         }
     }
+    moves
 }
 
-fn diff_drop_columns(old: &Schema, new: &Schema, cs: &mut ChangeSet) {
-    for old_table in old.tables() {
-        if let Some(new_table) = new.get_optional_table(old_table.name()) {
-            for old_col in old_table.columns() {
-                if !new_table.has_column(old_col.name()) {
-                    let rename_candidates: Vec<String> = new_table
-                        .columns()
-                        .iter()
-                        .filter(|nc| !old_table.has_column(nc.name()) && nc.column_type() == old_col.column_type())
-                        .map(|nc| nc.name().to_string())
-                        .collect();
-                    cs.add_change(SchemaChange::DropColumn {
-                        table_name: old_table.name().to_string(),
-                        column_name: old_col.name().to_string(),
-                        rename_candidates,
-                    });
-                }
+fn is_moved_from(moves: &[MovedTable], schema_name: Option<&str>, table: &Table) -> bool {
+    moves
+        .iter()
+        .any(|m| optional_names_equal(m.old_schema_name, schema_name) && m.old_table.name().eq_ignore_ascii_case(table.name()))
+}
+
+fn is_moved_to(moves: &[MovedTable], schema_name: Option<&str>, table: &Table) -> bool {
+    moves
+        .iter()
+        .any(|m| optional_names_equal(m.new_schema_name, schema_name) && m.new_table.name().eq_ignore_ascii_case(table.name()))
+}
+
+fn table_pairs<'a>(schemas: &[SchemaPair<'a>], relocated: &[MovedTable<'a>]) -> Result<Vec<TablePair<'a>>, DiffError> {
+    let mut tables: Vec<(Option<&str>, &Table, &Table)> = schemas
+        .iter()
+        .flat_map(|pair| {
+            pair.new.tables().iter().filter_map(|new_table| {
+                pair.old
+                    .get_optional_table(new_table.name())
+                    .map(|old_table| (pair.schema_name, old_table, new_table))
+            })
+        })
+        .collect();
+    tables.extend(relocated.iter().map(|m| (m.new_schema_name, m.old_table, m.new_table)));
+
+    let mut pairs = Vec::new();
+    for (schema_name, old, new) in tables {
+        let name = qualified_name(schema_name, new.name());
+        let owner = format!("table {}", name);
+        let renamed_columns = find_renamed(
+            "column",
+            &owner,
+            old.columns(),
+            new.columns(),
+            Column::name,
+            Column::previous_name,
+            |column| old.has_column(column.name()),
+        )?;
+        let renamed_constraints = find_renamed(
+            "constraint",
+            &owner,
+            old.constraints(),
+            new.constraints(),
+            Constraint::name,
+            Constraint::previous_name,
+            |constraint| constraint_exists_in(constraint, old.constraints()),
+        )?;
+        pairs.push(TablePair {
+            name,
+            old_name: qualified_name(schema_name, old.name()),
+            schema_name,
+            old,
+            new,
+            renamed_columns,
+            renamed_constraints,
+        });
+    }
+    Ok(pairs)
+}
+
+fn diff_schema_pairs(schemas: &[SchemaPair], moves: &[MovedTable], renames: &[MovedTable], cs: &mut ChangeSet) -> Result<(), DiffError> {
+    let relocated: Vec<MovedTable> = moves.iter().chain(renames).copied().collect();
+    let tables = table_pairs(schemas, &relocated)?;
+
+    // Drop phase (order matters: other SQL → procedures → views → triggers → aggregations → relations → keys → constraints → partitions → columns → tables → sequences → functions → enum types)
+    diff_drop_other_sql(schemas, cs);
+    diff_drop_procedures(schemas, cs);
+    diff_drop_views(schemas, cs);
+    diff_drop_triggers(&tables, cs);
+    diff_drop_aggregations(&tables, cs);
+    diff_drop_relations(&tables, cs);
+    diff_drop_keys(&tables, cs);
+    diff_drop_constraints(&tables, cs);
+    diff_drop_partitions(&tables, cs);
+    diff_drop_columns(&tables, cs);
+    diff_drop_tables(schemas, &relocated, cs);
+    diff_drop_sequences(schemas, cs);
+    diff_drop_functions(schemas, cs);
+    diff_drop_enum_types(schemas, cs);
+
+    // Rename phase: the drops above still name tables and columns as they were, the adds
+    // below as they become.
+    diff_rename_tables(renames, cs);
+    diff_rename_columns(&tables, cs);
+    diff_rename_constraints(&tables, cs);
+    diff_rename_indexes(&tables, cs)?;
+
+    // Add phase (order matters: top other SQL → enum types → sequences → functions → tables → columns → modify columns → table options → partitioning → descriptions → keys → constraints → relations → triggers → aggregations → views → procedures → bottom other SQL)
+    diff_add_other_sql(schemas, OtherSqlOrder::Top, cs);
+    diff_add_enum_types(schemas, cs);
+    diff_modify_enum_types(schemas, &tables, cs);
+    diff_add_sequences(schemas, cs);
+    diff_modify_sequences(schemas, cs);
+    diff_add_functions(schemas, cs);
+    diff_add_tables(schemas, &relocated, cs);
+    diff_add_columns(&tables, cs);
+    diff_modify_columns(schemas, &tables, cs);
+    diff_modify_table_options(&tables, cs);
+    diff_add_partitions(&tables, cs);
+    diff_descriptions(schemas, &tables, &relocated, cs);
+    diff_add_keys(&tables, cs);
+    diff_modify_keys(&tables, cs);
+    diff_add_constraints(&tables, cs);
+    diff_add_relations(&tables, cs);
+    diff_modify_relations(&tables, cs);
+    diff_add_triggers(&tables, cs);
+    diff_add_aggregations(&tables, cs);
+    diff_add_views(schemas, cs);
+    diff_add_procedures(schemas, cs);
+    diff_add_other_sql(schemas, OtherSqlOrder::Bottom, cs);
+    Ok(())
+}
+
+fn diff_drop_tables(schemas: &[SchemaPair], moves: &[MovedTable], cs: &mut ChangeSet) {
+    for pair in schemas {
+        for old_table in pair.old.tables() {
+            if pair.new.get_optional_table(old_table.name()).is_none() && !is_moved_from(moves, pair.schema_name, old_table) {
+                cs.add_change(SchemaChange::DropTable {
+                    table_name: qualified_name(pair.schema_name, old_table.name()),
+                });
             }
         }
     }
 }
 
-fn diff_add_columns(old: &Schema, new: &Schema, cs: &mut ChangeSet) {
-    for new_table in new.tables() {
-        if let Some(old_table) = old.get_optional_table(new_table.name()) {
-            for new_col in new_table.columns() {
-                if !old_table.has_column(new_col.name()) {
-                    cs.add_change(SchemaChange::AddColumn {
-                        table_name: new_table.name().to_string(),
-                        column: new_col.clone(),
-                    });
-                }
+fn diff_add_tables(schemas: &[SchemaPair], moves: &[MovedTable], cs: &mut ChangeSet) {
+    for pair in schemas {
+        for new_table in pair.new.tables() {
+            if pair.old.get_optional_table(new_table.name()).is_none() && !is_moved_to(moves, pair.schema_name, new_table) {
+                cs.add_change(SchemaChange::AddTable {
+                    table_name: qualified_name(pair.schema_name, new_table.name()),
+                });
             }
         }
     }
 }
 
-fn diff_modify_columns(old: &Schema, new: &Schema, cs: &mut ChangeSet) {
-    for new_table in new.tables() {
-        if let Some(old_table) = old.get_optional_table(new_table.name()) {
-            for new_col in new_table.columns() {
-                if old_table.has_column(new_col.name()) {
-                    let old_col = old_table.column(new_col.name());
-                    if columns_differ(old_col, new_col) {
-                        cs.add_change(SchemaChange::ModifyColumn {
-                            table_name: new_table.name().to_string(),
-                            old_column: old_col.clone(),
-                            new_column: new_col.clone(),
-                        });
-                    }
-                }
+fn diff_drop_columns(tables: &[TablePair], cs: &mut ChangeSet) {
+    for pair in tables {
+        for old_col in pair.old.columns() {
+            if !pair.has_new_column(old_col) {
+                let rename_candidates: Vec<String> = pair
+                    .new
+                    .columns()
+                    .iter()
+                    .filter(|nc| pair.old_column(nc).is_none() && nc.column_type() == old_col.column_type())
+                    .map(|nc| nc.name().to_string())
+                    .collect();
+                cs.add_change(SchemaChange::DropColumn {
+                    table_name: pair.old_name.clone(),
+                    column_name: old_col.name().to_string(),
+                    rename_candidates,
+                });
             }
         }
     }
 }
 
-fn columns_differ(a: &Column, b: &Column) -> bool {
-    a.column_type() != b.column_type()
-        || a.length() != b.length()
-        || a.scale() != b.scale()
-        || a.required() != b.required()
-        || a.default_constraint() != b.default_constraint()
-        || a.check_constraint() != b.check_constraint()
+fn diff_add_columns(tables: &[TablePair], cs: &mut ChangeSet) {
+    for pair in tables {
+        for new_col in pair.new.columns() {
+            if pair.old_column(new_col).is_none() {
+                cs.add_change(SchemaChange::AddColumn {
+                    table_name: pair.name.clone(),
+                    column: new_col.clone(),
+                });
+            }
+        }
+    }
 }
 
-fn diff_drop_keys(old: &Schema, new: &Schema, cs: &mut ChangeSet) {
-    for old_table in old.tables() {
-        if let Some(new_table) = new.get_optional_table(old_table.name()) {
-            for old_key in old_table.keys().iter().chain(old_table.indexes().iter()) {
-                if !key_exists_in(old_key, new_table.keys()) && !key_exists_in(old_key, new_table.indexes()) {
-                    cs.add_change(SchemaChange::DropKey {
-                        table_name: old_table.name().to_string(),
-                        key: old_key.clone(),
-                    });
-                }
+fn diff_modify_columns(schemas: &[SchemaPair], tables: &[TablePair], cs: &mut ChangeSet) {
+    for pair in tables {
+        let schema = schemas.iter().find(|schema| optional_names_equal(schema.schema_name, pair.schema_name));
+        for new_col in pair.new.columns() {
+            let Some(old_col) = pair.old_column(new_col) else {
+                continue;
+            };
+            let changes = changed_attributes(old_col, new_col);
+            if changes.is_empty() {
+                continue;
             }
+            let enum_type = new_col
+                .enum_type()
+                .and_then(|name| schema.and_then(|schema| schema.new.get_optional_enum_type(name)));
+            cs.add_change(SchemaChange::ModifyColumn {
+                table_name: pair.name.clone(),
+                old_column: old_col.clone(),
+                new_column: new_col.clone(),
+                changes,
+                enum_type: enum_type.cloned(),
+            });
         }
     }
 }
 
-fn diff_add_keys(old: &Schema, new: &Schema, cs: &mut ChangeSet) {
-    for new_table in new.tables() {
-        if let Some(old_table) = old.get_optional_table(new_table.name()) {
-            for new_key in new_table.keys().iter().chain(new_table.indexes().iter()) {
-                if !key_exists_in(new_key, old_table.keys()) && !key_exists_in(new_key, old_table.indexes()) {
-                    cs.add_change(SchemaChange::AddKey {
-                        table_name: new_table.name().to_string(),
-                        key: new_key.clone(),
+// Only the options a create script turns into SQL are compared: compression, the SQLite
+// table kinds and lock escalation. `data` and `noexport` only steer what gets exported.
+fn diff_modify_table_options(tables: &[TablePair], cs: &mut ChangeSet) {
+    for pair in tables {
+        let old_options = storage_options(pair.old);
+        let new_options = storage_options(pair.new);
+        let options_changed = old_options.len() != new_options.len()
+            || old_options.iter().any(|option| !new_options.contains(option));
+        if !options_changed && pair.old.lock_escalation() == pair.new.lock_escalation() {
+            continue;
+        }
+        cs.add_change(SchemaChange::ModifyTableOptions {
+            table_name: pair.name.clone(),
+            old_options,
+            new_options,
+            old_lock_escalation: pair.old.lock_escalation(),
+            new_lock_escalation: pair.new.lock_escalation(),
+            columns: pair.new.columns().to_vec(),
+        });
+    }
+}
+
+fn storage_options(table: &Table) -> Vec<TableOption> {
+    table
+        .options()
+        .iter()
+        .filter(|option| matches!(option, TableOption::Compress(_) | TableOption::Strict | TableOption::WithoutRowid))
+        .cloned()
+        .collect()
+}
+
+// A column without an identity mode is compared as a serial, which is what the reverse
+// engineer reads a serial column back as. Type names (enum, element and sequence) are
+// compared ignoring case, as the model looks them up.
+fn changed_attributes(a: &Column, b: &Column) -> Vec<ColumnAttribute> {
+    let attributes = [
+        (ColumnAttribute::Type, a.column_type() != b.column_type()),
+        (ColumnAttribute::Length, a.length() != b.length()),
+        (ColumnAttribute::Scale, a.scale() != b.scale()),
+        (ColumnAttribute::Required, a.required() != b.required()),
+        (ColumnAttribute::Default, a.default_constraint() != b.default_constraint()),
+        (ColumnAttribute::Check, a.check_constraint() != b.check_constraint()),
+        (ColumnAttribute::Generated, a.generated() != b.generated()),
+        (ColumnAttribute::EnumType, !optional_names_equal(a.enum_type(), b.enum_type())),
+        (ColumnAttribute::ElementType, !optional_names_equal(a.element_type(), b.element_type())),
+        (ColumnAttribute::MinValue, a.min_value() != b.min_value()),
+        (ColumnAttribute::MaxValue, a.max_value() != b.max_value()),
+        (ColumnAttribute::Sequence, !optional_names_equal(a.sequence(), b.sequence())),
+        (
+            ColumnAttribute::IdentityMode,
+            a.identity_mode().unwrap_or_default() != b.identity_mode().unwrap_or_default(),
+        ),
+        (ColumnAttribute::IdentityStart, a.identity_start() != b.identity_start()),
+        (ColumnAttribute::IdentityIncrement, a.identity_increment() != b.identity_increment()),
+    ];
+    attributes
+        .into_iter()
+        .filter(|(_, changed)| *changed)
+        .map(|(attribute, _)| attribute)
+        .collect()
+}
+
+fn optional_names_equal(a: Option<&str>, b: Option<&str>) -> bool {
+    match (a, b) {
+        (Some(a), Some(b)) => a.eq_ignore_ascii_case(b),
+        (a, b) => a == b,
+    }
+}
+
+/// Descriptions are diffed apart from the columns they belong to, so that a changed comment
+/// never turns into an `ALTER COLUMN`. New tables and columns are compared against "no
+/// description", which carries a description declared on them over to the migration too.
+fn diff_descriptions(schemas: &[SchemaPair], tables: &[TablePair], moves: &[MovedTable], cs: &mut ChangeSet) {
+    for pair in schemas {
+        for new_table in pair.new.tables() {
+            if pair.old.get_optional_table(new_table.name()).is_some() || is_moved_to(moves, pair.schema_name, new_table) {
+                continue;
+            }
+            let table_name = qualified_name(pair.schema_name, new_table.name());
+            if let Some(description) = new_table.description() {
+                cs.add_change(SchemaChange::ModifyDescription {
+                    table_name: table_name.clone(),
+                    column_name: None,
+                    old_description: None,
+                    new_description: Some(description.to_string()),
+                });
+            }
+            for column in new_table.columns() {
+                if let Some(description) = column.description() {
+                    cs.add_change(SchemaChange::ModifyDescription {
+                        table_name: table_name.clone(),
+                        column_name: Some(column.name().to_string()),
+                        old_description: None,
+                        new_description: Some(description.to_string()),
                     });
                 }
             }
         }
     }
+
+    for pair in tables {
+        let old_table_description = pair.old.description();
+        if old_table_description != pair.new.description() {
+            cs.add_change(SchemaChange::ModifyDescription {
+                table_name: pair.name.clone(),
+                column_name: None,
+                old_description: old_table_description.map(str::to_string),
+                new_description: pair.new.description().map(str::to_string),
+            });
+        }
+
+        for new_col in pair.new.columns() {
+            let old_col_description = pair.old_column(new_col).and_then(Column::description);
+            if old_col_description != new_col.description() {
+                cs.add_change(SchemaChange::ModifyDescription {
+                    table_name: pair.name.clone(),
+                    column_name: Some(new_col.name().to_string()),
+                    old_description: old_col_description.map(str::to_string),
+                    new_description: new_col.description().map(str::to_string),
+                });
+            }
+        }
+    }
 }
 
-fn key_exists_in(key: &Key, keys: &[Key]) -> bool {
-    keys.iter().any(|k| keys_equal(k, key))
+fn diff_drop_keys(tables: &[TablePair], cs: &mut ChangeSet) {
+    for pair in tables {
+        for (position, old_key) in numbered_keys(pair.old) {
+            let kept = numbered_keys(pair.new)
+                .into_iter()
+                .any(|(new_position, new_key)| key_kept(position, old_key, new_position, new_key, pair));
+            if !kept && modified_key(position, old_key, pair).is_none() {
+                cs.add_change(SchemaChange::DropKey {
+                    table_name: pair.old_name.clone(),
+                    key: old_key.clone(),
+                    position,
+                });
+            }
+        }
+    }
 }
 
-fn keys_equal(a: &Key, b: &Key) -> bool {
-    a.key_type() == b.key_type()
-        && a.columns().len() == b.columns().len()
-        && a.columns().iter().zip(b.columns().iter()).all(|(ac, bc)| {
-            ac.name().eq_ignore_ascii_case(bc.name())
+fn diff_add_keys(tables: &[TablePair], cs: &mut ChangeSet) {
+    for pair in tables {
+        for (position, new_key) in numbered_keys(pair.new) {
+            let kept = numbered_keys(pair.old).into_iter().any(|(old_position, old_key)| {
+                key_kept(old_position, old_key, position, new_key, pair)
+                    || modified_key(old_position, old_key, pair).is_some_and(|key| std::ptr::eq(key, new_key))
+            });
+            if !kept {
+                cs.add_change(SchemaChange::AddKey {
+                    table_name: pair.name.clone(),
+                    key: new_key.clone(),
+                    position,
+                });
+            }
+        }
+    }
+}
+
+fn diff_modify_keys(tables: &[TablePair], cs: &mut ChangeSet) {
+    for pair in tables {
+        for (position, old_key) in numbered_keys(pair.old) {
+            if numbered_keys(pair.new)
+                .into_iter()
+                .any(|(new_position, new_key)| key_kept(position, old_key, new_position, new_key, pair))
+            {
+                continue;
+            }
+            if let Some(new_key) = modified_key(position, old_key, pair) {
+                cs.add_change(SchemaChange::ModifyKey {
+                    table_name: pair.name.clone(),
+                    old_key: old_key.clone(),
+                    new_key: new_key.clone(),
+                    position,
+                });
+            }
+        }
+    }
+}
+
+/// Whether `old_key` is kept as `new_key`, each at its position among its table's keys of
+/// its type. The name the create script gives a key is numbered by its position, so a key
+/// that moved is dropped and added again under its new name, unless it is an index being
+/// renamed anyway.
+fn key_kept(old_position: usize, old_key: &Key, new_position: usize, new_key: &Key, pair: &TablePair) -> bool {
+    keys_equal(old_key, new_key, pair) && (old_position == new_position || renames_index(old_key, new_key))
+}
+
+/// Whether `new_key` renames the index `old_key`: it declares a previous name that the old
+/// one didn't declare already.
+fn renames_index(old_key: &Key, new_key: &Key) -> bool {
+    new_key
+        .previous_name()
+        .is_some_and(|name| !old_key.previous_name().is_some_and(|old_name| old_name.eq_ignore_ascii_case(name)))
+}
+
+/// The new key `old_key` became by changing only its options, at the same position. A key
+/// whose table or columns are renamed is dropped under its old names and added under the
+/// new ones instead, since its generated name can be made of them.
+fn modified_key<'a>(position: usize, old_key: &Key, pair: &TablePair<'a>) -> Option<&'a Key> {
+    if pair.old_name != pair.name {
+        return None;
+    }
+    numbered_keys(pair.new)
+        .into_iter()
+        .find(|(new_position, new_key)| {
+            *new_position == position
+                && same_key(old_key, new_key, pair)
+                && !key_options_equal(old_key, new_key)
+                && old_key.columns().iter().zip(new_key.columns().iter()).all(|(oc, nc)| oc.name().eq_ignore_ascii_case(nc.name()))
         })
+        .map(|(_, new_key)| new_key)
 }
 
-fn diff_drop_constraints(old: &Schema, new: &Schema, cs: &mut ChangeSet) {
-    for old_table in old.tables() {
-        if let Some(new_table) = new.get_optional_table(old_table.name()) {
-            for old_con in old_table.constraints() {
-                if !constraint_exists_in(old_con, new_table.constraints()) {
-                    cs.add_change(SchemaChange::DropConstraint {
-                        table_name: old_table.name().to_string(),
-                        constraint_name: old_con.name().to_string(),
-                    });
+/// Whether `old` is `new` once its table's columns are renamed; the database keeps a key on
+/// a renamed column, so such a key is not recreated.
+fn keys_equal(old: &Key, new: &Key, pair: &TablePair) -> bool {
+    same_key(old, new, pair) && key_options_equal(old, new)
+}
+
+/// Whether `old` and `new` are of the same type and on the same columns and expressions,
+/// whatever their options.
+fn same_key(old: &Key, new: &Key, pair: &TablePair) -> bool {
+    old.key_type() == new.key_type()
+        && old.columns().len() == new.columns().len()
+        && old.columns().iter().zip(new.columns().iter()).all(|(oc, nc)| {
+            oc.is_expression() == nc.is_expression()
+                && if oc.is_expression() {
+                    oc.name() == nc.name()
+                } else {
+                    pair.renamed_column_name(oc.name()).eq_ignore_ascii_case(nc.name())
                 }
+        })
+}
+
+fn key_options_equal(old: &Key, new: &Key) -> bool {
+    old.is_unique() == new.is_unique()
+        && old.method() == new.method()
+        && old.filter() == new.filter()
+        && old.compression() == new.compression()
+        && old.is_cluster() == new.is_cluster()
+        && optional_names_equal(old.include(), new.include())
+        && old.columns().iter().zip(new.columns().iter()).all(|(oc, nc)| {
+            oc.sort_order() == nc.sort_order() && oc.nulls_order() == nc.nulls_order()
+        })
+}
+
+/// An index declaring a previous name is renamed to the name the create script gives it,
+/// unless the old index declared the same one, in which case an earlier migration renamed
+/// it already.
+fn diff_rename_indexes(tables: &[TablePair], cs: &mut ChangeSet) -> Result<(), DiffError> {
+    for pair in tables {
+        for (position, new_key) in numbered_keys(pair.new).into_iter().filter(|(_, key)| key.is_index()) {
+            let Some(previous_name) = new_key.previous_name() else {
+                continue;
+            };
+            let Some(old_key) = pair.old_keys().find(|old_key| same_key(old_key, new_key, pair)) else {
+                return Err(DiffError::UnknownPreviousName(format!(
+                    "index on {} ({}) was previously named {}, but the old table has no index like it",
+                    pair.name,
+                    new_key.columns_as_string(),
+                    previous_name
+                )));
+            };
+            if !renames_index(old_key, new_key) {
+                continue;
             }
+            cs.add_change(SchemaChange::RenameIndex {
+                table_name: pair.name.clone(),
+                old_name: previous_name.to_string(),
+                key: new_key.clone(),
+                position,
+            });
         }
     }
+    Ok(())
 }
 
-fn diff_add_constraints(old: &Schema, new: &Schema, cs: &mut ChangeSet) {
-    for new_table in new.tables() {
-        if let Some(old_table) = old.get_optional_table(new_table.name()) {
-            for new_con in new_table.constraints() {
-                if !constraint_exists_in(new_con, old_table.constraints()) {
-                    cs.add_change(SchemaChange::AddConstraint {
-                        table_name: new_table.name().to_string(),
-                        constraint: new_con.clone(),
-                    });
-                }
+fn diff_drop_constraints(tables: &[TablePair], cs: &mut ChangeSet) {
+    for pair in tables {
+        for old_con in pair.old.constraints() {
+            if !constraint_exists_in(old_con, pair.new.constraints())
+                && !pair.renamed_constraints.iter().any(|(old, _)| old.name().eq_ignore_ascii_case(old_con.name()))
+            {
+                cs.add_change(SchemaChange::DropConstraint {
+                    table_name: pair.old_name.clone(),
+                    constraint_name: old_con.name().to_string(),
+                });
+            }
+        }
+    }
+}
+
+fn diff_add_constraints(tables: &[TablePair], cs: &mut ChangeSet) {
+    for pair in tables {
+        for new_con in pair.new.constraints() {
+            if !constraint_exists_in(new_con, pair.old.constraints())
+                && !pair.renamed_constraints.iter().any(|(_, new)| new.name().eq_ignore_ascii_case(new_con.name()))
+            {
+                cs.add_change(SchemaChange::AddConstraint {
+                    table_name: pair.name.clone(),
+                    constraint: new_con.clone(),
+                });
             }
         }
     }
 }
 
+fn diff_rename_tables(renames: &[MovedTable], cs: &mut ChangeSet) {
+    for renamed in renames {
+        cs.add_change(SchemaChange::RenameTable {
+            old_name: qualified_name(renamed.old_schema_name, renamed.old_table.name()),
+            new_name: qualified_name(renamed.new_schema_name, renamed.new_table.name()),
+        });
+    }
+}
+
+fn diff_rename_columns(tables: &[TablePair], cs: &mut ChangeSet) {
+    for pair in tables {
+        for (old_col, new_col) in &pair.renamed_columns {
+            cs.add_change(SchemaChange::RenameColumn {
+                table_name: pair.name.clone(),
+                old_name: old_col.name().to_string(),
+                new_name: new_col.name().to_string(),
+            });
+        }
+    }
+}
+
+fn diff_rename_constraints(tables: &[TablePair], cs: &mut ChangeSet) {
+    for pair in tables {
+        for (old_con, new_con) in &pair.renamed_constraints {
+            cs.add_change(SchemaChange::RenameConstraint {
+                table_name: pair.name.clone(),
+                old_name: old_con.name().to_string(),
+                new_name: new_con.name().to_string(),
+            });
+        }
+    }
+}
+
 fn constraint_exists_in(con: &Constraint, constraints: &[Constraint]) -> bool {
     constraints
         .iter()
         .any(|c| c.name().eq_ignore_ascii_case(con.name()))
 }
 
-fn diff_drop_relations(old: &Schema, new: &Schema, cs: &mut ChangeSet) {
-    for old_table in old.tables() {
-        if let Some(new_table) = new.get_optional_table(old_table.name()) {
-            for old_rel in old_table.relations() {
-                if !relation_exists_in(old_rel, new_table.relations()) {
-                    cs.add_change(SchemaChange::DropRelation {
-                        relation: old_rel.clone(),
-                    });
-                }
+fn diff_drop_relations(tables: &[TablePair], cs: &mut ChangeSet) {
+    for pair in tables {
+        for (index, old_rel) in pair.old.relations().iter().enumerate() {
+            if !relation_kept(index, old_rel, pair.new.relations()) {
+                cs.add_change(SchemaChange::DropRelation {
+                    table_name: pair.old_name.clone(),
+                    relation: old_rel.clone(),
+                    position: index + 1,
+                });
             }
         }
     }
 }
 
-fn diff_add_relations(old: &Schema, new: &Schema, cs: &mut ChangeSet) {
-    for new_table in new.tables() {
-        if let Some(old_table) = old.get_optional_table(new_table.name()) {
-            for new_rel in new_table.relations() {
-                if !relation_exists_in(new_rel, old_table.relations()) {
-                    cs.add_change(SchemaChange::AddRelation {
-                        relation: new_rel.clone(),
-                    });
-                }
+fn diff_add_relations(tables: &[TablePair], cs: &mut ChangeSet) {
+    for pair in tables {
+        for (index, new_rel) in pair.new.relations().iter().enumerate() {
+            if !relation_kept(index, new_rel, pair.old.relations()) {
+                cs.add_change(SchemaChange::AddRelation {
+                    table_name: pair.name.clone(),
+                    relation: new_rel.clone(),
+                    position: index + 1,
+                });
+            }
+        }
+    }
+}
+
+fn diff_modify_relations(tables: &[TablePair], cs: &mut ChangeSet) {
+    for pair in tables {
+        for (index, new_rel) in pair.new.relations().iter().enumerate() {
+            let Some(old_rel) = pair.old.relations().get(index).filter(|old_rel| relations_equal(old_rel, new_rel)) else {
+                continue;
+            };
+            if old_rel.relation_type() != new_rel.relation_type() {
+                cs.add_change(SchemaChange::ModifyRelation {
+                    table_name: pair.name.clone(),
+                    old_relation: old_rel.clone(),
+                    new_relation: new_rel.clone(),
+                    position: index + 1,
+                });
             }
         }
     }
 }
 
-fn relation_exists_in(rel: &Relation, relations: &[Relation]) -> bool {
-    relations.iter().any(|r| relations_equal(r, rel))
+/// Whether `rel`, the `index`th relation of its table, is at the same index among the
+/// other side's `relations`. The name the create script gives a foreign key is numbered by
+/// its position, so a relation that moved is dropped and added again under its new name.
+fn relation_kept(index: usize, rel: &Relation, relations: &[Relation]) -> bool {
+    relations.get(index).is_some_and(|other| relations_equal(other, rel))
 }
 
 fn relations_equal(a: &Relation, b: &Relation) -> bool {
     a.from_table_name().eq_ignore_ascii_case(b.from_table_name())
-        && a.from_column_name().eq_ignore_ascii_case(b.from_column_name())
         && a.to_table_name().eq_ignore_ascii_case(b.to_table_name())
-        && a.to_column_name().eq_ignore_ascii_case(b.to_column_name())
+        && a.columns().len() == b.columns().len()
+        && a.columns().iter().zip(b.columns().iter()).all(|(ac, bc)| {
+            ac.from_column_name().eq_ignore_ascii_case(bc.from_column_name())
+                && ac.to_column_name().eq_ignore_ascii_case(bc.to_column_name())
+        })
 }
 
-fn diff_drop_views(old: &Schema, new: &Schema, cs: &mut ChangeSet) {
-    for old_view in old.all_views() {
-        if !view_exists_in(old_view, new.all_views()) {
-            cs.add_change(SchemaChange::DropView {
-                view_name: old_view.name().to_string(),
-            });
+// Partitions are only compared while the strategy and key stay the same; otherwise the
+// whole partitioning is reported as one `ModifyPartitioning`. A partition whose bound
+// changed is dropped and created again.
+fn diff_drop_partitions(tables: &[TablePair], cs: &mut ChangeSet) {
+    for pair in tables {
+        let (Some(old_partitioning), Some(new_partitioning)) = (pair.old.partitioning(), pair.new.partitioning()) else {
+            continue;
+        };
+        if !same_partition_key(old_partitioning, new_partitioning) {
+            continue;
+        }
+        let new_partitions = new_partitioning.all_partitions(pair.new.name());
+        for old_partition in old_partitioning.all_partitions(pair.old.name()) {
+            if !new_partitions.contains(&old_partition) {
+                cs.add_change(SchemaChange::DropPartition {
+                    table_name: pair.old_name.clone(),
+                    partition_name: old_partition.name().to_string(),
+                });
+            }
         }
     }
 }
 
-fn diff_add_views(old: &Schema, new: &Schema, cs: &mut ChangeSet) {
-    for new_view in new.all_views() {
-        if !view_exists_in(new_view, old.all_views()) {
-            cs.add_change(SchemaChange::AddView {
-                view: new_view.clone(),
-            });
+fn diff_add_partitions(tables: &[TablePair], cs: &mut ChangeSet) {
+    for pair in tables {
+        match (pair.old.partitioning(), pair.new.partitioning()) {
+            (None, None) => {}
+            (Some(old_partitioning), Some(new_partitioning)) if same_partition_key(old_partitioning, new_partitioning) => {
+                let old_partitions = old_partitioning.all_partitions(pair.old.name());
+                for new_partition in new_partitioning.all_partitions(pair.new.name()) {
+                    if !old_partitions.contains(&new_partition) {
+                        cs.add_change(SchemaChange::AddPartition {
+                            table_name: pair.name.clone(),
+                            partition: new_partition,
+                        });
+                    }
+                }
+            }
+            (old_partitioning, new_partitioning) => {
+                cs.add_change(SchemaChange::ModifyPartitioning {
+                    table_name: pair.name.clone(),
+                    old_partitioning: old_partitioning.cloned(),
+                    new_partitioning: new_partitioning.cloned(),
+                });
+            }
+        }
+    }
+}
+
+fn same_partition_key(a: &Partitioning, b: &Partitioning) -> bool {
+    a.strategy() == b.strategy()
+        && a.columns().len() == b.columns().len()
+        && a.columns().iter().zip(b.columns()).all(|(x, y)| x.eq_ignore_ascii_case(y))
+}
+
+// A view whose definition changed is dropped and created again rather than altered, which
+// for a materialized view also rebuilds its indexes.
+fn diff_drop_views(schemas: &[SchemaPair], cs: &mut ChangeSet) {
+    for pair in schemas {
+        for old_view in pair.old.all_views() {
+            if !find_view(old_view, pair.new.all_views()).is_some_and(|new_view| new_view.same_definition(old_view)) {
+                cs.add_change(SchemaChange::DropView {
+                    view_name: qualified_name(pair.schema_name, old_view.name()),
+                    materialized: old_view.is_materialized(),
+                });
+            }
+        }
+    }
+}
+
+fn diff_add_views(schemas: &[SchemaPair], cs: &mut ChangeSet) {
+    for pair in schemas {
+        for new_view in pair.new.all_views() {
+            if !find_view(new_view, pair.old.all_views()).is_some_and(|old_view| old_view.same_definition(new_view)) {
+                cs.add_change(SchemaChange::AddView {
+                    view: new_view.clone(),
+                });
+            }
         }
     }
 }
 
-fn view_exists_in(view: &View, views: &[View]) -> bool {
+fn find_view<'a>(view: &View, views: &'a [View]) -> Option<&'a View> {
     views
         .iter()
-        .any(|v| v.name().eq_ignore_ascii_case(view.name()))
+        .find(|v| v.name().eq_ignore_ascii_case(view.name()))
+}
+
+fn diff_drop_sequences(schemas: &[SchemaPair], cs: &mut ChangeSet) {
+    for pair in schemas {
+        for old_sequence in pair.old.sequences() {
+            if pair.new.get_optional_sequence(old_sequence.name()).is_none() {
+                cs.add_change(SchemaChange::DropSequence {
+                    sequence_name: qualified_name(pair.schema_name, old_sequence.name()),
+                });
+            }
+        }
+    }
+}
+
+fn diff_add_sequences(schemas: &[SchemaPair], cs: &mut ChangeSet) {
+    for pair in schemas {
+        for new_sequence in pair.new.sequences() {
+            if pair.old.get_optional_sequence(new_sequence.name()).is_none() {
+                cs.add_change(SchemaChange::AddSequence {
+                    sequence: new_sequence.clone(),
+                });
+            }
+        }
+    }
+}
+
+fn diff_modify_sequences(schemas: &[SchemaPair], cs: &mut ChangeSet) {
+    for pair in schemas {
+        for new_sequence in pair.new.sequences() {
+            if let Some(old_sequence) = pair.old.get_optional_sequence(new_sequence.name())
+                && !sequence_options_equal(old_sequence, new_sequence)
+            {
+                cs.add_change(SchemaChange::ModifySequence {
+                    old_sequence: old_sequence.clone(),
+                    new_sequence: new_sequence.clone(),
+                });
+            }
+        }
+    }
+}
+
+fn sequence_options_equal(a: &Sequence, b: &Sequence) -> bool {
+    a.start() == b.start()
+        && a.increment() == b.increment()
+        && a.min_value() == b.min_value()
+        && a.max_value() == b.max_value()
+        && a.is_cycle() == b.is_cycle()
+        && a.cache() == b.cache()
+}
+
+fn diff_drop_enum_types(schemas: &[SchemaPair], cs: &mut ChangeSet) {
+    for pair in schemas {
+        for old_enum_type in pair.old.enum_types() {
+            if pair.new.get_optional_enum_type(old_enum_type.name()).is_none() {
+                cs.add_change(SchemaChange::DropEnumType {
+                    enum_type_name: old_enum_type.name().to_string(),
+                });
+            }
+        }
+    }
+}
+
+fn diff_add_enum_types(schemas: &[SchemaPair], cs: &mut ChangeSet) {
+    for pair in schemas {
+        for new_enum_type in pair.new.enum_types() {
+            if pair.old.get_optional_enum_type(new_enum_type.name()).is_none() {
+                cs.add_change(SchemaChange::AddEnumType {
+                    enum_type: new_enum_type.clone(),
+                });
+            }
+        }
+    }
+}
+
+// Enum values are compared by code, which is what the database stores. The columns passed
+// along are the existing ones that are of the type before and after the migration.
+fn diff_modify_enum_types(schemas: &[SchemaPair], tables: &[TablePair], cs: &mut ChangeSet) {
+    for pair in schemas {
+        for new_enum_type in pair.new.enum_types() {
+            let Some(old_enum_type) = pair.old.get_optional_enum_type(new_enum_type.name()) else {
+                continue;
+            };
+            if enum_codes(old_enum_type) == enum_codes(new_enum_type) {
+                continue;
+            }
+            let is_of_type = |column: &Column| {
+                column
+                    .enum_type()
+                    .is_some_and(|name| name.eq_ignore_ascii_case(new_enum_type.name()))
+            };
+            let mut columns = Vec::new();
+            for table in tables.iter().filter(|table| optional_names_equal(table.schema_name, pair.schema_name)) {
+                for new_col in table.new.columns().iter().filter(|c| is_of_type(c)) {
+                    if table.old_column(new_col).is_some_and(is_of_type) {
+                        columns.push((table.name.clone(), new_col.clone()));
+                    }
+                }
+            }
+            cs.add_change(SchemaChange::ModifyEnumType {
+                old_enum_type: old_enum_type.clone(),
+                new_enum_type: new_enum_type.clone(),
+                columns,
+            });
+        }
+    }
+}
+
+fn enum_codes(enum_type: &EnumType) -> Vec<&str> {
+    enum_type.values().iter().map(|value| value.code()).collect()
+}
+
+// Functions and procedures are written per database, so one is matched on its name and
+// its database together.
+fn diff_drop_functions(schemas: &[SchemaPair], cs: &mut ChangeSet) {
+    for pair in schemas {
+        for old_function in pair.old.functions() {
+            let exists = pair.new.functions().iter().any(|f| {
+                f.database_type() == old_function.database_type() && f.name().eq_ignore_ascii_case(old_function.name())
+            });
+            if !exists {
+                cs.add_change(SchemaChange::DropFunction {
+                    function: old_function.clone(),
+                });
+            }
+        }
+    }
+}
+
+fn diff_add_functions(schemas: &[SchemaPair], cs: &mut ChangeSet) {
+    for pair in schemas {
+        for new_function in pair.new.functions() {
+            let old_function = pair.old.functions().iter().find(|f| {
+                f.database_type() == new_function.database_type() && f.name().eq_ignore_ascii_case(new_function.name())
+            });
+            match old_function {
+                None => cs.add_change(SchemaChange::AddFunction {
+                    function: new_function.clone(),
+                }),
+                Some(old_function) if old_function.sql().trim() != new_function.sql().trim() => {
+                    cs.add_change(SchemaChange::ModifyFunction {
+                        old_function: old_function.clone(),
+                        new_function: new_function.clone(),
+                    })
+                }
+                Some(_) => {}
+            }
+        }
+    }
+}
+
+fn diff_drop_procedures(schemas: &[SchemaPair], cs: &mut ChangeSet) {
+    for pair in schemas {
+        for old_procedure in pair.old.procedures() {
+            let exists = pair.new.procedures().iter().any(|p| {
+                p.database_type() == old_procedure.database_type() && p.name().eq_ignore_ascii_case(old_procedure.name())
+            });
+            if !exists {
+                cs.add_change(SchemaChange::DropProcedure {
+                    procedure: old_procedure.clone(),
+                });
+            }
+        }
+    }
+}
+
+fn diff_add_procedures(schemas: &[SchemaPair], cs: &mut ChangeSet) {
+    for pair in schemas {
+        for new_procedure in pair.new.procedures() {
+            let old_procedure = pair.old.procedures().iter().find(|p| {
+                p.database_type() == new_procedure.database_type() && p.name().eq_ignore_ascii_case(new_procedure.name())
+            });
+            match old_procedure {
+                None => cs.add_change(SchemaChange::AddProcedure {
+                    procedure: new_procedure.clone(),
+                }),
+                Some(old_procedure) if old_procedure.sql().trim() != new_procedure.sql().trim() => {
+                    cs.add_change(SchemaChange::ModifyProcedure {
+                        old_procedure: old_procedure.clone(),
+                        new_procedure: new_procedure.clone(),
+                    })
+                }
+                Some(_) => {}
+            }
+        }
+    }
+}
+
+// Triggers, aggregations and other SQL have no names, so they are matched on everything
+// they hold.
+fn diff_drop_triggers(tables: &[TablePair], cs: &mut ChangeSet) {
+    for pair in tables {
+        for old_trigger in pair.old.triggers() {
+            if !pair.new.triggers().iter().any(|t| triggers_equal(t, old_trigger)) {
+                cs.add_change(SchemaChange::DropTrigger {
+                    table_name: pair.old_name.clone(),
+                    trigger: old_trigger.clone(),
+                });
+            }
+        }
+    }
+}
+
+fn diff_add_triggers(tables: &[TablePair], cs: &mut ChangeSet) {
+    for pair in tables {
+        for new_trigger in pair.new.triggers() {
+            if !pair.old.triggers().iter().any(|t| triggers_equal(t, new_trigger)) {
+                cs.add_change(SchemaChange::AddTrigger {
+                    table_name: pair.name.clone(),
+                    trigger: new_trigger.clone(),
+                });
+            }
+        }
+    }
+}
+
+fn triggers_equal(a: &Trigger, b: &Trigger) -> bool {
+    a.trigger_type() == b.trigger_type()
+        && a.database_type() == b.database_type()
+        && a.trigger_text().trim() == b.trigger_text().trim()
+}
+
+fn diff_drop_aggregations(tables: &[TablePair], cs: &mut ChangeSet) {
+    for pair in tables {
+        for old_aggregation in pair.old.aggregations() {
+            if !pair.new.aggregations().contains(old_aggregation) {
+                cs.add_change(SchemaChange::DropAggregation {
+                    table_name: pair.old_name.clone(),
+                    aggregation: old_aggregation.clone(),
+                });
+            }
+        }
+    }
+}
+
+fn diff_add_aggregations(tables: &[TablePair], cs: &mut ChangeSet) {
+    for pair in tables {
+        for new_aggregation in pair.new.aggregations() {
+            if !pair.old.aggregations().contains(new_aggregation) {
+                cs.add_change(SchemaChange::AddAggregation {
+                    table_name: pair.name.clone(),
+                    aggregation: new_aggregation.clone(),
+                });
+            }
+        }
+    }
+}
+
+fn diff_drop_other_sql(schemas: &[SchemaPair], cs: &mut ChangeSet) {
+    for pair in schemas {
+        for old_other_sql in pair.old.other_sql() {
+            if !pair.new.other_sql().iter().any(|o| other_sql_equal(o, old_other_sql)) {
+                cs.add_change(SchemaChange::DropOtherSql {
+                    other_sql: old_other_sql.clone(),
+                });
+            }
+        }
+    }
+}
+
+fn diff_add_other_sql(schemas: &[SchemaPair], order: OtherSqlOrder, cs: &mut ChangeSet) {
+    for pair in schemas {
+        for new_other_sql in pair.new.other_sql().iter().filter(|o| o.order() == order) {
+            if !pair.old.other_sql().iter().any(|o| other_sql_equal(o, new_other_sql)) {
+                cs.add_change(SchemaChange::AddOtherSql {
+                    other_sql: new_other_sql.clone(),
+                });
+            }
+        }
+    }
+}
+
+fn other_sql_equal(a: &OtherSql, b: &OtherSql) -> bool {
+    a.database_type() == b.database_type() && a.order() == b.order() && a.sql().trim() == b.sql().trim()
 }
@@ -0,0 +1,9 @@
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum DiffError {
+    #[error("Previous name not found: {0}")]
+    UnknownPreviousName(String),
+    #[error("Conflicting previous name: {0}")]
+    ConflictingPreviousName(String),
+}
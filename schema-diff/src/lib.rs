@@ -1,9 +1,11 @@
 pub mod change;
 pub mod change_set;
 pub mod diff_engine;
+pub mod error;
 #[cfg(test)]
 mod tests;
 
-pub use change::SchemaChange;
+pub use change::{ColumnAttribute, SchemaChange};
 pub use change_set::ChangeSet;
 pub use diff_engine::SchemaDiffEngine;
+pub use error::DiffError;
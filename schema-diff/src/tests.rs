@@ -1,10 +1,28 @@
 use schema_model::builder::column::ColumnBuilder;
+use schema_model::builder::key::KeyBuilder;
 use schema_model::builder::schema::SchemaBuilder;
+use schema_model::builder::sequence::SequenceBuilder;
 use schema_model::builder::table::TableBuilder;
+use schema_model::model::aggregation::{Aggregation, AggregationColumn, AggregationFrequency, AggregationType};
 use schema_model::model::column_type::ColumnType;
+use schema_model::model::constraint::Constraint;
+use schema_model::model::database_model::DatabaseModel;
+use schema_model::model::enum_type::{EnumType, EnumValue};
+use schema_model::model::function::Function;
+use schema_model::model::key::{Key, KeyColumn};
+use schema_model::model::other_sql::OtherSql;
+use schema_model::model::partition::{Partition, PartitionBound, PartitionTemplate, Partitioning};
+use schema_model::model::relation::{Relation, RelationColumn};
+use schema_model::model::trigger::Trigger;
+use schema_model::model::types::{
+    BooleanMode, CompressionLevel, DatabaseType, ForeignKeyMode, IdentityMode, KeyType, LockEscalation, OtherSqlOrder, PartitionStrategy,
+    RefreshPolicy, RelationType, TableOption, TriggerType,
+};
+use schema_model::model::view::View;
 
-use crate::change::SchemaChange;
+use crate::change::{ColumnAttribute, SchemaChange};
 use crate::diff_engine::SchemaDiffEngine;
+use crate::error::DiffError;
 
 #[test]
 fn detects_added_table() {
@@ -17,9 +35,9 @@ fn detects_added_table() {
         )
         .build();
 
-    let cs = SchemaDiffEngine::diff(&old, &new);
+    let cs = SchemaDiffEngine::diff(&old, &new).unwrap();
     assert_eq!(cs.len(), 1);
-    assert!(matches!(&cs.changes()[0], SchemaChange::AddTable { table_name } if table_name == "users"));
+    assert!(matches!(&cs.changes()[0], SchemaChange::AddTable { table_name } if table_name == "s.users"));
 }
 
 #[test]
@@ -33,9 +51,9 @@ fn detects_dropped_table() {
         .build();
     let new = SchemaBuilder::new(Some("s")).build();
 
-    let cs = SchemaDiffEngine::diff(&old, &new);
+    let cs = SchemaDiffEngine::diff(&old, &new).unwrap();
     assert_eq!(cs.len(), 1);
-    assert!(matches!(&cs.changes()[0], SchemaChange::DropTable { table_name } if table_name == "orders"));
+    assert!(matches!(&cs.changes()[0], SchemaChange::DropTable { table_name } if table_name == "s.orders"));
 }
 
 #[test]
@@ -56,7 +74,7 @@ fn detects_added_column() {
         )
         .build();
 
-    let cs = SchemaDiffEngine::diff(&old, &new);
+    let cs = SchemaDiffEngine::diff(&old, &new).unwrap();
     assert!(cs.changes().iter().any(|c| matches!(c, SchemaChange::AddColumn { column, .. } if column.name() == "name")));
 }
 
@@ -78,7 +96,7 @@ fn detects_dropped_column() {
         )
         .build();
 
-    let cs = SchemaDiffEngine::diff(&old, &new);
+    let cs = SchemaDiffEngine::diff(&old, &new).unwrap();
     assert!(cs.changes().iter().any(|c| matches!(c, SchemaChange::DropColumn { column_name, .. } if column_name == "name")));
 }
 
@@ -101,7 +119,7 @@ fn detects_rename_candidates_for_dropped_column() {
         )
         .build();
 
-    let cs = SchemaDiffEngine::diff(&old, &new);
+    let cs = SchemaDiffEngine::diff(&old, &new).unwrap();
     let drop = cs.changes().iter().find(|c| matches!(c, SchemaChange::DropColumn { column_name, .. } if column_name == "first_name"));
     assert!(drop.is_some());
     if let SchemaChange::DropColumn { rename_candidates, .. } = drop.unwrap() {
@@ -128,7 +146,7 @@ fn no_rename_candidates_when_types_differ() {
         )
         .build();
 
-    let cs = SchemaDiffEngine::diff(&old, &new);
+    let cs = SchemaDiffEngine::diff(&old, &new).unwrap();
     let drop = cs.changes().iter().find(|c| matches!(c, SchemaChange::DropColumn { column_name, .. } if column_name == "age"));
     assert!(drop.is_some());
     if let SchemaChange::DropColumn { rename_candidates, .. } = drop.unwrap() {
@@ -153,8 +171,8 @@ fn detects_modified_column() {
         )
         .build();
 
-    let cs = SchemaDiffEngine::diff(&old, &new);
-    assert!(cs.changes().iter().any(|c| matches!(c, SchemaChange::ModifyColumn { table_name, .. } if table_name == "users")));
+    let cs = SchemaDiffEngine::diff(&old, &new).unwrap();
+    assert!(cs.changes().iter().any(|c| matches!(c, SchemaChange::ModifyColumn { table_name, .. } if table_name == "s.users")));
 }
 
 #[test]
@@ -174,6 +192,770 @@ fn no_changes_when_identical() {
         )
         .build();
 
-    let cs = SchemaDiffEngine::diff(&s1, &s2);
+    let cs = SchemaDiffEngine::diff(&s1, &s2).unwrap();
     assert!(cs.is_empty());
 }
+
+#[test]
+fn detects_composite_relation_column_change() {
+    let columns = |second_from: &str| {
+        vec![
+            RelationColumn::new("tenant_id", "tenant_id"),
+            RelationColumn::new(second_from, "id"),
+        ]
+    };
+    let build = |second_from: &str| {
+        SchemaBuilder::new(Some("s"))
+            .add_table(
+                TableBuilder::new(Some("s"), "invoice")
+                    .add_column(ColumnBuilder::new(Some("s"), "tenant_id", ColumnType::Int).required(true).build())
+                    .add_column(ColumnBuilder::new(Some("s"), "customer_id", ColumnType::Int).build())
+                    .add_column(ColumnBuilder::new(Some("s"), "billing_customer_id", ColumnType::Int).build())
+                    .add_relation(Relation::new_composite(
+                        "customer",
+                        "invoice",
+                        columns(second_from),
+                        RelationType::Enforce,
+                        false,
+                    ))
+                    .build(),
+            )
+            .build()
+    };
+
+    let unchanged = SchemaDiffEngine::diff(&build("customer_id"), &build("customer_id")).unwrap();
+    assert!(unchanged.is_empty());
+
+    let cs = SchemaDiffEngine::diff(&build("customer_id"), &build("billing_customer_id")).unwrap();
+    assert!(cs.changes().iter().any(|c| matches!(c, SchemaChange::DropRelation { table_name, relation, .. } if table_name == "s.invoice" && relation.from_columns_as_string() == "tenant_id,customer_id")));
+    assert!(cs.changes().iter().any(|c| matches!(c, SchemaChange::AddRelation { relation, .. } if relation.from_columns_as_string() == "tenant_id,billing_customer_id")));
+}
+
+#[test]
+fn detects_table_and_column_description_changes() {
+    let old = SchemaBuilder::new(Some("s"))
+        .add_table(
+            TableBuilder::new(Some("s"), "users")
+                .description(Some("Users".to_string()))
+                .add_column(
+                    ColumnBuilder::new(Some("s"), "id", ColumnType::Int)
+                        .description(Some("Key".to_string()))
+                        .build(),
+                )
+                .build(),
+        )
+        .build();
+    let new = SchemaBuilder::new(Some("s"))
+        .add_table(
+            TableBuilder::new(Some("s"), "users")
+                .description(Some("Application users".to_string()))
+                .add_column(ColumnBuilder::new(Some("s"), "id", ColumnType::Int).build())
+                .build(),
+        )
+        .build();
+
+    let cs = SchemaDiffEngine::diff(&old, &new).unwrap();
+    assert_eq!(cs.len(), 2);
+    assert!(matches!(
+        &cs.changes()[0],
+        SchemaChange::ModifyDescription { table_name, column_name: None, old_description: Some(old), new_description: Some(new) }
+            if table_name == "s.users" && old == "Users" && new == "Application users"
+    ));
+    assert!(matches!(
+        &cs.changes()[1],
+        SchemaChange::ModifyDescription { column_name: Some(column), new_description: None, .. } if column == "id"
+    ));
+}
+
+#[test]
+fn describes_the_columns_of_an_added_table() {
+    let old = SchemaBuilder::new(Some("s")).build();
+    let new = SchemaBuilder::new(Some("s"))
+        .add_table(
+            TableBuilder::new(Some("s"), "users")
+                .add_column(
+                    ColumnBuilder::new(Some("s"), "id", ColumnType::Int)
+                        .description(Some("Key".to_string()))
+                        .build(),
+                )
+                .add_column(ColumnBuilder::new(Some("s"), "name", ColumnType::Varchar).build())
+                .build(),
+        )
+        .build();
+
+    let cs = SchemaDiffEngine::diff(&old, &new).unwrap();
+    assert_eq!(cs.len(), 2);
+    assert!(matches!(&cs.changes()[0], SchemaChange::AddTable { table_name } if table_name == "s.users"));
+    assert!(matches!(
+        &cs.changes()[1],
+        SchemaChange::ModifyDescription { table_name, column_name: Some(column), old_description: None, new_description: Some(new) }
+            if table_name == "s.users" && column == "id" && new == "Key"
+    ));
+}
+
+#[test]
+fn detects_index_predicate_change() {
+    let build = |filter: &str| {
+        SchemaBuilder::new(Some("s"))
+            .add_table(
+                TableBuilder::new(Some("s"), "users")
+                    .add_column(ColumnBuilder::new(Some("s"), "email", ColumnType::Varchar).build())
+                    .add_key(
+                        KeyBuilder::new(KeyType::Index)
+                            .add_key_column(KeyColumn::new_expression("lower(email)"))
+                            .filter(filter)
+                            .build(),
+                    )
+                    .build(),
+            )
+            .build()
+    };
+
+    let unchanged = SchemaDiffEngine::diff(&build("deleted_at is null"), &build("deleted_at is null")).unwrap();
+    assert!(unchanged.is_empty());
+
+    let cs = SchemaDiffEngine::diff(&build("deleted_at is null"), &build("active")).unwrap();
+    assert_eq!(cs.len(), 1);
+    assert!(matches!(&cs.changes()[0], SchemaChange::ModifyKey { table_name, old_key, new_key, .. }
+        if table_name == "s.users" && old_key.filter() == Some("deleted_at is null") && new_key.filter() == Some("active")));
+}
+
+#[test]
+fn detects_index_compression_change() {
+    let build = |compression: Option<CompressionLevel>| {
+        let mut key_builder = KeyBuilder::new(KeyType::Index).add_column("email");
+        if let Some(level) = compression {
+            key_builder = key_builder.compression(level);
+        }
+        SchemaBuilder::new(Some("s"))
+            .add_table(
+                TableBuilder::new(Some("s"), "users")
+                    .add_column(ColumnBuilder::new(Some("s"), "email", ColumnType::Varchar).build())
+                    .add_key(key_builder.build())
+                    .build(),
+            )
+            .build()
+    };
+
+    let cs = SchemaDiffEngine::diff(&build(None), &build(Some(CompressionLevel::Row))).unwrap();
+    assert_eq!(cs.len(), 1);
+    assert!(matches!(&cs.changes()[0], SchemaChange::ModifyKey { old_key, new_key, .. }
+        if old_key.compression().is_none() && new_key.compression() == Some(CompressionLevel::Row)));
+}
+
+#[test]
+fn detects_changed_index_options_and_recreates_keys_on_renamed_columns() {
+    let build = |column_name: &str, previous_name: Option<&str>, include: &str, cluster: bool, unique: bool| {
+        let column = ColumnBuilder::new(Some("s"), column_name, ColumnType::Varchar).previous_name(previous_name.map(String::from));
+        SchemaBuilder::new(Some("s"))
+            .add_table(
+                TableBuilder::new(Some("s"), "users")
+                    .add_column(column.build())
+                    .add_column(ColumnBuilder::new(Some("s"), "name", ColumnType::Varchar).build())
+                    .add_key(
+                        KeyBuilder::new(KeyType::Index)
+                            .add_column(column_name)
+                            .include(include)
+                            .cluster(cluster)
+                            .unique(unique)
+                            .build(),
+                    )
+                    .build(),
+            )
+            .build()
+    };
+
+    let cs = SchemaDiffEngine::diff(&build("email", None, "name", false, false), &build("email", None, "name", true, true)).unwrap();
+    assert_eq!(cs.len(), 1);
+    assert!(matches!(&cs.changes()[0], SchemaChange::ModifyKey { old_key, new_key, .. }
+        if !old_key.is_cluster() && !old_key.is_unique() && new_key.is_cluster() && new_key.is_unique()));
+
+    let cs = SchemaDiffEngine::diff(&build("email", None, "", false, false), &build("email", None, "name", false, false)).unwrap();
+    assert!(matches!(cs.changes(), [SchemaChange::ModifyKey { new_key, .. }] if new_key.include() == Some("name")));
+
+    // The index migrations gave the old key is named after the old column.
+    let cs = SchemaDiffEngine::diff(&build("email", None, "", false, false), &build("mail", Some("email"), "", false, true)).unwrap();
+    assert!(matches!(&cs.changes()[0], SchemaChange::DropKey { key, .. } if key.contains_column("email")));
+    assert!(matches!(&cs.changes()[1], SchemaChange::RenameColumn { .. }));
+    assert!(matches!(&cs.changes()[2], SchemaChange::AddKey { key, .. } if key.is_unique()));
+}
+
+#[test]
+fn recreates_keys_and_relations_whose_position_changed() {
+    let build = |dropped: bool, filter: &str, relation_type: RelationType| {
+        let mut table = TableBuilder::new(Some("s"), "invoice")
+            .add_column(ColumnBuilder::new(Some("s"), "customer_id", ColumnType::Int).build())
+            .add_column(ColumnBuilder::new(Some("s"), "region_id", ColumnType::Int).build())
+            .add_index(KeyBuilder::new(KeyType::Index).add_column("customer_id").filter(filter).build())
+            .add_relation(Relation::new("customer", "id", "invoice", "customer_id", relation_type, false));
+        if !dropped {
+            table = table
+                .add_index(KeyBuilder::new(KeyType::Index).add_column("region_id").build())
+                .add_relation(Relation::new("region", "id", "invoice", "region_id", RelationType::Cascade, false));
+        }
+        SchemaBuilder::new(Some("s")).add_table(table.build()).build()
+    };
+
+    // The create script numbers the names by position, so the kept index and relation
+    // keep theirs while the others are dropped.
+    let cs = SchemaDiffEngine::diff(&build(false, "customer_id > 0", RelationType::Cascade), &build(true, "customer_id > 1", RelationType::SetNull)).unwrap();
+    assert_eq!(cs.len(), 4);
+    assert!(matches!(&cs.changes()[0], SchemaChange::DropRelation { position: 2, .. }));
+    assert!(matches!(&cs.changes()[1], SchemaChange::DropKey { position: 2, .. }));
+    assert!(matches!(&cs.changes()[2], SchemaChange::ModifyKey { position: 1, .. }));
+    assert!(matches!(&cs.changes()[3], SchemaChange::ModifyRelation { position: 1, .. }));
+
+    // Adding an index and a relation in front moves the existing ones to second place.
+    let moved = |with_region: bool| {
+        let mut table = TableBuilder::new(Some("s"), "invoice")
+            .add_column(ColumnBuilder::new(Some("s"), "customer_id", ColumnType::Int).build())
+            .add_column(ColumnBuilder::new(Some("s"), "region_id", ColumnType::Int).build());
+        if with_region {
+            table = table
+                .add_index(KeyBuilder::new(KeyType::Index).add_column("region_id").build())
+                .add_relation(Relation::new("region", "id", "invoice", "region_id", RelationType::Cascade, false));
+        }
+        table = table
+            .add_index(KeyBuilder::new(KeyType::Index).add_column("customer_id").build())
+            .add_relation(Relation::new("customer", "id", "invoice", "customer_id", RelationType::Cascade, false));
+        SchemaBuilder::new(Some("s")).add_table(table.build()).build()
+    };
+    let cs = SchemaDiffEngine::diff(&moved(false), &moved(true)).unwrap();
+    assert_eq!(cs.len(), 6);
+    assert!(matches!(&cs.changes()[0], SchemaChange::DropRelation { relation, position: 1, .. } if relation.to_table_name() == "customer"));
+    assert!(matches!(&cs.changes()[1], SchemaChange::DropKey { key, position: 1, .. } if key.contains_column("customer_id")));
+    assert!(matches!(&cs.changes()[2], SchemaChange::AddKey { key, position: 1, .. } if key.contains_column("region_id")));
+    assert!(matches!(&cs.changes()[3], SchemaChange::AddKey { key, position: 2, .. } if key.contains_column("customer_id")));
+    assert!(matches!(&cs.changes()[4], SchemaChange::AddRelation { relation, position: 1, .. } if relation.to_table_name() == "region"));
+    assert!(matches!(&cs.changes()[5], SchemaChange::AddRelation { relation, position: 2, .. } if relation.to_table_name() == "customer"));
+}
+
+#[test]
+fn detects_changed_relation_actions_and_table_options() {
+    let build = |relation_type: RelationType, options: &[TableOption], lock_escalation: LockEscalation| {
+        let mut table = TableBuilder::new(Some("s"), "invoice")
+            .add_column(ColumnBuilder::new(Some("s"), "customer_id", ColumnType::Int).build())
+            .add_relation(Relation::new("customer", "id", "invoice", "customer_id", relation_type, false))
+            .add_option(TableOption::NoExport)
+            .lock_escalation(lock_escalation);
+        for option in options {
+            table = table.add_option(option.clone());
+        }
+        SchemaBuilder::new(Some("s")).add_table(table.build()).build()
+    };
+
+    let unchanged = SchemaDiffEngine::diff(
+        &build(RelationType::Cascade, &[TableOption::Strict, TableOption::WithoutRowid], LockEscalation::Auto),
+        &build(RelationType::Cascade, &[TableOption::WithoutRowid, TableOption::Strict], LockEscalation::Auto),
+    )
+    .unwrap();
+    assert!(unchanged.is_empty());
+
+    let cs = SchemaDiffEngine::diff(
+        &build(RelationType::Cascade, &[], LockEscalation::Auto),
+        &build(RelationType::SetNull, &[TableOption::Compress(CompressionLevel::Page)], LockEscalation::Table),
+    )
+    .unwrap();
+    assert_eq!(cs.len(), 2);
+    assert!(matches!(&cs.changes()[0], SchemaChange::ModifyTableOptions {
+        table_name, old_options, new_options, old_lock_escalation: LockEscalation::Auto, new_lock_escalation: LockEscalation::Table, columns
+    } if table_name == "s.invoice" && old_options.is_empty() && new_options == &[TableOption::Compress(CompressionLevel::Page)] && columns.len() == 1));
+    assert!(matches!(&cs.changes()[1], SchemaChange::ModifyRelation { old_relation, new_relation, .. }
+        if old_relation.relation_type() == RelationType::Cascade && new_relation.relation_type() == RelationType::SetNull));
+}
+
+#[test]
+fn detects_added_dropped_and_modified_sequences() {
+    let old = SchemaBuilder::new(Some("s"))
+        .add_sequence(SequenceBuilder::new(Some("s"), "legacy_no").build())
+        .add_sequence(SequenceBuilder::new(Some("s"), "document_no").increment(Some(1)).build())
+        .build();
+    let new = SchemaBuilder::new(Some("s"))
+        .add_sequence(SequenceBuilder::new(Some("s"), "document_no").increment(Some(10)).build())
+        .add_sequence(SequenceBuilder::new(Some("s"), "ticket_no").start(Some(100)).build())
+        .build();
+
+    let cs = SchemaDiffEngine::diff(&old, &new).unwrap();
+    assert_eq!(cs.len(), 3);
+    assert!(matches!(&cs.changes()[0], SchemaChange::DropSequence { sequence_name } if sequence_name == "s.legacy_no"));
+    assert!(matches!(&cs.changes()[1], SchemaChange::AddSequence { sequence } if sequence.name() == "ticket_no"));
+    assert!(matches!(
+        &cs.changes()[2],
+        SchemaChange::ModifySequence { old_sequence, new_sequence }
+            if old_sequence.increment() == Some(1) && new_sequence.increment() == Some(10)
+    ));
+}
+
+#[test]
+fn recreates_a_view_whose_definition_changed() {
+    let totals_sql = "select customer_id, sum(amount) as total from orders group by customer_id";
+    let by_customer = Key::new(KeyType::Index, vec![KeyColumn::new("customer_id")]);
+    let old = SchemaBuilder::new(Some("s"))
+        .add_view(View::new(Some("s"), "active_users", "select * from users", None))
+        .add_view(View::new_materialized(Some("s"), "order_totals", totals_sql, None, RefreshPolicy::Manual, vec![]))
+        .build();
+    let new = SchemaBuilder::new(Some("s"))
+        .add_view(View::new(Some("s"), "active_users", "select * from users", None))
+        .add_view(View::new_materialized(
+            Some("s"),
+            "order_totals",
+            totals_sql,
+            None,
+            RefreshPolicy::Manual,
+            vec![by_customer],
+        ))
+        .build();
+
+    let cs = SchemaDiffEngine::diff(&old, &new).unwrap();
+    assert_eq!(cs.len(), 2);
+    assert!(matches!(
+        &cs.changes()[0],
+        SchemaChange::DropView { view_name, materialized: true } if view_name == "s.order_totals"
+    ));
+    assert!(matches!(
+        &cs.changes()[1],
+        SchemaChange::AddView { view } if view.name() == "order_totals" && view.indexes().len() == 1
+    ));
+}
+
+#[test]
+fn detects_partition_changes() {
+    let yearly = |year: u32| {
+        Partition::new(
+            format!("events_p{}", year),
+            PartitionBound::Range {
+                from: vec![format!("{}-01-01", year)],
+                to: vec![format!("{}-01-01", year + 1)],
+            },
+        )
+    };
+    let events = |partitioning: Partitioning| {
+        TableBuilder::new(Some("s"), "events")
+            .add_column(ColumnBuilder::new(Some("s"), "created_at", ColumnType::Date).build())
+            .partitioning(Some(partitioning))
+            .build()
+    };
+    let by_created_at = |partitions: Vec<Partition>| {
+        Partitioning::new(PartitionStrategy::Range, vec!["created_at".to_string()], partitions, None)
+    };
+    let old = SchemaBuilder::new(Some("s"))
+        .add_table(events(by_created_at(vec![yearly(2023), yearly(2024)])))
+        .build();
+    let new = SchemaBuilder::new(Some("s"))
+        .add_table(events(by_created_at(vec![yearly(2024), yearly(2025)])))
+        .build();
+
+    let cs = SchemaDiffEngine::diff(&old, &new).unwrap();
+    assert_eq!(cs.len(), 2);
+    assert!(matches!(
+        &cs.changes()[0],
+        SchemaChange::DropPartition { table_name, partition_name } if table_name == "s.events" && partition_name == "events_p2023"
+    ));
+    assert!(matches!(
+        &cs.changes()[1],
+        SchemaChange::AddPartition { partition, .. } if partition.name() == "events_p2025"
+    ));
+
+    let hashed = SchemaBuilder::new(Some("s"))
+        .add_table(events(Partitioning::new(
+            PartitionStrategy::Hash,
+            vec!["created_at".to_string()],
+            vec![],
+            Some(PartitionTemplate::Hash { modulus: 2 }),
+        )))
+        .build();
+    let cs = SchemaDiffEngine::diff(&old, &hashed).unwrap();
+    assert_eq!(cs.len(), 1);
+    assert!(matches!(
+        &cs.changes()[0],
+        SchemaChange::ModifyPartitioning { old_partitioning: Some(_), new_partitioning: Some(p), .. } if p.strategy() == PartitionStrategy::Hash
+    ));
+}
+
+#[test]
+fn serial_column_becoming_an_identity_is_a_modified_column() {
+    let table = |identity_mode| {
+        TableBuilder::new(Some("s"), "orders")
+            .add_column(ColumnBuilder::new(Some("s"), "id", ColumnType::Sequence).identity_mode(identity_mode).build())
+            .build()
+    };
+    let unspecified = SchemaBuilder::new(Some("s")).add_table(table(None)).build();
+    let serial = SchemaBuilder::new(Some("s")).add_table(table(Some(IdentityMode::Serial))).build();
+    let identity = SchemaBuilder::new(Some("s")).add_table(table(Some(IdentityMode::Always))).build();
+
+    assert!(SchemaDiffEngine::diff(&unspecified, &serial).unwrap().is_empty());
+    let cs = SchemaDiffEngine::diff(&unspecified, &identity).unwrap();
+    assert_eq!(cs.len(), 1);
+    assert!(matches!(&cs.changes()[0], SchemaChange::ModifyColumn { new_column, .. } if new_column.identity_mode() == Some(IdentityMode::Always)));
+}
+
+#[test]
+fn modified_columns_list_each_changed_attribute() {
+    let old = SchemaBuilder::new(None::<&str>)
+        .add_table(
+            TableBuilder::new(None, "orders")
+                .add_column(ColumnBuilder::new(None, "amount", ColumnType::Int).min_value(Some(0.0)).build())
+                .add_column(ColumnBuilder::new(None, "total", ColumnType::Int).generated(Some("amount * 2".to_string())).build())
+                .add_column(ColumnBuilder::new(None, "status", ColumnType::Varchar).length(10).build())
+                .add_column(ColumnBuilder::new(None, "tags", ColumnType::Array).element_type(Some("int".to_string())).build())
+                .add_column(ColumnBuilder::new(None, "note", ColumnType::Text).build())
+                .build(),
+        )
+        .build();
+    let new = SchemaBuilder::new(None::<&str>)
+        .add_enum_type(EnumType::new("Status", vec![EnumValue::new("N", Some("N"))]))
+        .add_table(
+            TableBuilder::new(None, "orders")
+                .add_column(ColumnBuilder::new(None, "amount", ColumnType::Int).min_value(Some(1.0)).max_value(Some(100.0)).build())
+                .add_column(ColumnBuilder::new(None, "total", ColumnType::Int).build())
+                .add_column(ColumnBuilder::new(None, "status", ColumnType::Enum).enum_type(Some("status".to_string())).build())
+                .add_column(ColumnBuilder::new(None, "tags", ColumnType::Array).element_type(Some("INT".to_string())).build())
+                .add_column(ColumnBuilder::new(None, "note", ColumnType::Text).build())
+                .build(),
+        )
+        .build();
+
+    let cs = SchemaDiffEngine::diff(&old, &new).unwrap();
+    let modified: Vec<(&str, &[ColumnAttribute], Option<&str>)> = cs
+        .changes()
+        .iter()
+        .filter_map(|change| match change {
+            SchemaChange::ModifyColumn { new_column, changes, enum_type, .. } => {
+                Some((new_column.name(), changes.as_slice(), enum_type.as_ref().map(|e| e.name())))
+            }
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        modified,
+        vec![
+            ("amount", &[ColumnAttribute::MinValue, ColumnAttribute::MaxValue][..], None),
+            ("total", &[ColumnAttribute::Generated][..], None),
+            ("status", &[ColumnAttribute::Type, ColumnAttribute::Length, ColumnAttribute::EnumType][..], Some("Status")),
+        ]
+    );
+}
+
+fn model(schemas: Vec<schema_model::model::schema::Schema>) -> DatabaseModel {
+    DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, schemas)
+}
+
+fn table(schema_name: Option<&str>, name: &str, columns: &[&str]) -> schema_model::model::table::Table {
+    columns
+        .iter()
+        .fold(TableBuilder::new(schema_name, name), |builder, column| {
+            builder.add_column(ColumnBuilder::new(schema_name, column, ColumnType::Int).build())
+        })
+        .build()
+}
+
+#[test]
+fn diff_models_adds_and_drops_schemas_around_their_tables() {
+    let old = model(vec![
+        SchemaBuilder::new(None::<&str>).add_table(table(None, "users", &["id"])).build(),
+        SchemaBuilder::new(Some("legacy")).add_table(table(Some("legacy"), "audit", &["id"])).build(),
+    ]);
+    let new = model(vec![
+        SchemaBuilder::new(None::<&str>).add_table(table(None, "users", &["id", "age"])).build(),
+        SchemaBuilder::new(Some("sales")).add_table(table(Some("sales"), "orders", &["id"])).build(),
+    ]);
+
+    let cs = SchemaDiffEngine::diff_models(&old, &new).unwrap();
+
+    assert_eq!(cs.len(), 5, "{:?}", cs.changes());
+    assert!(matches!(&cs.changes()[0], SchemaChange::AddSchema { schema_name } if schema_name == "sales"));
+    assert!(matches!(&cs.changes()[1], SchemaChange::DropTable { table_name } if table_name == "legacy.audit"));
+    assert!(matches!(&cs.changes()[2], SchemaChange::AddTable { table_name } if table_name == "sales.orders"));
+    assert!(matches!(&cs.changes()[3], SchemaChange::AddColumn { table_name, column } if table_name == "users" && column.name() == "age"));
+    assert!(matches!(&cs.changes()[4], SchemaChange::DropSchema { schema_name } if schema_name == "legacy"));
+}
+
+#[test]
+fn diff_models_moves_a_table_between_schemas_and_diffs_it_in_its_new_home() {
+    let old = model(vec![
+        SchemaBuilder::new(Some("staging")).add_table(table(Some("staging"), "orders", &["id"])).build(),
+        SchemaBuilder::new(Some("sales")).build(),
+    ]);
+    let new = model(vec![
+        SchemaBuilder::new(Some("staging")).build(),
+        SchemaBuilder::new(Some("sales")).add_table(table(Some("sales"), "orders", &["id", "total"])).build(),
+    ]);
+
+    let cs = SchemaDiffEngine::diff_models(&old, &new).unwrap();
+
+    assert_eq!(cs.len(), 2, "{:?}", cs.changes());
+    assert!(matches!(
+        &cs.changes()[0],
+        SchemaChange::MoveTable { table_name, new_schema_name: Some(schema) } if table_name == "staging.orders" && schema == "sales"
+    ));
+    assert!(matches!(&cs.changes()[1], SchemaChange::AddColumn { table_name, .. } if table_name == "sales.orders"));
+}
+
+#[test]
+fn diff_models_does_not_guess_a_move_when_the_name_is_ambiguous() {
+    let old = model(vec![
+        SchemaBuilder::new(Some("a")).add_table(table(Some("a"), "log", &["id"])).build(),
+        SchemaBuilder::new(Some("b")).add_table(table(Some("b"), "log", &["id"])).build(),
+        SchemaBuilder::new(Some("c")).build(),
+    ]);
+    let new = model(vec![
+        SchemaBuilder::new(Some("a")).build(),
+        SchemaBuilder::new(Some("b")).build(),
+        SchemaBuilder::new(Some("c")).add_table(table(Some("c"), "log", &["id"])).build(),
+    ]);
+
+    let cs = SchemaDiffEngine::diff_models(&old, &new).unwrap();
+
+    assert!(!cs.changes().iter().any(|c| matches!(c, SchemaChange::MoveTable { .. })));
+    assert_eq!(cs.changes().iter().filter(|c| matches!(c, SchemaChange::DropTable { .. })).count(), 2);
+    assert!(cs.changes().iter().any(|c| matches!(c, SchemaChange::AddTable { table_name } if table_name == "c.log")));
+}
+
+fn enum_schema(codes: &[&str]) -> schema_model::model::schema::Schema {
+    SchemaBuilder::new(None::<&str>)
+        .add_enum_type(EnumType::new("Status", codes.iter().map(|code| EnumValue::new(*code, Some(*code))).collect()))
+        .add_table(
+            TableBuilder::new(None, "orders")
+                .add_column(ColumnBuilder::new(None, "status", ColumnType::Enum).enum_type(Some("Status".to_string())).build())
+                .add_column(ColumnBuilder::new(None, "id", ColumnType::Int).build())
+                .build(),
+        )
+        .build()
+}
+
+#[test]
+fn detects_changed_enum_values_with_the_columns_using_them() {
+    let cs = SchemaDiffEngine::diff(&enum_schema(&["N", "S"]), &enum_schema(&["N", "P", "S"])).unwrap();
+
+    assert_eq!(cs.len(), 1, "{:?}", cs.changes());
+    assert!(matches!(
+        &cs.changes()[0],
+        SchemaChange::ModifyEnumType { old_enum_type, new_enum_type, columns }
+            if old_enum_type.values().len() == 2
+                && new_enum_type.values().len() == 3
+                && columns.len() == 1
+                && columns[0].0 == "orders"
+                && columns[0].1.name() == "status"
+    ));
+    assert!(SchemaDiffEngine::diff(&enum_schema(&["N", "S"]), &enum_schema(&["N", "S"])).unwrap().is_empty());
+}
+
+#[test]
+fn detects_added_dropped_and_modified_programs_per_database() {
+    let function = |db_type, sql| Function::new(None, "totals", db_type, sql);
+    let old = SchemaBuilder::new(None::<&str>)
+        .add_functions(vec![
+            function(DatabaseType::Postgresql, "create function totals() ..."),
+            function(DatabaseType::SqlServer, "create function totals() ..."),
+        ])
+        .build();
+    let new = SchemaBuilder::new(None::<&str>)
+        .add_functions(vec![
+            function(DatabaseType::Postgresql, "  create function totals() ...\n"),
+            function(DatabaseType::SqlServer, "create function totals() -- changed"),
+            function(DatabaseType::MySql, "create function totals() ..."),
+        ])
+        .build();
+
+    let cs = SchemaDiffEngine::diff(&old, &new).unwrap();
+
+    assert_eq!(cs.len(), 2, "{:?}", cs.changes());
+    assert!(matches!(&cs.changes()[0], SchemaChange::ModifyFunction { new_function, .. } if new_function.database_type() == DatabaseType::SqlServer));
+    assert!(matches!(&cs.changes()[1], SchemaChange::AddFunction { function } if function.database_type() == DatabaseType::MySql));
+}
+
+#[test]
+fn detects_trigger_and_other_sql_changes() {
+    let schema = |trigger_text: &str, sql: &str| {
+        SchemaBuilder::new(None::<&str>)
+            .add_table(
+                TableBuilder::new(None, "orders")
+                    .add_column(ColumnBuilder::new(None, "id", ColumnType::Int).build())
+                    .add_trigger(Trigger::new(trigger_text, TriggerType::Delete, DatabaseType::Postgresql))
+                    .build(),
+            )
+            .add_other_sql(OtherSql::new(DatabaseType::Postgresql, OtherSqlOrder::Bottom, sql))
+            .build()
+    };
+
+    let cs = SchemaDiffEngine::diff(&schema("delete from a", "grant all"), &schema("delete from b", "grant all")).unwrap();
+    assert_eq!(cs.len(), 2, "{:?}", cs.changes());
+    assert!(matches!(&cs.changes()[0], SchemaChange::DropTrigger { table_name, trigger } if table_name == "orders" && trigger.trigger_text() == "delete from a"));
+    assert!(matches!(&cs.changes()[1], SchemaChange::AddTrigger { trigger, .. } if trigger.trigger_text() == "delete from b"));
+
+    let cs = SchemaDiffEngine::diff(&schema("delete from a", "grant all"), &schema("delete from a", "grant select")).unwrap();
+    assert_eq!(cs.len(), 2, "{:?}", cs.changes());
+    assert!(matches!(&cs.changes()[0], SchemaChange::DropOtherSql { other_sql } if other_sql.sql() == "grant all"));
+    assert!(matches!(&cs.changes()[1], SchemaChange::AddOtherSql { other_sql } if other_sql.sql() == "grant select"));
+}
+
+#[test]
+fn detects_aggregation_changes() {
+    let schema = |frequency: AggregationFrequency, criteria: Option<&str>| {
+        SchemaBuilder::new(None::<&str>)
+            .add_table(
+                TableBuilder::new(None, "sale")
+                    .add_column(ColumnBuilder::new(None, "amount", ColumnType::Int).build())
+                    .add_aggregation(Aggregation::new(
+                        "sale_summary",
+                        "sold_on",
+                        criteria,
+                        "summarized_at",
+                        frequency,
+                        vec![AggregationColumn::new(AggregationType::Sum, "amount", "total")],
+                        vec![],
+                    ))
+                    .build(),
+            )
+            .build()
+    };
+
+    let cs = SchemaDiffEngine::diff(&schema(AggregationFrequency::Daily, None), &schema(AggregationFrequency::Daily, None)).unwrap();
+    assert!(cs.is_empty(), "{:?}", cs.changes());
+
+    let cs = SchemaDiffEngine::diff(&schema(AggregationFrequency::Daily, None), &schema(AggregationFrequency::Monthly, None)).unwrap();
+    assert_eq!(cs.len(), 2, "{:?}", cs.changes());
+    assert!(matches!(&cs.changes()[0], SchemaChange::DropAggregation { table_name, aggregation }
+        if table_name == "sale" && aggregation.aggregation_frequency() == AggregationFrequency::Daily));
+    assert!(matches!(&cs.changes()[1], SchemaChange::AddAggregation { table_name, aggregation }
+        if table_name == "sale" && aggregation.aggregation_frequency() == AggregationFrequency::Monthly));
+
+    let cs = SchemaDiffEngine::diff(&schema(AggregationFrequency::Daily, None), &schema(AggregationFrequency::Daily, Some("amount > 0"))).unwrap();
+    assert_eq!(cs.len(), 2, "{:?}", cs.changes());
+    assert!(matches!(&cs.changes()[1], SchemaChange::AddAggregation { aggregation, .. } if aggregation.criteria() == Some("amount > 0")));
+}
+
+fn customer_schema(table_name: &str, previous_name: Option<&str>, column_name: &str, column_previous_name: Option<&str>) -> schema_model::model::schema::Schema {
+    let column = |name: &str, previous: Option<&str>| {
+        ColumnBuilder::new(None, name, ColumnType::Varchar).length(50).previous_name(previous.map(str::to_string)).build()
+    };
+    SchemaBuilder::new(None::<&str>)
+        .add_table(
+            TableBuilder::new(None, table_name)
+                .previous_name(previous_name.map(str::to_string))
+                .add_column(ColumnBuilder::new(None, "id", ColumnType::Int).build())
+                .add_column(column(column_name, column_previous_name))
+                .add_index(KeyBuilder::new(KeyType::Index).add_column(column_name).build())
+                .build(),
+        )
+        .build()
+}
+
+#[test]
+fn previous_names_rename_tables_and_columns_in_place() {
+    let old = customer_schema("client", None, "name", None);
+    let new = customer_schema("customer", Some("client"), "full_name", Some("name"));
+
+    let cs = SchemaDiffEngine::diff(&old, &new).unwrap();
+
+    // The index follows its renamed column, so nothing is dropped or created.
+    assert_eq!(cs.len(), 2, "{:?}", cs.changes());
+    assert!(matches!(&cs.changes()[0], SchemaChange::RenameTable { old_name, new_name } if old_name == "client" && new_name == "customer"));
+    assert!(matches!(
+        &cs.changes()[1],
+        SchemaChange::RenameColumn { table_name, old_name, new_name }
+            if table_name == "customer" && old_name == "name" && new_name == "full_name"
+    ));
+
+    // Once migrated, the hints stay in the model and change nothing.
+    assert!(SchemaDiffEngine::diff(&new, &new).unwrap().is_empty());
+}
+
+#[test]
+fn drops_on_a_renamed_table_run_before_the_rename_under_the_old_name() {
+    let old = customer_schema("client", None, "name", None);
+    let new = SchemaBuilder::new(None::<&str>)
+        .add_table(
+            TableBuilder::new(None, "customer")
+                .previous_name(Some("client".to_string()))
+                .add_column(ColumnBuilder::new(None, "id", ColumnType::Int).build())
+                .build(),
+        )
+        .build();
+
+    let cs = SchemaDiffEngine::diff(&old, &new).unwrap();
+
+    assert_eq!(cs.len(), 3, "{:?}", cs.changes());
+    assert!(matches!(&cs.changes()[0], SchemaChange::DropKey { table_name, .. } if table_name == "client"));
+    assert!(matches!(&cs.changes()[1], SchemaChange::DropColumn { table_name, .. } if table_name == "client"));
+    assert!(matches!(&cs.changes()[2], SchemaChange::RenameTable { .. }));
+}
+
+#[test]
+fn previous_names_rename_constraints_and_indexes() {
+    let table = |constraint: Constraint, index: Key| {
+        SchemaBuilder::new(None::<&str>)
+            .add_table(
+                TableBuilder::new(None, "orders")
+                    .add_column(ColumnBuilder::new(None, "total", ColumnType::Int).build())
+                    .add_constraint(constraint)
+                    .add_index(index)
+                    .build(),
+            )
+            .build()
+    };
+    let old = table(
+        Constraint::new("ck_total", "total >= 0", DatabaseType::Postgresql),
+        KeyBuilder::new(KeyType::Index).add_column("total").build(),
+    );
+    let new = table(
+        Constraint::new("ck_orders_total", "total >= 0", DatabaseType::Postgresql).with_previous_name(Some("ck_total".to_string())),
+        KeyBuilder::new(KeyType::Index).add_column("total").previous_name("ix_total").build(),
+    );
+
+    let cs = SchemaDiffEngine::diff(&old, &new).unwrap();
+
+    assert_eq!(cs.len(), 2, "{:?}", cs.changes());
+    assert!(matches!(
+        &cs.changes()[0],
+        SchemaChange::RenameConstraint { old_name, new_name, .. } if old_name == "ck_total" && new_name == "ck_orders_total"
+    ));
+    assert!(matches!(&cs.changes()[1], SchemaChange::RenameIndex { old_name, .. } if old_name == "ix_total"));
+    assert!(SchemaDiffEngine::diff(&new, &new).unwrap().is_empty());
+}
+
+#[test]
+fn renamed_indexes_take_the_name_of_their_new_position() {
+    let table = |indexes: Vec<Key>| {
+        let mut builder = TableBuilder::new(None, "orders")
+            .add_column(ColumnBuilder::new(None, "placed_at", ColumnType::Date).build())
+            .add_column(ColumnBuilder::new(None, "total", ColumnType::Int).build());
+        for index in indexes {
+            builder = builder.add_index(index);
+        }
+        SchemaBuilder::new(None::<&str>).add_table(builder.build()).build()
+    };
+    let old = table(vec![
+        KeyBuilder::new(KeyType::Index).add_column("placed_at").build(),
+        KeyBuilder::new(KeyType::Index).add_column("total").build(),
+    ]);
+    let new = table(vec![KeyBuilder::new(KeyType::Index).add_column("total").previous_name("ix_orders2").build()]);
+
+    let cs = SchemaDiffEngine::diff(&old, &new).unwrap();
+
+    // The dropped index frees ix_orders1 before the rename phase takes it.
+    assert_eq!(cs.len(), 2, "{:?}", cs.changes());
+    assert!(matches!(&cs.changes()[0], SchemaChange::DropKey { key, position: 1, .. } if key.columns_as_string() == "placed_at"));
+    assert!(matches!(
+        &cs.changes()[1],
+        SchemaChange::RenameIndex { old_name, key, position: 1, .. } if old_name == "ix_orders2" && key.columns_as_string() == "total"
+    ));
+}
+
+#[test]
+fn previous_names_that_point_at_nothing_fail_the_diff() {
+    let old = customer_schema("client", None, "name", None);
+
+    let err = SchemaDiffEngine::diff(&old, &customer_schema("customer", Some("buyer"), "name", None)).unwrap_err();
+    assert!(matches!(&err, DiffError::UnknownPreviousName(msg) if msg.contains("customer") && msg.contains("buyer")), "{}", err);
+
+    let err = SchemaDiffEngine::diff(&old, &customer_schema("client", None, "full_name", Some("nickname"))).unwrap_err();
+    assert!(matches!(&err, DiffError::UnknownPreviousName(msg) if msg.contains("full_name") && msg.contains("nickname")), "{}", err);
+
+    let both = SchemaBuilder::new(None::<&str>)
+        .add_table(table(None, "client", &["id"]))
+        .add_table(TableBuilder::new(None, "customer").previous_name(Some("client".to_string())).build())
+        .build();
+    let err = SchemaDiffEngine::diff(&old, &both).unwrap_err();
+    assert!(matches!(err, DiffError::ConflictingPreviousName(_)), "{}", err);
+}
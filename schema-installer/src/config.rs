@@ -1,5 +1,7 @@
 use std::path::PathBuf;
-use schema_model::model::types::{BooleanMode, ForeignKeyMode};
+use schema_model::model::naming::NamingConvention;
+use schema_model::model::types::{BooleanMode, ForeignKeyMode, IdentifierQuoting, IdentityMode};
+use schema_sql_generator::common::constraint_naming::ConstraintNaming;
 use schema_sql_generator::common::generator_type::GeneratorType;
 use crate::error::SchemaInstallerError;
 
@@ -9,6 +11,10 @@ pub struct SchemaInstallerConfig {
     pub schema_file: Option<PathBuf>,
     pub boolean_mode: BooleanMode,
     pub foreign_key_mode: ForeignKeyMode,
+    pub identity_mode: IdentityMode,
+    pub identifier_quoting: IdentifierQuoting,
+    pub naming_convention: NamingConvention,
+    pub constraint_naming: ConstraintNaming,
 }
 
 pub struct SchemaInstallerConfigBuilder {
@@ -17,6 +23,10 @@ pub struct SchemaInstallerConfigBuilder {
     schema_file: Option<PathBuf>,
     boolean_mode: BooleanMode,
     foreign_key_mode: ForeignKeyMode,
+    identity_mode: IdentityMode,
+    identifier_quoting: IdentifierQuoting,
+    naming_convention: NamingConvention,
+    constraint_naming: ConstraintNaming,
 }
 
 impl SchemaInstallerConfigBuilder {
@@ -27,6 +37,10 @@ impl SchemaInstallerConfigBuilder {
             schema_file: None,
             boolean_mode: BooleanMode::Native,
             foreign_key_mode: ForeignKeyMode::Relations,
+            identity_mode: IdentityMode::Serial,
+            identifier_quoting: IdentifierQuoting::WhenNeeded,
+            naming_convention: NamingConvention::Preserve,
+            constraint_naming: ConstraintNaming::default(),
         }
     }
 
@@ -55,6 +69,26 @@ impl SchemaInstallerConfigBuilder {
         self
     }
 
+    pub fn identity_mode(mut self, mode: IdentityMode) -> Self {
+        self.identity_mode = mode;
+        self
+    }
+
+    pub fn identifier_quoting(mut self, quoting: IdentifierQuoting) -> Self {
+        self.identifier_quoting = quoting;
+        self
+    }
+
+    pub fn naming_convention(mut self, naming: NamingConvention) -> Self {
+        self.naming_convention = naming;
+        self
+    }
+
+    pub fn constraint_naming(mut self, naming: ConstraintNaming) -> Self {
+        self.constraint_naming = naming;
+        self
+    }
+
     pub fn build(self) -> Result<SchemaInstallerConfig, SchemaInstallerError> {
         let database_type = self.database_type
             .ok_or_else(|| SchemaInstallerError::InvalidConfiguration("database_type required".to_string()))?;
@@ -72,6 +106,10 @@ impl SchemaInstallerConfigBuilder {
             schema_file: self.schema_file,
             boolean_mode: self.boolean_mode,
             foreign_key_mode: self.foreign_key_mode,
+            identity_mode: self.identity_mode,
+            identifier_quoting: self.identifier_quoting,
+            naming_convention: self.naming_convention,
+            constraint_naming: self.constraint_naming,
         })
     }
 }
@@ -8,6 +8,7 @@ use tiberius::Client;
 use tokio::net::TcpStream;
 use tokio_util::compat::{Compat, TokioAsyncReadCompatExt};
 
+#[allow(clippy::large_enum_variant)]
 pub enum AnyPool {
     Postgresql(Pool<Postgres>),
     Sqlite(Pool<Sqlite>),
@@ -62,6 +63,12 @@ impl AnyPool {
 
                 Ok(AnyPool::SqlServer(tokio::sync::Mutex::new(client)))
             }
+            GeneratorType::MySql => Err(SchemaInstallerError::InvalidConfiguration(
+                "the installer can't connect to MySQL/MariaDB; generate the script and run it with the mysql client".to_string(),
+            )),
+            GeneratorType::DuckDb => Err(SchemaInstallerError::InvalidConfiguration(
+                "the installer doesn't support DuckDB; generate the script and run it with the duckdb client".to_string(),
+            )),
         }
     }
 
@@ -93,7 +100,7 @@ impl AnyPool {
     }
 
     pub async fn ensure_migration_table(&self, database_type: &GeneratorType) -> Result<(), SchemaInstallerError> {
-        let ddl = SchemaMigrationDdl::schema_migration_ddl(database_type);
+        let ddl = SchemaMigrationDdl::schema_migration_ddl(database_type)?;
         self.execute_sql(&ddl).await
     }
 
@@ -162,6 +169,7 @@ impl AnyPool {
     pub async fn get_applied_migrations(&self) -> Result<Vec<AppliedMigration>, SchemaInstallerError> {
         match self {
             AnyPool::Postgresql(pool) => {
+                #[allow(clippy::type_complexity)]
                 let rows: Vec<(i64, String, String, String, i32, chrono::DateTime<chrono::Utc>, String, String)> =
                     sqlx::query_as(
                         "SELECT id, version, script_path, checksum, execution_time_ms, installed_at, status, tool_version FROM schema_migration ORDER BY installed_at"
@@ -189,6 +197,7 @@ impl AnyPool {
                     .collect())
             }
             AnyPool::Sqlite(pool) => {
+                #[allow(clippy::type_complexity)]
                 let rows: Vec<(i64, String, String, String, i64, String, String, String)> =
                     sqlx::query_as(
                         "SELECT id, version, script_path, checksum, execution_time_ms, installed_at, status, tool_version FROM schema_migration ORDER BY installed_at"
@@ -5,10 +5,9 @@ use schema_parser::parse_database_xml;
 use schema_sql_generator::common::generate_options::GenerateOptions;
 use schema_sql_generator::common::generator_type::GeneratorType;
 use schema_sql_generator::common::output_mode::OutputMode;
-use schema_sql_generator::common::print_writer::PrintWriter;
-use std::cell::RefCell;
+use schema_sql_generator::common::sql_split::split_sql_statements;
 use std::fs;
-use std::rc::Rc;
+use std::sync::Arc;
 
 pub struct SchemaInstaller;
 
@@ -36,38 +35,31 @@ impl SchemaInstaller {
         let database_model = parse_database_xml(&schema_content)
             .map_err(SchemaInstallerError::Parse)?;
 
-        // Catches dangling relation targets and enum-type references up front, with a
-        // clear message - several generator code paths panic on a reference that
-        // doesn't resolve, on the assumption the model was already validated.
-        let validation_errors = database_model.validate();
+        // Catches dangling relation targets and enum-type references up front, reporting
+        // all of them rather than the first one generation would fail on.
+        let validation_errors = database_model.validate_for(config.database_type.database_type());
         if !validation_errors.is_empty() {
             return Err(SchemaInstallerError::ValidationFailed(validation_errors.join("\n")));
         }
 
-        // Generate SQL by writing to temp file
-        // (PrintWriter's BufWriter makes it difficult to extract bytes in memory)
-        let temp_file = std::env::temp_dir().join(temp_install_file_name());
-        let file = std::fs::File::create(&temp_file)
-            .map_err(SchemaInstallerError::Io)?;
-
-        let writer_temp = PrintWriter::new(Box::new(file));
         let generate_options = GenerateOptions {
-            database_model: Rc::new(database_model),
-            writer: Rc::new(RefCell::new(writer_temp)),
+            database_model: Arc::new(database_model),
             boolean_mode: config.boolean_mode,
             foreign_key_mode: config.foreign_key_mode,
             output_mode: OutputMode::All,
             target_postgres_version: 17,
+            target_mariadb: false,
             emit_postgres_extensions: true,
             extension_check_user: None,
+            identity_mode: config.identity_mode,
+            // Existence guards let the script be applied over objects that are already there.
+            idempotent: true,
+            identifier_quoting: config.identifier_quoting,
+            naming_convention: config.naming_convention.clone(),
+            constraint_naming: config.constraint_naming.clone(),
         };
-
-        config.database_type.generate(generate_options);
-
-        let sql = std::fs::read_to_string(&temp_file)
-            .map_err(SchemaInstallerError::Io)?;
-
-        let _ = std::fs::remove_file(&temp_file);
+        let sql = config.database_type.generate_script(&generate_options)
+            .map_err(|error| SchemaInstallerError::Generation(error.to_string()))?;
 
         // Record migration under a fixed, reserved version so it can never collide
         // with real migration versions (which start at V1+).
@@ -151,50 +143,10 @@ impl SchemaInstaller {
         database_type: &GeneratorType,
         sql: &str,
     ) -> Result<(), SchemaInstallerError> {
-        for statement in crate::sql_split::split_sql_statements(sql, database_type) {
+        for statement in split_sql_statements(sql, database_type.database_type()) {
             pool.execute_sql(&statement).await?;
         }
 
         Ok(())
     }
 }
-
-/// Builds a temp filename for the generated install SQL. Combines the full (not just
-/// sub-second) nanosecond timestamp, the process id, and a per-process monotonic
-/// counter: `subsec_nanos()` alone (discarding the seconds component, with no PID or
-/// counter) could collide between two concurrent `install()` calls on the same host
-/// started moments apart, letting one overwrite or read the other's generated SQL
-/// mid-flight - and the timestamp alone isn't safe either, since two calls on a coarser
-/// clock (or simply fast enough back-to-back) can still land on the same nanosecond
-/// reading. The counter guarantees uniqueness within a process regardless of clock
-/// resolution; the PID guarantees it across processes.
-fn temp_install_file_name() -> String {
-    static COUNTER: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
-    let nanos = std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .map(|d| d.as_nanos())
-        .unwrap_or(0);
-    let count = COUNTER.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
-    format!("schema_install_temp_{}_{}_{}.sql", std::process::id(), nanos, count)
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn temp_install_file_name_embeds_the_process_id() {
-        let name = temp_install_file_name();
-        assert!(name.starts_with(&format!("schema_install_temp_{}_", std::process::id())));
-        assert!(name.ends_with(".sql"));
-    }
-
-    #[test]
-    fn temp_install_file_name_is_unique_across_calls() {
-        // A cheap proxy for the concurrency fix: two calls (even back-to-back, so the
-        // PID is identical) must not produce the same filename, since the nanosecond
-        // timestamp component differs.
-        let names: std::collections::HashSet<String> = (0..20).map(|_| temp_install_file_name()).collect();
-        assert_eq!(names.len(), 20, "expected all 20 generated names to be unique");
-    }
-}
@@ -1,6 +1,6 @@
 use clap::{Parser, Subcommand};
 use schema_installer::{DirectoryMigrationSource, Migrator, SchemaInstaller, SchemaInstallerConfigBuilder};
-use schema_model::model::types::{BooleanMode, ForeignKeyMode};
+use schema_model::model::types::{BooleanMode, ForeignKeyMode, IdentityMode};
 use schema_sql_generator::common::generator_type::GeneratorType;
 use std::path::PathBuf;
 
@@ -50,6 +50,8 @@ enum Commands {
     Install {
         #[arg(long, help = "Path to XML schema file")]
         schema_file: PathBuf,
+        #[arg(long, default_value = "serial", help = "PostgreSQL sequence columns (serial, always, by-default)")]
+        identity_mode: String,
     },
     /// Check if there are pending migrations (exits 0 = none, 1 = pending)
     PendingCheck {
@@ -133,13 +135,15 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 println!("No pending migrations");
             }
         }
-        Commands::Install { schema_file } => {
+        Commands::Install { schema_file, identity_mode } => {
+            let identity_mode: IdentityMode = identity_mode.parse()?;
             let config = SchemaInstallerConfigBuilder::new()
                 .database_type(database_type)
                 .connection_string(connection_string.clone())
                 .schema_file(schema_file)
                 .boolean_mode(boolean_mode)
                 .foreign_key_mode(foreign_key_mode)
+                .identity_mode(identity_mode)
                 .build()?;
 
             SchemaInstaller::install(&config).await?;
@@ -149,15 +153,22 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+/// MySQL, MariaDB and DuckDB parse too, so that they fail with the reason the installer
+/// can't run them rather than as an unknown type.
 fn parse_database_type(db_type: &str) -> Result<GeneratorType, Box<dyn std::error::Error>> {
     match db_type.to_lowercase().as_str() {
-        "postgresql" => Ok(GeneratorType::Postgresql),
-        "sqlite" => Ok(GeneratorType::Sqlite),
-        "sqlserver" | "mssql" => Ok(GeneratorType::SqlServer),
-        _ => {
-            eprintln!("Error: Unknown database type '{}'. Supported: postgresql, sqlite, sqlserver", db_type);
-            std::process::exit(1);
-        }
+        "postgres" => Ok(GeneratorType::Postgresql),
+        "mssql" => Ok(GeneratorType::SqlServer),
+        other => match other.parse() {
+            Ok(database_type) => Ok(database_type),
+            Err(_) => {
+                eprintln!(
+                    "Error: Unknown database type '{}'. Supported: postgresql, sqlite, sqlserver; mysql, mariadb and duckdb scripts are generated with schema-sql-generator",
+                    db_type
+                );
+                std::process::exit(1);
+            }
+        },
     }
 }
 
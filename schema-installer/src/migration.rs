@@ -45,6 +45,7 @@ impl MigrationStatus {
         }
     }
 
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Option<Self> {
         match s {
             "success" => Some(MigrationStatus::Success),
@@ -1,4 +1,5 @@
 use schema_sql_generator::common::generator_type::GeneratorType;
+use schema_sql_generator::common::sql_split::split_sql_statements;
 use std::collections::HashSet;
 use std::time::{Duration, Instant};
 
@@ -395,7 +396,7 @@ async fn execute_migration(
     database_type: &GeneratorType,
     sql: &str,
 ) -> Result<(), SchemaInstallerError> {
-    let statements = crate::sql_split::split_sql_statements(sql, database_type);
+    let statements = split_sql_statements(sql, database_type.database_type());
     // All statements in a migration file commit or roll back together, so a failure
     // partway through never leaves earlier statements permanently applied.
     pool.execute_transactional(&statements).await
@@ -1,10 +1,11 @@
+use crate::error::SchemaInstallerError;
 use schema_sql_generator::common::generator_type::GeneratorType;
 
 pub struct SchemaMigrationDdl;
 
 impl SchemaMigrationDdl {
-    pub fn schema_migration_ddl(database_type: &GeneratorType) -> String {
-        match database_type {
+    pub fn schema_migration_ddl(database_type: &GeneratorType) -> Result<String, SchemaInstallerError> {
+        let ddl = match database_type {
             GeneratorType::Postgresql => {
                 r#"CREATE TABLE IF NOT EXISTS schema_migration (
     id BIGSERIAL PRIMARY KEY,
@@ -50,7 +51,18 @@ END"#
 );"#
                     .to_string()
             }
-        }
+            GeneratorType::MySql => {
+                return Err(SchemaInstallerError::InvalidConfiguration(
+                    "the installer doesn't support MySQL/MariaDB, so it keeps no migration table there".to_string(),
+                ));
+            }
+            GeneratorType::DuckDb => {
+                return Err(SchemaInstallerError::InvalidConfiguration(
+                    "the installer doesn't support DuckDB, so it keeps no migration table there".to_string(),
+                ));
+            }
+        };
+        Ok(ddl)
     }
 }
 
@@ -60,21 +72,21 @@ mod tests {
 
     #[test]
     fn test_postgres_schema_migration_ddl() {
-        let ddl = SchemaMigrationDdl::schema_migration_ddl(&GeneratorType::Postgresql);
+        let ddl = SchemaMigrationDdl::schema_migration_ddl(&GeneratorType::Postgresql).unwrap();
         assert!(ddl.contains("CREATE TABLE IF NOT EXISTS schema_migration"));
         assert!(ddl.contains("BIGSERIAL PRIMARY KEY"));
     }
 
     #[test]
     fn test_sqlserver_schema_migration_ddl() {
-        let ddl = SchemaMigrationDdl::schema_migration_ddl(&GeneratorType::SqlServer);
+        let ddl = SchemaMigrationDdl::schema_migration_ddl(&GeneratorType::SqlServer).unwrap();
         assert!(ddl.contains("dbo.schema_migration"));
         assert!(ddl.contains("BIGINT IDENTITY(1,1)"));
     }
 
     #[test]
     fn test_sqlite_schema_migration_ddl() {
-        let ddl = SchemaMigrationDdl::schema_migration_ddl(&GeneratorType::Sqlite);
+        let ddl = SchemaMigrationDdl::schema_migration_ddl(&GeneratorType::Sqlite).unwrap();
         assert!(ddl.contains("CREATE TABLE IF NOT EXISTS schema_migration"));
         assert!(ddl.contains("INTEGER PRIMARY KEY AUTOINCREMENT"));
         assert!(ddl.contains("UNIQUE (version)"));
@@ -82,13 +94,21 @@ mod tests {
 
     #[test]
     fn test_postgres_schema_migration_ddl_has_unique_version() {
-        let ddl = SchemaMigrationDdl::schema_migration_ddl(&GeneratorType::Postgresql);
+        let ddl = SchemaMigrationDdl::schema_migration_ddl(&GeneratorType::Postgresql).unwrap();
         assert!(ddl.contains("UNIQUE (version)"));
     }
 
     #[test]
     fn test_sqlserver_schema_migration_ddl_has_unique_version() {
-        let ddl = SchemaMigrationDdl::schema_migration_ddl(&GeneratorType::SqlServer);
+        let ddl = SchemaMigrationDdl::schema_migration_ddl(&GeneratorType::SqlServer).unwrap();
         assert!(ddl.contains("UNIQUE (version)"));
     }
+
+    #[test]
+    fn test_mysql_and_duckdb_have_no_schema_migration_ddl() {
+        for database_type in [GeneratorType::MySql, GeneratorType::DuckDb] {
+            let error = SchemaMigrationDdl::schema_migration_ddl(&database_type).unwrap_err();
+            assert!(matches!(error, SchemaInstallerError::InvalidConfiguration(_)));
+        }
+    }
 }
@@ -1,8 +1,12 @@
+use schema_diff::SchemaDiffEngine;
 use schema_installer::connection::AnyPool;
 use schema_installer::{
-    DirectoryMigrationSource, EmbeddedMigrationSource, Migration, Migrator, SchemaInstallerConfigBuilder,
-    SchemaInstallerError,
+    DirectoryMigrationSource, EmbeddedMigrationSource, Migration, Migrator, SchemaInstaller,
+    SchemaInstallerConfig, SchemaInstallerConfigBuilder, SchemaInstallerError,
 };
+use schema_migration_generator::create_generator_with_naming;
+use schema_model::model::types::IdentifierQuoting;
+use schema_parser::parse_database_xml;
 use schema_sql_generator::common::generator_type::GeneratorType;
 use sqlx::Row;
 use std::path::PathBuf;
@@ -653,3 +657,170 @@ async fn test_sqlite_update_migration_status_errors_when_the_tracking_row_is_gon
         "update_migration_status should error when its tracking row no longer exists"
     );
 }
+
+/// Diffs two versions of a schema into migration `version`, as the CLI would for `config`.
+fn generated_migration(config: &SchemaInstallerConfig, version: &str, old_schema: &str, new_schema: &str) -> Migration {
+    let old_model = parse_database_xml(old_schema).expect("parse old schema");
+    let new_model = parse_database_xml(new_schema).expect("parse new schema");
+    let changes = SchemaDiffEngine::diff_models(&old_model, &new_model).expect("diff");
+    let mut sql = Vec::new();
+    create_generator_with_naming(
+        config.database_type.database_type(),
+        IdentifierQuoting::WhenNeeded,
+        config.naming_convention.clone(),
+        config.constraint_naming.clone(),
+    )
+    .generate(&changes, &mut sql)
+    .expect("generate migration");
+    Migration {
+        version: version.to_string(),
+        description: format!("generated migration {}", version),
+        script_path: format!("V{}__generated.sql", version),
+        sql: String::from_utf8(sql).expect("utf-8 migration"),
+    }
+}
+
+async fn sqlite_indexes(connection_string: &str, table: &str) -> Vec<(String, String)> {
+    let check_pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .connect(connection_string)
+        .await
+        .expect("connect to verify schema");
+    let rows = sqlx::query(
+        "SELECT il.name AS index_name, ii.name AS column_name \
+         FROM pragma_index_list(?) il, pragma_index_info(il.name) ii \
+         WHERE il.origin = 'c' ORDER BY il.name",
+    )
+    .bind(table)
+    .fetch_all(&check_pool)
+    .await
+    .expect("list indexes");
+    rows.iter().map(|row| (row.get("index_name"), row.get("column_name"))).collect()
+}
+
+fn orders_schema(indexes: &str) -> String {
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<database xmlns="http://stano.com/database">
+    <table name="orders">
+        <columns>
+            <column name="id" type="Sequence" required="true"/>
+            <column name="number" type="Varchar" length="20" required="true"/>
+            <column name="placed_at" type="Date"/>
+            <column name="customer_id" type="Int"/>
+            <column name="total" type="Int"/>
+        </columns>
+        <keys>
+            <primary>
+                <column name="id"/>
+            </primary>
+            <unique>
+                <column name="number"/>
+            </unique>
+            {}
+        </keys>
+    </table>
+</database>"#,
+        indexes
+    )
+}
+
+#[tokio::test]
+async fn test_sqlite_migration_replaces_the_indexes_the_install_script_created() {
+    let temp_dir = TempDir::new().expect("create temp dir");
+    let connection_string = sqlite_connection_string(&temp_dir, "test_install_then_migrate.db");
+    let old_schema = orders_schema(
+        r#"<index><column name="placed_at"/></index>
+            <index><column name="customer_id"/></index>"#,
+    );
+    let new_schema = orders_schema(
+        r#"<index><column name="customer_id"/></index>
+            <index><column name="total"/></index>"#,
+    );
+    let schema_path = temp_dir.path().join("orders.xml");
+    std::fs::write(&schema_path, &old_schema).expect("write schema");
+
+    let config = SchemaInstallerConfigBuilder::new()
+        .database_type(GeneratorType::Sqlite)
+        .connection_string(connection_string.clone())
+        .schema_file(schema_path)
+        .build()
+        .expect("valid config");
+    SchemaInstaller::install(&config).await.expect("install should succeed");
+
+    // The migration has to find the indexes under the names the install script gave them:
+    // `placed_at` goes, `customer_id` moves from second to first and `total` is added second.
+    let migration = generated_migration(&config, "1", &old_schema, &new_schema);
+    Migrator::migrate(&config, Box::new(EmbeddedMigrationSource { migrations: vec![migration] }))
+        .await
+        .expect("migration should succeed");
+
+    assert_eq!(
+        sqlite_indexes(&connection_string, "orders").await,
+        vec![
+            ("ix_orders1".to_string(), "customer_id".to_string()),
+            ("ix_orders2".to_string(), "total".to_string()),
+        ]
+    );
+}
+
+fn customer_schema(table: &str, index: &str) -> String {
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<database xmlns="http://stano.com/database">
+    <table {}>
+        <columns>
+            <column name="id" type="Int" required="true"/>
+            <column name="name" type="Varchar" length="100"/>
+        </columns>
+        <keys>
+            {}
+        </keys>
+    </table>
+</database>"#,
+        table, index
+    )
+}
+
+#[tokio::test]
+async fn test_sqlite_renamed_index_is_found_by_the_next_migration() {
+    let temp_dir = TempDir::new().expect("create temp dir");
+    let connection_string = sqlite_connection_string(&temp_dir, "test_rename_then_drop_index.db");
+    let client = customer_schema(r#"name="client""#, r#"<index><column name="name"/></index>"#);
+    let renamed = customer_schema(r#"name="customer" previousName="client""#, r#"<index><column name="name"/></index>"#);
+    let renamed_index = customer_schema(
+        r#"name="customer" previousName="client""#,
+        r#"<index previousName="ix_client1"><column name="name"/></index>"#,
+    );
+    let dropped_index = customer_schema(r#"name="customer" previousName="client""#, "");
+    let schema_path = temp_dir.path().join("client.xml");
+    std::fs::write(&schema_path, &client).expect("write schema");
+
+    let config = SchemaInstallerConfigBuilder::new()
+        .database_type(GeneratorType::Sqlite)
+        .connection_string(connection_string.clone())
+        .schema_file(schema_path)
+        .build()
+        .expect("valid config");
+    SchemaInstaller::install(&config).await.expect("install should succeed");
+
+    // Renaming the table keeps the index's name; the hint then renames it to the name the
+    // install script would give it, which is what the last migration drops.
+    let migrations = vec![
+        generated_migration(&config, "1", &client, &renamed),
+        generated_migration(&config, "2", &renamed, &renamed_index),
+    ];
+    Migrator::migrate(&config, Box::new(EmbeddedMigrationSource { migrations: migrations.clone() }))
+        .await
+        .expect("renames should succeed");
+    assert_eq!(
+        sqlite_indexes(&connection_string, "customer").await,
+        vec![("ix_customer1".to_string(), "name".to_string())]
+    );
+
+    let mut migrations = migrations;
+    migrations.push(generated_migration(&config, "3", &renamed_index, &dropped_index));
+    Migrator::migrate(&config, Box::new(EmbeddedMigrationSource { migrations }))
+        .await
+        .expect("dropping the index should succeed");
+    assert!(sqlite_indexes(&connection_string, "customer").await.is_empty());
+}
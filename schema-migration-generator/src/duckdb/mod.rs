@@ -0,0 +1,560 @@
+use std::io::Write;
+
+use schema_diff::{ChangeSet, ColumnAttribute, SchemaChange};
+use schema_model::model::column::Column;
+use schema_model::model::column_type::ColumnType;
+use schema_model::model::key::Key;
+use schema_model::model::relation::Relation;
+use schema_model::model::types::{DatabaseType, KeyType};
+use schema_sql_generator::common::generate_options::GenerateOptions;
+use schema_sql_generator::common::trigger_generator::TableTriggers;
+
+use crate::error::MigrationGeneratorError;
+use crate::migration_generator::{
+    changed_table_triggers, column_check_sql, column_default_sql, element_column, enum_values_sql, index_columns_sql,
+    options_without_model, qualified_name, replacing_create, same_key_sql, split_qualified_name, write_other_sql_removal,
+    write_rename_candidates, Identifiers, MigrationGenerator,
+};
+
+pub struct DuckDbMigrationGenerator {
+    options: GenerateOptions,
+}
+
+impl DuckDbMigrationGenerator {
+    pub fn new(options: GenerateOptions) -> Self {
+        Self { options }
+    }
+}
+
+impl Default for DuckDbMigrationGenerator {
+    fn default() -> Self {
+        Self::new(options_without_model())
+    }
+}
+
+impl MigrationGenerator for DuckDbMigrationGenerator {
+    fn generate(&self, change_set: &ChangeSet, writer: &mut dyn Write) -> Result<(), MigrationGeneratorError> {
+        let ids = Identifiers::new(DatabaseType::DuckDb, &self.options);
+        for change in change_set.changes() {
+            match change {
+                SchemaChange::AddSchema { schema_name } => {
+                    writeln!(writer, "CREATE SCHEMA IF NOT EXISTS {};", ids.generated(schema_name))?;
+                    writeln!(writer)?;
+                }
+                SchemaChange::DropSchema { schema_name } => {
+                    writeln!(writer, "DROP SCHEMA IF EXISTS {};", ids.generated(schema_name))?;
+                    writeln!(writer)?;
+                }
+                SchemaChange::MoveTable { table_name, new_schema_name } => {
+                    return Err(MigrationGeneratorError::UnsupportedChange(format!(
+                        "{} moves to schema {}; DuckDB can't change a table's schema, so it has to be recreated and its rows copied",
+                        table_name,
+                        new_schema_name.as_deref().unwrap_or("main")
+                    )));
+                }
+                // DuckDB can't create a table without columns, so the new table starts with
+                // the same placeholder key the SQLite migration uses.
+                SchemaChange::AddTable { table_name } => {
+                    writeln!(writer, "CREATE TABLE IF NOT EXISTS {} (id BIGINT PRIMARY KEY);", ids.name(table_name))?;
+                    writeln!(writer)?;
+                }
+                SchemaChange::DropTable { table_name } => {
+                    writeln!(writer, "DROP TABLE IF EXISTS {};", ids.name(table_name))?;
+                    writeln!(writer)?;
+                }
+                SchemaChange::RenameTable { old_name, new_name } => {
+                    let (_, new_name) = split_qualified_name(new_name);
+                    writeln!(writer, "ALTER TABLE {} RENAME TO {};", ids.name(old_name), ids.name(new_name))?;
+                    writeln!(writer)?;
+                }
+                // ADD COLUMN takes no constraints, so NOT NULL is set once the column exists.
+                SchemaChange::AddColumn { table_name, column } => {
+                    let quoted_table = ids.name(table_name);
+                    let quoted_column = ids.name(column.name());
+                    if matches!(column.column_type(), ColumnType::Sequence | ColumnType::LongSequence) {
+                        writeln!(
+                            writer,
+                            "CREATE SEQUENCE IF NOT EXISTS {};",
+                            ids.name(&column_sequence_name(table_name, column))
+                        )?;
+                    }
+                    let default = column_default_sql(&ids, column, default_sequence_name(table_name, column).as_deref())
+                        .map(|d| format!(" DEFAULT {}", d))
+                        .unwrap_or_default();
+                    writeln!(
+                        writer,
+                        "ALTER TABLE {} ADD COLUMN {}{}{};",
+                        quoted_table,
+                        quoted_column,
+                        column_type_sql(&ids, column),
+                        default
+                    )?;
+                    if column.required() {
+                        writeln!(writer, "ALTER TABLE {} ALTER COLUMN {} SET NOT NULL;", quoted_table, quoted_column)?;
+                    }
+                    writeln!(writer)?;
+                }
+                SchemaChange::DropColumn { table_name, column_name, rename_candidates } => {
+                    write_rename_candidates(writer, column_name, rename_candidates, |candidate| {
+                        format!("ALTER TABLE {} RENAME COLUMN {} TO {};", ids.name(table_name), ids.name(column_name), ids.name(candidate))
+                    })?;
+                    writeln!(writer, "ALTER TABLE {} DROP COLUMN {};", ids.name(table_name), ids.name(column_name))?;
+                    writeln!(writer)?;
+                }
+                SchemaChange::RenameColumn { table_name, old_name, new_name } => {
+                    writeln!(
+                        writer,
+                        "ALTER TABLE {} RENAME COLUMN {} TO {};",
+                        ids.name(table_name),
+                        ids.name(old_name),
+                        ids.name(new_name)
+                    )?;
+                    writeln!(writer)?;
+                }
+                // The create script gives DuckDB tables none of these options.
+                SchemaChange::ModifyTableOptions { .. } => {}
+                SchemaChange::ModifyColumn { table_name, old_column, new_column, changes, enum_type: _ } => {
+                    write_modify_column(writer, &ids, table_name, old_column, new_column, changes)?;
+                }
+                SchemaChange::ModifyDescription { table_name, column_name, old_description: _, new_description } => {
+                    let target = match column_name {
+                        Some(column_name) => format!("COLUMN {}.{}", ids.name(table_name), ids.name(column_name)),
+                        None => format!("TABLE {}", ids.name(table_name)),
+                    };
+                    let value = new_description
+                        .as_deref()
+                        .map(|d| format!("'{}'", d.replace('\'', "''")))
+                        .unwrap_or_else(|| "NULL".to_string());
+                    writeln!(writer, "COMMENT ON {} IS {};", target, value)?;
+                    writeln!(writer)?;
+                }
+                SchemaChange::AddKey { table_name, key, position } => {
+                    write_add_key(writer, &ids, table_name, key, *position)?;
+                }
+                SchemaChange::DropKey { table_name, key, position } => {
+                    write_drop_key(writer, &ids, table_name, key, *position)?;
+                }
+                // A key is only recreated when its changed options show in its definition here.
+                SchemaChange::ModifyKey { table_name, old_key, new_key, position } => {
+                    if !same_key_sql(write_add_key, &ids, table_name, old_key, *position, new_key, *position)? {
+                        write_drop_key(writer, &ids, table_name, old_key, *position)?;
+                        write_add_key(writer, &ids, table_name, new_key, *position)?;
+                    }
+                }
+                // DuckDB cannot rename an index, so it is created again under its new name.
+                SchemaChange::RenameIndex { table_name, old_name, key, position } => {
+                    let (schema_name, _) = split_qualified_name(table_name);
+                    writeln!(writer, "DROP INDEX IF EXISTS {};", ids.generated(&qualified_name(schema_name, old_name)))?;
+                    write_add_key(writer, &ids, table_name, key, *position)?;
+                }
+                // DuckDB keeps check constraints and foreign keys with the table definition;
+                // neither can be added to or dropped from an existing table.
+                SchemaChange::AddConstraint { table_name, constraint } => {
+                    writeln!(
+                        writer,
+                        "-- DuckDB does not support adding constraint '{}' to table '{}' in-place.",
+                        constraint.name(),
+                        table_name
+                    )?;
+                    writeln!(writer, "-- Manually recreate the table with CHECK ({}).", constraint.sql())?;
+                    writeln!(writer)?;
+                }
+                SchemaChange::DropConstraint { table_name, constraint_name } => {
+                    writeln!(
+                        writer,
+                        "-- DuckDB does not support dropping constraint '{}' from table '{}' in-place.",
+                        constraint_name,
+                        table_name
+                    )?;
+                    writeln!(writer, "-- Manually recreate the table without the constraint.")?;
+                    writeln!(writer)?;
+                }
+                SchemaChange::RenameConstraint { table_name, old_name, new_name } => {
+                    writeln!(
+                        writer,
+                        "-- DuckDB does not support renaming constraint '{}' on table '{}' in-place.",
+                        old_name,
+                        table_name
+                    )?;
+                    writeln!(writer, "-- Manually recreate the table with the constraint named '{}'.", new_name)?;
+                    writeln!(writer)?;
+                }
+                SchemaChange::AddRelation { table_name, relation, position } => {
+                    write_add_relation(writer, &ids, table_name, relation, *position)?;
+                }
+                SchemaChange::DropRelation { table_name, relation, position } => {
+                    writeln!(
+                        writer,
+                        "-- DuckDB does not support dropping foreign key {} on '{}.{}'.",
+                        ids.generated(&ids.foreign_key_name(table_name, relation, *position)),
+                        table_name,
+                        relation.from_columns_as_string()
+                    )?;
+                    writeln!(writer, "-- Manually recreate the table without this foreign key.")?;
+                    writeln!(writer)?;
+                }
+                // DuckDB foreign keys have no ON DELETE actions, so there is nothing to change.
+                SchemaChange::ModifyRelation { .. } => {}
+                // As in the create script, a materialized view is a table built from the
+                // view's query; replacing it refreshes the rows.
+                SchemaChange::AddView { view } if view.is_materialized() => {
+                    let view_name = qualified_name(view.schema_name(), view.name());
+                    writeln!(writer, "CREATE OR REPLACE TABLE {} AS", ids.name(&view_name))?;
+                    writeln!(writer, "{};", view.sql())?;
+                    writeln!(writer)?;
+                    for (index, key) in view.indexes().iter().enumerate() {
+                        write_add_key(writer, &ids, &view_name, key, index + 1)?;
+                    }
+                }
+                SchemaChange::AddView { view } => {
+                    let view_name = qualified_name(view.schema_name(), view.name());
+                    writeln!(writer, "CREATE OR REPLACE VIEW {} AS", ids.name(&view_name))?;
+                    writeln!(writer, "{};", view.sql())?;
+                    writeln!(writer)?;
+                }
+                SchemaChange::DropView { view_name, materialized } => {
+                    let kind = if *materialized { "TABLE" } else { "VIEW" };
+                    writeln!(writer, "DROP {} IF EXISTS {};", kind, ids.name(view_name))?;
+                    writeln!(writer)?;
+                }
+                // Partitioning is only generated for PostgreSQL; the table here was created
+                // as a plain table and stays one.
+                SchemaChange::AddPartition { .. }
+                | SchemaChange::DropPartition { .. }
+                | SchemaChange::ModifyPartitioning { .. } => {}
+                // Guarded like the sequence behind an added auto-increment column.
+                SchemaChange::AddSequence { sequence } => {
+                    writeln!(
+                        writer,
+                        "CREATE SEQUENCE IF NOT EXISTS {}{};",
+                        ids.name(&qualified_name(sequence.schema_name(), sequence.name())),
+                        sequence.options_sql().to_uppercase()
+                    )?;
+                    writeln!(writer)?;
+                }
+                SchemaChange::DropSequence { sequence_name } => {
+                    writeln!(writer, "DROP SEQUENCE IF EXISTS {};", ids.name(sequence_name))?;
+                    writeln!(writer)?;
+                }
+                // DuckDB has no ALTER SEQUENCE, and recreating the sequence would restart it.
+                SchemaChange::ModifySequence { old_sequence: _, new_sequence } => {
+                    writeln!(
+                        writer,
+                        "-- Sequence '{}' changed; DuckDB can't alter a sequence, so recreate it and restore its current value by hand.",
+                        new_sequence.name()
+                    )?;
+                    writeln!(writer)?;
+                }
+                SchemaChange::AddEnumType { enum_type } => {
+                    writeln!(writer, "CREATE TYPE {} AS ENUM ({});", ids.name(enum_type.name()), enum_values_sql(enum_type))?;
+                    writeln!(writer)?;
+                }
+                SchemaChange::DropEnumType { enum_type_name } => {
+                    writeln!(writer, "DROP TYPE IF EXISTS {};", ids.name(enum_type_name))?;
+                    writeln!(writer)?;
+                }
+                // DuckDB can't alter an enum type, nor drop one a column still uses, so the
+                // columns are moved to varchar while the type is recreated. A row holding a
+                // removed value makes the cast back fail.
+                SchemaChange::ModifyEnumType { new_enum_type, columns, .. } => {
+                    let type_name = ids.name(new_enum_type.name());
+                    for (table_name, column) in columns {
+                        writeln!(writer, "ALTER TABLE {} ALTER COLUMN {} TYPE varchar;", ids.name(table_name), ids.name(column.name()))?;
+                    }
+                    writeln!(writer, "DROP TYPE {};", type_name)?;
+                    writeln!(writer, "CREATE TYPE {} AS ENUM ({});", type_name, enum_values_sql(new_enum_type))?;
+                    for (table_name, column) in columns {
+                        writeln!(
+                            writer,
+                            "ALTER TABLE {} ALTER COLUMN {} TYPE {};",
+                            ids.name(table_name),
+                            ids.name(column.name()),
+                            type_name
+                        )?;
+                    }
+                    writeln!(writer)?;
+                }
+                SchemaChange::AddFunction { function: program } | SchemaChange::ModifyFunction { new_function: program, .. }
+                    if program.database_type() == DatabaseType::DuckDb =>
+                {
+                    writeln!(writer, "{};", replacing_create(program.sql(), "CREATE OR REPLACE").trim_end_matches(';'))?;
+                    writeln!(writer)?;
+                }
+                // A DuckDB function is a macro.
+                SchemaChange::DropFunction { function } if function.database_type() == DatabaseType::DuckDb => {
+                    let function_name = qualified_name(function.schema_name(), function.name());
+                    writeln!(writer, "DROP MACRO IF EXISTS {};", ids.generated(&function_name))?;
+                    writeln!(writer)?;
+                }
+                SchemaChange::AddProcedure { procedure: program } | SchemaChange::ModifyProcedure { new_procedure: program, .. }
+                    if program.database_type() == DatabaseType::DuckDb =>
+                {
+                    return Err(MigrationGeneratorError::UnsupportedChange(format!(
+                        "procedure {}: DuckDB has no stored procedures",
+                        qualified_name(program.schema_name(), program.name())
+                    )));
+                }
+                SchemaChange::AddOtherSql { other_sql } if other_sql.database_type() == DatabaseType::DuckDb => {
+                    writeln!(writer, "{};", other_sql.sql().trim().trim_end_matches(';'))?;
+                    writeln!(writer)?;
+                }
+                SchemaChange::DropOtherSql { other_sql } if other_sql.database_type() == DatabaseType::DuckDb => {
+                    write_other_sql_removal(writer, DatabaseType::DuckDb, other_sql.sql(), ";")?;
+                }
+                // Written with the table's regenerated triggers below.
+                SchemaChange::AddTrigger { .. }
+                | SchemaChange::DropTrigger { .. }
+                | SchemaChange::AddAggregation { .. }
+                | SchemaChange::DropAggregation { .. } => {}
+                // Programs and SQL written for another database.
+                SchemaChange::AddFunction { .. }
+                | SchemaChange::DropFunction { .. }
+                | SchemaChange::ModifyFunction { .. }
+                | SchemaChange::AddProcedure { .. }
+                | SchemaChange::DropProcedure { .. }
+                | SchemaChange::ModifyProcedure { .. }
+                | SchemaChange::AddOtherSql { .. }
+                | SchemaChange::DropOtherSql { .. } => {}
+            }
+        }
+        for (table_name, triggers) in changed_table_triggers(&self.options, DatabaseType::DuckDb, change_set)? {
+            write_table_triggers(writer, &ids, &table_name, &triggers)?;
+        }
+        Ok(())
+    }
+}
+
+/// DuckDB has no triggers, so there is nothing to replace; a table that needs one fails to
+/// regenerate, as it fails in the create script.
+fn write_table_triggers(
+    _writer: &mut dyn Write,
+    _ids: &Identifiers,
+    _table_name: &str,
+    _triggers: &TableTriggers,
+) -> Result<(), MigrationGeneratorError> {
+    Ok(())
+}
+
+/// The sequence behind an auto-increment column, named as the create script names it.
+fn column_sequence_name(table_name: &str, column: &Column) -> String {
+    format!("{}_{}_seq", table_name, column.name())
+}
+
+/// The sequence a column draws its default from: its own for a sequence column, as the
+/// create script names it, or else the one it names.
+fn default_sequence_name(table_name: &str, column: &Column) -> Option<String> {
+    match column.column_type() {
+        ColumnType::Sequence | ColumnType::LongSequence => Some(column_sequence_name(table_name, column)),
+        _ => column.sequence().map(str::to_string),
+    }
+}
+
+/// Alters a column one changed attribute at a time. DuckDB keeps generated columns and
+/// check constraints with the table definition, so changes to either are left as notes
+/// for recreating the table. Identity options don't apply to its sequence columns.
+fn write_modify_column(
+    writer: &mut dyn Write,
+    ids: &Identifiers,
+    table_name: &str,
+    old_column: &Column,
+    new_column: &Column,
+    changes: &[ColumnAttribute],
+) -> Result<(), MigrationGeneratorError> {
+    let table = ids.name(table_name);
+    let column = ids.name(new_column.name());
+    if changes.contains(&ColumnAttribute::Generated) {
+        writeln!(
+            writer,
+            "-- DuckDB does not support altering generated column '{}' on table '{}' in-place.",
+            new_column.name(),
+            table_name
+        )?;
+        match new_column.generated() {
+            Some(expression) => writeln!(
+                writer,
+                "-- Manually recreate the table with {}{} GENERATED ALWAYS AS ({}).",
+                column,
+                column_type_sql(ids, new_column),
+                expression
+            )?,
+            None => writeln!(writer, "-- Manually recreate the table with {} as a plain column.", column)?,
+        }
+        writeln!(writer)?;
+        return Ok(());
+    }
+
+    let mut altered = false;
+    if changes.iter().any(|change| change.is_type()) {
+        let new_type = column_type_sql(ids, new_column);
+        if new_type != column_type_sql(ids, old_column) {
+            writeln!(writer, "ALTER TABLE {} ALTER COLUMN {} TYPE{};", table, column, new_type)?;
+            altered = true;
+        }
+    }
+    if changes.contains(&ColumnAttribute::Required) {
+        let action = if new_column.required() { "SET" } else { "DROP" };
+        writeln!(writer, "ALTER TABLE {} ALTER COLUMN {} {} NOT NULL;", table, column, action)?;
+        altered = true;
+    }
+    if changes.contains(&ColumnAttribute::Default) || changes.contains(&ColumnAttribute::Sequence) {
+        match column_default_sql(ids, new_column, default_sequence_name(table_name, new_column).as_deref()) {
+            Some(default) => writeln!(writer, "ALTER TABLE {} ALTER COLUMN {} SET DEFAULT {};", table, column, default)?,
+            None => writeln!(writer, "ALTER TABLE {} ALTER COLUMN {} DROP DEFAULT;", table, column)?,
+        }
+        altered = true;
+    }
+    if changes.iter().any(|change| change.is_check()) {
+        writeln!(
+            writer,
+            "-- DuckDB does not support altering the check constraint on '{}.{}'.",
+            table_name,
+            new_column.name()
+        )?;
+        match column_check_sql(ids, new_column, None) {
+            Some(check) => writeln!(writer, "-- Manually recreate the table with {}.", check)?,
+            None => writeln!(writer, "-- Manually recreate the table without it.")?,
+        }
+        altered = true;
+    }
+    if altered {
+        writeln!(writer)?;
+    }
+    Ok(())
+}
+
+/// The type the create script gives `column`: an enum column is of its enum type, and a
+/// list of its element type.
+fn column_type_sql(ids: &Identifiers, column: &Column) -> String {
+    match column.column_type() {
+        ColumnType::Sequence => " integer".to_string(),
+        ColumnType::LongSequence => " bigint".to_string(),
+        ColumnType::Byte => " tinyint".to_string(),
+        ColumnType::Short => " smallint".to_string(),
+        ColumnType::Int => " integer".to_string(),
+        ColumnType::Long => " bigint".to_string(),
+        ColumnType::Float => " real".to_string(),
+        ColumnType::Double => " double".to_string(),
+        ColumnType::Decimal => {
+            let l = column.length();
+            let s = column.scale();
+            if l == 0 && s == 0 {
+                " decimal".to_string()
+            } else {
+                format!(" decimal({}, {})", l, s)
+            }
+        }
+        ColumnType::Boolean => " boolean".to_string(),
+        ColumnType::Date => " date".to_string(),
+        ColumnType::DateTime => " timestamp".to_string(),
+        ColumnType::Time => " time".to_string(),
+        ColumnType::Timestamp => " timestamp".to_string(),
+        ColumnType::TimestampTz => " timestamptz".to_string(),
+        ColumnType::Char => format!(" varchar({})", column.length()),
+        ColumnType::Varchar => " varchar".to_string(),
+        ColumnType::Text => " varchar".to_string(),
+        ColumnType::CiText => " varchar collate nocase".to_string(),
+        ColumnType::CsText => " varchar".to_string(),
+        ColumnType::Enum => match column.enum_type() {
+            Some(enum_type) => format!(" {}", ids.name(enum_type)),
+            None => " varchar".to_string(),
+        },
+        ColumnType::Binary => " blob".to_string(),
+        ColumnType::Uuid => " uuid".to_string(),
+        ColumnType::Json => " json".to_string(),
+        ColumnType::Array => match element_column(column) {
+            Some(element) => format!("{}[]", column_type_sql(ids, &element)),
+            None => " varchar[]".to_string(),
+        },
+    }
+}
+
+fn write_add_key(
+    writer: &mut dyn Write,
+    ids: &Identifiers,
+    table_name: &str,
+    key: &Key,
+    position: usize,
+) -> Result<(), MigrationGeneratorError> {
+    match key.key_type() {
+        KeyType::Primary => {
+            let col_names: Vec<&str> = key.columns().iter().map(|c| c.name()).collect();
+            writeln!(writer, "ALTER TABLE {} ADD PRIMARY KEY ({});", ids.name(table_name), ids.list(&col_names))?;
+        }
+        // DuckDB has a single index implementation, so an index method is not rendered.
+        KeyType::Unique | KeyType::Index => {
+            if let Some(filter) = key.filter() {
+                return Err(MigrationGeneratorError::UnsupportedChange(format!(
+                    "DuckDB has no partial indexes, so the index on {} can't be filtered by '{}'",
+                    table_name, filter
+                )));
+            }
+            let unique = if key.key_type() == KeyType::Unique || key.is_unique() { "UNIQUE " } else { "" };
+            writeln!(
+                writer,
+                "CREATE {}INDEX {} ON {} ({});",
+                unique,
+                ids.generated(&ids.key_name(table_name, key, position)),
+                ids.name(table_name),
+                index_columns_sql(ids, key, false)
+            )?;
+        }
+    }
+    writeln!(writer)?;
+    Ok(())
+}
+
+fn write_drop_key(
+    writer: &mut dyn Write,
+    ids: &Identifiers,
+    table_name: &str,
+    key: &Key,
+    position: usize,
+) -> Result<(), MigrationGeneratorError> {
+    match key.key_type() {
+        KeyType::Primary => {
+            writeln!(
+                writer,
+                "-- DuckDB does not support dropping a PRIMARY KEY constraint in-place on table '{}'.",
+                table_name
+            )?;
+            writeln!(writer, "-- Manually recreate the table without the primary key.")?;
+        }
+        // The index is found through its table's schema, since DROP INDEX names no table.
+        KeyType::Unique | KeyType::Index => {
+            let (schema_name, _) = split_qualified_name(table_name);
+            let key_name = ids.key_name(table_name, key, position);
+            if key.key_type() == KeyType::Unique {
+                writeln!(
+                    writer,
+                    "-- A unique constraint {} declared in the CREATE TABLE statement for '{}' can only be dropped by recreating the table.",
+                    key_name, table_name
+                )?;
+            }
+            writeln!(writer, "DROP INDEX IF EXISTS {};", ids.generated(&qualified_name(schema_name, &key_name)))?;
+        }
+    }
+    writeln!(writer)?;
+    Ok(())
+}
+
+/// DuckDB foreign keys have no ON DELETE actions, so only the reference itself is noted.
+fn write_add_relation(
+    writer: &mut dyn Write,
+    ids: &Identifiers,
+    table_name: &str,
+    relation: &Relation,
+    position: usize,
+) -> Result<(), MigrationGeneratorError> {
+    writeln!(writer, "-- DuckDB foreign keys must be declared at table creation time.")?;
+    writeln!(
+        writer,
+        "-- Ensure CONSTRAINT {} FOREIGN KEY ({}) REFERENCES {}({}) is in the CREATE TABLE statement for '{}'.",
+        ids.generated(&ids.foreign_key_name(table_name, relation, position)),
+        ids.list(&relation.from_column_names()),
+        ids.name(relation.to_table_name()),
+        ids.list(&relation.to_column_names()),
+        table_name
+    )?;
+    writeln!(writer)?;
+    Ok(())
+}
@@ -1,3 +1,4 @@
+use schema_sql_generator::common::generation_error::GenerationError;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -6,4 +7,6 @@ pub enum MigrationGeneratorError {
     Io(#[from] std::io::Error),
     #[error("Unsupported change for database: {0}")]
     UnsupportedChange(String),
+    #[error("Generation error: {0}")]
+    Generation(#[from] GenerationError),
 }
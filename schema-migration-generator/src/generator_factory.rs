@@ -1,14 +1,52 @@
-use schema_model::model::types::DatabaseType;
+use schema_model::model::constraint_naming::ConstraintNaming;
+use schema_model::model::naming::NamingConvention;
+use schema_model::model::types::{DatabaseType, IdentifierQuoting};
+use schema_sql_generator::common::generate_options::GenerateOptions;
 
-use crate::migration_generator::MigrationGenerator;
+use crate::duckdb::DuckDbMigrationGenerator;
+use crate::migration_generator::{options_without_model, MigrationGenerator};
+use crate::mysql::MySqlMigrationGenerator;
 use crate::postgresql::PostgresqlMigrationGenerator;
 use crate::sqlite::SqliteMigrationGenerator;
 use crate::sqlserver::SqlServerMigrationGenerator;
 
 pub fn create_generator(db_type: DatabaseType) -> Box<dyn MigrationGenerator> {
+    create_generator_with_quoting(db_type, IdentifierQuoting::default())
+}
+
+/// A generator for `db_type` that quotes identifiers per `quoting`.
+pub fn create_generator_with_quoting(db_type: DatabaseType, quoting: IdentifierQuoting) -> Box<dyn MigrationGenerator> {
+    create_generator_with_naming(db_type, quoting, NamingConvention::default(), ConstraintNaming::default())
+}
+
+/// A generator for `db_type` that quotes identifiers per `quoting`, spells model names per
+/// `naming_convention` and names keys and foreign keys with the templates, all as the
+/// create script was generated with. Without the new model it can't regenerate the
+/// triggers of a table whose custom trigger code or aggregations changed.
+pub fn create_generator_with_naming(
+    db_type: DatabaseType,
+    quoting: IdentifierQuoting,
+    naming_convention: NamingConvention,
+    constraint_naming: ConstraintNaming,
+) -> Box<dyn MigrationGenerator> {
+    let options = GenerateOptions {
+        identifier_quoting: quoting,
+        naming_convention,
+        constraint_naming,
+        ..options_without_model()
+    };
+    create_generator_with_options(db_type, options)
+}
+
+/// A generator for `db_type` that writes names as the create script generated with
+/// `options` does, and regenerates changed triggers from `options.database_model`, which
+/// must be the new model the changes were diffed to.
+pub fn create_generator_with_options(db_type: DatabaseType, options: GenerateOptions) -> Box<dyn MigrationGenerator> {
     match db_type {
-        DatabaseType::Postgresql => Box::new(PostgresqlMigrationGenerator),
-        DatabaseType::Sqlite => Box::new(SqliteMigrationGenerator),
-        DatabaseType::SqlServer => Box::new(SqlServerMigrationGenerator),
+        DatabaseType::Postgresql => Box::new(PostgresqlMigrationGenerator::new(options)),
+        DatabaseType::Sqlite => Box::new(SqliteMigrationGenerator::new(options)),
+        DatabaseType::SqlServer => Box::new(SqlServerMigrationGenerator::new(options)),
+        DatabaseType::MySql => Box::new(MySqlMigrationGenerator::new(options)),
+        DatabaseType::DuckDb => Box::new(DuckDbMigrationGenerator::new(options)),
     }
 }
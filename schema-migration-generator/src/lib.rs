@@ -1,12 +1,16 @@
+pub mod duckdb;
 pub mod error;
 pub mod migration_generator;
 pub mod generator_factory;
+pub mod mysql;
 pub mod postgresql;
 pub mod sqlserver;
 pub mod sqlite;
 
 pub use error::MigrationGeneratorError;
-pub use generator_factory::create_generator;
+pub use generator_factory::{
+    create_generator, create_generator_with_naming, create_generator_with_options, create_generator_with_quoting,
+};
 pub use migration_generator::MigrationGenerator;
 
 #[cfg(test)]
@@ -1,9 +1,545 @@
 use std::io::Write;
+use std::sync::Arc;
 
-use schema_diff::ChangeSet;
+use schema_diff::{ChangeSet, ColumnAttribute, SchemaChange};
+use schema_model::builder::column::ColumnBuilder;
+use schema_model::model::column::Column;
+use schema_model::model::column_type::ColumnType;
+use schema_model::model::constraint_naming::ConstraintNamer;
+use schema_model::model::database_model::DatabaseModel;
+use schema_model::model::enum_type::EnumType;
+use schema_model::model::key::Key;
+use schema_model::model::naming::NamingConvention;
+use schema_model::model::relation::Relation;
+use schema_model::model::sequence::Sequence;
+use schema_model::model::table::Table;
+use schema_model::model::types::{BooleanMode, DatabaseType, ForeignKeyMode, IdentifierQuoting};
+use schema_sql_generator::common::generate_options::GenerateOptions;
+use schema_sql_generator::common::generator_type::GeneratorType;
+use schema_sql_generator::common::sql_split::{skip_leading_comments, split_sql_statements};
+use schema_sql_generator::common::trigger_generator::TableTriggers;
 
 use crate::error::MigrationGeneratorError;
 
 pub trait MigrationGenerator {
     fn generate(&self, change_set: &ChangeSet, writer: &mut dyn Write) -> Result<(), MigrationGeneratorError>;
 }
+
+/// Quotes the names a migration writes, per the dialect and the quoting policy, spells
+/// model names as the naming convention does, and names keys and foreign keys as the
+/// create script does. Changes carry names as the model spells them, possibly
+/// schema-qualified (`sales.order`), so each dotted part is quoted on its own.
+#[derive(Debug, Clone)]
+pub(crate) struct Identifiers {
+    database_type: DatabaseType,
+    quoting: IdentifierQuoting,
+    naming_convention: NamingConvention,
+    names: ConstraintNamer,
+}
+
+impl Identifiers {
+    pub(crate) fn new(database_type: DatabaseType, options: &GenerateOptions) -> Self {
+        let names = ConstraintNamer::new(options.constraint_naming.clone(), options.naming_convention.clone(), database_type);
+        Self {
+            database_type,
+            quoting: options.identifier_quoting,
+            naming_convention: options.naming_convention.clone(),
+            names,
+        }
+    }
+
+    /// The database spelling of the model name `name`, unquoted. As in the create script,
+    /// the schema qualifying a name is not renamed by the naming convention.
+    pub(crate) fn database_name(&self, name: &str) -> String {
+        let (schema_name, name) = split_qualified_name(name);
+        qualified_name(schema_name, &self.naming_convention.to_database(name))
+    }
+
+    /// The model name `name` in its database spelling, quoted.
+    pub(crate) fn name(&self, name: &str) -> String {
+        self.generated(&self.database_name(name))
+    }
+
+    /// A name written as it is in the database, quoted: a schema, function or procedure
+    /// name, which the naming convention leaves alone, or a name built from database
+    /// spellings already, such as a key name.
+    pub(crate) fn generated(&self, name: &str) -> String {
+        name.split('.')
+            .map(|part| self.database_type.quote_identifier(part, self.quoting))
+            .collect::<Vec<_>>()
+            .join(".")
+    }
+
+    /// Each of `names` quoted and joined with commas, as the create script writes a key's
+    /// or foreign key's columns.
+    pub(crate) fn list(&self, names: &[&str]) -> String {
+        names.iter().map(|name| self.name(name)).collect::<Vec<_>>().join(",")
+    }
+
+    /// The name the create script gives `key`, the `position`th key of its type on the
+    /// table or materialized view `table_name`. The key lives in its table's schema, so
+    /// only the table's own name is used.
+    pub(crate) fn key_name(&self, table_name: &str, key: &Key, position: usize) -> String {
+        let (_, table_name) = split_qualified_name(table_name);
+        self.names.key_name(table_name, key, position)
+    }
+
+    /// The name the create script gives the check constraint on the column `column_name`
+    /// of the table `table_name`.
+    pub(crate) fn check_name(&self, table_name: &str, column_name: &str) -> String {
+        let (_, table_name) = split_qualified_name(table_name);
+        self.names.constraint_name(&self.names.naming().check, table_name, &[column_name], None)
+    }
+
+    /// The name the create script gives the foreign key behind `relation`, the
+    /// `position`th relation of the table `table_name`.
+    pub(crate) fn foreign_key_name(&self, table_name: &str, relation: &Relation, position: usize) -> String {
+        let (_, table_name) = split_qualified_name(table_name);
+        self.names.foreign_key_name(table_name, relation, position)
+    }
+}
+
+/// Splits a possibly schema-qualified name (`sales.order`) into its schema and its own name.
+pub(crate) fn split_qualified_name(name: &str) -> (Option<&str>, &str) {
+    match name.rsplit_once('.') {
+        Some((schema_name, name)) => (Some(schema_name), name),
+        None => (None, name),
+    }
+}
+
+/// `name` in `schema_name`, or left bare when it lives in the default schema.
+pub(crate) fn qualified_name(schema_name: Option<&str>, name: &str) -> String {
+    match schema_name {
+        Some(schema_name) => format!("{}.{}", schema_name, name),
+        None => name.to_string(),
+    }
+}
+
+/// The parenthesised column list of an index, with each entry's ordering. Expressions are
+/// wrapped in their own parentheses, as Postgres requires; `nulls_order_supported` is false
+/// for dialects without `NULLS FIRST/LAST`.
+pub(crate) fn index_columns_sql(identifiers: &Identifiers, key: &Key, nulls_order_supported: bool) -> String {
+    key.columns()
+        .iter()
+        .map(|c| {
+            let mut sql = if c.is_expression() {
+                format!("({})", c.name())
+            } else {
+                identifiers.name(c.name())
+            };
+            if let Some(sort_order) = c.sort_order() {
+                sql.push(' ');
+                sql.push_str(&sort_order.sql().to_uppercase());
+            }
+            if let Some(nulls_order) = c.nulls_order()
+                && nulls_order_supported
+            {
+                sql.push(' ');
+                sql.push_str(&nulls_order.sql().to_uppercase());
+            }
+            sql
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// The columns a key's `include` lists, which may be separated by commas or whitespace.
+pub(crate) fn include_columns(key: &Key) -> Vec<&str> {
+    key.include()
+        .map(|include| include.split(|ch: char| ch == ',' || ch.is_whitespace()).filter(|name| !name.is_empty()).collect())
+        .unwrap_or_default()
+}
+
+/// The signature of a dialect's `write_add_key`, which is given the key's position.
+pub(crate) type WriteAddKey = fn(&mut dyn Write, &Identifiers, &str, &Key, usize) -> Result<(), MigrationGeneratorError>;
+
+/// Whether `write_add_key` writes the same SQL for `old` and `new` at their positions, that
+/// is, whether the dialect creates both keys alike and under the same name, so that a
+/// change between them needs no rebuild.
+pub(crate) fn same_key_sql(
+    write_add_key: WriteAddKey,
+    identifiers: &Identifiers,
+    table_name: &str,
+    old: &Key,
+    old_position: usize,
+    new: &Key,
+    new_position: usize,
+) -> Result<bool, MigrationGeneratorError> {
+    let mut old_sql = Vec::new();
+    let mut new_sql = Vec::new();
+    write_add_key(&mut old_sql, identifiers, table_name, old, old_position)?;
+    write_add_key(&mut new_sql, identifiers, table_name, new, new_position)?;
+    Ok(old_sql == new_sql)
+}
+
+/// The `ALTER SEQUENCE` clauses moving `old`'s increment, bounds, cache and cycle settings
+/// to `new`'s. An option that is no longer set goes back to the database default, with
+/// `default_cache` as the clause that restores the default cache. The start value is
+/// left to the caller, since dialects differ on whether it can be altered.
+pub(crate) fn alter_sequence_clauses(old: &Sequence, new: &Sequence, default_cache: &str) -> Vec<String> {
+    let mut clauses = Vec::new();
+    if old.increment() != new.increment() {
+        clauses.push(format!("INCREMENT BY {}", new.increment().unwrap_or(1)));
+    }
+    if old.min_value() != new.min_value() {
+        clauses.push(match new.min_value() {
+            Some(min_value) => format!("MINVALUE {}", min_value),
+            None => "NO MINVALUE".to_string(),
+        });
+    }
+    if old.max_value() != new.max_value() {
+        clauses.push(match new.max_value() {
+            Some(max_value) => format!("MAXVALUE {}", max_value),
+            None => "NO MAXVALUE".to_string(),
+        });
+    }
+    if old.cache() != new.cache() {
+        clauses.push(match new.cache() {
+            Some(cache) => format!("CACHE {}", cache),
+            None => default_cache.to_string(),
+        });
+    }
+    if old.is_cycle() != new.is_cycle() {
+        clauses.push(if new.is_cycle() { "CYCLE" } else { "NO CYCLE" }.to_string());
+    }
+    clauses
+}
+
+/// `sql` with its leading `CREATE` turned into `create_or` (`CREATE OR REPLACE`, `CREATE OR
+/// ALTER`), so that running it over an existing function or procedure replaces it. SQL that
+/// already replaces, or doesn't start with `CREATE`, is returned as it is.
+pub(crate) fn replacing_create(sql: &str, create_or: &str) -> String {
+    let sql = sql.trim();
+    let replaces = sql.split_whitespace().nth(1).is_some_and(|word| word.eq_ignore_ascii_case("or"));
+    if starts_with_create(sql) && !replaces {
+        format!("{}{}", create_or, &sql["create".len()..])
+    } else {
+        sql.to_string()
+    }
+}
+
+/// Whether the statement `sql` creates something, as opposed to dropping it first.
+pub(crate) fn starts_with_create(sql: &str) -> bool {
+    sql.split_whitespace().next().is_some_and(|word| word.eq_ignore_ascii_case("create"))
+}
+
+/// The enum's codes as a list of SQL string literals.
+pub(crate) fn enum_values_sql(enum_type: &EnumType) -> String {
+    enum_type
+        .values()
+        .iter()
+        .map(|value| format!("'{}'", value.code().replace('\'', "''")))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// The check constraint the create script puts on `column`, as `CHECK (...)`: its own
+/// check, else the codes of `enum_type` (passed by dialects whose enum columns are
+/// checked text), else its min/max range. A check written without the `CHECK` keyword
+/// gets it added.
+pub(crate) fn column_check_sql(identifiers: &Identifiers, column: &Column, enum_type: Option<&EnumType>) -> Option<String> {
+    let column_name = identifiers.name(column.name());
+    if let Some(check) = column.check_constraint() {
+        let check = check.trim();
+        let keyword = check.get(..5).is_some_and(|start| start.eq_ignore_ascii_case("check"));
+        return Some(if keyword { check.to_string() } else { format!("CHECK ({})", check) });
+    }
+    if let Some(enum_type) = enum_type.filter(|_| column.column_type() == ColumnType::Enum) {
+        return Some(format!("CHECK ({} IN ({}))", column_name, enum_values_sql(enum_type)));
+    }
+    let bounds: Vec<String> = [(">=", column.min_value()), ("<=", column.max_value())]
+        .into_iter()
+        .filter_map(|(operator, bound)| bound.map(|bound| format!("{} {} {}", column_name, operator, bound)))
+        .collect();
+    (!bounds.is_empty()).then(|| format!("CHECK ({})", bounds.join(" AND ")))
+}
+
+/// A column of `column`'s array element type, so that a dialect can spell the array's
+/// type from its element's; `None` when the element type is missing or not a model type.
+pub(crate) fn element_column(column: &Column) -> Option<Column> {
+    let element_type = ColumnType::from_type_name(column.element_type()?).ok()?;
+    Some(ColumnBuilder::new(None, column.name(), element_type).length(column.length()).scale(column.scale()).build())
+}
+
+/// The changed attributes as a list for a comment: `type, min value`.
+pub(crate) fn attributes_list(changes: &[ColumnAttribute]) -> String {
+    changes.iter().map(ToString::to_string).collect::<Vec<_>>().join(", ")
+}
+
+/// The column's declared default, or else the next value of the sequence `sequence_name`
+/// it draws from, in the dialect's syntax.
+pub(crate) fn column_default_sql(ids: &Identifiers, column: &Column, sequence_name: Option<&str>) -> Option<String> {
+    column.default_constraint().map(str::to_string).or_else(|| {
+        sequence_name.map(|sequence_name| match ids.database_type {
+            DatabaseType::SqlServer => format!("NEXT VALUE FOR {}", ids.name(sequence_name)),
+            DatabaseType::MySql => format!("nextval({})", ids.name(sequence_name)),
+            _ => format!("nextval('{}')", ids.name(sequence_name)),
+        })
+    })
+}
+
+/// Notes above a dropped column that the diff paired with `rename_candidates`, new columns
+/// that may be the same one renamed, how to keep its data instead: each candidate's
+/// statement from `rename_sql` is what the migration runs once the new column names the
+/// dropped one as its `previousName`.
+pub(crate) fn write_rename_candidates(
+    writer: &mut dyn Write,
+    column_name: &str,
+    rename_candidates: &[String],
+    rename_sql: impl Fn(&str) -> String,
+) -> Result<(), MigrationGeneratorError> {
+    if rename_candidates.is_empty() {
+        return Ok(());
+    }
+    writeln!(
+        writer,
+        "-- {} is dropped with its data. If a new column replaces it, give that column previousName=\"{}\" to rename it instead:",
+        column_name, column_name
+    )?;
+    for candidate in rename_candidates {
+        writeln!(writer, "--   {}", rename_sql(candidate))?;
+    }
+    Ok(())
+}
+
+/// Writes `sql` as comment lines under `header`, for SQL the migration can't run itself.
+pub(crate) fn write_commented(writer: &mut dyn Write, header: &str, sql: &str) -> Result<(), MigrationGeneratorError> {
+    writeln!(writer, "-- {}", header)?;
+    for line in sql.trim().lines() {
+        writeln!(writer, "--   {}", line)?;
+    }
+    writeln!(writer)?;
+    Ok(())
+}
+
+/// Undoes the other SQL `sql`, which was removed from the model: each object one of its
+/// statements creates is dropped, last created first, with `terminator` after each drop.
+/// Statements that create nothing, such as grants or inserts, can't be undone from their
+/// text, so they are left as comments for the migration's author.
+pub(crate) fn write_other_sql_removal(
+    writer: &mut dyn Write,
+    database_type: DatabaseType,
+    sql: &str,
+    terminator: &str,
+) -> Result<(), MigrationGeneratorError> {
+    let mut drops = Vec::new();
+    let mut kept = Vec::new();
+    for statement in split_sql_statements(sql, database_type) {
+        match created_object(skip_leading_comments(&statement)).and_then(|object| object.drop_sql(database_type)) {
+            Some(drop_sql) => drops.push(drop_sql),
+            None => kept.push(statement),
+        }
+    }
+    for drop_sql in drops.iter().rev() {
+        writeln!(writer, "{}{}", drop_sql, terminator)?;
+        writeln!(writer)?;
+    }
+    if !kept.is_empty() {
+        write_commented(writer, "This SQL was removed from the model; undo what it did by hand:", &kept.join(";\n"))?;
+    }
+    Ok(())
+}
+
+/// An object a `CREATE` statement makes, with the table an index or trigger is on.
+struct CreatedObject<'a> {
+    kind: String,
+    name: &'a str,
+    table_name: Option<&'a str>,
+}
+
+impl CreatedObject<'_> {
+    /// The statement dropping the object, if the dialect can drop it from what the `CREATE`
+    /// statement says about it.
+    fn drop_sql(&self, database_type: DatabaseType) -> Option<String> {
+        let on_table = |table_name: Option<&str>| table_name.map(|table_name| format!(" ON {}", table_name));
+        let sql = match self.kind.as_str() {
+            "index" => match database_type {
+                DatabaseType::MySql => format!("DROP INDEX {}{}", self.name, on_table(self.table_name)?),
+                DatabaseType::SqlServer => format!("DROP INDEX IF EXISTS {}{}", self.name, on_table(self.table_name)?),
+                _ => format!("DROP INDEX IF EXISTS {}", self.name),
+            },
+            "trigger" if database_type == DatabaseType::Postgresql => {
+                format!("DROP TRIGGER IF EXISTS {}{}", self.name, on_table(self.table_name)?)
+            }
+            "table" | "view" | "materialized view" | "sequence" | "function" | "procedure" | "trigger" | "type" => {
+                format!("DROP {} IF EXISTS {}", self.kind.to_uppercase(), self.name)
+            }
+            _ => return None,
+        };
+        Some(sql)
+    }
+}
+
+/// What the statement `sql` creates, or `None` when it isn't a `CREATE` statement.
+fn created_object(sql: &str) -> Option<CreatedObject<'_>> {
+    let mut words = sql.split_whitespace().peekable();
+    if !words.next()?.eq_ignore_ascii_case("create") {
+        return None;
+    }
+    let mut kind = loop {
+        let word = words.next()?.to_lowercase();
+        match word.as_str() {
+            // `or replace`, `or alter`
+            "or" => {
+                words.next()?;
+            }
+            "unique" | "temp" | "temporary" | "clustered" | "nonclustered" => {}
+            _ if word.starts_with("definer=") => {}
+            _ => break word,
+        }
+    };
+    if kind == "materialized" {
+        kind = format!("{} {}", kind, words.next()?.to_lowercase());
+    }
+    if words.peek().is_some_and(|word| word.eq_ignore_ascii_case("if")) {
+        // `if not exists`
+        words.nth(2)?;
+    }
+    let name = name_part(words.next()?);
+    let table_name = words.skip_while(|word| !word.eq_ignore_ascii_case("on")).nth(1).map(name_part);
+    (!name.is_empty()).then_some(CreatedObject { kind, name, table_name })
+}
+
+/// The name at the start of `word`, without the argument or column list that may follow it.
+fn name_part(word: &str) -> &str {
+    word.split('(').next().unwrap_or_default().trim_end_matches(';')
+}
+
+/// The options of a generator given no model: names are written as the create script's
+/// defaults write them, and there is no model to regenerate triggers from.
+pub(crate) fn options_without_model() -> GenerateOptions {
+    GenerateOptions::new(Arc::new(DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, Vec::new())))
+}
+
+/// The triggers the create script now gives each table whose generated triggers
+/// `change_set` changes, by the name the table has once the migration has run. Custom
+/// trigger code and aggregations run inside a table's generated delete and update triggers,
+/// so a change to either rewrites them from `options.database_model`, the new model; an
+/// added table gets the triggers it needs too. Generated trigger bodies also spell out the
+/// tables and columns they touch, so a renamed table or column rewrites the triggers of its
+/// table and of every table whose triggers refer to it, and a renamed table's triggers
+/// under its old name are dropped.
+pub(crate) fn changed_table_triggers(
+    options: &GenerateOptions,
+    database_type: DatabaseType,
+    change_set: &ChangeSet,
+) -> Result<Vec<(String, TableTriggers)>, MigrationGeneratorError> {
+    let generator_type = generator_type(database_type);
+    let model = &options.database_model;
+    // Tables whose triggers changed must be in the model; the others are written only
+    // when they have triggers, which a model-less generator can't tell.
+    let mut changed_tables: Vec<String> = Vec::new();
+    let mut rewritten_tables: Vec<String> = Vec::new();
+    let mut renamed_tables: Vec<(String, &str)> = Vec::new();
+    for change in change_set.changes() {
+        match change {
+            SchemaChange::AddTrigger { table_name, trigger } | SchemaChange::DropTrigger { table_name, trigger }
+                if trigger.database_type() == database_type =>
+            {
+                changed_tables.push(table_name_after(change_set, table_name));
+            }
+            SchemaChange::AddAggregation { table_name, .. } | SchemaChange::DropAggregation { table_name, .. } => {
+                changed_tables.push(table_name_after(change_set, table_name));
+            }
+            SchemaChange::AddTable { table_name } => rewritten_tables.push(table_name_after(change_set, table_name)),
+            SchemaChange::RenameTable { old_name, new_name } => {
+                let table_name = table_name_after(change_set, new_name);
+                rewritten_tables.extend(tables_with_triggers_on(model, options.foreign_key_mode, &table_name));
+                renamed_tables.push((table_name, old_name));
+            }
+            SchemaChange::RenameColumn { table_name, .. } => {
+                let table_name = table_name_after(change_set, table_name);
+                rewritten_tables.extend(tables_with_triggers_on(model, options.foreign_key_mode, &table_name));
+            }
+            _ => {}
+        }
+    }
+
+    let mut table_triggers: Vec<(String, TableTriggers)> = Vec::new();
+    let tables = changed_tables.iter().map(|name| (name, true)).chain(rewritten_tables.iter().map(|name| (name, false)));
+    for (table_name, changed) in tables {
+        if table_triggers.iter().any(|(name, _)| name == table_name) {
+            continue;
+        }
+        if model.find_table_by_qualified_name_checked(table_name).is_none() {
+            if !changed {
+                continue;
+            }
+            return Err(MigrationGeneratorError::UnsupportedChange(format!(
+                "the triggers of {} are regenerated from the new model, which has no such table; create the generator with create_generator_with_options",
+                table_name
+            )));
+        }
+
+        let mut triggers = generator_type.generate_table_triggers(options, table_name)?;
+        if !changed {
+            if triggers.statements.is_empty() {
+                continue;
+            }
+            triggers.unneeded_names.clear();
+        }
+        for (_, old_name) in renamed_tables.iter().filter(|(name, _)| name == table_name) {
+            let (_, old_name) = split_qualified_name(old_name);
+            for name in generator_type.possible_trigger_names(options, old_name) {
+                if !triggers.unneeded_names.contains(&name) {
+                    triggers.unneeded_names.push(name);
+                }
+            }
+        }
+        table_triggers.push((table_name.clone(), triggers));
+    }
+    Ok(table_triggers)
+}
+
+/// `table_name` and every table whose generated triggers name it or its columns: the
+/// sources of aggregations into it, tables with aggregations reading through a relation
+/// to it, and, when foreign keys are kept by triggers, the tables it relates to either way.
+fn tables_with_triggers_on(model: &DatabaseModel, foreign_key_mode: ForeignKeyMode, table_name: &str) -> Vec<String> {
+    let Some(table) = model.find_table_by_qualified_name_checked(table_name) else {
+        return Vec::new();
+    };
+    let mut tables = vec![table_name.to_string()];
+    for other in model.all_tables().into_iter().filter(|other| !std::ptr::eq(*other, table)) {
+        let relates_to_table = other.relations().iter().any(|relation| is_table_named(model, relation.to_table_name(), table));
+        let aggregates_into_table =
+            other.aggregations().iter().any(|aggregation| is_table_named(model, aggregation.destination_table(), table));
+        let reads_from_table = relates_to_table && !other.aggregations().is_empty();
+        let related_by_triggers = foreign_key_mode == ForeignKeyMode::Triggers
+            && (relates_to_table || table.relations().iter().any(|relation| is_table_named(model, relation.to_table_name(), other)));
+        if aggregates_into_table || reads_from_table || related_by_triggers {
+            tables.push(qualified_name(other.schema_name(), other.name()));
+        }
+    }
+    tables
+}
+
+/// Whether the possibly schema-qualified `name` refers to `table`.
+fn is_table_named(model: &DatabaseModel, name: &str, table: &Table) -> bool {
+    model.find_table_by_qualified_name_checked(name).is_some_and(|found| std::ptr::eq(found, table))
+}
+
+/// The name the table `table_name` has once the renames and moves in `change_set` have run.
+fn table_name_after(change_set: &ChangeSet, table_name: &str) -> String {
+    let mut table_name = table_name.to_string();
+    for change in change_set.changes() {
+        match change {
+            SchemaChange::RenameTable { old_name, new_name } if *old_name == table_name => table_name = new_name.clone(),
+            SchemaChange::MoveTable { table_name: moved_name, new_schema_name } if *moved_name == table_name => {
+                let (_, name) = split_qualified_name(moved_name);
+                table_name = qualified_name(new_schema_name.as_deref(), name);
+            }
+            _ => {}
+        }
+    }
+    table_name
+}
+
+fn generator_type(database_type: DatabaseType) -> GeneratorType {
+    match database_type {
+        DatabaseType::Postgresql => GeneratorType::Postgresql,
+        DatabaseType::Sqlite => GeneratorType::Sqlite,
+        DatabaseType::SqlServer => GeneratorType::SqlServer,
+        DatabaseType::MySql => GeneratorType::MySql,
+        DatabaseType::DuckDb => GeneratorType::DuckDb,
+    }
+}
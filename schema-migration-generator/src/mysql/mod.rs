@@ -0,0 +1,672 @@
+use std::io::Write;
+
+use schema_diff::{ChangeSet, ColumnAttribute, SchemaChange};
+use schema_model::model::column::Column;
+use schema_model::model::column_type::ColumnType;
+use schema_model::model::enum_type::EnumType;
+use schema_model::model::key::Key;
+use schema_model::model::relation::Relation;
+use schema_model::model::types::{DatabaseType, IndexMethod, KeyType, RelationType};
+use schema_sql_generator::common::generate_options::GenerateOptions;
+use schema_sql_generator::common::trigger_generator::TableTriggers;
+
+use crate::error::MigrationGeneratorError;
+use crate::migration_generator::{
+    alter_sequence_clauses, changed_table_triggers, column_check_sql, column_default_sql, index_columns_sql,
+    options_without_model, qualified_name, same_key_sql, split_qualified_name, starts_with_create, write_other_sql_removal,
+    write_rename_candidates, Identifiers, MigrationGenerator,
+};
+
+pub struct MySqlMigrationGenerator {
+    options: GenerateOptions,
+}
+
+impl MySqlMigrationGenerator {
+    pub fn new(options: GenerateOptions) -> Self {
+        Self { options }
+    }
+}
+
+impl Default for MySqlMigrationGenerator {
+    fn default() -> Self {
+        Self::new(options_without_model())
+    }
+}
+
+impl MigrationGenerator for MySqlMigrationGenerator {
+    fn generate(&self, change_set: &ChangeSet, writer: &mut dyn Write) -> Result<(), MigrationGeneratorError> {
+        let ids = Identifiers::new(DatabaseType::MySql, &self.options);
+        for change in change_set.changes() {
+            if !self.options.target_mariadb {
+                reject_sequence_use(change)?;
+            }
+            match change {
+                // A MySQL schema is a database.
+                SchemaChange::AddSchema { schema_name } => {
+                    writeln!(writer, "CREATE DATABASE IF NOT EXISTS {};", ids.generated(schema_name))?;
+                    writeln!(writer)?;
+                }
+                SchemaChange::DropSchema { schema_name } => {
+                    writeln!(writer, "DROP DATABASE IF EXISTS {};", ids.generated(schema_name))?;
+                    writeln!(writer)?;
+                }
+                // Without a schema the table moves into the database connected to.
+                SchemaChange::MoveTable { table_name, new_schema_name } => {
+                    let (_, bare_name) = split_qualified_name(table_name);
+                    writeln!(
+                        writer,
+                        "RENAME TABLE {} TO {};",
+                        ids.name(table_name),
+                        ids.name(&qualified_name(new_schema_name.as_deref(), bare_name))
+                    )?;
+                    writeln!(writer)?;
+                }
+                // MySQL can't create a table without columns, so the new table starts with
+                // the same placeholder key the SQLite migration uses.
+                SchemaChange::AddTable { table_name } => {
+                    writeln!(
+                        writer,
+                        "CREATE TABLE IF NOT EXISTS {} (id BIGINT AUTO_INCREMENT PRIMARY KEY) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4;",
+                        ids.name(table_name)
+                    )?;
+                    writeln!(writer)?;
+                }
+                SchemaChange::DropTable { table_name } => {
+                    writeln!(writer, "DROP TABLE IF EXISTS {};", ids.name(table_name))?;
+                    writeln!(writer)?;
+                }
+                SchemaChange::RenameTable { old_name, new_name } => {
+                    writeln!(writer, "RENAME TABLE {} TO {};", ids.name(old_name), ids.name(new_name))?;
+                    writeln!(writer)?;
+                }
+                SchemaChange::AddColumn { table_name, column } => {
+                    writeln!(
+                        writer,
+                        "ALTER TABLE {} ADD COLUMN {}{};",
+                        ids.name(table_name),
+                        ids.name(column.name()),
+                        column_definition_sql(&ids, column)
+                    )?;
+                    writeln!(writer)?;
+                }
+                SchemaChange::DropColumn { table_name, column_name, rename_candidates } => {
+                    write_rename_candidates(writer, column_name, rename_candidates, |candidate| {
+                        format!("ALTER TABLE {} RENAME COLUMN {} TO {};", ids.name(table_name), ids.name(column_name), ids.name(candidate))
+                    })?;
+                    writeln!(writer, "ALTER TABLE {} DROP COLUMN {};", ids.name(table_name), ids.name(column_name))?;
+                    writeln!(writer)?;
+                }
+                SchemaChange::RenameColumn { table_name, old_name, new_name } => {
+                    writeln!(
+                        writer,
+                        "ALTER TABLE {} RENAME COLUMN {} TO {};",
+                        ids.name(table_name),
+                        ids.name(old_name),
+                        ids.name(new_name)
+                    )?;
+                    writeln!(writer)?;
+                }
+                // The create script gives MySQL tables none of these options.
+                SchemaChange::ModifyTableOptions { .. } => {}
+                SchemaChange::ModifyColumn { table_name, old_column: _, new_column, changes, enum_type } => {
+                    write_modify_column(writer, &ids, table_name, new_column, changes, enum_type.as_ref())?;
+                }
+                SchemaChange::ModifyDescription { table_name, column_name, old_description: _, new_description } => {
+                    let value = new_description.as_deref().map(escape_literal).unwrap_or_default();
+                    match column_name {
+                        Some(column_name) => {
+                            writeln!(
+                                writer,
+                                "-- MySQL only changes the comment of {}.{} by restating the column:",
+                                table_name, column_name
+                            )?;
+                            writeln!(
+                                writer,
+                                "--   ALTER TABLE {} MODIFY COLUMN {} ... COMMENT '{}';",
+                                ids.name(table_name),
+                                ids.name(column_name),
+                                value
+                            )?;
+                        }
+                        None => writeln!(writer, "ALTER TABLE {} COMMENT = '{}';", ids.name(table_name), value)?,
+                    }
+                    writeln!(writer)?;
+                }
+                SchemaChange::AddKey { table_name, key, position } => {
+                    write_add_key(writer, &ids, table_name, key, *position)?;
+                }
+                SchemaChange::DropKey { table_name, key, position } => {
+                    write_drop_key(writer, &ids, table_name, key, *position)?;
+                }
+                // A key is only recreated when its changed options show in its definition here.
+                SchemaChange::ModifyKey { table_name, old_key, new_key, position } => {
+                    if !same_key_sql(write_add_key, &ids, table_name, old_key, *position, new_key, *position)? {
+                        write_drop_key(writer, &ids, table_name, old_key, *position)?;
+                        write_add_key(writer, &ids, table_name, new_key, *position)?;
+                    }
+                }
+                SchemaChange::RenameIndex { table_name, old_name, key, position } => {
+                    writeln!(
+                        writer,
+                        "ALTER TABLE {} RENAME INDEX {} TO {};",
+                        ids.name(table_name),
+                        ids.generated(old_name),
+                        ids.generated(&ids.key_name(table_name, key, *position))
+                    )?;
+                    writeln!(writer)?;
+                }
+                SchemaChange::AddConstraint { table_name, constraint } => {
+                    writeln!(
+                        writer,
+                        "ALTER TABLE {} ADD CONSTRAINT {} CHECK ({});",
+                        ids.name(table_name),
+                        ids.name(constraint.name()),
+                        constraint.sql()
+                    )?;
+                    writeln!(writer)?;
+                }
+                SchemaChange::DropConstraint { table_name, constraint_name } => {
+                    writeln!(
+                        writer,
+                        "ALTER TABLE {} DROP CONSTRAINT {};",
+                        ids.name(table_name),
+                        ids.name(constraint_name)
+                    )?;
+                    writeln!(writer)?;
+                }
+                SchemaChange::RenameConstraint { table_name, old_name, new_name } => {
+                    writeln!(
+                        writer,
+                        "-- MySQL does not support renaming constraint '{}' on table '{}'.",
+                        old_name,
+                        table_name
+                    )?;
+                    writeln!(writer, "-- Manually drop it and add it again as '{}'.", new_name)?;
+                    writeln!(writer)?;
+                }
+                SchemaChange::AddRelation { table_name, relation, position } => {
+                    write_add_relation(writer, &ids, table_name, relation, *position)?;
+                }
+                SchemaChange::DropRelation { table_name, relation, position } => {
+                    write_drop_relation(writer, &ids, table_name, relation, *position)?;
+                }
+                SchemaChange::ModifyRelation { table_name, old_relation, new_relation, position } => {
+                    write_drop_relation(writer, &ids, table_name, old_relation, *position)?;
+                    write_add_relation(writer, &ids, table_name, new_relation, *position)?;
+                }
+                // As in the create script, a materialized view is a table filled from the
+                // view's query; rerunning the DELETE/INSERT pair refreshes it.
+                SchemaChange::AddView { view } if view.is_materialized() => {
+                    let qualified_view_name = qualified_name(view.schema_name(), view.name());
+                    let view_name = ids.name(&qualified_view_name);
+                    writeln!(writer, "CREATE TABLE IF NOT EXISTS {} AS", view_name)?;
+                    writeln!(writer, "SELECT * FROM ({}) AS {} LIMIT 0;", view.sql(), ids.name(view.name()))?;
+                    writeln!(writer)?;
+                    for (index, key) in view.indexes().iter().enumerate() {
+                        write_add_key(writer, &ids, &qualified_view_name, key, index + 1)?;
+                    }
+                    writeln!(writer, "DELETE FROM {};", view_name)?;
+                    writeln!(writer, "INSERT INTO {}", view_name)?;
+                    writeln!(writer, "{};", view.sql())?;
+                    writeln!(writer)?;
+                }
+                SchemaChange::AddView { view } => {
+                    let view_name = qualified_name(view.schema_name(), view.name());
+                    writeln!(writer, "CREATE OR REPLACE VIEW {} AS", ids.name(&view_name))?;
+                    writeln!(writer, "{};", view.sql())?;
+                    writeln!(writer)?;
+                }
+                SchemaChange::DropView { view_name, materialized } => {
+                    let kind = if *materialized { "TABLE" } else { "VIEW" };
+                    writeln!(writer, "DROP {} IF EXISTS {};", kind, ids.name(view_name))?;
+                    writeln!(writer)?;
+                }
+                // Partitioning is only generated for PostgreSQL; the table here was created
+                // as a plain table and stays one.
+                SchemaChange::AddPartition { .. }
+                | SchemaChange::DropPartition { .. }
+                | SchemaChange::ModifyPartitioning { .. } => {}
+                // Sequences are MariaDB's, which takes the same options as Postgres.
+                SchemaChange::AddSequence { sequence } => {
+                    writeln!(
+                        writer,
+                        "CREATE SEQUENCE {}{};",
+                        ids.name(&qualified_name(sequence.schema_name(), sequence.name())),
+                        sequence.options_sql().to_uppercase()
+                    )?;
+                    writeln!(writer)?;
+                }
+                // MySQL can't have created the sequence, nor parse its drop.
+                SchemaChange::DropSequence { .. } if !self.options.target_mariadb => {}
+                SchemaChange::DropSequence { sequence_name } => {
+                    writeln!(writer, "DROP SEQUENCE IF EXISTS {};", ids.name(sequence_name))?;
+                    writeln!(writer)?;
+                }
+                SchemaChange::ModifySequence { old_sequence, new_sequence } => {
+                    let mut clauses = alter_sequence_clauses(old_sequence, new_sequence, "CACHE 1000");
+                    if let Some(start) = new_sequence.start()
+                        && old_sequence.start() != new_sequence.start()
+                    {
+                        clauses.insert(0, format!("START WITH {}", start));
+                    }
+                    if !clauses.is_empty() {
+                        let sequence_name = qualified_name(new_sequence.schema_name(), new_sequence.name());
+                        writeln!(writer, "ALTER SEQUENCE {} {};", ids.name(&sequence_name), clauses.join(" "))?;
+                        writeln!(writer)?;
+                    }
+                }
+                // MySQL has no enum types; each enum column lists its codes in its own type.
+                SchemaChange::AddEnumType { .. } | SchemaChange::DropEnumType { .. } => {}
+                SchemaChange::ModifyEnumType { new_enum_type, columns, .. } => {
+                    for (table_name, column) in columns {
+                        writeln!(
+                            writer,
+                            "ALTER TABLE {} MODIFY COLUMN {}{};",
+                            ids.name(table_name),
+                            ids.name(column.name()),
+                            column_definition_with_type_sql(&ids, column, &enum_column_type_sql(new_enum_type))
+                        )?;
+                    }
+                    writeln!(writer)?;
+                }
+                // MySQL can't replace a stored program, so a changed one is dropped first.
+                SchemaChange::AddFunction { function: program } | SchemaChange::ModifyFunction { new_function: program, .. }
+                    if program.database_type() == DatabaseType::MySql =>
+                {
+                    let function_name = qualified_name(program.schema_name(), program.name());
+                    writeln!(writer, "DROP FUNCTION IF EXISTS {};", ids.generated(&function_name))?;
+                    write_stored_program(writer, program.sql())?;
+                }
+                SchemaChange::DropFunction { function } if function.database_type() == DatabaseType::MySql => {
+                    let function_name = qualified_name(function.schema_name(), function.name());
+                    writeln!(writer, "DROP FUNCTION IF EXISTS {};", ids.generated(&function_name))?;
+                    writeln!(writer)?;
+                }
+                SchemaChange::AddProcedure { procedure: program } | SchemaChange::ModifyProcedure { new_procedure: program, .. }
+                    if program.database_type() == DatabaseType::MySql =>
+                {
+                    let procedure_name = qualified_name(program.schema_name(), program.name());
+                    writeln!(writer, "DROP PROCEDURE IF EXISTS {};", ids.generated(&procedure_name))?;
+                    write_stored_program(writer, program.sql())?;
+                }
+                SchemaChange::DropProcedure { procedure } if procedure.database_type() == DatabaseType::MySql => {
+                    let procedure_name = qualified_name(procedure.schema_name(), procedure.name());
+                    writeln!(writer, "DROP PROCEDURE IF EXISTS {};", ids.generated(&procedure_name))?;
+                    writeln!(writer)?;
+                }
+                SchemaChange::AddOtherSql { other_sql } if other_sql.database_type() == DatabaseType::MySql => {
+                    writeln!(writer, "{};", other_sql.sql().trim().trim_end_matches(';'))?;
+                    writeln!(writer)?;
+                }
+                SchemaChange::DropOtherSql { other_sql } if other_sql.database_type() == DatabaseType::MySql => {
+                    write_other_sql_removal(writer, DatabaseType::MySql, other_sql.sql(), ";")?;
+                }
+                // Written with the table's regenerated triggers below.
+                SchemaChange::AddTrigger { .. }
+                | SchemaChange::DropTrigger { .. }
+                | SchemaChange::AddAggregation { .. }
+                | SchemaChange::DropAggregation { .. } => {}
+                // Programs and SQL written for another database.
+                SchemaChange::AddFunction { .. }
+                | SchemaChange::DropFunction { .. }
+                | SchemaChange::ModifyFunction { .. }
+                | SchemaChange::AddProcedure { .. }
+                | SchemaChange::DropProcedure { .. }
+                | SchemaChange::ModifyProcedure { .. }
+                | SchemaChange::AddOtherSql { .. }
+                | SchemaChange::DropOtherSql { .. } => {}
+            }
+        }
+        for (table_name, triggers) in changed_table_triggers(&self.options, DatabaseType::MySql, change_set)? {
+            write_table_triggers(writer, &ids, &table_name, &triggers)?;
+        }
+        Ok(())
+    }
+}
+
+/// Replaces the generated triggers of `table_name` with the ones the create script writes
+/// now, dropping the ones the table no longer needs. Each trigger is dropped before it is
+/// created again, with its body under its own delimiter.
+/// Fails `change` when it creates a sequence or a column drawing from one: sequences are
+/// MariaDB's, and MySQL itself has none.
+fn reject_sequence_use(change: &SchemaChange) -> Result<(), MigrationGeneratorError> {
+    let object = match change {
+        SchemaChange::AddSequence { sequence: new_sequence } | SchemaChange::ModifySequence { new_sequence, .. } => {
+            format!("sequence {}", qualified_name(new_sequence.schema_name(), new_sequence.name()))
+        }
+        SchemaChange::AddColumn { table_name, column } | SchemaChange::ModifyColumn { table_name, new_column: column, .. } => {
+            match column.sequence() {
+                Some(sequence_name) => format!("column {}.{}, drawing from sequence {},", table_name, column.name(), sequence_name),
+                None => return Ok(()),
+            }
+        }
+        _ => return Ok(()),
+    };
+    Err(MigrationGeneratorError::UnsupportedChange(format!(
+        "MySQL has no sequences, so {} needs MariaDB; set target_mariadb in the generator's options",
+        object
+    )))
+}
+
+fn write_table_triggers(
+    writer: &mut dyn Write,
+    ids: &Identifiers,
+    table_name: &str,
+    triggers: &TableTriggers,
+) -> Result<(), MigrationGeneratorError> {
+    let (schema_name, _) = split_qualified_name(table_name);
+    for trigger_name in &triggers.unneeded_names {
+        writeln!(writer, "DROP TRIGGER IF EXISTS {};", ids.generated(&qualified_name(schema_name, trigger_name)))?;
+        writeln!(writer)?;
+    }
+    for statement in &triggers.statements {
+        if starts_with_create(statement.sql_after_comments()) {
+            write_stored_program(writer, &statement.sql)?;
+        } else {
+            writeln!(writer, "{};", statement.sql)?;
+            writeln!(writer)?;
+        }
+    }
+    Ok(())
+}
+
+/// MySQL reads a backslash in a string literal as an escape, as well as a doubled quote.
+fn escape_literal(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('\'', "''")
+}
+
+/// The type, nullability, default and comment of `column`, as `ADD COLUMN` and
+/// `MODIFY COLUMN` take them. A generated column is stored, since only a stored one can
+/// be modified to and from a plain column, and takes no default.
+fn column_definition_sql(ids: &Identifiers, column: &Column) -> String {
+    column_definition_with_type_sql(ids, column, &column_type_sql(column))
+}
+
+fn column_definition_with_type_sql(ids: &Identifiers, column: &Column, type_sql: &str) -> String {
+    let generated = column
+        .generated()
+        .map(|expression| format!(" GENERATED ALWAYS AS ({}) STORED", expression))
+        .unwrap_or_default();
+    let not_null = if column.required() { " NOT NULL" } else { " NULL" };
+    let default = column_default_sql(ids, column, column.sequence())
+        .filter(|_| column.generated().is_none())
+        .map(|d| format!(" DEFAULT {}", d))
+        .unwrap_or_default();
+    let comment = column
+        .description()
+        .map(|d| format!(" COMMENT '{}'", escape_literal(d)))
+        .unwrap_or_default();
+    format!("{}{}{}{}{}", type_sql, generated, not_null, default, comment)
+}
+
+/// A native `ENUM` of the enum type's codes.
+fn enum_column_type_sql(enum_type: &EnumType) -> String {
+    let values = enum_type
+        .values()
+        .iter()
+        .map(|value| format!("'{}'", escape_literal(value.code())))
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!(" ENUM({})", values)
+}
+
+/// Alters a column one changed attribute at a time. `MODIFY COLUMN` restates the whole
+/// definition, so anything not repeated (the comment included) would be lost; it also
+/// turns a stored generated column into a plain one and back. The column's check is
+/// swapped around it. The auto-increment step is a server setting, so only a new start
+/// value is applied.
+fn write_modify_column(
+    writer: &mut dyn Write,
+    ids: &Identifiers,
+    table_name: &str,
+    new_column: &Column,
+    changes: &[ColumnAttribute],
+    enum_type: Option<&EnumType>,
+) -> Result<(), MigrationGeneratorError> {
+    let table = ids.name(table_name);
+    let check_changed = changes.iter().any(|change| change.is_check());
+    let restated = changes.iter().any(|change| {
+        change.is_type()
+            || matches!(
+                change,
+                ColumnAttribute::Required | ColumnAttribute::Default | ColumnAttribute::Sequence | ColumnAttribute::Generated
+            )
+    });
+
+    if check_changed {
+        write_drop_column_check(writer, ids, table_name, new_column.name())?;
+    }
+    if restated {
+        let type_sql = match enum_type {
+            Some(enum_type) if new_column.column_type() == ColumnType::Enum => enum_column_type_sql(enum_type),
+            _ => column_type_sql(new_column),
+        };
+        writeln!(
+            writer,
+            "ALTER TABLE {} MODIFY COLUMN {}{};",
+            table,
+            ids.name(new_column.name()),
+            column_definition_with_type_sql(ids, new_column, &type_sql)
+        )?;
+    }
+    if changes.contains(&ColumnAttribute::IdentityStart)
+        && let Some(start) = new_column.identity_start()
+    {
+        writeln!(writer, "ALTER TABLE {} AUTO_INCREMENT = {};", table, start)?;
+    }
+    if changes.contains(&ColumnAttribute::IdentityIncrement) {
+        writeln!(
+            writer,
+            "-- MySQL steps every auto-increment column by the auto_increment_increment setting; {}.{} can't step by {} on its own.",
+            table_name,
+            new_column.name(),
+            new_column.identity_increment().unwrap_or(1)
+        )?;
+    }
+    if check_changed && let Some(check) = column_check_sql(ids, new_column, None) {
+        let check_name = ids.generated(&ids.check_name(table_name, new_column.name()));
+        writeln!(writer, "ALTER TABLE {} ADD CONSTRAINT {} {};", table, check_name, check)?;
+    }
+    writeln!(writer)?;
+    Ok(())
+}
+
+/// Drops the check constraint on a column. MySQL drops a check only by its name, which the
+/// create script made up, so the name is looked up by the column the check mentions and
+/// the drop run as a prepared statement.
+fn write_drop_column_check(
+    writer: &mut dyn Write,
+    ids: &Identifiers,
+    table_name: &str,
+    column_name: &str,
+) -> Result<(), MigrationGeneratorError> {
+    let database_table_name = ids.database_name(table_name);
+    let (schema_name, bare_table_name) = split_qualified_name(&database_table_name);
+    let schema = schema_name
+        .map(|schema_name| format!("'{}'", escape_literal(schema_name)))
+        .unwrap_or_else(|| "DATABASE()".to_string());
+    writeln!(
+        writer,
+        "SET @check = (SELECT tc.CONSTRAINT_NAME FROM information_schema.TABLE_CONSTRAINTS tc \
+         JOIN information_schema.CHECK_CONSTRAINTS cc ON cc.CONSTRAINT_SCHEMA = tc.CONSTRAINT_SCHEMA AND cc.CONSTRAINT_NAME = tc.CONSTRAINT_NAME \
+         WHERE tc.TABLE_SCHEMA = {} AND tc.TABLE_NAME = '{}' AND tc.CONSTRAINT_TYPE = 'CHECK' AND cc.CHECK_CLAUSE LIKE '%`{}`%' LIMIT 1);",
+        schema,
+        escape_literal(bare_table_name),
+        escape_literal(&ids.database_name(column_name))
+    )?;
+    writeln!(
+        writer,
+        "SET @sql = IF(@check IS NULL, 'DO 0', CONCAT('ALTER TABLE {} DROP CHECK `', @check, '`'));",
+        escape_literal(&ids.name(table_name))
+    )?;
+    writeln!(writer, "PREPARE stmt FROM @sql;")?;
+    writeln!(writer, "EXECUTE stmt;")?;
+    writeln!(writer, "DEALLOCATE PREPARE stmt;")?;
+    Ok(())
+}
+
+/// Writes the `CREATE` statement of a stored program between `DELIMITER` commands, since
+/// the `;`s in its body would otherwise end it early.
+fn write_stored_program(writer: &mut dyn Write, sql: &str) -> Result<(), MigrationGeneratorError> {
+    writeln!(writer, "DELIMITER //")?;
+    writeln!(writer, "{}//", sql.trim().trim_end_matches(';'))?;
+    writeln!(writer, "DELIMITER ;")?;
+    writeln!(writer)?;
+    Ok(())
+}
+
+fn column_type_sql(column: &Column) -> String {
+    match column.column_type() {
+        ColumnType::Sequence => " integer AUTO_INCREMENT".to_string(),
+        ColumnType::LongSequence => " bigint AUTO_INCREMENT".to_string(),
+        ColumnType::Byte => " tinyint".to_string(),
+        ColumnType::Short => " smallint".to_string(),
+        ColumnType::Int => " integer".to_string(),
+        ColumnType::Long => " bigint".to_string(),
+        ColumnType::Float => " float".to_string(),
+        ColumnType::Double => " double".to_string(),
+        ColumnType::Decimal => {
+            let l = column.length();
+            let s = column.scale();
+            if l == 0 && s == 0 {
+                " decimal".to_string()
+            } else {
+                format!(" decimal({}, {})", l, s)
+            }
+        }
+        ColumnType::Boolean => " boolean".to_string(),
+        ColumnType::Date => " date".to_string(),
+        ColumnType::DateTime => " datetime".to_string(),
+        ColumnType::Time => " time".to_string(),
+        ColumnType::Timestamp => " datetime".to_string(),
+        ColumnType::TimestampTz => " timestamp".to_string(),
+        ColumnType::Char => format!(" char({})", column.length()),
+        ColumnType::Varchar => format!(" varchar({})", column.length()),
+        ColumnType::Text => " longtext".to_string(),
+        ColumnType::CiText => " longtext COLLATE utf8mb4_unicode_ci".to_string(),
+        ColumnType::CsText => " longtext COLLATE utf8mb4_bin".to_string(),
+        // The change doesn't carry the enum's values, which a native ENUM would need.
+        ColumnType::Enum => " varchar(255)".to_string(),
+        ColumnType::Binary => " longblob".to_string(),
+        ColumnType::Uuid => " char(36)".to_string(),
+        ColumnType::Json => " json".to_string(),
+        ColumnType::Array => " json".to_string(),
+    }
+}
+
+/// Adds a key; the index behind a unique key or index takes the name the create script gives
+/// it. MySQL names every primary key `PRIMARY`.
+fn write_add_key(
+    writer: &mut dyn Write,
+    ids: &Identifiers,
+    table_name: &str,
+    key: &Key,
+    position: usize,
+) -> Result<(), MigrationGeneratorError> {
+    match key.key_type() {
+        KeyType::Primary => {
+            let col_names: Vec<&str> = key.columns().iter().map(|c| c.name()).collect();
+            writeln!(writer, "ALTER TABLE {} ADD PRIMARY KEY ({});", ids.name(table_name), ids.list(&col_names))?;
+        }
+        KeyType::Unique | KeyType::Index => {
+            if let Some(expression) = key.columns().iter().find(|c| c.is_expression()) {
+                return Err(MigrationGeneratorError::UnsupportedChange(format!(
+                    "MariaDB cannot index the expression '{}' on {}; add a generated column instead",
+                    expression.name(),
+                    table_name
+                )));
+            }
+            if let Some(filter) = key.filter() {
+                return Err(MigrationGeneratorError::UnsupportedChange(format!(
+                    "MySQL has no partial indexes, so the index on {} can't be filtered by '{}'",
+                    table_name, filter
+                )));
+            }
+            let unique = if key.key_type() == KeyType::Unique || key.is_unique() { "UNIQUE " } else { "" };
+            let method = match key.method() {
+                Some(method @ (IndexMethod::BTree | IndexMethod::Hash)) => format!(" USING {}", method.sql().to_uppercase()),
+                _ => String::new(),
+            };
+            writeln!(
+                writer,
+                "CREATE {}INDEX {} ON {} ({}){};",
+                unique,
+                ids.generated(&ids.key_name(table_name, key, position)),
+                ids.name(table_name),
+                index_columns_sql(ids, key, false),
+                method
+            )?;
+        }
+    }
+    writeln!(writer)?;
+    Ok(())
+}
+
+fn write_drop_key(
+    writer: &mut dyn Write,
+    ids: &Identifiers,
+    table_name: &str,
+    key: &Key,
+    position: usize,
+) -> Result<(), MigrationGeneratorError> {
+    match key.key_type() {
+        KeyType::Primary => {
+            writeln!(writer, "ALTER TABLE {} DROP PRIMARY KEY;", ids.name(table_name))?;
+        }
+        KeyType::Unique | KeyType::Index => {
+            writeln!(
+                writer,
+                "DROP INDEX {} ON {};",
+                ids.generated(&ids.key_name(table_name, key, position)),
+                ids.name(table_name)
+            )?;
+        }
+    }
+    writeln!(writer)?;
+    Ok(())
+}
+
+fn write_drop_relation(
+    writer: &mut dyn Write,
+    ids: &Identifiers,
+    table_name: &str,
+    relation: &Relation,
+    position: usize,
+) -> Result<(), MigrationGeneratorError> {
+    writeln!(
+        writer,
+        "ALTER TABLE {} DROP FOREIGN KEY {};",
+        ids.name(table_name),
+        ids.generated(&ids.foreign_key_name(table_name, relation, position))
+    )?;
+    writeln!(writer)?;
+    Ok(())
+}
+
+fn write_add_relation(
+    writer: &mut dyn Write,
+    ids: &Identifiers,
+    table_name: &str,
+    relation: &Relation,
+    position: usize,
+) -> Result<(), MigrationGeneratorError> {
+    let fk_name = ids.foreign_key_name(table_name, relation, position);
+    let on_delete = match relation.relation_type() {
+        RelationType::Cascade => " ON DELETE CASCADE",
+        RelationType::SetNull => " ON DELETE SET NULL",
+        RelationType::DoNothing => " ON DELETE RESTRICT",
+        RelationType::Enforce => "",
+    };
+    writeln!(
+        writer,
+        "ALTER TABLE {} ADD CONSTRAINT {} FOREIGN KEY ({}) REFERENCES {}({}){};",
+        ids.name(table_name),
+        ids.generated(&fk_name),
+        ids.list(&relation.from_column_names()),
+        ids.name(relation.to_table_name()),
+        ids.list(&relation.to_column_names()),
+        on_delete
+    )?;
+    writeln!(writer)?;
+    Ok(())
+}
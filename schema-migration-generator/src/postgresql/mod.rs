@@ -1,130 +1,143 @@
 use std::io::Write;
 
-use schema_diff::{ChangeSet, SchemaChange};
+use schema_diff::{ChangeSet, ColumnAttribute, SchemaChange};
 use schema_model::model::column::Column;
 use schema_model::model::column_type::ColumnType;
+use schema_model::model::enum_type::EnumType;
 use schema_model::model::key::Key;
+use schema_model::model::partition::{PartitionBound, Partitioning};
 use schema_model::model::relation::Relation;
-use schema_model::model::types::{KeyType, RelationType};
+use schema_model::model::types::{DatabaseType, IdentityMode, KeyType, RelationType, TableOption};
+use schema_sql_generator::common::generate_options::GenerateOptions;
+use schema_sql_generator::common::trigger_generator::TableTriggers;
 
 use crate::error::MigrationGeneratorError;
-use crate::migration_generator::MigrationGenerator;
+use crate::migration_generator::{
+    alter_sequence_clauses, changed_table_triggers, column_check_sql, column_default_sql, element_column, enum_values_sql,
+    include_columns, index_columns_sql, options_without_model, qualified_name, replacing_create, same_key_sql,
+    split_qualified_name, write_other_sql_removal, write_rename_candidates, Identifiers, MigrationGenerator,
+};
 
-pub struct PostgresqlMigrationGenerator;
+pub struct PostgresqlMigrationGenerator {
+    options: GenerateOptions,
+}
+
+impl PostgresqlMigrationGenerator {
+    pub fn new(options: GenerateOptions) -> Self {
+        Self { options }
+    }
+}
+
+impl Default for PostgresqlMigrationGenerator {
+    fn default() -> Self {
+        Self::new(options_without_model())
+    }
+}
 
 impl MigrationGenerator for PostgresqlMigrationGenerator {
     fn generate(&self, change_set: &ChangeSet, writer: &mut dyn Write) -> Result<(), MigrationGeneratorError> {
+        let ids = Identifiers::new(DatabaseType::Postgresql, &self.options);
         for change in change_set.changes() {
             match change {
+                SchemaChange::AddSchema { schema_name } => {
+                    writeln!(writer, "CREATE SCHEMA IF NOT EXISTS {};", ids.generated(schema_name))?;
+                    writeln!(writer)?;
+                }
+                SchemaChange::DropSchema { schema_name } => {
+                    writeln!(writer, "DROP SCHEMA IF EXISTS {};", ids.generated(schema_name))?;
+                    writeln!(writer)?;
+                }
+                SchemaChange::MoveTable { table_name, new_schema_name } => {
+                    let new_schema_name = new_schema_name.as_deref().unwrap_or("public");
+                    writeln!(writer, "ALTER TABLE {} SET SCHEMA {};", ids.name(table_name), ids.generated(new_schema_name))?;
+                    writeln!(writer)?;
+                }
                 SchemaChange::AddTable { table_name } => {
-                    writeln!(writer, "CREATE TABLE {} ();", table_name)?;
+                    writeln!(writer, "CREATE TABLE {} ();", ids.name(table_name))?;
                     writeln!(writer)?;
                 }
                 SchemaChange::DropTable { table_name } => {
-                    writeln!(writer, "DROP TABLE IF EXISTS {};", table_name)?;
+                    writeln!(writer, "DROP TABLE IF EXISTS {};", ids.name(table_name))?;
                     writeln!(writer)?;
                 }
                 SchemaChange::RenameTable { old_name, new_name } => {
-                    writeln!(writer, "ALTER TABLE {} RENAME TO {};", old_name, new_name)?;
+                    let (_, new_name) = split_qualified_name(new_name);
+                    writeln!(writer, "ALTER TABLE {} RENAME TO {};", ids.name(old_name), ids.name(new_name))?;
                     writeln!(writer)?;
                 }
                 SchemaChange::AddColumn { table_name, column } => {
-                    let type_sql = column_type_sql(column);
-                    let not_null = if column.required() { " NOT NULL" } else { "" };
-                    let default = column
-                        .default_constraint()
-                        .map(|d| format!(" DEFAULT {}", d))
-                        .unwrap_or_default();
                     writeln!(
                         writer,
-                        "ALTER TABLE {} ADD COLUMN {}{}{}{};",
-                        table_name,
-                        column.name(),
-                        type_sql,
-                        not_null,
-                        default
+                        "ALTER TABLE {} ADD COLUMN {}{};",
+                        ids.name(table_name),
+                        ids.name(column.name()),
+                        column_definition_sql(&ids, column)
                     )?;
                     writeln!(writer)?;
                 }
                 SchemaChange::DropColumn { table_name, column_name, rename_candidates } => {
-                    if !rename_candidates.is_empty() {
-                        writeln!(writer, "-- TODO: possible rename? Consider replacing the DROP + ADD below with:")?;
-                        for candidate in rename_candidates {
-                            writeln!(writer, "--   ALTER TABLE {} RENAME COLUMN {} TO {};", table_name, column_name, candidate)?;
-                        }
-                    }
-                    writeln!(writer, "ALTER TABLE {} DROP COLUMN {};", table_name, column_name)?;
+                    write_rename_candidates(writer, column_name, rename_candidates, |candidate| {
+                        format!("ALTER TABLE {} RENAME COLUMN {} TO {};", ids.name(table_name), ids.name(column_name), ids.name(candidate))
+                    })?;
+                    writeln!(writer, "ALTER TABLE {} DROP COLUMN {};", ids.name(table_name), ids.name(column_name))?;
                     writeln!(writer)?;
                 }
                 SchemaChange::RenameColumn { table_name, old_name, new_name } => {
                     writeln!(
                         writer,
                         "ALTER TABLE {} RENAME COLUMN {} TO {};",
-                        table_name, old_name, new_name
+                        ids.name(table_name),
+                        ids.name(old_name),
+                        ids.name(new_name)
                     )?;
                     writeln!(writer)?;
                 }
-                SchemaChange::ModifyColumn { table_name, old_column, new_column } => {
-                    let new_type = column_type_sql(new_column);
-                    let old_type = column_type_sql(old_column);
-                    if new_type != old_type {
-                        writeln!(
-                            writer,
-                            "ALTER TABLE {} ALTER COLUMN {} TYPE {};",
-                            table_name,
-                            new_column.name(),
-                            new_type
-                        )?;
-                    }
-                    if old_column.required() != new_column.required() {
-                        if new_column.required() {
-                            writeln!(
-                                writer,
-                                "ALTER TABLE {} ALTER COLUMN {} SET NOT NULL;",
-                                table_name,
-                                new_column.name()
-                            )?;
-                        } else {
-                            writeln!(
-                                writer,
-                                "ALTER TABLE {} ALTER COLUMN {} DROP NOT NULL;",
-                                table_name,
-                                new_column.name()
-                            )?;
-                        }
-                    }
-                    if old_column.default_constraint() != new_column.default_constraint() {
-                        if let Some(default) = new_column.default_constraint() {
-                            writeln!(
-                                writer,
-                                "ALTER TABLE {} ALTER COLUMN {} SET DEFAULT {};",
-                                table_name,
-                                new_column.name(),
-                                default
-                            )?;
-                        } else {
-                            writeln!(
-                                writer,
-                                "ALTER TABLE {} ALTER COLUMN {} DROP DEFAULT;",
-                                table_name,
-                                new_column.name()
-                            )?;
-                        }
+                SchemaChange::ModifyTableOptions { table_name, old_options, new_options, columns, .. } => {
+                    let compressed = |options: &[TableOption]| options.iter().any(|option| matches!(option, TableOption::Compress(_)));
+                    if compressed(old_options) != compressed(new_options) {
+                        write_compression_change(writer, &ids, table_name, columns, compressed(new_options))?;
                     }
+                }
+                SchemaChange::ModifyColumn { table_name, old_column, new_column, changes, enum_type: _ } => {
+                    write_modify_column(writer, &ids, table_name, old_column, new_column, changes)?;
+                }
+                SchemaChange::ModifyDescription { table_name, column_name, old_description: _, new_description } => {
+                    let target = match column_name {
+                        Some(column_name) => format!("COLUMN {}.{}", ids.name(table_name), ids.name(column_name)),
+                        None => format!("TABLE {}", ids.name(table_name)),
+                    };
+                    let value = new_description
+                        .as_deref()
+                        .map(|d| format!("'{}'", d.replace('\'', "''")))
+                        .unwrap_or_else(|| "NULL".to_string());
+                    writeln!(writer, "COMMENT ON {} IS {};", target, value)?;
                     writeln!(writer)?;
                 }
-                SchemaChange::AddKey { table_name, key } => {
-                    write_add_key(writer, table_name, key)?;
+                SchemaChange::AddKey { table_name, key, position } => {
+                    write_add_key(writer, &ids, table_name, key, *position)?;
+                }
+                SchemaChange::DropKey { table_name, key, position } => {
+                    write_drop_key(writer, &ids, table_name, key, *position)?;
                 }
-                SchemaChange::DropKey { table_name, key } => {
-                    write_drop_key(writer, table_name, key)?;
+                SchemaChange::ModifyKey { table_name, old_key, new_key, position } => {
+                    write_modify_key(writer, &ids, table_name, old_key, new_key, *position)?;
+                }
+                SchemaChange::RenameIndex { table_name, old_name, key, position } => {
+                    let (schema_name, _) = split_qualified_name(table_name);
+                    writeln!(
+                        writer,
+                        "ALTER INDEX IF EXISTS {} RENAME TO {};",
+                        ids.generated(&qualified_name(schema_name, old_name)),
+                        ids.generated(&ids.key_name(table_name, key, *position))
+                    )?;
+                    writeln!(writer)?;
                 }
                 SchemaChange::AddConstraint { table_name, constraint } => {
                     writeln!(
                         writer,
                         "ALTER TABLE {} ADD CONSTRAINT {} CHECK ({});",
-                        table_name,
-                        constraint.name(),
+                        ids.name(table_name),
+                        ids.name(constraint.name()),
                         constraint.sql()
                     )?;
                     writeln!(writer)?;
@@ -133,39 +146,482 @@ impl MigrationGenerator for PostgresqlMigrationGenerator {
                     writeln!(
                         writer,
                         "ALTER TABLE {} DROP CONSTRAINT {};",
-                        table_name, constraint_name
+                        ids.name(table_name),
+                        ids.name(constraint_name)
                     )?;
                     writeln!(writer)?;
                 }
-                SchemaChange::AddRelation { relation } => {
-                    write_add_relation(writer, relation)?;
-                }
-                SchemaChange::DropRelation { relation } => {
-                    let fk_name = fk_constraint_name(relation);
+                SchemaChange::RenameConstraint { table_name, old_name, new_name } => {
                     writeln!(
                         writer,
-                        "ALTER TABLE {} DROP CONSTRAINT {};",
-                        relation.from_table_name(),
-                        fk_name
+                        "ALTER TABLE {} RENAME CONSTRAINT {} TO {};",
+                        ids.name(table_name),
+                        ids.name(old_name),
+                        ids.name(new_name)
                     )?;
                     writeln!(writer)?;
                 }
+                SchemaChange::AddRelation { table_name, relation, position } => {
+                    write_add_relation(writer, &ids, table_name, relation, *position)?;
+                }
+                SchemaChange::DropRelation { table_name, relation, position } => {
+                    write_drop_relation(writer, &ids, table_name, relation, *position)?;
+                }
+                SchemaChange::ModifyRelation { table_name, old_relation, new_relation, position } => {
+                    write_drop_relation(writer, &ids, table_name, old_relation, *position)?;
+                    write_add_relation(writer, &ids, table_name, new_relation, *position)?;
+                }
+                SchemaChange::AddView { view } if view.is_materialized() => {
+                    let view_name = qualified_name(view.schema_name(), view.name());
+                    writeln!(writer, "CREATE MATERIALIZED VIEW {} AS", ids.name(&view_name))?;
+                    writeln!(writer, "{};", view.sql())?;
+                    writeln!(writer)?;
+                    for (index, key) in view.indexes().iter().enumerate() {
+                        write_add_key(writer, &ids, &view_name, key, index + 1)?;
+                    }
+                }
                 SchemaChange::AddView { view } => {
-                    writeln!(writer, "CREATE OR REPLACE VIEW {} AS", view.name())?;
+                    let view_name = qualified_name(view.schema_name(), view.name());
+                    writeln!(writer, "CREATE OR REPLACE VIEW {} AS", ids.name(&view_name))?;
                     writeln!(writer, "{};", view.sql())?;
                     writeln!(writer)?;
                 }
-                SchemaChange::DropView { view_name } => {
-                    writeln!(writer, "DROP VIEW IF EXISTS {};", view_name)?;
+                SchemaChange::DropView { view_name, materialized } => {
+                    let kind = if *materialized { "MATERIALIZED VIEW" } else { "VIEW" };
+                    writeln!(writer, "DROP {} IF EXISTS {};", kind, ids.name(view_name))?;
+                    writeln!(writer)?;
+                }
+                SchemaChange::AddPartition { table_name, partition } => {
+                    let (schema_name, _) = split_qualified_name(table_name);
+                    writeln!(
+                        writer,
+                        "CREATE TABLE {} PARTITION OF {} {};",
+                        ids.name(&qualified_name(schema_name, partition.name())),
+                        ids.name(table_name),
+                        partition_bound_sql(partition.bound())
+                    )?;
+                    writeln!(writer)?;
+                }
+                SchemaChange::DropPartition { table_name, partition_name } => {
+                    let (schema_name, _) = split_qualified_name(table_name);
+                    writeln!(writer, "DROP TABLE IF EXISTS {};", ids.name(&qualified_name(schema_name, partition_name)))?;
+                    writeln!(writer)?;
+                }
+                SchemaChange::ModifyPartitioning { table_name, old_partitioning, new_partitioning } => {
+                    let describe = |partitioning: &Option<Partitioning>| match partitioning {
+                        Some(partitioning) => format!("partitioned by {}", partitioning),
+                        None => "not partitioned".to_string(),
+                    };
+                    return Err(MigrationGeneratorError::UnsupportedChange(format!(
+                        "{} goes from {} to {}; PostgreSQL can't change this in place, so the table has to be recreated and its rows copied",
+                        table_name,
+                        describe(old_partitioning),
+                        describe(new_partitioning)
+                    )));
+                }
+                SchemaChange::AddSequence { sequence } => {
+                    writeln!(
+                        writer,
+                        "CREATE SEQUENCE {}{};",
+                        ids.name(&qualified_name(sequence.schema_name(), sequence.name())),
+                        sequence.options_sql().to_uppercase()
+                    )?;
+                    writeln!(writer)?;
+                }
+                SchemaChange::DropSequence { sequence_name } => {
+                    writeln!(writer, "DROP SEQUENCE IF EXISTS {};", ids.name(sequence_name))?;
                     writeln!(writer)?;
                 }
+                SchemaChange::ModifySequence { old_sequence, new_sequence } => {
+                    let mut clauses = alter_sequence_clauses(old_sequence, new_sequence, "CACHE 1");
+                    // START WITH only sets the value a later RESTART goes back to; the
+                    // sequence's current value is not touched.
+                    if let Some(start) = new_sequence.start()
+                        && old_sequence.start() != new_sequence.start()
+                    {
+                        clauses.insert(0, format!("START WITH {}", start));
+                    }
+                    if !clauses.is_empty() {
+                        let sequence_name = qualified_name(new_sequence.schema_name(), new_sequence.name());
+                        writeln!(writer, "ALTER SEQUENCE {} {};", ids.name(&sequence_name), clauses.join(" "))?;
+                        writeln!(writer)?;
+                    }
+                }
+                SchemaChange::AddEnumType { enum_type } => {
+                    let type_name = ids.generated(&enum_type_name(enum_type.name()));
+                    writeln!(writer, "CREATE TYPE {} AS ENUM ({});", type_name, enum_values_sql(enum_type))?;
+                    writeln!(writer)?;
+                }
+                SchemaChange::DropEnumType { enum_type_name: name } => {
+                    writeln!(writer, "DROP TYPE IF EXISTS {};", ids.generated(&enum_type_name(name)))?;
+                    writeln!(writer)?;
+                }
+                SchemaChange::ModifyEnumType { old_enum_type, new_enum_type, columns } => {
+                    write_enum_type_change(writer, &ids, old_enum_type, new_enum_type, columns)?;
+                }
+                SchemaChange::AddFunction { function: program } | SchemaChange::ModifyFunction { new_function: program, .. }
+                    if program.database_type() == DatabaseType::Postgresql =>
+                {
+                    writeln!(writer, "{};", replacing_create(program.sql(), "CREATE OR REPLACE").trim_end_matches(';'))?;
+                    writeln!(writer)?;
+                }
+                SchemaChange::DropFunction { function } if function.database_type() == DatabaseType::Postgresql => {
+                    let function_name = qualified_name(function.schema_name(), function.name());
+                    writeln!(writer, "DROP FUNCTION IF EXISTS {};", ids.generated(&function_name))?;
+                    writeln!(writer)?;
+                }
+                SchemaChange::AddProcedure { procedure: program } | SchemaChange::ModifyProcedure { new_procedure: program, .. }
+                    if program.database_type() == DatabaseType::Postgresql =>
+                {
+                    writeln!(writer, "{};", replacing_create(program.sql(), "CREATE OR REPLACE").trim_end_matches(';'))?;
+                    writeln!(writer)?;
+                }
+                SchemaChange::DropProcedure { procedure } if procedure.database_type() == DatabaseType::Postgresql => {
+                    let procedure_name = qualified_name(procedure.schema_name(), procedure.name());
+                    writeln!(writer, "DROP PROCEDURE IF EXISTS {};", ids.generated(&procedure_name))?;
+                    writeln!(writer)?;
+                }
+                SchemaChange::AddOtherSql { other_sql } if other_sql.database_type() == DatabaseType::Postgresql => {
+                    writeln!(writer, "{};", other_sql.sql().trim().trim_end_matches(';'))?;
+                    writeln!(writer)?;
+                }
+                SchemaChange::DropOtherSql { other_sql } if other_sql.database_type() == DatabaseType::Postgresql => {
+                    write_other_sql_removal(writer, DatabaseType::Postgresql, other_sql.sql(), ";")?;
+                }
+                // Written with the table's regenerated triggers below.
+                SchemaChange::AddTrigger { .. }
+                | SchemaChange::DropTrigger { .. }
+                | SchemaChange::AddAggregation { .. }
+                | SchemaChange::DropAggregation { .. } => {}
+                // Programs and SQL written for another database.
+                SchemaChange::AddFunction { .. }
+                | SchemaChange::DropFunction { .. }
+                | SchemaChange::ModifyFunction { .. }
+                | SchemaChange::AddProcedure { .. }
+                | SchemaChange::DropProcedure { .. }
+                | SchemaChange::ModifyProcedure { .. }
+                | SchemaChange::AddOtherSql { .. }
+                | SchemaChange::DropOtherSql { .. } => {}
             }
         }
+        for (table_name, triggers) in changed_table_triggers(&self.options, DatabaseType::Postgresql, change_set)? {
+            write_table_triggers(writer, &ids, &table_name, &triggers)?;
+        }
         Ok(())
     }
 }
 
-fn column_type_sql(column: &Column) -> String {
+/// Replaces the generated triggers of `table_name` with the ones the create script writes
+/// now. A trigger the table no longer needs goes with its function; the others have their
+/// function replaced and the trigger created again.
+fn write_table_triggers(
+    writer: &mut dyn Write,
+    ids: &Identifiers,
+    table_name: &str,
+    triggers: &TableTriggers,
+) -> Result<(), MigrationGeneratorError> {
+    let (schema_name, _) = split_qualified_name(table_name);
+    for trigger_name in &triggers.unneeded_names {
+        let function_name = qualified_name(Some(schema_name.unwrap_or("public")), trigger_name);
+        writeln!(writer, "DROP FUNCTION IF EXISTS {}() CASCADE;", ids.generated(&function_name))?;
+        writeln!(writer)?;
+    }
+    for statement in &triggers.statements {
+        writeln!(writer, "{};", statement.sql)?;
+        writeln!(writer)?;
+    }
+    Ok(())
+}
+
+/// The type, nullability and default of `column`, or its generation expression in place of
+/// a default, as `ADD COLUMN` takes them.
+fn column_definition_sql(ids: &Identifiers, column: &Column) -> String {
+    let type_sql = identity_column_type_sql(column).unwrap_or_else(|| column_type_sql(ids, column));
+    let not_null = if column.required() { " NOT NULL" } else { "" };
+    let value = match column.generated() {
+        Some(expression) => format!(" GENERATED ALWAYS AS ({}) STORED", expression),
+        None => column_default_sql(ids, column, column.sequence()).map(|d| format!(" DEFAULT {}", d)).unwrap_or_default(),
+    };
+    format!("{}{}{}", type_sql, not_null, value)
+}
+
+/// Alters a column one changed attribute at a time. A column that becomes generated, or
+/// whose expression changes, is dropped and added again, taking the indexes and
+/// constraints on it along; one that stops being generated keeps its values. The
+/// column's check, named as the create script names it, is swapped around the other
+/// changes, so that it never checks a type the column no longer has.
+fn write_modify_column(
+    writer: &mut dyn Write,
+    ids: &Identifiers,
+    table_name: &str,
+    old_column: &Column,
+    new_column: &Column,
+    changes: &[ColumnAttribute],
+) -> Result<(), MigrationGeneratorError> {
+    let table = ids.name(table_name);
+    let column = ids.name(new_column.name());
+    let check_name = ids.generated(&ids.check_name(table_name, new_column.name()));
+    if changes.contains(&ColumnAttribute::Generated) {
+        if new_column.generated().is_some() {
+            writeln!(writer, "ALTER TABLE {} DROP COLUMN {};", table, column)?;
+            writeln!(writer, "ALTER TABLE {} ADD COLUMN {}{};", table, column, column_definition_sql(ids, new_column))?;
+            if let Some(check) = column_check_sql(ids, new_column, None) {
+                writeln!(writer, "ALTER TABLE {} ADD CONSTRAINT {} {};", table, check_name, check)?;
+            }
+            writeln!(writer)?;
+            return Ok(());
+        }
+        writeln!(writer, "ALTER TABLE {} ALTER COLUMN {} DROP EXPRESSION;", table, column)?;
+    }
+
+    let check_changed = changes.iter().any(|change| change.is_check());
+    if check_changed {
+        writeln!(writer, "ALTER TABLE {} DROP CONSTRAINT IF EXISTS {};", table, check_name)?;
+    }
+    if changes.iter().any(|change| change.is_type()) {
+        let new_type = column_type_sql(ids, new_column);
+        if new_type != column_type_sql(ids, old_column) {
+            // Text converts to an enum only through an explicit cast.
+            let using = if new_column.column_type() == ColumnType::Enum && new_column.enum_type().is_some() {
+                format!(" USING {}::text::{}", column, new_type.trim_start())
+            } else {
+                String::new()
+            };
+            writeln!(writer, "ALTER TABLE {} ALTER COLUMN {} TYPE{}{};", table, column, new_type, using)?;
+        }
+    }
+    if changes.contains(&ColumnAttribute::Required) {
+        let action = if new_column.required() { "SET" } else { "DROP" };
+        writeln!(writer, "ALTER TABLE {} ALTER COLUMN {} {} NOT NULL;", table, column, action)?;
+    }
+    if changes.iter().any(|change| change.is_identity()) {
+        write_identity_change(writer, ids, table_name, old_column, new_column)?;
+    }
+    if changes.contains(&ColumnAttribute::Default) || changes.contains(&ColumnAttribute::Sequence) {
+        match column_default_sql(ids, new_column, new_column.sequence()) {
+            Some(default) => writeln!(writer, "ALTER TABLE {} ALTER COLUMN {} SET DEFAULT {};", table, column, default)?,
+            None => writeln!(writer, "ALTER TABLE {} ALTER COLUMN {} DROP DEFAULT;", table, column)?,
+        }
+    }
+    if check_changed && let Some(check) = column_check_sql(ids, new_column, None) {
+        writeln!(writer, "ALTER TABLE {} ADD CONSTRAINT {} {};", table, check_name, check)?;
+    }
+    writeln!(writer)?;
+    Ok(())
+}
+
+/// The type of a sequence column that is an identity, with its `GENERATED ... AS IDENTITY`
+/// clause; `None` for a serial or any other column.
+fn identity_column_type_sql(column: &Column) -> Option<String> {
+    let clause = column.identity_mode()?.sql()?.to_uppercase();
+    let base_type = match column.column_type() {
+        ColumnType::Sequence => "integer",
+        ColumnType::LongSequence => "bigint",
+        _ => return None,
+    };
+    let options = identity_options_sql(column);
+    if options.is_empty() {
+        Some(format!(" {} {}", base_type, clause))
+    } else {
+        Some(format!(" {} {} ({})", base_type, clause, options.join(" ")))
+    }
+}
+
+fn identity_options_sql(column: &Column) -> Vec<String> {
+    let mut options = Vec::new();
+    if let Some(start) = column.identity_start() {
+        options.push(format!("START WITH {}", start));
+    }
+    if let Some(increment) = column.identity_increment() {
+        options.push(format!("INCREMENT BY {}", increment));
+    }
+    options
+}
+
+fn is_identity(column: &Column) -> bool {
+    column.identity_mode().is_some_and(|mode| mode != IdentityMode::Serial)
+}
+
+/// Converts a sequence column between a serial and an identity, or alters its identity.
+/// Going to an identity drops the serial's default and owned sequence; the identity's own
+/// sequence then continues after the highest existing value. Going back to a serial
+/// creates the `{table}_{column}_seq` sequence a `serial` would have had.
+fn write_identity_change(
+    writer: &mut dyn Write,
+    ids: &Identifiers,
+    table_name: &str,
+    old_column: &Column,
+    new_column: &Column,
+) -> Result<(), MigrationGeneratorError> {
+    let table = ids.name(table_name);
+    let column_name = ids.database_name(new_column.name());
+    let column = ids.name(new_column.name());
+    // A serial column's sequence is named after the table and column as the database spells them.
+    let serial_sequence_name = ids.generated(&format!("{}_{}_seq", ids.database_name(table_name), column_name));
+    match (is_identity(old_column), is_identity(new_column)) {
+        (false, true) if matches!(old_column.column_type(), ColumnType::Sequence | ColumnType::LongSequence) => {
+            let clause = new_column.identity_mode().and_then(|mode| mode.sql()).unwrap_or_default().to_uppercase();
+            let options = identity_options_sql(new_column);
+            let options = if options.is_empty() { String::new() } else { format!(" ({})", options.join(" ")) };
+            writeln!(writer, "ALTER TABLE {} ALTER COLUMN {} DROP DEFAULT;", table, column)?;
+            writeln!(writer, "DROP SEQUENCE IF EXISTS {};", serial_sequence_name)?;
+            writeln!(writer, "ALTER TABLE {} ALTER COLUMN {} ADD {}{};", table, column, clause, options)?;
+            writeln!(
+                writer,
+                "SELECT setval(pg_get_serial_sequence('{}', '{}'), MAX({})) FROM {} HAVING MAX({}) IS NOT NULL;",
+                table, column_name, column, table, column
+            )?;
+        }
+        (true, false) if matches!(new_column.column_type(), ColumnType::Sequence | ColumnType::LongSequence) => {
+            let sequence_name = serial_sequence_name;
+            writeln!(writer, "ALTER TABLE {} ALTER COLUMN {} DROP IDENTITY IF EXISTS;", table, column)?;
+            writeln!(writer, "CREATE SEQUENCE {} OWNED BY {}.{};", sequence_name, table, column)?;
+            writeln!(
+                writer,
+                "SELECT setval('{}', MAX({})) FROM {} HAVING MAX({}) IS NOT NULL;",
+                sequence_name, column, table, column
+            )?;
+            writeln!(
+                writer,
+                "ALTER TABLE {} ALTER COLUMN {} SET DEFAULT nextval('{}');",
+                table, column, sequence_name
+            )?;
+        }
+        (true, true) => {
+            let mut clauses = Vec::new();
+            if old_column.identity_mode() != new_column.identity_mode() {
+                clauses.push(match new_column.identity_mode() {
+                    Some(IdentityMode::ByDefault) => "SET GENERATED BY DEFAULT".to_string(),
+                    _ => "SET GENERATED ALWAYS".to_string(),
+                });
+            }
+            if old_column.identity_start() != new_column.identity_start() {
+                clauses.push(format!("SET START WITH {}", new_column.identity_start().unwrap_or(1)));
+            }
+            if old_column.identity_increment() != new_column.identity_increment() {
+                clauses.push(format!("SET INCREMENT BY {}", new_column.identity_increment().unwrap_or(1)));
+            }
+            if !clauses.is_empty() {
+                writeln!(writer, "ALTER TABLE {} ALTER COLUMN {} {};", table, column, clauses.join(" "))?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// The name the create script gives an enum type: its model name in snake case.
+fn enum_type_name(name: &str) -> String {
+    let mut result = String::new();
+    let mut prev_underscore = false;
+    for ch in name.chars() {
+        if ch.is_uppercase() && !prev_underscore && !result.is_empty() {
+            result.push('_');
+        }
+        result.extend(ch.to_lowercase());
+        prev_underscore = ch == '_';
+    }
+    result
+}
+
+/// When values were only added and the old ones keep their order, they are added in place;
+/// `ADD VALUE` can't run inside a transaction block before PostgreSQL 12. Anything else
+/// rebuilds the type: the old one is renamed away, the new one created, the columns cast
+/// over through text, and the old type dropped. A row still holding a removed value makes
+/// its cast fail, which stops the migration rather than losing the value.
+fn write_enum_type_change(
+    writer: &mut dyn Write,
+    ids: &Identifiers,
+    old_enum_type: &EnumType,
+    new_enum_type: &EnumType,
+    columns: &[(String, Column)],
+) -> Result<(), MigrationGeneratorError> {
+    let type_name = enum_type_name(new_enum_type.name());
+    let old_codes: Vec<&str> = old_enum_type.values().iter().map(|value| value.code()).collect();
+    let new_codes: Vec<&str> = new_enum_type.values().iter().map(|value| value.code()).collect();
+    let kept: Vec<&str> = new_codes.iter().copied().filter(|code| old_codes.contains(code)).collect();
+
+    if kept == old_codes {
+        for (index, code) in new_codes.iter().enumerate() {
+            if old_codes.contains(code) {
+                continue;
+            }
+            let position = new_codes[index + 1..]
+                .iter()
+                .find(|next| old_codes.contains(next))
+                .map(|next| format!(" BEFORE '{}'", next.replace('\'', "''")))
+                .unwrap_or_default();
+            writeln!(
+                writer,
+                "ALTER TYPE {} ADD VALUE IF NOT EXISTS '{}'{};",
+                ids.generated(&type_name),
+                code.replace('\'', "''"),
+                position
+            )?;
+        }
+        writeln!(writer)?;
+        return Ok(());
+    }
+
+    let old_type_name = format!("{}_old", type_name);
+    writeln!(writer, "ALTER TYPE {} RENAME TO {};", ids.generated(&type_name), ids.generated(&old_type_name))?;
+    writeln!(writer, "CREATE TYPE {} AS ENUM ({});", ids.generated(&type_name), enum_values_sql(new_enum_type))?;
+    for (table_name, column) in columns {
+        let table = ids.name(table_name);
+        let column_name = ids.name(column.name());
+        if column.default_constraint().is_some() {
+            writeln!(writer, "ALTER TABLE {} ALTER COLUMN {} DROP DEFAULT;", table, column_name)?;
+        }
+        writeln!(
+            writer,
+            "ALTER TABLE {} ALTER COLUMN {} TYPE {} USING {}::text::{};",
+            table,
+            column_name,
+            ids.generated(&type_name),
+            column_name,
+            ids.generated(&type_name)
+        )?;
+        if let Some(default) = column.default_constraint() {
+            writeln!(writer, "ALTER TABLE {} ALTER COLUMN {} SET DEFAULT {};", table, column_name, default)?;
+        }
+    }
+    writeln!(writer, "DROP TYPE {};", ids.generated(&old_type_name))?;
+    writeln!(writer)?;
+    Ok(())
+}
+
+/// The `FOR VALUES ...` (or `DEFAULT`) clause of a partition. Numbers and `MINVALUE`/`MAXVALUE`
+/// are left unquoted; every other bound value is quoted.
+fn partition_bound_sql(bound: &PartitionBound) -> String {
+    let values = |values: &[String]| {
+        values
+            .iter()
+            .map(|value| {
+                if value.parse::<f64>().is_ok()
+                    || value.eq_ignore_ascii_case("minvalue")
+                    || value.eq_ignore_ascii_case("maxvalue")
+                {
+                    value.to_uppercase()
+                } else {
+                    format!("'{}'", value.replace('\'', "''"))
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    match bound {
+        PartitionBound::Range { from, to } => format!("FOR VALUES FROM ({}) TO ({})", values(from), values(to)),
+        PartitionBound::List(list) => format!("FOR VALUES IN ({})", values(list)),
+        PartitionBound::Hash { modulus, remainder } => {
+            format!("FOR VALUES WITH (MODULUS {}, REMAINDER {})", modulus, remainder)
+        }
+        PartitionBound::Default => "DEFAULT".to_string(),
+    }
+}
+
+/// The type the create script gives `column`: an enum column is of its enum type, and an
+/// array of its element type.
+fn column_type_sql(ids: &Identifiers, column: &Column) -> String {
     match column.column_type() {
         ColumnType::Sequence => " serial".to_string(),
         ColumnType::LongSequence => " bigserial".to_string(),
@@ -195,35 +651,61 @@ fn column_type_sql(column: &Column) -> String {
         ColumnType::Text => " text".to_string(),
         ColumnType::CiText => " citext".to_string(),
         ColumnType::CsText => " text".to_string(),
-        ColumnType::Enum => " text".to_string(),
+        ColumnType::Enum => match column.enum_type() {
+            Some(enum_type) => format!(" {}", ids.generated(&enum_type_name(enum_type))),
+            None => " text".to_string(),
+        },
         ColumnType::Binary => " bytea".to_string(),
         ColumnType::Uuid => " uuid".to_string(),
         ColumnType::Json => " jsonb".to_string(),
-        ColumnType::Array => " text[]".to_string(),
+        ColumnType::Array => match element_column(column) {
+            Some(element) => format!("{}[]", column_type_sql(ids, &element)),
+            None => " text[]".to_string(),
+        },
     }
 }
 
-fn write_add_key(writer: &mut dyn Write, table_name: &str, key: &Key) -> Result<(), MigrationGeneratorError> {
-    let col_names: Vec<&str> = key.columns().iter().map(|c| c.name()).collect();
-    let cols = col_names.join(", ");
+/// Adds a key as the create script declares it: the primary key and unique keys as named
+/// table constraints, other indexes with `CREATE INDEX`.
+fn write_add_key(
+    writer: &mut dyn Write,
+    ids: &Identifiers,
+    table_name: &str,
+    key: &Key,
+    position: usize,
+) -> Result<(), MigrationGeneratorError> {
+    let key_name = ids.key_name(table_name, key, position);
     match key.key_type() {
-        KeyType::Primary => {
-            writeln!(writer, "ALTER TABLE {} ADD PRIMARY KEY ({});", table_name, cols)?;
-        }
-        KeyType::Unique => {
-            let idx_name = format!("idx_{}_{}", table_name, col_names.join("_"));
+        KeyType::Primary | KeyType::Unique => {
+            let col_names: Vec<&str> = key.columns().iter().map(|c| c.name()).collect();
             writeln!(
                 writer,
-                "CREATE UNIQUE INDEX {} ON {} ({});",
-                idx_name, table_name, cols
+                "ALTER TABLE {} ADD CONSTRAINT {} {} ({});",
+                ids.name(table_name),
+                ids.generated(&key_name),
+                if key.key_type() == KeyType::Primary { "PRIMARY KEY" } else { "UNIQUE" },
+                ids.list(&col_names)
             )?;
         }
         KeyType::Index => {
-            let idx_name = format!("idx_{}_{}", table_name, col_names.join("_"));
+            let unique = if key.is_unique() { "UNIQUE " } else { "" };
+            let method = key
+                .method()
+                .map(|m| format!(" USING {}", m.sql()))
+                .unwrap_or_default();
+            let include = include_columns(key);
+            let include = if include.is_empty() { String::new() } else { format!(" INCLUDE ({})", ids.list(&include)) };
+            let filter = key.filter().map(|f| format!(" WHERE {}", f)).unwrap_or_default();
             writeln!(
                 writer,
-                "CREATE INDEX {} ON {} ({});",
-                idx_name, table_name, cols
+                "CREATE {}INDEX {} ON {}{} ({}){}{};",
+                unique,
+                ids.generated(&key_name),
+                ids.name(table_name),
+                method,
+                index_columns_sql(ids, key, true),
+                include,
+                filter
             )?;
         }
     }
@@ -231,27 +713,120 @@ fn write_add_key(writer: &mut dyn Write, table_name: &str, key: &Key) -> Result<
     Ok(())
 }
 
-fn write_drop_key(writer: &mut dyn Write, table_name: &str, key: &Key) -> Result<(), MigrationGeneratorError> {
-    let col_names: Vec<&str> = key.columns().iter().map(|c| c.name()).collect();
+/// Drops a key; an index is found through its table's schema, since `DROP INDEX` names no table.
+fn write_drop_key(
+    writer: &mut dyn Write,
+    ids: &Identifiers,
+    table_name: &str,
+    key: &Key,
+    position: usize,
+) -> Result<(), MigrationGeneratorError> {
+    let (schema_name, _) = split_qualified_name(table_name);
+    let key_name = ids.key_name(table_name, key, position);
     match key.key_type() {
-        KeyType::Primary => {
-            writeln!(
-                writer,
-                "ALTER TABLE {} DROP CONSTRAINT {}_pkey;",
-                table_name, table_name
-            )?;
+        KeyType::Primary | KeyType::Unique => {
+            writeln!(writer, "ALTER TABLE {} DROP CONSTRAINT {};", ids.name(table_name), ids.generated(&key_name))?;
         }
-        KeyType::Unique | KeyType::Index => {
-            let idx_name = format!("idx_{}_{}", table_name, col_names.join("_"));
-            writeln!(writer, "DROP INDEX IF EXISTS {};", idx_name)?;
+        KeyType::Index => {
+            writeln!(writer, "DROP INDEX IF EXISTS {};", ids.generated(&qualified_name(schema_name, &key_name)))?;
         }
     }
     writeln!(writer)?;
     Ok(())
 }
 
-fn write_add_relation(writer: &mut dyn Write, relation: &Relation) -> Result<(), MigrationGeneratorError> {
-    let fk_name = fk_constraint_name(relation);
+/// Recreates a key whose changed options show in its definition. Clustering doesn't: it
+/// marks the index that `CLUSTER` orders the table by, and the mark goes with a dropped index.
+fn write_modify_key(
+    writer: &mut dyn Write,
+    ids: &Identifiers,
+    table_name: &str,
+    old_key: &Key,
+    new_key: &Key,
+    position: usize,
+) -> Result<(), MigrationGeneratorError> {
+    let rebuilt = !same_key_sql(write_add_key, ids, table_name, old_key, position, new_key, position)?;
+    if rebuilt {
+        write_drop_key(writer, ids, table_name, old_key, position)?;
+        write_add_key(writer, ids, table_name, new_key, position)?;
+    }
+    if new_key.is_cluster() && (rebuilt || !old_key.is_cluster()) {
+        let index = ids.key_name(table_name, new_key, position);
+        writeln!(writer, "ALTER TABLE {} CLUSTER ON {};", ids.name(table_name), ids.generated(&index))?;
+        writeln!(writer)?;
+    } else if old_key.is_cluster() && !new_key.is_cluster() && !rebuilt {
+        writeln!(writer, "ALTER TABLE {} SET WITHOUT CLUSTER;", ids.name(table_name))?;
+        writeln!(writer)?;
+    }
+    Ok(())
+}
+
+/// As in the create script, a compressed table stores its TOASTable columns with lz4; the
+/// others go back to pglz. `SET COMPRESSION` needs Postgres 14 and only affects new values.
+fn write_compression_change(
+    writer: &mut dyn Write,
+    ids: &Identifiers,
+    table_name: &str,
+    columns: &[Column],
+    compressed: bool,
+) -> Result<(), MigrationGeneratorError> {
+    let method = if compressed { "lz4" } else { "pglz" };
+    let toastable: Vec<&Column> = columns.iter().filter(|column| is_toastable(column.column_type())).collect();
+    if toastable.is_empty() {
+        return Ok(());
+    }
+    for column in toastable {
+        writeln!(
+            writer,
+            "ALTER TABLE {} ALTER COLUMN {} SET COMPRESSION {};",
+            ids.name(table_name),
+            ids.name(column.name()),
+            method
+        )?;
+    }
+    writeln!(writer)?;
+    Ok(())
+}
+
+fn is_toastable(column_type: ColumnType) -> bool {
+    matches!(
+        column_type,
+        ColumnType::Char
+            | ColumnType::Varchar
+            | ColumnType::Text
+            | ColumnType::CiText
+            | ColumnType::CsText
+            | ColumnType::Binary
+            | ColumnType::Json
+            | ColumnType::Array
+    )
+}
+
+fn write_drop_relation(
+    writer: &mut dyn Write,
+    ids: &Identifiers,
+    table_name: &str,
+    relation: &Relation,
+    position: usize,
+) -> Result<(), MigrationGeneratorError> {
+    writeln!(
+        writer,
+        "ALTER TABLE {} DROP CONSTRAINT {};",
+        ids.name(table_name),
+        ids.generated(&ids.foreign_key_name(table_name, relation, position))
+    )?;
+    writeln!(writer)?;
+    Ok(())
+}
+
+fn write_add_relation(
+    writer: &mut dyn Write,
+    ids: &Identifiers,
+    table_name: &str,
+    relation: &Relation,
+    position: usize,
+) -> Result<(), MigrationGeneratorError> {
+    let fk_name = ids.foreign_key_name(table_name, relation, position);
     let on_delete = match relation.relation_type() {
         RelationType::Cascade => " ON DELETE CASCADE",
         RelationType::SetNull => " ON DELETE SET NULL",
@@ -261,21 +836,13 @@ fn write_add_relation(writer: &mut dyn Write, relation: &Relation) -> Result<(),
     writeln!(
         writer,
         "ALTER TABLE {} ADD CONSTRAINT {} FOREIGN KEY ({}) REFERENCES {}({}){};",
-        relation.from_table_name(),
-        fk_name,
-        relation.from_column_name(),
-        relation.to_table_name(),
-        relation.to_column_name(),
+        ids.name(table_name),
+        ids.generated(&fk_name),
+        ids.list(&relation.from_column_names()),
+        ids.name(relation.to_table_name()),
+        ids.list(&relation.to_column_names()),
         on_delete
     )?;
     writeln!(writer)?;
     Ok(())
 }
-
-fn fk_constraint_name(relation: &Relation) -> String {
-    format!(
-        "fk_{}_{}",
-        relation.from_table_name(),
-        relation.from_column_name()
-    )
-}
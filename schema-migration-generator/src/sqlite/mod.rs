@@ -1,31 +1,73 @@
 use std::io::Write;
 
-use schema_diff::{ChangeSet, SchemaChange};
+use schema_diff::{ChangeSet, ColumnAttribute, SchemaChange};
 use schema_model::model::column::Column;
 use schema_model::model::column_type::ColumnType;
+use schema_model::model::enum_type::EnumType;
 use schema_model::model::key::Key;
 use schema_model::model::relation::Relation;
-use schema_model::model::types::{KeyType, RelationType};
+use schema_model::model::types::{DatabaseType, KeyType, RelationType, TableOption};
+use schema_sql_generator::common::generate_options::GenerateOptions;
+use schema_sql_generator::common::trigger_generator::TableTriggers;
 
 use crate::error::MigrationGeneratorError;
-use crate::migration_generator::MigrationGenerator;
+use crate::migration_generator::{
+    attributes_list, changed_table_triggers, column_check_sql, enum_values_sql, index_columns_sql, options_without_model,
+    qualified_name, same_key_sql, split_qualified_name, write_other_sql_removal, write_rename_candidates, Identifiers,
+    MigrationGenerator,
+};
 
-pub struct SqliteMigrationGenerator;
+pub struct SqliteMigrationGenerator {
+    options: GenerateOptions,
+}
+
+impl SqliteMigrationGenerator {
+    pub fn new(options: GenerateOptions) -> Self {
+        Self { options }
+    }
+}
+
+impl Default for SqliteMigrationGenerator {
+    fn default() -> Self {
+        Self::new(options_without_model())
+    }
+}
 
 impl MigrationGenerator for SqliteMigrationGenerator {
     fn generate(&self, change_set: &ChangeSet, writer: &mut dyn Write) -> Result<(), MigrationGeneratorError> {
+        let ids = Identifiers::new(DatabaseType::Sqlite, &self.options);
         for change in change_set.changes() {
             match change {
+                // A SQLite schema is an attached database file, which a migration script
+                // can't create or remove on its own.
+                SchemaChange::AddSchema { schema_name } => {
+                    writeln!(writer, "-- Schema '{}' is new; attach its database before running this script:", schema_name)?;
+                    writeln!(writer, "--   ATTACH DATABASE '{}.db' AS {};", schema_name, ids.generated(schema_name))?;
+                    writeln!(writer)?;
+                }
+                SchemaChange::DropSchema { schema_name } => {
+                    writeln!(writer, "-- Schema '{}' is gone; detach its database once this script has run:", schema_name)?;
+                    writeln!(writer, "--   DETACH DATABASE {};", ids.generated(schema_name))?;
+                    writeln!(writer)?;
+                }
+                SchemaChange::MoveTable { table_name, new_schema_name } => {
+                    return Err(MigrationGeneratorError::UnsupportedChange(format!(
+                        "{} moves to schema {}; SQLite can't move a table between attached databases, so it has to be recreated and its rows copied",
+                        table_name,
+                        new_schema_name.as_deref().unwrap_or("main")
+                    )));
+                }
                 SchemaChange::AddTable { table_name } => {
-                    writeln!(writer, "CREATE TABLE IF NOT EXISTS {} (id INTEGER PRIMARY KEY AUTOINCREMENT);", table_name)?;
+                    writeln!(writer, "CREATE TABLE IF NOT EXISTS {} (id INTEGER PRIMARY KEY AUTOINCREMENT);", ids.name(table_name))?;
                     writeln!(writer)?;
                 }
                 SchemaChange::DropTable { table_name } => {
-                    writeln!(writer, "DROP TABLE IF EXISTS {};", table_name)?;
+                    writeln!(writer, "DROP TABLE IF EXISTS {};", ids.name(table_name))?;
                     writeln!(writer)?;
                 }
                 SchemaChange::RenameTable { old_name, new_name } => {
-                    writeln!(writer, "ALTER TABLE {} RENAME TO {};", old_name, new_name)?;
+                    let (_, new_name) = split_qualified_name(new_name);
+                    writeln!(writer, "ALTER TABLE {} RENAME TO {};", ids.name(old_name), ids.name(new_name))?;
                     writeln!(writer)?;
                 }
                 SchemaChange::AddColumn { table_name, column } => {
@@ -38,8 +80,8 @@ impl MigrationGenerator for SqliteMigrationGenerator {
                     writeln!(
                         writer,
                         "ALTER TABLE {} ADD COLUMN {}{}{}{};",
-                        table_name,
-                        column.name(),
+                        ids.name(table_name),
+                        ids.name(column.name()),
                         type_sql,
                         not_null,
                         default
@@ -49,16 +91,14 @@ impl MigrationGenerator for SqliteMigrationGenerator {
                 // SQLite does not support DROP COLUMN before version 3.35.0.
                 // Generate a comment noting a manual table-rebuild may be needed.
                 SchemaChange::DropColumn { table_name, column_name, rename_candidates } => {
-                    if !rename_candidates.is_empty() {
-                        writeln!(writer, "-- TODO: possible rename? Consider replacing the DROP + ADD below with:")?;
-                        for candidate in rename_candidates {
-                            writeln!(writer, "--   ALTER TABLE {} RENAME COLUMN {} TO {};", table_name, column_name, candidate)?;
-                        }
-                    }
+                    write_rename_candidates(writer, column_name, rename_candidates, |candidate| {
+                        format!("ALTER TABLE {} RENAME COLUMN {} TO {};", ids.name(table_name), ids.name(column_name), ids.name(candidate))
+                    })?;
                     writeln!(
                         writer,
                         "-- SQLite 3.35+: ALTER TABLE {} DROP COLUMN {};",
-                        table_name, column_name
+                        ids.name(table_name),
+                        ids.name(column_name)
                     )?;
                     writeln!(writer, "-- For older SQLite: manually recreate the table without this column.")?;
                     writeln!(writer)?;
@@ -67,26 +107,81 @@ impl MigrationGenerator for SqliteMigrationGenerator {
                     writeln!(
                         writer,
                         "ALTER TABLE {} RENAME COLUMN {} TO {};",
-                        table_name, old_name, new_name
+                        ids.name(table_name),
+                        ids.name(old_name),
+                        ids.name(new_name)
                     )?;
                     writeln!(writer)?;
                 }
-                // SQLite does not support ALTER COLUMN — requires table rebuild.
-                SchemaChange::ModifyColumn { table_name, old_column: _, new_column } => {
-                    writeln!(
-                        writer,
-                        "-- SQLite does not support modifying column '{}' on table '{}' in-place.",
-                        new_column.name(),
-                        table_name
-                    )?;
-                    writeln!(writer, "-- Manually recreate the table with the updated column definition.")?;
+                // Compression and lock escalation don't apply to SQLite.
+                SchemaChange::ModifyTableOptions { table_name, old_options, new_options, .. } => {
+                    for (option, sql) in [(TableOption::Strict, "STRICT"), (TableOption::WithoutRowid, "WITHOUT ROWID")] {
+                        let added = new_options.contains(&option);
+                        if old_options.contains(&option) != added {
+                            writeln!(
+                                writer,
+                                "-- SQLite does not support changing the {} option of table '{}' in-place.",
+                                sql, table_name
+                            )?;
+                            let with = if added { "with" } else { "without" };
+                            writeln!(writer, "-- Manually recreate the table {} the {} option.", with, sql)?;
+                            writeln!(writer)?;
+                        }
+                    }
+                }
+                // SQLite does not support ALTER COLUMN — requires table rebuild. Identity
+                // options don't apply to an INTEGER column, so they are left out.
+                SchemaChange::ModifyColumn { table_name, old_column: _, new_column, changes, enum_type } => {
+                    let changes: Vec<ColumnAttribute> = changes.iter().copied().filter(|change| !change.is_identity()).collect();
+                    if !changes.is_empty() {
+                        writeln!(
+                            writer,
+                            "-- SQLite does not support modifying column '{}' on table '{}' in-place ({} changed).",
+                            new_column.name(),
+                            table_name,
+                            attributes_list(&changes)
+                        )?;
+                        writeln!(writer, "-- Manually recreate the table with the updated column definition:")?;
+                        writeln!(
+                            writer,
+                            "--   {}{}",
+                            ids.name(new_column.name()),
+                            column_definition_sql(&ids, new_column, enum_type.as_ref())
+                        )?;
+                        writeln!(writer)?;
+                    }
+                }
+                // SQLite has nowhere to store a description; the create script only carries
+                // them as SQL comments.
+                SchemaChange::ModifyDescription { table_name, column_name, old_description: _, new_description } => {
+                    let target = match column_name {
+                        Some(column_name) => format!("{}.{}", table_name, column_name),
+                        None => table_name.clone(),
+                    };
+                    match new_description {
+                        Some(description) => writeln!(writer, "-- {}: {}", target, description.replace('\n', "\n--   "))?,
+                        None => writeln!(writer, "-- {}: description removed", target)?,
+                    }
                     writeln!(writer)?;
                 }
-                SchemaChange::AddKey { table_name, key } => {
-                    write_add_key(writer, table_name, key)?;
+                SchemaChange::AddKey { table_name, key, position } => {
+                    write_add_key(writer, &ids, table_name, key, *position)?;
                 }
-                SchemaChange::DropKey { table_name, key } => {
-                    write_drop_key(writer, table_name, key)?;
+                SchemaChange::DropKey { table_name, key, position } => {
+                    write_drop_key(writer, &ids, table_name, key, *position)?;
+                }
+                // A key is only recreated when its changed options show in its definition here.
+                SchemaChange::ModifyKey { table_name, old_key, new_key, position } => {
+                    if !same_key_sql(write_add_key, &ids, table_name, old_key, *position, new_key, *position)? {
+                        write_drop_key(writer, &ids, table_name, old_key, *position)?;
+                        write_add_key(writer, &ids, table_name, new_key, *position)?;
+                    }
+                }
+                // SQLite cannot rename an index, so it is created again under its new name.
+                SchemaChange::RenameIndex { table_name, old_name, key, position } => {
+                    let (schema_name, _) = split_qualified_name(table_name);
+                    writeln!(writer, "DROP INDEX IF EXISTS {};", ids.generated(&qualified_name(schema_name, old_name)))?;
+                    write_add_key(writer, &ids, table_name, key, *position)?;
                 }
                 SchemaChange::AddConstraint { table_name, constraint } => {
                     writeln!(
@@ -108,34 +203,198 @@ impl MigrationGenerator for SqliteMigrationGenerator {
                     writeln!(writer, "-- Manually recreate the table without the constraint.")?;
                     writeln!(writer)?;
                 }
-                SchemaChange::AddRelation { relation } => {
-                    write_add_relation(writer, relation)?;
+                SchemaChange::RenameConstraint { table_name, old_name, new_name } => {
+                    writeln!(
+                        writer,
+                        "-- SQLite does not support renaming constraint '{}' on table '{}' in-place.",
+                        old_name,
+                        table_name
+                    )?;
+                    writeln!(writer, "-- Manually recreate the table with the constraint named '{}'.", new_name)?;
+                    writeln!(writer)?;
+                }
+                SchemaChange::AddRelation { table_name, relation, position } => {
+                    write_add_relation(writer, &ids, table_name, relation, *position)?;
                 }
-                SchemaChange::DropRelation { relation } => {
+                SchemaChange::DropRelation { table_name, relation, position } => {
                     writeln!(
                         writer,
-                        "-- SQLite does not support dropping foreign key on '{}.{}'.",
-                        relation.from_table_name(),
-                        relation.from_column_name()
+                        "-- SQLite does not support dropping foreign key {} on '{}.{}'.",
+                        ids.generated(&ids.foreign_key_name(table_name, relation, *position)),
+                        table_name,
+                        relation.from_columns_as_string()
                     )?;
                     writeln!(writer, "-- Manually recreate the table without this foreign key.")?;
                     writeln!(writer)?;
                 }
+                SchemaChange::ModifyRelation { table_name, old_relation: _, new_relation, position } => {
+                    writeln!(
+                        writer,
+                        "-- SQLite does not support changing the delete action of the foreign key on '{}.{}'.",
+                        table_name,
+                        new_relation.from_columns_as_string()
+                    )?;
+                    write_add_relation(writer, &ids, table_name, new_relation, *position)?;
+                }
+                // As in the create script, a materialized view is a table filled from the
+                // view's query; rerunning the DELETE/INSERT pair refreshes it.
+                SchemaChange::AddView { view } if view.is_materialized() => {
+                    let qualified_view_name = qualified_name(view.schema_name(), view.name());
+                    let view_name = ids.name(&qualified_view_name);
+                    writeln!(writer, "CREATE TABLE IF NOT EXISTS {} AS", view_name)?;
+                    writeln!(writer, "SELECT * FROM ({}) LIMIT 0;", view.sql())?;
+                    writeln!(writer)?;
+                    for (index, key) in view.indexes().iter().enumerate() {
+                        write_add_key(writer, &ids, &qualified_view_name, key, index + 1)?;
+                    }
+                    writeln!(writer, "DELETE FROM {};", view_name)?;
+                    writeln!(writer, "INSERT INTO {}", view_name)?;
+                    writeln!(writer, "{};", view.sql())?;
+                    writeln!(writer)?;
+                }
                 SchemaChange::AddView { view } => {
-                    writeln!(writer, "CREATE VIEW IF NOT EXISTS {} AS", view.name())?;
+                    let view_name = qualified_name(view.schema_name(), view.name());
+                    writeln!(writer, "CREATE VIEW IF NOT EXISTS {} AS", ids.name(&view_name))?;
                     writeln!(writer, "{};", view.sql())?;
                     writeln!(writer)?;
                 }
-                SchemaChange::DropView { view_name } => {
-                    writeln!(writer, "DROP VIEW IF EXISTS {};", view_name)?;
+                SchemaChange::DropView { view_name, materialized } => {
+                    let kind = if *materialized { "TABLE" } else { "VIEW" };
+                    writeln!(writer, "DROP {} IF EXISTS {};", kind, ids.name(view_name))?;
+                    writeln!(writer)?;
+                }
+                // Partitioning is only generated for PostgreSQL; the table here was created
+                // as a plain table and stays one.
+                SchemaChange::AddPartition { .. }
+                | SchemaChange::DropPartition { .. }
+                | SchemaChange::ModifyPartitioning { .. } => {}
+                // SQLite has no sequences; like the create script, a sequence is kept in a
+                // single-row table holding the last value handed out.
+                SchemaChange::AddSequence { sequence } => {
+                    let increment = sequence.increment().unwrap_or(1);
+                    let start = sequence.start().or(sequence.min_value()).unwrap_or(1);
+                    let sequence_name = ids.name(&qualified_name(sequence.schema_name(), sequence.name()));
+                    writeln!(writer, "CREATE TABLE IF NOT EXISTS {} (value INTEGER NOT NULL);", sequence_name)?;
+                    writeln!(writer, "INSERT INTO {} (value) VALUES ({});", sequence_name, start - increment)?;
+                    writeln!(writer)?;
+                }
+                SchemaChange::DropSequence { sequence_name } => {
+                    writeln!(writer, "DROP TABLE IF EXISTS {};", ids.name(sequence_name))?;
+                    writeln!(writer)?;
+                }
+                SchemaChange::ModifySequence { old_sequence: _, new_sequence } => {
+                    writeln!(
+                        writer,
+                        "-- Sequence '{}' changed; its SQLite table keeps the current value and the new options are applied by the application.",
+                        new_sequence.name()
+                    )?;
+                    writeln!(writer)?;
+                }
+                // SQLite has no enum types; an enum column is a text column whose check
+                // constraint is part of the table.
+                SchemaChange::AddEnumType { .. } | SchemaChange::DropEnumType { .. } => {}
+                SchemaChange::ModifyEnumType { new_enum_type, columns, .. } => {
+                    for (table_name, column) in columns {
+                        writeln!(
+                            writer,
+                            "-- SQLite does not support altering the check constraint on '{}.{}'.",
+                            table_name,
+                            column.name()
+                        )?;
+                        writeln!(
+                            writer,
+                            "-- Manually recreate the table with CHECK ({} IN ({})).",
+                            ids.name(column.name()),
+                            enum_values_sql(new_enum_type)
+                        )?;
+                    }
+                    writeln!(writer)?;
+                }
+                // SQLite has no functions to replace or drop; the model's SQL is run as it is.
+                SchemaChange::AddFunction { function: program } | SchemaChange::ModifyFunction { new_function: program, .. }
+                    if program.database_type() == DatabaseType::Sqlite =>
+                {
+                    writeln!(writer, "{};", program.sql().trim().trim_end_matches(';'))?;
+                    writeln!(writer)?;
+                }
+                SchemaChange::AddProcedure { procedure: program } | SchemaChange::ModifyProcedure { new_procedure: program, .. }
+                    if program.database_type() == DatabaseType::Sqlite =>
+                {
+                    return Err(MigrationGeneratorError::UnsupportedChange(format!(
+                        "procedure {}: SQLite has no stored procedures",
+                        qualified_name(program.schema_name(), program.name())
+                    )));
+                }
+                SchemaChange::AddOtherSql { other_sql } if other_sql.database_type() == DatabaseType::Sqlite => {
+                    writeln!(writer, "{};", other_sql.sql().trim().trim_end_matches(';'))?;
                     writeln!(writer)?;
                 }
+                SchemaChange::DropOtherSql { other_sql } if other_sql.database_type() == DatabaseType::Sqlite => {
+                    write_other_sql_removal(writer, DatabaseType::Sqlite, other_sql.sql(), ";")?;
+                }
+                // Written with the table's regenerated triggers below.
+                SchemaChange::AddTrigger { .. }
+                | SchemaChange::DropTrigger { .. }
+                | SchemaChange::AddAggregation { .. }
+                | SchemaChange::DropAggregation { .. } => {}
+                // Programs and SQL written for another database.
+                SchemaChange::AddFunction { .. }
+                | SchemaChange::DropFunction { .. }
+                | SchemaChange::ModifyFunction { .. }
+                | SchemaChange::AddProcedure { .. }
+                | SchemaChange::DropProcedure { .. }
+                | SchemaChange::ModifyProcedure { .. }
+                | SchemaChange::AddOtherSql { .. }
+                | SchemaChange::DropOtherSql { .. } => {}
             }
         }
+        for (table_name, triggers) in changed_table_triggers(&self.options, DatabaseType::Sqlite, change_set)? {
+            write_table_triggers(writer, &ids, &table_name, &triggers)?;
+        }
         Ok(())
     }
 }
 
+/// Replaces the generated triggers of `table_name` with the ones the create script writes
+/// now, dropping the ones the table no longer needs.
+fn write_table_triggers(
+    writer: &mut dyn Write,
+    ids: &Identifiers,
+    table_name: &str,
+    triggers: &TableTriggers,
+) -> Result<(), MigrationGeneratorError> {
+    let (schema_name, _) = split_qualified_name(table_name);
+    for trigger_name in &triggers.unneeded_names {
+        writeln!(writer, "DROP TRIGGER IF EXISTS {};", ids.generated(&qualified_name(schema_name, trigger_name)))?;
+        writeln!(writer)?;
+    }
+    for statement in &triggers.statements {
+        writeln!(writer, "{};", statement.sql)?;
+        writeln!(writer)?;
+    }
+    Ok(())
+}
+
+/// The column as the create script defines it in its table: type, nullability, default or
+/// generation expression, and check. Enum columns are text checked against the codes of
+/// `enum_type`.
+fn column_definition_sql(ids: &Identifiers, column: &Column, enum_type: Option<&EnumType>) -> String {
+    let mut sql = column_type_sql(column);
+    if column.required() {
+        sql.push_str(" NOT NULL");
+    }
+    if let Some(expression) = column.generated() {
+        sql.push_str(&format!(" GENERATED ALWAYS AS ({}) STORED", expression));
+    } else if let Some(default) = column.default_constraint() {
+        sql.push_str(&format!(" DEFAULT {}", default));
+    }
+    if let Some(check) = column_check_sql(ids, column, enum_type) {
+        sql.push(' ');
+        sql.push_str(&check);
+    }
+    sql
+}
+
 fn column_type_sql(column: &Column) -> String {
     match column.column_type() {
         ColumnType::Sequence | ColumnType::LongSequence => " INTEGER".to_string(),
@@ -153,9 +412,13 @@ fn column_type_sql(column: &Column) -> String {
     }
 }
 
-fn write_add_key(writer: &mut dyn Write, table_name: &str, key: &Key) -> Result<(), MigrationGeneratorError> {
-    let col_names: Vec<&str> = key.columns().iter().map(|c| c.name()).collect();
-    let cols = col_names.join(", ");
+fn write_add_key(
+    writer: &mut dyn Write,
+    ids: &Identifiers,
+    table_name: &str,
+    key: &Key,
+    position: usize,
+) -> Result<(), MigrationGeneratorError> {
     match key.key_type() {
         KeyType::Primary => {
             writeln!(
@@ -165,20 +428,20 @@ fn write_add_key(writer: &mut dyn Write, table_name: &str, key: &Key) -> Result<
             )?;
             writeln!(writer, "-- Manually recreate the table with the primary key.")?;
         }
-        KeyType::Unique => {
-            let idx_name = format!("idx_{}_{}", table_name, col_names.join("_"));
-            writeln!(
-                writer,
-                "CREATE UNIQUE INDEX IF NOT EXISTS {} ON {} ({});",
-                idx_name, table_name, cols
-            )?;
-        }
-        KeyType::Index => {
-            let idx_name = format!("idx_{}_{}", table_name, col_names.join("_"));
+        // SQLite has a single index implementation, so an index method is not rendered. The
+        // schema goes on the index; its table has to be named without one.
+        KeyType::Unique | KeyType::Index => {
+            let (schema_name, bare_table_name) = split_qualified_name(table_name);
+            let unique = if key.key_type() == KeyType::Unique || key.is_unique() { "UNIQUE " } else { "" };
+            let filter = key.filter().map(|f| format!(" WHERE {}", f)).unwrap_or_default();
             writeln!(
                 writer,
-                "CREATE INDEX IF NOT EXISTS {} ON {} ({});",
-                idx_name, table_name, cols
+                "CREATE {}INDEX IF NOT EXISTS {} ON {} ({}){};",
+                unique,
+                ids.generated(&qualified_name(schema_name, &ids.key_name(table_name, key, position))),
+                ids.name(bare_table_name),
+                index_columns_sql(ids, key, true),
+                filter
             )?;
         }
     }
@@ -186,8 +449,13 @@ fn write_add_key(writer: &mut dyn Write, table_name: &str, key: &Key) -> Result<
     Ok(())
 }
 
-fn write_drop_key(writer: &mut dyn Write, table_name: &str, key: &Key) -> Result<(), MigrationGeneratorError> {
-    let col_names: Vec<&str> = key.columns().iter().map(|c| c.name()).collect();
+fn write_drop_key(
+    writer: &mut dyn Write,
+    ids: &Identifiers,
+    table_name: &str,
+    key: &Key,
+    position: usize,
+) -> Result<(), MigrationGeneratorError> {
     match key.key_type() {
         KeyType::Primary => {
             writeln!(
@@ -198,15 +466,29 @@ fn write_drop_key(writer: &mut dyn Write, table_name: &str, key: &Key) -> Result
             writeln!(writer, "-- Manually recreate the table without the primary key.")?;
         }
         KeyType::Unique | KeyType::Index => {
-            let idx_name = format!("idx_{}_{}", table_name, col_names.join("_"));
-            writeln!(writer, "DROP INDEX IF EXISTS {};", idx_name)?;
+            let (schema_name, _) = split_qualified_name(table_name);
+            let key_name = ids.key_name(table_name, key, position);
+            if key.key_type() == KeyType::Unique {
+                writeln!(
+                    writer,
+                    "-- A unique constraint {} declared in the CREATE TABLE statement for '{}' can only be dropped by recreating the table.",
+                    key_name, table_name
+                )?;
+            }
+            writeln!(writer, "DROP INDEX IF EXISTS {};", ids.generated(&qualified_name(schema_name, &key_name)))?;
         }
     }
     writeln!(writer)?;
     Ok(())
 }
 
-fn write_add_relation(writer: &mut dyn Write, relation: &Relation) -> Result<(), MigrationGeneratorError> {
+fn write_add_relation(
+    writer: &mut dyn Write,
+    ids: &Identifiers,
+    table_name: &str,
+    relation: &Relation,
+    position: usize,
+) -> Result<(), MigrationGeneratorError> {
     let on_delete = match relation.relation_type() {
         RelationType::Cascade => " ON DELETE CASCADE",
         RelationType::SetNull => " ON DELETE SET NULL",
@@ -219,12 +501,13 @@ fn write_add_relation(writer: &mut dyn Write, relation: &Relation) -> Result<(),
     )?;
     writeln!(
         writer,
-        "-- Ensure FOREIGN KEY ({}) REFERENCES {}({}){} is in the CREATE TABLE statement for '{}'.",
-        relation.from_column_name(),
-        relation.to_table_name(),
-        relation.to_column_name(),
+        "-- Ensure CONSTRAINT {} FOREIGN KEY ({}) REFERENCES {}({}){} is in the CREATE TABLE statement for '{}'.",
+        ids.generated(&ids.foreign_key_name(table_name, relation, position)),
+        ids.list(&relation.from_column_names()),
+        ids.name(relation.to_table_name()),
+        ids.list(&relation.to_column_names()),
         on_delete,
-        relation.from_table_name()
+        table_name
     )?;
     writeln!(writer)?;
     Ok(())
@@ -1,27 +1,73 @@
 use std::io::Write;
 
-use schema_diff::{ChangeSet, SchemaChange};
+use schema_diff::{ChangeSet, ColumnAttribute, SchemaChange};
 use schema_model::model::column::Column;
 use schema_model::model::column_type::ColumnType;
+use schema_model::model::enum_type::EnumType;
 use schema_model::model::key::Key;
 use schema_model::model::relation::Relation;
-use schema_model::model::types::{KeyType, RelationType};
+use schema_model::model::types::{CompressionLevel, DatabaseType, KeyType, LockEscalation, RelationType, TableOption};
+use schema_model::model::view::View;
+use schema_sql_generator::common::generate_options::GenerateOptions;
+use schema_sql_generator::common::trigger_generator::TableTriggers;
 
 use crate::error::MigrationGeneratorError;
-use crate::migration_generator::MigrationGenerator;
+use crate::migration_generator::{
+    alter_sequence_clauses, changed_table_triggers, column_check_sql, column_default_sql, enum_values_sql, include_columns,
+    index_columns_sql, options_without_model, qualified_name, replacing_create, same_key_sql, split_qualified_name,
+    starts_with_create, write_other_sql_removal, write_rename_candidates, Identifiers, MigrationGenerator,
+};
 
-pub struct SqlServerMigrationGenerator;
+pub struct SqlServerMigrationGenerator {
+    options: GenerateOptions,
+}
+
+impl SqlServerMigrationGenerator {
+    pub fn new(options: GenerateOptions) -> Self {
+        Self { options }
+    }
+}
+
+impl Default for SqlServerMigrationGenerator {
+    fn default() -> Self {
+        Self::new(options_without_model())
+    }
+}
 
 impl MigrationGenerator for SqlServerMigrationGenerator {
     fn generate(&self, change_set: &ChangeSet, writer: &mut dyn Write) -> Result<(), MigrationGeneratorError> {
+        let ids = Identifiers::new(DatabaseType::SqlServer, &self.options);
         for change in change_set.changes() {
             match change {
+                // `CREATE SCHEMA` has to be the only statement in its batch, hence `EXEC`.
+                SchemaChange::AddSchema { schema_name } => {
+                    writeln!(
+                        writer,
+                        "IF SCHEMA_ID('{}') IS NULL EXEC('CREATE SCHEMA {}');",
+                        schema_name.replace('\'', "''"),
+                        ids.generated(schema_name)
+                    )?;
+                    writeln!(writer, "GO")?;
+                    writeln!(writer)?;
+                }
+                SchemaChange::DropSchema { schema_name } => {
+                    writeln!(writer, "DROP SCHEMA IF EXISTS {};", ids.generated(schema_name))?;
+                    writeln!(writer, "GO")?;
+                    writeln!(writer)?;
+                }
+                SchemaChange::MoveTable { table_name, new_schema_name } => {
+                    let new_schema_name = new_schema_name.as_deref().unwrap_or("dbo");
+                    writeln!(writer, "ALTER SCHEMA {} TRANSFER {};", ids.generated(new_schema_name), ids.name(table_name))?;
+                    writeln!(writer, "GO")?;
+                    writeln!(writer)?;
+                }
                 SchemaChange::AddTable { table_name } => {
-                    writeln!(writer, "CREATE TABLE {} ();", table_name)?;
+                    writeln!(writer, "CREATE TABLE {} ();", ids.name(table_name))?;
                     writeln!(writer, "GO")?;
                     writeln!(writer)?;
                 }
                 SchemaChange::DropTable { table_name } => {
+                    let table_name = ids.name(table_name);
                     writeln!(
                         writer,
                         "IF OBJECT_ID('{}', 'U') IS NOT NULL DROP TABLE {};",
@@ -30,38 +76,30 @@ impl MigrationGenerator for SqlServerMigrationGenerator {
                     writeln!(writer, "GO")?;
                     writeln!(writer)?;
                 }
+                // sp_rename takes the new name as it is, unquoted and without the schema the
+                // table stays in.
                 SchemaChange::RenameTable { old_name, new_name } => {
-                    writeln!(writer, "EXEC sp_rename '{}', '{}';", old_name, new_name)?;
+                    let (_, new_name) = split_qualified_name(new_name);
+                    writeln!(writer, "EXEC sp_rename '{}', '{}';", ids.name(old_name), ids.database_name(new_name))?;
                     writeln!(writer, "GO")?;
                     writeln!(writer)?;
                 }
                 SchemaChange::AddColumn { table_name, column } => {
-                    let type_sql = column_type_sql(column);
-                    let not_null = if column.required() { " NOT NULL" } else { " NULL" };
-                    let default = column
-                        .default_constraint()
-                        .map(|d| format!(" DEFAULT {}", d))
-                        .unwrap_or_default();
                     writeln!(
                         writer,
-                        "ALTER TABLE {} ADD {}{}{}{};",
-                        table_name,
-                        column.name(),
-                        type_sql,
-                        not_null,
-                        default
+                        "ALTER TABLE {} ADD {}{};",
+                        ids.name(table_name),
+                        ids.name(column.name()),
+                        column_definition_sql(&ids, column, None)
                     )?;
                     writeln!(writer, "GO")?;
                     writeln!(writer)?;
                 }
                 SchemaChange::DropColumn { table_name, column_name, rename_candidates } => {
-                    if !rename_candidates.is_empty() {
-                        writeln!(writer, "-- TODO: possible rename? Consider replacing the DROP + ADD below with:")?;
-                        for candidate in rename_candidates {
-                            writeln!(writer, "--   EXEC sp_rename '{}.{}', '{}', 'COLUMN';", table_name, column_name, candidate)?;
-                        }
-                    }
-                    writeln!(writer, "ALTER TABLE {} DROP COLUMN {};", table_name, column_name)?;
+                    write_rename_candidates(writer, column_name, rename_candidates, |candidate| {
+                        format!("EXEC sp_rename '{}.{}', '{}', 'COLUMN';", ids.name(table_name), ids.name(column_name), ids.database_name(candidate))
+                    })?;
+                    writeln!(writer, "ALTER TABLE {} DROP COLUMN {};", ids.name(table_name), ids.name(column_name))?;
                     writeln!(writer, "GO")?;
                     writeln!(writer)?;
                 }
@@ -69,37 +107,55 @@ impl MigrationGenerator for SqlServerMigrationGenerator {
                     writeln!(
                         writer,
                         "EXEC sp_rename '{}.{}', '{}', 'COLUMN';",
-                        table_name, old_name, new_name
+                        ids.name(table_name),
+                        ids.name(old_name),
+                        ids.database_name(new_name)
                     )?;
                     writeln!(writer, "GO")?;
                     writeln!(writer)?;
                 }
-                SchemaChange::ModifyColumn { table_name, old_column: _, new_column } => {
-                    let type_sql = column_type_sql(new_column);
-                    let null = if new_column.required() { " NOT NULL" } else { " NULL" };
+                SchemaChange::ModifyTableOptions {
+                    table_name,
+                    old_options,
+                    new_options,
+                    old_lock_escalation,
+                    new_lock_escalation,
+                    columns: _,
+                } => {
+                    write_modify_table_options(writer, &ids, table_name, old_options, new_options, *old_lock_escalation, *new_lock_escalation)?;
+                }
+                SchemaChange::ModifyColumn { table_name, old_column, new_column, changes, enum_type } => {
+                    write_modify_column(writer, &ids, table_name, old_column, new_column, changes, enum_type.as_ref())?;
+                }
+                SchemaChange::ModifyDescription { table_name, column_name, old_description, new_description } => {
+                    write_description(writer, &ids, table_name, column_name.as_deref(), old_description.as_deref(), new_description.as_deref())?;
+                }
+                SchemaChange::AddKey { table_name, key, position } => {
+                    write_add_key(writer, &ids, table_name, key, *position)?;
+                }
+                SchemaChange::DropKey { table_name, key, position } => {
+                    write_drop_key(writer, &ids, table_name, key, *position)?;
+                }
+                SchemaChange::ModifyKey { table_name, old_key, new_key, position } => {
+                    write_modify_key(writer, &ids, table_name, old_key, new_key, *position)?;
+                }
+                SchemaChange::RenameIndex { table_name, old_name, key, position } => {
                     writeln!(
                         writer,
-                        "ALTER TABLE {} ALTER COLUMN {}{}{};",
-                        table_name,
-                        new_column.name(),
-                        type_sql,
-                        null
+                        "EXEC sp_rename '{}.{}', '{}', 'INDEX';",
+                        ids.name(table_name),
+                        ids.generated(old_name),
+                        ids.key_name(table_name, key, *position)
                     )?;
                     writeln!(writer, "GO")?;
                     writeln!(writer)?;
                 }
-                SchemaChange::AddKey { table_name, key } => {
-                    write_add_key(writer, table_name, key)?;
-                }
-                SchemaChange::DropKey { table_name, key } => {
-                    write_drop_key(writer, table_name, key)?;
-                }
                 SchemaChange::AddConstraint { table_name, constraint } => {
                     writeln!(
                         writer,
                         "ALTER TABLE {} ADD CONSTRAINT {} CHECK ({});",
-                        table_name,
-                        constraint.name(),
+                        ids.name(table_name),
+                        ids.name(constraint.name()),
                         constraint.sql()
                     )?;
                     writeln!(writer, "GO")?;
@@ -109,32 +165,50 @@ impl MigrationGenerator for SqlServerMigrationGenerator {
                     writeln!(
                         writer,
                         "ALTER TABLE {} DROP CONSTRAINT {};",
-                        table_name, constraint_name
+                        ids.name(table_name),
+                        ids.name(constraint_name)
                     )?;
                     writeln!(writer, "GO")?;
                     writeln!(writer)?;
                 }
-                SchemaChange::AddRelation { relation } => {
-                    write_add_relation(writer, relation)?;
-                }
-                SchemaChange::DropRelation { relation } => {
-                    let fk_name = fk_constraint_name(relation);
+                // A constraint is an object of its table's schema, so it is named through that.
+                SchemaChange::RenameConstraint { table_name, old_name, new_name } => {
+                    let (schema_name, _) = split_qualified_name(table_name);
                     writeln!(
                         writer,
-                        "ALTER TABLE {} DROP CONSTRAINT {};",
-                        relation.from_table_name(),
-                        fk_name
+                        "EXEC sp_rename '{}', '{}', 'OBJECT';",
+                        ids.name(&qualified_name(schema_name, old_name)),
+                        ids.database_name(new_name)
                     )?;
                     writeln!(writer, "GO")?;
                     writeln!(writer)?;
                 }
+                SchemaChange::AddRelation { table_name, relation, position } => {
+                    write_add_relation(writer, &ids, table_name, relation, *position)?;
+                }
+                SchemaChange::DropRelation { table_name, relation, position } => {
+                    write_drop_relation(writer, &ids, table_name, relation, *position)?;
+                }
+                SchemaChange::ModifyRelation { table_name, old_relation, new_relation, position } => {
+                    write_drop_relation(writer, &ids, table_name, old_relation, *position)?;
+                    write_add_relation(writer, &ids, table_name, new_relation, *position)?;
+                }
+                SchemaChange::AddView { view } if view.is_materialized() => {
+                    write_add_indexed_view(writer, &ids, view)?;
+                }
                 SchemaChange::AddView { view } => {
-                    writeln!(writer, "CREATE OR ALTER VIEW {} AS", view.name())?;
+                    writeln!(
+                        writer,
+                        "CREATE OR ALTER VIEW {} AS",
+                        ids.name(&qualified_name(view.schema_name(), view.name()))
+                    )?;
                     writeln!(writer, "{};", view.sql())?;
                     writeln!(writer, "GO")?;
                     writeln!(writer)?;
                 }
-                SchemaChange::DropView { view_name } => {
+                // Dropping an indexed view drops its indexes along with it.
+                SchemaChange::DropView { view_name, .. } => {
+                    let view_name = ids.name(view_name);
                     writeln!(
                         writer,
                         "IF OBJECT_ID('{}', 'V') IS NOT NULL DROP VIEW {};",
@@ -143,16 +217,153 @@ impl MigrationGenerator for SqlServerMigrationGenerator {
                     writeln!(writer, "GO")?;
                     writeln!(writer)?;
                 }
+                // Partitioning is only generated for PostgreSQL; the table here was created
+                // as a plain table and stays one.
+                SchemaChange::AddPartition { .. }
+                | SchemaChange::DropPartition { .. }
+                | SchemaChange::ModifyPartitioning { .. } => {}
+                SchemaChange::AddSequence { sequence } => {
+                    // Without an explicit start a SQL Server sequence begins at the type's
+                    // minimum rather than at 1.
+                    let start = if sequence.start().is_none() && sequence.min_value().is_none() {
+                        " START WITH 1"
+                    } else {
+                        ""
+                    };
+                    writeln!(
+                        writer,
+                        "CREATE SEQUENCE {} AS bigint{}{};",
+                        ids.name(&qualified_name(sequence.schema_name(), sequence.name())),
+                        start,
+                        sequence.options_sql().to_uppercase()
+                    )?;
+                    writeln!(writer, "GO")?;
+                    writeln!(writer)?;
+                }
+                SchemaChange::DropSequence { sequence_name } => {
+                    let sequence_name = ids.name(sequence_name);
+                    writeln!(
+                        writer,
+                        "IF OBJECT_ID('{}', 'SO') IS NOT NULL DROP SEQUENCE {};",
+                        sequence_name, sequence_name
+                    )?;
+                    writeln!(writer, "GO")?;
+                    writeln!(writer)?;
+                }
+                // SQL Server can only RESTART a sequence, which would reset its current
+                // value, so a changed start value is not applied.
+                SchemaChange::ModifySequence { old_sequence, new_sequence } => {
+                    let clauses = alter_sequence_clauses(old_sequence, new_sequence, "CACHE");
+                    if !clauses.is_empty() {
+                        let sequence_name = qualified_name(new_sequence.schema_name(), new_sequence.name());
+                        writeln!(writer, "ALTER SEQUENCE {} {};", ids.name(&sequence_name), clauses.join(" "))?;
+                        writeln!(writer, "GO")?;
+                        writeln!(writer)?;
+                    }
+                }
+                // SQL Server has no enum types; an enum column is a character column with a
+                // check constraint on the codes.
+                SchemaChange::AddEnumType { .. } | SchemaChange::DropEnumType { .. } => {}
+                SchemaChange::ModifyEnumType { old_enum_type, new_enum_type, columns } => {
+                    for (table_name, column) in columns {
+                        write_enum_column_change(writer, &ids, table_name, column, old_enum_type, new_enum_type)?;
+                    }
+                }
+                SchemaChange::AddFunction { function: program } | SchemaChange::ModifyFunction { new_function: program, .. }
+                    if program.database_type() == DatabaseType::SqlServer =>
+                {
+                    writeln!(writer, "{}", replacing_create(program.sql(), "CREATE OR ALTER"))?;
+                    writeln!(writer, "GO")?;
+                    writeln!(writer)?;
+                }
+                SchemaChange::DropFunction { function } if function.database_type() == DatabaseType::SqlServer => {
+                    let function_name = qualified_name(function.schema_name(), function.name());
+                    writeln!(writer, "DROP FUNCTION IF EXISTS {};", ids.generated(&function_name))?;
+                    writeln!(writer, "GO")?;
+                    writeln!(writer)?;
+                }
+                SchemaChange::AddProcedure { procedure: program } | SchemaChange::ModifyProcedure { new_procedure: program, .. }
+                    if program.database_type() == DatabaseType::SqlServer =>
+                {
+                    writeln!(writer, "{}", replacing_create(program.sql(), "CREATE OR ALTER"))?;
+                    writeln!(writer, "GO")?;
+                    writeln!(writer)?;
+                }
+                SchemaChange::DropProcedure { procedure } if procedure.database_type() == DatabaseType::SqlServer => {
+                    let procedure_name = qualified_name(procedure.schema_name(), procedure.name());
+                    writeln!(writer, "DROP PROCEDURE IF EXISTS {};", ids.generated(&procedure_name))?;
+                    writeln!(writer, "GO")?;
+                    writeln!(writer)?;
+                }
+                SchemaChange::AddOtherSql { other_sql } if other_sql.database_type() == DatabaseType::SqlServer => {
+                    writeln!(writer, "{}", other_sql.sql().trim())?;
+                    writeln!(writer, "GO")?;
+                    writeln!(writer)?;
+                }
+                SchemaChange::DropOtherSql { other_sql } if other_sql.database_type() == DatabaseType::SqlServer => {
+                    write_other_sql_removal(writer, DatabaseType::SqlServer, other_sql.sql(), ";\nGO")?;
+                }
+                // Written with the table's regenerated triggers below.
+                SchemaChange::AddTrigger { .. }
+                | SchemaChange::DropTrigger { .. }
+                | SchemaChange::AddAggregation { .. }
+                | SchemaChange::DropAggregation { .. } => {}
+                // Programs and SQL written for another database.
+                SchemaChange::AddFunction { .. }
+                | SchemaChange::DropFunction { .. }
+                | SchemaChange::ModifyFunction { .. }
+                | SchemaChange::AddProcedure { .. }
+                | SchemaChange::DropProcedure { .. }
+                | SchemaChange::ModifyProcedure { .. }
+                | SchemaChange::AddOtherSql { .. }
+                | SchemaChange::DropOtherSql { .. } => {}
             }
         }
+        for (table_name, triggers) in changed_table_triggers(&self.options, DatabaseType::SqlServer, change_set)? {
+            write_table_triggers(writer, &ids, &table_name, &triggers)?;
+        }
         Ok(())
     }
 }
 
+/// Replaces the generated triggers of `table_name` with the ones the create script writes
+/// now: the ones the table no longer needs are dropped and the others altered in place,
+/// so the create script's drops ahead of each trigger are left out.
+fn write_table_triggers(
+    writer: &mut dyn Write,
+    ids: &Identifiers,
+    table_name: &str,
+    triggers: &TableTriggers,
+) -> Result<(), MigrationGeneratorError> {
+    let (schema_name, _) = split_qualified_name(table_name);
+    for trigger_name in &triggers.unneeded_names {
+        writeln!(writer, "DROP TRIGGER IF EXISTS {};", ids.generated(&qualified_name(schema_name, trigger_name)))?;
+        writeln!(writer, "GO")?;
+        writeln!(writer)?;
+    }
+    for statement in &triggers.statements {
+        let sql = statement.sql_after_comments();
+        if starts_with_create(sql) {
+            writeln!(writer, "{}", replacing_create(sql, "CREATE OR ALTER"))?;
+            writeln!(writer, "GO")?;
+            writeln!(writer)?;
+        }
+    }
+    Ok(())
+}
+
+fn identity_sql(column: &Column) -> String {
+    format!(
+        "identity({},{})",
+        column.identity_start().unwrap_or(1),
+        column.identity_increment().unwrap_or(1)
+    )
+}
+
 fn column_type_sql(column: &Column) -> String {
     match column.column_type() {
-        ColumnType::Sequence => " integer identity(1,1)".to_string(),
-        ColumnType::LongSequence => " bigint identity(1,1)".to_string(),
+        ColumnType::Sequence => format!(" integer {}", identity_sql(column)),
+        ColumnType::LongSequence => format!(" bigint {}", identity_sql(column)),
         ColumnType::Byte => " smallint".to_string(),
         ColumnType::Short => " smallint".to_string(),
         ColumnType::Int => " integer".to_string(),
@@ -192,27 +403,45 @@ fn column_type_sql(column: &Column) -> String {
     }
 }
 
-fn write_add_key(writer: &mut dyn Write, table_name: &str, key: &Key) -> Result<(), MigrationGeneratorError> {
-    let col_names: Vec<&str> = key.columns().iter().map(|c| c.name()).collect();
-    let cols = col_names.join(", ");
-    match key.key_type() {
-        KeyType::Primary => {
-            writeln!(writer, "ALTER TABLE {} ADD PRIMARY KEY ({});", table_name, cols)?;
+/// Descriptions live in the `MS_Description` extended property, which has to be added,
+/// updated or dropped depending on whether the table/column had one before.
+fn write_description(
+    writer: &mut dyn Write,
+    ids: &Identifiers,
+    table_name: &str,
+    column_name: Option<&str>,
+    old_description: Option<&str>,
+    new_description: Option<&str>,
+) -> Result<(), MigrationGeneratorError> {
+    let literal = |value: &str| value.replace('\'', "''");
+    let table_name = ids.database_name(table_name);
+    let (schema_name, table_name) = table_name.split_once('.').unwrap_or(("dbo", &table_name));
+    let mut levels = format!(
+        "@level0type = N'SCHEMA', @level0name = N'{}', @level1type = N'TABLE', @level1name = N'{}'",
+        literal(schema_name),
+        literal(table_name)
+    );
+    if let Some(column_name) = column_name {
+        levels.push_str(&format!(", @level2type = N'COLUMN', @level2name = N'{}'", literal(&ids.database_name(column_name))));
+    }
+    match (old_description, new_description) {
+        (_, None) => {
+            writeln!(writer, "EXEC sp_dropextendedproperty @name = N'MS_Description', {};", levels)?;
         }
-        KeyType::Unique => {
-            let idx_name = format!("idx_{}_{}", table_name, col_names.join("_"));
+        (None, Some(description)) => {
             writeln!(
                 writer,
-                "CREATE UNIQUE INDEX {} ON {} ({});",
-                idx_name, table_name, cols
+                "EXEC sp_addextendedproperty @name = N'MS_Description', @value = N'{}', {};",
+                literal(description),
+                levels
             )?;
         }
-        KeyType::Index => {
-            let idx_name = format!("idx_{}_{}", table_name, col_names.join("_"));
+        (Some(_), Some(description)) => {
             writeln!(
                 writer,
-                "CREATE INDEX {} ON {} ({});",
-                idx_name, table_name, cols
+                "EXEC sp_updateextendedproperty @name = N'MS_Description', @value = N'{}', {};",
+                literal(description),
+                levels
             )?;
         }
     }
@@ -221,22 +450,267 @@ fn write_add_key(writer: &mut dyn Write, table_name: &str, key: &Key) -> Result<
     Ok(())
 }
 
-fn write_drop_key(writer: &mut dyn Write, table_name: &str, key: &Key) -> Result<(), MigrationGeneratorError> {
-    let col_names: Vec<&str> = key.columns().iter().map(|c| c.name()).collect();
+/// The type, nullability and default of `column`, as `ADD` takes them; a generated column
+/// is a persisted computed column instead. An enum column is typed after `enum_type`
+/// when it is known.
+fn column_definition_sql(ids: &Identifiers, column: &Column, enum_type: Option<&EnumType>) -> String {
+    if let Some(expression) = column.generated() {
+        let not_null = if column.required() { " NOT NULL" } else { "" };
+        return format!(" AS ({}) PERSISTED{}", expression, not_null);
+    }
+    let not_null = if column.required() { " NOT NULL" } else { " NULL" };
+    let default = column_default_sql(ids, column, column.sequence()).map(|d| format!(" DEFAULT {}", d)).unwrap_or_default();
+    format!("{}{}{}", modified_column_type_sql(column, enum_type), not_null, default)
+}
+
+/// The column's type, with an enum column sized to the codes of `enum_type` as the create
+/// script sizes it.
+fn modified_column_type_sql(column: &Column, enum_type: Option<&EnumType>) -> String {
+    match enum_type {
+        Some(enum_type) if column.column_type() == ColumnType::Enum => format!(" {}", enum_column_type_sql(enum_type)),
+        _ => column_type_sql(column),
+    }
+}
+
+/// Alters a column one changed attribute at a time, in one batch. SQL Server won't alter
+/// or drop a column that a check or default constraint depends on, so those are dropped
+/// first and added again at the end. A column that becomes or stops being a computed
+/// column, or whose expression changes, is dropped and added again. Identity options
+/// can't be altered at all and are left as a note.
+fn write_modify_column(
+    writer: &mut dyn Write,
+    ids: &Identifiers,
+    table_name: &str,
+    old_column: &Column,
+    new_column: &Column,
+    changes: &[ColumnAttribute],
+    enum_type: Option<&EnumType>,
+) -> Result<(), MigrationGeneratorError> {
+    let table = ids.name(table_name);
+    let column = ids.name(new_column.name());
+    let rebuilt = changes.contains(&ColumnAttribute::Generated);
+    let type_changed = changes.iter().any(|change| change.is_type());
+    let check_changed = rebuilt
+        || changes.iter().any(|change| change.is_check())
+        || (changes.contains(&ColumnAttribute::EnumType) && enum_type.is_some());
+    let default_changed = rebuilt
+        || changes.contains(&ColumnAttribute::Default)
+        || changes.contains(&ColumnAttribute::Sequence)
+        || (type_changed && column_default_sql(ids, old_column, old_column.sequence()).is_some());
+
+    if check_changed {
+        write_drop_column_constraint(writer, ids, table_name, new_column.name(), ColumnConstraint::Check)?;
+    }
+    if default_changed {
+        write_drop_column_constraint(writer, ids, table_name, new_column.name(), ColumnConstraint::Default)?;
+    }
+    if rebuilt {
+        writeln!(writer, "ALTER TABLE {} DROP COLUMN {};", table, column)?;
+        writeln!(writer, "ALTER TABLE {} ADD {}{};", table, column, column_definition_sql(ids, new_column, enum_type))?;
+    } else {
+        if type_changed || changes.contains(&ColumnAttribute::Required) {
+            let null = if new_column.required() { " NOT NULL" } else { " NULL" };
+            writeln!(
+                writer,
+                "ALTER TABLE {} ALTER COLUMN {}{}{};",
+                table,
+                column,
+                modified_column_type_sql(new_column, enum_type),
+                null
+            )?;
+        }
+        if changes.contains(&ColumnAttribute::IdentityStart) || changes.contains(&ColumnAttribute::IdentityIncrement) {
+            writeln!(
+                writer,
+                "-- SQL Server can't alter the identity of {}.{}; recreate the column as {} to apply it.",
+                table_name,
+                new_column.name(),
+                identity_sql(new_column)
+            )?;
+        }
+        if default_changed && let Some(default) = column_default_sql(ids, new_column, new_column.sequence()) {
+            writeln!(writer, "ALTER TABLE {} ADD DEFAULT {} FOR {};", table, default, column)?;
+        }
+    }
+    if check_changed && let Some(check) = column_check_sql(ids, new_column, enum_type) {
+        let check_name = ids.generated(&ids.check_name(table_name, new_column.name()));
+        writeln!(writer, "ALTER TABLE {} ADD CONSTRAINT {} {};", table, check_name, check)?;
+    }
+    writeln!(writer, "GO")?;
+    writeln!(writer)?;
+    Ok(())
+}
+
+/// A constraint SQL Server keeps on a single column.
+#[derive(Debug, Clone, Copy)]
+enum ColumnConstraint {
+    Check,
+    Default,
+}
+
+/// Drops a column's check or default constraint, found by the column it belongs to, so
+/// that one SQL Server named itself is found as well as one the create script named.
+fn write_drop_column_constraint(
+    writer: &mut dyn Write,
+    ids: &Identifiers,
+    table_name: &str,
+    column_name: &str,
+    constraint: ColumnConstraint,
+) -> Result<(), MigrationGeneratorError> {
+    let (variable, catalog) = match constraint {
+        ColumnConstraint::Check => ("@check", "sys.check_constraints"),
+        ColumnConstraint::Default => ("@default", "sys.default_constraints"),
+    };
+    let table = ids.name(table_name);
+    writeln!(
+        writer,
+        "DECLARE {} sysname = (SELECT name FROM {} WHERE parent_object_id = OBJECT_ID('{}') AND parent_column_id = COLUMNPROPERTY(OBJECT_ID('{}'), '{}', 'ColumnId'));",
+        variable,
+        catalog,
+        table,
+        table,
+        ids.database_name(column_name).replace('\'', "''")
+    )?;
+    writeln!(writer, "IF {} IS NOT NULL EXEC('ALTER TABLE {} DROP CONSTRAINT ' + {});", variable, table, variable)?;
+    Ok(())
+}
+
+/// Swaps the check constraint on an enum column's codes for one named as the create
+/// script names it. The column is widened first when a new code no longer fits the type
+/// the create script gave it.
+fn write_enum_column_change(
+    writer: &mut dyn Write,
+    ids: &Identifiers,
+    table_name: &str,
+    column: &Column,
+    old_enum_type: &EnumType,
+    new_enum_type: &EnumType,
+) -> Result<(), MigrationGeneratorError> {
+    let table = ids.name(table_name);
+    let column_name = ids.name(column.name());
+    write_drop_column_constraint(writer, ids, table_name, column.name(), ColumnConstraint::Check)?;
+    let new_type = enum_column_type_sql(new_enum_type);
+    if enum_column_type_sql(old_enum_type) != new_type {
+        let null = if column.required() { "NOT NULL" } else { "NULL" };
+        writeln!(writer, "ALTER TABLE {} ALTER COLUMN {} {} {};", table, column_name, new_type, null)?;
+    }
+    writeln!(
+        writer,
+        "ALTER TABLE {} ADD CONSTRAINT {} CHECK ({} IN ({}));",
+        table,
+        ids.generated(&ids.check_name(table_name, column.name())),
+        column_name,
+        enum_values_sql(new_enum_type)
+    )?;
+    writeln!(writer, "GO")?;
+    writeln!(writer)?;
+    Ok(())
+}
+
+/// The type the create script gives a column of `enum_type`: `nchar` when every code has
+/// the same length, `nvarchar` otherwise.
+fn enum_column_type_sql(enum_type: &EnumType) -> String {
+    let lengths: Vec<usize> = enum_type.values().iter().map(|value| value.code().len()).collect();
+    let max_length = lengths.iter().copied().max().unwrap_or(0);
+    if lengths.iter().all(|length| *length == max_length) {
+        format!("nchar({})", max_length)
+    } else {
+        format!("nvarchar({})", max_length)
+    }
+}
+
+/// A materialized view becomes a schema-bound view whose first unique index is created as the
+/// unique clustered index that stores its rows; every other index has to follow that one, so
+/// without a unique index none of them can be created.
+fn write_add_indexed_view(writer: &mut dyn Write, ids: &Identifiers, view: &View) -> Result<(), MigrationGeneratorError> {
+    let view_name = qualified_name(view.schema_name(), view.name());
+    writeln!(writer, "CREATE OR ALTER VIEW {} WITH SCHEMABINDING AS", ids.name(&view_name))?;
+    writeln!(writer, "{};", view.sql())?;
+    writeln!(writer, "GO")?;
+    writeln!(writer)?;
+
+    let Some(clustered) = view.indexes().iter().position(|key| key.is_unique()) else {
+        writeln!(
+            writer,
+            "-- {} has no unique index, so SQL Server keeps it as a plain schema-bound view.",
+            view_name
+        )?;
+        writeln!(writer)?;
+        return Ok(());
+    };
+    writeln!(
+        writer,
+        "CREATE UNIQUE CLUSTERED INDEX {} ON {} ({});",
+        ids.generated(&ids.key_name(&view_name, &view.indexes()[clustered], clustered + 1)),
+        ids.name(&view_name),
+        index_columns_sql(ids, &view.indexes()[clustered], false)
+    )?;
+    writeln!(writer, "GO")?;
+    writeln!(writer)?;
+    for (index, key) in view.indexes().iter().enumerate().filter(|(index, _)| *index != clustered) {
+        write_add_key(writer, ids, &view_name, key, index + 1)?;
+    }
+    Ok(())
+}
+
+/// Adds a key as the create script declares it: the primary key and unique keys as named
+/// table constraints, other indexes with `CREATE INDEX`.
+fn write_add_key(
+    writer: &mut dyn Write,
+    ids: &Identifiers,
+    table_name: &str,
+    key: &Key,
+    position: usize,
+) -> Result<(), MigrationGeneratorError> {
+    let key_name = ids.key_name(table_name, key, position);
+    let compression = key
+        .compression()
+        .map(|level| format!(" WITH (DATA_COMPRESSION = {})", level.sql().to_uppercase()))
+        .unwrap_or_default();
     match key.key_type() {
-        KeyType::Primary => {
+        KeyType::Primary | KeyType::Unique => {
+            let col_names: Vec<&str> = key.columns().iter().map(|c| c.name()).collect();
+            let constraint = match key.key_type() {
+                KeyType::Primary => "PRIMARY KEY",
+                _ if key.is_cluster() => "UNIQUE CLUSTERED",
+                _ => "UNIQUE",
+            };
             writeln!(
                 writer,
-                "ALTER TABLE {} DROP CONSTRAINT {}_pkey;",
-                table_name, table_name
+                "ALTER TABLE {} ADD CONSTRAINT {} {} ({}){};",
+                ids.name(table_name),
+                ids.generated(&key_name),
+                constraint,
+                ids.list(&col_names),
+                compression
             )?;
         }
-        KeyType::Unique | KeyType::Index => {
-            let idx_name = format!("idx_{}_{}", table_name, col_names.join("_"));
+        KeyType::Index => {
+            if let Some(expression) = key.columns().iter().find(|c| c.is_expression()) {
+                return Err(MigrationGeneratorError::UnsupportedChange(format!(
+                    "SQL Server cannot index the expression '{}' on {}; add a computed column instead",
+                    expression.name(),
+                    table_name
+                )));
+            }
+            // SQL Server has neither NULLS FIRST/LAST nor a choice of index method; a
+            // filter becomes a filtered index.
+            let unique = if key.is_unique() { "UNIQUE " } else { "" };
+            let clustered = if key.is_cluster() { "CLUSTERED " } else { "" };
+            let include = include_columns(key);
+            let include = if include.is_empty() { String::new() } else { format!(" INCLUDE ({})", ids.list(&include)) };
+            let filter = key.filter().map(|f| format!(" WHERE {}", f)).unwrap_or_default();
             writeln!(
                 writer,
-                "IF EXISTS (SELECT 1 FROM sys.indexes WHERE name = '{}') DROP INDEX {} ON {};",
-                idx_name, idx_name, table_name
+                "CREATE {}{}INDEX {} ON {} ({}){}{}{};",
+                unique,
+                clustered,
+                ids.generated(&key_name),
+                ids.name(table_name),
+                index_columns_sql(ids, key, false),
+                include,
+                filter,
+                compression
             )?;
         }
     }
@@ -245,8 +719,139 @@ fn write_drop_key(writer: &mut dyn Write, table_name: &str, key: &Key) -> Result
     Ok(())
 }
 
-fn write_add_relation(writer: &mut dyn Write, relation: &Relation) -> Result<(), MigrationGeneratorError> {
-    let fk_name = fk_constraint_name(relation);
+fn write_drop_key(
+    writer: &mut dyn Write,
+    ids: &Identifiers,
+    table_name: &str,
+    key: &Key,
+    position: usize,
+) -> Result<(), MigrationGeneratorError> {
+    let key_name = ids.key_name(table_name, key, position);
+    match key.key_type() {
+        KeyType::Primary | KeyType::Unique => {
+            writeln!(writer, "ALTER TABLE {} DROP CONSTRAINT {};", ids.name(table_name), ids.generated(&key_name))?;
+        }
+        KeyType::Index => {
+            // Index names are only unique per table, so the lookup is scoped to this one.
+            let table_name = ids.name(table_name);
+            writeln!(
+                writer,
+                "IF EXISTS (SELECT 1 FROM sys.indexes WHERE name = '{}' AND object_id = OBJECT_ID('{}')) DROP INDEX {} ON {};",
+                key_name,
+                table_name,
+                ids.generated(&key_name),
+                table_name
+            )?;
+        }
+    }
+    writeln!(writer, "GO")?;
+    writeln!(writer)?;
+    Ok(())
+}
+
+/// A key whose only change is its compression is rebuilt in place; any other change that
+/// shows in its definition drops and creates it again.
+fn write_modify_key(
+    writer: &mut dyn Write,
+    ids: &Identifiers,
+    table_name: &str,
+    old_key: &Key,
+    new_key: &Key,
+    position: usize,
+) -> Result<(), MigrationGeneratorError> {
+    if same_key_sql(write_add_key, ids, table_name, old_key, position, new_key, position)? {
+        return Ok(());
+    }
+    let mut recompressed = old_key.clone();
+    recompressed.set_compression(new_key.compression());
+    if !same_key_sql(write_add_key, ids, table_name, &recompressed, position, new_key, position)? {
+        write_drop_key(writer, ids, table_name, old_key, position)?;
+        return write_add_key(writer, ids, table_name, new_key, position);
+    }
+    let index = ids.key_name(table_name, new_key, position);
+    writeln!(
+        writer,
+        "ALTER INDEX {} ON {} REBUILD WITH (DATA_COMPRESSION = {});",
+        ids.generated(&index),
+        ids.name(table_name),
+        compression_sql(new_key.compression())
+    )?;
+    writeln!(writer, "GO")?;
+    writeln!(writer)?;
+    Ok(())
+}
+
+/// Rebuilds a table whose compression changed, and sets its lock escalation as the create
+/// script does.
+fn write_modify_table_options(
+    writer: &mut dyn Write,
+    ids: &Identifiers,
+    table_name: &str,
+    old_options: &[TableOption],
+    new_options: &[TableOption],
+    old_lock_escalation: LockEscalation,
+    new_lock_escalation: LockEscalation,
+) -> Result<(), MigrationGeneratorError> {
+    let compression = |options: &[TableOption]| {
+        options.iter().find_map(|option| match option {
+            TableOption::Compress(level) => Some(*level),
+            _ => None,
+        })
+    };
+    let new_compression = compression(new_options);
+    if compression(old_options) != new_compression {
+        writeln!(
+            writer,
+            "ALTER TABLE {} REBUILD WITH (DATA_COMPRESSION = {});",
+            ids.name(table_name),
+            compression_sql(new_compression)
+        )?;
+        writeln!(writer, "GO")?;
+        writeln!(writer)?;
+    }
+    if old_lock_escalation != new_lock_escalation {
+        let lock_escalation = match new_lock_escalation {
+            LockEscalation::Auto => "AUTO",
+            LockEscalation::Disable => "DISABLE",
+            LockEscalation::Table => "TABLE",
+        };
+        writeln!(writer, "ALTER TABLE {} SET (LOCK_ESCALATION = {});", ids.name(table_name), lock_escalation)?;
+        writeln!(writer, "GO")?;
+        writeln!(writer)?;
+    }
+    Ok(())
+}
+
+fn compression_sql(compression: Option<CompressionLevel>) -> String {
+    compression.map(|level| level.sql().to_uppercase()).unwrap_or_else(|| "NONE".to_string())
+}
+
+fn write_drop_relation(
+    writer: &mut dyn Write,
+    ids: &Identifiers,
+    table_name: &str,
+    relation: &Relation,
+    position: usize,
+) -> Result<(), MigrationGeneratorError> {
+    writeln!(
+        writer,
+        "ALTER TABLE {} DROP CONSTRAINT {};",
+        ids.name(table_name),
+        ids.generated(&ids.foreign_key_name(table_name, relation, position))
+    )?;
+    writeln!(writer, "GO")?;
+    writeln!(writer)?;
+    Ok(())
+}
+
+fn write_add_relation(
+    writer: &mut dyn Write,
+    ids: &Identifiers,
+    table_name: &str,
+    relation: &Relation,
+    position: usize,
+) -> Result<(), MigrationGeneratorError> {
+    let fk_name = ids.foreign_key_name(table_name, relation, position);
     let on_delete = match relation.relation_type() {
         RelationType::Cascade => " ON DELETE CASCADE",
         RelationType::SetNull => " ON DELETE SET NULL",
@@ -256,22 +861,14 @@ fn write_add_relation(writer: &mut dyn Write, relation: &Relation) -> Result<(),
     writeln!(
         writer,
         "ALTER TABLE {} ADD CONSTRAINT {} FOREIGN KEY ({}) REFERENCES {}({}){};",
-        relation.from_table_name(),
-        fk_name,
-        relation.from_column_name(),
-        relation.to_table_name(),
-        relation.to_column_name(),
+        ids.name(table_name),
+        ids.generated(&fk_name),
+        ids.list(&relation.from_column_names()),
+        ids.name(relation.to_table_name()),
+        ids.list(&relation.to_column_names()),
         on_delete
     )?;
     writeln!(writer, "GO")?;
     writeln!(writer)?;
     Ok(())
 }
-
-fn fk_constraint_name(relation: &Relation) -> String {
-    format!(
-        "fk_{}_{}",
-        relation.from_table_name(),
-        relation.from_column_name()
-    )
-}
@@ -1,10 +1,32 @@
-use schema_diff::change::SchemaChange;
+use schema_diff::change::{ColumnAttribute, SchemaChange};
 use schema_diff::change_set::ChangeSet;
 use schema_model::builder::column::ColumnBuilder;
+use schema_model::builder::key::KeyBuilder;
+use schema_model::builder::schema::SchemaBuilder;
+use schema_model::builder::sequence::SequenceBuilder;
+use schema_model::builder::table::TableBuilder;
+use schema_model::model::aggregation::{Aggregation, AggregationColumn, AggregationFrequency, AggregationType};
 use schema_model::model::column_type::ColumnType;
-use schema_model::model::types::DatabaseType;
+use schema_model::model::constraint_naming::ConstraintNaming;
+use schema_model::model::database_model::DatabaseModel;
+use schema_model::model::enum_type::{EnumType, EnumValue};
+use schema_model::model::function::Function;
+use schema_model::model::key::KeyColumn;
+use schema_model::model::naming::NamingConvention;
+use schema_model::model::other_sql::OtherSql;
+use schema_model::model::partition::{Partition, PartitionBound, PartitionTemplate, Partitioning};
+use schema_model::model::procedure::Procedure;
+use schema_model::model::relation::{Relation, RelationColumn};
+use schema_model::model::trigger::Trigger;
+use schema_model::model::types::{
+    BooleanMode, CompressionLevel, DatabaseType, ForeignKeyMode, IdentifierQuoting, IdentityMode, IndexMethod, KeyType, LockEscalation, NullsOrder, OtherSqlOrder, PartitionStrategy, RefreshPolicy, RelationType, SortOrder, TableOption, TriggerType,
+};
+use schema_model::model::view::View;
+use schema_sql_generator::common::generate_options::GenerateOptions;
+use std::sync::Arc;
 
-use crate::create_generator;
+use crate::{create_generator, create_generator_with_naming, create_generator_with_options, create_generator_with_quoting};
+use crate::error::MigrationGeneratorError;
 
 #[test]
 fn postgresql_add_table() {
@@ -74,7 +96,7 @@ fn sqlite_rename_table() {
 }
 
 #[test]
-fn postgresql_drop_column_with_rename_candidates_emits_todo() {
+fn postgresql_drop_column_with_rename_candidates_emits_rename_hint() {
     let mut cs = ChangeSet::new();
     cs.add_change(SchemaChange::DropColumn {
         table_name: "users".to_string(),
@@ -86,13 +108,14 @@ fn postgresql_drop_column_with_rename_candidates_emits_todo() {
     let mut output = Vec::new();
     generator.generate(&cs, &mut output).unwrap();
     let sql = String::from_utf8(output).unwrap();
-    assert!(sql.contains("-- TODO: possible rename?"));
-    assert!(sql.contains("RENAME COLUMN first_name TO full_name"));
+    assert!(sql.contains("-- first_name is dropped with its data. If a new column replaces it, give that column previousName=\"first_name\" to rename it instead:"));
+    assert!(sql.contains("--   ALTER TABLE users RENAME COLUMN first_name TO full_name;"));
+    assert!(!sql.contains("TODO"));
     assert!(sql.contains("DROP COLUMN first_name"));
 }
 
 #[test]
-fn postgresql_drop_column_no_candidates_no_todo() {
+fn postgresql_drop_column_no_candidates_no_rename_hint() {
     let mut cs = ChangeSet::new();
     cs.add_change(SchemaChange::DropColumn {
         table_name: "users".to_string(),
@@ -104,7 +127,7 @@ fn postgresql_drop_column_no_candidates_no_todo() {
     let mut output = Vec::new();
     generator.generate(&cs, &mut output).unwrap();
     let sql = String::from_utf8(output).unwrap();
-    assert!(!sql.contains("-- TODO"));
+    assert!(!sql.contains("previousName"));
     assert!(sql.contains("DROP COLUMN legacy_field"));
 }
 
@@ -121,7 +144,1280 @@ fn sqlserver_drop_column_with_rename_candidates_emits_sp_rename_hint() {
     let mut output = Vec::new();
     generator.generate(&cs, &mut output).unwrap();
     let sql = String::from_utf8(output).unwrap();
-    assert!(sql.contains("-- TODO: possible rename?"));
-    assert!(sql.contains("sp_rename 'orders.old_col', 'new_col', 'COLUMN'"));
+    assert!(sql.contains("previousName=\"old_col\""));
+    assert!(sql.contains("--   EXEC sp_rename 'orders.old_col', 'new_col', 'COLUMN';"));
     assert!(sql.contains("DROP COLUMN old_col"));
 }
+
+#[test]
+fn postgresql_add_composite_relation() {
+    let mut cs = ChangeSet::new();
+    cs.add_change(SchemaChange::AddRelation {
+        table_name: "invoice".to_string(),
+        relation: Relation::new_composite(
+            "customer",
+            "invoice",
+            vec![
+                RelationColumn::new("tenant_id", "tenant_id"),
+                RelationColumn::new("customer_id", "id"),
+            ],
+            RelationType::Cascade,
+            false,
+        ),
+        position: 1,
+    });
+
+    let generator = create_generator(DatabaseType::Postgresql);
+    let mut output = Vec::new();
+    generator.generate(&cs, &mut output).unwrap();
+    let sql = String::from_utf8(output).unwrap();
+    assert!(sql.contains(
+        "ALTER TABLE invoice ADD CONSTRAINT fk_invoice1 FOREIGN KEY (tenant_id,customer_id) REFERENCES customer(tenant_id,id) ON DELETE CASCADE;"
+    ));
+}
+
+#[test]
+fn postgresql_modify_description() {
+    let mut cs = ChangeSet::new();
+    cs.add_change(SchemaChange::ModifyDescription {
+        table_name: "users".to_string(),
+        column_name: None,
+        old_description: None,
+        new_description: Some("People's accounts".to_string()),
+    });
+    cs.add_change(SchemaChange::ModifyDescription {
+        table_name: "users".to_string(),
+        column_name: Some("email".to_string()),
+        old_description: Some("Email".to_string()),
+        new_description: None,
+    });
+
+    let generator = create_generator(DatabaseType::Postgresql);
+    let mut output = Vec::new();
+    generator.generate(&cs, &mut output).unwrap();
+    let sql = String::from_utf8(output).unwrap();
+    assert!(sql.contains("COMMENT ON TABLE users IS 'People''s accounts';"));
+    assert!(sql.contains("COMMENT ON COLUMN users.email IS NULL;"));
+}
+
+#[test]
+fn sqlserver_modify_description_picks_add_update_or_drop() {
+    let mut cs = ChangeSet::new();
+    cs.add_change(SchemaChange::ModifyDescription {
+        table_name: "users".to_string(),
+        column_name: None,
+        old_description: None,
+        new_description: Some("Accounts".to_string()),
+    });
+    cs.add_change(SchemaChange::ModifyDescription {
+        table_name: "sales.orders".to_string(),
+        column_name: Some("total".to_string()),
+        old_description: Some("Total".to_string()),
+        new_description: Some("Order total".to_string()),
+    });
+    cs.add_change(SchemaChange::ModifyDescription {
+        table_name: "users".to_string(),
+        column_name: Some("email".to_string()),
+        old_description: Some("Email".to_string()),
+        new_description: None,
+    });
+
+    let generator = create_generator(DatabaseType::SqlServer);
+    let mut output = Vec::new();
+    generator.generate(&cs, &mut output).unwrap();
+    let sql = String::from_utf8(output).unwrap();
+    assert!(sql.contains("EXEC sp_addextendedproperty @name = N'MS_Description', @value = N'Accounts', @level0type = N'SCHEMA', @level0name = N'dbo', @level1type = N'TABLE', @level1name = N'users';"));
+    assert!(sql.contains("EXEC sp_updateextendedproperty @name = N'MS_Description', @value = N'Order total', @level0type = N'SCHEMA', @level0name = N'sales', @level1type = N'TABLE', @level1name = N'orders', @level2type = N'COLUMN', @level2name = N'total';"));
+    assert!(sql.contains("EXEC sp_dropextendedproperty @name = N'MS_Description', @level0type = N'SCHEMA', @level0name = N'dbo', @level1type = N'TABLE', @level1name = N'users', @level2type = N'COLUMN', @level2name = N'email';"));
+}
+
+#[test]
+fn sqlserver_description_escapes_quotes_in_object_names() {
+    let mut cs = ChangeSet::new();
+    cs.add_change(SchemaChange::ModifyDescription {
+        table_name: "o'neil.user's".to_string(),
+        column_name: Some("nick'name".to_string()),
+        old_description: None,
+        new_description: Some("Owner's nickname".to_string()),
+    });
+
+    let generator = create_generator(DatabaseType::SqlServer);
+    let mut output = Vec::new();
+    generator.generate(&cs, &mut output).unwrap();
+    let sql = String::from_utf8(output).unwrap();
+    assert!(sql.contains("EXEC sp_addextendedproperty @name = N'MS_Description', @value = N'Owner''s nickname', @level0type = N'SCHEMA', @level0name = N'o''neil', @level1type = N'TABLE', @level1name = N'user''s', @level2type = N'COLUMN', @level2name = N'nick''name';"));
+}
+
+fn partial_expression_index() -> schema_model::model::key::Key {
+    KeyBuilder::new(KeyType::Index)
+        .add_key_column(KeyColumn::new_expression("lower(email)"))
+        .add_key_column(
+            KeyColumn::new("created_at")
+                .with_sort_order(Some(SortOrder::Desc))
+                .with_nulls_order(Some(NullsOrder::Last)),
+        )
+        .method(IndexMethod::BTree)
+        .filter("deleted_at is null")
+        .build()
+}
+
+#[test]
+fn postgresql_add_and_drop_partial_expression_index() {
+    let mut cs = ChangeSet::new();
+    cs.add_change(SchemaChange::AddKey {
+        table_name: "users".to_string(),
+        key: partial_expression_index(),
+        position: 1,
+    });
+    cs.add_change(SchemaChange::DropKey {
+        table_name: "users".to_string(),
+        key: partial_expression_index(),
+        position: 1,
+    });
+
+    let generator = create_generator(DatabaseType::Postgresql);
+    let mut output = Vec::new();
+    generator.generate(&cs, &mut output).unwrap();
+    let sql = String::from_utf8(output).unwrap();
+    assert!(sql.contains(
+        "CREATE INDEX ix_users1 ON users USING btree ((lower(email)), created_at DESC NULLS LAST) WHERE deleted_at is null;"
+    ));
+    assert!(sql.contains("DROP INDEX IF EXISTS ix_users1;"));
+}
+
+#[test]
+fn sqlserver_rejects_expression_index() {
+    let mut cs = ChangeSet::new();
+    cs.add_change(SchemaChange::AddKey {
+        table_name: "users".to_string(),
+        key: partial_expression_index(),
+        position: 1,
+    });
+
+    let generator = create_generator(DatabaseType::SqlServer);
+    let mut output = Vec::new();
+    let err = generator.generate(&cs, &mut output).unwrap_err();
+    assert!(matches!(err, MigrationGeneratorError::UnsupportedChange(msg) if msg.contains("lower(email)")));
+}
+
+#[test]
+fn sqlserver_compresses_added_keys() {
+    let mut cs = ChangeSet::new();
+    cs.add_change(SchemaChange::AddKey {
+        table_name: "users".to_string(),
+        key: KeyBuilder::new(KeyType::Primary).add_column("id").compress(true).build(),
+        position: 1,
+    });
+    cs.add_change(SchemaChange::AddKey {
+        table_name: "users".to_string(),
+        key: KeyBuilder::new(KeyType::Index).add_column("email").compression(CompressionLevel::Row).build(),
+        position: 1,
+    });
+
+    let generator = create_generator(DatabaseType::SqlServer);
+    let mut output = Vec::new();
+    generator.generate(&cs, &mut output).unwrap();
+    let sql = String::from_utf8(output).unwrap();
+    assert!(sql.contains("ALTER TABLE users ADD CONSTRAINT pk_users PRIMARY KEY (id) WITH (DATA_COMPRESSION = PAGE);"));
+    assert!(sql.contains(" ON users (email) WITH (DATA_COMPRESSION = ROW);"));
+}
+
+#[test]
+fn postgresql_sequence_changes() {
+    let mut cs = ChangeSet::new();
+    cs.add_change(SchemaChange::AddSequence {
+        sequence: SequenceBuilder::new(None, "document_no").start(Some(1000)).increment(Some(10)).build(),
+    });
+    cs.add_change(SchemaChange::ModifySequence {
+        old_sequence: SequenceBuilder::new(None, "ticket_no").max_value(Some(999)).build(),
+        new_sequence: SequenceBuilder::new(None, "ticket_no").start(Some(5)).cycle(true).build(),
+    });
+    cs.add_change(SchemaChange::AddColumn {
+        table_name: "invoice".to_string(),
+        column: ColumnBuilder::new(None, "invoice_no", ColumnType::Long)
+            .sequence(Some("document_no".to_string()))
+            .build(),
+    });
+    cs.add_change(SchemaChange::DropSequence {
+        sequence_name: "legacy_no".to_string(),
+    });
+
+    let generator = create_generator(DatabaseType::Postgresql);
+    let mut output = Vec::new();
+    generator.generate(&cs, &mut output).unwrap();
+    let sql = String::from_utf8(output).unwrap();
+    assert!(sql.contains("CREATE SEQUENCE document_no START WITH 1000 INCREMENT BY 10;"));
+    assert!(sql.contains("ALTER SEQUENCE ticket_no START WITH 5 NO MAXVALUE CYCLE;"));
+    assert!(sql.contains("ALTER TABLE invoice ADD COLUMN invoice_no bigint DEFAULT nextval('document_no');"));
+    assert!(sql.contains("DROP SEQUENCE IF EXISTS legacy_no;"));
+}
+
+#[test]
+fn sqlserver_sequence_starts_at_one_and_columns_draw_next_value() {
+    let mut cs = ChangeSet::new();
+    cs.add_change(SchemaChange::AddSequence {
+        sequence: SequenceBuilder::new(None, "document_no").build(),
+    });
+    cs.add_change(SchemaChange::AddColumn {
+        table_name: "invoice".to_string(),
+        column: ColumnBuilder::new(None, "invoice_no", ColumnType::Long)
+            .sequence(Some("document_no".to_string()))
+            .build(),
+    });
+
+    let generator = create_generator(DatabaseType::SqlServer);
+    let mut output = Vec::new();
+    generator.generate(&cs, &mut output).unwrap();
+    let sql = String::from_utf8(output).unwrap();
+    assert!(sql.contains("CREATE SEQUENCE document_no AS bigint START WITH 1;"));
+    assert!(sql.contains("DEFAULT NEXT VALUE FOR document_no;"));
+}
+
+#[test]
+fn mysql_sequences_need_mariadb() {
+    let mut cs = ChangeSet::new();
+    cs.add_change(SchemaChange::DropSequence { sequence_name: "legacy_no".to_string() });
+    cs.add_change(SchemaChange::AddSequence {
+        sequence: SequenceBuilder::new(None, "document_no").start(Some(1000)).build(),
+    });
+    cs.add_change(SchemaChange::AddColumn {
+        table_name: "invoice".to_string(),
+        column: ColumnBuilder::new(None, "invoice_no", ColumnType::Long)
+            .sequence(Some("document_no".to_string()))
+            .build(),
+    });
+
+    let mut output = Vec::new();
+    let error = create_generator(DatabaseType::MySql).generate(&cs, &mut output).unwrap_err();
+    assert!(matches!(&error, MigrationGeneratorError::UnsupportedChange(msg) if msg.contains("sequence document_no needs MariaDB")), "{}", error);
+    assert!(output.is_empty());
+
+    let mut output = Vec::new();
+    let mariadb = GenerateOptions { target_mariadb: true, ..GenerateOptions::new(Arc::new(DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![]))) };
+    create_generator_with_options(DatabaseType::MySql, mariadb).generate(&cs, &mut output).unwrap();
+    let sql = String::from_utf8(output).unwrap();
+    assert!(sql.contains("DROP SEQUENCE IF EXISTS legacy_no;"));
+    assert!(sql.contains("CREATE SEQUENCE document_no START WITH 1000;"));
+    assert!(sql.contains("ALTER TABLE invoice ADD COLUMN invoice_no bigint NULL DEFAULT nextval(document_no);"), "{}", sql);
+}
+
+#[test]
+fn mysql_restates_modified_columns_and_drops_keys_by_their_own_syntax() {
+    let mut cs = ChangeSet::new();
+    cs.add_change(SchemaChange::RenameTable {
+        old_name: "old_users".to_string(),
+        new_name: "users".to_string(),
+    });
+    cs.add_change(SchemaChange::ModifyColumn {
+        table_name: "users".to_string(),
+        old_column: ColumnBuilder::new(None, "nickname", ColumnType::Varchar).length(50).build(),
+        new_column: ColumnBuilder::new(None, "nickname", ColumnType::Varchar)
+            .length(100)
+            .required(true)
+            .description(Some("Shown as 'nick'".to_string()))
+            .build(),
+        changes: vec![ColumnAttribute::Length, ColumnAttribute::Required],
+        enum_type: None,
+    });
+    cs.add_change(SchemaChange::DropKey {
+        table_name: "users".to_string(),
+        key: KeyBuilder::new(KeyType::Index).add_column("nickname").build(),
+        position: 1,
+    });
+    cs.add_change(SchemaChange::DropRelation {
+        table_name: "users".to_string(),
+        relation: Relation::new("account", "id", "users", "account_id", RelationType::Enforce, false),
+        position: 1,
+    });
+
+    let generator = create_generator(DatabaseType::MySql);
+    let mut output = Vec::new();
+    generator.generate(&cs, &mut output).unwrap();
+    let sql = String::from_utf8(output).unwrap();
+    assert!(sql.contains("RENAME TABLE old_users TO users;"));
+    assert!(sql.contains("ALTER TABLE users MODIFY COLUMN nickname varchar(100) NOT NULL COMMENT 'Shown as ''nick''';"));
+    assert!(sql.contains("DROP INDEX ix_users1 ON users;"));
+    assert!(sql.contains("ALTER TABLE users DROP FOREIGN KEY fk_users1;"));
+}
+
+#[test]
+fn mysql_rejects_partial_index() {
+    let mut cs = ChangeSet::new();
+    cs.add_change(SchemaChange::AddKey {
+        table_name: "users".to_string(),
+        key: KeyBuilder::new(KeyType::Index).add_column("email").filter("deleted_at is null").build(),
+        position: 1,
+    });
+
+    let generator = create_generator(DatabaseType::MySql);
+    let mut output = Vec::new();
+    let err = generator.generate(&cs, &mut output).unwrap_err();
+    assert!(matches!(err, MigrationGeneratorError::UnsupportedChange(msg) if msg.contains("deleted_at is null")));
+}
+
+#[test]
+fn duckdb_adds_sequence_columns_and_sets_not_null_separately() {
+    let mut cs = ChangeSet::new();
+    cs.add_change(SchemaChange::AddSequence {
+        sequence: SequenceBuilder::new(None, "document_no").start(Some(1000)).build(),
+    });
+    cs.add_change(SchemaChange::AddColumn {
+        table_name: "invoice".to_string(),
+        column: ColumnBuilder::new(None, "line_no", ColumnType::LongSequence).required(true).build(),
+    });
+    cs.add_change(SchemaChange::ModifyColumn {
+        table_name: "invoice".to_string(),
+        old_column: ColumnBuilder::new(None, "notes", ColumnType::Varchar).build(),
+        new_column: ColumnBuilder::new(None, "notes", ColumnType::Json).required(true).build(),
+        changes: vec![ColumnAttribute::Type, ColumnAttribute::Required],
+        enum_type: None,
+    });
+    cs.add_change(SchemaChange::AddRelation {
+        table_name: "invoice".to_string(),
+        relation: Relation::new("customer", "id", "invoice", "customer_id", RelationType::Cascade, false),
+        position: 1,
+    });
+
+    let generator = create_generator(DatabaseType::DuckDb);
+    let mut output = Vec::new();
+    generator.generate(&cs, &mut output).unwrap();
+    let sql = String::from_utf8(output).unwrap();
+    assert!(sql.contains("CREATE SEQUENCE IF NOT EXISTS document_no START WITH 1000;"));
+    assert!(sql.contains("CREATE SEQUENCE IF NOT EXISTS invoice_line_no_seq;"));
+    assert!(sql.contains("ALTER TABLE invoice ADD COLUMN line_no bigint DEFAULT nextval('invoice_line_no_seq');"));
+    assert!(sql.contains("ALTER TABLE invoice ALTER COLUMN line_no SET NOT NULL;"));
+    assert!(sql.contains("ALTER TABLE invoice ALTER COLUMN notes TYPE json;"));
+    assert!(sql.contains("ALTER TABLE invoice ALTER COLUMN notes SET NOT NULL;"));
+    assert!(sql.contains("-- Ensure CONSTRAINT fk_invoice1 FOREIGN KEY (customer_id) REFERENCES customer(id) is in the CREATE TABLE statement for 'invoice'."));
+}
+
+#[test]
+fn duckdb_rejects_partial_index() {
+    let mut cs = ChangeSet::new();
+    cs.add_change(SchemaChange::AddKey {
+        table_name: "users".to_string(),
+        key: KeyBuilder::new(KeyType::Index).add_column("email").filter("deleted_at is null").build(),
+        position: 1,
+    });
+
+    let generator = create_generator(DatabaseType::DuckDb);
+    let mut output = Vec::new();
+    let err = generator.generate(&cs, &mut output).unwrap_err();
+    assert!(matches!(err, MigrationGeneratorError::UnsupportedChange(msg) if msg.contains("DuckDB has no partial indexes")));
+}
+
+fn order_totals_view() -> View {
+    View::new_materialized(
+        None,
+        "order_totals",
+        "SELECT customer_id, SUM(amount) AS total, COUNT_BIG(*) AS orders FROM dbo.orders GROUP BY customer_id",
+        None,
+        RefreshPolicy::Manual,
+        vec![
+            KeyBuilder::new(KeyType::Index).add_column("total").build(),
+            KeyBuilder::new(KeyType::Index).add_column("customer_id").unique(true).build(),
+        ],
+    )
+}
+
+#[test]
+fn postgresql_recreates_materialized_view_with_its_indexes() {
+    let mut cs = ChangeSet::new();
+    cs.add_change(SchemaChange::DropView { view_name: "order_totals".to_string(), materialized: true });
+    cs.add_change(SchemaChange::AddView { view: order_totals_view() });
+
+    let generator = create_generator(DatabaseType::Postgresql);
+    let mut output = Vec::new();
+    generator.generate(&cs, &mut output).unwrap();
+    let sql = String::from_utf8(output).unwrap();
+    assert!(sql.contains("DROP MATERIALIZED VIEW IF EXISTS order_totals;"));
+    assert!(sql.contains("CREATE MATERIALIZED VIEW order_totals AS"));
+    assert!(sql.contains("CREATE INDEX ix_order_totals1 ON order_totals (total);"));
+    assert!(sql.contains("CREATE UNIQUE INDEX ix_order_totals2 ON order_totals (customer_id);"));
+}
+
+#[test]
+fn sqlserver_materialized_view_clusters_on_its_unique_index_first() {
+    let mut cs = ChangeSet::new();
+    cs.add_change(SchemaChange::AddView { view: order_totals_view() });
+
+    let generator = create_generator(DatabaseType::SqlServer);
+    let mut output = Vec::new();
+    generator.generate(&cs, &mut output).unwrap();
+    let sql = String::from_utf8(output).unwrap();
+    assert!(sql.contains("CREATE OR ALTER VIEW order_totals WITH SCHEMABINDING AS"));
+    let clustered = sql
+        .find("CREATE UNIQUE CLUSTERED INDEX ix_order_totals2 ON order_totals (customer_id);")
+        .expect("clustered index");
+    let secondary = sql.find("CREATE INDEX ix_order_totals1 ON order_totals (total);").expect("secondary index");
+    assert!(clustered < secondary);
+}
+
+#[test]
+fn sqlite_materialized_view_is_a_table_filled_from_the_query() {
+    let mut cs = ChangeSet::new();
+    cs.add_change(SchemaChange::DropView { view_name: "order_totals".to_string(), materialized: true });
+    cs.add_change(SchemaChange::AddView { view: order_totals_view() });
+
+    let generator = create_generator(DatabaseType::Sqlite);
+    let mut output = Vec::new();
+    generator.generate(&cs, &mut output).unwrap();
+    let sql = String::from_utf8(output).unwrap();
+    assert!(sql.contains("DROP TABLE IF EXISTS order_totals;"));
+    assert!(sql.contains("CREATE TABLE IF NOT EXISTS order_totals AS\nSELECT * FROM (SELECT customer_id"));
+    assert!(sql.contains("CREATE UNIQUE INDEX IF NOT EXISTS ix_order_totals2 ON order_totals (customer_id);"));
+    assert!(sql.contains("DELETE FROM order_totals;\nINSERT INTO order_totals\nSELECT customer_id"));
+}
+
+#[test]
+fn postgresql_partition_changes() {
+    let mut cs = ChangeSet::new();
+    cs.add_change(SchemaChange::DropPartition {
+        table_name: "events".to_string(),
+        partition_name: "events_p2023".to_string(),
+    });
+    cs.add_change(SchemaChange::AddPartition {
+        table_name: "events".to_string(),
+        partition: Partition::new(
+            "events_p2025",
+            PartitionBound::Range {
+                from: vec!["2025-01-01".to_string()],
+                to: vec!["2026-01-01".to_string()],
+            },
+        ),
+    });
+    cs.add_change(SchemaChange::AddPartition {
+        table_name: "events".to_string(),
+        partition: Partition::new("events_other", PartitionBound::Default),
+    });
+
+    let generator = create_generator(DatabaseType::Postgresql);
+    let mut output = Vec::new();
+    generator.generate(&cs, &mut output).unwrap();
+    let sql = String::from_utf8(output).unwrap();
+    assert!(sql.contains("DROP TABLE IF EXISTS events_p2023;"));
+    assert!(sql.contains("CREATE TABLE events_p2025 PARTITION OF events FOR VALUES FROM ('2025-01-01') TO ('2026-01-01');"));
+    assert!(sql.contains("CREATE TABLE events_other PARTITION OF events DEFAULT;"));
+}
+
+#[test]
+fn postgresql_rejects_changed_partitioning() {
+    let mut cs = ChangeSet::new();
+    cs.add_change(SchemaChange::ModifyPartitioning {
+        table_name: "events".to_string(),
+        old_partitioning: None,
+        new_partitioning: Some(Partitioning::new(
+            PartitionStrategy::Hash,
+            vec!["id".to_string()],
+            vec![],
+            Some(PartitionTemplate::Hash { modulus: 4 }),
+        )),
+    });
+
+    let generator = create_generator(DatabaseType::Postgresql);
+    let mut output = Vec::new();
+    let err = generator.generate(&cs, &mut output).unwrap_err();
+    assert!(matches!(err, MigrationGeneratorError::UnsupportedChange(msg) if msg.contains("partitioned by hash (id)")));
+
+    let generator = create_generator(DatabaseType::Sqlite);
+    let mut output = Vec::new();
+    generator.generate(&cs, &mut output).unwrap();
+    assert!(output.is_empty());
+}
+
+#[test]
+fn postgresql_converts_serial_column_to_identity_and_back() {
+    let serial = ColumnBuilder::new(None, "id", ColumnType::LongSequence).required(true).build();
+    let identity = ColumnBuilder::new(None, "id", ColumnType::LongSequence)
+        .required(true)
+        .identity_mode(Some(IdentityMode::Always))
+        .identity_start(Some(1000))
+        .build();
+    let mut cs = ChangeSet::new();
+    cs.add_change(SchemaChange::ModifyColumn {
+        table_name: "orders".to_string(),
+        old_column: serial.clone(),
+        new_column: identity.clone(),
+        changes: vec![ColumnAttribute::IdentityMode, ColumnAttribute::IdentityStart],
+        enum_type: None,
+    });
+    cs.add_change(SchemaChange::ModifyColumn {
+        table_name: "invoices".to_string(),
+        old_column: identity,
+        new_column: serial,
+        changes: vec![ColumnAttribute::IdentityMode, ColumnAttribute::IdentityStart],
+        enum_type: None,
+    });
+
+    let generator = create_generator(DatabaseType::Postgresql);
+    let mut output = Vec::new();
+    generator.generate(&cs, &mut output).unwrap();
+    let sql = String::from_utf8(output).unwrap();
+    assert!(sql.contains(
+        "ALTER TABLE orders ALTER COLUMN id DROP DEFAULT;\n\
+         DROP SEQUENCE IF EXISTS orders_id_seq;\n\
+         ALTER TABLE orders ALTER COLUMN id ADD GENERATED ALWAYS AS IDENTITY (START WITH 1000);\n\
+         SELECT setval(pg_get_serial_sequence('orders', 'id'), MAX(id)) FROM orders HAVING MAX(id) IS NOT NULL;\n"
+    ));
+    assert!(sql.contains("ALTER TABLE invoices ALTER COLUMN id DROP IDENTITY IF EXISTS;"));
+    assert!(sql.contains("CREATE SEQUENCE invoices_id_seq OWNED BY invoices.id;"));
+    assert!(sql.contains("ALTER TABLE invoices ALTER COLUMN id SET DEFAULT nextval('invoices_id_seq');"));
+    assert!(!sql.contains("TYPE"));
+}
+
+#[test]
+fn postgresql_adds_identity_column() {
+    let mut cs = ChangeSet::new();
+    cs.add_change(SchemaChange::AddColumn {
+        table_name: "events".to_string(),
+        column: ColumnBuilder::new(None, "id", ColumnType::Sequence)
+            .identity_mode(Some(IdentityMode::ByDefault))
+            .identity_increment(Some(10))
+            .build(),
+    });
+
+    let generator = create_generator(DatabaseType::Postgresql);
+    let mut output = Vec::new();
+    generator.generate(&cs, &mut output).unwrap();
+    let sql = String::from_utf8(output).unwrap();
+    assert!(sql.contains("ALTER TABLE events ADD COLUMN id integer GENERATED BY DEFAULT AS IDENTITY (INCREMENT BY 10);"));
+}
+
+fn reserved_name_changes() -> ChangeSet {
+    let mut cs = ChangeSet::new();
+    cs.add_change(SchemaChange::AddColumn {
+        table_name: "sales.order".to_string(),
+        column: ColumnBuilder::new(None, "user", ColumnType::Int).build(),
+    });
+    cs.add_change(SchemaChange::AddRelation {
+        table_name: "order".to_string(),
+        relation: Relation::new("user", "id", "order", "user", RelationType::Enforce, false),
+        position: 1,
+    });
+    cs
+}
+
+#[test]
+fn reserved_words_are_quoted_for_each_dialect() {
+    let generate = |db_type| {
+        let mut output = Vec::new();
+        create_generator(db_type).generate(&reserved_name_changes(), &mut output).unwrap();
+        String::from_utf8(output).unwrap()
+    };
+
+    let sql = generate(DatabaseType::Postgresql);
+    assert!(sql.contains("ALTER TABLE sales.\"order\" ADD COLUMN \"user\" integer;"));
+    assert!(sql.contains(
+        "ALTER TABLE \"order\" ADD CONSTRAINT fk_order1 FOREIGN KEY (\"user\") REFERENCES \"user\"(id);"
+    ));
+
+    let sql = generate(DatabaseType::SqlServer);
+    assert!(sql.contains("ALTER TABLE sales.[order] ADD [user] integer NULL;"));
+    assert!(sql.contains("ALTER TABLE [order] ADD CONSTRAINT fk_order1 FOREIGN KEY ([user]) REFERENCES [user](id);"));
+
+    let sql = generate(DatabaseType::Sqlite);
+    assert!(sql.contains("ALTER TABLE sales.\"order\" ADD COLUMN user INTEGER;"));
+
+    let sql = generate(DatabaseType::MySql);
+    assert!(sql.contains("ALTER TABLE sales.`order` ADD COLUMN user integer NULL;"));
+    assert!(sql.contains("ALTER TABLE `order` ADD CONSTRAINT fk_order1 FOREIGN KEY (user) REFERENCES user(id);"));
+}
+
+#[test]
+fn identifier_quoting_can_be_forced_on_or_off() {
+    let generate = |quoting| {
+        let mut output = Vec::new();
+        create_generator_with_quoting(DatabaseType::Postgresql, quoting)
+            .generate(&reserved_name_changes(), &mut output)
+            .unwrap();
+        String::from_utf8(output).unwrap()
+    };
+
+    assert!(generate(IdentifierQuoting::Always).contains("ALTER TABLE \"sales\".\"order\" ADD COLUMN \"user\" integer;"));
+    assert!(generate(IdentifierQuoting::Never).contains("ALTER TABLE sales.order ADD COLUMN user integer;"));
+}
+
+fn naming_convention_changes() -> ChangeSet {
+    let mut cs = ChangeSet::new();
+    cs.add_change(SchemaChange::AddColumn {
+        table_name: "sales.OrderLine".to_string(),
+        column: ColumnBuilder::new(None, "UnitPrice", ColumnType::Long).build(),
+    });
+    cs.add_change(SchemaChange::RenameColumn {
+        table_name: "sales.OrderLine".to_string(),
+        old_name: "Quantity".to_string(),
+        new_name: "OrderedQuantity".to_string(),
+    });
+    cs.add_change(SchemaChange::AddKey {
+        table_name: "sales.OrderLine".to_string(),
+        key: KeyBuilder::new(KeyType::Index).add_column("ProductID").build(),
+        position: 1,
+    });
+    cs.add_change(SchemaChange::AddRelation {
+        table_name: "sales.OrderLine".to_string(),
+        relation: Relation::new("Product", "ID", "sales.OrderLine", "ProductID", RelationType::Enforce, false),
+        position: 1,
+    });
+    cs
+}
+
+#[test]
+fn migration_names_follow_the_naming_convention() {
+    let mut output = Vec::new();
+    create_generator_with_naming(DatabaseType::Postgresql, IdentifierQuoting::WhenNeeded, NamingConvention::SnakeCase, ConstraintNaming::default())
+        .generate(&naming_convention_changes(), &mut output)
+        .unwrap();
+    let sql = String::from_utf8(output).unwrap();
+    assert!(sql.contains("ALTER TABLE sales.order_line ADD COLUMN unit_price bigint;"));
+    assert!(sql.contains("ALTER TABLE sales.order_line RENAME COLUMN quantity TO ordered_quantity;"));
+    assert!(sql.contains("CREATE INDEX ix_order_line1 ON sales.order_line (product_id);"));
+    assert!(sql.contains("ALTER TABLE sales.order_line ADD CONSTRAINT fk_order_line1 FOREIGN KEY (product_id) REFERENCES product(id);"));
+
+    let mut output = Vec::new();
+    create_generator_with_naming(DatabaseType::SqlServer, IdentifierQuoting::WhenNeeded, NamingConvention::SnakeCase, ConstraintNaming::default())
+        .generate(&naming_convention_changes(), &mut output)
+        .unwrap();
+    let sql = String::from_utf8(output).unwrap();
+    assert!(sql.contains("EXEC sp_rename 'sales.order_line.quantity', 'ordered_quantity', 'COLUMN';"));
+    assert!(sql.contains("CREATE INDEX ix_order_line1 ON sales.order_line (product_id);"));
+}
+
+#[test]
+fn keys_and_relations_follow_custom_naming_templates() {
+    let naming = ConstraintNaming {
+        primary_key: "{table}_pk".to_string(),
+        unique_key: "{table}_uk{n}".to_string(),
+        foreign_key: "{table}_{column}_fk".to_string(),
+        index: "{table}_{column}_idx".to_string(),
+        ..ConstraintNaming::default()
+    };
+    let mut cs = ChangeSet::new();
+    cs.add_change(SchemaChange::AddKey {
+        table_name: "orders".to_string(),
+        key: KeyBuilder::new(KeyType::Primary).add_column("id").build(),
+        position: 1,
+    });
+    cs.add_change(SchemaChange::AddKey {
+        table_name: "orders".to_string(),
+        key: KeyBuilder::new(KeyType::Unique).add_column("number").build(),
+        position: 2,
+    });
+    cs.add_change(SchemaChange::AddKey {
+        table_name: "orders".to_string(),
+        key: KeyBuilder::new(KeyType::Index).add_column("placed_at").build(),
+        position: 1,
+    });
+    cs.add_change(SchemaChange::AddRelation {
+        table_name: "orders".to_string(),
+        relation: Relation::new("customer", "id", "orders", "customer_id", RelationType::Enforce, false),
+        position: 1,
+    });
+
+    let mut output = Vec::new();
+    create_generator_with_naming(DatabaseType::Postgresql, IdentifierQuoting::WhenNeeded, NamingConvention::Preserve, naming)
+        .generate(&cs, &mut output)
+        .unwrap();
+    let sql = String::from_utf8(output).unwrap();
+    assert!(sql.contains("ALTER TABLE orders ADD CONSTRAINT orders_pk PRIMARY KEY (id);"));
+    assert!(sql.contains("ALTER TABLE orders ADD CONSTRAINT orders_uk2 UNIQUE (number);"));
+    assert!(sql.contains("CREATE INDEX orders_placed_at_idx ON orders (placed_at);"));
+    assert!(sql.contains("ALTER TABLE orders ADD CONSTRAINT orders_customer_id_fk FOREIGN KEY (customer_id) REFERENCES customer(id);"));
+}
+
+fn schema_changes() -> ChangeSet {
+    let mut cs = ChangeSet::new();
+    cs.add_change(SchemaChange::AddSchema { schema_name: "archive".to_string() });
+    cs.add_change(SchemaChange::MoveTable {
+        table_name: "sales.orders".to_string(),
+        new_schema_name: Some("archive".to_string()),
+    });
+    cs.add_change(SchemaChange::DropSchema { schema_name: "legacy".to_string() });
+    cs
+}
+
+#[test]
+fn schemas_are_created_dropped_and_tables_moved_per_dialect() {
+    let generate = |db_type| {
+        let mut output = Vec::new();
+        create_generator(db_type).generate(&schema_changes(), &mut output).map(|_| String::from_utf8(output).unwrap())
+    };
+
+    let sql = generate(DatabaseType::Postgresql).unwrap();
+    assert!(sql.contains("CREATE SCHEMA IF NOT EXISTS archive;"));
+    assert!(sql.contains("ALTER TABLE sales.orders SET SCHEMA archive;"));
+    assert!(sql.contains("DROP SCHEMA IF EXISTS legacy;"));
+
+    let sql = generate(DatabaseType::SqlServer).unwrap();
+    assert!(sql.contains("IF SCHEMA_ID('archive') IS NULL EXEC('CREATE SCHEMA archive');\nGO"));
+    assert!(sql.contains("ALTER SCHEMA archive TRANSFER sales.orders;\nGO"));
+    assert!(sql.contains("DROP SCHEMA IF EXISTS legacy;\nGO"));
+
+    let sql = generate(DatabaseType::MySql).unwrap();
+    assert!(sql.contains("CREATE DATABASE IF NOT EXISTS archive;"));
+    assert!(sql.contains("RENAME TABLE sales.orders TO archive.orders;"));
+    assert!(sql.contains("DROP DATABASE IF EXISTS legacy;"));
+
+    for db_type in [DatabaseType::Sqlite, DatabaseType::DuckDb] {
+        let err = generate(db_type).unwrap_err();
+        assert!(matches!(err, MigrationGeneratorError::UnsupportedChange(msg) if msg.contains("sales.orders")));
+    }
+}
+
+#[test]
+fn tables_moved_to_the_default_schema_land_in_the_dialect_default() {
+    let mut cs = ChangeSet::new();
+    cs.add_change(SchemaChange::MoveTable { table_name: "sales.orders".to_string(), new_schema_name: None });
+    let generate = |db_type| {
+        let mut output = Vec::new();
+        create_generator(db_type).generate(&cs, &mut output).unwrap();
+        String::from_utf8(output).unwrap()
+    };
+
+    assert!(generate(DatabaseType::Postgresql).contains("ALTER TABLE sales.orders SET SCHEMA public;"));
+    assert!(generate(DatabaseType::SqlServer).contains("ALTER SCHEMA dbo TRANSFER sales.orders;"));
+    assert!(generate(DatabaseType::MySql).contains("RENAME TABLE sales.orders TO orders;"));
+}
+
+#[test]
+fn qualified_tables_keep_indexes_and_renames_in_their_schema() {
+    let mut cs = ChangeSet::new();
+    cs.add_change(SchemaChange::RenameTable {
+        old_name: "sales.orders".to_string(),
+        new_name: "sales.purchases".to_string(),
+    });
+    cs.add_change(SchemaChange::AddKey {
+        table_name: "sales.purchases".to_string(),
+        key: KeyBuilder::new(KeyType::Index).add_column("placed_at").build(),
+        position: 1,
+    });
+    cs.add_change(SchemaChange::DropKey {
+        table_name: "sales.purchases".to_string(),
+        key: KeyBuilder::new(KeyType::Index).add_column("status").build(),
+        position: 2,
+    });
+    let generate = |db_type| {
+        let mut output = Vec::new();
+        create_generator(db_type).generate(&cs, &mut output).unwrap();
+        String::from_utf8(output).unwrap()
+    };
+
+    let sql = generate(DatabaseType::Postgresql);
+    assert!(sql.contains("ALTER TABLE sales.orders RENAME TO purchases;"));
+    assert!(sql.contains("CREATE INDEX ix_purchases1 ON sales.purchases (placed_at);"));
+    assert!(sql.contains("DROP INDEX IF EXISTS sales.ix_purchases2;"));
+
+    let sql = generate(DatabaseType::SqlServer);
+    assert!(sql.contains("EXEC sp_rename 'sales.orders', 'purchases';"));
+    assert!(sql.contains(
+        "IF EXISTS (SELECT 1 FROM sys.indexes WHERE name = 'ix_purchases2' AND object_id = OBJECT_ID('sales.purchases')) DROP INDEX ix_purchases2 ON sales.purchases;"
+    ));
+
+    let sql = generate(DatabaseType::Sqlite);
+    assert!(sql.contains("CREATE INDEX IF NOT EXISTS sales.ix_purchases1 ON purchases (placed_at);"));
+    assert!(sql.contains("DROP INDEX IF EXISTS sales.ix_purchases2;"));
+}
+
+fn status_enum(codes: &[&str]) -> EnumType {
+    EnumType::new("OrderStatus", codes.iter().map(|code| EnumValue::new(*code, Some(*code))).collect())
+}
+
+fn enum_change(old_codes: &[&str], new_codes: &[&str]) -> ChangeSet {
+    let column = ColumnBuilder::new(None, "status", ColumnType::Enum)
+        .enum_type(Some("OrderStatus".to_string()))
+        .default_constraint(Some("'N'".to_string()))
+        .build();
+    let mut cs = ChangeSet::new();
+    cs.add_change(SchemaChange::ModifyEnumType {
+        old_enum_type: status_enum(old_codes),
+        new_enum_type: status_enum(new_codes),
+        columns: vec![("orders".to_string(), column)],
+    });
+    cs
+}
+
+fn generate_sql(db_type: DatabaseType, cs: &ChangeSet) -> String {
+    let mut output = Vec::new();
+    create_generator(db_type).generate(cs, &mut output).unwrap();
+    String::from_utf8(output).unwrap()
+}
+
+#[test]
+fn postgresql_adds_enum_values_in_place() {
+    let sql = generate_sql(DatabaseType::Postgresql, &enum_change(&["N", "S"], &["N", "P", "S", "C"]));
+    assert!(sql.contains("ALTER TYPE order_status ADD VALUE IF NOT EXISTS 'P' BEFORE 'S';"));
+    assert!(sql.contains("ALTER TYPE order_status ADD VALUE IF NOT EXISTS 'C';"));
+    assert!(!sql.contains("RENAME TO"));
+}
+
+#[test]
+fn postgresql_rebuilds_enum_when_values_are_removed_or_reordered() {
+    for new_codes in [&["N"][..], &["S", "N"][..]] {
+        let sql = generate_sql(DatabaseType::Postgresql, &enum_change(&["N", "S"], new_codes));
+        assert!(sql.contains("ALTER TYPE order_status RENAME TO order_status_old;"));
+        assert!(sql.contains("ALTER TABLE orders ALTER COLUMN status DROP DEFAULT;"));
+        assert!(sql.contains("ALTER TABLE orders ALTER COLUMN status TYPE order_status USING status::text::order_status;"));
+        assert!(sql.contains("ALTER TABLE orders ALTER COLUMN status SET DEFAULT 'N';"));
+        assert!(sql.contains("DROP TYPE order_status_old;"));
+    }
+}
+
+#[test]
+fn enum_columns_follow_a_changed_enum_per_dialect() {
+    let cs = enum_change(&["N", "S"], &["N", "S", "XL"]);
+
+    let sql = generate_sql(DatabaseType::SqlServer, &cs);
+    assert!(sql.contains("EXEC('ALTER TABLE orders DROP CONSTRAINT ' + @check)"));
+    assert!(sql.contains("ALTER TABLE orders ALTER COLUMN status nvarchar(2) NULL;"));
+    assert!(sql.contains("ALTER TABLE orders ADD CONSTRAINT ck_orders_status_7F194E8C CHECK (status IN ('N', 'S', 'XL'));\nGO"));
+
+    let sql = generate_sql(DatabaseType::MySql, &cs);
+    assert!(sql.contains("ALTER TABLE orders MODIFY COLUMN status ENUM('N', 'S', 'XL')"));
+
+    let sql = generate_sql(DatabaseType::DuckDb, &cs);
+    assert!(sql.contains("ALTER TABLE orders ALTER COLUMN status TYPE varchar;"));
+    assert!(sql.contains("CREATE TYPE OrderStatus AS ENUM ('N', 'S', 'XL');"));
+
+    let sql = generate_sql(DatabaseType::Sqlite, &cs);
+    assert!(sql.contains("-- Manually recreate the table with CHECK (status IN ('N', 'S', 'XL'))."));
+}
+
+fn program_changes() -> ChangeSet {
+    let mut cs = ChangeSet::new();
+    for (db_type, sql) in [
+        (DatabaseType::Postgresql, "create function totals() returns int as $$ select 1 $$ language sql"),
+        (DatabaseType::SqlServer, "create function totals() returns int as begin return 1 end"),
+        (DatabaseType::MySql, "create function totals() returns int deterministic return 1;"),
+    ] {
+        cs.add_change(SchemaChange::AddFunction { function: Function::new(None, "totals", db_type, sql) });
+    }
+    cs.add_change(SchemaChange::ModifyProcedure {
+        old_procedure: Procedure::new(Some("sales"), "close_day", DatabaseType::SqlServer, "create procedure sales.close_day as select 0"),
+        new_procedure: Procedure::new(Some("sales"), "close_day", DatabaseType::SqlServer, "create procedure sales.close_day as select 1"),
+    });
+    cs.add_change(SchemaChange::DropProcedure {
+        procedure: Procedure::new(None, "purge", DatabaseType::Postgresql, "create procedure purge() language sql as $$ $$"),
+    });
+    cs.add_change(SchemaChange::AddOtherSql {
+        other_sql: OtherSql::new(DatabaseType::MySql, OtherSqlOrder::Bottom, "set global event_scheduler = on"),
+    });
+    cs
+}
+
+#[test]
+fn programs_are_replaced_in_place_and_filtered_by_database() {
+    let sql = generate_sql(DatabaseType::Postgresql, &program_changes());
+    assert!(sql.contains("CREATE OR REPLACE function totals() returns int as $$ select 1 $$ language sql;"));
+    assert!(sql.contains("DROP PROCEDURE IF EXISTS purge;"));
+    assert!(!sql.contains("begin return 1 end"));
+    assert!(!sql.contains("event_scheduler"));
+
+    let sql = generate_sql(DatabaseType::SqlServer, &program_changes());
+    assert!(sql.contains("CREATE OR ALTER function totals() returns int as begin return 1 end\nGO"));
+    assert!(sql.contains("CREATE OR ALTER procedure sales.close_day as select 1\nGO"));
+    assert!(!sql.contains("purge"));
+
+    let sql = generate_sql(DatabaseType::MySql, &program_changes());
+    assert!(sql.contains("DROP FUNCTION IF EXISTS totals;"));
+    assert!(sql.contains("DELIMITER //\ncreate function totals() returns int deterministic return 1//\nDELIMITER ;"));
+    assert!(sql.contains("set global event_scheduler = on;"));
+}
+
+#[test]
+fn removed_other_sql_drops_what_it_created() {
+    let removed = |db_type| {
+        let mut cs = ChangeSet::new();
+        cs.add_change(SchemaChange::DropOtherSql {
+            other_sql: OtherSql::new(
+                db_type,
+                OtherSqlOrder::Bottom,
+                "create or replace function order_count(a int) returns int as $$ select 1 $$ language sql;\n\
+                 create unique index if not exists ix_orders_ref on orders (ref);\n\
+                 create trigger orders_audit after insert on orders for each row execute function audit();\n\
+                 grant select on orders to reporting;",
+            ),
+        });
+        generate_sql(db_type, &cs)
+    };
+
+    let sql = removed(DatabaseType::Postgresql);
+    assert!(sql.starts_with(
+        "DROP TRIGGER IF EXISTS orders_audit ON orders;\n\nDROP INDEX IF EXISTS ix_orders_ref;\n\nDROP FUNCTION IF EXISTS order_count;\n\n"
+    ));
+    assert!(sql.contains("-- This SQL was removed from the model; undo what it did by hand:\n--   grant select on orders to reporting\n"));
+    assert!(!sql.contains("--   create"));
+
+    let sql = removed(DatabaseType::MySql);
+    assert!(sql.contains("DROP TRIGGER IF EXISTS orders_audit;"));
+    assert!(sql.contains("DROP INDEX ix_orders_ref ON orders;"));
+}
+
+#[test]
+fn procedures_are_rejected_where_the_dialect_has_none() {
+    for db_type in [DatabaseType::Sqlite, DatabaseType::DuckDb] {
+        let mut cs = ChangeSet::new();
+        cs.add_change(SchemaChange::AddProcedure { procedure: Procedure::new(None, "purge", db_type, "create procedure purge") });
+        let err = create_generator(db_type).generate(&cs, &mut Vec::new()).unwrap_err();
+        assert!(matches!(err, MigrationGeneratorError::UnsupportedChange(msg) if msg.contains("purge")));
+    }
+}
+
+/// A new model in which `orders` has custom delete trigger code for every dialect and
+/// `sale` is summed into `sale_summary`.
+fn trigger_model() -> GenerateOptions {
+    let mut orders = TableBuilder::new(None, "orders")
+        .add_column(ColumnBuilder::new(None, "id", ColumnType::Int).required(true).build())
+        .add_key(KeyBuilder::new(KeyType::Primary).add_column("id").build());
+    for db_type in [DatabaseType::Postgresql, DatabaseType::Sqlite, DatabaseType::SqlServer, DatabaseType::MySql] {
+        orders = orders.add_trigger(Trigger::new("   delete from audit;", TriggerType::Delete, db_type));
+    }
+    let sale = TableBuilder::new(None, "sale")
+        .add_column(ColumnBuilder::new(None, "sold_on", ColumnType::Date).build())
+        .add_column(ColumnBuilder::new(None, "amount", ColumnType::Int).build())
+        .add_aggregation(sale_aggregation(AggregationFrequency::Monthly));
+    let summary = TableBuilder::new(None, "sale_summary")
+        .add_column(ColumnBuilder::new(None, "sold_on", ColumnType::Date).build())
+        .add_column(ColumnBuilder::new(None, "total", ColumnType::Int).build())
+        .add_column(ColumnBuilder::new(None, "summarized_at", ColumnType::Timestamp).build());
+    let schema = SchemaBuilder::new(None::<&str>).add_table(orders.build()).add_table(sale.build()).add_table(summary.build()).build();
+    GenerateOptions::new(Arc::new(DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema])))
+}
+
+fn sale_aggregation(frequency: AggregationFrequency) -> Aggregation {
+    Aggregation::new(
+        "sale_summary",
+        "sold_on",
+        None,
+        "summarized_at",
+        frequency,
+        vec![AggregationColumn::new(AggregationType::Sum, "amount", "total")],
+        vec![],
+    )
+}
+
+fn trigger_sql(db_type: DatabaseType, cs: &ChangeSet) -> Result<String, MigrationGeneratorError> {
+    let mut output = Vec::new();
+    create_generator_with_options(db_type, trigger_model()).generate(cs, &mut output)?;
+    Ok(String::from_utf8(output).unwrap())
+}
+
+#[test]
+fn changed_trigger_code_regenerates_the_tables_triggers_per_dialect() {
+    let mut cs = ChangeSet::new();
+    for db_type in [DatabaseType::Postgresql, DatabaseType::Sqlite, DatabaseType::SqlServer, DatabaseType::MySql] {
+        cs.add_change(SchemaChange::AddTrigger {
+            table_name: "orders".to_string(),
+            trigger: Trigger::new("   delete from audit;", TriggerType::Delete, db_type),
+        });
+    }
+
+    let sql = trigger_sql(DatabaseType::Postgresql, &cs).unwrap();
+    assert!(sql.contains("DROP FUNCTION IF EXISTS public.orders_update() CASCADE;"));
+    assert!(sql.contains("create or replace function public.orders_delete() returns trigger as $BODY$\nbegin\n   delete from audit;\n"));
+    assert!(sql.contains("drop trigger if exists orders_delete on public.orders cascade;\n\ncreate trigger orders_delete after delete on public.orders"));
+
+    let sql = trigger_sql(DatabaseType::Sqlite, &cs).unwrap();
+    assert!(sql.contains("DROP TRIGGER IF EXISTS orders_insert;"));
+    assert!(sql.contains("drop trigger if exists orders_delete;\n\ncreate trigger orders_delete before delete on orders for each row\nbegin\n   delete from audit;\nend;"));
+
+    let sql = trigger_sql(DatabaseType::MySql, &cs).unwrap();
+    assert!(sql.contains("DROP TRIGGER IF EXISTS orders_update;"));
+    assert!(sql.contains("drop trigger if exists orders_delete;\n\nDELIMITER //\ncreate trigger orders_delete after delete on orders for each row"));
+    assert!(sql.contains("end//\nDELIMITER ;"));
+
+    let sql = trigger_sql(DatabaseType::SqlServer, &cs).unwrap();
+    assert!(sql.contains("DROP TRIGGER IF EXISTS orders_update;\nGO"));
+    assert!(sql.contains("CREATE OR ALTER trigger orders_delete on dbo.orders for delete as"));
+    assert!(!sql.contains("drop trigger orders_delete"));
+}
+
+#[test]
+fn changed_aggregations_regenerate_the_source_tables_triggers() {
+    let mut cs = ChangeSet::new();
+    cs.add_change(SchemaChange::DropAggregation { table_name: "sale".to_string(), aggregation: sale_aggregation(AggregationFrequency::Daily) });
+    cs.add_change(SchemaChange::AddAggregation { table_name: "sale".to_string(), aggregation: sale_aggregation(AggregationFrequency::Monthly) });
+
+    let sql = trigger_sql(DatabaseType::Postgresql, &cs).unwrap();
+    assert_eq!(sql.matches("create or replace function public.sale_delete()").count(), 1);
+    assert!(sql.contains("create or replace function public.sale_update()"));
+    assert!(sql.contains("date_trunc('month'"));
+    assert!(!sql.contains("DROP FUNCTION"));
+
+    let error = trigger_sql(DatabaseType::DuckDb, &cs).unwrap_err();
+    assert!(error.to_string().contains("DuckDB has no triggers to maintain aggregations with"), "{}", error);
+}
+
+#[test]
+fn regenerating_triggers_needs_the_new_model() {
+    let mut cs = ChangeSet::new();
+    cs.add_change(SchemaChange::AddTrigger {
+        table_name: "orders".to_string(),
+        trigger: Trigger::new("delete from audit", TriggerType::Delete, DatabaseType::Postgresql),
+    });
+
+    let error = create_generator(DatabaseType::Postgresql).generate(&cs, &mut Vec::new()).unwrap_err();
+    assert!(matches!(error, MigrationGeneratorError::UnsupportedChange(msg) if msg.contains("create_generator_with_options")));
+    assert!(create_generator(DatabaseType::MySql).generate(&cs, &mut Vec::new()).is_ok());
+}
+
+#[test]
+fn renames_regenerate_the_triggers_naming_the_renamed_table_or_column() {
+    let mut cs = ChangeSet::new();
+    cs.add_change(SchemaChange::RenameTable { old_name: "sales".to_string(), new_name: "sale".to_string() });
+    let sql = trigger_sql(DatabaseType::Postgresql, &cs).unwrap();
+    assert!(sql.contains("DROP FUNCTION IF EXISTS public.sales_delete() CASCADE;"));
+    assert!(sql.contains("create or replace function public.sale_delete()"));
+    let sql = trigger_sql(DatabaseType::SqlServer, &cs).unwrap();
+    assert!(sql.contains("DROP TRIGGER IF EXISTS sales_update;\nGO"));
+    assert!(sql.contains("CREATE OR ALTER trigger sale_update on dbo.sale"));
+
+    // The summary has no triggers of its own, but the sale triggers write to it.
+    let mut cs = ChangeSet::new();
+    cs.add_change(SchemaChange::RenameTable { old_name: "summary".to_string(), new_name: "sale_summary".to_string() });
+    cs.add_change(SchemaChange::RenameColumn {
+        table_name: "sale_summary".to_string(),
+        old_name: "amount".to_string(),
+        new_name: "total".to_string(),
+    });
+    let sql = trigger_sql(DatabaseType::Postgresql, &cs).unwrap();
+    assert_eq!(sql.matches("create or replace function public.sale_update()").count(), 1);
+    assert!(sql.contains("public.sale_summary"));
+    assert!(!sql.contains("summary_delete"));
+    assert!(!sql.contains("DROP FUNCTION"));
+    assert!(!sql.contains("orders_"));
+
+    let mut cs = ChangeSet::new();
+    cs.add_change(SchemaChange::RenameColumn {
+        table_name: "orders".to_string(),
+        old_name: "order_id".to_string(),
+        new_name: "id".to_string(),
+    });
+    let sql = trigger_sql(DatabaseType::MySql, &cs).unwrap();
+    assert!(sql.contains("create trigger orders_delete"));
+    assert!(!sql.contains("sale_"));
+    assert!(generate_sql(DatabaseType::MySql, &cs).contains("RENAME COLUMN order_id TO id"));
+}
+
+#[test]
+fn renamed_tables_regenerate_the_triggers_keeping_their_foreign_keys() {
+    let relation = Relation::new("customer", "id", "invoice", "customer_id", RelationType::Cascade, false);
+    let mut customer = TableBuilder::new(None, "customer")
+        .add_column(ColumnBuilder::new(None, "id", ColumnType::Int).required(true).build())
+        .add_key(KeyBuilder::new(KeyType::Primary).add_column("id").build())
+        .build();
+    customer.add_reverse_relation(relation.clone());
+    let invoice = TableBuilder::new(None, "invoice")
+        .add_column(ColumnBuilder::new(None, "id", ColumnType::Int).required(true).build())
+        .add_column(ColumnBuilder::new(None, "customer_id", ColumnType::Int).build())
+        .add_key(KeyBuilder::new(KeyType::Primary).add_column("id").build())
+        .add_relation(relation);
+    let schema = SchemaBuilder::new(None::<&str>).add_table(customer).add_table(invoice.build()).build();
+    let model = Arc::new(DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Triggers, vec![schema]));
+    let mut cs = ChangeSet::new();
+    cs.add_change(SchemaChange::RenameTable { old_name: "client".to_string(), new_name: "customer".to_string() });
+
+    let mut sql = Vec::new();
+    let options = GenerateOptions { foreign_key_mode: ForeignKeyMode::Triggers, ..GenerateOptions::new(model.clone()) };
+    create_generator_with_options(DatabaseType::Postgresql, options).generate(&cs, &mut sql).unwrap();
+    let sql = String::from_utf8(sql).unwrap();
+    assert!(sql.contains("DROP FUNCTION IF EXISTS public.client_delete() CASCADE;"));
+    assert!(sql.contains("create or replace function public.customer_delete()"));
+    assert!(sql.contains("create or replace function public.invoice_update()"));
+
+    let mut sql = Vec::new();
+    create_generator_with_options(DatabaseType::Postgresql, GenerateOptions::new(model)).generate(&cs, &mut sql).unwrap();
+    assert!(!String::from_utf8(sql).unwrap().contains("invoice_update"));
+}
+
+fn rename_changes() -> ChangeSet {
+    let mut cs = ChangeSet::new();
+    cs.add_change(SchemaChange::RenameConstraint {
+        table_name: "sales.orders".to_string(),
+        old_name: "ck_total".to_string(),
+        new_name: "ck_orders_total".to_string(),
+    });
+    cs.add_change(SchemaChange::RenameIndex {
+        table_name: "sales.orders".to_string(),
+        old_name: "idx_purchases_total".to_string(),
+        key: KeyBuilder::new(KeyType::Index).add_column("total").build(),
+        position: 1,
+    });
+    cs
+}
+
+#[test]
+fn constraints_and_indexes_are_renamed_per_dialect() {
+    let sql = generate_sql(DatabaseType::Postgresql, &rename_changes());
+    assert!(sql.contains("ALTER TABLE sales.orders RENAME CONSTRAINT ck_total TO ck_orders_total;"));
+    assert!(sql.contains("ALTER INDEX IF EXISTS sales.idx_purchases_total RENAME TO ix_orders1;"));
+
+    let sql = generate_sql(DatabaseType::SqlServer, &rename_changes());
+    assert!(sql.contains("EXEC sp_rename 'sales.ck_total', 'ck_orders_total', 'OBJECT';\nGO"));
+    assert!(sql.contains("EXEC sp_rename 'sales.orders.idx_purchases_total', 'ix_orders1', 'INDEX';\nGO"));
+
+    let sql = generate_sql(DatabaseType::MySql, &rename_changes());
+    assert!(sql.contains("-- MySQL does not support renaming constraint 'ck_total'"));
+    assert!(sql.contains("ALTER TABLE sales.orders RENAME INDEX idx_purchases_total TO ix_orders1;"));
+
+    for (db_type, create_index) in [
+        (DatabaseType::Sqlite, "CREATE INDEX IF NOT EXISTS sales.ix_orders1 ON orders (total);"),
+        (DatabaseType::DuckDb, "CREATE INDEX ix_orders1 ON sales.orders (total);"),
+    ] {
+        let sql = generate_sql(db_type, &rename_changes());
+        assert!(sql.contains("does not support renaming constraint 'ck_total'"));
+        assert!(sql.contains("DROP INDEX IF EXISTS sales.idx_purchases_total;"));
+        assert!(sql.contains(create_index), "{}", sql);
+    }
+}
+
+fn column_attribute_changes() -> ChangeSet {
+    let mut cs = ChangeSet::new();
+    cs.add_change(SchemaChange::ModifyColumn {
+        table_name: "orders".to_string(),
+        old_column: ColumnBuilder::new(None, "amount", ColumnType::Int).min_value(Some(0.0)).build(),
+        new_column: ColumnBuilder::new(None, "amount", ColumnType::Int).min_value(Some(1.0)).max_value(Some(100.0)).build(),
+        changes: vec![ColumnAttribute::MinValue, ColumnAttribute::MaxValue],
+        enum_type: None,
+    });
+    cs.add_change(SchemaChange::ModifyColumn {
+        table_name: "orders".to_string(),
+        old_column: ColumnBuilder::new(None, "total", ColumnType::Int).build(),
+        new_column: ColumnBuilder::new(None, "total", ColumnType::Int).generated(Some("amount * 2".to_string())).build(),
+        changes: vec![ColumnAttribute::Generated],
+        enum_type: None,
+    });
+    cs.add_change(SchemaChange::ModifyColumn {
+        table_name: "orders".to_string(),
+        old_column: ColumnBuilder::new(None, "status", ColumnType::Varchar).build(),
+        new_column: ColumnBuilder::new(None, "status", ColumnType::Enum).enum_type(Some("OrderStatus".to_string())).build(),
+        changes: vec![ColumnAttribute::Type, ColumnAttribute::EnumType],
+        enum_type: Some(status_enum(&["N", "S"])),
+    });
+    cs
+}
+
+#[test]
+fn modified_columns_alter_each_changed_attribute_per_dialect() {
+    let cs = column_attribute_changes();
+
+    let sql = generate_sql(DatabaseType::Postgresql, &cs);
+    assert!(sql.contains("ALTER TABLE orders DROP CONSTRAINT IF EXISTS ck_orders_amount_6005CEF2;"));
+    assert!(sql.contains("ALTER TABLE orders ADD CONSTRAINT ck_orders_amount_6005CEF2 CHECK (amount >= 1 AND amount <= 100);"));
+    assert!(sql.contains(
+        "ALTER TABLE orders DROP COLUMN total;\n\
+         ALTER TABLE orders ADD COLUMN total integer GENERATED ALWAYS AS (amount * 2) STORED;\n"
+    ));
+    assert!(sql.contains("ALTER TABLE orders ALTER COLUMN status TYPE order_status USING status::text::order_status;"));
+
+    let sql = generate_sql(DatabaseType::SqlServer, &cs);
+    assert!(sql.contains("IF @default IS NOT NULL EXEC('ALTER TABLE orders DROP CONSTRAINT ' + @default);\nALTER TABLE orders DROP COLUMN total;"));
+    assert!(sql.contains("ALTER TABLE orders ADD total AS (amount * 2) PERSISTED;"));
+    assert!(sql.contains("ALTER TABLE orders ALTER COLUMN status nchar(1) NULL;\nALTER TABLE orders ADD CONSTRAINT ck_orders_status_7F194E8C CHECK (status IN ('N', 'S'));"));
+
+    let sql = generate_sql(DatabaseType::MySql, &cs);
+    assert!(sql.contains("SET @sql = IF(@check IS NULL, 'DO 0', CONCAT('ALTER TABLE orders DROP CHECK `', @check, '`'));"));
+    assert!(sql.contains("ALTER TABLE orders ADD CONSTRAINT ck_orders_amount_6005CEF2 CHECK (amount >= 1 AND amount <= 100);"));
+    assert!(sql.contains("ALTER TABLE orders MODIFY COLUMN total integer GENERATED ALWAYS AS (amount * 2) STORED NULL;"));
+    assert!(sql.contains("ALTER TABLE orders MODIFY COLUMN status ENUM('N', 'S') NULL;"));
+
+    let sql = generate_sql(DatabaseType::Sqlite, &cs);
+    assert!(sql.contains("on table 'orders' in-place (min value, max value changed)."));
+    assert!(sql.contains("--   total INTEGER GENERATED ALWAYS AS (amount * 2) STORED"));
+    assert!(sql.contains("--   status TEXT CHECK (status IN ('N', 'S'))"));
+
+    let sql = generate_sql(DatabaseType::DuckDb, &cs);
+    assert!(sql.contains("-- Manually recreate the table with CHECK (amount >= 1 AND amount <= 100)."));
+    assert!(sql.contains("-- Manually recreate the table with total integer GENERATED ALWAYS AS (amount * 2)."));
+    assert!(sql.contains("ALTER TABLE orders ALTER COLUMN status TYPE OrderStatus;"));
+}
+
+#[test]
+fn postgresql_keeps_the_values_of_a_column_that_stops_being_generated() {
+    let mut cs = ChangeSet::new();
+    cs.add_change(SchemaChange::ModifyColumn {
+        table_name: "orders".to_string(),
+        old_column: ColumnBuilder::new(None, "total", ColumnType::Int).generated(Some("amount * 2".to_string())).build(),
+        new_column: ColumnBuilder::new(None, "total", ColumnType::Long).default_constraint(Some("0".to_string())).build(),
+        changes: vec![ColumnAttribute::Type, ColumnAttribute::Default, ColumnAttribute::Generated],
+        enum_type: None,
+    });
+
+    let sql = generate_sql(DatabaseType::Postgresql, &cs);
+    assert!(sql.contains(
+        "ALTER TABLE orders ALTER COLUMN total DROP EXPRESSION;\n\
+         ALTER TABLE orders ALTER COLUMN total TYPE bigint;\n\
+         ALTER TABLE orders ALTER COLUMN total SET DEFAULT 0;\n"
+    ));
+    assert!(!sql.contains("DROP COLUMN"));
+}
+
+fn option_changes() -> ChangeSet {
+    let mut cs = ChangeSet::new();
+    cs.add_change(SchemaChange::ModifyTableOptions {
+        table_name: "orders".to_string(),
+        old_options: vec![TableOption::Strict],
+        new_options: vec![TableOption::Compress(CompressionLevel::Page)],
+        old_lock_escalation: LockEscalation::Auto,
+        new_lock_escalation: LockEscalation::Disable,
+        columns: vec![
+            ColumnBuilder::new(None, "id", ColumnType::Int).build(),
+            ColumnBuilder::new(None, "notes", ColumnType::Text).build(),
+        ],
+    });
+    cs.add_change(SchemaChange::ModifyKey {
+        table_name: "orders".to_string(),
+        old_key: KeyBuilder::new(KeyType::Index).add_column("customer_id").build(),
+        new_key: KeyBuilder::new(KeyType::Index).add_column("customer_id").include("total, status").cluster(true).build(),
+        position: 1,
+    });
+    cs.add_change(SchemaChange::ModifyKey {
+        table_name: "orders".to_string(),
+        old_key: KeyBuilder::new(KeyType::Index).add_column("placed_at").build(),
+        new_key: KeyBuilder::new(KeyType::Index).add_column("placed_at").compression(CompressionLevel::Row).build(),
+        position: 2,
+    });
+    cs.add_change(SchemaChange::ModifyRelation {
+        table_name: "orders".to_string(),
+        old_relation: Relation::new("customer", "id", "orders", "customer_id", RelationType::Cascade, false),
+        new_relation: Relation::new("customer", "id", "orders", "customer_id", RelationType::SetNull, false),
+        position: 1,
+    });
+    cs
+}
+
+#[test]
+fn changed_table_index_and_relation_options_are_applied_per_dialect() {
+    let cs = option_changes();
+
+    let sql = generate_sql(DatabaseType::Postgresql, &cs);
+    assert!(sql.contains("ALTER TABLE orders ALTER COLUMN notes SET COMPRESSION lz4;\n\n"));
+    assert!(!sql.contains("COLUMN id SET COMPRESSION"));
+    assert!(sql.contains(
+        "DROP INDEX IF EXISTS ix_orders1;\n\n\
+         CREATE INDEX ix_orders1 ON orders (customer_id) INCLUDE (total,status);\n\n\
+         ALTER TABLE orders CLUSTER ON ix_orders1;\n"
+    ));
+    assert!(!sql.contains("ix_orders2"));
+    assert!(sql.contains(
+        "ALTER TABLE orders DROP CONSTRAINT fk_orders1;\n\n\
+         ALTER TABLE orders ADD CONSTRAINT fk_orders1 FOREIGN KEY (customer_id) REFERENCES customer(id) ON DELETE SET NULL;"
+    ));
+
+    let sql = generate_sql(DatabaseType::SqlServer, &cs);
+    assert!(sql.contains("ALTER TABLE orders REBUILD WITH (DATA_COMPRESSION = PAGE);\nGO"));
+    assert!(sql.contains("ALTER TABLE orders SET (LOCK_ESCALATION = DISABLE);\nGO"));
+    assert!(sql.contains("CREATE CLUSTERED INDEX ix_orders1 ON orders (customer_id) INCLUDE (total,status);"));
+    assert!(sql.contains("ALTER INDEX ix_orders2 ON orders REBUILD WITH (DATA_COMPRESSION = ROW);\nGO"));
+    assert!(sql.contains("ON DELETE SET NULL;"));
+
+    let sql = generate_sql(DatabaseType::MySql, &cs);
+    assert!(!sql.contains("COMPRESSION") && !sql.contains("INDEX"));
+    assert!(sql.contains("ALTER TABLE orders DROP FOREIGN KEY fk_orders1;\n\nALTER TABLE orders ADD CONSTRAINT"));
+
+    let sql = generate_sql(DatabaseType::Sqlite, &cs);
+    assert!(sql.contains("-- SQLite does not support changing the STRICT option of table 'orders' in-place.\n-- Manually recreate the table without the STRICT option."));
+    assert!(!sql.contains("INDEX"));
+    assert!(sql.contains("-- Ensure CONSTRAINT fk_orders1 FOREIGN KEY (customer_id) REFERENCES customer(id) ON DELETE SET NULL is in the CREATE TABLE statement for 'orders'."));
+
+    let sql = generate_sql(DatabaseType::DuckDb, &cs);
+    assert!(sql.is_empty());
+}
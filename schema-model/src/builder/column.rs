@@ -1,5 +1,6 @@
 use crate::model::column::Column;
 use crate::model::column_type::ColumnType;
+use crate::model::types::IdentityMode;
 
 /// ColumnBuilder holds intermediate settings for a column and produces a model::Column on build.
 #[derive(Debug)]
@@ -17,6 +18,12 @@ pub struct ColumnBuilder {
     max_value: Option<f64>,
     enum_type: Option<String>,
     element_type: Option<String>,
+    description: Option<String>,
+    sequence: Option<String>,
+    identity_mode: Option<IdentityMode>,
+    identity_start: Option<i64>,
+    identity_increment: Option<i64>,
+    previous_name: Option<String>,
 }
 
 impl ColumnBuilder {
@@ -35,6 +42,12 @@ impl ColumnBuilder {
             max_value: None,
             enum_type: None,
             element_type: None,
+            description: None,
+            sequence: None,
+            identity_mode: None,
+            identity_start: None,
+            identity_increment: None,
+            previous_name: None,
         }
     }
     pub fn length(mut self, length: i32) -> Self {
@@ -87,6 +100,36 @@ impl ColumnBuilder {
         self
     }
 
+    pub fn description(mut self, description: Option<String>) -> Self {
+        self.description = description;
+        self
+    }
+
+    pub fn sequence(mut self, sequence: Option<String>) -> Self {
+        self.sequence = sequence;
+        self
+    }
+
+    pub fn identity_mode(mut self, identity_mode: Option<IdentityMode>) -> Self {
+        self.identity_mode = identity_mode;
+        self
+    }
+
+    pub fn identity_start(mut self, identity_start: Option<i64>) -> Self {
+        self.identity_start = identity_start;
+        self
+    }
+
+    pub fn identity_increment(mut self, identity_increment: Option<i64>) -> Self {
+        self.identity_increment = identity_increment;
+        self
+    }
+
+    pub fn previous_name(mut self, previous_name: Option<String>) -> Self {
+        self.previous_name = previous_name;
+        self
+    }
+
     pub fn build(self) -> Column {
         Column::new_all(
             self.schema_name,
@@ -102,6 +145,12 @@ impl ColumnBuilder {
             self.max_value,
             self.enum_type,
             self.element_type,
+            self.description,
+            self.sequence,
+            self.identity_mode,
+            self.identity_start,
+            self.identity_increment,
+            self.previous_name,
         )
     }
 }
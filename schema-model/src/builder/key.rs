@@ -1,5 +1,5 @@
 use crate::model::key::{Key, KeyColumn};
-use crate::model::types::KeyType;
+use crate::model::types::{CompressionLevel, IndexMethod, KeyType};
 
 /// KeyBuilder collects column names and attributes, producing a model::Key.
 #[derive(Debug)]
@@ -7,9 +7,12 @@ pub struct KeyBuilder {
     key_type: KeyType,
     columns: Vec<KeyColumn>,
     cluster: bool,
-    compress: bool,
+    compression: Option<CompressionLevel>,
     unique: bool,
     include: Option<String>,
+    method: Option<IndexMethod>,
+    filter: Option<String>,
+    previous_name: Option<String>,
 }
 
 impl KeyBuilder {
@@ -18,21 +21,32 @@ impl KeyBuilder {
             key_type,
             columns: Vec::new(),
             cluster: false,
-            compress: false,
+            compression: None,
             unique: false,
             include: None,
+            method: None,
+            filter: None,
+            previous_name: None,
         }
     }
     pub fn add_column<S: Into<String>>(mut self, name: S) -> Self {
         self.columns.push(KeyColumn::new(name));
         self
     }
+    pub fn add_key_column(mut self, column: KeyColumn) -> Self {
+        self.columns.push(column);
+        self
+    }
     pub fn cluster(mut self, v: bool) -> Self {
         self.cluster = v;
         self
     }
     pub fn compress(mut self, v: bool) -> Self {
-        self.compress = v;
+        self.compression = v.then(CompressionLevel::default);
+        self
+    }
+    pub fn compression(mut self, level: CompressionLevel) -> Self {
+        self.compression = Some(level);
         self
     }
     pub fn unique(mut self, v: bool) -> Self {
@@ -43,20 +57,38 @@ impl KeyBuilder {
         self.include = Some(s.into());
         self
     }
+    pub fn method(mut self, method: IndexMethod) -> Self {
+        self.method = Some(method);
+        self
+    }
+    pub fn filter<S: Into<String>>(mut self, filter: S) -> Self {
+        self.filter = Some(filter.into());
+        self
+    }
+
+    pub fn previous_name<S: Into<String>>(mut self, previous_name: S) -> Self {
+        self.previous_name = Some(previous_name.into());
+        self
+    }
 
     pub fn build(self) -> Key {
-        if self.cluster || self.compress || self.unique || self.include.is_some() {
+        let mut key = if self.cluster || self.unique || self.include.is_some() {
             Key::new_full(
                 self.key_type,
                 self.columns,
                 self.cluster,
-                self.compress,
+                false,
                 self.unique,
                 self.include,
             )
         } else {
             Key::new(self.key_type, self.columns)
-        }
+        };
+        key.set_method(self.method);
+        key.set_filter(self.filter);
+        key.set_compression(self.compression);
+        key.set_previous_name(self.previous_name);
+        key
     }
 }
 
@@ -74,6 +106,13 @@ mod tests {
             .build();
         assert_eq!(k.columns().len(), 2);
         assert!(k.is_compress());
+        assert_eq!(k.compression(), Some(CompressionLevel::Page));
         assert_eq!(k.include(), Some("x"));
     }
+
+    #[test]
+    fn build_index_with_row_compression() {
+        let k = KeyBuilder::new(KeyType::Index).add_column("a").compression(CompressionLevel::Row).build();
+        assert_eq!(k.compression(), Some(CompressionLevel::Row));
+    }
 }
@@ -1,9 +1,11 @@
 pub mod column;
 pub mod key;
 pub mod schema;
+pub mod sequence;
 pub mod table;
 
 pub use column::ColumnBuilder;
 pub use key::KeyBuilder;
 pub use schema::SchemaBuilder;
+pub use sequence::SequenceBuilder;
 pub use table::TableBuilder;
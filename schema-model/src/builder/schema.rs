@@ -3,6 +3,7 @@ use crate::model::function::Function;
 use crate::model::other_sql::OtherSql;
 use crate::model::procedure::Procedure;
 use crate::model::schema::Schema;
+use crate::model::sequence::Sequence;
 use crate::model::table::Table;
 use crate::model::view::View;
 
@@ -34,6 +35,11 @@ impl SchemaBuilder {
         self.schema.add_enum_type(enum_type);
         self
     }
+    /// Add a sequence.
+    pub fn add_sequence(mut self, sequence: Sequence) -> Self {
+        self.schema.add_sequence(sequence);
+        self
+    }
     /// Add functions.
     pub fn add_functions(mut self, functions: Vec<Function>) -> Self {
         self.schema.add_functions(functions);
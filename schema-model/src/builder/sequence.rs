@@ -0,0 +1,72 @@
+use crate::model::sequence::Sequence;
+
+/// SequenceBuilder collects the optional sequence settings, producing a model::Sequence.
+#[derive(Debug)]
+pub struct SequenceBuilder {
+    schema_name: Option<String>,
+    name: String,
+    start: Option<i64>,
+    increment: Option<i64>,
+    min_value: Option<i64>,
+    max_value: Option<i64>,
+    cycle: bool,
+    cache: Option<i64>,
+}
+
+impl SequenceBuilder {
+    pub fn new<S: Into<String>>(schema_name: Option<S>, name: S) -> Self {
+        Self {
+            schema_name: schema_name.map(|s| s.into()),
+            name: name.into(),
+            start: None,
+            increment: None,
+            min_value: None,
+            max_value: None,
+            cycle: false,
+            cache: None,
+        }
+    }
+
+    pub fn start(mut self, start: Option<i64>) -> Self {
+        self.start = start;
+        self
+    }
+
+    pub fn increment(mut self, increment: Option<i64>) -> Self {
+        self.increment = increment;
+        self
+    }
+
+    pub fn min_value(mut self, min_value: Option<i64>) -> Self {
+        self.min_value = min_value;
+        self
+    }
+
+    pub fn max_value(mut self, max_value: Option<i64>) -> Self {
+        self.max_value = max_value;
+        self
+    }
+
+    pub fn cycle(mut self, cycle: bool) -> Self {
+        self.cycle = cycle;
+        self
+    }
+
+    pub fn cache(mut self, cache: Option<i64>) -> Self {
+        self.cache = cache;
+        self
+    }
+
+    pub fn build(self) -> Sequence {
+        Sequence::new(
+            self.schema_name,
+            self.name,
+            self.start,
+            self.increment,
+            self.min_value,
+            self.max_value,
+            self.cycle,
+            self.cache,
+        )
+    }
+}
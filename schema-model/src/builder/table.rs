@@ -3,6 +3,7 @@ use crate::model::column::Column;
 use crate::model::constraint::Constraint;
 use crate::model::initial_data::InitialData;
 use crate::model::key::Key;
+use crate::model::partition::Partitioning;
 use crate::model::relation::Relation;
 use crate::model::table::Table;
 use crate::model::trigger::Trigger;
@@ -25,6 +26,9 @@ pub struct TableBuilder {
     initial_data: Vec<InitialData>,
     options: Vec<TableOption>,
     aggregations: Vec<Aggregation>,
+    description: Option<String>,
+    partitioning: Option<Partitioning>,
+    previous_name: Option<String>,
 }
 
 impl TableBuilder {
@@ -44,6 +48,9 @@ impl TableBuilder {
             initial_data: Vec::new(),
             options: Vec::new(),
             aggregations: Vec::new(),
+            description: None,
+            partitioning: None,
+            previous_name: None,
         }
     }
 
@@ -107,8 +114,23 @@ impl TableBuilder {
         self
     }
 
+    pub fn description(mut self, description: Option<String>) -> Self {
+        self.description = description;
+        self
+    }
+
+    pub fn partitioning(mut self, partitioning: Option<Partitioning>) -> Self {
+        self.partitioning = partitioning;
+        self
+    }
+
+    pub fn previous_name(mut self, previous_name: Option<String>) -> Self {
+        self.previous_name = previous_name;
+        self
+    }
+
     pub fn build(self) -> Table {
-        Table::new(
+        let mut table = Table::new(
             self.schema_name,
             self.name,
             self.export_date_column,
@@ -123,7 +145,11 @@ impl TableBuilder {
             self.initial_data,
             self.options,
             self.aggregations,
-        )
+        );
+        table.set_description(self.description);
+        table.set_partitioning(self.partitioning);
+        table.set_previous_name(self.previous_name);
+        table
     }
 }
 
@@ -12,7 +12,7 @@ pub enum AggregationFrequency {
     Yearly,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct AggregationGroup {
     source: String,
     destination: String,
@@ -41,7 +41,7 @@ impl AggregationGroup {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct AggregationColumn {
     aggregation_type: AggregationType,
     source_column: String,
@@ -72,7 +72,7 @@ impl AggregationColumn {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Aggregation {
     destination_table: String,
     date_column: String,
@@ -1,5 +1,5 @@
 use crate::model::column_type::ColumnType;
-use crate::model::types::BooleanMode;
+use crate::model::types::{BooleanMode, IdentityMode};
 
 #[derive(Debug, Clone)]
 pub struct Column {
@@ -16,6 +16,12 @@ pub struct Column {
     max_value: Option<f64>,
     enum_type: Option<String>,
     element_type: Option<String>,
+    description: Option<String>,
+    sequence: Option<String>,
+    identity_mode: Option<IdentityMode>,
+    identity_start: Option<i64>,
+    identity_increment: Option<i64>,
+    previous_name: Option<String>,
 }
 
 impl Column {
@@ -41,9 +47,16 @@ impl Column {
             max_value: None,
             enum_type: None,
             element_type: None,
+            description: None,
+            sequence: None,
+            identity_mode: None,
+            identity_start: None,
+            identity_increment: None,
+            previous_name: None,
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn new_all<S: Into<String>>(
         schema_name: Option<S>,
         name: S,
@@ -58,6 +71,12 @@ impl Column {
         max_value: Option<f64>,
         enum_type: Option<String>,
         element_type: Option<String>,
+        description: Option<String>,
+        sequence: Option<String>,
+        identity_mode: Option<IdentityMode>,
+        identity_start: Option<i64>,
+        identity_increment: Option<i64>,
+        previous_name: Option<String>,
     ) -> Self {
         Self {
             schema_name: schema_name.map(|s| s.into()),
@@ -73,6 +92,12 @@ impl Column {
             max_value,
             enum_type,
             element_type,
+            description,
+            sequence,
+            identity_mode,
+            identity_start,
+            identity_increment,
+            previous_name,
         }
     }
 
@@ -133,6 +158,36 @@ impl Column {
         self.element_type.as_deref()
     }
 
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    /// The name of the sequence (in the column's own schema) whose next value is the
+    /// column's default.
+    pub fn sequence(&self) -> Option<&str> {
+        self.sequence.as_deref()
+    }
+
+    /// How this sequence column is generated on PostgreSQL, overriding the generation
+    /// option; `None` follows the option.
+    pub fn identity_mode(&self) -> Option<IdentityMode> {
+        self.identity_mode
+    }
+
+    /// The first value of an identity column, when not the database's default.
+    pub fn identity_start(&self) -> Option<i64> {
+        self.identity_start
+    }
+
+    pub fn identity_increment(&self) -> Option<i64> {
+        self.identity_increment
+    }
+
+    /// The name the column had before it was renamed.
+    pub fn previous_name(&self) -> Option<&str> {
+        self.previous_name.as_deref()
+    }
+
     pub fn has_min_or_max_value(&self) -> bool {
         self.min_value.is_some() || self.max_value.is_some()
     }
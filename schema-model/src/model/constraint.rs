@@ -5,6 +5,7 @@ pub struct Constraint {
     name: String,
     sql: String,
     database_type: DatabaseType,
+    previous_name: Option<String>,
 }
 
 impl Constraint {
@@ -13,9 +14,15 @@ impl Constraint {
             name: name.into(),
             sql: sql.into(),
             database_type,
+            previous_name: None,
         }
     }
 
+    pub fn with_previous_name(mut self, previous_name: Option<String>) -> Self {
+        self.previous_name = previous_name;
+        self
+    }
+
     pub fn name(&self) -> &str {
         &self.name
     }
@@ -25,6 +32,10 @@ impl Constraint {
     pub fn database_type(&self) -> DatabaseType {
         self.database_type
     }
+    /// The name the constraint had before it was renamed.
+    pub fn previous_name(&self) -> Option<&str> {
+        self.previous_name.as_deref()
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,279 @@
+use crate::model::key::Key;
+use crate::model::naming::NamingConvention;
+use crate::model::relation::Relation;
+use crate::model::table::Table;
+use crate::model::types::{DatabaseType, KeyType};
+
+/// Java-compatible `String.hashCode()`: a 32-bit, base-31 polynomial hash over UTF-16 code
+/// units. All identifiers this is used for are ASCII, so iterating `char`s is equivalent.
+pub fn java_string_hash(s: &str) -> u32 {
+    s.chars()
+        .fold(0i32, |h, c| h.wrapping_mul(31).wrapping_add(c as i32)) as u32
+}
+
+/// The templates the generator names constraints and indexes with. A template can use
+/// `{table}`, `{column}` (the columns joined with `_`), `{n}` (the position among the
+/// table's keys of that kind) and `{hash}`. `{table:9}` or `{column:9}` keeps only the
+/// first nine characters. The defaults match the legacy Java codegen tool.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConstraintNaming {
+    pub primary_key: String,
+    pub unique_key: String,
+    pub foreign_key: String,
+    pub index: String,
+    pub check: String,
+    pub default: String,
+}
+
+impl Default for ConstraintNaming {
+    fn default() -> Self {
+        Self {
+            primary_key: "pk_{table}".to_string(),
+            unique_key: "ak_{table}{n}".to_string(),
+            foreign_key: "fk_{table}{n}".to_string(),
+            index: "ix_{table}{n}".to_string(),
+            check: "ck_{table:9}_{column:9}_{hash}".to_string(),
+            default: "df_{table:9}_{column:9}_{hash}".to_string(),
+        }
+    }
+}
+
+/// Fills in `template` for `table` and `column`. `{hash}` is `java_string_hash` of
+/// `"{table}_{column}"` lowercased, in uppercase hex and not zero-padded (as Java's
+/// `Integer.toHexString(hash).toUpperCase()` writes it), always over the full names. A
+/// result longer than `max_len` characters has its table part shortened to fit.
+pub fn render_constraint_name(template: &str, table: &str, column: &str, position: Option<usize>, max_len: usize) -> String {
+    let hash = format!("{:X}", java_string_hash(&format!("{}_{}", table, column).to_lowercase()));
+    let name = fill_template(template, table, column, position, &hash);
+    let excess = name.chars().count().saturating_sub(max_len);
+    if excess == 0 {
+        return name;
+    }
+    // Truncate by char, not byte index, so a multi-byte UTF-8 table name can't be cut
+    // mid-character.
+    let table_chars = table.chars().count();
+    let shortened: String = table.chars().take(table_chars.saturating_sub(excess)).collect();
+    fill_template(template, &shortened, column, position, &hash)
+}
+
+fn fill_template(template: &str, table: &str, column: &str, position: Option<usize>, hash: &str) -> String {
+    let mut out = String::with_capacity(template.len() + table.len() + column.len());
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        out.push_str(&rest[..open]);
+        let Some(close) = rest[open..].find('}') else {
+            rest = &rest[open..];
+            break;
+        };
+        let placeholder = &rest[open + 1..open + close];
+        let (key, width) = match placeholder.split_once(':') {
+            Some((key, width)) => (key, width.parse::<usize>().ok()),
+            None => (placeholder, None),
+        };
+        let truncate = |value: &str| match width {
+            Some(width) => value.chars().take(width).collect(),
+            None => value.to_string(),
+        };
+        match key {
+            "table" => out.push_str(&truncate(table)),
+            "column" => out.push_str(&truncate(column)),
+            "n" => out.push_str(&position.map(|n| n.to_string()).unwrap_or_default()),
+            "hash" => out.push_str(hash),
+            _ => out.push_str(&rest[open..=open + close]),
+        }
+        rest = &rest[open + close + 1..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Names constraints and indexes from a `ConstraintNaming`'s templates for one database,
+/// spelling the table and column parts per the naming convention and keeping each name
+/// within the dialect's key name length. The create script and migrations both name keys
+/// and foreign keys through it, so a migration finds what the create script created.
+#[derive(Debug, Clone)]
+pub struct ConstraintNamer {
+    naming: ConstraintNaming,
+    naming_convention: NamingConvention,
+    database_type: DatabaseType,
+}
+
+impl ConstraintNamer {
+    pub fn new(naming: ConstraintNaming, naming_convention: NamingConvention, database_type: DatabaseType) -> Self {
+        Self { naming, naming_convention, database_type }
+    }
+
+    pub fn naming(&self) -> &ConstraintNaming {
+        &self.naming
+    }
+
+    /// A table or column name as part of a generated name. These have always been
+    /// lowercased, which `NamingConvention::Preserve` keeps; any other convention spells
+    /// them itself.
+    pub fn generated_name_part(&self, name: &str) -> String {
+        if self.naming_convention.is_preserve() {
+            name.to_lowercase()
+        } else {
+            self.naming_convention.to_database(name)
+        }
+    }
+
+    /// The name `template` (one of the `ConstraintNaming` templates) gives a constraint or
+    /// index on `table_name` over `column_names`, kept within the dialect's key name length.
+    pub fn constraint_name(&self, template: &str, table_name: &str, column_names: &[&str], position: Option<usize>) -> String {
+        let column = column_names.iter().map(|name| self.generated_name_part(name)).collect::<Vec<_>>().join("_");
+        render_constraint_name(
+            template,
+            &self.generated_name_part(table_name),
+            &column,
+            position,
+            self.database_type.max_key_name_length(),
+        )
+    }
+
+    /// The name of `key` on the table `table_name`, where `position` is its place among
+    /// the table's keys of its type (see `numbered_keys`); a primary key isn't numbered.
+    pub fn key_name(&self, table_name: &str, key: &Key, position: usize) -> String {
+        let column_names: Vec<&str> = key.columns().iter().map(|column| column.name()).collect();
+        match key.key_type() {
+            KeyType::Primary => self.constraint_name(&self.naming.primary_key, table_name, &column_names, None),
+            KeyType::Unique => self.constraint_name(&self.naming.unique_key, table_name, &column_names, Some(position)),
+            KeyType::Index => self.index_name(table_name, key, position),
+        }
+    }
+
+    /// The name of the `position`th (1-based) index on a table or materialized view. An
+    /// expression contributes its identifier characters to `{column}` (`lower(email)` ->
+    /// `lower_email`).
+    pub fn index_name(&self, owner_name: &str, key: &Key, position: usize) -> String {
+        let column_names: Vec<String> = key
+            .columns()
+            .iter()
+            .map(|column| {
+                column
+                    .name()
+                    .split(|c: char| !c.is_alphanumeric() && c != '_')
+                    .filter(|part| !part.is_empty())
+                    .collect::<Vec<_>>()
+                    .join("_")
+            })
+            .collect();
+        let column_names: Vec<&str> = column_names.iter().map(String::as_str).collect();
+        self.constraint_name(&self.naming.index, owner_name, &column_names, Some(position))
+    }
+
+    /// The name of the foreign key behind `relation`, the `position`th (1-based) of the
+    /// relations of the table `table_name`.
+    pub fn foreign_key_name(&self, table_name: &str, relation: &Relation, position: usize) -> String {
+        self.constraint_name(&self.naming.foreign_key, table_name, &relation.from_column_names(), Some(position))
+    }
+}
+
+/// `table`'s keys and then its indexes, each with the position `ConstraintNamer::key_name`
+/// numbers it by: its 1-based place among the keys of its type. As in the create script,
+/// keys are numbered among the table's keys and indexes among its indexes; one listed
+/// with the other kind comes after those.
+pub fn numbered_keys(table: &Table) -> Vec<(usize, &Key)> {
+    let count = |keys: &[Key], key_type: KeyType| keys.iter().filter(|key| key.key_type() == key_type).count();
+    let mut numbered = Vec::new();
+    for (i, key) in table.keys().iter().enumerate() {
+        let listed_first = if key.is_index() { count(table.indexes(), KeyType::Index) } else { 0 };
+        numbered.push((listed_first + count(&table.keys()[..i], key.key_type()) + 1, key));
+    }
+    for (i, key) in table.indexes().iter().enumerate() {
+        let listed_first = if key.is_index() { 0 } else { count(table.keys(), key.key_type()) };
+        numbered.push((listed_first + count(&table.indexes()[..i], key.key_type()) + 1, key));
+    }
+    numbered
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::TableBuilder;
+    use crate::model::key::KeyColumn;
+    use crate::model::types::RelationType;
+
+    #[test]
+    fn java_string_hash_matches_known_values() {
+        // Verified by reproducing Java's String.hashCode() against every check/default
+        // constraint name in the legacy tool's SQL Server reference output.
+        assert_eq!(java_string_hash("earning_type_id"), 0x97E3620B);
+        assert_eq!(java_string_hash("user_id"), 0xF73AEE0F);
+        assert_eq!(
+            java_string_hash("tip_pool_contributor_template_contribution_basis"),
+            0x326125D1
+        );
+    }
+
+    #[test]
+    fn hashed_constraint_name_matches_legacy_format() {
+        let naming = ConstraintNaming::default();
+        assert_eq!(
+            render_constraint_name(&naming.default, "earning_type", "id", None, 128),
+            "df_earning_t_id_97E3620B"
+        );
+        assert_eq!(
+            render_constraint_name(&naming.check, "tip_pool_contributor_template", "contribution_basis", None, 128),
+            "ck_tip_pool__contribut_326125D1"
+        );
+    }
+
+    #[test]
+    fn hashed_constraint_name_does_not_zero_pad_the_hash() {
+        // The legacy Java tool formats the hash with Integer.toHexString(...).toUpperCase(),
+        // which drops leading zero nibbles rather than zero-padding to a fixed width.
+        assert_eq!(java_string_hash("tip_sub_pool_template_hours_mode"), 0x0DCD6758);
+        assert_eq!(
+            render_constraint_name(&ConstraintNaming::default().check, "tip_sub_pool_template", "hours_mode", None, 128),
+            "ck_tip_sub_p_hours_mod_DCD6758"
+        );
+    }
+
+    #[test]
+    fn render_constraint_name_fills_in_custom_templates() {
+        assert_eq!(render_constraint_name("fk_{table}_{column}", "orders", "customer_id", Some(2), 63), "fk_orders_customer_id");
+        assert_eq!(render_constraint_name("{table}_pkey", "orders", "id", None, 63), "orders_pkey");
+        assert_eq!(render_constraint_name("ix_{table}{n}_{other}", "orders", "", Some(3), 63), "ix_orders3_{other}");
+    }
+
+    #[test]
+    fn render_constraint_name_shortens_the_table_part_to_fit() {
+        let name = render_constraint_name("fk_{table}{n}", &"a".repeat(70), "", Some(12), 63);
+        assert_eq!(name.len(), 63);
+        assert!(name.starts_with("fk_aaa") && name.ends_with("a12"));
+    }
+
+    #[test]
+    fn key_names_follow_the_templates() {
+        let namer = ConstraintNamer::new(ConstraintNaming::default(), NamingConvention::Preserve, DatabaseType::Postgresql);
+        let primary = Key::new(KeyType::Primary, vec![KeyColumn::new("Id")]);
+        let unique = Key::new(KeyType::Unique, vec![KeyColumn::new("Code")]);
+        let index = Key::new(KeyType::Index, vec![KeyColumn::new_expression("lower(Email)")]);
+        let relation = Relation::new("Client", "Id", "Order", "ClientId", RelationType::Cascade, false);
+        assert_eq!(namer.key_name("Client", &primary, 1), "pk_client");
+        assert_eq!(namer.key_name("Client", &unique, 2), "ak_client2");
+        assert_eq!(namer.key_name("Client", &index, 1), "ix_client1");
+        assert_eq!(namer.foreign_key_name("Order", &relation, 3), "fk_order3");
+
+        let custom = ConstraintNaming { index: "{table}_{column}_idx".to_string(), ..ConstraintNaming::default() };
+        let snake = ConstraintNamer::new(custom, NamingConvention::SnakeCase, DatabaseType::Postgresql);
+        assert_eq!(snake.key_name("ClientAccount", &index, 1), "client_account_lower_email_idx");
+    }
+
+    #[test]
+    fn numbered_keys_count_each_key_type_on_its_own() {
+        let table = TableBuilder::new(None, "Client")
+            .add_key(Key::new(KeyType::Primary, vec![KeyColumn::new("Id")]))
+            .add_key(Key::new(KeyType::Unique, vec![KeyColumn::new("Code")]))
+            .add_key(Key::new(KeyType::Unique, vec![KeyColumn::new("Name")]))
+            .add_key(Key::new(KeyType::Index, vec![KeyColumn::new("Code")]))
+            .add_index(Key::new(KeyType::Index, vec![KeyColumn::new("Name")]))
+            .build();
+        let positions: Vec<(usize, KeyType)> = numbered_keys(&table).into_iter().map(|(n, key)| (n, key.key_type())).collect();
+        assert_eq!(
+            positions,
+            vec![(1, KeyType::Primary), (1, KeyType::Unique), (2, KeyType::Unique), (2, KeyType::Index), (1, KeyType::Index)]
+        );
+    }
+}
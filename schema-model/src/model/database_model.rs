@@ -1,7 +1,8 @@
 use crate::model::enum_type::EnumType;
 use crate::model::schema::Schema;
 use crate::model::table::Table;
-use crate::model::types::{BooleanMode, ForeignKeyMode};
+use crate::model::column_type::ColumnType;
+use crate::model::types::{BooleanMode, DatabaseType, ForeignKeyMode, TableOption};
 
 #[derive(Debug, Default)]
 pub struct DatabaseModel {
@@ -34,10 +35,11 @@ impl DatabaseModel {
     }
 
     pub fn default_schema(&self) -> &Schema {
-        self.schemas
-            .iter()
-            .find(|s| s.schema_name().is_none())
-            .expect("Default schema not found")
+        self.default_schema_checked().expect("Default schema not found")
+    }
+
+    pub fn default_schema_checked(&self) -> Option<&Schema> {
+        self.schemas.iter().find(|s| s.schema_name().is_none())
     }
 
     pub fn default_schema_mut(&mut self) -> &mut Schema {
@@ -48,15 +50,13 @@ impl DatabaseModel {
     }
 
     pub fn find_schema(&self, schema_name: Option<&str>) -> &Schema {
-        if schema_name.is_none() {
-            return self.default_schema();
-        }
+        self.find_schema_checked(schema_name).expect("Schema not found")
+    }
 
-        self.schemas
-            .iter()
-            .filter(|s| s.schema_name().is_some())
-            .find(|s| s.schema_name().unwrap() == schema_name.unwrap())
-            .expect("Schema not found")
+    /// Same as `find_schema`, but returns `None` instead of panicking when the schema
+    /// doesn't exist.
+    pub fn find_schema_checked(&self, schema_name: Option<&str>) -> Option<&Schema> {
+        self.schemas.iter().find(|s| s.schema_name() == schema_name)
     }
 
     pub(crate) fn find_schema_mut(&mut self, schema_name: Option<&str>) -> &mut Schema {
@@ -76,6 +76,10 @@ impl DatabaseModel {
         schema.get_enum_type(enum_type)
     }
 
+    pub fn find_enum_type_checked(&self, schema_name: Option<&str>, enum_type: &str) -> Option<&EnumType> {
+        self.find_schema_checked(schema_name)?.get_optional_enum_type(enum_type)
+    }
+
     pub fn all_tables(&self) -> Vec<&Table> {
         self.schemas
             .iter()
@@ -88,6 +92,10 @@ impl DatabaseModel {
         schema.get_table(table_name)
     }
 
+    pub fn find_table_checked(&self, schema_name: Option<&str>, table_name: &str) -> Option<&Table> {
+        self.find_schema_checked(schema_name)?.get_optional_table(table_name)
+    }
+
     pub fn find_table_by_qualified_name(&self, qualified_name: &str) -> &Table {
         let parts: Vec<&str> = qualified_name.split('.').collect();
         let (schema_name, table_name) = if parts.len() == 2 {
@@ -110,10 +118,7 @@ impl DatabaseModel {
             (None, qualified_name)
         };
 
-        self.schemas
-            .iter()
-            .find(|s| s.schema_name() == schema_name)
-            .and_then(|s| s.get_optional_table(table_name))
+        self.find_table_checked(schema_name, table_name)
     }
 
     /// Checks model-wide invariants that individual `Schema::validate()` calls can't see
@@ -132,15 +137,94 @@ impl DatabaseModel {
 
         for table in self.all_tables() {
             for relation in table.relations() {
+                if relation.columns().is_empty() {
+                    errors.push(format!(
+                        "ERROR: {} has a relation to '{}' without any columns",
+                        table.name(),
+                        relation.to_table_name()
+                    ));
+                }
                 if self.find_table_by_qualified_name_checked(relation.to_table_name()).is_none() {
                     errors.push(format!(
                         "ERROR: {}.{} has a relation to '{}' which does not exist",
                         table.name(),
-                        relation.from_column_name(),
+                        relation.from_columns_as_string(),
                         relation.to_table_name()
                     ));
                 }
             }
+
+            for aggregation in table.aggregations() {
+                if self.find_table_by_qualified_name_checked(aggregation.destination_table()).is_none() {
+                    errors.push(format!(
+                        "ERROR: {} has an aggregation into '{}' which does not exist",
+                        table.name(),
+                        aggregation.destination_table()
+                    ));
+                }
+                for group in aggregation.aggregation_groups() {
+                    if let Some(derived_from) = group.source_derived_from()
+                        && table.single_column_relation(derived_from).is_none()
+                    {
+                        errors.push(format!(
+                            "ERROR: {}.{} is used as sourceDerivedFrom for aggregation group '{}', but it has no single column relation",
+                            table.name(),
+                            derived_from,
+                            group.destination()
+                        ));
+                    }
+                }
+            }
+        }
+
+        errors
+    }
+
+    /// `validate`, plus the checks for what the given database can't express.
+    pub fn validate_for(&self, database_type: DatabaseType) -> Vec<String> {
+        let mut errors = self.validate();
+
+        if database_type == DatabaseType::Sqlite {
+            for table in self.all_tables() {
+                if table.has_option(TableOption::WithoutRowid) {
+                    if table.primary_key().is_none() {
+                        errors.push(format!(
+                            "ERROR: table {} is without rowid but has no primary key; SQLite requires one",
+                            table.name()
+                        ));
+                    } else if let Some(column) = table.rowid_alias_column() {
+                        errors.push(format!(
+                            "ERROR: {}.{} is a sequence primary key, but table {} is without rowid; SQLite can only auto-increment the rowid",
+                            table.name(),
+                            column.name(),
+                            table.name()
+                        ));
+                    }
+                }
+
+                let rowid_alias = table.rowid_alias_column().map(|c| c.name());
+                let primary_key_columns = table.primary_key_columns().unwrap_or_default();
+                for column in table.columns() {
+                    if !matches!(column.column_type(), ColumnType::Sequence | ColumnType::LongSequence)
+                        || rowid_alias == Some(column.name())
+                    {
+                        continue;
+                    }
+                    if primary_key_columns.iter().any(|c| c.eq_ignore_ascii_case(column.name())) {
+                        errors.push(format!(
+                            "ERROR: {}.{} is a sequence column in a composite primary key; SQLite can only auto-increment a single-column integer primary key",
+                            table.name(),
+                            column.name()
+                        ));
+                    } else {
+                        errors.push(format!(
+                            "ERROR: {}.{} is a sequence column but not the primary key; SQLite can only auto-increment a single-column integer primary key",
+                            table.name(),
+                            column.name()
+                        ));
+                    }
+                }
+            }
         }
 
         errors
@@ -171,6 +255,9 @@ impl DatabaseModel {
 mod tests {
     use super::*;
     use crate::builder::{ColumnBuilder, SchemaBuilder, TableBuilder};
+    use crate::model::aggregation::{
+        Aggregation, AggregationColumn, AggregationFrequency, AggregationGroup, AggregationType,
+    };
     use crate::model::column_type::ColumnType;
     use crate::model::relation::Relation;
     use crate::model::types::RelationType;
@@ -214,4 +301,128 @@ mod tests {
 
         assert!(model.validate().is_empty());
     }
+
+    #[test]
+    fn validate_reports_error_for_a_relation_without_columns() {
+        let parent = TableBuilder::new(None::<&str>, "parent")
+            .add_column(ColumnBuilder::new(None::<&str>, "id", ColumnType::Int).build())
+            .build();
+        let child = TableBuilder::new(None::<&str>, "child")
+            .add_column(ColumnBuilder::new(None::<&str>, "parent_id", ColumnType::Int).build())
+            .add_relation(Relation::new_composite("parent", "child", vec![], RelationType::Enforce, false))
+            .build();
+        let schema = SchemaBuilder::new(None::<&str>).add_table(parent).add_table(child).build();
+        let model = DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema]);
+
+        let errors = model.validate();
+        assert_eq!(errors, vec!["ERROR: child has a relation to 'parent' without any columns".to_string()]);
+    }
+
+    #[test]
+    fn validate_reports_error_for_an_aggregation_with_a_missing_destination_or_derived_relation() {
+        let sale = TableBuilder::new(None::<&str>, "sale")
+            .add_column(ColumnBuilder::new(None::<&str>, "store_id", ColumnType::Int).build())
+            .add_aggregation(Aggregation::new(
+                "sale_summary",
+                "sale_date",
+                None,
+                "updated_at",
+                AggregationFrequency::Daily,
+                vec![AggregationColumn::new(AggregationType::Count, "", "sale_count")],
+                vec![AggregationGroup::new("region_id", "region_id", Some("store_id"))],
+            ))
+            .build();
+        let schema = SchemaBuilder::new(None::<&str>).add_table(sale).build();
+        let model = DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema]);
+
+        let errors = model.validate();
+        assert_eq!(errors.len(), 2);
+        assert!(errors[0].contains("sale_summary"));
+        assert!(errors[1].contains("sale.store_id"));
+    }
+
+    #[test]
+    fn validate_for_sqlite_reports_sequences_that_cannot_be_a_rowid_alias() {
+        use crate::model::key::{Key, KeyColumn};
+        use crate::model::types::KeyType;
+
+        let sequence_key = TableBuilder::new(None::<&str>, "invoice")
+            .add_column(ColumnBuilder::new(None::<&str>, "id", ColumnType::LongSequence).required(true).build())
+            .add_key(Key::new(KeyType::Primary, vec![KeyColumn::new("id")]))
+            .build();
+        let no_key = TableBuilder::new(None::<&str>, "audit")
+            .add_column(ColumnBuilder::new(None::<&str>, "id", ColumnType::Sequence).build())
+            .build();
+        let composite_key = TableBuilder::new(None::<&str>, "line")
+            .add_column(ColumnBuilder::new(None::<&str>, "invoice_id", ColumnType::Long).build())
+            .add_column(ColumnBuilder::new(None::<&str>, "line_id", ColumnType::Sequence).build())
+            .add_key(Key::new(KeyType::Primary, vec![KeyColumn::new("invoice_id"), KeyColumn::new("line_id")]))
+            .build();
+        let schema = SchemaBuilder::new(None::<&str>)
+            .add_table(sequence_key)
+            .add_table(no_key)
+            .add_table(composite_key)
+            .build();
+        let model = DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema]);
+
+        assert!(model.validate().is_empty());
+        assert!(model.validate_for(DatabaseType::Postgresql).is_empty());
+        let errors = model.validate_for(DatabaseType::Sqlite);
+        assert_eq!(errors.len(), 2, "{:?}", errors);
+        assert!(errors[0].contains("audit.id is a sequence column but not the primary key"));
+        assert!(errors[1].contains("line.line_id is a sequence column in a composite primary key"));
+    }
+
+    #[test]
+    fn validate_for_sqlite_reports_without_rowid_tables_it_cannot_create() {
+        use crate::model::key::{Key, KeyColumn};
+        use crate::model::types::KeyType;
+
+        let no_key = TableBuilder::new(None::<&str>, "audit")
+            .add_column(ColumnBuilder::new(None::<&str>, "message", ColumnType::Text).build())
+            .add_option(TableOption::WithoutRowid)
+            .build();
+        let sequence_key = TableBuilder::new(None::<&str>, "invoice")
+            .add_column(ColumnBuilder::new(None::<&str>, "id", ColumnType::Sequence).required(true).build())
+            .add_key(Key::new(KeyType::Primary, vec![KeyColumn::new("id")]))
+            .add_option(TableOption::WithoutRowid)
+            .build();
+        let natural_key = TableBuilder::new(None::<&str>, "line")
+            .add_column(ColumnBuilder::new(None::<&str>, "invoice_id", ColumnType::Long).required(true).build())
+            .add_column(ColumnBuilder::new(None::<&str>, "line_no", ColumnType::Int).required(true).build())
+            .add_key(Key::new(KeyType::Primary, vec![KeyColumn::new("invoice_id"), KeyColumn::new("line_no")]))
+            .add_option(TableOption::WithoutRowid)
+            .build();
+        let schema = SchemaBuilder::new(None::<&str>)
+            .add_table(no_key)
+            .add_table(sequence_key)
+            .add_table(natural_key)
+            .build();
+        let model = DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema]);
+
+        assert!(model.validate_for(DatabaseType::Postgresql).is_empty());
+        let errors = model.validate_for(DatabaseType::Sqlite);
+        assert_eq!(errors.len(), 2, "{:?}", errors);
+        assert!(errors[0].contains("table audit is without rowid but has no primary key"));
+        assert!(errors[1].contains("invoice.id is a sequence primary key"));
+    }
+
+    #[test]
+    fn checked_lookups_return_none_for_names_that_do_not_resolve() {
+        let table = TableBuilder::new(Some("app"), "invoice")
+            .add_column(ColumnBuilder::new(Some("app"), "id", ColumnType::Int).build())
+            .build();
+        let schema = SchemaBuilder::new(Some("app")).add_table(table).build();
+        let model = DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema]);
+
+        assert!(model.find_schema_checked(Some("app")).is_some());
+        assert!(model.find_schema_checked(None).is_none());
+        assert!(model.default_schema_checked().is_none());
+        assert!(model.find_table_checked(Some("app"), "INVOICE").is_some());
+        assert!(model.find_table_checked(Some("sales"), "invoice").is_none());
+        assert!(model.find_enum_type_checked(Some("app"), "status").is_none());
+        let invoice = model.find_table_by_qualified_name_checked("app.invoice").unwrap();
+        assert!(invoice.column_checked("ID").is_some());
+        assert!(invoice.column_checked("total").is_none());
+    }
 }
@@ -1,18 +1,57 @@
-use crate::model::types::KeyType;
+use crate::model::types::{CompressionLevel, IndexMethod, KeyType, NullsOrder, SortOrder};
 
-#[derive(Debug, Clone)]
+/// One entry of a key or index: either a column, or (for an index) an SQL expression
+/// such as `lower(email)`, in which case `name` holds the expression text.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct KeyColumn {
     name: String,
+    expression: bool,
+    sort_order: Option<SortOrder>,
+    nulls_order: Option<NullsOrder>,
 }
 
 impl KeyColumn {
     pub fn new<S: Into<String>>(name: S) -> Self {
-        Self { name: name.into() }
+        Self {
+            name: name.into(),
+            expression: false,
+            sort_order: None,
+            nulls_order: None,
+        }
+    }
+
+    pub fn new_expression<S: Into<String>>(expression: S) -> Self {
+        Self {
+            expression: true,
+            ..Self::new(expression)
+        }
+    }
+
+    pub fn with_sort_order(mut self, sort_order: Option<SortOrder>) -> Self {
+        self.sort_order = sort_order;
+        self
+    }
+
+    pub fn with_nulls_order(mut self, nulls_order: Option<NullsOrder>) -> Self {
+        self.nulls_order = nulls_order;
+        self
     }
 
     pub fn name(&self) -> &str {
         &self.name
     }
+
+    pub fn is_expression(&self) -> bool {
+        self.expression
+    }
+
+    pub fn sort_order(&self) -> Option<SortOrder> {
+        self.sort_order
+    }
+
+    pub fn nulls_order(&self) -> Option<NullsOrder> {
+        self.nulls_order
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -20,9 +59,12 @@ pub struct Key {
     key_type: KeyType,
     columns: Vec<KeyColumn>,
     cluster: bool,
-    compress: bool,
+    compression: Option<CompressionLevel>,
     unique: bool,
     include: Option<String>,
+    method: Option<IndexMethod>,
+    filter: Option<String>,
+    previous_name: Option<String>,
 }
 
 impl Key {
@@ -31,9 +73,12 @@ impl Key {
             key_type,
             columns,
             cluster: false,
-            compress: false,
+            compression: None,
             unique: false,
             include: None,
+            method: None,
+            filter: None,
+            previous_name: None,
         }
     }
 
@@ -49,9 +94,12 @@ impl Key {
             key_type,
             columns,
             cluster,
-            compress,
+            compression: compress.then(CompressionLevel::default),
             unique,
             include: include.map(|s| s.into()),
+            method: None,
+            filter: None,
+            previous_name: None,
         }
     }
 
@@ -68,7 +116,12 @@ impl Key {
     }
 
     pub fn is_compress(&self) -> bool {
-        self.compress
+        self.compression.is_some()
+    }
+
+    /// The level a compressed key is stored at; `None` when it isn't compressed.
+    pub fn compression(&self) -> Option<CompressionLevel> {
+        self.compression
     }
 
     pub fn is_unique(&self) -> bool {
@@ -79,12 +132,46 @@ impl Key {
         self.include.as_deref()
     }
 
+    pub fn method(&self) -> Option<IndexMethod> {
+        self.method
+    }
+
+    /// The predicate of a partial (Postgres/SQLite) or filtered (SQL Server) index,
+    /// without the `where` keyword.
+    pub fn filter(&self) -> Option<&str> {
+        self.filter.as_deref()
+    }
+
+    /// The name the index has in the database when that isn't the one migrations would
+    /// give it now, typically because its table or one of its columns was renamed.
+    pub fn previous_name(&self) -> Option<&str> {
+        self.previous_name.as_deref()
+    }
+
+    pub fn set_method(&mut self, method: Option<IndexMethod>) {
+        self.method = method;
+    }
+
+    pub fn set_filter(&mut self, filter: Option<String>) {
+        self.filter = filter;
+    }
+
+    pub fn set_compression(&mut self, compression: Option<CompressionLevel>) {
+        self.compression = compression;
+    }
+
+    pub fn set_previous_name(&mut self, previous_name: Option<String>) {
+        self.previous_name = previous_name;
+    }
+
     pub fn is_index(&self) -> bool {
         self.key_type == KeyType::Index
     }
 
     pub fn contains_column(&self, column_name: &str) -> bool {
-        self.columns.iter().any(|c| c.name().eq_ignore_ascii_case(column_name))
+        self.columns
+            .iter()
+            .any(|c| !c.is_expression() && c.name().eq_ignore_ascii_case(column_name))
     }
 
     pub fn columns_as_string(&self) -> String {
@@ -121,6 +208,22 @@ mod tests {
         assert_eq!(k2.include(), Some("inc"));
     }
 
+    #[test]
+    fn expression_columns_are_not_table_columns() {
+        let k = Key::new(
+            KeyType::Index,
+            vec![
+                KeyColumn::new_expression("lower(email)"),
+                KeyColumn::new("created_at").with_sort_order(Some(SortOrder::Desc)).with_nulls_order(Some(NullsOrder::Last)),
+            ],
+        );
+        assert!(!k.contains_column("lower(email)"));
+        assert!(k.contains_column("created_at"));
+        assert!(k.columns()[0].is_expression());
+        assert_eq!(k.columns()[1].sort_order(), Some(SortOrder::Desc));
+        assert_eq!(k.columns()[1].nulls_order(), Some(NullsOrder::Last));
+    }
+
     #[test]
     fn contains_column_is_case_insensitive() {
         // Matches the case-insensitive lookup used everywhere else in the model
@@ -2,15 +2,20 @@ pub mod aggregation;
 pub mod column;
 pub mod column_type;
 pub mod constraint;
+pub mod constraint_naming;
 pub mod database_model;
 pub mod enum_type;
 pub mod function;
 pub mod initial_data;
 pub mod key;
+pub mod naming;
 pub mod other_sql;
+pub mod partition;
 pub mod procedure;
 pub mod relation;
+mod reserved_words;
 pub mod schema;
+pub mod sequence;
 pub mod table;
 pub mod trigger;
 pub mod types;
@@ -0,0 +1,169 @@
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+
+type NameTransform = Arc<dyn Fn(&str) -> String + Send + Sync>;
+
+/// How the names in a model are spelled in the database. Generation maps model names to
+/// database names with `to_database`; reverse engineering maps them back with `to_model`.
+#[derive(Clone, Default)]
+pub enum NamingConvention {
+    /// Names are used exactly as the model spells them.
+    #[default]
+    Preserve,
+    /// `ParentTable` becomes `parent_table`, and reads back as `ParentTable`.
+    SnakeCase,
+    /// `ParentTable` becomes `parenttable`. The original case can't be recovered, so the
+    /// database name reads back unchanged.
+    Lower,
+    /// `ParentTable` becomes `PARENTTABLE`, reading back unchanged like `Lower`.
+    Upper,
+    /// A pair of functions, one each way.
+    Custom {
+        to_database: NameTransform,
+        to_model: NameTransform,
+    },
+}
+
+impl NamingConvention {
+    /// A convention that maps names with `to_database` and back with `to_model`.
+    pub fn custom(
+        to_database: impl Fn(&str) -> String + Send + Sync + 'static,
+        to_model: impl Fn(&str) -> String + Send + Sync + 'static,
+    ) -> Self {
+        NamingConvention::Custom {
+            to_database: Arc::new(to_database),
+            to_model: Arc::new(to_model),
+        }
+    }
+
+    pub fn is_preserve(&self) -> bool {
+        matches!(self, NamingConvention::Preserve)
+    }
+
+    /// The database spelling of the model name `name`.
+    pub fn to_database(&self, name: &str) -> String {
+        match self {
+            NamingConvention::Preserve => name.to_string(),
+            NamingConvention::SnakeCase => snake_case(name),
+            NamingConvention::Lower => name.to_lowercase(),
+            NamingConvention::Upper => name.to_uppercase(),
+            NamingConvention::Custom { to_database, .. } => to_database(name),
+        }
+    }
+
+    /// The model spelling of the database name `name`.
+    pub fn to_model(&self, name: &str) -> String {
+        match self {
+            NamingConvention::Preserve | NamingConvention::Lower | NamingConvention::Upper => name.to_string(),
+            NamingConvention::SnakeCase => pascal_case(name),
+            NamingConvention::Custom { to_model, .. } => to_model(name),
+        }
+    }
+}
+
+impl fmt::Debug for NamingConvention {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NamingConvention::Preserve => f.write_str("Preserve"),
+            NamingConvention::SnakeCase => f.write_str("SnakeCase"),
+            NamingConvention::Lower => f.write_str("Lower"),
+            NamingConvention::Upper => f.write_str("Upper"),
+            NamingConvention::Custom { .. } => f.write_str("Custom"),
+        }
+    }
+}
+
+impl FromStr for NamingConvention {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().replace(['-', '_'], "").as_str() {
+            "preserve" => Ok(NamingConvention::Preserve),
+            "snakecase" | "snake" => Ok(NamingConvention::SnakeCase),
+            "lower" | "lowercase" => Ok(NamingConvention::Lower),
+            "upper" | "uppercase" => Ok(NamingConvention::Upper),
+            _ => Err(format!("Unknown naming convention: {}", s)),
+        }
+    }
+}
+
+/// `name` as lowercase words joined by underscores, breaking where a capital follows a
+/// lowercase letter or digit. A run of capitals stays one word (`HTTPServer` -> `http_server`).
+fn snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut out = String::with_capacity(name.len() + 4);
+    for (i, &c) in chars.iter().enumerate() {
+        if c == ' ' || c == '-' {
+            out.push('_');
+            continue;
+        }
+        if c.is_uppercase() && i > 0 {
+            let prev = chars[i - 1];
+            let next_is_lower = chars.get(i + 1).is_some_and(|next| next.is_lowercase());
+            if prev.is_lowercase() || prev.is_ascii_digit() || (prev.is_uppercase() && next_is_lower) {
+                out.push('_');
+            }
+        }
+        out.extend(c.to_lowercase());
+    }
+    out
+}
+
+fn pascal_case(name: &str) -> String {
+    name.split('_')
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect::<String>(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snake_case_splits_words_and_acronyms() {
+        let naming = NamingConvention::SnakeCase;
+        assert_eq!(naming.to_database("ParentTable"), "parent_table");
+        assert_eq!(naming.to_database("parentId"), "parent_id");
+        assert_eq!(naming.to_database("HTTPServer"), "http_server");
+        assert_eq!(naming.to_database("Address2Line"), "address2_line");
+        assert_eq!(naming.to_database("already_snake"), "already_snake");
+    }
+
+    #[test]
+    fn snake_case_reads_back_as_pascal_case() {
+        let naming = NamingConvention::SnakeCase;
+        assert_eq!(naming.to_model("parent_table"), "ParentTable");
+        assert_eq!(naming.to_model(&naming.to_database("ChildTable")), "ChildTable");
+    }
+
+    #[test]
+    fn lower_upper_and_preserve() {
+        assert_eq!(NamingConvention::Lower.to_database("ParentTable"), "parenttable");
+        assert_eq!(NamingConvention::Upper.to_database("ParentTable"), "PARENTTABLE");
+        assert_eq!(NamingConvention::Preserve.to_database("ParentTable"), "ParentTable");
+        assert_eq!(NamingConvention::Upper.to_model("PARENTTABLE"), "PARENTTABLE");
+    }
+
+    #[test]
+    fn custom_uses_both_functions() {
+        let naming = NamingConvention::custom(|name| format!("t_{}", name), |name| name.trim_start_matches("t_").to_string());
+        assert_eq!(naming.to_database("orders"), "t_orders");
+        assert_eq!(naming.to_model("t_orders"), "orders");
+    }
+
+    #[test]
+    fn naming_convention_from_str() {
+        assert!(matches!("snake_case".parse::<NamingConvention>(), Ok(NamingConvention::SnakeCase)));
+        assert!(matches!("Upper".parse::<NamingConvention>(), Ok(NamingConvention::Upper)));
+        assert!(matches!("preserve".parse::<NamingConvention>(), Ok(NamingConvention::Preserve)));
+        assert!("kebab".parse::<NamingConvention>().is_err());
+    }
+}
@@ -0,0 +1,346 @@
+use crate::model::types::{PartitionInterval, PartitionStrategy};
+use std::fmt;
+
+/// The rows a partition accepts. Bound values are kept as written, unquoted; Postgres's
+/// `MINVALUE`/`MAXVALUE` are allowed as range bounds.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PartitionBound {
+    /// `from` is inclusive and `to` exclusive, with one value per partition key column.
+    Range { from: Vec<String>, to: Vec<String> },
+    List(Vec<String>),
+    Hash { modulus: u32, remainder: u32 },
+    /// Takes every row no other partition accepts.
+    Default,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Partition {
+    name: String,
+    bound: PartitionBound,
+}
+
+impl Partition {
+    pub fn new<S: Into<String>>(name: S, bound: PartitionBound) -> Self {
+        Self { name: name.into(), bound }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn bound(&self) -> &PartitionBound {
+        &self.bound
+    }
+}
+
+/// Generates a run of partitions instead of declaring each one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PartitionTemplate {
+    /// `count` consecutive ranges of one `interval` each, the first starting on `start`
+    /// (a `YYYY-MM-DD` date).
+    Range { interval: PartitionInterval, start: String, count: u32 },
+    /// One partition per remainder of `modulus`.
+    Hash { modulus: u32 },
+}
+
+/// How a table is partitioned: the strategy, the key columns, and its partitions, declared
+/// one by one and/or generated from a template.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Partitioning {
+    strategy: PartitionStrategy,
+    columns: Vec<String>,
+    partitions: Vec<Partition>,
+    template: Option<PartitionTemplate>,
+}
+
+impl Partitioning {
+    pub fn new(
+        strategy: PartitionStrategy,
+        columns: Vec<String>,
+        partitions: Vec<Partition>,
+        template: Option<PartitionTemplate>,
+    ) -> Self {
+        Self {
+            strategy,
+            columns,
+            partitions,
+            template,
+        }
+    }
+
+    pub fn strategy(&self) -> PartitionStrategy {
+        self.strategy
+    }
+
+    pub fn columns(&self) -> &[String] {
+        &self.columns
+    }
+
+    pub fn partitions(&self) -> &[Partition] {
+        &self.partitions
+    }
+
+    pub fn template(&self) -> Option<&PartitionTemplate> {
+        self.template.as_ref()
+    }
+
+    /// The declared partitions followed by the ones generated from the template, named
+    /// after `table_name` (`events_p2024_01`, `events_p0`, ...). A range template whose
+    /// start isn't a valid date generates nothing; `Schema::validate` reports it.
+    pub fn all_partitions(&self, table_name: &str) -> Vec<Partition> {
+        let mut partitions = self.partitions.clone();
+        match &self.template {
+            Some(PartitionTemplate::Range { interval, start, count }) => {
+                let Some(mut from) = Date::parse(start) else {
+                    return partitions;
+                };
+                for _ in 0..*count {
+                    let to = from.advance(*interval);
+                    partitions.push(Partition::new(
+                        format!("{}_p{}", table_name, from.suffix(*interval)),
+                        PartitionBound::Range {
+                            from: vec![from.to_string()],
+                            to: vec![to.to_string()],
+                        },
+                    ));
+                    from = to;
+                }
+            }
+            Some(PartitionTemplate::Hash { modulus }) => {
+                for remainder in 0..*modulus {
+                    partitions.push(Partition::new(
+                        format!("{}_p{}", table_name, remainder),
+                        PartitionBound::Hash {
+                            modulus: *modulus,
+                            remainder,
+                        },
+                    ));
+                }
+            }
+            None => {}
+        }
+        partitions
+    }
+
+    /// Checks the template and every declared bound against the strategy, returning the
+    /// problems found.
+    pub fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        for partition in &self.partitions {
+            let matches_strategy = match (partition.bound(), self.strategy) {
+                (PartitionBound::Range { from, to }, PartitionStrategy::Range) => {
+                    if from.len() != self.columns.len() || to.len() != self.columns.len() {
+                        errors.push(format!(
+                            "partition {} needs one from and one to value per partition column",
+                            partition.name()
+                        ));
+                    }
+                    true
+                }
+                (PartitionBound::List(_), PartitionStrategy::List) => true,
+                (PartitionBound::Hash { modulus, remainder }, PartitionStrategy::Hash) => {
+                    if remainder >= modulus {
+                        errors.push(format!(
+                            "partition {} has a remainder ({}) that is not below its modulus ({})",
+                            partition.name(),
+                            remainder,
+                            modulus
+                        ));
+                    }
+                    true
+                }
+                (PartitionBound::Default, strategy) => strategy != PartitionStrategy::Hash,
+                _ => false,
+            };
+            if !matches_strategy {
+                errors.push(format!(
+                    "partition {} has a bound that doesn't fit {} partitioning",
+                    partition.name(),
+                    self.strategy.sql()
+                ));
+            }
+        }
+        match &self.template {
+            Some(PartitionTemplate::Range { start, .. }) => {
+                if self.strategy != PartitionStrategy::Range || self.columns.len() != 1 {
+                    errors.push("a range template needs range partitioning on a single column".to_string());
+                }
+                if Date::parse(start).is_none() {
+                    errors.push(format!("the range template's start '{}' is not a YYYY-MM-DD date", start));
+                }
+            }
+            Some(PartitionTemplate::Hash { modulus }) => {
+                if self.strategy != PartitionStrategy::Hash {
+                    errors.push("a hash template needs hash partitioning".to_string());
+                }
+                if *modulus == 0 {
+                    errors.push("the hash template's modulus must be at least 1".to_string());
+                }
+            }
+            None => {}
+        }
+        errors
+    }
+}
+
+impl fmt::Display for Partitioning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({})", self.strategy.sql(), self.columns.join(", "))
+    }
+}
+
+/// Just enough of a calendar date to step through a range template.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Date {
+    year: i32,
+    month: u32,
+    day: u32,
+}
+
+impl Date {
+    fn parse(s: &str) -> Option<Date> {
+        let mut parts = s.trim().splitn(3, '-');
+        let year = parts.next()?.parse().ok()?;
+        let month = parts.next()?.parse().ok()?;
+        let day = parts.next()?.parse().ok()?;
+        ((1..=12).contains(&month) && day >= 1 && day <= days_in_month(year, month)).then_some(Date { year, month, day })
+    }
+
+    fn advance(self, interval: PartitionInterval) -> Date {
+        match interval {
+            PartitionInterval::Day if self.day < days_in_month(self.year, self.month) => Date {
+                day: self.day + 1,
+                ..self
+            },
+            PartitionInterval::Day if self.month < 12 => Date {
+                month: self.month + 1,
+                day: 1,
+                ..self
+            },
+            PartitionInterval::Day => Date {
+                year: self.year + 1,
+                month: 1,
+                day: 1,
+            },
+            PartitionInterval::Month if self.month < 12 => Date {
+                month: self.month + 1,
+                day: self.day.min(days_in_month(self.year, self.month + 1)),
+                ..self
+            },
+            PartitionInterval::Month => Date {
+                year: self.year + 1,
+                month: 1,
+                ..self
+            },
+            PartitionInterval::Year => Date {
+                year: self.year + 1,
+                day: self.day.min(days_in_month(self.year + 1, self.month)),
+                ..self
+            },
+        }
+    }
+
+    fn suffix(&self, interval: PartitionInterval) -> String {
+        match interval {
+            PartitionInterval::Day => format!("{:04}_{:02}_{:02}", self.year, self.month, self.day),
+            PartitionInterval::Month => format!("{:04}_{:02}", self.year, self.month),
+            PartitionInterval::Year => format!("{:04}", self.year),
+        }
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn monthly_template_generates_consecutive_ranges_across_a_year_end() {
+        let partitioning = Partitioning::new(
+            PartitionStrategy::Range,
+            vec!["created_at".to_string()],
+            vec![Partition::new("events_default", PartitionBound::Default)],
+            Some(PartitionTemplate::Range {
+                interval: PartitionInterval::Month,
+                start: "2024-11-01".to_string(),
+                count: 3,
+            }),
+        );
+
+        let partitions = partitioning.all_partitions("events");
+
+        assert_eq!(partitions.len(), 4);
+        assert_eq!(partitions[0].name(), "events_default");
+        assert_eq!(partitions[1].name(), "events_p2024_11");
+        assert_eq!(
+            partitions[3].bound(),
+            &PartitionBound::Range {
+                from: vec!["2025-01-01".to_string()],
+                to: vec!["2025-02-01".to_string()],
+            }
+        );
+        assert!(partitioning.validate().is_empty());
+        assert_eq!(partitioning.to_string(), "range (created_at)");
+    }
+
+    #[test]
+    fn daily_and_hash_templates() {
+        let daily = Partitioning::new(
+            PartitionStrategy::Range,
+            vec!["day".to_string()],
+            vec![],
+            Some(PartitionTemplate::Range {
+                interval: PartitionInterval::Day,
+                start: "2024-02-28".to_string(),
+                count: 2,
+            }),
+        );
+        let partitions = daily.all_partitions("t");
+        assert_eq!(partitions[1].name(), "t_p2024_02_29");
+        assert_eq!(
+            partitions[1].bound(),
+            &PartitionBound::Range {
+                from: vec!["2024-02-29".to_string()],
+                to: vec!["2024-03-01".to_string()],
+            }
+        );
+
+        let hashed = Partitioning::new(PartitionStrategy::Hash, vec!["id".to_string()], vec![], Some(PartitionTemplate::Hash { modulus: 4 }));
+        let partitions = hashed.all_partitions("t");
+        assert_eq!(partitions.len(), 4);
+        assert_eq!(partitions[3].bound(), &PartitionBound::Hash { modulus: 4, remainder: 3 });
+    }
+
+    #[test]
+    fn validate_reports_bounds_that_do_not_fit_the_strategy() {
+        let partitioning = Partitioning::new(
+            PartitionStrategy::List,
+            vec!["region".to_string()],
+            vec![Partition::new("t_p0", PartitionBound::Hash { modulus: 2, remainder: 0 })],
+            Some(PartitionTemplate::Range {
+                interval: PartitionInterval::Month,
+                start: "2024-13-01".to_string(),
+                count: 1,
+            }),
+        );
+
+        let errors = partitioning.validate();
+
+        assert_eq!(errors.len(), 3, "{:?}", errors);
+        assert!(errors[0].contains("t_p0"));
+    }
+}
@@ -1,11 +1,33 @@
 use crate::model::types::RelationType;
 
+/// One column pairing of a (possibly composite) foreign key: `from_column_name` on the
+/// child table references `to_column_name` on the parent table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RelationColumn {
+    from_column_name: String,
+    to_column_name: String,
+}
+
+impl RelationColumn {
+    pub fn new<S: Into<String>>(from_column_name: S, to_column_name: S) -> Self {
+        Self {
+            from_column_name: from_column_name.into(),
+            to_column_name: to_column_name.into(),
+        }
+    }
+    pub fn from_column_name(&self) -> &str {
+        &self.from_column_name
+    }
+    pub fn to_column_name(&self) -> &str {
+        &self.to_column_name
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Relation {
     to_table_name: String,
-    to_column_name: String,
     from_table_name: String,
-    from_column_name: String,
+    columns: Vec<RelationColumn>,
     relation_type: RelationType,
     disable_usage_checking: bool,
 }
@@ -18,12 +40,29 @@ impl Relation {
         from_column_name: SS,
         relation_type: RelationType,
         disable_usage_checking: bool,
+    ) -> Self {
+        Self::new_composite(
+            to_table_name,
+            from_table_name,
+            vec![RelationColumn::new(from_column_name, to_column_name)],
+            relation_type,
+            disable_usage_checking,
+        )
+    }
+
+    /// Creates a relation whose foreign key spans several columns. `columns` is ordered:
+    /// the n-th pair lines up the n-th foreign key column with the n-th referenced column.
+    pub fn new_composite<SS: Into<String>>(
+        to_table_name: SS,
+        from_table_name: SS,
+        columns: Vec<RelationColumn>,
+        relation_type: RelationType,
+        disable_usage_checking: bool,
     ) -> Self {
         Self {
             to_table_name: to_table_name.into(),
-            to_column_name: to_column_name.into(),
             from_table_name: from_table_name.into(),
-            from_column_name: from_column_name.into(),
+            columns,
             relation_type,
             disable_usage_checking,
         }
@@ -31,14 +70,31 @@ impl Relation {
     pub fn to_table_name(&self) -> &str {
         &self.to_table_name
     }
-    pub fn to_column_name(&self) -> &str {
-        &self.to_column_name
-    }
     pub fn from_table_name(&self) -> &str {
         &self.from_table_name
     }
-    pub fn from_column_name(&self) -> &str {
-        &self.from_column_name
+    pub fn columns(&self) -> &[RelationColumn] {
+        &self.columns
+    }
+    pub fn from_column_names(&self) -> Vec<&str> {
+        self.columns.iter().map(|c| c.from_column_name()).collect()
+    }
+    pub fn to_column_names(&self) -> Vec<&str> {
+        self.columns.iter().map(|c| c.to_column_name()).collect()
+    }
+    pub fn from_columns_as_string(&self) -> String {
+        self.from_column_names().join(",")
+    }
+    pub fn to_columns_as_string(&self) -> String {
+        self.to_column_names().join(",")
+    }
+    pub fn contains_from_column(&self, column_name: &str) -> bool {
+        self.columns
+            .iter()
+            .any(|c| c.from_column_name().eq_ignore_ascii_case(column_name))
+    }
+    pub fn is_composite(&self) -> bool {
+        self.columns.len() > 1
     }
     pub fn relation_type(&self) -> RelationType {
         self.relation_type
@@ -65,10 +121,30 @@ mod tests {
             true,
         );
         assert_eq!(r.to_table_name(), "parent");
-        assert_eq!(r.to_column_name(), "id");
+        assert_eq!(r.to_column_names(), vec!["id"]);
         assert_eq!(r.from_table_name(), "child");
-        assert_eq!(r.from_column_name(), "parent_id");
+        assert_eq!(r.from_column_names(), vec!["parent_id"]);
         assert_eq!(r.relation_type(), RelationType::Cascade);
         assert!(r.disable_usage_checking());
+        assert!(!r.is_composite());
+    }
+
+    #[test]
+    fn composite_columns_keep_their_order() {
+        let r = Relation::new_composite(
+            "parent",
+            "child",
+            vec![
+                RelationColumn::new("tenant_id", "tenant_id"),
+                RelationColumn::new("parent_id", "id"),
+            ],
+            RelationType::Enforce,
+            false,
+        );
+        assert!(r.is_composite());
+        assert_eq!(r.from_columns_as_string(), "tenant_id,parent_id");
+        assert_eq!(r.to_columns_as_string(), "tenant_id,id");
+        assert!(r.contains_from_column("PARENT_ID"));
+        assert!(!r.contains_from_column("id"));
     }
 }
@@ -0,0 +1,144 @@
+//! Words each dialect reserves, so an identifier spelled like one has to be quoted. The
+//! lists are lowercase and sorted so they can be searched with `binary_search`.
+
+/// The key words PostgreSQL lists as reserved, including those that may only be used as
+/// function or type names.
+pub(crate) const POSTGRES_RESERVED_WORDS: &[&str] = &[
+    "all", "analyse", "analyze", "and", "any", "array", "as", "asc", "asymmetric",
+    "authorization", "between", "bigint", "binary", "bit", "boolean", "both", "case", "cast",
+    "char", "character", "check", "coalesce", "collate", "collation", "column", "concurrently",
+    "constraint", "create", "cross", "current_catalog", "current_date", "current_role",
+    "current_schema", "current_time", "current_timestamp", "current_user", "dec", "decimal",
+    "default", "deferrable", "desc", "distinct", "do", "else", "end", "except", "exists",
+    "extract", "false", "fetch", "float", "for", "foreign", "freeze", "from", "full", "grant",
+    "greatest", "group", "grouping", "having", "ilike", "in", "initially", "inner", "inout",
+    "int", "integer", "intersect", "interval", "into", "is", "isnull", "join", "json",
+    "lateral", "leading", "least", "left", "like", "limit", "localtime", "localtimestamp",
+    "national", "natural", "nchar", "none", "normalize", "not", "notnull", "null", "nullif",
+    "numeric", "offset", "on", "only", "or", "order", "out", "outer", "overlaps", "overlay",
+    "placing", "position", "precision", "primary", "real", "references", "returning", "right",
+    "row", "select", "session_user", "setof", "similar", "smallint", "some", "substring",
+    "symmetric", "system_user", "table", "tablesample", "then", "time", "timestamp", "to",
+    "trailing", "treat", "trim", "true", "union", "unique", "user", "using", "values",
+    "varchar", "variadic", "verbose", "when", "where", "window", "with", "xmlattributes",
+    "xmlconcat", "xmlelement", "xmlexists", "xmlforest", "xmlnamespaces", "xmlparse", "xmlpi",
+    "xmlroot", "xmlserialize", "xmltable",
+];
+
+/// SQLite's key words. SQLite accepts many of them as names in some positions, but quoting
+/// every one is the only way to be sure a statement parses.
+pub(crate) const SQLITE_RESERVED_WORDS: &[&str] = &[
+    "abort", "action", "add", "after", "all", "alter", "always", "analyze", "and", "as", "asc",
+    "attach", "autoincrement", "before", "begin", "between", "by", "cascade", "case", "cast",
+    "check", "collate", "column", "commit", "conflict", "constraint", "create", "cross",
+    "current", "current_date", "current_time", "current_timestamp", "database", "default",
+    "deferrable", "deferred", "delete", "desc", "detach", "distinct", "do", "drop", "each",
+    "else", "end", "escape", "except", "exclude", "exclusive", "exists", "explain", "fail",
+    "filter", "first", "following", "for", "foreign", "from", "full", "generated", "glob",
+    "group", "groups", "having", "if", "ignore", "immediate", "in", "index", "indexed",
+    "initially", "inner", "insert", "instead", "intersect", "into", "is", "isnull", "join",
+    "key", "last", "left", "like", "limit", "match", "materialized", "natural", "no", "not",
+    "nothing", "notnull", "null", "nulls", "of", "offset", "on", "or", "order", "others",
+    "outer", "over", "partition", "plan", "pragma", "preceding", "primary", "query", "raise",
+    "range", "recursive", "references", "regexp", "reindex", "release", "rename", "replace",
+    "restrict", "returning", "right", "rollback", "row", "rows", "savepoint", "select", "set",
+    "table", "temp", "temporary", "then", "ties", "to", "transaction", "trigger", "unbounded",
+    "union", "unique", "update", "using", "vacuum", "values", "view", "virtual", "when",
+    "where", "window", "with", "without",
+];
+
+/// The words Transact-SQL reserves.
+pub(crate) const SQLSERVER_RESERVED_WORDS: &[&str] = &[
+    "add", "all", "alter", "and", "any", "as", "asc", "authorization", "backup", "begin",
+    "between", "break", "browse", "bulk", "by", "cascade", "case", "check", "checkpoint",
+    "close", "clustered", "coalesce", "collate", "column", "commit", "compute", "constraint",
+    "contains", "containstable", "continue", "convert", "create", "cross", "current",
+    "current_date", "current_time", "current_timestamp", "current_user", "cursor", "database",
+    "dbcc", "deallocate", "declare", "default", "delete", "deny", "desc", "disk", "distinct",
+    "distributed", "double", "drop", "dump", "else", "end", "errlvl", "escape", "except",
+    "exec", "execute", "exists", "exit", "external", "fetch", "file", "fillfactor", "for",
+    "foreign", "freetext", "freetexttable", "from", "full", "function", "goto", "grant",
+    "group", "having", "holdlock", "identity", "identity_insert", "identitycol", "if", "in",
+    "index", "inner", "insert", "intersect", "into", "is", "join", "key", "kill", "left",
+    "like", "lineno", "load", "merge", "national", "nocheck", "nonclustered", "not", "null",
+    "nullif", "of", "off", "offsets", "on", "open", "opendatasource", "openquery",
+    "openrowset", "openxml", "option", "or", "order", "outer", "over", "percent", "pivot",
+    "plan", "precision", "primary", "print", "proc", "procedure", "public", "raiserror",
+    "read", "readtext", "reconfigure", "references", "replication", "restore", "restrict",
+    "return", "revert", "revoke", "right", "rollback", "rowcount", "rowguidcol", "rule",
+    "save", "schema", "securityaudit", "select", "semantickeyphrasetable",
+    "semanticsimilaritydetailstable", "semanticsimilaritytable", "session_user", "set",
+    "setuser", "shutdown", "some", "statistics", "system_user", "table", "tablesample",
+    "textsize", "then", "to", "top", "tran", "transaction", "trigger", "truncate",
+    "try_convert", "tsequal", "union", "unique", "unpivot", "update", "updatetext", "use",
+    "user", "values", "varying", "view", "waitfor", "when", "where", "while", "with",
+    "within", "writetext",
+];
+
+/// The words MySQL reserves; MariaDB reserves nearly the same set.
+pub(crate) const MYSQL_RESERVED_WORDS: &[&str] = &[
+    "accessible", "add", "all", "alter", "analyze", "and", "as", "asc", "asensitive", "before",
+    "between", "bigint", "binary", "blob", "both", "by", "call", "cascade", "case", "change",
+    "char", "character", "check", "collate", "column", "condition", "constraint", "continue",
+    "convert", "create", "cross", "cube", "cume_dist", "current_date", "current_time",
+    "current_timestamp", "current_user", "cursor", "database", "databases", "day_hour",
+    "day_microsecond", "day_minute", "day_second", "dec", "decimal", "declare", "default",
+    "delayed", "delete", "dense_rank", "desc", "describe", "deterministic", "distinct",
+    "distinctrow", "div", "double", "drop", "dual", "each", "else", "elseif", "empty",
+    "enclosed", "escaped", "except", "exists", "exit", "explain", "false", "fetch",
+    "first_value", "float", "float4", "float8", "for", "force", "foreign", "from", "fulltext",
+    "function", "generated", "get", "grant", "group", "grouping", "groups", "having",
+    "high_priority", "hour_microsecond", "hour_minute", "hour_second", "if", "ignore", "in",
+    "index", "infile", "inner", "inout", "insensitive", "insert", "int", "int1", "int2",
+    "int3", "int4", "int8", "integer", "intersect", "interval", "into", "io_after_gtids",
+    "io_before_gtids", "is", "iterate", "join", "json_table", "key", "keys", "kill", "lag",
+    "last_value", "lateral", "lead", "leading", "leave", "left", "like", "limit", "linear",
+    "lines", "load", "localtime", "localtimestamp", "lock", "long", "longblob", "longtext",
+    "loop", "low_priority", "master_bind", "master_ssl_verify_server_cert", "match",
+    "maxvalue", "mediumblob", "mediumint", "mediumtext", "middleint", "minute_microsecond",
+    "minute_second", "mod", "modifies", "natural", "no_write_to_binlog", "not", "nth_value",
+    "ntile", "null", "numeric", "of", "offset", "on", "optimize", "optimizer_costs", "option",
+    "optionally", "or", "order", "out", "outer", "outfile", "over", "partition",
+    "percent_rank", "precision", "primary", "procedure", "purge", "range", "rank", "read",
+    "read_write", "reads", "real", "recursive", "references", "regexp", "release", "rename",
+    "repeat", "replace", "require", "resignal", "restrict", "return", "returning", "revoke",
+    "right", "rlike", "row", "row_number", "rows", "schema", "schemas", "second_microsecond",
+    "select", "sensitive", "separator", "set", "show", "signal", "smallint", "spatial",
+    "specific", "sql", "sql_big_result", "sql_calc_found_rows", "sql_small_result",
+    "sqlexception", "sqlstate", "sqlwarning", "ssl", "starting", "stored", "straight_join",
+    "system", "table", "terminated", "then", "tinyblob", "tinyint", "tinytext", "to",
+    "trailing", "trigger", "true", "undo", "union", "unique", "unlock", "unsigned", "update",
+    "usage", "use", "using", "utc_date", "utc_time", "utc_timestamp", "values", "varbinary",
+    "varchar", "varcharacter", "varying", "virtual", "when", "where", "while", "window",
+    "with", "write", "xor", "year_month", "zerofill",
+];
+
+/// DuckDB's reserved key words, along with those it only allows as function or type
+/// names.
+pub(crate) const DUCKDB_RESERVED_WORDS: &[&str] = &[
+    "all", "analyse", "analyze", "and", "any", "array", "as", "asc", "asymmetric",
+    "authorization", "between", "binary", "both", "case", "cast", "check", "collate",
+    "collation", "column", "concurrently", "constraint", "create", "cross", "default",
+    "deferrable", "desc", "describe", "distinct", "do", "else", "end", "except", "false",
+    "fetch", "for", "foreign", "freeze", "from", "full", "generated", "glob", "grant", "group",
+    "having", "ilike", "in", "initially", "inner", "intersect", "into", "is", "isnull", "join",
+    "lambda", "lateral", "leading", "left", "like", "limit", "map", "natural", "not",
+    "notnull", "null", "offset", "on", "only", "or", "order", "outer", "overlaps", "pivot",
+    "pivot_longer", "pivot_wider", "placing", "positional", "primary", "qualify", "references",
+    "returning", "right", "select", "show", "similar", "some", "struct", "summarize",
+    "symmetric", "table", "tablesample", "then", "to", "trailing", "true", "try_cast", "union",
+    "unique", "unpivot", "using", "variadic", "verbose", "when", "where", "window", "with",
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn word_lists_are_sorted_lowercase_and_unique() {
+        for words in [POSTGRES_RESERVED_WORDS, SQLITE_RESERVED_WORDS, SQLSERVER_RESERVED_WORDS, MYSQL_RESERVED_WORDS, DUCKDB_RESERVED_WORDS] {
+            assert!(words.windows(2).all(|pair| pair[0] < pair[1]));
+            assert!(words.iter().all(|word| *word == word.to_lowercase()));
+        }
+    }
+}
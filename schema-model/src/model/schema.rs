@@ -1,9 +1,11 @@
+use crate::model::column_type::ColumnType;
 use crate::model::enum_type::EnumType;
 use crate::model::function::Function;
 use crate::model::other_sql::OtherSql;
 use crate::model::procedure::Procedure;
+use crate::model::sequence::Sequence;
 use crate::model::table::Table;
-use crate::model::types::{DatabaseType, RelationType};
+use crate::model::types::{DatabaseType, IdentityMode, RefreshPolicy, RelationType};
 use crate::model::view::View;
 use std::collections::HashMap;
 
@@ -16,6 +18,7 @@ pub struct Schema {
     functions: Vec<Function>,
     procedures: Vec<Procedure>,
     other_sql: Vec<OtherSql>,
+    sequences: Vec<Sequence>,
     // Case-insensitive map: store lowercase name -> index in tables vec
     table_map: HashMap<String, usize>,
     enum_types: HashMap<String, EnumType>,
@@ -31,6 +34,7 @@ impl Default for Schema {
             functions: Vec::new(),
             procedures: Vec::new(),
             other_sql: Vec::new(),
+            sequences: Vec::new(),
             table_map: HashMap::new(),
             enum_types: HashMap::new(),
         }
@@ -111,11 +115,22 @@ impl Schema {
     }
 
     pub fn get_enum_type(&self, type_name: &str) -> &EnumType {
-        self.enum_types
-            .get(&type_name.to_lowercase())
+        self.get_optional_enum_type(type_name)
             .unwrap_or_else(|| panic!("Unable to locate an enum type with the name '{}'", type_name))
     }
 
+    pub fn get_optional_enum_type(&self, type_name: &str) -> Option<&EnumType> {
+        self.enum_types.get(&type_name.to_lowercase())
+    }
+
+    pub fn sequences(&self) -> &[Sequence] {
+        &self.sequences
+    }
+
+    pub fn get_optional_sequence(&self, name: &str) -> Option<&Sequence> {
+        self.sequences.iter().find(|s| s.name().eq_ignore_ascii_case(name))
+    }
+
     pub fn functions(&self) -> &[Function] {
         &self.functions
     }
@@ -130,6 +145,29 @@ impl Schema {
 
     pub fn validate(&self) -> Vec<String> {
         let mut errors: Vec<String> = Vec::new();
+        for sequence in &self.sequences {
+            if sequence.increment() == Some(0) {
+                errors.push(format!("ERROR: sequence {} has an increment of 0", sequence));
+            }
+            if let (Some(min_value), Some(max_value)) = (sequence.min_value(), sequence.max_value())
+                && min_value >= max_value
+            {
+                errors.push(format!(
+                    "ERROR: sequence {} has a minValue ({}) that is not below its maxValue ({})",
+                    sequence, min_value, max_value
+                ));
+            }
+        }
+
+        for view in &self.views {
+            if view.refresh_policy() == RefreshPolicy::Concurrent && !view.indexes().iter().any(|key| key.is_unique()) {
+                errors.push(format!(
+                    "ERROR: materialized view {} is refreshed concurrently, which needs a unique index on the view",
+                    view
+                ));
+            }
+        }
+
         for table in &self.tables {
             if table.columns().is_empty() {
                 errors.push(format!(
@@ -138,28 +176,59 @@ impl Schema {
                 ));
             }
 
+            if let Some(partitioning) = table.partitioning() {
+                for error in partitioning.validate() {
+                    errors.push(format!("ERROR: table {}: {}", table.name(), error));
+                }
+                for column_name in partitioning.columns() {
+                    if !table.has_column(column_name) {
+                        errors.push(format!(
+                            "ERROR: table {} is partitioned by {}, which is not one of its columns",
+                            table.name(),
+                            column_name
+                        ));
+                    }
+                }
+                // Postgres can only enforce uniqueness within each partition, so every
+                // unique key has to contain the whole partition key.
+                for key in table.keys() {
+                    if let Some(missing) = partitioning.columns().iter().find(|column_name| !key.contains_column(column_name)) {
+                        errors.push(format!(
+                            "ERROR: table {} is partitioned by {}, so its {:?} key ({}) must include {}",
+                            table.name(),
+                            partitioning,
+                            key.key_type(),
+                            key.columns_as_string(),
+                            missing
+                        ));
+                    }
+                }
+            }
+
             for relation in table.relations() {
                 if relation.relation_type() == RelationType::SetNull {
-                    let from_column_name = relation.from_column_name();
-                    match table.has_column(from_column_name) {
-                        true if table.column(from_column_name).is_required() => {
-                            errors.push(format!(
-                                "ERROR: {}.{} is required. The {}.{} relation specifies setnull, which is not allowed",
-                                table.name(),
-                                from_column_name,
-                                relation.to_table_name(),
-                                relation.to_column_name()
-                            ));
-                        }
-                        true => {}
-                        false => {
-                            errors.push(format!(
-                                "ERROR: {}.{} does not exist. The {}.{} relation refers to it as the source column",
-                                table.name(),
-                                from_column_name,
-                                relation.to_table_name(),
-                                relation.to_column_name()
-                            ));
+                    for relation_column in relation.columns() {
+                        let from_column_name = relation_column.from_column_name();
+                        match table.has_column(from_column_name) {
+                            true if table.column(from_column_name).is_required() => {
+                                errors.push(format!(
+                                    "ERROR: {}.{} is required. The {}.{} relation specifies setnull, which is not allowed",
+                                    table.name(),
+                                    from_column_name,
+                                    relation.to_table_name(),
+                                    relation_column.to_column_name()
+                                ));
+                            }
+                            true => {}
+                            false => {
+                                errors.push(format!(
+                                    "ERROR: {}.{} does not exist. The {}.{} relation refers to it as the source column",
+                                    table.name(),
+                                    from_column_name,
+                                    relation.to_table_name(),
+                                    relation_column.to_column_name()
+                                ));
+                            }
                         }
                     }
                 }
@@ -177,6 +246,41 @@ impl Schema {
                     ));
                 }
 
+                if let Some(sequence_name) = column.sequence()
+                    && self.get_optional_sequence(sequence_name).is_none()
+                {
+                    errors.push(format!(
+                        "ERROR: {}.{} takes its default from sequence '{}' which is not defined in this schema",
+                        table.name(),
+                        column.name(),
+                        sequence_name
+                    ));
+                }
+
+                let has_identity_settings = column.identity_mode().is_some()
+                    || column.identity_start().is_some()
+                    || column.identity_increment().is_some();
+                if has_identity_settings
+                    && !matches!(column.column_type(), ColumnType::Sequence | ColumnType::LongSequence)
+                {
+                    errors.push(format!(
+                        "ERROR: {}.{} has identity settings but is not a sequence or longSequence column",
+                        table.name(),
+                        column.name()
+                    ));
+                } else if column.identity_mode() == Some(IdentityMode::Serial)
+                    && (column.identity_start().is_some() || column.identity_increment().is_some())
+                {
+                    errors.push(format!(
+                        "ERROR: {}.{} is a serial column, which takes no identity start or increment",
+                        table.name(),
+                        column.name()
+                    ));
+                }
+                if column.identity_increment() == Some(0) {
+                    errors.push(format!("ERROR: {}.{} has an identity increment of 0", table.name(), column.name()));
+                }
+
                 if column.column_type() == crate::model::column_type::ColumnType::Array
                     && column.element_type().is_none()
                 {
@@ -217,6 +321,10 @@ impl Schema {
             .insert(enum_type.name().to_lowercase(), enum_type);
     }
 
+    pub(crate) fn add_sequence(&mut self, sequence: Sequence) {
+        self.sequences.push(sequence);
+    }
+
     pub(crate) fn add_functions(&mut self, functions: Vec<Function>) {
         self.functions.extend(functions);
     }
@@ -308,6 +416,54 @@ mod tests {
         assert_eq!(pg[0].name(), "v1");
     }
 
+    #[test]
+    fn validate_reports_concurrent_refresh_without_unique_index() {
+        use crate::model::key::{Key, KeyColumn};
+        use crate::model::types::KeyType;
+
+        let mut s = make_schema();
+        let index = Key::new(KeyType::Index, vec![KeyColumn::new("id")]);
+        s.add_view(View::new_materialized(Some("s"), "totals", "select 1 as id", None, RefreshPolicy::Concurrent, vec![index]));
+        let unique = Key::new_full(KeyType::Index, vec![KeyColumn::new("id")], false, false, true, None::<String>);
+        s.add_view(View::new_materialized(Some("s"), "ok", "select 1 as id", None, RefreshPolicy::Concurrent, vec![unique]));
+
+        let errors = s.validate();
+        assert_eq!(errors.len(), 1, "{:?}", errors);
+        assert!(errors[0].contains("s.totals") && errors[0].contains("unique index"));
+    }
+
+    #[test]
+    fn validate_reports_partition_key_missing_from_primary_key() {
+        use crate::builder::{ColumnBuilder, KeyBuilder, TableBuilder};
+        use crate::model::partition::{PartitionTemplate, Partitioning};
+        use crate::model::types::{KeyType, PartitionInterval, PartitionStrategy};
+
+        let partitioning = Partitioning::new(
+            PartitionStrategy::Range,
+            vec!["created_at".to_string()],
+            vec![],
+            Some(PartitionTemplate::Range {
+                interval: PartitionInterval::Month,
+                start: "2024-01-01".to_string(),
+                count: 12,
+            }),
+        );
+        let mut s = make_schema();
+        s.add_table(
+            TableBuilder::new(Some("s"), "events")
+                .add_column(ColumnBuilder::new(Some("s"), "id", ColumnType::Long).required(true).build())
+                .add_column(ColumnBuilder::new(Some("s"), "created_at", ColumnType::Timestamp).required(true).build())
+                .add_key(KeyBuilder::new(KeyType::Primary).add_column("id").build())
+                .partitioning(Some(partitioning))
+                .build(),
+        );
+
+        let errors = s.validate();
+
+        assert_eq!(errors.len(), 1, "{:?}", errors);
+        assert!(errors[0].contains("events") && errors[0].contains("must include created_at"));
+    }
+
     #[test]
     fn validate_setnull_error_when_required() {
         let mut s = make_schema();
@@ -434,6 +590,60 @@ mod tests {
         assert!(errors[0].contains("StatusType"));
     }
 
+    #[test]
+    fn validate_reports_undeclared_sequences_and_inconsistent_sequence_options() {
+        use crate::builder::{ColumnBuilder, SchemaBuilder, SequenceBuilder, TableBuilder};
+        use crate::model::column_type::ColumnType as CT;
+
+        let table = TableBuilder::new(Some("s"), "orders")
+            .add_column(
+                ColumnBuilder::new(Some("s"), "order_no", CT::Long)
+                    .sequence(Some("order_no_seq".to_string()))
+                    .build(),
+            )
+            .add_column(
+                ColumnBuilder::new(Some("s"), "invoice_no", CT::Long)
+                    .sequence(Some("DOCUMENT_SEQ".to_string()))
+                    .build(),
+            )
+            .build();
+        let schema = SchemaBuilder::new(Some("s"))
+            .add_table(table)
+            .add_sequence(
+                SequenceBuilder::new(Some("s"), "document_seq")
+                    .increment(Some(0))
+                    .min_value(Some(10))
+                    .max_value(Some(10))
+                    .build(),
+            )
+            .build();
+
+        let errors = schema.validate();
+        assert_eq!(errors.len(), 3);
+        assert!(errors[0].contains("increment of 0"));
+        assert!(errors[1].contains("minValue (10)"));
+        assert!(errors[2].contains("order_no_seq"));
+    }
+
+    #[test]
+    fn validate_reports_misplaced_identity_settings() {
+        use crate::builder::{ColumnBuilder, SchemaBuilder, TableBuilder};
+        use crate::model::column_type::ColumnType as CT;
+
+        let table = TableBuilder::new(Some("s"), "orders")
+            .add_column(ColumnBuilder::new(Some("s"), "id", CT::Sequence).identity_mode(Some(IdentityMode::Always)).identity_start(Some(1000)).build())
+            .add_column(ColumnBuilder::new(Some("s"), "line_id", CT::LongSequence).identity_mode(Some(IdentityMode::Serial)).identity_increment(Some(0)).build())
+            .add_column(ColumnBuilder::new(Some("s"), "amount", CT::Int).identity_start(Some(1)).build())
+            .build();
+        let schema = SchemaBuilder::new(Some("s")).add_table(table).build();
+
+        let errors = schema.validate();
+        assert_eq!(errors.len(), 3, "{:?}", errors);
+        assert!(errors[0].contains("orders.line_id is a serial column"));
+        assert!(errors[1].contains("identity increment of 0"));
+        assert!(errors[2].contains("orders.amount"));
+    }
+
     #[test]
     fn validate_reports_error_for_table_with_no_columns() {
         // A table with zero columns generates invalid `create table t (\n)` DDL; this
@@ -0,0 +1,137 @@
+use crate::model::types::DatabaseType;
+use std::fmt;
+
+/// A standalone sequence, shared by any number of columns through their `sequence`
+/// attribute. Unset options are left to the database's defaults.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sequence {
+    schema_name: Option<String>,
+    name: String,
+    start: Option<i64>,
+    increment: Option<i64>,
+    min_value: Option<i64>,
+    max_value: Option<i64>,
+    cycle: bool,
+    cache: Option<i64>,
+}
+
+impl Sequence {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new<S: Into<String>>(
+        schema_name: Option<S>,
+        name: S,
+        start: Option<i64>,
+        increment: Option<i64>,
+        min_value: Option<i64>,
+        max_value: Option<i64>,
+        cycle: bool,
+        cache: Option<i64>,
+    ) -> Self {
+        Self {
+            schema_name: schema_name.map(|s| s.into()),
+            name: name.into(),
+            start,
+            increment,
+            min_value,
+            max_value,
+            cycle,
+            cache,
+        }
+    }
+
+    pub fn schema_name(&self) -> Option<&str> {
+        self.schema_name.as_deref()
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn start(&self) -> Option<i64> {
+        self.start
+    }
+
+    pub fn increment(&self) -> Option<i64> {
+        self.increment
+    }
+
+    pub fn min_value(&self) -> Option<i64> {
+        self.min_value
+    }
+
+    pub fn max_value(&self) -> Option<i64> {
+        self.max_value
+    }
+
+    pub fn is_cycle(&self) -> bool {
+        self.cycle
+    }
+
+    pub fn cache(&self) -> Option<i64> {
+        self.cache
+    }
+
+    pub fn fully_qualified_sequence_name(&self, database_type: DatabaseType) -> String {
+        database_type.qualified_name(self.schema_name(), self.name())
+    }
+
+    /// The explicitly set options as the clauses shared by Postgres and SQL Server
+    /// (`start with 100 increment by 10 ... cycle`), each preceded by a space. Empty when
+    /// every option is left at its default.
+    pub fn options_sql(&self) -> String {
+        let mut sql = String::new();
+        if let Some(start) = self.start {
+            sql.push_str(&format!(" start with {}", start));
+        }
+        if let Some(increment) = self.increment {
+            sql.push_str(&format!(" increment by {}", increment));
+        }
+        if let Some(min_value) = self.min_value {
+            sql.push_str(&format!(" minvalue {}", min_value));
+        }
+        if let Some(max_value) = self.max_value {
+            sql.push_str(&format!(" maxvalue {}", max_value));
+        }
+        if let Some(cache) = self.cache {
+            sql.push_str(&format!(" cache {}", cache));
+        }
+        if self.cycle {
+            sql.push_str(" cycle");
+        }
+        sql
+    }
+}
+
+impl fmt::Display for Sequence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.schema_name {
+            Some(schema) => write!(f, "{}.{}", schema, self.name),
+            None => write!(f, "{}", self.name),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn options_sql_lists_only_the_options_that_were_set() {
+        let plain = Sequence::new(None::<&str>, "seq", None, None, None, None, false, None);
+        assert_eq!(plain.options_sql(), "");
+
+        let tuned = Sequence::new(Some("s"), "seq", Some(1000), Some(5), Some(1), Some(999999), true, Some(20));
+        assert_eq!(
+            tuned.options_sql(),
+            " start with 1000 increment by 5 minvalue 1 maxvalue 999999 cache 20 cycle"
+        );
+    }
+
+    #[test]
+    fn fully_qualified_sequence_name_falls_back_to_default_schema() {
+        let s = Sequence::new(None::<&str>, "seq", None, None, None, None, false, None);
+        assert_eq!(s.fully_qualified_sequence_name(DatabaseType::Postgresql), "public.seq");
+        assert_eq!(s.fully_qualified_sequence_name(DatabaseType::SqlServer), "dbo.seq");
+        assert_eq!(s.to_string(), "seq");
+    }
+}
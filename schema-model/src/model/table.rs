@@ -4,9 +4,10 @@ use crate::model::column_type::ColumnType;
 use crate::model::constraint::Constraint;
 use crate::model::initial_data::InitialData;
 use crate::model::key::Key;
+use crate::model::partition::Partitioning;
 use crate::model::relation::Relation;
 use crate::model::trigger::Trigger;
-use crate::model::types::{BooleanMode, DatabaseType, KeyType, LockEscalation, TableOption};
+use crate::model::types::{BooleanMode, CompressionLevel, DatabaseType, KeyType, LockEscalation, TableOption};
 use std::fmt;
 
 #[derive(Debug, Clone)]
@@ -26,6 +27,9 @@ pub struct Table {
     initial_data: Vec<InitialData>,
     options: Vec<TableOption>,
     aggregations: Vec<Aggregation>,
+    description: Option<String>,
+    partitioning: Option<Partitioning>,
+    previous_name: Option<String>,
 }
 
 impl Table {
@@ -62,6 +66,9 @@ impl Table {
             initial_data,
             options,
             aggregations,
+            description: None,
+            partitioning: None,
+            previous_name: None,
         }
     }
 
@@ -125,11 +132,23 @@ impl Table {
         &self.aggregations
     }
 
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    /// How the table is split into partitions, when it is partitioned.
+    pub fn partitioning(&self) -> Option<&Partitioning> {
+        self.partitioning.as_ref()
+    }
+
+    /// The name the table had before it was renamed, so that a migration renames it instead
+    /// of dropping it and creating it again.
+    pub fn previous_name(&self) -> Option<&str> {
+        self.previous_name.as_deref()
+    }
+
     pub fn column(&self, column_name: &str) -> &Column {
-        let lower = column_name.to_lowercase();
-        self.columns
-            .iter()
-            .find(|c| c.name().eq_ignore_ascii_case(&lower))
+        self.column_checked(column_name)
             .unwrap_or_else(|| {
                 panic!(
                     "Unable to locate a column with the name '{}' in table '{}'",
@@ -138,6 +157,12 @@ impl Table {
             })
     }
 
+    /// Same as `column`, but returns `None` instead of panicking when the table has no
+    /// such column.
+    pub fn column_checked(&self, column_name: &str) -> Option<&Column> {
+        self.columns.iter().find(|c| c.name().eq_ignore_ascii_case(column_name))
+    }
+
     pub fn primary_key(&self) -> Option<&Key> {
         self.keys.iter().find(|k| k.key_type() == KeyType::Primary)
     }
@@ -156,6 +181,19 @@ impl Table {
         })
     }
 
+    /// The sequence column that makes up the whole primary key, if there is one - the column
+    /// SQLite can turn into an auto-incrementing `integer primary key` rowid alias.
+    pub fn rowid_alias_column(&self) -> Option<&Column> {
+        let key_columns = self.primary_key()?.columns();
+        let [key_column] = key_columns.as_slice() else {
+            return None;
+        };
+        self.columns.iter().find(|c| {
+            c.name().eq_ignore_ascii_case(key_column.name())
+                && matches!(c.column_type(), ColumnType::Sequence | ColumnType::LongSequence)
+        })
+    }
+
     pub fn primary_key_columns(&self) -> Option<Vec<String>> {
         self.primary_key()
             .map(|k| k.columns().iter().map(|kc| kc.name().to_string()).collect())
@@ -165,6 +203,14 @@ impl Table {
         self.options.contains(&option)
     }
 
+    /// The level the table is compressed at; `None` when it isn't compressed.
+    pub fn compression(&self) -> Option<CompressionLevel> {
+        self.options.iter().find_map(|option| match option {
+            TableOption::Compress(level) => Some(*level),
+            _ => None,
+        })
+    }
+
     pub fn has_column_constraints(&self, boolean_mode: BooleanMode) -> bool {
         self.columns
             .iter()
@@ -183,7 +229,14 @@ impl Table {
         let name = column.name();
         self.relations
             .iter()
-            .find(|r| r.from_column_name().eq_ignore_ascii_case(name))
+            .find(|r| r.contains_from_column(name))
+    }
+
+    /// The single-column relation whose foreign key column is `column_name`, if any.
+    pub fn single_column_relation(&self, column_name: &str) -> Option<&Relation> {
+        self.relations
+            .iter()
+            .find(|r| !r.is_composite() && r.contains_from_column(column_name))
     }
 
     pub fn fully_qualified_table_name(&self, database_type: DatabaseType) -> String {
@@ -193,6 +246,18 @@ impl Table {
     pub fn add_reverse_relation(&mut self, relation: Relation) {
         self.reverse_relations.push(relation);
     }
+
+    pub fn set_description(&mut self, description: Option<String>) {
+        self.description = description;
+    }
+
+    pub fn set_partitioning(&mut self, partitioning: Option<Partitioning>) {
+        self.partitioning = partitioning;
+    }
+
+    pub fn set_previous_name(&mut self, previous_name: Option<String>) {
+        self.previous_name = previous_name;
+    }
 }
 
 impl fmt::Display for Table {
@@ -3,6 +3,10 @@ pub enum DatabaseType {
     Postgresql,
     Sqlite,
     SqlServer,
+    /// MySQL, and MariaDB, which shares its SQL.
+    MySql,
+    /// DuckDB, the embedded analytical database.
+    DuckDb,
 }
 
 impl DatabaseType {
@@ -11,6 +15,8 @@ impl DatabaseType {
             DatabaseType::Postgresql => ";",
             DatabaseType::Sqlite => ";",
             DatabaseType::SqlServer => "\nGO",
+            DatabaseType::MySql => ";",
+            DatabaseType::DuckDb => ";",
         }
     }
 
@@ -19,6 +25,8 @@ impl DatabaseType {
             DatabaseType::Postgresql => 63,
             DatabaseType::Sqlite => 128,
             DatabaseType::SqlServer => 32,
+            DatabaseType::MySql => 64,
+            DatabaseType::DuckDb => 128,
         }
     }
 
@@ -26,19 +34,111 @@ impl DatabaseType {
         match self {
             DatabaseType::Postgresql => Some("public"),
             DatabaseType::SqlServer => Some("dbo"),
-            DatabaseType::Sqlite => None,
+            DatabaseType::DuckDb => Some("main"),
+            DatabaseType::Sqlite | DatabaseType::MySql => None,
         }
     }
 
     pub fn qualified_name(&self, schema_name: Option<&str>, name: &str) -> String {
-        let resolved = match schema_name {
+        match self.resolved_schema(schema_name) {
+            Some(schema) => format!("{}.{}", schema, name),
+            None => name.to_string(),
+        }
+    }
+
+    fn resolved_schema<'a>(&self, schema_name: Option<&'a str>) -> Option<&'a str> {
+        match schema_name {
             Some(s) if *self == DatabaseType::SqlServer && s.eq_ignore_ascii_case("public") => Some("dbo"),
+            Some(s) if *self == DatabaseType::DuckDb && s.eq_ignore_ascii_case("public") => Some("main"),
+            // A MySQL schema is a database; `public` means the one connected to.
+            Some(s) if *self == DatabaseType::MySql && s.eq_ignore_ascii_case("public") => None,
             Some(s) => Some(s),
             None => self.default_schema(),
+        }
+    }
+
+    /// Whether `word` is reserved in this dialect, ignoring case.
+    pub fn is_reserved_word(&self, word: &str) -> bool {
+        let words = match self {
+            DatabaseType::Postgresql => POSTGRES_RESERVED_WORDS,
+            DatabaseType::Sqlite => SQLITE_RESERVED_WORDS,
+            DatabaseType::SqlServer => SQLSERVER_RESERVED_WORDS,
+            DatabaseType::MySql => MYSQL_RESERVED_WORDS,
+            DatabaseType::DuckDb => DUCKDB_RESERVED_WORDS,
         };
-        match resolved {
-            Some(schema) => format!("{}.{}", schema, name),
-            None => name.to_string(),
+        words.binary_search(&word.to_lowercase().as_str()).is_ok()
+    }
+
+    /// `name` wrapped in this dialect's identifier quotes when `quoting` calls for it:
+    /// double quotes on PostgreSQL, SQLite and DuckDB, brackets on SQL Server and backticks on
+    /// MySQL. A name that is already quoted is left as it is.
+    pub fn quote_identifier(&self, name: &str, quoting: IdentifierQuoting) -> String {
+        let (open, close) = match self {
+            DatabaseType::SqlServer => ('[', ']'),
+            DatabaseType::MySql => ('`', '`'),
+            DatabaseType::Postgresql | DatabaseType::Sqlite | DatabaseType::DuckDb => ('"', '"'),
+        };
+        let already_quoted = name.len() >= 2 && name.starts_with(open) && name.ends_with(close);
+        let quote = match quoting {
+            IdentifierQuoting::Always => true,
+            IdentifierQuoting::WhenNeeded => !self.is_plain_identifier(name),
+            IdentifierQuoting::Never => false,
+        };
+        if quote && !already_quoted {
+            let escaped = name.replace(close, &format!("{}{}", close, close));
+            format!("{}{}{}", open, escaped, close)
+        } else {
+            name.to_string()
+        }
+    }
+
+    /// `qualified_name` with the schema and the name each quoted per `quoting`.
+    pub fn quoted_qualified_name(&self, schema_name: Option<&str>, name: &str, quoting: IdentifierQuoting) -> String {
+        let quoted_name = self.quote_identifier(name, quoting);
+        match self.resolved_schema(schema_name) {
+            Some(schema) => format!("{}.{}", self.quote_identifier(schema, quoting), quoted_name),
+            None => quoted_name,
+        }
+    }
+
+    /// Whether `name` can be written without quotes: it is not a reserved word and has no
+    /// character the dialect only accepts quoted. Case is left alone, so PostgreSQL folds a
+    /// mixed-case name just as it folds the same name in hand-written SQL.
+    fn is_plain_identifier(&self, name: &str) -> bool {
+        let mut chars = name.chars();
+        let starts_plain = chars
+            .next()
+            .is_some_and(|first| first == '_' || first.is_ascii_alphabetic());
+        let rest_plain = chars.all(|c| match self {
+            DatabaseType::Postgresql | DatabaseType::MySql => c == '_' || c == '$' || c.is_ascii_alphanumeric(),
+            DatabaseType::Sqlite | DatabaseType::DuckDb => c == '_' || c.is_ascii_alphanumeric(),
+            DatabaseType::SqlServer => matches!(c, '_' | '@' | '#' | '$') || c.is_ascii_alphanumeric(),
+        });
+        starts_plain && rest_plain && !self.is_reserved_word(name)
+    }
+}
+
+/// When generated SQL wraps identifiers in the dialect's quotes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum IdentifierQuoting {
+    /// Quote every identifier.
+    Always,
+    /// Quote reserved words and names with characters that are only valid quoted.
+    #[default]
+    WhenNeeded,
+    /// Write names exactly as the model spells them.
+    Never,
+}
+
+impl FromStr for IdentifierQuoting {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().replace('-', "").as_str() {
+            "always" => Ok(IdentifierQuoting::Always),
+            "whenneeded" => Ok(IdentifierQuoting::WhenNeeded),
+            "never" => Ok(IdentifierQuoting::Never),
+            _ => Err(format!("Unknown identifier quoting: {}", s)),
         }
     }
 }
@@ -107,7 +207,42 @@ pub enum OtherSqlOrder {
 pub enum TableOption {
     Data,
     NoExport,
-    Compress,
+    Compress(CompressionLevel),
+    /// A SQLite `strict` table, which rejects values that don't match a column's type.
+    Strict,
+    /// A SQLite `without rowid` table, clustered on its primary key.
+    WithoutRowid,
+}
+
+/// How much SQL Server compresses a table's or index's pages. Other databases compress at
+/// a single level and only note that compression was asked for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Default)]
+pub enum CompressionLevel {
+    Row,
+    #[default]
+    Page,
+}
+
+impl CompressionLevel {
+    pub fn sql(&self) -> &'static str {
+        match self {
+            CompressionLevel::Row => "row",
+            CompressionLevel::Page => "page",
+        }
+    }
+}
+
+impl FromStr for CompressionLevel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "row" => Ok(CompressionLevel::Row),
+            "page" => Ok(CompressionLevel::Page),
+            _ => Err(format!("Unknown compression level: {}", s)),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -146,6 +281,209 @@ impl FromStr for LockEscalation {
     }
 }
 
+/// Per-column ordering of an index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SortOrder {
+    Asc,
+    Desc,
+}
+
+impl SortOrder {
+    pub fn sql(&self) -> &'static str {
+        match self {
+            SortOrder::Asc => "asc",
+            SortOrder::Desc => "desc",
+        }
+    }
+}
+
+impl FromStr for SortOrder {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "asc" => Ok(SortOrder::Asc),
+            "desc" => Ok(SortOrder::Desc),
+            _ => Err(format!("Unknown sort order: {}", s)),
+        }
+    }
+}
+
+/// Where an index column sorts its nulls (`NULLS FIRST`/`NULLS LAST`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NullsOrder {
+    First,
+    Last,
+}
+
+impl NullsOrder {
+    pub fn sql(&self) -> &'static str {
+        match self {
+            NullsOrder::First => "nulls first",
+            NullsOrder::Last => "nulls last",
+        }
+    }
+}
+
+impl FromStr for NullsOrder {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "first" => Ok(NullsOrder::First),
+            "last" => Ok(NullsOrder::Last),
+            _ => Err(format!("Unknown nulls order: {}", s)),
+        }
+    }
+}
+
+/// The access method of an index (Postgres' `USING` clause).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IndexMethod {
+    BTree,
+    Hash,
+    Gin,
+    Gist,
+    Brin,
+}
+
+impl IndexMethod {
+    pub fn sql(&self) -> &'static str {
+        match self {
+            IndexMethod::BTree => "btree",
+            IndexMethod::Hash => "hash",
+            IndexMethod::Gin => "gin",
+            IndexMethod::Gist => "gist",
+            IndexMethod::Brin => "brin",
+        }
+    }
+}
+
+impl FromStr for IndexMethod {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "btree" => Ok(IndexMethod::BTree),
+            "hash" => Ok(IndexMethod::Hash),
+            "gin" => Ok(IndexMethod::Gin),
+            "gist" => Ok(IndexMethod::Gist),
+            "brin" => Ok(IndexMethod::Brin),
+            _ => Err(format!("Unknown index method: {}", s)),
+        }
+    }
+}
+
+/// How a materialized view is brought up to date. `Concurrent` refreshes without locking out
+/// readers, which Postgres only allows on a view with a unique index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum RefreshPolicy {
+    #[default]
+    Manual,
+    Concurrent,
+}
+
+impl FromStr for RefreshPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "manual" => Ok(RefreshPolicy::Manual),
+            "concurrent" => Ok(RefreshPolicy::Concurrent),
+            _ => Err(format!("Unknown refresh policy: {}", s)),
+        }
+    }
+}
+
+/// How PostgreSQL fills a `sequence`/`longSequence` column: through a `serial` type, or as a
+/// standard identity column that is `GENERATED ALWAYS` or `GENERATED BY DEFAULT`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum IdentityMode {
+    #[default]
+    Serial,
+    Always,
+    ByDefault,
+}
+
+impl IdentityMode {
+    /// The `generated ... as identity` clause, or `None` for a serial column.
+    pub fn sql(&self) -> Option<&'static str> {
+        match self {
+            IdentityMode::Serial => None,
+            IdentityMode::Always => Some("generated always as identity"),
+            IdentityMode::ByDefault => Some("generated by default as identity"),
+        }
+    }
+}
+
+impl FromStr for IdentityMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().replace('-', "").as_str() {
+            "serial" => Ok(IdentityMode::Serial),
+            "always" => Ok(IdentityMode::Always),
+            "bydefault" => Ok(IdentityMode::ByDefault),
+            _ => Err(format!("Unknown identity mode: {}", s)),
+        }
+    }
+}
+
+/// How a partitioned table's rows are spread over its partitions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PartitionStrategy {
+    Range,
+    List,
+    Hash,
+}
+
+impl PartitionStrategy {
+    pub fn sql(&self) -> &'static str {
+        match self {
+            PartitionStrategy::Range => "range",
+            PartitionStrategy::List => "list",
+            PartitionStrategy::Hash => "hash",
+        }
+    }
+}
+
+impl FromStr for PartitionStrategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "range" => Ok(PartitionStrategy::Range),
+            "list" => Ok(PartitionStrategy::List),
+            "hash" => Ok(PartitionStrategy::Hash),
+            _ => Err(format!("Unknown partition strategy: {}", s)),
+        }
+    }
+}
+
+/// The span of each range partition generated from a partition template.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PartitionInterval {
+    Day,
+    Month,
+    Year,
+}
+
+impl FromStr for PartitionInterval {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "day" => Ok(PartitionInterval::Day),
+            "month" => Ok(PartitionInterval::Month),
+            "year" => Ok(PartitionInterval::Year),
+            _ => Err(format!("Unknown partition interval: {}", s)),
+        }
+    }
+}
+
+use crate::model::reserved_words::{
+    DUCKDB_RESERVED_WORDS, MYSQL_RESERVED_WORDS, POSTGRES_RESERVED_WORDS, SQLITE_RESERVED_WORDS, SQLSERVER_RESERVED_WORDS,
+};
 use std::str::FromStr;
 
 #[cfg(test)]
@@ -186,10 +524,24 @@ mod tests {
 
     #[test]
     fn table_option_equality() {
-        assert_eq!(TableOption::Compress, TableOption::Compress);
+        assert_eq!(TableOption::Compress(CompressionLevel::Page), TableOption::Compress(CompressionLevel::Page));
+        assert_ne!(TableOption::Compress(CompressionLevel::Row), TableOption::Compress(CompressionLevel::Page));
         assert_ne!(TableOption::Data, TableOption::NoExport);
     }
 
+    #[test]
+    fn index_option_names_parse_case_insensitively() {
+        assert_eq!(IndexMethod::from_str("GIN"), Ok(IndexMethod::Gin));
+        assert_eq!(SortOrder::from_str("Desc"), Ok(SortOrder::Desc));
+        assert_eq!(NullsOrder::from_str("last"), Ok(NullsOrder::Last));
+        assert!(IndexMethod::from_str("spgist").is_err());
+        assert_eq!(RefreshPolicy::from_str("Concurrent"), Ok(RefreshPolicy::Concurrent));
+        assert!(RefreshPolicy::from_str("on commit").is_err());
+        assert_eq!(PartitionStrategy::from_str("RANGE"), Ok(PartitionStrategy::Range));
+        assert_eq!(PartitionInterval::from_str("Month"), Ok(PartitionInterval::Month));
+        assert!(PartitionInterval::from_str("week").is_err());
+    }
+
     #[test]
     fn default_schema_per_database_type() {
         assert_eq!(DatabaseType::Postgresql.default_schema(), Some("public"));
@@ -217,4 +569,71 @@ mod tests {
         assert_eq!(DatabaseType::SqlServer.qualified_name(Some("PUBLIC"), "users"), "dbo.users");
         assert_eq!(DatabaseType::Postgresql.qualified_name(Some("public"), "users"), "public.users");
     }
+
+    #[test]
+    fn quote_identifier_when_needed_quotes_reserved_words_only() {
+        let quoting = IdentifierQuoting::WhenNeeded;
+        assert_eq!(DatabaseType::Postgresql.quote_identifier("order", quoting), "\"order\"");
+        assert_eq!(DatabaseType::SqlServer.quote_identifier("User", quoting), "[User]");
+        assert_eq!(DatabaseType::Sqlite.quote_identifier("group", quoting), "\"group\"");
+        assert_eq!(DatabaseType::Postgresql.quote_identifier("customer_id", quoting), "customer_id");
+    }
+
+    #[test]
+    fn quote_identifier_when_needed_quotes_names_with_invalid_characters() {
+        let quoting = IdentifierQuoting::WhenNeeded;
+        assert_eq!(DatabaseType::Postgresql.quote_identifier("firstName", quoting), "firstName");
+        assert_eq!(DatabaseType::Postgresql.quote_identifier("firstName", IdentifierQuoting::Always), "\"firstName\"");
+        assert_eq!(DatabaseType::SqlServer.quote_identifier("firstName", quoting), "firstName");
+        assert_eq!(DatabaseType::Sqlite.quote_identifier("first name", quoting), "\"first name\"");
+        assert_eq!(DatabaseType::SqlServer.quote_identifier("odd]name", quoting), "[odd]]name]");
+        assert_eq!(DatabaseType::Postgresql.quote_identifier("\"Order\"", quoting), "\"Order\"");
+    }
+
+    #[test]
+    fn mysql_quotes_with_backticks_and_has_no_default_schema() {
+        let quoting = IdentifierQuoting::WhenNeeded;
+        assert_eq!(DatabaseType::MySql.quote_identifier("order", quoting), "`order`");
+        assert_eq!(DatabaseType::MySql.quote_identifier("firstName", quoting), "firstName");
+        assert_eq!(DatabaseType::MySql.quote_identifier("odd`name", quoting), "`odd``name`");
+        assert_eq!(DatabaseType::MySql.qualified_name(None, "users"), "users");
+        assert_eq!(DatabaseType::MySql.qualified_name(Some("public"), "users"), "users");
+        assert_eq!(DatabaseType::MySql.quoted_qualified_name(Some("sales"), "key", quoting), "sales.`key`");
+    }
+
+    #[test]
+    fn duckdb_keeps_case_unquoted_and_defaults_to_the_main_schema() {
+        let quoting = IdentifierQuoting::WhenNeeded;
+        assert_eq!(DatabaseType::DuckDb.quote_identifier("firstName", quoting), "firstName");
+        assert_eq!(DatabaseType::DuckDb.quote_identifier("pivot", quoting), "\"pivot\"");
+        assert_eq!(DatabaseType::DuckDb.qualified_name(None, "users"), "main.users");
+        assert_eq!(DatabaseType::DuckDb.qualified_name(Some("public"), "users"), "main.users");
+        assert_eq!(DatabaseType::DuckDb.qualified_name(Some("sales"), "users"), "sales.users");
+    }
+
+    #[test]
+    fn quote_identifier_honours_always_and_never() {
+        assert_eq!(DatabaseType::Postgresql.quote_identifier("users", IdentifierQuoting::Always), "\"users\"");
+        assert_eq!(DatabaseType::Postgresql.quote_identifier("order", IdentifierQuoting::Never), "order");
+    }
+
+    #[test]
+    fn quoted_qualified_name_quotes_schema_and_name_separately() {
+        let quoting = IdentifierQuoting::WhenNeeded;
+        assert_eq!(DatabaseType::Postgresql.quoted_qualified_name(None, "order", quoting), "public.\"order\"");
+        assert_eq!(DatabaseType::SqlServer.quoted_qualified_name(Some("public"), "user", quoting), "dbo.[user]");
+        assert_eq!(DatabaseType::Sqlite.quoted_qualified_name(None, "order", quoting), "\"order\"");
+        assert_eq!(
+            DatabaseType::SqlServer.quoted_qualified_name(Some("sales"), "orders", IdentifierQuoting::Always),
+            "[sales].[orders]"
+        );
+    }
+
+    #[test]
+    fn identifier_quoting_from_str() {
+        assert_eq!("always".parse::<IdentifierQuoting>(), Ok(IdentifierQuoting::Always));
+        assert_eq!("when-needed".parse::<IdentifierQuoting>(), Ok(IdentifierQuoting::WhenNeeded));
+        assert_eq!("Never".parse::<IdentifierQuoting>(), Ok(IdentifierQuoting::Never));
+        assert!("sometimes".parse::<IdentifierQuoting>().is_err());
+    }
 }
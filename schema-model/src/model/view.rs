@@ -1,4 +1,5 @@
-use crate::model::types::DatabaseType;
+use crate::model::key::Key;
+use crate::model::types::{DatabaseType, RefreshPolicy};
 use std::fmt;
 
 #[derive(Debug, Clone)]
@@ -7,6 +8,9 @@ pub struct View {
     name: String,
     sql: String,
     database_type: Option<DatabaseType>,
+    materialized: bool,
+    refresh_policy: RefreshPolicy,
+    indexes: Vec<Key>,
 }
 impl View {
     pub fn new<S: Into<String>>(
@@ -20,6 +24,27 @@ impl View {
             name: name.into(),
             sql: sql.into(),
             database_type,
+            materialized: false,
+            refresh_policy: RefreshPolicy::default(),
+            indexes: Vec::new(),
+        }
+    }
+
+    /// A view whose result is stored and refreshed according to `refresh_policy`, with its
+    /// own indexes.
+    pub fn new_materialized<S: Into<String>>(
+        schema_name: Option<S>,
+        name: S,
+        sql: S,
+        database_type: Option<DatabaseType>,
+        refresh_policy: RefreshPolicy,
+        indexes: Vec<Key>,
+    ) -> Self {
+        Self {
+            materialized: true,
+            refresh_policy,
+            indexes,
+            ..Self::new(schema_name, name, sql, database_type)
         }
     }
     pub fn schema_name(&self) -> Option<&str> {
@@ -38,6 +63,37 @@ impl View {
         self.database_type
     }
 
+    pub fn is_materialized(&self) -> bool {
+        self.materialized
+    }
+
+    pub fn refresh_policy(&self) -> RefreshPolicy {
+        self.refresh_policy
+    }
+
+    pub fn indexes(&self) -> &[Key] {
+        &self.indexes
+    }
+
+    /// Whether `other` would produce the same database object: same query, kind, refresh
+    /// policy and indexes. The query text is compared after trimming surrounding whitespace.
+    pub fn same_definition(&self, other: &View) -> bool {
+        self.sql.trim() == other.sql.trim()
+            && self.materialized == other.materialized
+            && self.refresh_policy == other.refresh_policy
+            && self.indexes.len() == other.indexes.len()
+            && self
+                .indexes
+                .iter()
+                .zip(other.indexes.iter())
+                .all(|(a, b)| {
+                    a.is_unique() == b.is_unique()
+                        && a.columns() == b.columns()
+                        && a.method() == b.method()
+                        && a.filter() == b.filter()
+                })
+    }
+
     pub fn fully_qualified_view_name(&self, database_type: DatabaseType) -> String {
         database_type.qualified_name(self.schema_name(), self.name())
     }
@@ -64,6 +120,32 @@ mod tests {
         assert_eq!(v.sql(), "select *");
         assert_eq!(v.database_type().unwrap(), DatabaseType::Postgresql);
         assert_eq!(format!("{}", v), "s.v1");
+        assert!(!v.is_materialized());
+        assert!(v.indexes().is_empty());
+    }
+
+    #[test]
+    fn same_definition_compares_query_kind_and_indexes() {
+        use crate::model::key::KeyColumn;
+        use crate::model::types::KeyType;
+
+        let index = Key::new(KeyType::Index, vec![KeyColumn::new("customer_id")]);
+        let mv = View::new_materialized(None, "totals", "select 1", None, RefreshPolicy::Manual, vec![index.clone()]);
+        let reformatted = View::new_materialized(None, "totals", " select 1\n", None, RefreshPolicy::Manual, vec![index]);
+        assert!(mv.same_definition(&reformatted));
+
+        let plain = View::new(None, "totals", "select 1", None);
+        assert!(!mv.same_definition(&plain));
+
+        let other_index = View::new_materialized(
+            None,
+            "totals",
+            "select 1",
+            None,
+            RefreshPolicy::Manual,
+            vec![Key::new(KeyType::Index, vec![KeyColumn::new("total")])],
+        );
+        assert!(!mv.same_definition(&other_index));
     }
 
     #[test]
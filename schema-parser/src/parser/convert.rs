@@ -1,13 +1,15 @@
 use super::nodes::*;
-use crate::parser::table_parser::parse_table;
-use schema_model::builder::SchemaBuilder;
+use crate::parser::table_parser::{parse_index, parse_table};
+use schema_model::builder::{SchemaBuilder, SequenceBuilder};
 use schema_model::model::database_model::DatabaseModel;
 use schema_model::model::relation::Relation;
 use schema_model::model::schema::Schema;
+use schema_model::model::sequence::Sequence;
 use schema_model::model::types::{
-    BooleanMode, DatabaseType, ForeignKeyMode, OtherSqlOrder,
+    BooleanMode, DatabaseType, ForeignKeyMode, OtherSqlOrder, RefreshPolicy,
 };
 use schema_model::model::view::View;
+use std::str::FromStr;
 use schema_model::model::{
     aggregation::AggregationFrequency,
     enum_type::{EnumType, EnumValue},
@@ -61,16 +63,7 @@ fn default_schema(database: &DatabaseXml) -> Result<Option<Schema>, String> {
     }
 
     for view_xml in database.views.iter() {
-        let database_type = optional_database_type(
-            view_xml.database_type.as_deref(),
-            &format!("view '{}'", view_xml.name),
-        )?;
-        schema_builder = schema_builder.add_view(View::new(
-            None,
-            &view_xml.name,
-            &view_xml.sql,
-            database_type,
-        ));
+        schema_builder = schema_builder.add_view(view(view_xml, None, &format!("view '{}'", view_xml.name))?);
     }
 
     for enum_xml in database.enums.iter() {
@@ -82,6 +75,10 @@ fn default_schema(database: &DatabaseXml) -> Result<Option<Schema>, String> {
         schema_builder = schema_builder.add_enum_type(EnumType::new(&enum_xml.name, evs));
     }
 
+    for sequence_xml in database.sequences.iter() {
+        schema_builder = schema_builder.add_sequence(sequence(sequence_xml, None));
+    }
+
     for function_xml in database.functions.iter() {
         let mut functions: Vec<Function> = Vec::new();
         for vendor_sql_xml in function_xml.sql.iter() {
@@ -135,6 +132,7 @@ fn default_schema(database: &DatabaseXml) -> Result<Option<Schema>, String> {
     if !root_schema.tables().is_empty()
         || !database.views.is_empty()
         || !database.enums.is_empty()
+        || !database.sequences.is_empty()
         || !database.functions.is_empty()
         || !database.procedures.is_empty()
         || !database.other_sql.is_empty()
@@ -157,16 +155,11 @@ fn sub_schema(schema_xml: &SchemaXml) -> Result<Schema, String> {
     }
 
     for view_xml in schema_xml.views.iter() {
-        let database_type = optional_database_type(
-            view_xml.database_type.as_deref(),
-            &format!("view '{}.{}'", schema_xml.name, view_xml.name),
-        )?;
-        schema_builder = schema_builder.add_view(View::new(
+        schema_builder = schema_builder.add_view(view(
+            view_xml,
             Some(&schema_xml.name),
-            &view_xml.name,
-            &view_xml.sql,
-            database_type,
-        ));
+            &format!("view '{}.{}'", schema_xml.name, view_xml.name),
+        )?);
     }
 
     for enum_xml in schema_xml.enums.iter() {
@@ -178,6 +171,10 @@ fn sub_schema(schema_xml: &SchemaXml) -> Result<Schema, String> {
         schema_builder = schema_builder.add_enum_type(EnumType::new(&enum_xml.name, enum_values));
     }
 
+    for sequence_xml in schema_xml.sequences.iter() {
+        schema_builder = schema_builder.add_sequence(sequence(sequence_xml, Some(&schema_xml.name)));
+    }
+
     for function_xml in schema_xml.functions.iter() {
         let mut functions: Vec<Function> = Vec::new();
         for vendor_sql_xml in function_xml.sql.iter() {
@@ -238,6 +235,8 @@ pub(crate) fn str_to_database_type(s: Option<&str>) -> Option<DatabaseType> {
         "postgresql" => Some(DatabaseType::Postgresql),
         "sqlite" => Some(DatabaseType::Sqlite),
         "sqlserver" | "mssql" => Some(DatabaseType::SqlServer),
+        "mysql" | "mariadb" => Some(DatabaseType::MySql),
+        "duckdb" => Some(DatabaseType::DuckDb),
         _ => None,
     })
 }
@@ -266,6 +265,48 @@ pub(crate) fn optional_database_type(value: Option<&str>, context: &str) -> Resu
     }
 }
 
+fn view(view_xml: &ViewXml, schema_name: Option<&str>, context: &str) -> Result<View, String> {
+    let database_type = optional_database_type(view_xml.database_type.as_deref(), context)?;
+    if !view_xml.materialized.unwrap_or(false) {
+        if view_xml.refresh.is_some() || !view_xml.indexes.is_empty() {
+            return Err(format!("{context}: refresh and <index> are only allowed on a materialized view"));
+        }
+        return Ok(View::new(schema_name, &view_xml.name, &view_xml.sql, database_type));
+    }
+
+    let refresh_policy = view_xml
+        .refresh
+        .as_deref()
+        .map(RefreshPolicy::from_str)
+        .transpose()
+        .map_err(|e| format!("{context}: {e}"))?
+        .unwrap_or_default();
+    let indexes = view_xml
+        .indexes
+        .iter()
+        .map(|index_xml| parse_index(index_xml, context))
+        .collect::<Result<Vec<_>, String>>()?;
+    Ok(View::new_materialized(
+        schema_name,
+        &view_xml.name,
+        &view_xml.sql,
+        database_type,
+        refresh_policy,
+        indexes,
+    ))
+}
+
+fn sequence(sequence_xml: &SequenceXml, schema_name: Option<&str>) -> Sequence {
+    SequenceBuilder::new(schema_name, &sequence_xml.name)
+        .start(sequence_xml.start)
+        .increment(sequence_xml.increment)
+        .min_value(sequence_xml.min_value)
+        .max_value(sequence_xml.max_value)
+        .cycle(sequence_xml.cycle.unwrap_or(false))
+        .cache(sequence_xml.cache)
+        .build()
+}
+
 fn other_sql_order(o: &OtherSqlOrderXml) -> Option<OtherSqlOrder> {
     match o {
         OtherSqlOrderXml::Top => Some(OtherSqlOrder::Top),
@@ -307,11 +348,10 @@ fn reverse_relations(database_model: &mut DatabaseModel) -> Result<(), String> {
                 parent_table_parts.0,
                 parent_table_parts.1.to_string(),
                 qualified_from_table_name.clone(),
-                Relation::new(
+                Relation::new_composite(
                     relation.to_table_name(),
-                    relation.to_column_name(),
                     qualified_from_table_name.as_str(),
-                    relation.from_column_name(),
+                    relation.columns().to_vec(),
                     relation.relation_type(),
                     false,
                 ),
@@ -1,4 +1,5 @@
 mod convert;
+#[allow(clippy::module_inception)]
 pub mod parser;
 pub mod roxml_parser;
 mod nodes;
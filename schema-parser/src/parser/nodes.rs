@@ -20,6 +20,10 @@ pub struct DatabaseXml {
     #[serde(rename = "enum")]
     pub enums: Vec<EnumXml>,
 
+    #[serde(default)]
+    #[serde(rename = "sequence")]
+    pub sequences: Vec<SequenceXml>,
+
     #[serde(default)]
     #[serde(rename = "view")]
     pub views: Vec<ViewXml>,
@@ -57,6 +61,10 @@ pub struct SchemaXml {
     #[serde(rename = "enum")]
     pub enums: Vec<EnumXml>,
 
+    #[serde(default)]
+    #[serde(rename = "sequence")]
+    pub sequences: Vec<SequenceXml>,
+
     #[serde(default)]
     #[serde(rename = "view")]
     pub views: Vec<ViewXml>,
@@ -85,9 +93,15 @@ pub struct TableXml {
     #[serde(rename = "@exportDataColumn")]
     pub export_data_column: Option<String>,
     #[serde(rename = "@compress")]
-    pub compress: Option<bool>,
+    pub compress: Option<String>,
     #[serde(rename = "@lockEscalation")]
     pub lock_escalation: Option<String>,
+    #[serde(rename = "@strict")]
+    pub strict: Option<bool>,
+    #[serde(rename = "@withoutRowid")]
+    pub without_rowid: Option<bool>,
+    #[serde(rename = "@previousName")]
+    pub previous_name: Option<String>,
     #[serde(rename = "columns")]
     pub columns: Option<ColumnsXml>,
     #[serde(rename = "keys")]
@@ -102,6 +116,55 @@ pub struct TableXml {
     pub aggregations: Option<AggregationsXml>,
     #[serde(rename = "initialData")]
     pub initial_data: Option<InitialDataXml>,
+    #[serde(rename = "partitionBy")]
+    pub partition_by: Option<PartitionByXml>,
+    #[serde(rename = "description")]
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PartitionByXml {
+    #[serde(rename = "@strategy")]
+    pub strategy: String,
+    #[serde(rename = "@columns")]
+    pub columns: String,
+    #[serde(rename = "partition")]
+    pub partitions: Vec<PartitionXml>,
+    #[serde(rename = "template")]
+    pub template: Option<PartitionTemplateXml>,
+}
+
+/// One declared partition; which bound attributes apply depends on the strategy:
+/// `from`/`to` for range, `values` for list, `modulus`/`remainder` for hash, or
+/// `default="true"`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PartitionXml {
+    #[serde(rename = "@name")]
+    pub name: String,
+    #[serde(rename = "@from")]
+    pub from: Option<String>,
+    #[serde(rename = "@to")]
+    pub to: Option<String>,
+    #[serde(rename = "@values")]
+    pub values: Option<String>,
+    #[serde(rename = "@modulus")]
+    pub modulus: Option<u32>,
+    #[serde(rename = "@remainder")]
+    pub remainder: Option<u32>,
+    #[serde(rename = "@default")]
+    pub default: Option<bool>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PartitionTemplateXml {
+    #[serde(rename = "@interval")]
+    pub interval: Option<String>,
+    #[serde(rename = "@start")]
+    pub start: Option<String>,
+    #[serde(rename = "@count")]
+    pub count: Option<u32>,
+    #[serde(rename = "@modulus")]
+    pub modulus: Option<u32>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -130,12 +193,24 @@ pub struct ColumnXml {
     pub enum_type: Option<String>,
     #[serde(rename = "@elementType")]
     pub element_type: Option<String>,
+    #[serde(rename = "@sequence")]
+    pub sequence: Option<String>,
+    #[serde(rename = "@identity")]
+    pub identity: Option<String>,
+    #[serde(rename = "@identityStart")]
+    pub identity_start: Option<i64>,
+    #[serde(rename = "@identityIncrement")]
+    pub identity_increment: Option<i64>,
     #[serde(rename = "@minValue")]
     pub min_value: Option<f64>,
     #[serde(rename = "@maxValue")]
     pub max_value: Option<f64>,
+    #[serde(rename = "@previousName")]
+    pub previous_name: Option<String>,
     #[serde(rename = "check")]
     pub check: Option<CheckXml>,
+    #[serde(rename = "description")]
+    pub description: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -165,15 +240,27 @@ pub struct IndexXml {
     #[serde(rename = "@include")]
     pub include: Option<String>,
     #[serde(rename = "@compress")]
-    pub compress: Option<bool>,
+    pub compress: Option<String>,
     #[serde(rename = "@unique")]
     pub unique: Option<bool>,
+    #[serde(rename = "@method")]
+    pub method: Option<String>,
+    #[serde(rename = "@where")]
+    pub where_clause: Option<String>,
+    #[serde(rename = "@previousName")]
+    pub previous_name: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct KeyColumnXml {
     #[serde(rename = "@name")]
-    pub name: String,
+    pub name: Option<String>,
+    #[serde(rename = "@expression")]
+    pub expression: Option<String>,
+    #[serde(rename = "@order")]
+    pub order: Option<String>,
+    #[serde(rename = "@nulls")]
+    pub nulls: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -203,6 +290,12 @@ pub struct ViewXml {
     pub name: String,
     #[serde(rename = "@databaseType")]
     pub database_type: Option<String>,
+    #[serde(rename = "@materialized")]
+    pub materialized: Option<bool>,
+    #[serde(rename = "@refresh")]
+    pub refresh: Option<String>,
+    #[serde(rename = "index")]
+    pub indexes: Vec<IndexXml>,
     #[serde(rename = "$text")]
     pub sql: String,
 }
@@ -257,6 +350,24 @@ pub struct EnumXml {
     pub value: Vec<EnumValueXml>,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SequenceXml {
+    #[serde(rename = "@name")]
+    pub name: String,
+    #[serde(rename = "@start")]
+    pub start: Option<i64>,
+    #[serde(rename = "@increment")]
+    pub increment: Option<i64>,
+    #[serde(rename = "@minValue")]
+    pub min_value: Option<i64>,
+    #[serde(rename = "@maxValue")]
+    pub max_value: Option<i64>,
+    #[serde(rename = "@cycle")]
+    pub cycle: Option<bool>,
+    #[serde(rename = "@cache")]
+    pub cache: Option<i64>,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct EnumValueXml {
     #[serde(rename = "@name")]
@@ -301,6 +412,8 @@ pub struct ConstraintXml {
     pub name: String,
     #[serde(rename = "@databaseType")]
     pub database_type: Option<String>,
+    #[serde(rename = "@previousName")]
+    pub previous_name: Option<String>,
     #[serde(rename = "$text")]
     pub sql: String,
 }
@@ -15,6 +15,8 @@ pub fn parse_database_xml(xml: &str) -> Result<DatabaseModel, String> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use schema_model::model::partition::PartitionBound;
+    use schema_model::model::types::{CompressionLevel, IdentityMode, IndexMethod, NullsOrder, PartitionStrategy, RefreshPolicy, SortOrder, TableOption};
     use std::fs;
 
     #[test]
@@ -29,7 +31,7 @@ mod tests {
         assert!(default_schema.tables().len() >= 3);
 
         let parent = default_schema.get_table("ParentTable");
-        assert_eq!(parent.columns().len(), 4);
+        assert_eq!(parent.columns().len(), 8);
         assert!(parent.primary_key().is_some());
 
         let child = default_schema.get_table("ChildTable");
@@ -109,6 +111,393 @@ mod tests {
         assert_eq!(reverse_relations[0].from_table_name(), "sales.OrderItem");
     }
 
+    #[test]
+    fn composite_relation_pairs_columns_in_order() {
+        let xml = wrap(
+            r#"
+            <table name="Customer">
+                <columns>
+                    <column name="TenantId" type="int" required="true"/>
+                    <column name="Id" type="int" required="true"/>
+                </columns>
+                <keys>
+                    <primary>
+                        <column name="TenantId"/>
+                        <column name="Id"/>
+                    </primary>
+                </keys>
+            </table>
+            <table name="Invoice">
+                <columns>
+                    <column name="TenantId" type="int" required="true"/>
+                    <column name="CustomerId" type="int" required="true"/>
+                </columns>
+                <relations>
+                    <relation src="TenantId, CustomerId" table="Customer" column="TenantId, Id" type="enforce"/>
+                </relations>
+            </table>
+            "#,
+        );
+
+        let database = parse_database_xml(&xml).expect("parse ok");
+
+        let default_schema = &database.schemas()[0];
+        let relation = &default_schema.get_table("Invoice").relations()[0];
+        assert_eq!(relation.from_column_names(), vec!["TenantId", "CustomerId"]);
+        assert_eq!(relation.to_column_names(), vec!["TenantId", "Id"]);
+
+        let reverse_relations = default_schema.get_table("Customer").reverse_relations();
+        assert_eq!(reverse_relations[0].from_columns_as_string(), "TenantId,CustomerId");
+    }
+
+    #[test]
+    fn composite_relation_with_mismatched_column_counts_returns_error() {
+        let xml = wrap(
+            r#"
+            <table name="Customer">
+                <columns>
+                    <column name="TenantId" type="int" required="true"/>
+                    <column name="Id" type="int" required="true"/>
+                </columns>
+            </table>
+            <table name="Invoice">
+                <columns>
+                    <column name="CustomerId" type="int" required="true"/>
+                </columns>
+                <relations>
+                    <relation src="CustomerId" table="Customer" column="TenantId,Id" type="enforce"/>
+                </relations>
+            </table>
+            "#,
+        );
+
+        let result = parse_database_xml(&xml);
+
+        assert!(result.is_err());
+        assert!(result.unwrap_err().contains("Invoice"));
+    }
+
+    #[test]
+    fn table_and_column_descriptions_are_carried_into_the_model() {
+        let xml = wrap(
+            r#"
+            <table name="Customer" description="People who buy things">
+                <columns>
+                    <column name="Id" type="int" required="true">
+                        <description>Surrogate key</description>
+                    </column>
+                    <column name="Name" type="varchar" length="50"/>
+                </columns>
+            </table>
+            "#,
+        );
+
+        let database = parse_database_xml(&xml).expect("parse ok");
+
+        let customer = database.schemas()[0].get_table("Customer");
+        assert_eq!(customer.description(), Some("People who buy things"));
+        assert_eq!(customer.column("Id").description(), Some("Surrogate key"));
+        assert_eq!(customer.column("Name").description(), None);
+    }
+
+    #[test]
+    fn index_with_method_expression_ordering_and_predicate() {
+        let xml = wrap(
+            r#"
+            <table name="Account">
+                <columns>
+                    <column name="Email" type="varchar" length="100"/>
+                    <column name="CreatedAt" type="timestamp"/>
+                    <column name="DeletedAt" type="timestamp"/>
+                </columns>
+                <keys>
+                    <index method="btree" unique="true" where="DeletedAt is null">
+                        <column expression="lower(Email)"/>
+                        <column name="CreatedAt" order="desc" nulls="last"/>
+                    </index>
+                </keys>
+            </table>
+            "#,
+        );
+
+        let database = parse_database_xml(&xml).expect("parse ok");
+
+        let index = &database.schemas()[0].get_table("Account").indexes()[0];
+        assert_eq!(index.method(), Some(IndexMethod::BTree));
+        assert_eq!(index.filter(), Some("DeletedAt is null"));
+        assert!(index.columns()[0].is_expression());
+        assert_eq!(index.columns()[0].name(), "lower(Email)");
+        assert_eq!(index.columns()[1].sort_order(), Some(SortOrder::Desc));
+        assert_eq!(index.columns()[1].nulls_order(), Some(NullsOrder::Last));
+    }
+
+    #[test]
+    fn compress_picks_the_default_level_or_the_one_named() {
+        let xml = wrap(
+            r#"
+            <table name="Ledger" compress="row">
+                <columns>
+                    <column name="Id" type="int"/>
+                    <column name="Code" type="varchar" length="10"/>
+                </columns>
+                <keys>
+                    <index compress="true">
+                        <column name="Code"/>
+                    </index>
+                </keys>
+            </table>
+            "#,
+        );
+
+        let database = parse_database_xml(&xml).expect("parse ok");
+
+        let table = database.schemas()[0].get_table("Ledger");
+        assert_eq!(table.compression(), Some(CompressionLevel::Row));
+        assert_eq!(table.indexes()[0].compression(), Some(CompressionLevel::Page));
+    }
+
+    #[test]
+    fn strict_and_without_rowid_become_table_options() {
+        let xml = wrap(
+            r#"
+            <table name="Ledger" strict="true" withoutRowid="true">
+                <columns>
+                    <column name="Id" type="int"/>
+                </columns>
+            </table>
+            <table name="Journal" strict="false">
+                <columns>
+                    <column name="Id" type="int"/>
+                </columns>
+            </table>
+            "#,
+        );
+
+        let database = parse_database_xml(&xml).expect("parse ok");
+
+        let ledger = database.schemas()[0].get_table("Ledger");
+        assert!(ledger.has_option(TableOption::Strict));
+        assert!(ledger.has_option(TableOption::WithoutRowid));
+        assert!(database.schemas()[0].get_table("Journal").options().is_empty());
+    }
+
+    #[test]
+    fn previous_names_are_carried_into_the_model() {
+        let xml = wrap(
+            r#"
+            <table name="Customer" previousName="Client">
+                <columns>
+                    <column name="FullName" type="varchar" length="50" previousName="Name"/>
+                </columns>
+                <keys>
+                    <index previousName="idx_Client_Name">
+                        <column name="FullName"/>
+                    </index>
+                </keys>
+                <constraints>
+                    <constraint name="ck_customer_name" databaseType="postgresql" previousName="ck_client_name">FullName &lt;&gt; ''</constraint>
+                </constraints>
+            </table>
+            "#,
+        );
+
+        let database = parse_database_xml(&xml).expect("parse ok");
+
+        let customer = database.schemas()[0].get_table("Customer");
+        assert_eq!(customer.previous_name(), Some("Client"));
+        assert_eq!(customer.column("FullName").previous_name(), Some("Name"));
+        assert_eq!(customer.indexes()[0].previous_name(), Some("idx_Client_Name"));
+        assert_eq!(customer.constraints()[0].previous_name(), Some("ck_client_name"));
+    }
+
+    #[test]
+    fn unrecognized_compression_level_returns_error() {
+        let xml = wrap(
+            r#"
+            <table name="Ledger" compress="zip">
+                <columns>
+                    <column name="Id" type="int"/>
+                </columns>
+            </table>
+            "#,
+        );
+
+        let result = parse_database_xml(&xml);
+
+        assert!(result.is_err());
+        assert!(result.unwrap_err().contains("zip"));
+    }
+
+    #[test]
+    fn unrecognized_index_method_returns_error() {
+        let xml = wrap(
+            r#"
+            <table name="Doc">
+                <columns>
+                    <column name="Body" type="json"/>
+                </columns>
+                <keys>
+                    <index method="rtree">
+                        <column name="Body"/>
+                    </index>
+                </keys>
+            </table>
+            "#,
+        );
+
+        let result = parse_database_xml(&xml);
+
+        assert!(result.is_err());
+        assert!(result.unwrap_err().contains("rtree"));
+    }
+
+    #[test]
+    fn sequence_and_the_columns_drawing_from_it() {
+        let xml = wrap(
+            r#"
+            <sequence name="document_no" start="1000" increment="10" minValue="1000" maxValue="99999990" cycle="true" cache="20"/>
+            <table name="Invoice">
+                <columns>
+                    <column name="InvoiceNo" type="long" sequence="document_no"/>
+                </columns>
+            </table>
+            "#,
+        );
+
+        let database = parse_database_xml(&xml).expect("parse ok");
+
+        let schema = &database.schemas()[0];
+        let sequence = schema.get_optional_sequence("document_no").expect("sequence");
+        assert_eq!(sequence.start(), Some(1000));
+        assert_eq!(sequence.increment(), Some(10));
+        assert_eq!(sequence.min_value(), Some(1000));
+        assert_eq!(sequence.max_value(), Some(99999990));
+        assert!(sequence.is_cycle());
+        assert_eq!(sequence.cache(), Some(20));
+        assert_eq!(schema.get_table("Invoice").column("InvoiceNo").sequence(), Some("document_no"));
+    }
+
+    #[test]
+    fn identity_settings_on_sequence_columns() {
+        let xml = wrap(
+            r#"
+            <table name="Orders">
+                <columns>
+                    <column name="Id" type="longSequence" identity="byDefault" identityStart="1000" identityIncrement="5"/>
+                    <column name="LegacyId" type="sequence" identity="serial"/>
+                </columns>
+            </table>
+            "#,
+        );
+
+        let database = parse_database_xml(&xml).expect("parse ok");
+
+        let table = database.schemas()[0].get_table("Orders");
+        assert_eq!(table.column("Id").identity_mode(), Some(IdentityMode::ByDefault));
+        assert_eq!(table.column("Id").identity_start(), Some(1000));
+        assert_eq!(table.column("Id").identity_increment(), Some(5));
+        assert_eq!(table.column("LegacyId").identity_mode(), Some(IdentityMode::Serial));
+
+        let bad = wrap(r#"<table name="T"><columns><column name="Id" type="sequence" identity="sometimes"/></columns></table>"#);
+        let err = parse_database_xml(&bad).unwrap_err();
+        assert!(err.contains("Unknown identity mode"), "{}", err);
+    }
+
+    #[test]
+    fn non_numeric_sequence_option_returns_error() {
+        let xml = wrap(r#"<sequence name="document_no" start="one"/>"#);
+
+        let result = parse_database_xml(&xml);
+
+        assert!(result.is_err());
+        assert!(result.unwrap_err().contains("start"));
+    }
+
+    #[test]
+    fn materialized_view_with_refresh_policy_and_indexes() {
+        let xml = wrap(
+            r#"
+            <view name="order_totals" materialized="true" refresh="concurrent">
+                select customer_id, sum(amount) as total from orders group by customer_id
+                <index unique="true"><column name="customer_id"/></index>
+                <index><column name="total" order="desc"/></index>
+            </view>
+            "#,
+        );
+
+        let database = parse_database_xml(&xml).expect("parse ok");
+
+        let view = &database.schemas()[0].all_views()[0];
+        assert!(view.is_materialized());
+        assert_eq!(view.refresh_policy(), RefreshPolicy::Concurrent);
+        assert_eq!(view.sql(), "select customer_id, sum(amount) as total from orders group by customer_id");
+        assert_eq!(view.indexes().len(), 2);
+        assert!(view.indexes()[0].is_unique());
+        assert_eq!(view.indexes()[1].columns()[0].sort_order(), Some(SortOrder::Desc));
+    }
+
+    #[test]
+    fn index_on_a_plain_view_returns_error() {
+        let xml = wrap(r#"<view name="v">select 1 as id<index><column name="id"/></index></view>"#);
+
+        let result = parse_database_xml(&xml);
+
+        assert!(result.unwrap_err().contains("only allowed on a materialized view"));
+    }
+
+    #[test]
+    fn range_partitioned_table_with_declared_partitions_and_template() {
+        let xml = wrap(
+            r#"
+            <table name="events">
+                <columns>
+                    <column name="id" type="long" required="true"/>
+                    <column name="created_at" type="timestamp" required="true"/>
+                </columns>
+                <partitionBy strategy="range" columns="created_at">
+                    <partition name="events_archive" from="minvalue" to="2024-01-01"/>
+                    <partition name="events_default" default="true"/>
+                    <template interval="month" start="2024-01-01" count="12"/>
+                </partitionBy>
+            </table>
+            "#,
+        );
+
+        let database = parse_database_xml(&xml).expect("parse ok");
+
+        let table = database.schemas()[0].get_table("events");
+        let partitioning = table.partitioning().expect("partitioned");
+        assert_eq!(partitioning.strategy(), PartitionStrategy::Range);
+        assert_eq!(partitioning.columns(), ["created_at"]);
+        assert_eq!(
+            partitioning.partitions()[0].bound(),
+            &PartitionBound::Range {
+                from: vec!["minvalue".to_string()],
+                to: vec!["2024-01-01".to_string()],
+            }
+        );
+        assert_eq!(partitioning.partitions()[1].bound(), &PartitionBound::Default);
+        assert_eq!(partitioning.all_partitions("events").len(), 14);
+    }
+
+    #[test]
+    fn partition_without_a_bound_returns_error() {
+        let xml = wrap(
+            r#"
+            <table name="events">
+                <columns><column name="region" type="varchar" length="2"/></columns>
+                <partitionBy strategy="list" columns="region">
+                    <partition name="events_us"/>
+                </partitionBy>
+            </table>
+            "#,
+        );
+
+        let result = parse_database_xml(&xml);
+
+        assert!(result.unwrap_err().contains("partition 'events_us' needs"));
+    }
+
     #[test]
     fn function_with_unrecognized_database_type_returns_error_instead_of_vanishing() {
         let xml = wrap(
@@ -20,6 +20,7 @@ fn parse_database_node(node: Node) -> Result<DatabaseXml, String> {
 
     let mut tables = Vec::new();
     let mut enums = Vec::new();
+    let mut sequences = Vec::new();
     let mut views = Vec::new();
     let mut functions = Vec::new();
     let mut procedures = Vec::new();
@@ -30,6 +31,7 @@ fn parse_database_node(node: Node) -> Result<DatabaseXml, String> {
         match child.tag_name().name() {
             "table" if child.has_tag_name((NS, "table")) => tables.push(parse_table_node(child)?),
             "enum" if child.has_tag_name((NS, "enum")) => enums.push(parse_enum_node(child)?),
+            "sequence" if child.has_tag_name((NS, "sequence")) => sequences.push(parse_sequence_node(child)?),
             "view" if child.has_tag_name((NS, "view")) => views.push(parse_view_node(child)?),
             "function" if child.has_tag_name((NS, "function")) => functions.push(parse_function_node(child)?),
             "procedure" if child.has_tag_name((NS, "procedure")) => procedures.push(parse_procedure_node(child)?),
@@ -45,6 +47,7 @@ fn parse_database_node(node: Node) -> Result<DatabaseXml, String> {
         case_sensitive_text,
         tables,
         enums,
+        sequences,
         views,
         functions,
         procedures,
@@ -59,6 +62,7 @@ fn parse_schema_node(node: Node) -> Result<SchemaXml, String> {
 
     let mut tables = Vec::new();
     let mut enums = Vec::new();
+    let mut sequences = Vec::new();
     let mut views = Vec::new();
     let mut functions = Vec::new();
     let mut procedures = Vec::new();
@@ -68,6 +72,7 @@ fn parse_schema_node(node: Node) -> Result<SchemaXml, String> {
         match child.tag_name().name() {
             "table" if child.has_tag_name((NS, "table")) => tables.push(parse_table_node(child)?),
             "enum" if child.has_tag_name((NS, "enum")) => enums.push(parse_enum_node(child)?),
+            "sequence" if child.has_tag_name((NS, "sequence")) => sequences.push(parse_sequence_node(child)?),
             "view" if child.has_tag_name((NS, "view")) => views.push(parse_view_node(child)?),
             "function" if child.has_tag_name((NS, "function")) => functions.push(parse_function_node(child)?),
             "procedure" if child.has_tag_name((NS, "procedure")) => procedures.push(parse_procedure_node(child)?),
@@ -76,7 +81,7 @@ fn parse_schema_node(node: Node) -> Result<SchemaXml, String> {
         }
     }
 
-    Ok(SchemaXml { name, case_sensitive_text, tables, enums, views, functions, procedures, other_sql })
+    Ok(SchemaXml { name, case_sensitive_text, tables, enums, sequences, views, functions, procedures, other_sql })
 }
 
 fn parse_table_node(node: Node) -> Result<TableXml, String> {
@@ -84,8 +89,12 @@ fn parse_table_node(node: Node) -> Result<TableXml, String> {
     let data_opt = attr_bool(node, "data");
     let no_export = attr_bool(node, "noExport");
     let export_data_column = attr_string(node, "exportDataColumn");
-    let compress = attr_bool(node, "compress");
+    let compress = attr_string(node, "compress");
     let lock_escalation = attr_string(node, "lockEscalation");
+    let strict = attr_bool(node, "strict");
+    let without_rowid = attr_bool(node, "withoutRowid");
+    let previous_name = attr_string(node, "previousName");
+    let description = description(node);
 
     let mut columns: Option<ColumnsXml> = None;
     let mut keys: Option<KeysXml> = None;
@@ -94,6 +103,7 @@ fn parse_table_node(node: Node) -> Result<TableXml, String> {
     let mut constraints: Option<ConstraintsXml> = None;
     let mut aggregations: Option<AggregationsXml> = None;
     let mut initial_data: Option<InitialDataXml> = None;
+    let mut partition_by: Option<PartitionByXml> = None;
 
     for child in node.children().filter(|n| n.is_element()) {
         if child.has_tag_name((NS, "columns")) {
@@ -110,6 +120,8 @@ fn parse_table_node(node: Node) -> Result<TableXml, String> {
             aggregations = Some(parse_aggregations_node(child)?);
         } else if child.has_tag_name((NS, "initialData")) {
             initial_data = Some(parse_initial_data_node(child));
+        } else if child.has_tag_name((NS, "partitionBy")) {
+            partition_by = Some(parse_partition_by_node(child)?);
         }
     }
 
@@ -120,6 +132,9 @@ fn parse_table_node(node: Node) -> Result<TableXml, String> {
         export_data_column,
         compress,
         lock_escalation,
+        strict,
+        without_rowid,
+        previous_name,
         columns,
         keys,
         relations,
@@ -127,6 +142,39 @@ fn parse_table_node(node: Node) -> Result<TableXml, String> {
         constraints,
         aggregations,
         initial_data,
+        partition_by,
+        description,
+    })
+}
+
+fn parse_partition_by_node(node: Node) -> Result<PartitionByXml, String> {
+    let mut partitions = Vec::new();
+    let mut template = None;
+    for c in node.children().filter(|n| n.is_element()) {
+        if c.has_tag_name((NS, "partition")) {
+            partitions.push(PartitionXml {
+                name: attr_string_required(c, "name")?,
+                from: attr_string(c, "from"),
+                to: attr_string(c, "to"),
+                values: attr_string(c, "values"),
+                modulus: attr_whole_number(c, "modulus")?,
+                remainder: attr_whole_number(c, "remainder")?,
+                default: attr_bool(c, "default"),
+            });
+        } else if c.has_tag_name((NS, "template")) {
+            template = Some(PartitionTemplateXml {
+                interval: attr_string(c, "interval"),
+                start: attr_string(c, "start"),
+                count: attr_whole_number(c, "count")?,
+                modulus: attr_whole_number(c, "modulus")?,
+            });
+        }
+    }
+    Ok(PartitionByXml {
+        strategy: attr_string_required(node, "strategy")?,
+        columns: attr_string_required(node, "columns")?,
+        partitions,
+        template,
     })
 }
 
@@ -149,12 +197,18 @@ fn parse_column_node(node: Node) -> Result<ColumnXml, String> {
         generated: attr_string(node, "generated"),
         enum_type: attr_string(node, "enumType"),
         element_type: attr_string(node, "elementType"),
+        sequence: attr_string(node, "sequence"),
+        identity: attr_string(node, "identity"),
+        identity_start: attr_whole_number(node, "identityStart")?,
+        identity_increment: attr_whole_number(node, "identityIncrement")?,
         min_value: attr_f64(node, "minValue"),
         max_value: attr_f64(node, "maxValue"),
+        previous_name: attr_string(node, "previousName"),
         check: node
             .children()
             .find(|n| n.has_tag_name((NS, "check")))
             .map(parse_check_node),
+        description: description(node),
     })
 }
 
@@ -185,7 +239,10 @@ fn parse_key_columns_node(node: Node) -> Result<KeyColumnsXml, String> {
     let mut columns = Vec::new();
     for c in node.children().filter(|n| n.has_tag_name((NS, "column"))) {
         columns.push(KeyColumnXml {
-            name: attr_string_required(c, "name")?,
+            name: Some(attr_string_required(c, "name")?),
+            expression: None,
+            order: None,
+            nulls: None,
         });
     }
     let cluster = attr_bool(node, "cluster");
@@ -195,13 +252,32 @@ fn parse_key_columns_node(node: Node) -> Result<KeyColumnsXml, String> {
 fn parse_index_node(node: Node) -> Result<IndexXml, String> {
     let mut columns = Vec::new();
     for c in node.children().filter(|n| n.has_tag_name((NS, "column"))) {
-        columns.push(KeyColumnXml { name: attr_string_required(c, "name")? });
+        // An index entry is either a column (`name`) or an expression such as
+        // `lower(email)`; exactly one of the two must be given.
+        let name = attr_string(c, "name");
+        let expression = attr_string(c, "expression");
+        if name.is_some() == expression.is_some() {
+            return Err("<column> element of an <index> needs exactly one of the 'name' or 'expression' attributes".to_string());
+        }
+        columns.push(KeyColumnXml {
+            name,
+            expression,
+            order: attr_string(c, "order"),
+            nulls: attr_string(c, "nulls"),
+        });
     }
     Ok(IndexXml {
         columns,
         include: attr_string(node, "include"),
-        compress: attr_bool(node, "compress"),
+        compress: attr_string(node, "compress"),
         unique: attr_bool(node, "unique"),
+        method: attr_string(node, "method"),
+        where_clause: attr_string(node, "where").or_else(|| {
+            node.children()
+                .find(|n| n.has_tag_name((NS, "where")))
+                .map(collect_text)
+        }),
+        previous_name: attr_string(node, "previousName"),
     })
 }
 
@@ -223,6 +299,13 @@ fn parse_view_node(node: Node) -> Result<ViewXml, String> {
     Ok(ViewXml {
         name: attr_string_required(node, "name")?,
         database_type: attr_string(node, "databaseType"),
+        materialized: attr_bool(node, "materialized"),
+        refresh: attr_string(node, "refresh"),
+        indexes: node
+            .children()
+            .filter(|n| n.has_tag_name((NS, "index")))
+            .map(parse_index_node)
+            .collect::<Result<Vec<_>, String>>()?,
         sql: collect_text(node),
     })
 }
@@ -276,6 +359,18 @@ fn parse_enum_node(node: Node) -> Result<EnumXml, String> {
     Ok(EnumXml { name, value })
 }
 
+fn parse_sequence_node(node: Node) -> Result<SequenceXml, String> {
+    Ok(SequenceXml {
+        name: attr_string_required(node, "name")?,
+        start: attr_whole_number(node, "start")?,
+        increment: attr_whole_number(node, "increment")?,
+        min_value: attr_whole_number(node, "minValue")?,
+        max_value: attr_whole_number(node, "maxValue")?,
+        cycle: attr_bool(node, "cycle"),
+        cache: attr_whole_number(node, "cache")?,
+    })
+}
+
 fn parse_triggers_node(node: Node) -> Result<TriggersXml, String> {
     let mut update = Vec::new();
     let mut delete = Vec::new();
@@ -295,6 +390,7 @@ fn parse_constraints_node(node: Node) -> Result<ConstraintsXml, String> {
         constraint.push(ConstraintXml {
             name: attr_string_required(c, "name")?,
             database_type: attr_string(c, "databaseType"),
+            previous_name: attr_string(c, "previousName"),
             sql: collect_text(c),
         });
     }
@@ -394,10 +490,40 @@ fn attr_i32(node: Node, name: &str) -> Option<i32> {
     node.attribute(name).and_then(|v| v.parse::<i32>().ok())
 }
 
+/// Reads an optional whole-number attribute, reporting a value that isn't one as an error
+/// rather than dropping it.
+fn attr_whole_number<T: std::str::FromStr>(node: Node, name: &str) -> Result<Option<T>, String> {
+    node.attribute(name)
+        .map(|v| {
+            v.trim().parse::<T>().map_err(|_| {
+                format!(
+                    "<{}> attribute '{}' must be a whole number, got '{}'",
+                    node.tag_name().name(),
+                    name,
+                    v
+                )
+            })
+        })
+        .transpose()
+}
+
 fn attr_f64(node: Node, name: &str) -> Option<f64> {
     node.attribute(name).and_then(|v| v.parse::<f64>().ok())
 }
 
+/// Reads a table/column description, given either as a `description` attribute or as the
+/// text of a `<description>` child element (handier for multi-line text). The attribute
+/// wins when both are present; blank text counts as no description.
+fn description(node: Node) -> Option<String> {
+    attr_string(node, "description")
+        .or_else(|| {
+            node.children()
+                .find(|n| n.has_tag_name((NS, "description")))
+                .map(collect_text)
+        })
+        .filter(|s| !s.trim().is_empty())
+}
+
 fn collect_text(node: Node) -> String {
     let mut out = String::new();
     for n in node.children() {
@@ -446,6 +572,29 @@ mod tests {
         assert_eq!(column.required, Some(true));
     }
 
+    #[test]
+    fn description_is_read_from_attribute_or_child_element() {
+        let doc = Document::parse(
+            r#"<table xmlns="http://stano.com/database" name="t">
+                <description>
+                    Customer orders
+                </description>
+                <columns>
+                    <column name="x" type="int" description="Order number"/>
+                    <column name="y" type="int"><description> </description></column>
+                </columns>
+            </table>"#,
+        )
+        .unwrap();
+
+        let table = parse_table_node(doc.root_element()).expect("parse ok");
+
+        assert_eq!(table.description.as_deref(), Some("Customer orders"));
+        let columns = table.columns.unwrap().column;
+        assert_eq!(columns[0].description.as_deref(), Some("Order number"));
+        assert_eq!(columns[1].description, None);
+    }
+
     #[test]
     fn missing_required_attribute_returns_error_instead_of_defaulting_to_empty_string() {
         let doc = Document::parse(r#"<column xmlns="http://stano.com/database" type="int"/>"#).unwrap();
@@ -1,5 +1,5 @@
 use crate::parser::convert::agg_frequency_from_str;
-use crate::parser::nodes::TableXml;
+use crate::parser::nodes::{IndexXml, KeyColumnXml, PartitionXml, RelationXml, TableXml};
 use schema_model::builder::{ColumnBuilder, KeyBuilder};
 use schema_model::model::aggregation::{
     Aggregation, AggregationColumn, AggregationGroup, AggregationType,
@@ -8,24 +8,29 @@ use schema_model::model::column::Column;
 use schema_model::model::column_type::ColumnType;
 use schema_model::model::constraint::Constraint;
 use schema_model::model::initial_data::InitialData;
-use schema_model::model::key::Key;
-use schema_model::model::relation::Relation;
+use schema_model::model::key::{Key, KeyColumn};
+use schema_model::model::partition::{Partition, PartitionBound, PartitionTemplate, Partitioning};
+use schema_model::model::relation::{Relation, RelationColumn};
 use schema_model::model::table::Table;
 use schema_model::model::trigger::Trigger;
-use schema_model::model::types::{KeyType, LockEscalation, RelationType, TableOption, TriggerType};
+use schema_model::model::types::{
+    CompressionLevel, IdentityMode, IndexMethod, KeyType, LockEscalation, NullsOrder, PartitionInterval, PartitionStrategy, RelationType, SortOrder,
+    TableOption, TriggerType,
+};
+use std::str::FromStr;
 
 pub(crate) fn parse_table(table_xml: &TableXml, schema_name: Option<&str>) -> Result<Table, String> {
     let columns = parse_columns(table_xml, schema_name)?;
     let keys = parse_keys(table_xml);
-    let indexes = parse_indexes(table_xml);
+    let indexes = parse_indexes(table_xml)?;
     let relations = parse_relations(table_xml)?;
     let triggers = parse_triggers(table_xml)?;
     let constraints = parse_constraints(table_xml)?;
     let aggregations = parse_aggregations(table_xml)?;
     let initial_data = parse_initial_data(table_xml)?;
-    let options = parse_table_options(table_xml);
+    let options = parse_table_options(table_xml)?;
 
-    Ok(Table::new(
+    let mut table = Table::new(
         schema_name,
         table_xml.name.as_str(),
         table_xml.export_data_column.as_deref(),
@@ -40,11 +45,15 @@ pub(crate) fn parse_table(table_xml: &TableXml, schema_name: Option<&str>) -> Re
         initial_data,
         options,
         aggregations,
-    ))
+    );
+    table.set_description(table_xml.description.clone());
+    table.set_partitioning(parse_partitioning(table_xml)?);
+    table.set_previous_name(table_xml.previous_name.clone());
+
+    Ok(table)
 }
 
 fn lock_escalation(table_xml: &TableXml) -> Result<LockEscalation, String> {
-    use std::str::FromStr;
     match table_xml.lock_escalation.as_deref() {
         Some(s) => LockEscalation::from_str(s)
             .map_err(|e| format!("table '{}': {}", table_xml.name, e)),
@@ -64,6 +73,12 @@ fn parse_columns(table_xml: &TableXml, schema_name: Option<&str>) -> Result<Vec<
                     table_xml.name, column_xml.name, e
                 )
             })?;
+            let identity_mode = column_xml
+                .identity
+                .as_deref()
+                .map(IdentityMode::from_str)
+                .transpose()
+                .map_err(|e| format!("table '{}', column '{}': {}", table_xml.name, column_xml.name, e))?;
             let column = ColumnBuilder::new(schema_name, &column_xml.name, column_type)
                 .length(column_xml.length.unwrap_or(0))
                 .scale(column_xml.scale.unwrap_or(0))
@@ -75,6 +90,12 @@ fn parse_columns(table_xml: &TableXml, schema_name: Option<&str>) -> Result<Vec<
                 .max_value(column_xml.max_value)
                 .enum_type(column_xml.enum_type.clone())
                 .element_type(column_xml.element_type.clone())
+                .description(column_xml.description.clone())
+                .sequence(column_xml.sequence.clone())
+                .identity_mode(identity_mode)
+                .identity_start(column_xml.identity_start)
+                .identity_increment(column_xml.identity_increment)
+                .previous_name(column_xml.previous_name.clone())
                 .build();
             columns.push(column);
         }
@@ -90,7 +111,7 @@ fn parse_keys(table_xml: &TableXml) -> Vec<Key> {
         if let Some(primary_key_xml) = &keys_xml.primary {
             let mut kb = KeyBuilder::new(KeyType::Primary);
             for kc in primary_key_xml.columns.iter() {
-                kb = kb.add_column(&kc.name);
+                kb = kb.add_column(kc.name.as_deref().unwrap_or_default());
             }
             if let Some(c) = primary_key_xml.cluster {
                 kb = kb.cluster(c);
@@ -100,7 +121,7 @@ fn parse_keys(table_xml: &TableXml) -> Vec<Key> {
         for unique_key_xml in keys_xml.uniques.iter() {
             let mut kb = KeyBuilder::new(KeyType::Unique);
             for kc in unique_key_xml.columns.iter() {
-                kb = kb.add_column(&kc.name);
+                kb = kb.add_column(kc.name.as_deref().unwrap_or_default());
             }
             if let Some(c) = unique_key_xml.cluster {
                 kb = kb.cluster(c);
@@ -112,29 +133,126 @@ fn parse_keys(table_xml: &TableXml) -> Vec<Key> {
     keys
 }
 
-fn parse_indexes(table_xml: &TableXml) -> Vec<Key> {
+fn parse_indexes(table_xml: &TableXml) -> Result<Vec<Key>, String> {
     let mut indexes = Vec::new();
 
     if let Some(keys_xml) = &table_xml.keys {
         for index_xml in keys_xml.indexes.iter() {
-            let mut key_builder = KeyBuilder::new(KeyType::Index);
-            for key_column_xml in index_xml.columns.iter() {
-                key_builder = key_builder.add_column(&key_column_xml.name);
-            }
-            if let Some(s) = &index_xml.include {
-                key_builder = key_builder.include(s);
-            }
-            if let Some(v) = index_xml.compress {
-                key_builder = key_builder.compress(v);
-            }
-            if let Some(v) = index_xml.unique {
-                key_builder = key_builder.unique(v);
-            }
-            indexes.push(key_builder.build());
+            indexes.push(parse_index(index_xml, &format!("table '{}'", table_xml.name))?);
         }
     }
 
-    indexes
+    Ok(indexes)
+}
+
+/// Converts an `<index>` element, shared by tables and materialized views. `owner` names the
+/// table or view in error messages.
+pub(crate) fn parse_index(index_xml: &IndexXml, owner: &str) -> Result<Key, String> {
+    let mut key_builder = KeyBuilder::new(KeyType::Index);
+    for key_column_xml in index_xml.columns.iter() {
+        key_builder = key_builder.add_key_column(index_column(owner, key_column_xml)?);
+    }
+    if let Some(method) = &index_xml.method {
+        let method = IndexMethod::from_str(method).map_err(|e| format!("{}: index {}", owner, e))?;
+        key_builder = key_builder.method(method);
+    }
+    if let Some(where_clause) = &index_xml.where_clause {
+        key_builder = key_builder.filter(where_clause);
+    }
+    if let Some(s) = &index_xml.include {
+        key_builder = key_builder.include(s);
+    }
+    if let Some(level) = compression(index_xml.compress.as_deref()).map_err(|e| format!("{}: index {}", owner, e))? {
+        key_builder = key_builder.compression(level);
+    }
+    if let Some(v) = index_xml.unique {
+        key_builder = key_builder.unique(v);
+    }
+    if let Some(previous_name) = &index_xml.previous_name {
+        key_builder = key_builder.previous_name(previous_name);
+    }
+    Ok(key_builder.build())
+}
+
+fn index_column(owner: &str, key_column_xml: &KeyColumnXml) -> Result<KeyColumn, String> {
+    let key_column = match (&key_column_xml.name, &key_column_xml.expression) {
+        (Some(name), _) => KeyColumn::new(name.as_str()),
+        (None, Some(expression)) => KeyColumn::new_expression(expression.as_str()),
+        (None, None) => unreachable!("the XML reader requires a name or an expression on an index column"),
+    };
+    let sort_order = key_column_xml
+        .order
+        .as_deref()
+        .map(SortOrder::from_str)
+        .transpose()
+        .map_err(|e| format!("{}: index column '{}': {}", owner, key_column.name(), e))?;
+    let nulls_order = key_column_xml
+        .nulls
+        .as_deref()
+        .map(NullsOrder::from_str)
+        .transpose()
+        .map_err(|e| format!("{}: index column '{}': {}", owner, key_column.name(), e))?;
+
+    Ok(key_column.with_sort_order(sort_order).with_nulls_order(nulls_order))
+}
+
+fn parse_partitioning(table_xml: &TableXml) -> Result<Option<Partitioning>, String> {
+    let Some(partition_by) = &table_xml.partition_by else {
+        return Ok(None);
+    };
+    let strategy = PartitionStrategy::from_str(&partition_by.strategy)
+        .map_err(|e| format!("table '{}': {}", table_xml.name, e))?;
+    let columns = split_column_list(&partition_by.columns)
+        .into_iter()
+        .map(str::to_string)
+        .collect();
+    let partitions = partition_by
+        .partitions
+        .iter()
+        .map(|partition_xml| partition(table_xml, partition_xml))
+        .collect::<Result<Vec<_>, String>>()?;
+    let template = match &partition_by.template {
+        None => None,
+        Some(template_xml) => match (template_xml.modulus, &template_xml.interval, &template_xml.start, template_xml.count) {
+            (Some(modulus), None, None, None) => Some(PartitionTemplate::Hash { modulus }),
+            (None, Some(interval), Some(start), Some(count)) => Some(PartitionTemplate::Range {
+                interval: PartitionInterval::from_str(interval).map_err(|e| format!("table '{}': {}", table_xml.name, e))?,
+                start: start.clone(),
+                count,
+            }),
+            _ => {
+                return Err(format!(
+                    "table '{}': a partition <template> needs either 'modulus', or 'interval', 'start' and 'count'",
+                    table_xml.name
+                ));
+            }
+        },
+    };
+
+    Ok(Some(Partitioning::new(strategy, columns, partitions, template)))
+}
+
+fn partition(table_xml: &TableXml, partition_xml: &PartitionXml) -> Result<Partition, String> {
+    let values = |list: &str| split_column_list(list).into_iter().map(str::to_string).collect::<Vec<_>>();
+    let bound = match partition_xml {
+        PartitionXml { default: Some(true), .. } => PartitionBound::Default,
+        PartitionXml { from: Some(from), to: Some(to), .. } => PartitionBound::Range {
+            from: values(from),
+            to: values(to),
+        },
+        PartitionXml { values: Some(list), .. } => PartitionBound::List(values(list)),
+        PartitionXml { modulus: Some(modulus), remainder: Some(remainder), .. } => PartitionBound::Hash {
+            modulus: *modulus,
+            remainder: *remainder,
+        },
+        _ => {
+            return Err(format!(
+                "table '{}': partition '{}' needs 'from' and 'to', 'values', 'modulus' and 'remainder', or default=\"true\"",
+                table_xml.name, partition_xml.name
+            ));
+        }
+    };
+    Ok(Partition::new(partition_xml.name.as_str(), bound))
 }
 
 fn parse_relations(table_xml: &TableXml) -> Result<Vec<Relation>, String> {
@@ -154,11 +272,11 @@ fn parse_relations(table_xml: &TableXml) -> Result<Vec<Relation>, String> {
                     ));
                 }
             };
-            relations.push(Relation::new(
+            let columns = relation_columns(table_xml, relation_xml)?;
+            relations.push(Relation::new_composite(
                 relation_xml.table.clone(),
-                relation_xml.column.clone(),
                 table_xml.name.clone(),
-                relation_xml.src.clone(),
+                columns,
                 relation_type,
                 relation_xml.disable_usage_checking.unwrap_or(false),
             ));
@@ -168,6 +286,39 @@ fn parse_relations(table_xml: &TableXml) -> Result<Vec<Relation>, String> {
     Ok(relations)
 }
 
+/// Pairs up the comma-separated `src` and `column` lists of a `<relation>` in order, so a
+/// composite foreign key is written as e.g. `src="tenant_id,customer_id" column="tenant_id,id"`.
+fn relation_columns(table_xml: &TableXml, relation_xml: &RelationXml) -> Result<Vec<RelationColumn>, String> {
+    let from_columns = split_column_list(&relation_xml.src);
+    let to_columns = split_column_list(&relation_xml.column);
+
+    if from_columns.is_empty() || from_columns.len() != to_columns.len() {
+        return Err(format!(
+            "table '{}': relation to '{}' pairs {} source column(s) '{}' with {} referenced column(s) '{}'",
+            table_xml.name,
+            relation_xml.table,
+            from_columns.len(),
+            relation_xml.src,
+            to_columns.len(),
+            relation_xml.column
+        ));
+    }
+
+    Ok(from_columns
+        .into_iter()
+        .zip(to_columns)
+        .map(|(from, to)| RelationColumn::new(from, to))
+        .collect())
+}
+
+fn split_column_list(columns: &str) -> Vec<&str> {
+    columns
+        .split(',')
+        .map(str::trim)
+        .filter(|c| !c.is_empty())
+        .collect()
+}
+
 fn parse_triggers(table_xml: &TableXml) -> Result<Vec<Trigger>, String> {
     let mut triggers = Vec::new();
 
@@ -205,11 +356,10 @@ fn parse_constraints(table_xml: &TableXml) -> Result<Vec<Constraint>, String> {
                 constraint_xml.database_type.as_deref(),
                 &format!("constraint '{}' on table '{}'", constraint_xml.name, table_xml.name),
             )?;
-            constraints.push(Constraint::new(
-                &constraint_xml.name,
-                &constraint_xml.sql,
-                database_type,
-            ));
+            constraints.push(
+                Constraint::new(&constraint_xml.name, &constraint_xml.sql, database_type)
+                    .with_previous_name(constraint_xml.previous_name.clone()),
+            );
         }
     }
 
@@ -281,7 +431,7 @@ fn parse_initial_data(table_xml: &TableXml) -> Result<Vec<InitialData>, String>
     Ok(initial_data)
 }
 
-fn parse_table_options(table_xml: &TableXml) -> Vec<TableOption> {
+fn parse_table_options(table_xml: &TableXml) -> Result<Vec<TableOption>, String> {
     let mut options = Vec::new();
 
     if table_xml.data_opt.unwrap_or(false) {
@@ -290,9 +440,24 @@ fn parse_table_options(table_xml: &TableXml) -> Vec<TableOption> {
     if table_xml.no_export.unwrap_or(false) {
         options.push(TableOption::NoExport);
     }
-    if table_xml.compress.unwrap_or(false) {
-        options.push(TableOption::Compress);
+    if let Some(level) = compression(table_xml.compress.as_deref()).map_err(|e| format!("table '{}': {}", table_xml.name, e))? {
+        options.push(TableOption::Compress(level));
+    }
+    if table_xml.strict.unwrap_or(false) {
+        options.push(TableOption::Strict);
     }
+    if table_xml.without_rowid.unwrap_or(false) {
+        options.push(TableOption::WithoutRowid);
+    }
+
+    Ok(options)
+}
 
-    options
+/// A `compress` attribute: `true` compresses at the default level, `row` or `page` picks one.
+fn compression(value: Option<&str>) -> Result<Option<CompressionLevel>, String> {
+    match value.map(|v| v.to_ascii_lowercase()).as_deref() {
+        None | Some("false") | Some("0") | Some("no") | Some("off") => Ok(None),
+        Some("true") | Some("1") | Some("yes") | Some("on") => Ok(Some(CompressionLevel::default())),
+        Some(level) => CompressionLevel::from_str(level).map(Some),
+    }
 }
@@ -9,7 +9,7 @@ fn test_parser() {
     let schemas = database.schemas();
 
     assert_eq!(schemas.len(), 2);
-    assert_eq!(schemas[0].tables().len(), 7);
+    assert_eq!(schemas[0].tables().len(), 9);
     assert_eq!(schemas[0].get_table("ParentTable").name(), "ParentTable");
     assert_eq!(schemas[0].get_table("ChildTable").name(), "ChildTable");
     assert_eq!(schemas[0].get_table("ColumnTesterTable").name(), "ColumnTesterTable");
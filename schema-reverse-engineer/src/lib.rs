@@ -5,4 +5,4 @@ pub mod xml_writer;
 
 pub use error::SchemaReverseEngineerError;
 pub use reader::read_schema;
-pub use xml_writer::write_database_xml;
+pub use xml_writer::{write_database_xml, write_database_xml_with_naming};
@@ -1,5 +1,6 @@
 use clap::Parser;
-use schema_reverse_engineer::{read_schema, write_database_xml};
+use schema_model::model::naming::NamingConvention;
+use schema_reverse_engineer::{read_schema, write_database_xml_with_naming};
 use sqlx::postgres::PgPoolOptions;
 use std::path::PathBuf;
 
@@ -16,6 +17,9 @@ struct Args {
 
     #[arg(long, help = "Path to write the generated XML schema file to")]
     file: PathBuf,
+
+    #[arg(long, default_value = "preserve", help = "Naming convention the database was generated with (preserve|snake-case|lower|upper); names are mapped back to the model spelling")]
+    naming: NamingConvention,
 }
 
 #[tokio::main]
@@ -28,7 +32,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .await?;
 
     let model = read_schema(&pool, &args.db_schema).await?;
-    let xml = write_database_xml(&model);
+    let xml = write_database_xml_with_naming(&model, &args.naming);
 
     std::fs::write(&args.file, xml)?;
 
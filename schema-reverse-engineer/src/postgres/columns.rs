@@ -1,5 +1,6 @@
 use crate::error::SchemaReverseEngineerError;
 use schema_model::model::column_type::ColumnType;
+use schema_model::model::types::IdentityMode;
 use sqlx::PgPool;
 use std::collections::HashSet;
 
@@ -15,6 +16,11 @@ pub struct ColumnInfo {
     pub generated: Option<String>,
     pub enum_type: Option<String>,
     pub element_type: Option<String>,
+    pub description: Option<String>,
+    pub sequence: Option<String>,
+    pub identity_mode: Option<IdentityMode>,
+    pub identity_start: Option<i64>,
+    pub identity_increment: Option<i64>,
 }
 
 #[derive(Debug, sqlx::FromRow)]
@@ -29,21 +35,28 @@ struct ColumnRow {
     is_nullable: String,
     column_default: Option<String>,
     is_identity: String,
+    identity_generation: Option<String>,
+    identity_start: Option<String>,
+    identity_increment: Option<String>,
     is_generated: String,
     generation_expression: Option<String>,
+    description: Option<String>,
 }
 
 pub async fn list_columns(
     pool: &PgPool,
     db_schema: &str,
     enum_type_names: &HashSet<String>,
+    sequence_names: &HashSet<String>,
 ) -> Result<Vec<ColumnInfo>, SchemaReverseEngineerError> {
     let rows: Vec<ColumnRow> = sqlx::query_as(
         "SELECT c.table_name, c.column_name, c.data_type, c.udt_name, \
                 c.character_maximum_length::int4 AS character_maximum_length, \
                 c.numeric_precision::int4 AS numeric_precision, \
                 c.numeric_scale::int4 AS numeric_scale, \
-                c.is_nullable, c.column_default, c.is_identity, c.is_generated, c.generation_expression \
+                c.is_nullable, c.column_default, c.is_identity, c.identity_generation, c.identity_start, \
+                c.identity_increment, c.is_generated, c.generation_expression, \
+                col_description(format('%I.%I', c.table_schema, c.table_name)::regclass, c.ordinal_position::int4) AS description \
          FROM information_schema.columns c \
          JOIN information_schema.tables t \
            ON t.table_schema = c.table_schema AND t.table_name = c.table_name AND t.table_type = 'BASE TABLE' \
@@ -56,18 +69,28 @@ pub async fn list_columns(
     .map_err(|e| SchemaReverseEngineerError::Introspection(e.to_string()))?;
 
     rows.into_iter()
-        .map(|row| build_column_info(row, enum_type_names))
+        .map(|row| build_column_info(row, enum_type_names, sequence_names))
         .collect()
 }
 
-fn build_column_info(row: ColumnRow, enum_type_names: &HashSet<String>) -> Result<ColumnInfo, SchemaReverseEngineerError> {
+fn build_column_info(
+    row: ColumnRow,
+    enum_type_names: &HashSet<String>,
+    sequence_names: &HashSet<String>,
+) -> Result<ColumnInfo, SchemaReverseEngineerError> {
     let is_generated = row.is_generated == "ALWAYS";
-    let is_autoincrement = row.is_identity == "YES"
-        || row
-            .column_default
-            .as_deref()
-            .map(|d| d.starts_with("nextval("))
-            .unwrap_or(false);
+    let nextval_sequence = row.column_default.as_deref().and_then(nextval_sequence_name);
+    // A default drawing from a standalone sequence keeps the column a plain integer that
+    // references the sequence; any other `nextval` default comes from a `serial` column.
+    let sequence = nextval_sequence.clone().filter(|name| sequence_names.contains(name));
+    let is_autoincrement = row.is_identity == "YES" || (nextval_sequence.is_some() && sequence.is_none());
+
+    let (identity_mode, identity_start, identity_increment) = identity_settings(
+        &row.is_identity,
+        row.identity_generation.as_deref(),
+        row.identity_start.as_deref(),
+        row.identity_increment.as_deref(),
+    );
 
     let (column_type, element_type) =
         map_column_type(&row.data_type, &row.udt_name, is_autoincrement, enum_type_names)?;
@@ -101,7 +124,10 @@ fn build_column_info(row: ColumnRow, enum_type_names: &HashSet<String>) -> Resul
     // Postgres always reports a default expression for identity/sequence columns
     // (e.g. `nextval(...)`); suppress it here since the column type itself already
     // conveys that it's auto-generated (matches the Java writer's behavior).
-    let default_constraint = if is_generated || matches!(column_type, ColumnType::Sequence | ColumnType::LongSequence) {
+    let default_constraint = if is_generated
+        || sequence.is_some()
+        || matches!(column_type, ColumnType::Sequence | ColumnType::LongSequence)
+    {
         None
     } else {
         row.column_default
@@ -118,9 +144,42 @@ fn build_column_info(row: ColumnRow, enum_type_names: &HashSet<String>) -> Resul
         generated,
         enum_type,
         element_type,
+        description: row.description,
+        sequence,
+        identity_mode,
+        identity_start,
+        identity_increment,
     })
 }
 
+/// The identity mode of an identity column, with its start and increment when they aren't
+/// Postgres's default of 1. A serial column reports no identity and gets `None`.
+fn identity_settings(
+    is_identity: &str,
+    generation: Option<&str>,
+    start: Option<&str>,
+    increment: Option<&str>,
+) -> (Option<IdentityMode>, Option<i64>, Option<i64>) {
+    if is_identity != "YES" {
+        return (None, None, None);
+    }
+    let identity_mode = match generation {
+        Some("BY DEFAULT") => IdentityMode::ByDefault,
+        _ => IdentityMode::Always,
+    };
+    let non_default = |value: Option<&str>| value.and_then(|v| v.parse::<i64>().ok()).filter(|&v| v != 1);
+    (Some(identity_mode), non_default(start), non_default(increment))
+}
+
+/// The sequence named by a `nextval('...'::regclass)` default, without its schema and
+/// identifier quotes.
+fn nextval_sequence_name(default: &str) -> Option<String> {
+    let quoted = default.strip_prefix("nextval('")?;
+    let name = &quoted[..quoted.find('\'')?];
+    let name = name.rsplit('.').next().unwrap_or(name);
+    Some(name.trim_matches('"').to_string())
+}
+
 /// Maps a Postgres column's `data_type`/`udt_name` (as reported by `information_schema.columns`)
 /// to a schema-model `ColumnType`, plus an element-type name (lowercase `ColumnType::name()`)
 /// when the column is an array.
@@ -242,6 +301,26 @@ mod tests {
         );
     }
 
+    #[test]
+    fn nextval_sequence_name_strips_schema_cast_and_quotes() {
+        assert_eq!(nextval_sequence_name("nextval('document_no'::regclass)").as_deref(), Some("document_no"));
+        assert_eq!(nextval_sequence_name("nextval('sales.\"Doc\"'::regclass)").as_deref(), Some("Doc"));
+        assert_eq!(nextval_sequence_name("now()"), None);
+    }
+
+    #[test]
+    fn identity_settings_keep_only_non_default_start_and_increment() {
+        assert_eq!(identity_settings("NO", None, None, None), (None, None, None));
+        assert_eq!(
+            identity_settings("YES", Some("ALWAYS"), Some("1"), Some("1")),
+            (Some(IdentityMode::Always), None, None)
+        );
+        assert_eq!(
+            identity_settings("YES", Some("BY DEFAULT"), Some("1000"), Some("10")),
+            (Some(IdentityMode::ByDefault), Some(1000), Some(10))
+        );
+    }
+
     #[test]
     fn maps_numeric_to_decimal() {
         let none = enums(&[]);
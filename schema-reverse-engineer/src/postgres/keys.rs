@@ -1,10 +1,17 @@
 use crate::error::SchemaReverseEngineerError;
+use schema_model::model::key::KeyColumn;
+use schema_model::model::types::{IndexMethod, NullsOrder, SortOrder};
 use sqlx::PgPool;
-use std::collections::{HashMap, HashSet};
+use std::collections::btree_map::Entry;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::str::FromStr;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct KeyInfo {
-    pub columns: Vec<String>,
+    pub columns: Vec<KeyColumn>,
+    pub unique: bool,
+    pub method: Option<IndexMethod>,
+    pub filter: Option<String>,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -26,11 +33,16 @@ struct IndexRow {
     index_name: String,
     is_unique: bool,
     is_primary: bool,
-    column_name: String,
+    method: String,
+    predicate: Option<String>,
+    is_expression: bool,
+    column_definition: String,
+    is_descending: bool,
+    is_nulls_first: bool,
 }
 
-/// Reads primary keys plus unique/non-unique indexes for every table in `db_schema` and
-/// merges them into one `TableKeys` per table.
+/// Reads primary keys plus unique/non-unique indexes for every table and materialized view in
+/// `db_schema` and merges them into one `TableKeys` per table or view.
 ///
 /// `exclusion_constraint_names` skips unique indexes that merely back a Postgres exclusion
 /// constraint (already captured separately as a `Constraint`), matching the equivalent
@@ -64,36 +76,55 @@ pub async fn list_keys(
             .entry(row.table_name)
             .or_default()
             .primary
-            .get_or_insert_with(|| KeyInfo { columns: Vec::new() })
+            .get_or_insert_with(KeyInfo::default)
             .columns
-            .push(row.column_name);
+            .push(KeyColumn::new(row.column_name));
     }
 
+    // One row per key column (INCLUDE columns excluded) of every index. `pg_get_indexdef`
+    // renders the column name, or the expression text for an expression column (indkey 0);
+    // indoption bit 1 is DESC and bit 2 is NULLS FIRST.
     let index_rows: Vec<IndexRow> = sqlx::query_as(
         "SELECT t.relname AS table_name, i.relname AS index_name, ix.indisunique AS is_unique, \
-                ix.indisprimary AS is_primary, a.attname AS column_name \
+                ix.indisprimary AS is_primary, am.amname AS method, \
+                pg_get_expr(ix.indpred, ix.indrelid) AS predicate, \
+                ix.indkey[k.position - 1] = 0 AS is_expression, \
+                pg_get_indexdef(ix.indexrelid, k.position, true) AS column_definition, \
+                (ix.indoption[k.position - 1]::int4 & 1) <> 0 AS is_descending, \
+                (ix.indoption[k.position - 1]::int4 & 2) <> 0 AS is_nulls_first \
          FROM pg_index ix \
          JOIN pg_class t ON t.oid = ix.indrelid \
          JOIN pg_class i ON i.oid = ix.indexrelid \
+         JOIN pg_am am ON am.oid = i.relam \
          JOIN pg_namespace n ON n.oid = t.relnamespace \
-         JOIN pg_attribute a ON a.attrelid = t.oid AND a.attnum = ANY(ix.indkey) \
-         WHERE n.nspname = $1 AND t.relkind = 'r' \
-         ORDER BY t.relname, i.relname, array_position(ix.indkey, a.attnum)",
+         CROSS JOIN LATERAL generate_series(1, ix.indnkeyatts::int4) AS k(position) \
+         WHERE n.nspname = $1 AND t.relkind IN ('r', 'p', 'm') \
+         ORDER BY t.relname, i.relname, k.position",
     )
     .bind(db_schema)
     .fetch_all(pool)
     .await
     .map_err(|e| SchemaReverseEngineerError::Introspection(e.to_string()))?;
 
-    let mut by_index: HashMap<(String, String), (bool, bool, Vec<String>)> = HashMap::new();
+    // Keyed in name order so every run lists a table's indexes the same way.
+    let mut by_index: BTreeMap<(String, String), (bool, KeyInfo)> = BTreeMap::new();
     for row in index_rows {
-        let entry = by_index
-            .entry((row.table_name, row.index_name))
-            .or_insert((row.is_unique, row.is_primary, Vec::new()));
-        entry.2.push(row.column_name);
+        let entry = match by_index.entry((row.table_name.clone(), row.index_name.clone())) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert((
+                row.is_primary,
+                KeyInfo {
+                    columns: Vec::new(),
+                    unique: row.is_unique,
+                    method: index_method(&row.index_name, &row.method)?,
+                    filter: row.predicate.clone(),
+                },
+            )),
+        };
+        entry.1.columns.push(key_column(&row));
     }
 
-    for ((table_name, index_name), (is_unique, is_primary, columns)) in by_index {
+    for ((table_name, index_name), (is_primary, key_info)) in by_index {
         // The index backing the primary key is already represented via the PK query above.
         if is_primary {
             continue;
@@ -104,10 +135,12 @@ pub async fn list_keys(
         }
 
         let table_keys = result.entry(table_name).or_default();
-        if is_unique {
-            table_keys.unique.push(KeyInfo { columns });
+        // Only a unique index over plain columns can be written back as a unique key; a
+        // partial, expression or non-btree unique index stays an index flagged unique.
+        if key_info.unique && is_plain(&key_info) {
+            table_keys.unique.push(key_info);
         } else {
-            table_keys.index.push(KeyInfo { columns });
+            table_keys.index.push(key_info);
         }
     }
 
@@ -120,3 +153,84 @@ pub async fn list_keys(
 
     Ok(result)
 }
+
+/// The model only records a method other than the default btree.
+fn index_method(index_name: &str, method: &str) -> Result<Option<IndexMethod>, SchemaReverseEngineerError> {
+    match IndexMethod::from_str(method) {
+        Ok(IndexMethod::BTree) => Ok(None),
+        Ok(method) => Ok(Some(method)),
+        Err(_) => Err(SchemaReverseEngineerError::Introspection(format!(
+            "index '{}' uses the unsupported access method '{}'",
+            index_name, method
+        ))),
+    }
+}
+
+/// Builds a key column from one index row, keeping only the non-default orderings: ASC
+/// sorts nulls last and DESC sorts them first unless told otherwise.
+fn key_column(row: &IndexRow) -> KeyColumn {
+    let key_column = if row.is_expression {
+        KeyColumn::new_expression(row.column_definition.as_str())
+    } else {
+        KeyColumn::new(row.column_definition.as_str())
+    };
+    let sort_order = row.is_descending.then_some(SortOrder::Desc);
+    let nulls_order = match (row.is_descending, row.is_nulls_first) {
+        (false, true) => Some(NullsOrder::First),
+        (true, false) => Some(NullsOrder::Last),
+        _ => None,
+    };
+    key_column.with_sort_order(sort_order).with_nulls_order(nulls_order)
+}
+
+fn is_plain(key_info: &KeyInfo) -> bool {
+    key_info.method.is_none()
+        && key_info.filter.is_none()
+        && key_info
+            .columns
+            .iter()
+            .all(|c| !c.is_expression() && c.sort_order().is_none() && c.nulls_order().is_none())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(is_expression: bool, column_definition: &str, is_descending: bool, is_nulls_first: bool) -> IndexRow {
+        IndexRow {
+            table_name: "users".to_string(),
+            index_name: "ix_users1".to_string(),
+            is_unique: false,
+            is_primary: false,
+            method: "btree".to_string(),
+            predicate: None,
+            is_expression,
+            column_definition: column_definition.to_string(),
+            is_descending,
+            is_nulls_first,
+        }
+    }
+
+    #[test]
+    fn key_column_keeps_only_non_default_orderings() {
+        let plain = key_column(&row(false, "email", false, false));
+        assert_eq!(plain, KeyColumn::new("email"));
+
+        let descending = key_column(&row(false, "created_at", true, true));
+        assert_eq!(descending, KeyColumn::new("created_at").with_sort_order(Some(SortOrder::Desc)));
+
+        let desc_nulls_last = key_column(&row(false, "created_at", true, false));
+        assert_eq!(desc_nulls_last.nulls_order(), Some(NullsOrder::Last));
+
+        let expression = key_column(&row(true, "lower(email::text)", false, true));
+        assert!(expression.is_expression());
+        assert_eq!(expression.nulls_order(), Some(NullsOrder::First));
+    }
+
+    #[test]
+    fn index_method_drops_the_default_btree_and_rejects_unknown_methods() {
+        assert_eq!(index_method("ix", "btree").unwrap(), None);
+        assert_eq!(index_method("ix", "gin").unwrap(), Some(IndexMethod::Gin));
+        assert!(index_method("ix", "spgist").is_err());
+    }
+}
@@ -2,6 +2,8 @@ pub mod columns;
 pub mod constraints;
 pub mod enums;
 pub mod keys;
+pub mod partitions;
 pub mod relations;
+pub mod sequences;
 pub mod tables;
 pub mod views;
@@ -0,0 +1,188 @@
+use crate::error::SchemaReverseEngineerError;
+use schema_model::model::partition::{Partition, PartitionBound, Partitioning};
+use schema_model::model::types::PartitionStrategy;
+use sqlx::PgPool;
+use std::collections::HashMap;
+
+#[derive(Debug, sqlx::FromRow)]
+struct PartitionedTableRow {
+    table_name: String,
+    strategy: String,
+    key_count: i32,
+    columns: Vec<String>,
+}
+
+/// Reads how each partitioned table of `db_schema` is partitioned (`pg_partitioned_table`)
+/// along with its partitions, keyed by table name. Partitions are always read back as
+/// declared partitions; a template they were generated from can't be recovered.
+pub async fn list_partitionings(
+    pool: &PgPool,
+    db_schema: &str,
+) -> Result<HashMap<String, Partitioning>, SchemaReverseEngineerError> {
+    // An expression in the partition key has attnum 0 and no pg_attribute row, which
+    // leaves `columns` shorter than `key_count`.
+    let table_rows: Vec<PartitionedTableRow> = sqlx::query_as(
+        "SELECT c.relname AS table_name, pt.partstrat::text AS strategy, pt.partnatts::int4 AS key_count, \
+                ARRAY(SELECT a.attname::text \
+                      FROM unnest(pt.partattrs::int2[]) WITH ORDINALITY AS k(attnum, position) \
+                      JOIN pg_attribute a ON a.attrelid = c.oid AND a.attnum = k.attnum \
+                      ORDER BY k.position) AS columns \
+         FROM pg_partitioned_table pt \
+         JOIN pg_class c ON c.oid = pt.partrelid \
+         JOIN pg_namespace n ON n.oid = c.relnamespace \
+         WHERE n.nspname = $1 AND c.relkind = 'p' \
+         ORDER BY c.relname",
+    )
+    .bind(db_schema)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| SchemaReverseEngineerError::Introspection(e.to_string()))?;
+
+    // Indexes on a partitioned table are partitioned too and have their own pg_inherits
+    // rows, with no bound; only table partitions are read.
+    let partition_rows: Vec<(String, String, String)> = sqlx::query_as(
+        "SELECT parent.relname, child.relname, pg_get_expr(child.relpartbound, child.oid) \
+         FROM pg_inherits i \
+         JOIN pg_class parent ON parent.oid = i.inhparent \
+         JOIN pg_class child ON child.oid = i.inhrelid \
+         JOIN pg_namespace n ON n.oid = parent.relnamespace \
+         WHERE n.nspname = $1 AND child.relispartition \
+           AND parent.relkind = 'p' AND child.relkind IN ('r', 'p') \
+         ORDER BY parent.relname, child.relname",
+    )
+    .bind(db_schema)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| SchemaReverseEngineerError::Introspection(e.to_string()))?;
+
+    let mut partitions_by_table: HashMap<String, Vec<Partition>> = HashMap::new();
+    for (table_name, partition_name, bound) in partition_rows {
+        let bound = parse_partition_bound(&bound).ok_or_else(|| {
+            SchemaReverseEngineerError::Introspection(format!(
+                "partition '{}' has a bound that can't be read: {}",
+                partition_name, bound
+            ))
+        })?;
+        partitions_by_table
+            .entry(table_name)
+            .or_default()
+            .push(Partition::new(partition_name, bound));
+    }
+
+    let mut partitionings = HashMap::new();
+    for row in table_rows {
+        let strategy = match row.strategy.as_str() {
+            "r" => PartitionStrategy::Range,
+            "l" => PartitionStrategy::List,
+            "h" => PartitionStrategy::Hash,
+            other => {
+                return Err(SchemaReverseEngineerError::Introspection(format!(
+                    "table '{}' uses the unknown partition strategy '{}'",
+                    row.table_name, other
+                )));
+            }
+        };
+        if row.columns.len() != row.key_count as usize {
+            return Err(SchemaReverseEngineerError::Introspection(format!(
+                "table '{}' is partitioned by an expression, which can't be described",
+                row.table_name
+            )));
+        }
+        let partitions = partitions_by_table.remove(&row.table_name).unwrap_or_default();
+        partitionings.insert(row.table_name, Partitioning::new(strategy, row.columns, partitions, None));
+    }
+    Ok(partitionings)
+}
+
+/// Parses the bound `pg_get_expr` renders for a partition: `DEFAULT`,
+/// `FOR VALUES IN (...)`, `FOR VALUES FROM (...) TO (...)` or
+/// `FOR VALUES WITH (modulus m, remainder r)`.
+fn parse_partition_bound(bound: &str) -> Option<PartitionBound> {
+    let bound = bound.trim();
+    if bound.eq_ignore_ascii_case("default") {
+        return Some(PartitionBound::Default);
+    }
+    let rest = strip_prefix_ignore_case(bound, "for values")?.trim_start();
+    if let Some(list) = strip_prefix_ignore_case(rest, "in") {
+        let (values, _) = parenthesised_values(list)?;
+        return Some(PartitionBound::List(values));
+    }
+    if let Some(from) = strip_prefix_ignore_case(rest, "from") {
+        let (from, rest) = parenthesised_values(from)?;
+        let (to, _) = parenthesised_values(strip_prefix_ignore_case(rest.trim_start(), "to")?)?;
+        return Some(PartitionBound::Range { from, to });
+    }
+    let (options, _) = parenthesised_values(strip_prefix_ignore_case(rest, "with")?)?;
+    let mut modulus = None;
+    let mut remainder = None;
+    for option in options {
+        let (name, value) = option.split_once(' ')?;
+        let value = value.trim().parse().ok()?;
+        match name.to_ascii_lowercase().as_str() {
+            "modulus" => modulus = Some(value),
+            "remainder" => remainder = Some(value),
+            _ => return None,
+        }
+    }
+    Some(PartitionBound::Hash {
+        modulus: modulus?,
+        remainder: remainder?,
+    })
+}
+
+fn strip_prefix_ignore_case<'a>(s: &'a str, prefix: &str) -> Option<&'a str> {
+    let head = s.get(..prefix.len())?;
+    head.eq_ignore_ascii_case(prefix).then(|| &s[prefix.len()..])
+}
+
+/// Splits the comma separated list in the parentheses `s` starts with, unquoting string
+/// literals, and returns it with the text after the closing parenthesis.
+fn parenthesised_values(s: &str) -> Option<(Vec<String>, &str)> {
+    let s = s.trim_start().strip_prefix('(')?;
+    let mut values = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+    let mut chars = s.char_indices().peekable();
+    while let Some((i, ch)) = chars.next() {
+        match ch {
+            '\'' if in_quotes && chars.peek().map(|&(_, next)| next) == Some('\'') => {
+                current.push('\'');
+                chars.next();
+            }
+            '\'' => in_quotes = !in_quotes,
+            ',' if !in_quotes => values.push(std::mem::take(&mut current).trim().to_string()),
+            ')' if !in_quotes => {
+                values.push(current.trim().to_string());
+                return Some((values, &s[i + 1..]));
+            }
+            _ => current.push(ch),
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_partition_bound_reads_each_form() {
+        assert_eq!(parse_partition_bound("DEFAULT"), Some(PartitionBound::Default));
+        assert_eq!(
+            parse_partition_bound("FOR VALUES IN ('eu', 'o''hare')"),
+            Some(PartitionBound::List(vec!["eu".to_string(), "o'hare".to_string()]))
+        );
+        assert_eq!(
+            parse_partition_bound("FOR VALUES FROM (MINVALUE, 10) TO ('2024-01-01', MAXVALUE)"),
+            Some(PartitionBound::Range {
+                from: vec!["MINVALUE".to_string(), "10".to_string()],
+                to: vec!["2024-01-01".to_string(), "MAXVALUE".to_string()],
+            })
+        );
+        assert_eq!(
+            parse_partition_bound("FOR VALUES WITH (modulus 4, remainder 3)"),
+            Some(PartitionBound::Hash { modulus: 4, remainder: 3 })
+        );
+        assert_eq!(parse_partition_bound("FOR VALUES LIKE (1)"), None);
+    }
+}
@@ -1,11 +1,12 @@
 use crate::error::SchemaReverseEngineerError;
-use schema_model::model::relation::Relation;
+use schema_model::model::relation::{Relation, RelationColumn};
 use schema_model::model::types::RelationType;
 use sqlx::PgPool;
 use std::collections::HashMap;
 
 #[derive(Debug, sqlx::FromRow)]
 struct ForeignKeyRow {
+    constraint_name: String,
     from_table: String,
     from_column: String,
     to_table: String,
@@ -18,6 +19,7 @@ struct ForeignKeyRow {
 /// `key_column_usage.position_in_unique_constraint`, which correctly lines up each FK column
 /// with its referenced column regardless of declaration order (the Java original grouped by
 /// `KEY_SEQ` per side independently, which risked mis-pairing columns for composite keys).
+/// The rows of one constraint are folded into a single, possibly composite, `Relation`.
 ///
 /// Uses `delete_rule` (not `update_rule`, which the Java original used) to determine cascade
 /// behavior, since `ON DELETE` is what `RelationType` models.
@@ -26,7 +28,7 @@ pub async fn list_foreign_keys(
     db_schema: &str,
 ) -> Result<HashMap<String, Vec<Relation>>, SchemaReverseEngineerError> {
     let rows: Vec<ForeignKeyRow> = sqlx::query_as(
-        "SELECT tc.table_name AS from_table, fkcu.column_name AS from_column, \
+        "SELECT tc.constraint_name AS constraint_name, tc.table_name AS from_table, fkcu.column_name AS from_column, \
                 ucu.table_name AS to_table, ucu.column_name AS to_column, \
                 rc.delete_rule AS delete_rule \
          FROM information_schema.table_constraints tc \
@@ -39,21 +41,35 @@ pub async fn list_foreign_keys(
           AND ucu.constraint_schema = rc.unique_constraint_schema \
           AND ucu.ordinal_position = fkcu.position_in_unique_constraint \
          WHERE tc.table_schema = $1 AND tc.constraint_type = 'FOREIGN KEY' \
-         ORDER BY tc.constraint_name, fkcu.ordinal_position",
+         ORDER BY tc.table_name, tc.constraint_name, fkcu.ordinal_position",
     )
     .bind(db_schema)
     .fetch_all(pool)
     .await
     .map_err(|e| SchemaReverseEngineerError::Introspection(e.to_string()))?;
 
-    let mut by_table: HashMap<String, Vec<Relation>> = HashMap::new();
+    // Rows arrive ordered by constraint, then by FK column position, so each constraint's
+    // column pairs are contiguous and already in declaration order.
+    let mut constraints: Vec<(ForeignKeyRow, Vec<RelationColumn>)> = Vec::new();
     for row in rows {
+        let column = RelationColumn::new(row.from_column.clone(), row.to_column.clone());
+        match constraints.last_mut() {
+            Some((first, columns))
+                if first.constraint_name == row.constraint_name && first.from_table == row.from_table =>
+            {
+                columns.push(column);
+            }
+            _ => constraints.push((row, vec![column])),
+        }
+    }
+
+    let mut by_table: HashMap<String, Vec<Relation>> = HashMap::new();
+    for (row, columns) in constraints {
         let relation_type = map_delete_rule(&row.delete_rule);
-        let relation = Relation::new(
+        let relation = Relation::new_composite(
             row.to_table,
-            row.to_column,
             row.from_table.clone(),
-            row.from_column,
+            columns,
             relation_type,
             false,
         );
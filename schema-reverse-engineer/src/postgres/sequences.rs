@@ -0,0 +1,100 @@
+use crate::error::SchemaReverseEngineerError;
+use schema_model::builder::SequenceBuilder;
+use schema_model::model::sequence::Sequence;
+use sqlx::PgPool;
+
+#[derive(Debug, sqlx::FromRow)]
+struct SequenceRow {
+    sequencename: String,
+    data_type: String,
+    start_value: i64,
+    increment_by: i64,
+    min_value: i64,
+    max_value: i64,
+    cycle: bool,
+    cache_size: i64,
+}
+
+/// Reads the standalone sequences of `db_schema` from `pg_sequences`. Sequences owned by a
+/// `serial` or identity column are skipped; those columns are read as sequence column types.
+pub async fn list_sequences(pool: &PgPool, db_schema: &str) -> Result<Vec<Sequence>, SchemaReverseEngineerError> {
+    let rows: Vec<SequenceRow> = sqlx::query_as(
+        "SELECT s.sequencename, s.data_type::text AS data_type, s.start_value, s.increment_by, \
+                s.min_value, s.max_value, s.cycle, s.cache_size \
+         FROM pg_sequences s \
+         JOIN pg_namespace n ON n.nspname = s.schemaname \
+         JOIN pg_class c ON c.relnamespace = n.oid AND c.relname = s.sequencename \
+         WHERE s.schemaname = $1 \
+           AND NOT EXISTS ( \
+               SELECT 1 FROM pg_depend d \
+               WHERE d.classid = 'pg_class'::regclass AND d.objid = c.oid AND d.deptype IN ('a', 'i')) \
+         ORDER BY s.sequencename",
+    )
+    .bind(db_schema)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| SchemaReverseEngineerError::Introspection(e.to_string()))?;
+
+    Ok(rows.into_iter().map(build_sequence).collect())
+}
+
+/// Builds the model sequence, leaving out every option that matches what `create sequence`
+/// would pick on its own, so a plain sequence reads back as a plain `<sequence>`.
+fn build_sequence(row: SequenceRow) -> Sequence {
+    let (type_min, type_max) = match row.data_type.as_str() {
+        "smallint" => (i16::MIN as i64, i16::MAX as i64),
+        "integer" => (i32::MIN as i64, i32::MAX as i64),
+        _ => (i64::MIN, i64::MAX),
+    };
+    let ascending = row.increment_by > 0;
+    let (default_min, default_max) = if ascending { (1, type_max) } else { (type_min, -1) };
+    let min_value = Some(row.min_value).filter(|&v| v != default_min);
+    let max_value = Some(row.max_value).filter(|&v| v != default_max);
+    let default_start = if ascending { row.min_value } else { row.max_value };
+
+    SequenceBuilder::new(None::<&str>, row.sequencename.as_str())
+        .start(Some(row.start_value).filter(|&v| v != default_start))
+        .increment(Some(row.increment_by).filter(|&v| v != 1))
+        .min_value(min_value)
+        .max_value(max_value)
+        .cycle(row.cycle)
+        .cache(Some(row.cache_size).filter(|&v| v != 1))
+        .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(start_value: i64, increment_by: i64, min_value: i64, max_value: i64, cache_size: i64) -> SequenceRow {
+        SequenceRow {
+            sequencename: "document_no".to_string(),
+            data_type: "bigint".to_string(),
+            start_value,
+            increment_by,
+            min_value,
+            max_value,
+            cycle: false,
+            cache_size,
+        }
+    }
+
+    #[test]
+    fn default_options_are_left_unset() {
+        let sequence = build_sequence(row(1, 1, 1, i64::MAX, 1));
+        assert_eq!(sequence.options_sql(), "");
+
+        let descending = build_sequence(row(-1, -1, i64::MIN, -1, 1));
+        assert_eq!(descending.options_sql(), " increment by -1");
+    }
+
+    #[test]
+    fn explicit_options_are_kept() {
+        let sequence = build_sequence(row(1000, 10, 1000, 99999990, 20));
+        assert_eq!(sequence.start(), None);
+        assert_eq!(sequence.increment(), Some(10));
+        assert_eq!(sequence.min_value(), Some(1000));
+        assert_eq!(sequence.max_value(), Some(99999990));
+        assert_eq!(sequence.cache(), Some(20));
+    }
+}
@@ -1,17 +1,61 @@
 use crate::error::SchemaReverseEngineerError;
 use sqlx::PgPool;
+use std::collections::HashSet;
 
-pub async fn list_tables(pool: &PgPool, db_schema: &str) -> Result<Vec<String>, SchemaReverseEngineerError> {
-    let rows: Vec<(String,)> = sqlx::query_as(
-        "SELECT table_name \
-         FROM information_schema.tables \
-         WHERE table_schema = $1 AND table_type = 'BASE TABLE' \
-         ORDER BY table_name",
+#[derive(Debug, Clone)]
+pub struct TableInfo {
+    pub table_name: String,
+    pub description: Option<String>,
+}
+
+/// Lists the base tables of `db_schema`. Partitions are left out; they are read as part of
+/// their parent's partitioning.
+pub async fn list_tables(pool: &PgPool, db_schema: &str) -> Result<Vec<TableInfo>, SchemaReverseEngineerError> {
+    let rows: Vec<(String, Option<String>)> = sqlx::query_as(
+        "SELECT t.table_name, d.description \
+         FROM information_schema.tables t \
+         JOIN pg_catalog.pg_namespace n ON n.nspname = t.table_schema \
+         JOIN pg_catalog.pg_class c ON c.relnamespace = n.oid AND c.relname = t.table_name \
+         LEFT JOIN pg_catalog.pg_description d \
+           ON d.objoid = c.oid AND d.classoid = 'pg_catalog.pg_class'::regclass AND d.objsubid = 0 \
+         WHERE t.table_schema = $1 AND t.table_type = 'BASE TABLE' AND NOT c.relispartition \
+         ORDER BY t.table_name",
+    )
+    .bind(db_schema)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| SchemaReverseEngineerError::Introspection(e.to_string()))?;
+
+    Ok(rows
+        .into_iter()
+        .map(|(table_name, description)| TableInfo { table_name, description })
+        .collect())
+}
+
+/// Lists the tables of `db_schema` with a column stored using lz4, which is how the SQL
+/// generator writes a compressed table. Per-column compression arrived in Postgres 14, so
+/// older servers have none.
+pub async fn list_compressed_tables(pool: &PgPool, db_schema: &str) -> Result<HashSet<String>, SchemaReverseEngineerError> {
+    let server_version: String = sqlx::query_scalar("SELECT current_setting('server_version_num')")
+        .fetch_one(pool)
+        .await
+        .map_err(|e| SchemaReverseEngineerError::Introspection(e.to_string()))?;
+    if server_version.parse::<u32>().unwrap_or(0) < 140000 {
+        return Ok(HashSet::new());
+    }
+
+    let rows: Vec<String> = sqlx::query_scalar(
+        "SELECT DISTINCT c.relname::text \
+         FROM pg_catalog.pg_class c \
+         JOIN pg_catalog.pg_namespace n ON n.oid = c.relnamespace \
+         JOIN pg_catalog.pg_attribute a ON a.attrelid = c.oid \
+         WHERE n.nspname = $1 AND c.relkind IN ('r', 'p') \
+           AND a.attnum > 0 AND NOT a.attisdropped AND a.attcompression = 'l'",
     )
     .bind(db_schema)
     .fetch_all(pool)
     .await
     .map_err(|e| SchemaReverseEngineerError::Introspection(e.to_string()))?;
 
-    Ok(rows.into_iter().map(|(name,)| name).collect())
+    Ok(rows.into_iter().collect())
 }
@@ -20,3 +20,35 @@ pub async fn list_views(pool: &PgPool, db_schema: &str) -> Result<Vec<View>, Sch
         .map(|(name, sql)| View::new(None, name.as_str(), sql.trim(), Some(DatabaseType::Postgresql)))
         .collect())
 }
+
+#[derive(Debug, Clone)]
+pub struct MaterializedViewInfo {
+    pub name: String,
+    pub sql: String,
+}
+
+/// Lists the materialized views in `db_schema`. Their indexes come back from
+/// `keys::list_keys` alongside the tables' and are attached by the reader.
+pub async fn list_materialized_views(
+    pool: &PgPool,
+    db_schema: &str,
+) -> Result<Vec<MaterializedViewInfo>, SchemaReverseEngineerError> {
+    let rows: Vec<(String, String)> = sqlx::query_as(
+        "SELECT matviewname, definition \
+         FROM pg_matviews \
+         WHERE schemaname = $1 \
+         ORDER BY matviewname",
+    )
+    .bind(db_schema)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| SchemaReverseEngineerError::Introspection(e.to_string()))?;
+
+    Ok(rows
+        .into_iter()
+        .map(|(name, sql)| MaterializedViewInfo {
+            name,
+            sql: sql.trim().trim_end_matches(';').to_string(),
+        })
+        .collect())
+}
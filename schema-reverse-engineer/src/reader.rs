@@ -1,25 +1,36 @@
 use crate::error::SchemaReverseEngineerError;
-use crate::postgres::{columns, constraints, enums, keys, relations, tables, views};
+use crate::postgres::{columns, constraints, enums, keys, partitions, relations, sequences, tables, views};
 use schema_model::builder::{ColumnBuilder, KeyBuilder, SchemaBuilder, TableBuilder};
 use schema_model::model::database_model::DatabaseModel;
-use schema_model::model::types::{BooleanMode, ForeignKeyMode, KeyType};
+use schema_model::model::key::Key;
+use schema_model::model::types::{
+    BooleanMode, CompressionLevel, DatabaseType, ForeignKeyMode, KeyType, RefreshPolicy, TableOption,
+};
+use schema_model::model::view::View;
 use sqlx::PgPool;
 use std::collections::{HashMap, HashSet};
 
 /// Introspects the given Postgres schema (`db_schema`, e.g. `"public"`) and builds a
 /// `DatabaseModel` describing its tables, columns, keys, foreign keys, check/exclusion
-/// constraints, enum types, and views.
+/// constraints, enum types, sequences, partitioning, and views, including materialized views
+/// and their indexes.
 pub async fn read_schema(pool: &PgPool, db_schema: &str) -> Result<DatabaseModel, SchemaReverseEngineerError> {
-    let table_names = tables::list_tables(pool, db_schema).await?;
+    let table_infos = tables::list_tables(pool, db_schema).await?;
     let enum_types = enums::list_enum_types(pool, db_schema).await?;
     let enum_type_names: HashSet<String> = enum_types.iter().map(|e| e.name().to_string()).collect();
 
-    let all_columns = columns::list_columns(pool, db_schema, &enum_type_names).await?;
+    let sequences = sequences::list_sequences(pool, db_schema).await?;
+    let sequence_names: HashSet<String> = sequences.iter().map(|s| s.name().to_string()).collect();
+
+    let all_columns = columns::list_columns(pool, db_schema, &enum_type_names, &sequence_names).await?;
     let exclusion_constraint_names = constraints::list_exclusion_constraint_names(pool, db_schema).await?;
     let mut table_keys = keys::list_keys(pool, db_schema, &exclusion_constraint_names).await?;
     let mut table_relations = relations::list_foreign_keys(pool, db_schema).await?;
     let mut table_constraints = constraints::list_constraints(pool, db_schema).await?;
     let table_views = views::list_views(pool, db_schema).await?;
+    let materialized_views = views::list_materialized_views(pool, db_schema).await?;
+    let mut table_partitionings = partitions::list_partitionings(pool, db_schema).await?;
+    let compressed_tables = tables::list_compressed_tables(pool, db_schema).await?;
 
     let mut columns_by_table: HashMap<String, Vec<columns::ColumnInfo>> = HashMap::new();
     for column in all_columns {
@@ -30,12 +41,33 @@ pub async fn read_schema(pool: &PgPool, db_schema: &str) -> Result<DatabaseModel
     for enum_type in enum_types {
         schema_builder = schema_builder.add_enum_type(enum_type);
     }
+    for sequence in sequences {
+        schema_builder = schema_builder.add_sequence(sequence);
+    }
     for view in table_views {
         schema_builder = schema_builder.add_view(view);
     }
+    for view_info in materialized_views {
+        // Postgres doesn't record how a materialized view is meant to be refreshed.
+        let indexes = table_keys.remove(&view_info.name).map(view_indexes).unwrap_or_default();
+        schema_builder = schema_builder.add_view(View::new_materialized(
+            None,
+            view_info.name.as_str(),
+            view_info.sql.as_str(),
+            Some(DatabaseType::Postgresql),
+            RefreshPolicy::Manual,
+            indexes,
+        ));
+    }
 
-    for table_name in &table_names {
-        let mut table_builder = TableBuilder::new(None::<&str>, table_name.as_str());
+    for table_info in &table_infos {
+        let table_name = &table_info.table_name;
+        let mut table_builder = TableBuilder::new(None::<&str>, table_name.as_str())
+            .description(table_info.description.clone())
+            .partitioning(table_partitionings.remove(table_name));
+        if compressed_tables.contains(table_name) {
+            table_builder = table_builder.add_option(TableOption::Compress(CompressionLevel::default()));
+        }
 
         for column in columns_by_table.remove(table_name).unwrap_or_default() {
             table_builder = table_builder.add_column(
@@ -47,19 +79,24 @@ pub async fn read_schema(pool: &PgPool, db_schema: &str) -> Result<DatabaseModel
                     .generated(column.generated)
                     .enum_type(column.enum_type)
                     .element_type(column.element_type)
+                    .description(column.description)
+                    .sequence(column.sequence)
+                    .identity_mode(column.identity_mode)
+                    .identity_start(column.identity_start)
+                    .identity_increment(column.identity_increment)
                     .build(),
             );
         }
 
         if let Some(keys_for_table) = table_keys.remove(table_name) {
             if let Some(primary) = keys_for_table.primary {
-                table_builder = table_builder.add_key(build_key(KeyType::Primary, primary.columns));
+                table_builder = table_builder.add_key(build_key(KeyType::Primary, primary));
             }
             for unique in keys_for_table.unique {
-                table_builder = table_builder.add_key(build_key(KeyType::Unique, unique.columns));
+                table_builder = table_builder.add_key(build_key(KeyType::Unique, unique));
             }
             for index in keys_for_table.index {
-                table_builder = table_builder.add_index(build_key(KeyType::Index, index.columns));
+                table_builder = table_builder.add_index(build_key(KeyType::Index, index));
             }
         }
 
@@ -79,10 +116,31 @@ pub async fn read_schema(pool: &PgPool, db_schema: &str) -> Result<DatabaseModel
     Ok(DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema]))
 }
 
-fn build_key(key_type: KeyType, columns: Vec<String>) -> schema_model::model::key::Key {
+/// A view has no keys of its own, so every index found on it, including a unique one that
+/// `list_keys` promoted to a primary key, is kept as an index.
+fn view_indexes(view_keys: keys::TableKeys) -> Vec<Key> {
+    view_keys
+        .primary
+        .into_iter()
+        .chain(view_keys.unique)
+        .chain(view_keys.index)
+        .map(|key_info| build_key(KeyType::Index, key_info))
+        .collect()
+}
+
+fn build_key(key_type: KeyType, key_info: keys::KeyInfo) -> Key {
     let mut builder = KeyBuilder::new(key_type);
-    for column in columns {
-        builder = builder.add_column(column);
+    for column in key_info.columns {
+        builder = builder.add_key_column(column);
+    }
+    if key_type == KeyType::Index {
+        builder = builder.unique(key_info.unique);
+        if let Some(method) = key_info.method {
+            builder = builder.method(method);
+        }
+        if let Some(filter) = key_info.filter {
+            builder = builder.filter(filter);
+        }
     }
     builder.build()
 }
@@ -3,10 +3,15 @@ use schema_model::model::constraint::Constraint;
 use schema_model::model::database_model::DatabaseModel;
 use schema_model::model::enum_type::EnumType;
 use schema_model::model::key::Key;
+use schema_model::model::naming::NamingConvention;
+use schema_model::model::partition::{PartitionBound, Partitioning};
 use schema_model::model::relation::Relation;
 use schema_model::model::schema::Schema;
+use schema_model::model::sequence::Sequence;
 use schema_model::model::table::Table;
-use schema_model::model::types::{BooleanMode, ForeignKeyMode, KeyType};
+use schema_model::model::types::{
+    BooleanMode, CompressionLevel, ForeignKeyMode, IdentityMode, KeyType, NullsOrder, RefreshPolicy,
+};
 use schema_model::model::view::View;
 use std::fmt::Write as _;
 
@@ -15,6 +20,13 @@ const NAMESPACE: &str = "http://stano.com/database";
 /// Serializes a `DatabaseModel` to the schema-rs XML schema definition format (see
 /// `schema-xsd/schema.xsd`), the inverse of `schema_parser::parse_database_xml`.
 pub fn write_database_xml(model: &DatabaseModel) -> String {
+    write_database_xml_with_naming(model, &NamingConvention::Preserve)
+}
+
+/// `write_database_xml` for a database generated with `naming`: table, column, key,
+/// relation, view, sequence and constraint names are mapped back to their model spelling,
+/// so the XML regenerates the same database.
+pub fn write_database_xml_with_naming(model: &DatabaseModel, naming: &NamingConvention) -> String {
     let mut out = String::new();
     out.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
     let _ = writeln!(
@@ -26,22 +38,25 @@ pub fn write_database_xml(model: &DatabaseModel) -> String {
     );
 
     for schema in model.schemas() {
-        write_schema_body(&mut out, schema, 1);
+        write_schema_body(&mut out, schema, naming, 1);
     }
 
     out.push_str("</database>\n");
     out
 }
 
-fn write_schema_body(out: &mut String, schema: &Schema, indent: usize) {
+fn write_schema_body(out: &mut String, schema: &Schema, naming: &NamingConvention, indent: usize) {
     for enum_type in schema.enum_types() {
         write_enum(out, enum_type, indent);
     }
+    for sequence in schema.sequences() {
+        write_sequence(out, sequence, naming, indent);
+    }
     for table in schema.tables() {
-        write_table(out, table, indent);
+        write_table(out, table, naming, indent);
     }
     for view in schema.all_views() {
-        write_view(out, view, indent);
+        write_view(out, view, naming, indent);
     }
 }
 
@@ -60,45 +75,123 @@ fn write_enum(out: &mut String, enum_type: &EnumType, indent: usize) {
     out.push_str("</enum>\n\n");
 }
 
-fn write_view(out: &mut String, view: &View, indent: usize) {
+fn write_sequence(out: &mut String, sequence: &Sequence, naming: &NamingConvention, indent: usize) {
     push_indent(out, indent);
-    let _ = writeln!(out, "<view name=\"{}\">", xml_escape(view.name()));
+    let _ = write!(out, "<sequence name=\"{}\"", xml_escape(&naming.to_model(sequence.name())));
+    if let Some(start) = sequence.start() {
+        let _ = write!(out, " start=\"{}\"", start);
+    }
+    if let Some(increment) = sequence.increment() {
+        let _ = write!(out, " increment=\"{}\"", increment);
+    }
+    if let Some(min_value) = sequence.min_value() {
+        let _ = write!(out, " minValue=\"{}\"", min_value);
+    }
+    if let Some(max_value) = sequence.max_value() {
+        let _ = write!(out, " maxValue=\"{}\"", max_value);
+    }
+    if sequence.is_cycle() {
+        out.push_str(" cycle=\"true\"");
+    }
+    if let Some(cache) = sequence.cache() {
+        let _ = write!(out, " cache=\"{}\"", cache);
+    }
+    out.push_str("/>\n\n");
+}
+
+fn write_view(out: &mut String, view: &View, naming: &NamingConvention, indent: usize) {
+    push_indent(out, indent);
+    let _ = write!(out, "<view name=\"{}\"", xml_escape(&naming.to_model(view.name())));
+    if view.is_materialized() {
+        out.push_str(" materialized=\"true\"");
+        if view.refresh_policy() == RefreshPolicy::Concurrent {
+            out.push_str(" refresh=\"concurrent\"");
+        }
+    }
+    out.push_str(">\n");
     push_indent(out, indent + 1);
     write_cdata(out, view.sql());
     out.push('\n');
+    for index in view.indexes() {
+        write_index(out, index, naming, indent + 1);
+    }
     push_indent(out, indent);
     out.push_str("</view>\n\n");
 }
 
-fn write_table(out: &mut String, table: &Table, indent: usize) {
+fn write_table(out: &mut String, table: &Table, naming: &NamingConvention, indent: usize) {
     push_indent(out, indent);
-    let _ = writeln!(out, "<table name=\"{}\">", xml_escape(table.name()));
+    let _ = write!(out, "<table name=\"{}\"", xml_escape(&naming.to_model(table.name())));
+    if let Some(description) = table.description() {
+        let _ = write!(out, " description=\"{}\"", description_attribute(description));
+    }
+    if let Some(level) = table.compression() {
+        let _ = write!(out, " compress=\"{}\"", compress_attribute(level));
+    }
+    out.push_str(">\n");
 
-    write_columns(out, table.columns(), indent + 1);
-    write_keys(out, table, indent + 1);
-    write_relations(out, table.relations(), indent + 1);
-    write_constraints(out, table.constraints(), indent + 1);
+    write_columns(out, table.columns(), naming, indent + 1);
+    write_keys(out, table, naming, indent + 1);
+    write_relations(out, table.relations(), naming, indent + 1);
+    write_constraints(out, table.constraints(), naming, indent + 1);
+    if let Some(partitioning) = table.partitioning() {
+        write_partitioning(out, partitioning, naming, indent + 1);
+    }
 
     push_indent(out, indent);
     out.push_str("</table>\n\n");
 }
 
-fn write_columns(out: &mut String, columns: &[Column], indent: usize) {
+fn write_partitioning(out: &mut String, partitioning: &Partitioning, naming: &NamingConvention, indent: usize) {
+    push_indent(out, indent);
+    let _ = writeln!(
+        out,
+        "<partitionBy strategy=\"{}\" columns=\"{}\">",
+        partitioning.strategy().sql(),
+        xml_escape(&model_names(naming, partitioning.columns().iter().map(String::as_str)))
+    );
+    for partition in partitioning.partitions() {
+        push_indent(out, indent + 1);
+        let _ = write!(out, "<partition name=\"{}\"", xml_escape(&naming.to_model(partition.name())));
+        match partition.bound() {
+            PartitionBound::Range { from, to } => {
+                let _ = write!(
+                    out,
+                    " from=\"{}\" to=\"{}\"",
+                    xml_escape(&from.join(",")),
+                    xml_escape(&to.join(","))
+                );
+            }
+            PartitionBound::List(values) => {
+                let _ = write!(out, " values=\"{}\"", xml_escape(&values.join(",")));
+            }
+            PartitionBound::Hash { modulus, remainder } => {
+                let _ = write!(out, " modulus=\"{}\" remainder=\"{}\"", modulus, remainder);
+            }
+            PartitionBound::Default => out.push_str(" default=\"true\""),
+        }
+        out.push_str("/>\n");
+    }
+    push_indent(out, indent);
+    out.push_str("</partitionBy>\n");
+}
+
+fn write_columns(out: &mut String, columns: &[Column], naming: &NamingConvention, indent: usize) {
     push_indent(out, indent);
     out.push_str("<columns>\n");
     for column in columns {
-        write_column(out, column, indent + 1);
+        write_column(out, column, naming, indent + 1);
     }
     push_indent(out, indent);
     out.push_str("</columns>\n");
 }
 
-fn write_column(out: &mut String, column: &Column, indent: usize) {
+fn write_column(out: &mut String, column: &Column, naming: &NamingConvention, indent: usize) {
     push_indent(out, indent);
     let _ = write!(
         out,
         "<column name=\"{}\" type=\"{}\"",
-        xml_escape(column.name()),
+        xml_escape(&naming.to_model(column.name())),
         column.column_type().name().to_lowercase()
     );
     if column.length() > 0 {
@@ -122,12 +215,27 @@ fn write_column(out: &mut String, column: &Column, indent: usize) {
     if let Some(element_type) = column.element_type() {
         let _ = write!(out, " elementType=\"{}\"", xml_escape(element_type));
     }
+    if let Some(sequence) = column.sequence() {
+        let _ = write!(out, " sequence=\"{}\"", xml_escape(&naming.to_model(sequence)));
+    }
+    if let Some(identity_mode) = column.identity_mode() {
+        let _ = write!(out, " identity=\"{}\"", identity_mode_str(identity_mode));
+    }
+    if let Some(start) = column.identity_start() {
+        let _ = write!(out, " identityStart=\"{}\"", start);
+    }
+    if let Some(increment) = column.identity_increment() {
+        let _ = write!(out, " identityIncrement=\"{}\"", increment);
+    }
     if let Some(min_value) = column.min_value() {
         let _ = write!(out, " minValue=\"{}\"", min_value);
     }
     if let Some(max_value) = column.max_value() {
         let _ = write!(out, " maxValue=\"{}\"", max_value);
     }
+    if let Some(description) = column.description() {
+        let _ = write!(out, " description=\"{}\"", description_attribute(description));
+    }
 
     if let Some(check) = column.check_constraint() {
         out.push_str(">\n");
@@ -140,7 +248,7 @@ fn write_column(out: &mut String, column: &Column, indent: usize) {
     }
 }
 
-fn write_keys(out: &mut String, table: &Table, indent: usize) {
+fn write_keys(out: &mut String, table: &Table, naming: &NamingConvention, indent: usize) {
     // The XSD requires a <primary> element whenever <keys> is present, so a table with no
     // primary key (and no promoted single-unique-key) can't emit unique/index keys either.
     let Some(primary) = table.primary_key() else {
@@ -149,29 +257,29 @@ fn write_keys(out: &mut String, table: &Table, indent: usize) {
 
     push_indent(out, indent);
     out.push_str("<keys>\n");
-    write_key_columns(out, "primary", primary, indent + 1);
+    write_key_columns(out, "primary", primary, naming, indent + 1);
     for unique in table.keys().iter().filter(|k| k.key_type() == KeyType::Unique) {
-        write_key_columns(out, "unique", unique, indent + 1);
+        write_key_columns(out, "unique", unique, naming, indent + 1);
     }
     for index in table.indexes() {
-        write_index(out, index, indent + 1);
+        write_index(out, index, naming, indent + 1);
     }
     push_indent(out, indent);
     out.push_str("</keys>\n");
 }
 
-fn write_key_columns(out: &mut String, tag: &str, key: &Key, indent: usize) {
+fn write_key_columns(out: &mut String, tag: &str, key: &Key, naming: &NamingConvention, indent: usize) {
     push_indent(out, indent);
     let _ = writeln!(out, "<{}>", tag);
     for column in key.columns() {
         push_indent(out, indent + 1);
-        let _ = writeln!(out, "<column name=\"{}\"/>", xml_escape(column.name()));
+        let _ = writeln!(out, "<column name=\"{}\"/>", xml_escape(&naming.to_model(column.name())));
     }
     push_indent(out, indent);
     let _ = writeln!(out, "</{}>", tag);
 }
 
-fn write_index(out: &mut String, index: &Key, indent: usize) {
+fn write_index(out: &mut String, index: &Key, naming: &NamingConvention, indent: usize) {
     push_indent(out, indent);
     let _ = write!(out, "<index");
     if index.is_unique() {
@@ -180,16 +288,41 @@ fn write_index(out: &mut String, index: &Key, indent: usize) {
     if let Some(include) = index.include() {
         let _ = write!(out, " include=\"{}\"", xml_escape(include));
     }
+    if let Some(level) = index.compression() {
+        let _ = write!(out, " compress=\"{}\"", compress_attribute(level));
+    }
+    if let Some(method) = index.method() {
+        let _ = write!(out, " method=\"{}\"", method.sql());
+    }
+    if let Some(filter) = index.filter() {
+        let _ = write!(out, " where=\"{}\"", xml_escape(filter));
+    }
     out.push_str(">\n");
     for column in index.columns() {
         push_indent(out, indent + 1);
-        let _ = writeln!(out, "<column name=\"{}\"/>", xml_escape(column.name()));
+        let (attribute, name) = if column.is_expression() {
+            ("expression", column.name().to_string())
+        } else {
+            ("name", naming.to_model(column.name()))
+        };
+        let _ = write!(out, "<column {}=\"{}\"", attribute, xml_escape(&name));
+        if let Some(sort_order) = column.sort_order() {
+            let _ = write!(out, " order=\"{}\"", sort_order.sql());
+        }
+        if let Some(nulls_order) = column.nulls_order() {
+            let nulls = match nulls_order {
+                NullsOrder::First => "first",
+                NullsOrder::Last => "last",
+            };
+            let _ = write!(out, " nulls=\"{}\"", nulls);
+        }
+        out.push_str("/>\n");
     }
     push_indent(out, indent);
     out.push_str("</index>\n");
 }
 
-fn write_relations(out: &mut String, relations: &[Relation], indent: usize) {
+fn write_relations(out: &mut String, relations: &[Relation], naming: &NamingConvention, indent: usize) {
     if relations.is_empty() {
         return;
     }
@@ -200,9 +333,9 @@ fn write_relations(out: &mut String, relations: &[Relation], indent: usize) {
         let _ = writeln!(
             out,
             "<relation src=\"{}\" table=\"{}\" column=\"{}\" type=\"{}\"/>",
-            xml_escape(relation.from_column_name()),
-            xml_escape(relation.to_table_name()),
-            xml_escape(relation.to_column_name()),
+            xml_escape(&model_names(naming, relation.from_column_names())),
+            xml_escape(&model_table_name(naming, relation.to_table_name())),
+            xml_escape(&model_names(naming, relation.to_column_names())),
             relation_type_str(relation.relation_type())
         );
     }
@@ -210,7 +343,7 @@ fn write_relations(out: &mut String, relations: &[Relation], indent: usize) {
     out.push_str("</relations>\n");
 }
 
-fn write_constraints(out: &mut String, constraints: &[Constraint], indent: usize) {
+fn write_constraints(out: &mut String, constraints: &[Constraint], naming: &NamingConvention, indent: usize) {
     if constraints.is_empty() {
         return;
     }
@@ -221,7 +354,7 @@ fn write_constraints(out: &mut String, constraints: &[Constraint], indent: usize
         let _ = writeln!(
             out,
             "<constraint name=\"{}\" databaseType=\"{}\">",
-            xml_escape(constraint.name()),
+            xml_escape(&naming.to_model(constraint.name())),
             database_type_str(constraint.database_type())
         );
         push_indent(out, indent + 2);
@@ -234,6 +367,20 @@ fn write_constraints(out: &mut String, constraints: &[Constraint], indent: usize
     out.push_str("</constraints>\n");
 }
 
+/// `names` mapped back to their model spelling and joined with commas.
+fn model_names<'a>(naming: &NamingConvention, names: impl IntoIterator<Item = &'a str>) -> String {
+    names.into_iter().map(|name| naming.to_model(name)).collect::<Vec<_>>().join(",")
+}
+
+/// A possibly schema-qualified table name with only the table part mapped; schema names
+/// aren't renamed on the way out either.
+fn model_table_name(naming: &NamingConvention, name: &str) -> String {
+    match name.rsplit_once('.') {
+        Some((schema_name, table_name)) => format!("{}.{}", schema_name, naming.to_model(table_name)),
+        None => naming.to_model(name),
+    }
+}
+
 fn relation_type_str(relation_type: schema_model::model::types::RelationType) -> &'static str {
     use schema_model::model::types::RelationType;
     match relation_type {
@@ -244,6 +391,14 @@ fn relation_type_str(relation_type: schema_model::model::types::RelationType) ->
     }
 }
 
+fn identity_mode_str(mode: IdentityMode) -> &'static str {
+    match mode {
+        IdentityMode::Serial => "serial",
+        IdentityMode::Always => "always",
+        IdentityMode::ByDefault => "byDefault",
+    }
+}
+
 fn boolean_mode_str(mode: BooleanMode) -> &'static str {
     match mode {
         BooleanMode::Native => "native",
@@ -258,6 +413,8 @@ fn database_type_str(database_type: schema_model::model::types::DatabaseType) ->
         DatabaseType::Postgresql => "postgresql",
         DatabaseType::Sqlite => "sqlite",
         DatabaseType::SqlServer => "sqlserver",
+        DatabaseType::MySql => "mysql",
+        DatabaseType::DuckDb => "duckdb",
     }
 }
 
@@ -269,6 +426,15 @@ fn foreign_key_mode_str(mode: ForeignKeyMode) -> &'static str {
     }
 }
 
+/// The default level is written as `true`, which is also all a Postgres database can tell us.
+fn compress_attribute(level: CompressionLevel) -> &'static str {
+    if level == CompressionLevel::default() {
+        "true"
+    } else {
+        level.sql()
+    }
+}
+
 fn push_indent(out: &mut String, indent: usize) {
     for _ in 0..indent {
         out.push_str("  ");
@@ -304,14 +470,21 @@ fn xml_escape(value: &str) -> String {
     escaped
 }
 
+/// Escapes a description for an attribute value, keeping its line breaks: a parser
+/// normalizes a literal newline inside an attribute to a space, but not a `&#10;`.
+fn description_attribute(description: &str) -> String {
+    xml_escape(description).replace('\n', "&#10;")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use schema_model::builder::{ColumnBuilder, KeyBuilder, SchemaBuilder, TableBuilder};
     use schema_model::model::column_type::ColumnType;
     use schema_model::model::enum_type::EnumValue;
+    use schema_model::model::partition::Partition;
     use schema_model::model::relation::Relation;
-    use schema_model::model::types::{DatabaseType, RelationType};
+    use schema_model::model::types::{DatabaseType, PartitionStrategy, RelationType};
 
     #[test]
     fn writes_table_with_columns_pk_and_relation() {
@@ -345,6 +518,33 @@ mod tests {
         assert!(xml.contains("<view name=\"v1\">"));
     }
 
+    #[test]
+    fn snake_case_database_names_are_written_back_in_pascal_case() {
+        let parent = TableBuilder::new(None::<&str>, "parent_table")
+            .add_column(ColumnBuilder::new(None::<&str>, "parent_id", ColumnType::Sequence).required(true).build())
+            .add_key(KeyBuilder::new(KeyType::Primary).add_column("parent_id").build())
+            .build();
+
+        let child = TableBuilder::new(None::<&str>, "child_table")
+            .add_column(ColumnBuilder::new(None::<&str>, "child_id", ColumnType::Sequence).required(true).build())
+            .add_column(ColumnBuilder::new(None::<&str>, "parent_id", ColumnType::Int).required(true).build())
+            .add_key(KeyBuilder::new(KeyType::Primary).add_column("child_id").build())
+            .add_relation(Relation::new("parent_table", "parent_id", "child_table", "parent_id", RelationType::Cascade, false))
+            .build();
+
+        let schema = SchemaBuilder::new(None::<&str>).add_table(parent).add_table(child).build();
+        let model = DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema]);
+        let xml = write_database_xml_with_naming(&model, &NamingConvention::SnakeCase);
+
+        assert!(xml.contains("<table name=\"ParentTable\">"));
+        assert!(xml.contains("<column name=\"ParentId\" type=\"sequence\" required=\"true\"/>"));
+        assert!(xml.contains("<relation src=\"ParentId\" table=\"ParentTable\" column=\"ParentId\" type=\"cascade\"/>"));
+
+        let reparsed = schema_parser::parse_database_xml(&xml).expect("written XML should parse");
+        let child = reparsed.find_table_checked(None, "ChildTable").expect("ChildTable should be read back");
+        assert_eq!(NamingConvention::SnakeCase.to_database(child.columns()[1].name()), "parent_id");
+    }
+
     #[test]
     fn write_cdata_splits_embedded_close_sequence() {
         let mut out = String::new();
@@ -372,6 +572,155 @@ mod tests {
         assert_eq!(reparsed_view.sql(), "select * from t where arr[1:2]]>x");
     }
 
+    #[test]
+    fn materialized_view_with_indexes_round_trips_through_the_real_parser() {
+        let view = View::new_materialized(
+            None::<&str>,
+            "order_totals",
+            "select customer_id, sum(amount) as total from orders group by customer_id",
+            None,
+            RefreshPolicy::Concurrent,
+            vec![
+                KeyBuilder::new(KeyType::Index).add_column("customer_id").unique(true).build(),
+                KeyBuilder::new(KeyType::Index).add_column("total").build(),
+            ],
+        );
+        let schema = SchemaBuilder::new(None::<&str>).add_view(view.clone()).build();
+        let model = DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema]);
+        let xml = write_database_xml(&model);
+
+        assert!(xml.contains("<view name=\"order_totals\" materialized=\"true\" refresh=\"concurrent\">"));
+        let reparsed = schema_parser::parse_database_xml(&xml).expect("generated xml should parse");
+        let reparsed_view = &reparsed.default_schema().all_views()[0];
+        assert!(reparsed_view.same_definition(&view));
+    }
+
+    #[test]
+    fn partitioned_table_round_trips_through_the_real_parser() {
+        let partitioning = Partitioning::new(
+            PartitionStrategy::List,
+            vec!["region".to_string()],
+            vec![
+                Partition::new("orders_europe", PartitionBound::List(vec!["de".to_string(), "fr".to_string()])),
+                Partition::new("orders_other", PartitionBound::Default),
+            ],
+            None,
+        );
+        let table = TableBuilder::new(None::<&str>, "orders")
+            .add_column(ColumnBuilder::new(None::<&str>, "region", ColumnType::Varchar).length(2).required(true).build())
+            .partitioning(Some(partitioning.clone()))
+            .build();
+        let schema = SchemaBuilder::new(None::<&str>).add_table(table).build();
+        let model = DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema]);
+        let xml = write_database_xml(&model);
+
+        assert!(xml.contains("<partitionBy strategy=\"list\" columns=\"region\">"));
+        assert!(xml.contains("<partition name=\"orders_europe\" values=\"de,fr\"/>"));
+        let reparsed = schema_parser::parse_database_xml(&xml).expect("generated xml should parse");
+        assert_eq!(reparsed.default_schema().get_table("orders").partitioning(), Some(&partitioning));
+    }
+
+    #[test]
+    fn multi_line_descriptions_round_trip_through_the_real_parser() {
+        let table = TableBuilder::new(None::<&str>, "t")
+            .description(Some("Orders \"placed\"\nby customers".to_string()))
+            .add_column(ColumnBuilder::new(None::<&str>, "a", ColumnType::Int).description(Some("A & B".to_string())).build())
+            .build();
+        let schema = SchemaBuilder::new(None::<&str>).add_table(table).build();
+        let model = DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema]);
+        let xml = write_database_xml(&model);
+
+        let reparsed = schema_parser::parse_database_xml(&xml).expect("parse ok");
+        let reparsed_table = reparsed.default_schema().get_table("t");
+        assert_eq!(reparsed_table.description(), Some("Orders \"placed\"\nby customers"));
+        assert_eq!(reparsed_table.column("a").description(), Some("A & B"));
+    }
+
+    #[test]
+    fn sequences_and_columns_drawing_from_them_round_trip_through_the_real_parser() {
+        use schema_model::builder::SequenceBuilder;
+
+        let table = TableBuilder::new(None::<&str>, "invoice")
+            .add_column(
+                ColumnBuilder::new(None::<&str>, "invoice_no", ColumnType::Long)
+                    .sequence(Some("document_no".to_string()))
+                    .build(),
+            )
+            .build();
+        let sequence = SequenceBuilder::new(None::<&str>, "document_no")
+            .start(Some(1000))
+            .increment(Some(10))
+            .cycle(true)
+            .build();
+        let schema = SchemaBuilder::new(None::<&str>).add_sequence(sequence.clone()).add_table(table).build();
+        let model = DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema]);
+        let xml = write_database_xml(&model);
+
+        let reparsed = schema_parser::parse_database_xml(&xml).expect("parse ok");
+        let reparsed_schema = reparsed.default_schema();
+        assert_eq!(reparsed_schema.sequences(), &[sequence]);
+        assert_eq!(reparsed_schema.get_table("invoice").column("invoice_no").sequence(), Some("document_no"));
+    }
+
+    #[test]
+    fn partial_expression_index_round_trips_through_the_real_parser() {
+        use schema_model::model::key::KeyColumn;
+        use schema_model::model::types::{IndexMethod, SortOrder};
+
+        let table = TableBuilder::new(None::<&str>, "t")
+            .add_column(ColumnBuilder::new(None::<&str>, "id", ColumnType::Int).build())
+            .add_column(ColumnBuilder::new(None::<&str>, "email", ColumnType::Varchar).build())
+            .add_key(KeyBuilder::new(KeyType::Primary).add_column("id").build())
+            .add_index(
+                KeyBuilder::new(KeyType::Index)
+                    .add_key_column(KeyColumn::new_expression("lower(email)"))
+                    .add_key_column(KeyColumn::new("id").with_sort_order(Some(SortOrder::Desc)).with_nulls_order(Some(NullsOrder::Last)))
+                    .method(IndexMethod::Hash)
+                    .filter("id > 0 and email <> ''")
+                    .unique(true)
+                    .build(),
+            )
+            .build();
+        let schema = SchemaBuilder::new(None::<&str>).add_table(table).build();
+        let model = DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema]);
+        let xml = write_database_xml(&model);
+
+        let reparsed = schema_parser::parse_database_xml(&xml).expect("parse ok");
+        let index = &reparsed.default_schema().get_table("t").indexes()[0];
+        assert!(index.is_unique());
+        assert_eq!(index.method(), Some(IndexMethod::Hash));
+        assert_eq!(index.filter(), Some("id > 0 and email <> ''"));
+        assert_eq!(
+            index.columns(),
+            &vec![
+                KeyColumn::new_expression("lower(email)"),
+                KeyColumn::new("id").with_sort_order(Some(SortOrder::Desc)).with_nulls_order(Some(NullsOrder::Last)),
+            ]
+        );
+    }
+
+    #[test]
+    fn compression_round_trips_through_the_real_parser() {
+        use schema_model::model::types::TableOption;
+
+        let table = TableBuilder::new(None::<&str>, "t")
+            .add_column(ColumnBuilder::new(None::<&str>, "id", ColumnType::Int).build())
+            .add_key(KeyBuilder::new(KeyType::Primary).add_column("id").build())
+            .add_index(KeyBuilder::new(KeyType::Index).add_column("id").compression(CompressionLevel::Row).build())
+            .add_option(TableOption::Compress(CompressionLevel::Page))
+            .build();
+        let schema = SchemaBuilder::new(None::<&str>).add_table(table).build();
+        let model = DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema]);
+        let xml = write_database_xml(&model);
+        assert!(xml.contains("<table name=\"t\" compress=\"true\">"));
+        assert!(xml.contains("<index compress=\"row\">"));
+
+        let reparsed = schema_parser::parse_database_xml(&xml).expect("parse ok");
+        let reparsed_table = reparsed.default_schema().get_table("t");
+        assert_eq!(reparsed_table.compression(), Some(CompressionLevel::Page));
+        assert_eq!(reparsed_table.indexes()[0].compression(), Some(CompressionLevel::Row));
+    }
+
     #[test]
     fn constraint_sql_containing_cdata_close_sequence_round_trips_through_the_real_parser() {
         let table = TableBuilder::new(None::<&str>, "t")
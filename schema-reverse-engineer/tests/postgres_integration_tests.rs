@@ -1,5 +1,6 @@
 use schema_model::model::column_type::ColumnType;
-use schema_model::model::types::{KeyType, RelationType};
+use schema_model::model::partition::PartitionBound;
+use schema_model::model::types::{IdentityMode, KeyType, PartitionStrategy, RelationType, SortOrder};
 use schema_reverse_engineer::{read_schema, write_database_xml};
 use sqlx::PgPool;
 use sqlx::postgres::PgPoolOptions;
@@ -13,6 +14,8 @@ fn docker_tests_enabled() -> bool {
 const SCHEMA_SQL: &str = r#"
 CREATE TYPE mood AS ENUM ('sad', 'ok', 'happy');
 
+CREATE SEQUENCE document_no START 1000 INCREMENT 10;
+
 CREATE TABLE customers (
     id uuid PRIMARY KEY,
     name varchar(100) NOT NULL,
@@ -28,14 +31,36 @@ CREATE TABLE orders (
     customer_id uuid NOT NULL,
     line_no int4 NOT NULL,
     amount numeric(10,2) NOT NULL CHECK (amount >= 0),
+    invoice_no int8 DEFAULT nextval('document_no'),
     PRIMARY KEY (id),
     CONSTRAINT fk_orders_customer FOREIGN KEY (customer_id) REFERENCES customers (id) ON DELETE CASCADE
 );
 
 CREATE INDEX idx_orders_line_no ON orders (line_no);
+CREATE INDEX idx_customers_lower_name ON customers (lower(name), email DESC) WHERE profile IS NOT NULL;
+
+COMMENT ON TABLE customers IS 'People who place orders';
+COMMENT ON COLUMN customers.email IS 'Primary contact address';
 
 CREATE VIEW order_totals AS
 SELECT customer_id, sum(amount) AS total FROM orders GROUP BY customer_id;
+
+CREATE MATERIALIZED VIEW customer_order_counts AS
+SELECT customer_id, count(*) AS order_count FROM orders GROUP BY customer_id;
+CREATE UNIQUE INDEX idx_customer_order_counts ON customer_order_counts (customer_id);
+
+CREATE TABLE audit_log (
+    id bigint GENERATED ALWAYS AS IDENTITY (START WITH 100) PRIMARY KEY,
+    message text
+);
+
+CREATE TABLE events (
+    id bigint NOT NULL,
+    created_at date NOT NULL,
+    PRIMARY KEY (id, created_at)
+) PARTITION BY RANGE (created_at);
+CREATE TABLE events_p2024 PARTITION OF events FOR VALUES FROM ('2024-01-01') TO ('2025-01-01');
+CREATE TABLE events_other PARTITION OF events DEFAULT;
 "#;
 
 async fn setup_pool() -> (testcontainers::ContainerAsync<Postgres>, PgPool) {
@@ -73,12 +98,24 @@ async fn test_reverse_engineer_postgres_schema() {
     assert_eq!(customers.column("profile").column_type(), ColumnType::Json);
     assert_eq!(customers.column("current_mood").column_type(), ColumnType::Enum);
     assert_eq!(customers.column("current_mood").enum_type(), Some("mood"));
+    assert_eq!(customers.description(), Some("People who place orders"));
+    assert_eq!(customers.column("email").description(), Some("Primary contact address"));
+    assert_eq!(customers.column("name").description(), None);
     assert!(customers.primary_key().is_some());
     assert!(customers
         .keys()
         .iter()
         .any(|k| k.key_type() == KeyType::Unique && k.contains_column("email")));
 
+    let lower_name_index = customers
+        .indexes()
+        .iter()
+        .find(|k| k.columns()[0].is_expression())
+        .expect("expression index present");
+    assert!(lower_name_index.columns()[0].name().contains("lower"));
+    assert_eq!(lower_name_index.columns()[1].sort_order(), Some(SortOrder::Desc));
+    assert!(lower_name_index.filter().expect("partial index predicate").contains("profile IS NOT NULL"));
+
     let orders = schema.get_table("orders");
     assert_eq!(orders.column("id").column_type(), ColumnType::Sequence);
     assert_eq!(orders.column("amount").column_type(), ColumnType::Decimal);
@@ -86,16 +123,48 @@ async fn test_reverse_engineer_postgres_schema() {
     assert_eq!(orders.column("amount").scale(), 2);
     assert!(!orders.constraints().is_empty(), "check constraint on amount should be captured");
     assert!(orders.indexes().iter().any(|k| k.contains_column("line_no")));
+    assert_eq!(orders.column("invoice_no").sequence(), Some("document_no"));
+    assert_eq!(orders.column("id").sequence(), None);
+    assert_eq!(orders.column("id").identity_mode(), None, "a serial column is not an identity");
+
+    let audit_id = schema.get_table("audit_log").column("id");
+    assert_eq!(audit_id.column_type(), ColumnType::LongSequence);
+    assert_eq!(audit_id.identity_mode(), Some(IdentityMode::Always));
+    assert_eq!(audit_id.identity_start(), Some(100));
+    assert_eq!(audit_id.identity_increment(), None);
+
+    let document_no = schema.get_optional_sequence("document_no").expect("standalone sequence present");
+    assert_eq!(document_no.start(), Some(1000));
+    assert_eq!(document_no.increment(), Some(10));
+    assert_eq!(schema.sequences().len(), 1, "the serial's owned sequence should not be listed");
 
     let relation = orders.relations().first().expect("fk relation present");
     assert_eq!(relation.from_table_name(), "orders");
-    assert_eq!(relation.from_column_name(), "customer_id");
+    assert_eq!(relation.from_column_names(), vec!["customer_id"]);
     assert_eq!(relation.to_table_name(), "customers");
-    assert_eq!(relation.to_column_name(), "id");
+    assert_eq!(relation.to_column_names(), vec!["id"]);
     assert_eq!(relation.relation_type(), RelationType::Cascade);
 
     assert!(schema.enum_types().any(|e| e.name() == "mood"));
-    assert!(schema.all_views().iter().any(|v| v.name() == "order_totals"));
+    assert!(schema.all_views().iter().any(|v| v.name() == "order_totals" && !v.is_materialized()));
+    let order_counts = schema
+        .all_views()
+        .iter()
+        .find(|v| v.name() == "customer_order_counts")
+        .expect("materialized view present");
+    assert!(order_counts.is_materialized());
+    assert_eq!(order_counts.indexes().len(), 1);
+    assert!(order_counts.indexes()[0].is_unique());
+    assert!(schema.get_optional_table("customer_order_counts").is_none());
+
+    let events = schema.get_table("events");
+    let partitioning = events.partitioning().expect("events is partitioned");
+    assert_eq!(partitioning.strategy(), PartitionStrategy::Range);
+    assert_eq!(partitioning.columns(), ["created_at"]);
+    assert_eq!(partitioning.partitions().len(), 2);
+    assert!(partitioning.partitions().iter().any(|p| p.name() == "events_other" && p.bound() == &PartitionBound::Default));
+    assert!(events.primary_key().is_some());
+    assert!(schema.get_optional_table("events_p2024").is_none(), "partitions are not listed as tables");
 
     // Round-trip the generated XML back through the existing parser to make sure the writer
     // produces XML the rest of the toolkit can actually read.
@@ -107,4 +176,6 @@ async fn test_reverse_engineer_postgres_schema() {
     let parsed_orders = parsed_schema.get_table("orders");
     assert_eq!(parsed_orders.relations().len(), 1);
     assert_eq!(parsed_orders.relations()[0].relation_type(), RelationType::Cascade);
+    assert!(parsed_schema.get_optional_sequence("document_no").is_some());
+    assert_eq!(parsed_schema.get_table("events").partitioning(), Some(partitioning));
 }
@@ -0,0 +1,102 @@
+//! Dialect-independent pieces of the aggregation triggers. An `<aggregate>` keeps a
+//! summary table up to date from the rows of the table declaring it: each source row
+//! falls into the bucket given by truncating its `date_column` to the aggregation's
+//! frequency, plus one value per group column. The summary table stores the bucket start
+//! in a column of the same name as `date_column`, the group values in each group's
+//! `destination` column, and sets `time_stamp_column` whenever a row is touched.
+
+use crate::common::generation_error::{resolve_table, GenerationError};
+use crate::common::sql_generator_settings::SqlGeneratorSettings;
+use schema_model::model::aggregation::{AggregationColumn, AggregationGroup, AggregationType};
+use schema_model::model::table::Table;
+
+/// The SQL expression yielding `group`'s value for the source row(s) referred to by `row`
+/// (`NEW`/`OLD` in a Postgres row trigger, `inserted`/`deleted` in a SQL Server trigger).
+/// A group with `source_derived_from` takes its value from the `source` column of the row
+/// that the `source_derived_from` column references through its relation.
+pub fn aggregation_group_value(
+    settings: &SqlGeneratorSettings,
+    table: &Table,
+    group: &AggregationGroup,
+    row: &str,
+) -> Result<String, GenerationError> {
+    match group.source_derived_from() {
+        Some(derived_from) => {
+            let object = || format!("aggregation group {}.{}", table.name(), group.destination());
+            let relation = table.single_column_relation(derived_from).ok_or_else(|| {
+                GenerationError::unsupported(
+                    object(),
+                    format!("its sourceDerivedFrom column '{}' has no single column relation", derived_from),
+                )
+            })?;
+            let parent_table = resolve_table(settings.database_model(), relation.to_table_name(), object)?;
+            Ok(format!(
+                "(select {} from {} where {} = {}.{})",
+                settings.quote(group.source()),
+                settings.qualified_name(parent_table.schema_name(), parent_table.name()),
+                settings.quote(relation.to_column_names()[0]),
+                row,
+                settings.quote(derived_from)
+            ))
+        }
+        None => Ok(format!("{}.{}", row, settings.quote(group.source()))),
+    }
+}
+
+/// The amount a single source row contributes to `column`: its (null-safe) value for a
+/// sum, or one for a count.
+pub fn aggregation_row_value(settings: &SqlGeneratorSettings, column: &AggregationColumn, row: &str) -> String {
+    match column.aggregation_type() {
+        AggregationType::Sum => format!("coalesce({}.{}, 0)", row, settings.quote(column.source_column())),
+        AggregationType::Count => "1".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::generate_options::GenerateOptions;
+    use schema_model::builder::{ColumnBuilder, SchemaBuilder, TableBuilder};
+    use schema_model::model::column_type::ColumnType;
+    use schema_model::model::relation::Relation;
+    use schema_model::model::database_model::DatabaseModel;
+    use schema_model::model::types::{BooleanMode, DatabaseType, ForeignKeyMode, RelationType};
+    use std::sync::Arc;
+
+    fn make_settings(model: DatabaseModel, database_type: DatabaseType) -> SqlGeneratorSettings {
+        SqlGeneratorSettings::new(database_type, &GenerateOptions::new(Arc::new(model)))
+    }
+
+    #[test]
+    fn group_value_reads_the_source_column_of_the_row() {
+        let table = TableBuilder::new(None::<&str>, "sale").build();
+        let model = DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![]);
+        let group = AggregationGroup::new("store_id", "store_id", None);
+
+        let value = aggregation_group_value(&make_settings(model, DatabaseType::Postgresql), &table, &group, "NEW").unwrap();
+
+        assert_eq!(value, "NEW.store_id");
+    }
+
+    #[test]
+    fn derived_group_value_looks_up_the_source_column_through_the_relation() {
+        let store = TableBuilder::new(None::<&str>, "store")
+            .add_column(ColumnBuilder::new(None::<&str>, "id", ColumnType::Int).required(true).build())
+            .add_column(ColumnBuilder::new(None::<&str>, "region_id", ColumnType::Int).build())
+            .build();
+        let sale = TableBuilder::new(None::<&str>, "sale")
+            .add_column(ColumnBuilder::new(None::<&str>, "store_id", ColumnType::Int).build())
+            .add_relation(Relation::new("store", "id", "sale", "store_id", RelationType::Enforce, false))
+            .build();
+        let schema = SchemaBuilder::new(None::<&str>)
+            .add_table(store)
+            .add_table(sale.clone())
+            .build();
+        let model = DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema]);
+        let group = AggregationGroup::new("region_id", "region_id", Some("store_id"));
+
+        let value = aggregation_group_value(&make_settings(model, DatabaseType::SqlServer), &sale, &group, "inserted").unwrap();
+
+        assert_eq!(value, "(select region_id from dbo.store where id = inserted.store_id)");
+    }
+}
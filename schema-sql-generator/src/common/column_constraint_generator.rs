@@ -1,4 +1,5 @@
-use crate::common::constraint_naming;
+use crate::common::column_type_generator::find_enum_type;
+use crate::common::generation_error::GenerationError;
 use crate::common::generator_context::GeneratorContext;
 use crate::common::sql_string::escape_sql_literal;
 use schema_model::model::column::Column;
@@ -6,10 +7,8 @@ use schema_model::model::column_type::ColumnType;
 use schema_model::model::table::Table;
 use schema_model::model::types::BooleanMode;
 
-const CK_PREFIX: &str = "ck_";
-
 pub trait ColumnConstraintGenerator {
-    fn column_check_constraints(&self, table: &Table) -> Vec<String>;
+    fn column_check_constraints(&self, table: &Table) -> Result<Vec<String>, GenerationError>;
 }
 
 pub struct DefaultColumnConstraintGenerator {
@@ -25,55 +24,49 @@ impl DefaultColumnConstraintGenerator {
         &self.context
     }
 
-    pub fn generate_constraint(&self, table: &Table, column: &Column) -> String {
-        let constraint_sql = self.check_constraint_sql(column);
+    pub fn generate_constraint(&self, table: &Table, column: &Column) -> Result<String, GenerationError> {
+        let constraint_sql = self.check_constraint_sql(table, column)?;
 
         if let Some(constraint_sql) = constraint_sql {
-            return format!(
+            return Ok(format!(
                 "   constraint {} {}",
-                self.constraint_name(table.name(), column.name()),
+                self.context.settings().quote_generated(&self.constraint_name(table.name(), column.name())),
                 constraint_sql
-            );
+            ));
         }
 
-        String::new()
+        Ok(String::new())
     }
 
     fn constraint_name(&self, table_name: &str, column_name: &str) -> String {
-        constraint_naming::hashed_constraint_name(CK_PREFIX, table_name, column_name)
+        let settings = self.context.settings();
+        settings.constraint_name(&settings.constraint_naming().check, table_name, &[column_name], None)
     }
 
-    fn check_constraint_sql(&self, column: &Column) -> Option<String> {
+    fn check_constraint_sql(&self, table: &Table, column: &Column) -> Result<Option<String>, GenerationError> {
         if column.column_type() == ColumnType::Boolean {
-            self.boolean_check_constraint(column)
+            Ok(self.boolean_check_constraint(column))
         } else if let Some(constraint) = column.check_constraint() {
-            Some(constraint.to_string())
+            Ok(Some(constraint.to_string()))
         } else if column.column_type() == ColumnType::Enum {
-            self.enum_check_constraint_sql(column)
+            self.enum_check_constraint_sql(table, column).map(Some)
         } else if column.has_min_or_max_value() {
-            self.min_max_constraint_sql(column)
+            Ok(self.min_max_constraint_sql(column))
         } else {
-            None
+            Ok(None)
         }
     }
 
     fn boolean_check_constraint(&self, column: &Column) -> Option<String> {
         match self.context.settings().boolean_mode() {
-            BooleanMode::YesNo => Some(format!("check({} in ('Yes','No'))", column.name())),
-            BooleanMode::YN => Some(format!("check({} in ('Y','N'))", column.name())),
+            BooleanMode::YesNo => Some(format!("check({} in ('Yes','No'))", self.context.settings().quote(column.name()))),
+            BooleanMode::YN => Some(format!("check({} in ('Y','N'))", self.context.settings().quote(column.name()))),
             BooleanMode::Native => None,
         }
     }
 
-    fn enum_check_constraint_sql(&self, column: &Column) -> Option<String> {
-        let schema_name = column.schema_name();
-        let schema = self
-            .context
-            .settings()
-            .database_model()
-            .find_schema(schema_name);
-        let enum_type = column.enum_type();
-        let enum_values = schema.get_enum_type(enum_type?).values().clone();
+    fn enum_check_constraint_sql(&self, table: &Table, column: &Column) -> Result<String, GenerationError> {
+        let enum_values = find_enum_type(&self.context, table, column)?.values();
 
         let joined_values = enum_values
             .iter()
@@ -81,16 +74,17 @@ impl DefaultColumnConstraintGenerator {
             .collect::<Vec<_>>()
             .join(",");
 
-        Some(format!("check({} in ({}))", column.name(), joined_values))
+        Ok(format!("check({} in ({}))", self.context.settings().quote(column.name()), joined_values))
     }
 
     fn min_max_constraint_sql(&self, column: &Column) -> Option<String> {
         let min_value = column.min_value();
         let max_value = column.max_value();
+        let column_name = self.context.settings().quote(column.name());
         let mut sql = String::from("check(");
 
         if let Some(min_value) = min_value {
-            sql.push_str(column.name());
+            sql.push_str(&column_name);
             sql.push_str(" >= ");
             sql.push_str(min_value.to_string().as_str());
         }
@@ -100,7 +94,7 @@ impl DefaultColumnConstraintGenerator {
         }
 
         if let Some(max_value) = max_value {
-            sql.push_str(column.name());
+            sql.push_str(&column_name);
             sql.push_str(" <= ");
             sql.push_str(max_value.to_string().as_str());
         }
@@ -112,7 +106,7 @@ impl DefaultColumnConstraintGenerator {
 }
 
 impl ColumnConstraintGenerator for DefaultColumnConstraintGenerator {
-    fn column_check_constraints(&self, table: &Table) -> Vec<String> {
+    fn column_check_constraints(&self, table: &Table) -> Result<Vec<String>, GenerationError> {
         let columns = table.columns_with_check_constraints(self.context.settings().boolean_mode());
 
         columns
@@ -1,13 +1,11 @@
 use crate::common::column_type_generator::ColumnTypeGenerator;
-use crate::common::constraint_naming;
+use crate::common::generation_error::GenerationError;
 use crate::common::generator_context::GeneratorContext;
 use schema_model::model::column::Column;
 use schema_model::model::column_type::ColumnType;
 use schema_model::model::table::Table;
 use schema_model::model::types::BooleanMode;
 
-const DF_PREFIX: &str = "df_";
-
 /// Controls how a column's `default` constraint is named in generated DDL.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum DefaultConstraintNaming {
@@ -21,13 +19,13 @@ pub enum DefaultConstraintNaming {
 }
 
 pub trait ColumnGenerator {
-    fn column_definitions(&self, table: &Table) -> Vec<String>;
+    fn column_definitions(&self, table: &Table) -> Result<Vec<String>, GenerationError>;
 
-    fn column_sql(&self, table: &Table, column: &Column) -> String;
+    fn column_sql(&self, table: &Table, column: &Column) -> Result<String, GenerationError>;
 
-    fn column_options(&self, table: &Table, column: &Column) -> String;
+    fn column_options(&self, table: &Table, column: &Column) -> Result<String, GenerationError>;
 
-    fn default_value(&self, table: &Table, column: &Column) -> Option<String>;
+    fn default_value(&self, table: &Table, column: &Column) -> Result<Option<String>, GenerationError>;
 }
 
 pub struct DefaultColumnGenerator {
@@ -49,6 +47,10 @@ impl DefaultColumnGenerator {
         }
     }
 
+    pub fn context(&self) -> &GeneratorContext {
+        &self.context
+    }
+
     fn convert_boolean_default_constraint(&self, value: bool) -> String {
         match self.context.settings().boolean_mode() {
             BooleanMode::Native => self.column_type_generator.native_boolean_literal(value),
@@ -87,45 +89,67 @@ impl DefaultColumnGenerator {
         table: &Table,
         column: &Column,
         default_constraint: Option<&str>,
-    ) -> Option<String> {
-        let schema = self
-            .context
-            .settings()
-            .database_model()
-            .find_schema(table.schema_name());
+    ) -> Result<Option<String>, GenerationError> {
         let primary_key_columns = table.primary_key_columns();
-
-        if column.required()
+        let generates_key = column.required()
             && primary_key_columns.is_some_and(|columns| columns.contains(&column.name().to_string()))
-            && table.column_relation(column).is_none()
-        {
-            return Some(self.column_type_generator.uuid_default_value_sql(schema));
-        }
+            && table.column_relation(column).is_none();
 
-        if default_constraint.is_some_and(|dc| dc.eq_ignore_ascii_case("generate_uuid()")) {
-            return Some(self.column_type_generator.uuid_default_value_sql(schema));
+        if !generates_key && !default_constraint.is_some_and(|dc| dc.eq_ignore_ascii_case("generate_uuid()")) {
+            return Ok(None);
         }
 
-        None
+        let schema = self
+            .context
+            .settings()
+            .database_model()
+            .find_schema_checked(table.schema_name())
+            .ok_or_else(|| {
+                GenerationError::unresolved(
+                    format!("table {}", table.name()),
+                    format!("schema '{}'", table.schema_name().unwrap_or_default()),
+                )
+            })?;
+        Ok(Some(self.column_type_generator.uuid_default_value_sql(schema)))
+    }
+
+    fn sequence_default_value(&self, table: &Table, column: &Column) -> Result<Option<String>, GenerationError> {
+        let Some(sequence_name) = column.sequence() else {
+            return Ok(None);
+        };
+        let sequence = self
+            .context
+            .settings()
+            .database_model()
+            .find_schema_checked(table.schema_name())
+            .and_then(|schema| schema.get_optional_sequence(sequence_name))
+            .ok_or_else(|| {
+                GenerationError::unresolved(
+                    format!("column {}.{}", table.name(), column.name()),
+                    format!("sequence '{}'", sequence_name),
+                )
+            })?;
+        Ok(self.column_type_generator.sequence_default_value_sql(sequence))
     }
 
     fn default_constraint(&self, table: &Table, column: &Column, default_value: &str) -> String {
         match self.default_constraint_naming {
             DefaultConstraintNaming::Unnamed => format!("default {}", default_value),
             DefaultConstraintNaming::NamedByColumn => {
-                format!("constraint {} default {}", column.name(), default_value)
+                format!("constraint {} default {}", self.context.settings().quote(column.name()), default_value)
             }
             DefaultConstraintNaming::NamedWithHash => {
+                let settings = self.context.settings();
                 let constraint_name =
-                    constraint_naming::hashed_constraint_name(DF_PREFIX, table.name(), column.name());
-                format!("constraint {} default {}", constraint_name, default_value)
+                    settings.constraint_name(&settings.constraint_naming().default, table.name(), &[column.name()], None);
+                format!("constraint {} default {}", settings.quote_generated(&constraint_name), default_value)
             }
         }
     }
 }
 
 impl ColumnGenerator for DefaultColumnGenerator {
-    fn column_definitions(&self, table: &Table) -> Vec<String> {
+    fn column_definitions(&self, table: &Table) -> Result<Vec<String>, GenerationError> {
         table
             .columns()
             .iter()
@@ -133,26 +157,19 @@ impl ColumnGenerator for DefaultColumnGenerator {
             .collect()
     }
 
-    fn column_sql(&self, table: &Table, column: &Column) -> String {
-        let column_options = self.column_options(table, column);
+    fn column_sql(&self, table: &Table, column: &Column) -> Result<String, GenerationError> {
+        let column_options = self.column_options(table, column)?;
+        let column_type = self.column_type_generator.column_type_sql(table, column)?;
+        let column_name = self.context.settings().quote(column.name());
 
         if column_options.is_empty() {
-            return format!(
-                "   {} {}",
-                column.name(),
-                self.column_type_generator.column_type_sql(table, column)
-            );
+            return Ok(format!("   {} {}", column_name, column_type));
         }
 
-        format!(
-            "   {} {} {}",
-            column.name(),
-            self.column_type_generator.column_type_sql(table, column),
-            column_options
-        )
+        Ok(format!("   {} {} {}", column_name, column_type, column_options))
     }
 
-    fn column_options(&self, table: &Table, column: &Column) -> String {
+    fn column_options(&self, table: &Table, column: &Column) -> Result<String, GenerationError> {
         let mut options = String::new();
 
         if column.required() {
@@ -163,7 +180,7 @@ impl ColumnGenerator for DefaultColumnGenerator {
             options.push_str("not null")
         }
 
-        let default_value = self.default_value(table, column);
+        let default_value = self.default_value(table, column)?;
 
         if let Some(default_value) = default_value {
             if !options.is_empty() {
@@ -173,16 +190,19 @@ impl ColumnGenerator for DefaultColumnGenerator {
             options.push_str(self.default_constraint(table, column, default_value.as_ref()).as_str());
         }
 
-        options.trim().to_string()
+        Ok(options.trim().to_string())
     }
 
-    fn default_value(&self, table: &Table, column: &Column) -> Option<String> {
+    fn default_value(&self, table: &Table, column: &Column) -> Result<Option<String>, GenerationError> {
         let default_constraint = column.default_constraint();
 
         match column.column_type() {
-            ColumnType::Boolean => self.boolean_default_value(default_constraint),
+            ColumnType::Boolean => Ok(self.boolean_default_value(default_constraint)),
             ColumnType::Uuid => self.uuid_default_value(table, column, default_constraint),
-            _ => default_constraint.map(String::from),
+            _ => match default_constraint {
+                Some(default_constraint) => Ok(Some(default_constraint.to_string())),
+                None => self.sequence_default_value(table, column),
+            },
         }
     }
 }
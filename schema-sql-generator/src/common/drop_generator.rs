@@ -0,0 +1,277 @@
+use crate::common::generated_statement::StatementKind;
+use crate::common::generation_error::GenerationError;
+use crate::common::generator_context::GeneratorContext;
+use crate::common::relation_generator::DefaultRelationGenerator;
+use crate::common::sql_writer::SqlWriter;
+use crate::sql_println;
+use schema_model::model::database_model::DatabaseModel;
+use schema_model::model::table::Table;
+use schema_model::model::types::{DatabaseType, ForeignKeyMode};
+use schema_model::model::view::View;
+use std::ptr;
+
+pub trait DropGenerator {
+    fn output_drops(&self) -> Result<(), GenerationError>;
+    fn output_drop_procedures(&self) -> Result<(), GenerationError>;
+    fn output_drop_views(&self) -> Result<(), GenerationError>;
+    fn output_drop_functions(&self) -> Result<(), GenerationError>;
+    fn output_drop_triggers(&self) -> Result<(), GenerationError>;
+    fn output_drop_relations(&self) -> Result<(), GenerationError>;
+    fn output_drop_tables(&self) -> Result<(), GenerationError>;
+    fn output_drop_enum_types(&self) -> Result<(), GenerationError>;
+    fn output_drop_sequences(&self) -> Result<(), GenerationError>;
+}
+
+/// Drops everything the create script makes, in the reverse of the order it makes it in.
+/// Foreign key constraints only exist, and so are only dropped, with
+/// `ForeignKeyMode::Relations`. Dialect wrappers call this with themselves so their
+/// overrides of the individual steps apply.
+pub fn output_drop_steps(generator: &dyn DropGenerator, foreign_key_mode: ForeignKeyMode) -> Result<(), GenerationError> {
+    generator.output_drop_procedures()?;
+    generator.output_drop_views()?;
+    generator.output_drop_functions()?;
+    generator.output_drop_triggers()?;
+    if foreign_key_mode == ForeignKeyMode::Relations {
+        generator.output_drop_relations()?;
+    }
+    generator.output_drop_tables()?;
+    generator.output_drop_enum_types()?;
+    generator.output_drop_sequences()
+}
+
+/// The tables of `database_model` ordered so each comes before the tables it references,
+/// which is the order they can be dropped in without tripping a foreign key. Tables caught
+/// in a reference cycle keep their name order once nothing else can go first.
+pub fn tables_in_drop_order(database_model: &DatabaseModel) -> Vec<&Table> {
+    let mut remaining = database_model.all_tables();
+    let mut created: Vec<&Table> = Vec::with_capacity(remaining.len());
+
+    let references_any = |table: &Table, tables: &[&Table]| {
+        table
+            .relations()
+            .iter()
+            .filter_map(|relation| database_model.find_table_by_qualified_name_checked(relation.to_table_name()))
+            .any(|referenced| !ptr::eq(referenced, table) && tables.iter().any(|other| ptr::eq(*other, referenced)))
+    };
+
+    while !remaining.is_empty() {
+        let ready = remaining
+            .iter()
+            .position(|table| !references_any(table, &remaining))
+            .unwrap_or(0);
+        created.push(remaining.remove(ready));
+    }
+
+    created.reverse();
+    created
+}
+
+pub struct DefaultDropGenerator {
+    context: GeneratorContext,
+}
+
+impl DefaultDropGenerator {
+    pub fn new(context: GeneratorContext) -> Self {
+        Self {
+            context,
+        }
+    }
+
+    pub fn context(&self) -> &GeneratorContext {
+        &self.context
+    }
+
+    fn output_drop(&self, writer: &mut SqlWriter, kind: StatementKind, object_type: &str, schema_name: Option<&str>, name: &str, suffix: &str) -> Result<(), GenerationError> {
+        let settings = self.context.settings();
+        writer.begin_object(kind, Some(&settings.database_type().qualified_name(schema_name, name)));
+        // Functions and procedures are created by their own SQL, under the name it gives them.
+        let quoted_name = match kind {
+            StatementKind::Function | StatementKind::Procedure => settings.qualified_generated_name(schema_name, name),
+            _ => settings.qualified_name(schema_name, name),
+        };
+        sql_println!(writer, "drop {} if exists {}{}{}", object_type, quoted_name, suffix, settings.statement_separator())?;
+        Ok(())
+    }
+
+    fn views(&self) -> Vec<View> {
+        let database_type = self.context.settings().database_type();
+        let mut views: Vec<View> = self.context.settings().database_model()
+            .schemas()
+            .iter()
+            .flat_map(|schema| schema.views(database_type))
+            .collect();
+        views.reverse();
+        views
+    }
+}
+
+impl DropGenerator for DefaultDropGenerator {
+    fn output_drops(&self) -> Result<(), GenerationError> {
+        output_drop_steps(self, self.context.settings().foreign_key_mode())
+    }
+
+    fn output_drop_procedures(&self) -> Result<(), GenerationError> {
+        let database_type = self.context.settings().database_type();
+        let database_model = self.context.settings().database_model();
+
+        self.context.with_writer(|writer| {
+            for schema in database_model.schemas().iter().rev() {
+                for procedure in schema.procedures().iter().rev().filter(|procedure| procedure.database_type() == database_type) {
+                    self.output_drop(writer, StatementKind::Procedure, "procedure", procedure.schema_name(), procedure.name(), "")?;
+                }
+            }
+            Ok(())
+        })
+    }
+
+    fn output_drop_views(&self) -> Result<(), GenerationError> {
+        let database_type = self.context.settings().database_type();
+
+        self.context.with_writer(|writer| {
+            for view in self.views() {
+                // SQLite, MySQL and DuckDB keep a materialized view's rows in an ordinary table.
+                let object_type = match (database_type, view.is_materialized()) {
+                    (DatabaseType::Postgresql, true) => "materialized view",
+                    (DatabaseType::Sqlite | DatabaseType::MySql | DatabaseType::DuckDb, true) => "table",
+                    _ => "view",
+                };
+                self.output_drop(writer, StatementKind::View, object_type, view.schema_name(), view.name(), "")?;
+            }
+            Ok(())
+        })
+    }
+
+    fn output_drop_functions(&self) -> Result<(), GenerationError> {
+        let database_type = self.context.settings().database_type();
+        let database_model = self.context.settings().database_model();
+
+        self.context.with_writer(|writer| {
+            for schema in database_model.schemas().iter().rev() {
+                for function in schema.functions().iter().rev().filter(|function| function.database_type() == database_type) {
+                    self.output_drop(writer, StatementKind::Function, "function", function.schema_name(), function.name(), "")?;
+                }
+            }
+            Ok(())
+        })
+    }
+
+    fn output_drop_triggers(&self) -> Result<(), GenerationError> {
+        Ok(())
+    }
+
+    fn output_drop_relations(&self) -> Result<(), GenerationError> {
+        let settings = self.context.settings();
+        let separator = settings.statement_separator();
+        let relation_generator = DefaultRelationGenerator::new(self.context.clone());
+
+        self.context.with_writer(|writer| {
+            for table in tables_in_drop_order(settings.database_model()) {
+                let table_name = settings.qualified_name(table.schema_name(), table.name());
+                for relation_index in 0..table.relations().len() {
+                    let relation_name = relation_generator.relation_constraint_name(table, relation_index);
+                    writer.begin_object(StatementKind::Relation, Some(&relation_name));
+                    sql_println!(writer, "alter table if exists {} drop constraint if exists {}{}", table_name, settings.quote_generated(&relation_name), separator)?;
+                }
+            }
+            Ok(())
+        })
+    }
+
+    fn output_drop_tables(&self) -> Result<(), GenerationError> {
+        let database_type = self.context.settings().database_type();
+        let cascade_suffix = if database_type == DatabaseType::Postgresql { " cascade" } else { "" };
+
+        self.context.with_writer(|writer| {
+            for table in tables_in_drop_order(self.context.settings().database_model()) {
+                self.output_drop(writer, StatementKind::Table, "table", table.schema_name(), table.name(), cascade_suffix)?;
+            }
+            Ok(())
+        })
+    }
+
+    fn output_drop_enum_types(&self) -> Result<(), GenerationError> {
+        Ok(())
+    }
+
+    fn output_drop_sequences(&self) -> Result<(), GenerationError> {
+        let database_type = self.context.settings().database_type();
+        let database_model = self.context.settings().database_model();
+        // SQLite emulates a sequence with a single-row table.
+        let object_type = if database_type == DatabaseType::Sqlite { "table" } else { "sequence" };
+
+        self.context.with_writer(|writer| {
+            for sequence in database_model.schemas().iter().rev().flat_map(|schema| schema.sequences().iter().rev()) {
+                self.output_drop(writer, StatementKind::Sequence, object_type, sequence.schema_name(), sequence.name(), "")?;
+            }
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::test_support::make_context;
+    use schema_model::builder::{ColumnBuilder, SchemaBuilder, TableBuilder};
+    use schema_model::model::column_type::ColumnType;
+    use schema_model::model::relation::Relation;
+    use schema_model::model::types::{BooleanMode, RelationType};
+
+    fn make_model() -> DatabaseModel {
+        let region = TableBuilder::new(None::<&str>, "region")
+            .add_column(ColumnBuilder::new(None::<&str>, "id", ColumnType::Int).required(true).build())
+            .build();
+        let customer = TableBuilder::new(None::<&str>, "customer")
+            .add_column(ColumnBuilder::new(None::<&str>, "id", ColumnType::Int).required(true).build())
+            .add_column(ColumnBuilder::new(None::<&str>, "region_id", ColumnType::Int).build())
+            .add_relation(Relation::new("region", "id", "customer", "region_id", RelationType::Enforce, false))
+            .build();
+        let address = TableBuilder::new(None::<&str>, "address")
+            .add_column(ColumnBuilder::new(None::<&str>, "customer_id", ColumnType::Int).build())
+            .add_relation(Relation::new("customer", "id", "address", "customer_id", RelationType::Cascade, false))
+            .build();
+        let schema = SchemaBuilder::new(None::<&str>)
+            .add_table(address)
+            .add_table(customer)
+            .add_table(region)
+            .build();
+        DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema])
+    }
+
+    #[test]
+    fn tables_in_drop_order_puts_referencing_tables_first() {
+        let model = make_model();
+
+        let names: Vec<&str> = tables_in_drop_order(&model).iter().map(|table| table.name()).collect();
+
+        assert_eq!(names, vec!["address", "customer", "region"]);
+    }
+
+    #[test]
+    fn tables_in_drop_order_keeps_every_table_of_a_reference_cycle() {
+        let a = TableBuilder::new(None::<&str>, "a")
+            .add_relation(Relation::new("b", "id", "a", "b_id", RelationType::Enforce, false))
+            .build();
+        let b = TableBuilder::new(None::<&str>, "b")
+            .add_relation(Relation::new("a", "id", "b", "a_id", RelationType::Enforce, false))
+            .build();
+        let schema = SchemaBuilder::new(None::<&str>).add_table(a).add_table(b).build();
+        let model = DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema]);
+
+        assert_eq!(tables_in_drop_order(&model).len(), 2);
+    }
+
+    #[test]
+    fn output_drops_drops_relations_before_tables() {
+        let (ctx, buffer) = make_context(make_model(), DatabaseType::Postgresql);
+
+        DefaultDropGenerator::new(ctx).output_drops().unwrap();
+
+        let output = buffer.contents();
+        assert!(output.contains("alter table if exists public.address drop constraint if exists fk_address1;"));
+        let relations = output.find("drop constraint").unwrap();
+        let tables = output.find("drop table").unwrap();
+        assert!(relations < tables);
+        assert!(output.find("drop table if exists public.address cascade;").unwrap() < output.find("drop table if exists public.region cascade;").unwrap());
+    }
+}
@@ -1,15 +1,17 @@
+use crate::common::generated_statement::StatementKind;
+use crate::common::generation_error::GenerationError;
 use crate::common::generator_context::GeneratorContext;
 use crate::common::sql_writer::SqlWriter;
 use schema_model::model::function::Function;
 
 pub trait FunctionGenerator {
-    fn output_functions(&self);
+    fn output_functions(&self) -> Result<(), GenerationError>;
     fn output_function(
         &self,
         writer: &mut SqlWriter,
         statement_separator: &str,
         function: &Function,
-    );
+    ) -> Result<(), GenerationError>;
 
 }
 
@@ -35,33 +37,34 @@ impl DefaultFunctionGenerator {
     /// struct would otherwise never see its own override invoked - callers that need
     /// their override honored should pass `self` (as a `&dyn FunctionGenerator`) here
     /// instead of calling `output_functions` directly.
-    pub fn output_functions_via(&self, generator: &dyn FunctionGenerator) {
+    pub fn output_functions_via(&self, generator: &dyn FunctionGenerator) -> Result<(), GenerationError> {
         let database_type = self.context.settings().database_type();
         let statement_separator = self.context.settings().statement_separator();
         let database_model = self.context.settings().database_model();
 
         self.context.with_writer(|writer| {
-            database_model.schemas().iter().for_each(|schema| {
-                schema
-                    .functions()
-                    .iter()
-                    .filter(|function| function.database_type() == database_type)
-                    .for_each(|function| {
-                        generator.output_function(writer, statement_separator, function);
-                    })
-            });
-        });
+            for schema in database_model.schemas() {
+                for function in schema.functions().iter().filter(|function| function.database_type() == database_type) {
+                    writer.begin_object(StatementKind::Function, Some(function.name()));
+                    generator
+                        .output_function(writer, statement_separator, function)
+                        .map_err(|e| e.within(|| format!("function {}", function.name())))?;
+                }
+            }
+            Ok(())
+        })
     }
 }
 
 impl FunctionGenerator for DefaultFunctionGenerator {
-    fn output_functions(&self) {
-        self.output_functions_via(self);
+    fn output_functions(&self) -> Result<(), GenerationError> {
+        self.output_functions_via(self)
     }
 
-    fn output_function(&self, writer: &mut SqlWriter, statement_separator: &str, function: &Function) {
-        writer.print(function.sql());
-        writer.println(statement_separator);
-        writer.newline();
+    fn output_function(&self, writer: &mut SqlWriter, statement_separator: &str, function: &Function) -> Result<(), GenerationError> {
+        writer.print(function.sql())?;
+        writer.println(statement_separator)?;
+        writer.newline()?;
+        Ok(())
     }
 }
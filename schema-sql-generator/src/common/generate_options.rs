@@ -1,32 +1,49 @@
+use crate::common::constraint_naming::ConstraintNaming;
 use crate::common::output_mode::OutputMode;
-use crate::common::print_writer::PrintWriter;
 use schema_model::model::database_model::DatabaseModel;
-use schema_model::model::types::{BooleanMode, ForeignKeyMode};
-use std::cell::RefCell;
-use std::rc::Rc;
+use schema_model::model::naming::NamingConvention;
+use schema_model::model::types::{BooleanMode, ForeignKeyMode, IdentifierQuoting, IdentityMode};
+use std::sync::Arc;
 
+/// What to generate. Holds no writer, so the options can be shared between threads and
+/// generation can run wherever they are sent.
+#[derive(Debug, Clone)]
 pub struct GenerateOptions {
-    pub database_model: Rc<DatabaseModel>,
-    pub writer: Rc<RefCell<PrintWriter>>,
+    pub database_model: Arc<DatabaseModel>,
     pub foreign_key_mode: ForeignKeyMode,
     pub boolean_mode: BooleanMode,
     pub output_mode: OutputMode,
     pub target_postgres_version: u32,
+    /// Whether a MySQL script is for MariaDB, which adds sequences to MySQL's SQL.
+    pub target_mariadb: bool,
     pub emit_postgres_extensions: bool,
     pub extension_check_user: Option<String>,
+    pub identity_mode: IdentityMode,
+    /// Guard each create with an existence check so the script can be run again.
+    pub idempotent: bool,
+    pub identifier_quoting: IdentifierQuoting,
+    /// How model names are spelled in the database: tables, columns, views, sequences and
+    /// the parts generated constraint, index and trigger names are built from.
+    pub naming_convention: NamingConvention,
+    pub constraint_naming: ConstraintNaming,
 }
 
 impl GenerateOptions {
-    pub fn new(database_model: Rc<DatabaseModel>, writer: Rc<RefCell<PrintWriter>>) -> Self {
+    pub fn new(database_model: Arc<DatabaseModel>) -> Self {
         Self {
             database_model,
-            writer,
             foreign_key_mode: ForeignKeyMode::Relations,
             boolean_mode: BooleanMode::Native,
             output_mode: OutputMode::All,
             target_postgres_version: 0,
+            target_mariadb: false,
             emit_postgres_extensions: true,
             extension_check_user: None,
+            identity_mode: IdentityMode::Serial,
+            idempotent: false,
+            identifier_quoting: IdentifierQuoting::WhenNeeded,
+            naming_convention: NamingConvention::Preserve,
+            constraint_naming: ConstraintNaming::default(),
         }
     }
 }
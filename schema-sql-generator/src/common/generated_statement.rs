@@ -0,0 +1,118 @@
+use crate::common::sql_split::{skip_leading_comments, split_sql_statements};
+use schema_model::model::types::DatabaseType;
+
+/// The kind of object a generated statement creates or changes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StatementKind {
+    Extension,
+    Schema,
+    Type,
+    Sequence,
+    Table,
+    Index,
+    Relation,
+    Trigger,
+    Function,
+    View,
+    Procedure,
+    OtherSql,
+}
+
+/// One executable statement of a generated script, without its statement separator.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GeneratedStatement {
+    pub kind: StatementKind,
+    /// The object the statement belongs to; `None` for free-form `<otherSql>` and the
+    /// extension block, which belong to no single object.
+    pub object_name: Option<String>,
+    pub sql: String,
+}
+
+impl GeneratedStatement {
+    /// The statement's SQL after the comments it starts with, such as the header naming
+    /// the object, so it can be told apart by its first keyword.
+    pub fn sql_after_comments(&self) -> &str {
+        skip_leading_comments(&self.sql)
+    }
+}
+
+/// Where the SQL for one object starts in the script being written.
+#[derive(Debug, Clone)]
+pub(crate) struct ObjectMarker {
+    pub position: usize,
+    pub kind: StatementKind,
+    pub object_name: Option<String>,
+}
+
+/// Splits `script` into statements, attributing each to the object whose marker precedes
+/// it. Pieces holding nothing but comments are dropped, since there is nothing to run.
+pub(crate) fn split_generated_script(
+    script: &str,
+    markers: &[ObjectMarker],
+    database_type: DatabaseType,
+) -> Vec<GeneratedStatement> {
+    let mut statements = Vec::new();
+
+    for (index, marker) in markers.iter().enumerate() {
+        let end = markers.get(index + 1).map_or(script.len(), |next| next.position);
+        for sql in split_sql_statements(&script[marker.position..end], database_type) {
+            if !is_only_comments(&sql) {
+                statements.push(GeneratedStatement {
+                    kind: marker.kind,
+                    object_name: marker.object_name.clone(),
+                    sql,
+                });
+            }
+        }
+    }
+
+    statements
+}
+
+fn is_only_comments(sql: &str) -> bool {
+    skip_leading_comments(sql).is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn marker(position: usize, kind: StatementKind, object_name: &str) -> ObjectMarker {
+        ObjectMarker {
+            position,
+            kind,
+            object_name: Some(object_name.to_string()),
+        }
+    }
+
+    #[test]
+    fn statements_are_attributed_to_the_preceding_marker() {
+        let script = "/* users */\ndrop table if exists users;\ncreate table users (id int);\n\ncreate index ix_users1 on users (id);\n";
+        let index_start = script.find("create index").unwrap();
+        let markers = vec![
+            marker(0, StatementKind::Table, "users"),
+            marker(index_start, StatementKind::Index, "ix_users1"),
+        ];
+
+        let statements = split_generated_script(script, &markers, DatabaseType::Postgresql);
+
+        assert_eq!(statements.len(), 3);
+        assert_eq!(statements[0].kind, StatementKind::Table);
+        assert_eq!(statements[0].sql, "/* users */\ndrop table if exists users");
+        assert_eq!(statements[0].sql_after_comments(), "drop table if exists users");
+        assert_eq!(statements[1].sql, "create table users (id int)");
+        assert_eq!(statements[2].kind, StatementKind::Index);
+        assert_eq!(statements[2].object_name.as_deref(), Some("ix_users1"));
+    }
+
+    #[test]
+    fn comment_only_pieces_are_dropped() {
+        let script = "create materialized view totals as select 1;\n/* refresh with: refresh materialized view totals; */\n";
+        let markers = vec![marker(0, StatementKind::View, "totals")];
+
+        let statements = split_generated_script(script, &markers, DatabaseType::Postgresql);
+
+        assert_eq!(statements.len(), 1);
+        assert_eq!(statements[0].sql, "create materialized view totals as select 1");
+    }
+}
@@ -0,0 +1,105 @@
+use schema_model::model::database_model::DatabaseModel;
+use schema_model::model::table::Table;
+use thiserror::Error;
+
+/// Why generating a script failed, naming the object being generated when it's known.
+#[derive(Debug, Error)]
+pub enum GenerationError {
+    #[error("I/O error while generating {}: {source}", object.as_deref().unwrap_or("the script"))]
+    Io {
+        object: Option<String>,
+        #[source]
+        source: std::io::Error,
+    },
+    /// `object` refers to a table, schema, column or type that isn't in the model.
+    #[error("{object} refers to {reference}, which does not exist")]
+    UnresolvedReference { object: String, reference: String },
+    /// `object` needs something the target database can't express.
+    #[error("{object} can't be generated: {reason}")]
+    Unsupported { object: String, reason: String },
+}
+
+impl GenerationError {
+    pub fn unresolved<O: Into<String>, R: Into<String>>(object: O, reference: R) -> Self {
+        GenerationError::UnresolvedReference {
+            object: object.into(),
+            reference: reference.into(),
+        }
+    }
+
+    pub fn unsupported<O: Into<String>, R: Into<String>>(object: O, reason: R) -> Self {
+        GenerationError::Unsupported {
+            object: object.into(),
+            reason: reason.into(),
+        }
+    }
+
+    /// The object that failed, if it's known.
+    pub fn object(&self) -> Option<&str> {
+        match self {
+            GenerationError::Io { object, .. } => object.as_deref(),
+            GenerationError::UnresolvedReference { object, .. } | GenerationError::Unsupported { object, .. } => {
+                Some(object)
+            }
+        }
+    }
+
+    /// Attributes an I/O error to `object` unless it already names one; write failures
+    /// surface deep in the writer, before anything knows what was being written.
+    pub fn within(self, object: impl FnOnce() -> String) -> Self {
+        match self {
+            GenerationError::Io { object: None, source } => GenerationError::Io {
+                object: Some(object()),
+                source,
+            },
+            other => other,
+        }
+    }
+}
+
+impl From<std::io::Error> for GenerationError {
+    fn from(source: std::io::Error) -> Self {
+        GenerationError::Io { object: None, source }
+    }
+}
+
+/// Looks up the table `qualified_name` refers to, failing with an error that names
+/// `object` as the one holding the dangling reference.
+pub fn resolve_table<'a>(
+    database_model: &'a DatabaseModel,
+    qualified_name: &str,
+    object: impl FnOnce() -> String,
+) -> Result<&'a Table, GenerationError> {
+    database_model
+        .find_table_by_qualified_name_checked(qualified_name)
+        .ok_or_else(|| GenerationError::unresolved(object(), format!("table '{}'", qualified_name)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Error, ErrorKind};
+
+    #[test]
+    fn within_names_the_object_an_io_error_happened_in() {
+        let error = GenerationError::from(Error::from(ErrorKind::WriteZero)).within(|| "table invoice".to_string());
+
+        assert_eq!(error.object(), Some("table invoice"));
+        assert!(error.to_string().starts_with("I/O error while generating table invoice"));
+
+        let error = error.within(|| "view totals".to_string());
+        assert_eq!(error.object(), Some("table invoice"));
+    }
+
+    #[test]
+    fn unresolved_reference_keeps_its_object() {
+        let error = GenerationError::unresolved("relation order.customer_id", "table 'customer'")
+            .within(|| "table order".to_string());
+
+        assert_eq!(error.object(), Some("relation order.customer_id"));
+        assert_eq!(
+            error.to_string(),
+            "relation order.customer_id refers to table 'customer', which does not exist"
+        );
+    }
+}
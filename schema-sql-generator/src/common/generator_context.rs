@@ -29,11 +29,11 @@ impl GeneratorContext {
         Rc::clone(&self.writer)
     }
 
-    pub fn with_writer<F>(&self, f: F)
+    pub fn with_writer<F, R>(&self, f: F) -> R
     where
-        F: FnOnce(&mut SqlWriter),
+        F: FnOnce(&mut SqlWriter) -> R,
     {
         let mut writer = self.writer.borrow_mut();
-        f(&mut writer);
+        f(&mut writer)
     }
 }
@@ -1,47 +1,162 @@
 use crate::common::generate_options::GenerateOptions;
+use crate::common::generated_statement::{split_generated_script, GeneratedStatement, ObjectMarker};
+use crate::common::generation_error::{resolve_table, GenerationError};
 use crate::common::generator_context::GeneratorContext;
+use crate::common::print_writer::PrintWriter;
 use crate::common::sql_generator::SqlGenerator;
 use crate::common::sql_generator_settings::SqlGeneratorSettings;
 use crate::common::sql_writer::SqlWriter;
+use crate::common::trigger_generator::{TableTriggers, TriggerGenerator};
+use crate::duckdb::duckdb_generator::DuckDbGenerator;
+use crate::duckdb::duckdb_trigger_generator::DuckDbTriggerGenerator;
+use crate::mysql::mysql_generator::MySqlGenerator;
+use crate::mysql::mysql_trigger_generator::MySqlTriggerGenerator;
 use crate::postgresql::postgres_generator::PostgresGenerator;
+use crate::postgresql::postgres_trigger_generator::PostgresTriggerGenerator;
 use crate::sqlite::sqlite_generator::SqliteGenerator;
+use crate::sqlite::sqlite_trigger_generator::SqliteTriggerGenerator;
 use crate::sqlserver::sqlserver_generator::SqlServerGenerator;
+use crate::sqlserver::sqlserver_trigger_generator::SqlServerTriggerGenerator;
 use schema_model::model::types::DatabaseType;
+use std::cell::RefCell;
+use std::io::{self, Write};
+use std::rc::Rc;
 use std::str::FromStr;
 
 pub enum GeneratorType {
     Postgresql,
     Sqlite,
     SqlServer,
+    MySql,
+    DuckDb,
 }
 
 impl GeneratorType {
-    pub fn new_generator(&self, options: GenerateOptions) -> Box<dyn SqlGenerator> {
-        let context = self.build_context(&options);
+    pub fn new_generator(&self, options: &GenerateOptions, writer: PrintWriter) -> Box<dyn SqlGenerator> {
+        let context = self.build_context(options, writer);
         match self {
             GeneratorType::Postgresql => Box::new(PostgresGenerator::new(context)),
             GeneratorType::Sqlite => Box::new(SqliteGenerator::new(context)),
             GeneratorType::SqlServer => Box::new(SqlServerGenerator::new(context)),
+            GeneratorType::MySql => Box::new(MySqlGenerator::new(context)),
+            GeneratorType::DuckDb => Box::new(DuckDbGenerator::new(context)),
         }
     }
 
-    pub fn generate(&self, options: GenerateOptions) {
-        self.new_generator(options).generate();
+    /// Writes the whole script to `writer` and flushes it, so a failed write is reported
+    /// here rather than lost when the writer is dropped.
+    pub fn generate(&self, options: &GenerateOptions, writer: Box<dyn Write>) -> Result<(), GenerationError> {
+        let generator = self.new_generator(options, PrintWriter::new(writer));
+        generator.generate()?;
+        generator.context().with_writer(|writer| writer.flush())?;
+        Ok(())
     }
 
-    fn build_context(&self, options: &GenerateOptions) -> GeneratorContext {
-        let db_type = match self {
+    /// Generates the whole script in memory.
+    pub fn generate_script(&self, options: &GenerateOptions) -> Result<String, GenerationError> {
+        self.generate_in_memory(options).map(|(script, _)| script)
+    }
+
+    /// Generates the script in memory as separate statements, each tagged with the kind
+    /// and name of the object it belongs to, ready to be executed one at a time.
+    pub fn generate_statements(&self, options: &GenerateOptions) -> Result<Vec<GeneratedStatement>, GenerationError> {
+        let (script, markers) = self.generate_in_memory(options)?;
+        Ok(split_generated_script(&script, &markers, self.database_type()))
+    }
+
+    /// Generates the triggers the create script gives the table `table_name` of the model,
+    /// for a migration that has to bring the table's triggers up to date.
+    pub fn generate_table_triggers(&self, options: &GenerateOptions, table_name: &str) -> Result<TableTriggers, GenerationError> {
+        let table = resolve_table(&options.database_model, table_name, || format!("the triggers of table {}", table_name))?;
+        let buffer = MemoryBuffer::default();
+        let context = self.build_context(options, PrintWriter::new(Box::new(buffer.clone())));
+        let trigger_generator = self.new_trigger_generator(context.clone());
+        trigger_generator.output_table_triggers(table)?;
+        let (script, markers) = buffer.script(&context)?;
+
+        let names = trigger_generator.trigger_names(table);
+        let unneeded_names = trigger_generator
+            .possible_trigger_names(table.name())
+            .into_iter()
+            .filter(|name| !names.contains(name))
+            .collect();
+        Ok(TableTriggers {
+            statements: split_generated_script(&script, &markers, self.database_type()),
+            unneeded_names,
+        })
+    }
+
+    /// The unqualified names of every trigger the create script can give a table named
+    /// `table_name`, such as the ones a renamed table still has under its old name.
+    pub fn possible_trigger_names(&self, options: &GenerateOptions, table_name: &str) -> Vec<String> {
+        let context = self.build_context(options, PrintWriter::new(Box::new(io::sink())));
+        self.new_trigger_generator(context).possible_trigger_names(table_name)
+    }
+
+    pub fn database_type(&self) -> DatabaseType {
+        match self {
             GeneratorType::Postgresql => DatabaseType::Postgresql,
             GeneratorType::Sqlite => DatabaseType::Sqlite,
             GeneratorType::SqlServer => DatabaseType::SqlServer,
-        };
+            GeneratorType::MySql => DatabaseType::MySql,
+            GeneratorType::DuckDb => DatabaseType::DuckDb,
+        }
+    }
+
+    fn generate_in_memory(&self, options: &GenerateOptions) -> Result<(String, Vec<ObjectMarker>), GenerationError> {
+        let buffer = MemoryBuffer::default();
+        let generator = self.new_generator(options, PrintWriter::new(Box::new(buffer.clone())));
+        generator.generate()?;
+        buffer.script(generator.context())
+    }
+
+    fn new_trigger_generator(&self, context: GeneratorContext) -> Box<dyn TriggerGenerator> {
+        match self {
+            GeneratorType::Postgresql => Box::new(PostgresTriggerGenerator::new(context)),
+            GeneratorType::Sqlite => Box::new(SqliteTriggerGenerator::new(context)),
+            GeneratorType::SqlServer => Box::new(SqlServerTriggerGenerator::new(context)),
+            GeneratorType::MySql => Box::new(MySqlTriggerGenerator::new(context)),
+            GeneratorType::DuckDb => Box::new(DuckDbTriggerGenerator::new(context)),
+        }
+    }
+
+    fn build_context(&self, options: &GenerateOptions, writer: PrintWriter) -> GeneratorContext {
         GeneratorContext::new(
-            SqlGeneratorSettings::new(db_type, options),
-            SqlWriter::new(options.writer.clone()),
+            SqlGeneratorSettings::new(self.database_type(), options),
+            SqlWriter::new(Rc::new(RefCell::new(writer))),
         )
     }
 }
 
+/// The in-memory destination of `generate_script`, `generate_statements` and
+/// `generate_table_triggers`.
+#[derive(Clone, Default)]
+struct MemoryBuffer(Rc<RefCell<Vec<u8>>>);
+
+impl MemoryBuffer {
+    /// Flushes the writer of `context` into the buffer and returns what it holds, with the
+    /// markers of the objects written.
+    fn script(&self, context: &GeneratorContext) -> Result<(String, Vec<ObjectMarker>), GenerationError> {
+        let markers = context.with_writer(|writer| -> Result<_, GenerationError> {
+            writer.flush()?;
+            Ok(writer.markers().to_vec())
+        })?;
+
+        let script = String::from_utf8_lossy(&self.0.borrow()).into_owned();
+        Ok((script, markers))
+    }
+}
+
+impl Write for MemoryBuffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.borrow_mut().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
 impl FromStr for GeneratorType {
     type Err = String;
 
@@ -50,7 +165,113 @@ impl FromStr for GeneratorType {
             "postgresql" => Ok(GeneratorType::Postgresql),
             "sqlite" => Ok(GeneratorType::Sqlite),
             "sqlserver" => Ok(GeneratorType::SqlServer),
+            "mysql" | "mariadb" => Ok(GeneratorType::MySql),
+            "duckdb" => Ok(GeneratorType::DuckDb),
             _ => Err(format!("Unknown generator type: {}", s)),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::generated_statement::StatementKind;
+    use schema_model::builder::{ColumnBuilder, SchemaBuilder, TableBuilder};
+    use schema_model::model::column_type::ColumnType;
+    use schema_model::model::database_model::DatabaseModel;
+    use schema_model::model::key::{Key, KeyColumn};
+    use schema_model::model::relation::Relation;
+    use schema_model::model::types::{BooleanMode, ForeignKeyMode, KeyType, RelationType};
+    use std::sync::Arc;
+
+    fn make_options() -> GenerateOptions {
+        let customer = TableBuilder::new(None::<&str>, "customer")
+            .add_column(ColumnBuilder::new(None::<&str>, "id", ColumnType::Int).required(true).build())
+            .build();
+        let order = TableBuilder::new(None::<&str>, "order")
+            .add_column(ColumnBuilder::new(None::<&str>, "customer_id", ColumnType::Int).build())
+            .add_index(Key::new_full(KeyType::Index, vec![KeyColumn::new("customer_id")], false, false, false, None::<String>))
+            .add_relation(Relation::new("customer", "id", "order", "customer_id", RelationType::Enforce, false))
+            .build();
+        let schema = SchemaBuilder::new(None::<&str>).add_table(customer).add_table(order).build();
+        let model = DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema]);
+        GenerateOptions::new(Arc::new(model))
+    }
+
+    #[test]
+    fn generate_options_can_be_sent_to_another_thread() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<GenerateOptions>();
+
+        let options = make_options();
+        let script = std::thread::spawn(move || GeneratorType::Postgresql.generate_script(&options))
+            .join()
+            .unwrap()
+            .unwrap();
+
+        assert!(script.contains("create table public.\"order\""));
+    }
+
+    #[test]
+    fn generate_script_matches_generate_to_a_writer() {
+        let options = make_options();
+        let buffer = MemoryBuffer::default();
+        GeneratorType::Sqlite.generate(&options, Box::new(buffer.clone())).unwrap();
+
+        let script = GeneratorType::Sqlite.generate_script(&options).unwrap();
+
+        assert_eq!(script, String::from_utf8_lossy(&buffer.0.borrow()));
+    }
+
+    #[test]
+    fn generate_statements_tags_each_statement_with_its_object() {
+        let statements = GeneratorType::Postgresql.generate_statements(&make_options()).unwrap();
+
+        let named = |kind: StatementKind| {
+            statements
+                .iter()
+                .filter(|statement| statement.kind == kind)
+                .map(|statement| (statement.object_name.as_deref().unwrap_or_default(), statement.sql.as_str()))
+                .collect::<Vec<_>>()
+        };
+
+        let tables = named(StatementKind::Table);
+        assert!(tables.iter().any(|(name, sql)| *name == "public.order" && sql.starts_with("create table public.\"order\"")));
+        assert!(tables.iter().all(|(_, sql)| !sql.contains("create index")));
+        let indexes = named(StatementKind::Index);
+        assert_eq!(indexes.len(), 1);
+        assert_eq!(indexes[0].0, "ix_order1");
+        let relations = named(StatementKind::Relation);
+        assert_eq!(relations.len(), 1);
+        assert!(relations[0].1.starts_with("alter table public.\"order\" add constraint fk_order1"));
+        assert!(statements.iter().all(|statement| !statement.sql.trim_end().ends_with(';')));
+    }
+
+    #[test]
+    fn generate_table_triggers_writes_only_that_tables_triggers() {
+        let mut options = make_options();
+        options.foreign_key_mode = ForeignKeyMode::Triggers;
+
+        let triggers = GeneratorType::Postgresql.generate_table_triggers(&options, "order").unwrap();
+
+        assert_eq!(triggers.unneeded_names, vec!["order_delete"]);
+        assert!(triggers.statements.iter().all(|statement| statement.kind == StatementKind::Trigger));
+        let sql: Vec<&str> = triggers.statements.iter().map(|statement| statement.sql_after_comments()).collect();
+        assert!(sql[0].starts_with("create or replace function public.order_update() returns trigger"));
+        assert_eq!(sql[1], "drop trigger if exists order_update on public.\"order\" cascade");
+        assert!(sql[2].starts_with("create trigger order_update after insert or update on public.\"order\""));
+        assert_eq!(sql.len(), 3);
+
+        let error = GeneratorType::Postgresql.generate_table_triggers(&options, "invoice").unwrap_err();
+        assert_eq!(error.to_string(), "the triggers of table invoice refers to table 'invoice', which does not exist");
+    }
+
+    #[test]
+    fn possible_trigger_names_cover_every_event_the_dialect_triggers_on() {
+        let options = make_options();
+
+        assert_eq!(GeneratorType::Postgresql.possible_trigger_names(&options, "Order"), vec!["order_delete", "order_update"]);
+        assert_eq!(GeneratorType::MySql.possible_trigger_names(&options, "order"), vec!["order_delete", "order_insert", "order_update"]);
+        assert!(GeneratorType::DuckDb.possible_trigger_names(&options, "order").is_empty());
+    }
+}
@@ -1,14 +1,18 @@
+use crate::common::generated_statement::StatementKind;
+use crate::common::generation_error::GenerationError;
 use crate::common::generator_context::GeneratorContext;
+use crate::common::sql_generator_settings::SqlGeneratorSettings;
+use crate::common::sql_string::escape_sql_literal;
 use crate::common::sql_writer::SqlWriter;
-use schema_model::model::key::Key;
+use schema_model::model::key::{Key, KeyColumn};
 use schema_model::model::table::Table;
+use schema_model::model::types::{DatabaseType, IndexMethod};
 
-const IX_PREFIX: &str = "ix_";
 
 pub trait IndexGenerator {
-    fn output_indexes(&self);
+    fn output_indexes(&self) -> Result<(), GenerationError>;
 
-    fn output_indexes_for_table(&self, writer: &mut SqlWriter, table: &Table);
+    fn output_indexes_for_table(&self, writer: &mut SqlWriter, table: &Table) -> Result<(), GenerationError>;
 
     fn output_index(
         &self,
@@ -17,7 +21,7 @@ pub trait IndexGenerator {
         table: &Table,
         key_name: &str,
         key: &Key,
-    );
+    ) -> Result<(), GenerationError>;
 
     fn index_options(&self, key: &Key) -> Option<String>;
 }
@@ -37,60 +41,41 @@ impl DefaultIndexGenerator {
 }
 
 impl IndexGenerator for DefaultIndexGenerator {
-    fn output_indexes(&self) {
+    fn output_indexes(&self) -> Result<(), GenerationError> {
         let database_model = self.context.settings().database_model();
 
         self.context.with_writer(|writer| {
-            database_model.schemas().iter().for_each(|schema| {
-                schema.tables().iter().for_each(|table| {
-                    self.output_indexes_for_table(writer, table);
-                });
-            });
-        });
+            for schema in database_model.schemas() {
+                for table in schema.tables() {
+                    self.output_indexes_for_table(writer, table)?;
+                }
+            }
+            Ok(())
+        })
     }
 
-    fn output_indexes_for_table(&self, writer: &mut SqlWriter, table: &Table) {
+    fn output_indexes_for_table(&self, writer: &mut SqlWriter, table: &Table) -> Result<(), GenerationError> {
         if !table.indexes().is_empty() {
-            let max_key_name_length = self
-                .context()
-                .settings()
-                .database_type()
-                .max_key_name_length();
-
             for (key_index, key) in table
                 .indexes()
                 .iter()
                 .filter(|key| key.is_index())
                 .enumerate()
             {
-                let suffix_str = (key_index + 1).to_string();
-                let mut key_name = format!("{}{}{}", IX_PREFIX, table.name(), suffix_str).to_lowercase();
-
-                if key_name.len() > max_key_name_length {
-                    // Reserve space for the *actual* suffix length, not a hard-coded
-                    // budget - a table with >=10 indexes needs a 2-digit suffix, and a
-                    // fixed 4-char reservation (3-char prefix + 1-digit suffix) would
-                    // produce an identifier over the length limit.
-                    let max_name_len = max_key_name_length.saturating_sub(IX_PREFIX.len() + suffix_str.len());
-                    let truncated = table
-                        .name()
-                        .chars()
-                        .take(max_name_len)
-                        .collect::<String>();
-                    key_name = format!("{}{}{}", IX_PREFIX, truncated, suffix_str).to_lowercase();
-                }
+                let key_name = index_name(self.context().settings(), table.name(), key, key_index + 1);
 
                 self.output_index(
                     writer,
                     self.context().settings().statement_separator(),
                     table,
-                    key_name.as_str(),
+                    &key_name,
                     key,
-                );
+                )?;
             }
 
-            writer.newline();
+            writer.newline()?;
         }
+        Ok(())
     }
 
     fn output_index(
@@ -100,54 +85,139 @@ impl IndexGenerator for DefaultIndexGenerator {
         table: &Table,
         key_name: &str,
         key: &Key,
-    ) {
-        let index_options = self.index_options(key);
-        let fully_qualified_table_name = table.fully_qualified_table_name(self.context().settings().database_type());
-        let index_columns = key
-            .columns()
-            .iter()
-            .map(|column| column.name())
-            .collect::<Vec<_>>()
-            .join(", ");
-
-        if let Some(index_options) = index_options {
-            writer.println(
-                format!(
-                    "create {}index {} on {} ({}) {}{}",
-                    if key.is_unique() { "unique " } else { "" },
-                    key_name,
-                    fully_qualified_table_name,
-                    index_columns,
-                    index_options,
-                    statement_separator
-                )
-                    .as_str(),
-            );
-        } else {
-            writer.println(
-                format!(
-                    "create {}index {} on {} ({}){}",
-                    if key.is_unique() { "unique " } else { "" },
-                    key_name,
-                    fully_qualified_table_name,
-                    index_columns,
-                    statement_separator
-                )
-                    .as_str(),
-            );
+    ) -> Result<(), GenerationError> {
+        let database_type = self.context().settings().database_type();
+        let fully_qualified_table_name = self.context().settings().qualified_name(table.schema_name(), table.name());
+        let index_columns = index_columns_sql(self.context().settings(), table.name(), key)?;
+        // Postgres names an index's access method before the columns and MySQL after
+        // them, knowing only btree and hash; elsewhere the method is a storage hint with
+        // no equivalent, so it is left out.
+        let (method, trailing_method) = match (database_type, key.method()) {
+            (DatabaseType::Postgresql, Some(method)) => (format!(" using {}", method.sql()), String::new()),
+            (DatabaseType::MySql, Some(method @ (IndexMethod::BTree | IndexMethod::Hash))) => {
+                (String::new(), format!(" using {}", method.sql()))
+            }
+            _ => (String::new(), String::new()),
+        };
+        if let (DatabaseType::DuckDb, Some(filter)) = (database_type, key.filter()) {
+            return Err(GenerationError::unsupported(
+                format!("index {}", key_name),
+                format!("DuckDB has no partial indexes, so the filter '{}' can't be applied", filter),
+            ));
         }
+        let filter = key
+            .filter()
+            .map(|filter| format!(" where {}", filter))
+            .unwrap_or_default();
+        let index_options = self
+            .index_options(key)
+            .map(|index_options| format!(" {}", index_options))
+            .unwrap_or_default();
+
+        writer.begin_object(StatementKind::Index, Some(key_name));
+        let if_not_exists = match (database_type, self.context().settings().idempotent()) {
+            (DatabaseType::SqlServer, true) => {
+                writer.println(
+                    format!(
+                        "if not exists (select 1 from sys.indexes where name = '{}' and object_id = object_id('{}'))",
+                        escape_sql_literal(key_name),
+                        escape_sql_literal(&fully_qualified_table_name)
+                    )
+                        .as_str(),
+                )?;
+                ""
+            }
+            (_, true) => "if not exists ",
+            (_, false) => "",
+        };
+
+        writer.println(
+            format!(
+                "create {}index {}{} on {}{} ({}){}{}{}{}",
+                if key.is_unique() { "unique " } else { "" },
+                if_not_exists,
+                self.context().settings().quote_generated(key_name),
+                fully_qualified_table_name,
+                method,
+                index_columns,
+                trailing_method,
+                filter,
+                index_options,
+                statement_separator
+            )
+                .as_str(),
+        )?;
+        Ok(())
     }
 
-    fn index_options(&self, _key: &Key) -> Option<String> {
-        None
+    /// SQL Server compresses an index's pages when asked; no other dialect has an option
+    /// for it.
+    fn index_options(&self, key: &Key) -> Option<String> {
+        match (self.context.settings().database_type(), key.compression()) {
+            (DatabaseType::SqlServer, Some(level)) => Some(format!("with (data_compression = {})", level.sql())),
+            _ => None,
+        }
     }
 }
 
+/// The generated name of the `position`th (1-based) index on a table or materialized view,
+/// as `ConstraintNamer::index_name` gives it.
+pub(crate) fn index_name(settings: &SqlGeneratorSettings, owner_name: &str, key: &Key, position: usize) -> String {
+    settings.constraint_namer().index_name(owner_name, key, position)
+}
+
+/// The parenthesized column list of an index, without the parentheses.
+pub(crate) fn index_columns_sql(settings: &SqlGeneratorSettings, owner_name: &str, key: &Key) -> Result<String, GenerationError> {
+    Ok(key.columns()
+        .iter()
+        .map(|column| index_column_sql(settings, owner_name, column))
+        .collect::<Result<Vec<_>, _>>()?
+        .join(", "))
+}
+
+fn index_column_sql(settings: &SqlGeneratorSettings, owner_name: &str, column: &KeyColumn) -> Result<String, GenerationError> {
+    let database_type = settings.database_type();
+    let mut sql = if column.is_expression() {
+        // SQL Server and MariaDB can only index an expression through a computed column.
+        let computed_column = match database_type {
+            DatabaseType::SqlServer => Some(("SQL Server", "computed")),
+            DatabaseType::MySql => Some(("MariaDB", "generated")),
+            _ => None,
+        };
+        if let Some((database, kind)) = computed_column {
+            return Err(GenerationError::unsupported(
+                format!("index on {}", owner_name),
+                format!(
+                    "the expression '{}' can't be indexed directly on {}; add a {} column instead",
+                    column.name(),
+                    database,
+                    kind
+                ),
+            ));
+        }
+        format!("({})", column.name())
+    } else {
+        settings.quote(column.name())
+    };
+    if let Some(sort_order) = column.sort_order() {
+        sql.push(' ');
+        sql.push_str(sort_order.sql());
+    }
+    // SQL Server and MySQL have no NULLS FIRST/LAST: nulls always sort as the lowest value.
+    if let Some(nulls_order) = column.nulls_order()
+        && !matches!(database_type, DatabaseType::SqlServer | DatabaseType::MySql)
+    {
+        sql.push(' ');
+        sql.push_str(nulls_order.sql());
+    }
+    Ok(sql)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::common::test_support::make_context;
-    use schema_model::builder::{SchemaBuilder, TableBuilder};
+    use crate::common::test_support::{make_context, make_idempotent_context};
+    use schema_model::builder::{KeyBuilder, SchemaBuilder, TableBuilder};
     use schema_model::model::database_model::DatabaseModel;
     use schema_model::model::key::KeyColumn;
     use schema_model::model::types::{BooleanMode, DatabaseType, ForeignKeyMode, KeyType};
@@ -167,7 +237,7 @@ mod tests {
         let (ctx, buffer) = make_context(model, DatabaseType::SqlServer);
 
         let generator = DefaultIndexGenerator::new(ctx);
-        generator.output_indexes();
+        generator.output_indexes().unwrap();
 
         let output = buffer.contents();
         assert!(output.contains("create index"));
@@ -189,7 +259,7 @@ mod tests {
         let (ctx, buffer) = make_context(model, DatabaseType::SqlServer);
 
         let generator = DefaultIndexGenerator::new(ctx);
-        generator.output_indexes();
+        generator.output_indexes().unwrap();
 
         let output = buffer.contents();
         for line in output.lines().filter(|l| l.starts_with("create index")) {
@@ -198,4 +268,44 @@ mod tests {
         }
         assert!(output.contains("ix_"));
     }
+
+    #[test]
+    fn output_index_guards_the_create_when_idempotent() {
+        let table = TableBuilder::new(None::<&str>, "users")
+            .add_index(Key::new(KeyType::Index, vec![KeyColumn::new("name")]))
+            .build();
+        let make_model = || {
+            let schema = SchemaBuilder::new(None::<&str>).add_table(table.clone()).build();
+            DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema])
+        };
+
+        let (ctx, buffer) = make_idempotent_context(make_model(), DatabaseType::Postgresql);
+        DefaultIndexGenerator::new(ctx).output_indexes().unwrap();
+        assert!(buffer.contents().contains("create index if not exists ix_users1 on public.users (name);"));
+
+        let (ctx, buffer) = make_idempotent_context(make_model(), DatabaseType::SqlServer);
+        DefaultIndexGenerator::new(ctx).output_indexes().unwrap();
+        assert!(buffer.contents().contains(
+            "if not exists (select 1 from sys.indexes where name = 'ix_users1' and object_id = object_id('dbo.users'))\ncreate index ix_users1 on dbo.users (name)"
+        ));
+    }
+
+    #[test]
+    fn output_index_compresses_only_on_sql_server() {
+        let table = TableBuilder::new(None::<&str>, "users")
+            .add_index(KeyBuilder::new(KeyType::Index).add_column("name").compress(true).build())
+            .build();
+        let make_model = || {
+            let schema = SchemaBuilder::new(None::<&str>).add_table(table.clone()).build();
+            DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema])
+        };
+
+        let (ctx, buffer) = make_context(make_model(), DatabaseType::SqlServer);
+        DefaultIndexGenerator::new(ctx).output_indexes().unwrap();
+        assert!(buffer.contents().contains("create index ix_users1 on dbo.users (name) with (data_compression = page)"));
+
+        let (ctx, buffer) = make_context(make_model(), DatabaseType::Postgresql);
+        DefaultIndexGenerator::new(ctx).output_indexes().unwrap();
+        assert!(buffer.contents().contains("create index ix_users1 on public.users (name);"));
+    }
 }
@@ -1,16 +1,16 @@
+use crate::common::generation_error::GenerationError;
 use crate::common::generator_context::GeneratorContext;
+use schema_model::model::key::Key;
 use schema_model::model::table::Table;
 
-const PK_PREFIX: &str = "pk_";
-const AK_PREFIX: &str = "ak_";
-
 pub trait KeyGenerator {
-    fn key_constraints(&self, table: &Table) -> Vec<String>;
+    fn key_constraints(&self, table: &Table) -> Result<Vec<String>, GenerationError>;
 }
 
 pub struct DefaultKeyGenerator {
     context: GeneratorContext,
     nonclustered_primary_key: bool,
+    data_compression: bool,
 }
 
 impl DefaultKeyGenerator {
@@ -18,6 +18,7 @@ impl DefaultKeyGenerator {
         Self {
             context,
             nonclustered_primary_key: false,
+            data_compression: false,
         }
     }
 
@@ -28,40 +29,36 @@ impl DefaultKeyGenerator {
         self
     }
 
+    /// When set, a compressed key renders `with (data_compression = ...)` after its columns,
+    /// as SQL Server spells it.
+    pub fn with_data_compression(mut self, value: bool) -> Self {
+        self.data_compression = value;
+        self
+    }
+
     pub fn context(&self) -> &GeneratorContext {
         &self.context
     }
 
-    fn constraint_name(&self, prefix: &str, table_name: &str, suffix: Option<usize>) -> String {
-        let database_type = self.context.settings().database_type();
-        let max_key_name_length = database_type.max_key_name_length();
-
-        let suffix_str = suffix.map(|n| n.to_string()).unwrap_or_default();
-        let full_name = if suffix.is_some() {
-            format!("{}{}{}", prefix, table_name, suffix_str)
-        } else {
-            format!("{}{}", prefix, table_name)
-        };
+    fn quoted_columns(&self, key: &Key) -> String {
+        self.context.settings().quote_list(&key_column_names(key))
+    }
 
-        if full_name.len() <= max_key_name_length {
-            return full_name.to_lowercase();
+    fn compression_clause(&self, key: &Key) -> String {
+        match key.compression() {
+            Some(level) if self.data_compression => format!(" with (data_compression = {})", level.sql()),
+            _ => String::new(),
         }
+    }
 
-        // Truncate table name to fit within max_key_name_length. Truncate by char, not
-        // byte index - a byte-index slice can land mid-character for any multi-byte
-        // UTF-8 table name (CJK, Cyrillic, emoji, ...) once it's long enough to need
-        // truncating, which panics ("byte index N is not a char boundary").
-        let suffix_len = suffix_str.len();
-        let prefix_len = prefix.len();
-        let available = max_key_name_length.saturating_sub(prefix_len + suffix_len);
-        let truncated_table_name: String = table_name.chars().take(available).collect();
-
-        format!("{}{}{}", prefix, truncated_table_name, suffix_str).to_lowercase()
+    /// The unique key constraints alone, for a dialect that declares the primary key
+    /// somewhere else.
+    pub fn key_constraints_without_primary_key(&self, table: &Table) -> Vec<String> {
+        self.constraints(table, false)
     }
-}
 
-impl KeyGenerator for DefaultKeyGenerator {
-    fn key_constraints(&self, table: &Table) -> Vec<String> {
+    fn constraints(&self, table: &Table, include_primary_key: bool) -> Vec<String> {
+        let settings = self.context.settings();
         let mut constraints = Vec::new();
         let mut unique_key_counter = 0;
 
@@ -72,26 +69,31 @@ impl KeyGenerator for DefaultKeyGenerator {
 
             match key.key_type() {
                 schema_model::model::types::KeyType::Primary => {
-                    let constraint_name = self.constraint_name(PK_PREFIX, table.name(), None);
+                    if !include_primary_key {
+                        continue;
+                    }
+                    let constraint_name = settings.constraint_namer().key_name(table.name(), key, 1);
                     let primary_key_clause = if self.nonclustered_primary_key {
                         "primary key nonclustered"
                     } else {
                         "primary key"
                     };
                     constraints.push(format!(
-                        "   constraint {} {} ({})",
-                        constraint_name,
+                        "   constraint {} {} ({}){}",
+                        settings.quote_generated(&constraint_name),
                         primary_key_clause,
-                        key.columns_as_string()
+                        self.quoted_columns(key),
+                        self.compression_clause(key)
                     ));
                 }
                 schema_model::model::types::KeyType::Unique => {
                     unique_key_counter += 1;
-                    let constraint_name = self.constraint_name(AK_PREFIX, table.name(), Some(unique_key_counter));
+                    let constraint_name = settings.constraint_namer().key_name(table.name(), key, unique_key_counter);
                     constraints.push(format!(
-                        "   constraint {} unique ({})",
-                        constraint_name,
-                        key.columns_as_string()
+                        "   constraint {} unique ({}){}",
+                        settings.quote_generated(&constraint_name),
+                        self.quoted_columns(key),
+                        self.compression_clause(key)
                     ));
                 }
                 schema_model::model::types::KeyType::Index => unreachable!(),
@@ -102,14 +104,25 @@ impl KeyGenerator for DefaultKeyGenerator {
     }
 }
 
+fn key_column_names(key: &Key) -> Vec<&str> {
+    key.columns().iter().map(|column| column.name()).collect()
+}
+
+impl KeyGenerator for DefaultKeyGenerator {
+    fn key_constraints(&self, table: &Table) -> Result<Vec<String>, GenerationError> {
+        Ok(self.constraints(table, true))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::common::test_support::make_context;
+    use crate::common::test_support::{make_context, make_context_with_naming};
     use schema_model::builder::TableBuilder;
     use schema_model::model::database_model::DatabaseModel;
     use schema_model::model::key::Key;
     use schema_model::model::key::KeyColumn;
+    use schema_model::model::naming::NamingConvention;
     use schema_model::model::types::{BooleanMode, DatabaseType, ForeignKeyMode, KeyType};
 
     #[test]
@@ -122,7 +135,7 @@ mod tests {
         let (ctx, _buffer) = make_context(model, DatabaseType::Postgresql);
 
         let generator = DefaultKeyGenerator::new(ctx);
-        assert_eq!(generator.key_constraints(&table), vec![] as Vec<String>);
+        assert_eq!(generator.key_constraints(&table).unwrap(), vec![] as Vec<String>);
     }
 
     #[test]
@@ -138,7 +151,7 @@ mod tests {
         let (ctx, _buffer) = make_context(model, DatabaseType::Postgresql);
 
         let generator = DefaultKeyGenerator::new(ctx);
-        let constraints = generator.key_constraints(&table);
+        let constraints = generator.key_constraints(&table).unwrap();
         assert_eq!(constraints.len(), 1);
         assert_eq!(constraints[0], "   constraint pk_users primary key (id)");
     }
@@ -159,11 +172,31 @@ mod tests {
         let (ctx, _buffer) = make_context(model, DatabaseType::Postgresql);
 
         let generator = DefaultKeyGenerator::new(ctx);
-        let constraints = generator.key_constraints(&table);
+        let constraints = generator.key_constraints(&table).unwrap();
         assert_eq!(constraints.len(), 1);
         assert_eq!(constraints[0], "   constraint pk_users primary key (org_id,user_id)");
     }
 
+    #[test]
+    fn snake_case_naming_renames_key_columns_and_constraint_names() {
+        let pk = Key::new(KeyType::Primary, vec![KeyColumn::new("ParentId")]);
+        let uq = Key::new(KeyType::Unique, vec![KeyColumn::new("ExternalCode")]);
+        let table = TableBuilder::new(None::<&str>, "ParentTable")
+            .add_key(pk)
+            .add_key(uq)
+            .build();
+        let schema = schema_model::builder::SchemaBuilder::new(None::<&str>)
+            .add_table(table.clone())
+            .build();
+        let model = DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema]);
+        let (ctx, _buffer) = make_context_with_naming(model, DatabaseType::Postgresql, NamingConvention::SnakeCase);
+
+        let generator = DefaultKeyGenerator::new(ctx);
+        let constraints = generator.key_constraints(&table).unwrap();
+        assert_eq!(constraints[0], "   constraint pk_parent_table primary key (parent_id)");
+        assert_eq!(constraints[1], "   constraint ak_parent_table1 unique (external_code)");
+    }
+
     #[test]
     fn unique_key_single_column() {
         let uq = Key::new(KeyType::Unique, vec![KeyColumn::new("email")]);
@@ -177,7 +210,7 @@ mod tests {
         let (ctx, _buffer) = make_context(model, DatabaseType::Postgresql);
 
         let generator = DefaultKeyGenerator::new(ctx);
-        let constraints = generator.key_constraints(&table);
+        let constraints = generator.key_constraints(&table).unwrap();
         assert_eq!(constraints.len(), 1);
         assert_eq!(constraints[0], "   constraint ak_users1 unique (email)");
     }
@@ -198,7 +231,7 @@ mod tests {
         let (ctx, _buffer) = make_context(model, DatabaseType::Postgresql);
 
         let generator = DefaultKeyGenerator::new(ctx);
-        let constraints = generator.key_constraints(&table);
+        let constraints = generator.key_constraints(&table).unwrap();
         assert_eq!(constraints.len(), 1);
         assert_eq!(constraints[0], "   constraint ak_products1 unique (tenant_id,code)");
     }
@@ -220,7 +253,7 @@ mod tests {
         let (ctx, _buffer) = make_context(model, DatabaseType::Postgresql);
 
         let generator = DefaultKeyGenerator::new(ctx);
-        let constraints = generator.key_constraints(&table);
+        let constraints = generator.key_constraints(&table).unwrap();
         assert_eq!(constraints.len(), 3);
         assert_eq!(constraints[0], "   constraint pk_users primary key (id)");
         assert_eq!(constraints[1], "   constraint ak_users1 unique (email)");
@@ -242,7 +275,7 @@ mod tests {
         let (ctx, _buffer) = make_context(model, DatabaseType::Postgresql);
 
         let generator = DefaultKeyGenerator::new(ctx);
-        let constraints = generator.key_constraints(&table);
+        let constraints = generator.key_constraints(&table).unwrap();
         // Only primary key should be emitted, index should be filtered
         assert_eq!(constraints.len(), 1);
         assert_eq!(constraints[0], "   constraint pk_users primary key (id)");
@@ -263,7 +296,7 @@ mod tests {
         let (ctx, _buffer) = make_context(model, DatabaseType::Postgresql);
 
         let generator = DefaultKeyGenerator::new(ctx);
-        let constraints = generator.key_constraints(&table);
+        let constraints = generator.key_constraints(&table).unwrap();
         assert_eq!(constraints.len(), 2);
         assert_eq!(constraints[0], "   constraint ak_products1 unique (email)");
         assert_eq!(constraints[1], "   constraint ak_products2 unique (username)");
@@ -285,7 +318,7 @@ mod tests {
         let (ctx, _buffer) = make_context(model, DatabaseType::Postgresql);
 
         let generator = DefaultKeyGenerator::new(ctx);
-        let constraints = generator.key_constraints(&table);
+        let constraints = generator.key_constraints(&table).unwrap();
         assert_eq!(constraints.len(), 2);
 
         // pk_* constraint name should be <= 63 chars
@@ -327,9 +360,9 @@ mod tests {
         let (ctx, _buffer) = make_context(model, DatabaseType::Postgresql);
 
         let generator = DefaultKeyGenerator::new(ctx);
-        let constraints = generator.key_constraints(&table);
+        let constraints = generator.key_constraints(&table).unwrap();
         assert_eq!(constraints.len(), 1);
-        assert!(constraints[0].contains("constraint pk_"));
+        assert!(constraints[0].contains("constraint \"pk_"));
     }
 
     #[test]
@@ -347,7 +380,7 @@ mod tests {
         let (ctx, _buffer) = make_context(model, DatabaseType::SqlServer);
 
         let generator = DefaultKeyGenerator::new(ctx).with_nonclustered_primary_key(true);
-        let constraints = generator.key_constraints(&table);
+        let constraints = generator.key_constraints(&table).unwrap();
         assert_eq!(constraints.len(), 2);
         assert_eq!(constraints[0], "   constraint pk_users primary key nonclustered (id)");
         // Unique keys are unaffected.
@@ -12,13 +12,20 @@ pub mod index_generator;
 pub mod relation_generator;
 pub mod function_generator;
 pub mod view_generator;
+pub mod sequence_generator;
 pub mod procedure_generator;
 pub mod trigger_generator;
+pub mod drop_generator;
 pub mod other_sql_generator;
+pub mod sql_split;
 pub mod sql_string;
+pub mod aggregation_support;
+pub mod trigger_support;
 pub mod sql_writer;
 pub mod column_type_generator;
 pub mod sql_generator_settings;
 pub mod generate_options;
+pub mod generated_statement;
+pub mod generation_error;
 pub mod generator_context;
 pub mod test_support;
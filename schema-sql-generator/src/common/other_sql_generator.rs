@@ -1,11 +1,13 @@
+use crate::common::generated_statement::StatementKind;
+use crate::common::generation_error::GenerationError;
 use crate::common::generator_context::GeneratorContext;
 use crate::common::sql_writer::SqlWriter;
 use schema_model::model::types::OtherSqlOrder;
 
 pub trait OtherSqlGenerator {
-    fn output_other_sql_top(&self);
-    fn output_other_sql_bottom(&self);
-    fn output_other_sql(&self, writer: &mut SqlWriter, statement_separator: &str, sql: &str);
+    fn output_other_sql_top(&self) -> Result<(), GenerationError>;
+    fn output_other_sql_bottom(&self) -> Result<(), GenerationError>;
+    fn output_other_sql(&self, writer: &mut SqlWriter, statement_separator: &str, sql: &str) -> Result<(), GenerationError>;
 }
 
 pub struct DefaultOtherSqlGenerator {
@@ -23,49 +25,54 @@ impl DefaultOtherSqlGenerator {
 }
 
 impl OtherSqlGenerator for DefaultOtherSqlGenerator {
-    fn output_other_sql_top(&self) {
+    fn output_other_sql_top(&self) -> Result<(), GenerationError> {
         let database_type = self.context.settings().database_type();
         let statement_separator = self.context.settings().statement_separator();
         let database_model = self.context.settings().database_model();
 
         self.context.with_writer(|writer| {
-            database_model.schemas().iter().for_each(|schema| {
-                schema
+            for schema in database_model.schemas() {
+                for sql in schema
                     .other_sql()
                     .iter()
                     .filter(|sql| sql.database_type() == database_type)
                     .filter(|sql| sql.order() == OtherSqlOrder::Top)
                     .filter(|sql| !sql.sql().is_empty())
-                    .for_each(|sql| {
-                        self.output_other_sql(writer, statement_separator, sql.sql());
-                    })
-            });
-        });
+                {
+                    writer.begin_object(StatementKind::OtherSql, None);
+                    self.output_other_sql(writer, statement_separator, sql.sql())?;
+                }
+            }
+            Ok(())
+        })
     }
 
-    fn output_other_sql_bottom(&self) {
+    fn output_other_sql_bottom(&self) -> Result<(), GenerationError> {
         let database_type = self.context.settings().database_type();
         let statement_separator = self.context.settings().statement_separator();
         let database_model = self.context.settings().database_model();
 
         self.context.with_writer(|writer| {
-            database_model.schemas().iter().for_each(|schema| {
-                schema
+            for schema in database_model.schemas() {
+                for sql in schema
                     .other_sql()
                     .iter()
                     .filter(|sql| sql.database_type() == database_type)
                     .filter(|sql| sql.order() == OtherSqlOrder::Bottom)
                     .filter(|sql| !sql.sql().is_empty())
-                    .for_each(|sql| {
-                        self.output_other_sql(writer, statement_separator, sql.sql());
-                    })
-            });
-        });
+                {
+                    writer.begin_object(StatementKind::OtherSql, None);
+                    self.output_other_sql(writer, statement_separator, sql.sql())?;
+                }
+            }
+            Ok(())
+        })
     }
 
-    fn output_other_sql(&self, writer: &mut SqlWriter, statement_separator: &str, sql: &str) {
-        writer.print(sql);
-        writer.println(statement_separator);
-        writer.newline();
+    fn output_other_sql(&self, writer: &mut SqlWriter, statement_separator: &str, sql: &str) -> Result<(), GenerationError> {
+        writer.print(sql)?;
+        writer.println(statement_separator)?;
+        writer.newline()?;
+        Ok(())
     }
 }
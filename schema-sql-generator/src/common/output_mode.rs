@@ -5,6 +5,8 @@ pub enum OutputMode {
     All,
     IndexesOnly,
     TriggersOnly,
+    /// A teardown script dropping everything `All` creates.
+    Drop,
 }
 
 impl OutputMode {
@@ -30,6 +32,7 @@ impl FromStr for OutputMode {
             "all" => Ok(OutputMode::All),
             "indexes-only" => Ok(OutputMode::IndexesOnly),
             "triggers-only" => Ok(OutputMode::TriggersOnly),
+            "drop" => Ok(OutputMode::Drop),
             _ => Err(format!("Unknown output mode: {}", s)),
         }
     }
@@ -1,103 +1,83 @@
-use std::io::{BufWriter, ErrorKind, Write};
+use std::io::{self, BufWriter, Write};
 
 pub struct PrintWriter {
-    writer: BufWriter<Box<dyn Write>>,
+    writer: BufWriter<CountingWriter>,
     auto_flush: bool,
-    exit_quietly_on_broken_pipe: bool,
-}
-
-fn is_broken_pipe(e: &std::io::Error) -> bool {
-    e.kind() == ErrorKind::BrokenPipe
 }
 
 impl PrintWriter {
     pub fn new(writer: Box<dyn Write>) -> Self {
         Self {
-            writer: BufWriter::new(writer),
+            writer: BufWriter::new(CountingWriter { inner: writer, written: 0 }),
             auto_flush: false,
-            exit_quietly_on_broken_pipe: false,
         }
     }
 
     pub fn new_auto_flush(writer: Box<dyn Write>) -> Self {
         Self {
-            writer: BufWriter::new(writer),
+            writer: BufWriter::new(CountingWriter { inner: writer, written: 0 }),
             auto_flush: true,
-            exit_quietly_on_broken_pipe: false,
         }
     }
 
-    /// Opts into treating a broken pipe as "exit the process quietly" instead of
-    /// panicking. Only appropriate for a writer that's actually connected to something
-    /// pipe-like (e.g. a CLI's stdout, which may be piped into `head`/`less` and closed
-    /// early - a routine condition every other Unix-style text-producing tool exits from
-    /// silently, and Rust ignoring `SIGPIPE` by default turns into an `io::Error` here
-    /// instead of the process dying outright). This is opt-in and off by default because
-    /// `PrintWriter` is also used to write to plain files (which can't produce a broken
-    /// pipe in practice) and in-process buffers - an embedder writing to one of those
-    /// should never have the *whole process* exited out from under it by a shared
-    /// low-level writer type on some other, unexpected I/O condition.
-    pub fn exit_quietly_on_broken_pipe(mut self, value: bool) -> Self {
-        self.exit_quietly_on_broken_pipe = value;
-        self
+    /// The number of bytes printed so far, whether or not they have been flushed yet.
+    pub fn position(&self) -> usize {
+        self.writer.get_ref().written + self.writer.buffer().len()
     }
 
-    /// Handles a write/flush failure, following the `exit_quietly_on_broken_pipe`
-    /// policy for a broken pipe specifically. Any other I/O error (disk full,
-    /// permission denied, ...) is always unexpected here - there's no meaningful
-    /// recovery for a generator not built around `Result`-returning writes, so it
-    /// always fails loudly via panic.
-    fn handle_write_error(&self, context: &str, e: std::io::Error) -> ! {
-        if self.exit_quietly_on_broken_pipe && is_broken_pipe(&e) {
-            std::process::exit(0);
-        }
-        panic!("Error while {}: {}", context, e)
+    pub fn print(&mut self, text: &str) -> io::Result<()> {
+        write!(self.writer, "{}", text)
     }
 
-    pub fn print(&mut self, text: &str) {
-        if let Err(e) = write!(self.writer, "{}", text) {
-            self.handle_write_error("writing", e);
-        }
+    pub fn println(&mut self, text: &str) -> io::Result<()> {
+        writeln!(self.writer, "{}", text)?;
+        self.flush_if_auto()
     }
 
-    pub fn println(&mut self, text: &str) {
-        if let Err(e) = writeln!(self.writer, "{}", text) {
-            self.handle_write_error("writing", e);
-        }
-        if self.auto_flush {
-            self.flush();
-        }
+    pub fn printf(&mut self, args: std::fmt::Arguments) -> io::Result<()> {
+        write!(self.writer, "{}", args)?;
+        self.flush_if_auto()
     }
 
-    pub fn printf(&mut self, args: std::fmt::Arguments) {
-        if let Err(e) = write!(self.writer, "{}", args) {
-            self.handle_write_error("writing", e);
-        }
-        if self.auto_flush {
-            self.flush();
-        }
+    pub fn newline(&mut self) -> io::Result<()> {
+        writeln!(self.writer)?;
+        self.flush_if_auto()
     }
 
-    pub fn newline(&mut self) {
-        if let Err(e) = writeln!(self.writer) {
-            self.handle_write_error("writing", e);
-        }
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+
+    fn flush_if_auto(&mut self) -> io::Result<()> {
         if self.auto_flush {
-            self.flush();
+            self.flush()?;
         }
+        Ok(())
     }
+}
 
-    pub fn flush(&mut self) {
-        if let Err(e) = self.writer.flush() {
-            self.handle_write_error("flushing", e);
-        }
+/// Counts the bytes that make it through the `BufWriter` to the real writer.
+struct CountingWriter {
+    inner: Box<dyn Write>,
+    written: usize,
+}
+
+impl Write for CountingWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.written += written;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::io;
+    use std::io::ErrorKind;
 
     /// A `Write` impl that always fails with a configurable error kind, used to drive
     /// `PrintWriter` down its error-handling paths without needing a real closed pipe.
@@ -114,42 +94,28 @@ mod tests {
     }
 
     #[test]
-    fn is_broken_pipe_classifies_error_kinds_correctly() {
-        assert!(is_broken_pipe(&io::Error::from(ErrorKind::BrokenPipe)));
-        assert!(!is_broken_pipe(&io::Error::from(ErrorKind::PermissionDenied)));
-        assert!(!is_broken_pipe(&io::Error::from(ErrorKind::OutOfMemory)));
+    fn flush_returns_the_underlying_error() {
+        // `print` alone doesn't reach the underlying `Write` here - `BufWriter` buffers a
+        // short string rather than flushing it immediately - so the error only shows up
+        // on the explicit `flush()`.
+        let mut writer = PrintWriter::new(Box::new(FailingWriter(ErrorKind::PermissionDenied)));
+        assert!(writer.print("hello").is_ok());
+        assert_eq!(writer.flush().unwrap_err().kind(), ErrorKind::PermissionDenied);
     }
 
     #[test]
-    #[should_panic(expected = "Error while flushing")]
-    fn flush_panics_on_a_non_broken_pipe_error() {
-        // Unexpected I/O errors (not a broken pipe) must still fail loudly, since
-        // nothing downstream is built to recover from a partially-written script.
-        // (`print` alone doesn't reach the underlying `Write` here - `BufWriter`
-        // buffers a short string rather than flushing it immediately - so this drives
-        // the error path via an explicit `flush()`, which always reaches it.)
-        let mut writer = PrintWriter::new(Box::new(FailingWriter(ErrorKind::PermissionDenied)));
-        writer.print("hello");
-        writer.flush();
+    fn auto_flush_returns_a_broken_pipe_from_println() {
+        let mut writer = PrintWriter::new_auto_flush(Box::new(FailingWriter(ErrorKind::BrokenPipe)));
+        assert_eq!(writer.println("hello").unwrap_err().kind(), ErrorKind::BrokenPipe);
     }
 
     #[test]
-    #[should_panic(expected = "Error while flushing")]
-    fn flush_panics_on_a_broken_pipe_by_default() {
-        // `PrintWriter` is also used to write to plain files (schema-installer's temp
-        // file, the CLI's output file) and in-process buffers - neither can produce a
-        // broken pipe in practice, but if one somehow did, a shared low-level writer
-        // type silently exiting the whole process out from under an arbitrary embedder
-        // would be far more surprising than a panic. Quietly exiting on a broken pipe
-        // is opt-in (`exit_quietly_on_broken_pipe(true)`) for exactly this reason - the
-        // default must still panic.
-        let mut writer = PrintWriter::new(Box::new(FailingWriter(ErrorKind::BrokenPipe)));
-        writer.print("hello");
-        writer.flush();
+    fn position_counts_buffered_and_flushed_bytes() {
+        let mut writer = PrintWriter::new(Box::new(Vec::<u8>::new()));
+        writer.print("abc").unwrap();
+        assert_eq!(writer.position(), 3);
+        writer.flush().unwrap();
+        writer.println("de").unwrap();
+        assert_eq!(writer.position(), 6);
     }
-
-    // Note: the broken-pipe-exits-quietly path itself calls `std::process::exit(0)`,
-    // which can't be exercised in-process without terminating the test runner -
-    // `is_broken_pipe`'s classification (tested above) is what determines whether that
-    // path is taken once a caller has opted in via `exit_quietly_on_broken_pipe(true)`.
 }
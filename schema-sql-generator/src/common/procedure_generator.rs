@@ -1,15 +1,17 @@
+use crate::common::generated_statement::StatementKind;
+use crate::common::generation_error::GenerationError;
 use crate::common::generator_context::GeneratorContext;
 use crate::common::sql_writer::SqlWriter;
 use schema_model::model::procedure::Procedure;
 
 pub trait ProcedureGenerator {
-    fn output_procedures(&self);
+    fn output_procedures(&self) -> Result<(), GenerationError>;
     fn output_procedure(
         &self,
         writer: &mut SqlWriter,
         statement_separator: &str,
         procedure: &Procedure,
-    );
+    ) -> Result<(), GenerationError>;
 }
 
 pub struct DefaultProcedureGenerator {
@@ -32,33 +34,34 @@ impl DefaultProcedureGenerator {
     /// struct would otherwise never see its own override invoked - callers that need
     /// their override honored should pass `self` (as a `&dyn ProcedureGenerator`) here
     /// instead of calling `output_procedures` directly.
-    pub fn output_procedures_via(&self, generator: &dyn ProcedureGenerator) {
+    pub fn output_procedures_via(&self, generator: &dyn ProcedureGenerator) -> Result<(), GenerationError> {
         let database_type = self.context.settings().database_type();
         let statement_separator = self.context.settings().statement_separator();
         let database_model = self.context.settings().database_model();
 
         self.context.with_writer(|writer| {
-            database_model.schemas().iter().for_each(|schema| {
-                schema
-                    .procedures()
-                    .iter()
-                    .filter(|procedure| procedure.database_type() == database_type)
-                    .for_each(|procedure| {
-                        generator.output_procedure(writer, statement_separator, procedure);
-                    })
-            });
-        });
+            for schema in database_model.schemas() {
+                for procedure in schema.procedures().iter().filter(|procedure| procedure.database_type() == database_type) {
+                    writer.begin_object(StatementKind::Procedure, Some(procedure.name()));
+                    generator
+                        .output_procedure(writer, statement_separator, procedure)
+                        .map_err(|e| e.within(|| format!("procedure {}", procedure.name())))?;
+                }
+            }
+            Ok(())
+        })
     }
 }
 
 impl ProcedureGenerator for DefaultProcedureGenerator {
-    fn output_procedures(&self) {
-        self.output_procedures_via(self);
+    fn output_procedures(&self) -> Result<(), GenerationError> {
+        self.output_procedures_via(self)
     }
 
-    fn output_procedure(&self, writer: &mut SqlWriter, statement_separator: &str, procedure: &Procedure) {
-        writer.print(procedure.sql());
-        writer.println(statement_separator);
-        writer.newline();
+    fn output_procedure(&self, writer: &mut SqlWriter, statement_separator: &str, procedure: &Procedure) -> Result<(), GenerationError> {
+        writer.print(procedure.sql())?;
+        writer.println(statement_separator)?;
+        writer.newline()?;
+        Ok(())
     }
 }
@@ -1,14 +1,15 @@
+use crate::common::generated_statement::StatementKind;
+use crate::common::generation_error::{resolve_table, GenerationError};
 use crate::common::generator_context::GeneratorContext;
+use crate::common::sql_string::escape_sql_literal;
 use crate::common::sql_writer::SqlWriter;
 use schema_model::model::database_model::DatabaseModel;
 use schema_model::model::relation::Relation;
 use schema_model::model::table::Table;
-use schema_model::model::types::RelationType;
-
-const FK_PREFIX: &str = "fk_";
+use schema_model::model::types::{DatabaseType, RelationType};
 
 pub trait RelationGenerator {
-    fn output_relations(&self);
+    fn output_relations(&self) -> Result<(), GenerationError>;
 }
 
 pub struct DefaultRelationGenerator {
@@ -26,36 +27,25 @@ impl DefaultRelationGenerator {
         &self.context
     }
 
-    fn output_relation_for_table(&self, writer: &mut SqlWriter, table: &Table) {
+    fn output_relation_for_table(&self, writer: &mut SqlWriter, table: &Table) -> Result<(), GenerationError> {
         let database_model = self.context.settings().database_model();
 
         for (relation_index, relation) in table.relations().iter().enumerate() {
             let relation_name = self.relation_constraint_name(table, relation_index);
-            self.output_relation(writer, &relation_name, database_model, table, relation);
+            self.output_relation(writer, &relation_name, database_model, table, relation)
+                .map_err(|e| e.within(|| format!("relation {}", relation_name)))?;
         }
+        Ok(())
     }
 
-    /// Builds the constraint name for the given relation, truncating the table-name
-    /// portion so the identifier stays within the target database's max key name length.
+    /// The constraint name of the table's `relation_index`th relation, from the foreign key
+    /// template and kept within the target database's max key name length.
     pub fn relation_constraint_name(&self, table: &Table, relation_index: usize) -> String {
-        let database_type = self.context.settings().database_type();
-        let max_key_name_length = database_type.max_key_name_length();
-        let table_name = table.name();
-        let suffix_str = (relation_index + 1).to_string();
-        let mut relation_name = format!("{}{}{}", FK_PREFIX, table_name, suffix_str);
-
-        if relation_name.len() > max_key_name_length {
-            // Reserve space for the *actual* suffix length, not a hard-coded single
-            // digit - a table with >=10 relations needs a 2-digit suffix, and reserving
-            // only 1 char for it would produce an identifier over the length limit.
-            // Truncate by char, not byte index, so multi-byte UTF-8 table names don't
-            // panic ("byte index N is not a char boundary").
-            let available = max_key_name_length.saturating_sub(FK_PREFIX.len() + suffix_str.len());
-            let truncated_table_name: String = table_name.chars().take(available).collect();
-            relation_name = format!("{}{}{}", FK_PREFIX, truncated_table_name, suffix_str);
+        let settings = self.context.settings();
+        match table.relations().get(relation_index) {
+            Some(relation) => settings.constraint_namer().foreign_key_name(table.name(), relation, relation_index + 1),
+            None => settings.constraint_name(&settings.constraint_naming().foreign_key, table.name(), &[], Some(relation_index + 1)),
         }
-
-        relation_name.to_lowercase()
     }
 
     fn output_relation(&self,
@@ -63,23 +53,53 @@ impl DefaultRelationGenerator {
                        relation_name: &str,
                        database_model: &DatabaseModel,
                        table: &Table,
-                       relation: &Relation) {
+                       relation: &Relation) -> Result<(), GenerationError> {
         let operation = self.relation_operation_type(relation.relation_type());
         let database_type = self.context.settings().database_type();
-        let to_table = database_model.find_table_by_qualified_name(relation.to_table_name());
-
-        writer.print(format!("alter table {}", table.fully_qualified_table_name(database_type)).as_str());
-        writer.print(" add constraint ");
-        writer.print(relation_name);
-        writer.print(" foreign key (");
-        writer.print(relation.from_column_name());
-        writer.print(") references ");
-        writer.print(to_table.fully_qualified_table_name(database_type).as_str());
-        writer.print("(");
-        writer.print(relation.to_column_name());
-        writer.print(") on delete ");
-        writer.print(operation);
-        writer.println(self.context().settings().statement_separator());
+        let to_table = resolve_table(database_model, relation.to_table_name(), || format!("relation {}", relation_name))?;
+
+        let settings = self.context.settings();
+        let fully_qualified_table_name = settings.qualified_name(table.schema_name(), table.name());
+        // MariaDB can check for the foreign key itself.
+        let if_not_exists = if database_type == DatabaseType::MySql && settings.idempotent() { "if not exists " } else { "" };
+        let add_constraint = format!(
+            "alter table {} add constraint {} foreign key {}({}) references {}({}) on delete {}",
+            fully_qualified_table_name,
+            settings.quote_generated(relation_name),
+            if_not_exists,
+            settings.quote_list(&relation.from_column_names()),
+            settings.qualified_name(to_table.schema_name(), to_table.name()),
+            settings.quote_list(&relation.to_column_names()),
+            operation
+        );
+        let separator = self.context().settings().statement_separator();
+
+        writer.begin_object(StatementKind::Relation, Some(relation_name));
+        match (database_type, self.context.settings().idempotent()) {
+            // Postgres has no `add constraint if not exists`, so the check runs in a block.
+            (DatabaseType::Postgresql, true) => {
+                writer.println("do $$")?;
+                writer.println("begin")?;
+                writer.println(format!(
+                    "   if not exists (select 1 from pg_constraint where conname = '{}' and conrelid = '{}'::regclass) then",
+                    escape_sql_literal(relation_name),
+                    escape_sql_literal(&fully_qualified_table_name)
+                ).as_str())?;
+                writer.println(format!("      {};", add_constraint).as_str())?;
+                writer.println("   end if;")?;
+                writer.println("end")?;
+                writer.println(format!("$${}", separator).as_str())?;
+            }
+            (DatabaseType::SqlServer, true) => {
+                let fully_qualified_relation_name = settings.qualified_generated_name(table.schema_name(), relation_name);
+                writer.println(format!("if object_id('{}', 'F') is null", escape_sql_literal(&fully_qualified_relation_name)).as_str())?;
+                writer.println(format!("{}{}", add_constraint, separator).as_str())?;
+            }
+            _ => {
+                writer.println(format!("{}{}", add_constraint, separator).as_str())?;
+            }
+        }
+        Ok(())
     }
 
     pub fn relation_operation_type(&self, relation_type: RelationType) -> &str {
@@ -94,30 +114,31 @@ impl DefaultRelationGenerator {
 }
 
 impl RelationGenerator for DefaultRelationGenerator {
-    fn output_relations(&self) {
+    fn output_relations(&self) -> Result<(), GenerationError> {
         let database_model = self.context.settings().database_model();
         let has_relations = database_model.all_tables().iter().any(|table| {!table.relations().is_empty()});
 
         if has_relations {
-            self.context.with_writer(|writer| {
-                writer.println("/* relations */");
+            self.context.with_writer(|writer| -> Result<(), GenerationError> {
+                writer.println("/* relations */")?;
 
-                database_model.all_tables().iter().filter(|table| {
-                    !table.relations().is_empty()
-                }).for_each(|table| {
-                    self.output_relation_for_table(writer, table);
-                });
+                for table in database_model.all_tables().iter().filter(|table| !table.relations().is_empty()) {
+                    self.output_relation_for_table(writer, table)?;
+                }
 
-                writer.newline();
-            });
+                writer.newline()?;
+                Ok(())
+            })?;
         }
+        Ok(())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::common::test_support::make_context;
+    use crate::common::test_support::{make_context, make_idempotent_context};
+    use schema_model::model::relation::RelationColumn;
     use schema_model::builder::{ColumnBuilder, SchemaBuilder, TableBuilder};
     use schema_model::model::column_type::ColumnType;
     use schema_model::model::types::{BooleanMode, DatabaseType, ForeignKeyMode};
@@ -143,7 +164,7 @@ mod tests {
         let (ctx, buffer) = make_context(model, DatabaseType::Postgresql);
 
         let generator = DefaultRelationGenerator::new(ctx);
-        generator.output_relations();
+        generator.output_relations().unwrap();
 
         let output = buffer.contents();
         assert!(output.contains("add constraint "));
@@ -152,6 +173,22 @@ mod tests {
         assert!(constraint_name.len() <= 63, "constraint name '{}' exceeds postgres's 63 char limit", constraint_name);
     }
 
+    #[test]
+    fn output_relations_reports_a_relation_to_a_missing_table() {
+        let order = TableBuilder::new(None::<&str>, "order")
+            .add_column(ColumnBuilder::new(None::<&str>, "customer_id", ColumnType::Int).build())
+            .add_relation(Relation::new("customer", "id", "order", "customer_id", RelationType::Enforce, false))
+            .build();
+        let schema = SchemaBuilder::new(None::<&str>).add_table(order).build();
+        let model = DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema]);
+        let (ctx, _buffer) = make_context(model, DatabaseType::Postgresql);
+
+        let error = DefaultRelationGenerator::new(ctx).output_relations().unwrap_err();
+
+        assert_eq!(error.object(), Some("relation fk_order1"));
+        assert!(error.to_string().contains("table 'customer', which does not exist"));
+    }
+
     #[test]
     fn relation_constraint_name_truncates_multi_byte_table_name_without_panicking() {
         // Regression test: byte-index slicing panics ("not a char boundary") on
@@ -188,4 +225,68 @@ mod tests {
         assert!(name.ends_with("10"));
         assert!(name.len() <= 32, "constraint name '{}' exceeds SQL Server's 32 char limit", name);
     }
+
+    #[test]
+    fn output_relations_lists_every_column_of_a_composite_relation_in_order() {
+        let parent = TableBuilder::new(None::<&str>, "customer")
+            .add_column(ColumnBuilder::new(None::<&str>, "tenant_id", ColumnType::Int).required(true).build())
+            .add_column(ColumnBuilder::new(None::<&str>, "id", ColumnType::Int).required(true).build())
+            .build();
+        let child = TableBuilder::new(None::<&str>, "invoice")
+            .add_column(ColumnBuilder::new(None::<&str>, "tenant_id", ColumnType::Int).required(true).build())
+            .add_column(ColumnBuilder::new(None::<&str>, "customer_id", ColumnType::Int).required(true).build())
+            .add_relation(Relation::new_composite(
+                "customer",
+                "invoice",
+                vec![
+                    RelationColumn::new("tenant_id", "tenant_id"),
+                    RelationColumn::new("customer_id", "id"),
+                ],
+                RelationType::Enforce,
+                false,
+            ))
+            .build();
+        let schema = SchemaBuilder::new(None::<&str>)
+            .add_table(parent)
+            .add_table(child)
+            .build();
+        let model = DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema]);
+        let (ctx, buffer) = make_context(model, DatabaseType::Postgresql);
+
+        let generator = DefaultRelationGenerator::new(ctx);
+        generator.output_relations().unwrap();
+
+        assert!(buffer.contents().contains(
+            "foreign key (tenant_id,customer_id) references public.customer(tenant_id,id) on delete no action"
+        ));
+    }
+
+    #[test]
+    fn output_relations_guards_the_constraint_when_idempotent() {
+        let customer = TableBuilder::new(None::<&str>, "customer")
+            .add_column(ColumnBuilder::new(None::<&str>, "id", ColumnType::Int).required(true).build())
+            .build();
+        let order = TableBuilder::new(None::<&str>, "order")
+            .add_column(ColumnBuilder::new(None::<&str>, "customer_id", ColumnType::Int).build())
+            .add_relation(Relation::new("customer", "id", "order", "customer_id", RelationType::Enforce, false))
+            .build();
+        let make_model = || {
+            let schema = SchemaBuilder::new(None::<&str>).add_table(customer.clone()).add_table(order.clone()).build();
+            DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema])
+        };
+
+        let (ctx, buffer) = make_idempotent_context(make_model(), DatabaseType::Postgresql);
+        DefaultRelationGenerator::new(ctx).output_relations().unwrap();
+        let output = buffer.contents();
+        assert!(output.contains("   if not exists (select 1 from pg_constraint where conname = 'fk_order1' and conrelid = 'public.\"order\"'::regclass) then\n"));
+        assert!(output.contains("      alter table public.\"order\" add constraint fk_order1 foreign key (customer_id) references public.customer(id) on delete no action;\n   end if;\nend\n$$;"));
+
+        let (ctx, buffer) = make_idempotent_context(make_model(), DatabaseType::SqlServer);
+        DefaultRelationGenerator::new(ctx).output_relations().unwrap();
+        assert!(buffer.contents().contains("if object_id('dbo.fk_order1', 'F') is null\nalter table dbo.[order] add constraint fk_order1"));
+
+        let (ctx, buffer) = make_idempotent_context(make_model(), DatabaseType::MySql);
+        DefaultRelationGenerator::new(ctx).output_relations().unwrap();
+        assert!(buffer.contents().contains("alter table `order` add constraint fk_order1 foreign key if not exists (customer_id) references customer(id) on delete no action;"));
+    }
 }
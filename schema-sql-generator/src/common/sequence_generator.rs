@@ -0,0 +1,28 @@
+use crate::common::generation_error::GenerationError;
+use crate::common::generator_context::GeneratorContext;
+
+pub trait SequenceGenerator {
+    fn output_sequences(&self) -> Result<(), GenerationError>;
+}
+
+pub struct DefaultSequenceGenerator {
+    context: GeneratorContext,
+}
+
+impl DefaultSequenceGenerator {
+    pub fn new(context: GeneratorContext) -> Self {
+        Self {
+            context,
+        }
+    }
+
+    pub fn context(&self) -> &GeneratorContext {
+        &self.context
+    }
+}
+
+impl SequenceGenerator for DefaultSequenceGenerator {
+    fn output_sequences(&self) -> Result<(), GenerationError> {
+        Ok(())
+    }
+}
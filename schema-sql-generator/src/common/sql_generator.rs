@@ -1,10 +1,13 @@
+use crate::common::drop_generator::DropGenerator;
 use crate::common::function_generator::FunctionGenerator;
+use crate::common::generation_error::GenerationError;
 use crate::common::generator_context::GeneratorContext;
 use crate::common::index_generator::IndexGenerator;
 use crate::common::other_sql_generator::OtherSqlGenerator;
 use crate::common::output_mode::OutputMode;
 use crate::common::procedure_generator::ProcedureGenerator;
 use crate::common::relation_generator::RelationGenerator;
+use crate::common::sequence_generator::SequenceGenerator;
 use crate::common::table_generator::TableGenerator;
 use crate::common::trigger_generator::TriggerGenerator;
 use crate::common::view_generator::ViewGenerator;
@@ -13,52 +16,63 @@ use schema_model::model::types::ForeignKeyMode;
 pub trait SqlGenerator {
     fn context(&self) -> &GeneratorContext;
 
-    fn generate(&self)  {
-        self.output_sql();
+    fn generate(&self) -> Result<(), GenerationError> {
+        self.output_sql()
     }
 
-    fn output_sql(&self) {
-        self.output_header();
+    fn output_sql(&self) -> Result<(), GenerationError> {
+        if self.context().settings().output_mode() == OutputMode::Drop {
+            return self.output_drops();
+        }
+
+        self.output_header()?;
 
         if self.context().settings().output_mode() == OutputMode::IndexesOnly {
-            self.output_indexes();
+            self.output_indexes()?;
         } else if self.context().settings().output_mode() == OutputMode::TriggersOnly {
-            self.output_triggers();
+            self.output_triggers()?;
         } else {
-            self.output_other_sql_top();
-            self.output_tables();
+            self.output_other_sql_top()?;
+            self.output_sequences()?;
+            self.output_tables()?;
 
             if self.context().settings().foreign_key_mode() == ForeignKeyMode::Relations {
-                self.output_relations();
+                self.output_relations()?;
             }
 
-            self.output_triggers();
-            self.output_functions();
-            self.output_views();
-            self.output_procedures();
-            self.output_other_sql_bottom();
+            self.output_triggers()?;
+            self.output_functions()?;
+            self.output_views()?;
+            self.output_procedures()?;
+            self.output_other_sql_bottom()?;
         }
+
+        Ok(())
     }
 
-    fn output_header(&self);
+    fn output_header(&self) -> Result<(), GenerationError>;
 
-    fn output_tables(&self);
+    fn output_sequences(&self) -> Result<(), GenerationError>;
 
-    fn output_relations(&self);
+    fn output_tables(&self) -> Result<(), GenerationError>;
 
-    fn output_indexes(&self);
+    fn output_relations(&self) -> Result<(), GenerationError>;
 
-    fn output_triggers(&self);
+    fn output_indexes(&self) -> Result<(), GenerationError>;
 
-    fn output_functions(&self);
+    fn output_triggers(&self) -> Result<(), GenerationError>;
 
-    fn output_views(&self);
+    fn output_functions(&self) -> Result<(), GenerationError>;
 
-    fn output_procedures(&self);
+    fn output_views(&self) -> Result<(), GenerationError>;
 
-    fn output_other_sql_top(&self);
+    fn output_procedures(&self) -> Result<(), GenerationError>;
 
-    fn output_other_sql_bottom(&self);
+    fn output_other_sql_top(&self) -> Result<(), GenerationError>;
+
+    fn output_other_sql_bottom(&self) -> Result<(), GenerationError>;
+
+    fn output_drops(&self) -> Result<(), GenerationError>;
 }
 
 pub struct DefaultSqlGenerator {
@@ -71,9 +85,12 @@ pub struct DefaultSqlGenerator {
     procedure_generator: Box<dyn ProcedureGenerator>,
     trigger_generator: Box<dyn TriggerGenerator>,
     other_sql_generator: Box<dyn OtherSqlGenerator>,
+    sequence_generator: Box<dyn SequenceGenerator>,
+    drop_generator: Box<dyn DropGenerator>,
 }
 
 impl DefaultSqlGenerator {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         context: GeneratorContext,
         table_generator: Box<dyn TableGenerator>,
@@ -84,6 +101,8 @@ impl DefaultSqlGenerator {
         procedure_generator: Box<dyn ProcedureGenerator>,
         trigger_generator: Box<dyn TriggerGenerator>,
         other_sql_generator: Box<dyn OtherSqlGenerator>,
+        sequence_generator: Box<dyn SequenceGenerator>,
+        drop_generator: Box<dyn DropGenerator>,
     ) -> Self {
         Self {
             context,
@@ -95,6 +114,8 @@ impl DefaultSqlGenerator {
             procedure_generator,
             trigger_generator,
             other_sql_generator,
+            sequence_generator,
+            drop_generator,
         }
     }
 }
@@ -104,70 +125,86 @@ impl SqlGenerator for DefaultSqlGenerator {
         &self.context
     }
 
-    fn generate(&self) {
-        self.output_sql();
+    fn generate(&self) -> Result<(), GenerationError> {
+        self.output_sql()
     }
 
-    fn output_sql(&self) {
-        self.output_header();
+    fn output_sql(&self) -> Result<(), GenerationError> {
+        if self.context.settings().output_mode() == OutputMode::Drop {
+            return self.output_drops();
+        }
+
+        self.output_header()?;
 
         if self.context.settings().output_mode() == OutputMode::IndexesOnly {
-            self.output_indexes();
+            self.output_indexes()?;
         } else if self.context.settings().output_mode() == OutputMode::TriggersOnly {
-            self.output_triggers();
+            self.output_triggers()?;
         } else {
-            self.output_other_sql_top();
-            self.output_tables();
+            self.output_other_sql_top()?;
+            self.output_sequences()?;
+            self.output_tables()?;
 
             if self.context.settings().foreign_key_mode() == ForeignKeyMode::Relations {
-                self.output_relations();
+                self.output_relations()?;
             }
 
-            self.output_triggers();
-            self.output_functions();
-            self.output_views();
-            self.output_procedures();
-            self.output_other_sql_bottom();
+            self.output_triggers()?;
+            self.output_functions()?;
+            self.output_views()?;
+            self.output_procedures()?;
+            self.output_other_sql_bottom()?;
         }
+
+        Ok(())
     }
 
-    fn output_header(&self) {
+    fn output_header(&self) -> Result<(), GenerationError> {
+        Ok(())
     }
 
-    fn output_tables(&self) {
-        self.table_generator.output_tables();
+    fn output_sequences(&self) -> Result<(), GenerationError> {
+        self.sequence_generator.output_sequences()
     }
 
-    fn output_relations(&self) {
-        self.relation_generator.output_relations();
+    fn output_tables(&self) -> Result<(), GenerationError> {
+        self.table_generator.output_tables()
     }
 
-    fn output_indexes(&self) {
-        self.index_generator.output_indexes();
+    fn output_relations(&self) -> Result<(), GenerationError> {
+        self.relation_generator.output_relations()
     }
 
-    fn output_triggers(&self) {
-        self.trigger_generator.output_triggers();
+    fn output_indexes(&self) -> Result<(), GenerationError> {
+        self.index_generator.output_indexes()
     }
 
-    fn output_functions(&self) {
-        self.function_generator.output_functions();
+    fn output_triggers(&self) -> Result<(), GenerationError> {
+        self.trigger_generator.output_triggers()
     }
 
-    fn output_views(&self) {
-        self.view_generator.output_views();
+    fn output_functions(&self) -> Result<(), GenerationError> {
+        self.function_generator.output_functions()
     }
 
-    fn output_procedures(&self) {
-        self.procedure_generator.output_procedures();
+    fn output_views(&self) -> Result<(), GenerationError> {
+        self.view_generator.output_views()
     }
 
-    fn output_other_sql_top(&self) {
-        self.other_sql_generator.output_other_sql_top();
+    fn output_procedures(&self) -> Result<(), GenerationError> {
+        self.procedure_generator.output_procedures()
     }
 
-    fn output_other_sql_bottom(&self) {
-        self.other_sql_generator.output_other_sql_bottom();
+    fn output_other_sql_top(&self) -> Result<(), GenerationError> {
+        self.other_sql_generator.output_other_sql_top()
+    }
+
+    fn output_other_sql_bottom(&self) -> Result<(), GenerationError> {
+        self.other_sql_generator.output_other_sql_bottom()
+    }
+
+    fn output_drops(&self) -> Result<(), GenerationError> {
+        self.drop_generator.output_drops()
     }
 }
 
@@ -182,6 +219,7 @@ mod tests {
     use crate::common::print_writer::PrintWriter;
     use crate::common::procedure_generator::ProcedureGenerator;
     use crate::common::relation_generator::RelationGenerator;
+    use crate::common::sequence_generator::SequenceGenerator;
     use crate::common::sql_generator_settings::SqlGeneratorSettings;
     use crate::common::sql_writer::SqlWriter;
     use crate::common::table_generator::TableGenerator;
@@ -194,6 +232,7 @@ mod tests {
     use schema_model::model::types::{BooleanMode, DatabaseType, ForeignKeyMode};
     use std::cell::RefCell;
     use std::rc::Rc;
+    use std::sync::Arc;
 
     #[derive(Clone, Default)]
     struct CallLog(Rc<RefCell<Vec<&'static str>>>);
@@ -210,55 +249,77 @@ mod tests {
 
     struct FakeTableGenerator(CallLog);
     impl TableGenerator for FakeTableGenerator {
-        fn output_tables(&self) { self.0.record("tables"); }
-        fn output_table(&self, _table: &Table) {}
-        fn output_table_header(&self, _table: &Table) {}
-        fn output_table_definition(&self, _table: &Table) {}
-        fn output_table_footer(&self, _table: &Table) {}
-        fn output_indexes(&self, _table: &Table) {}
-        fn output_initial_data(&self, _table: &Table) {}
+        fn output_tables(&self) -> Result<(), GenerationError> { self.0.record("tables"); Ok(()) }
+        fn output_table(&self, _table: &Table) -> Result<(), GenerationError> { Ok(()) }
+        fn output_table_header(&self, _table: &Table) -> Result<(), GenerationError> { Ok(()) }
+        fn output_table_definition(&self, _table: &Table) -> Result<(), GenerationError> { Ok(()) }
+        fn output_table_footer(&self, _table: &Table) -> Result<(), GenerationError> { Ok(()) }
+        fn output_table_descriptions(&self, _table: &Table) -> Result<(), GenerationError> { Ok(()) }
+        fn output_indexes(&self, _table: &Table) -> Result<(), GenerationError> { Ok(()) }
+        fn output_initial_data(&self, _table: &Table) -> Result<(), GenerationError> { Ok(()) }
     }
 
     struct FakeRelationGenerator(CallLog);
     impl RelationGenerator for FakeRelationGenerator {
-        fn output_relations(&self) { self.0.record("relations"); }
+        fn output_relations(&self) -> Result<(), GenerationError> { self.0.record("relations"); Ok(()) }
     }
 
     struct FakeIndexGenerator(CallLog);
     impl IndexGenerator for FakeIndexGenerator {
-        fn output_indexes(&self) { self.0.record("indexes"); }
-        fn output_indexes_for_table(&self, _writer: &mut SqlWriter, _table: &Table) {}
-        fn output_index(&self, _writer: &mut SqlWriter, _statement_separator: &str, _table: &Table, _key_name: &str, _key: &Key) {}
+        fn output_indexes(&self) -> Result<(), GenerationError> { self.0.record("indexes"); Ok(()) }
+        fn output_indexes_for_table(&self, _writer: &mut SqlWriter, _table: &Table) -> Result<(), GenerationError> { Ok(()) }
+        fn output_index(&self, _writer: &mut SqlWriter, _statement_separator: &str, _table: &Table, _key_name: &str, _key: &Key) -> Result<(), GenerationError> { Ok(()) }
         fn index_options(&self, _key: &Key) -> Option<String> { None }
     }
 
     struct FakeFunctionGenerator(CallLog);
     impl FunctionGenerator for FakeFunctionGenerator {
-        fn output_functions(&self) { self.0.record("functions"); }
-        fn output_function(&self, _writer: &mut SqlWriter, _statement_separator: &str, _function: &schema_model::model::function::Function) {}
+        fn output_functions(&self) -> Result<(), GenerationError> { self.0.record("functions"); Ok(()) }
+        fn output_function(&self, _writer: &mut SqlWriter, _statement_separator: &str, _function: &schema_model::model::function::Function) -> Result<(), GenerationError> { Ok(()) }
     }
 
     struct FakeViewGenerator(CallLog);
     impl ViewGenerator for FakeViewGenerator {
-        fn output_views(&self) { self.0.record("views"); }
+        fn output_views(&self) -> Result<(), GenerationError> { self.0.record("views"); Ok(()) }
     }
 
     struct FakeProcedureGenerator(CallLog);
     impl ProcedureGenerator for FakeProcedureGenerator {
-        fn output_procedures(&self) { self.0.record("procedures"); }
-        fn output_procedure(&self, _writer: &mut SqlWriter, _statement_separator: &str, _procedure: &schema_model::model::procedure::Procedure) {}
+        fn output_procedures(&self) -> Result<(), GenerationError> { self.0.record("procedures"); Ok(()) }
+        fn output_procedure(&self, _writer: &mut SqlWriter, _statement_separator: &str, _procedure: &schema_model::model::procedure::Procedure) -> Result<(), GenerationError> { Ok(()) }
     }
 
     struct FakeTriggerGenerator(CallLog);
     impl TriggerGenerator for FakeTriggerGenerator {
-        fn output_triggers(&self) { self.0.record("triggers"); }
+        fn output_triggers(&self) -> Result<(), GenerationError> { self.0.record("triggers"); Ok(()) }
+        fn output_table_triggers(&self, _table: &Table) -> Result<(), GenerationError> { Ok(()) }
+        fn trigger_names(&self, _table: &Table) -> Vec<String> { Vec::new() }
+        fn possible_trigger_names(&self, _table_name: &str) -> Vec<String> { Vec::new() }
     }
 
     struct FakeOtherSqlGenerator(CallLog);
     impl OtherSqlGenerator for FakeOtherSqlGenerator {
-        fn output_other_sql_top(&self) { self.0.record("other_sql_top"); }
-        fn output_other_sql_bottom(&self) { self.0.record("other_sql_bottom"); }
-        fn output_other_sql(&self, _writer: &mut SqlWriter, _statement_separator: &str, _sql: &str) {}
+        fn output_other_sql_top(&self) -> Result<(), GenerationError> { self.0.record("other_sql_top"); Ok(()) }
+        fn output_other_sql_bottom(&self) -> Result<(), GenerationError> { self.0.record("other_sql_bottom"); Ok(()) }
+        fn output_other_sql(&self, _writer: &mut SqlWriter, _statement_separator: &str, _sql: &str) -> Result<(), GenerationError> { Ok(()) }
+    }
+
+    struct FakeSequenceGenerator(CallLog);
+    impl SequenceGenerator for FakeSequenceGenerator {
+        fn output_sequences(&self) -> Result<(), GenerationError> { self.0.record("sequences"); Ok(()) }
+    }
+
+    struct FakeDropGenerator(CallLog);
+    impl DropGenerator for FakeDropGenerator {
+        fn output_drops(&self) -> Result<(), GenerationError> { self.0.record("drops"); Ok(()) }
+        fn output_drop_procedures(&self) -> Result<(), GenerationError> { Ok(()) }
+        fn output_drop_views(&self) -> Result<(), GenerationError> { Ok(()) }
+        fn output_drop_functions(&self) -> Result<(), GenerationError> { Ok(()) }
+        fn output_drop_triggers(&self) -> Result<(), GenerationError> { Ok(()) }
+        fn output_drop_relations(&self) -> Result<(), GenerationError> { Ok(()) }
+        fn output_drop_tables(&self) -> Result<(), GenerationError> { Ok(()) }
+        fn output_drop_enum_types(&self) -> Result<(), GenerationError> { Ok(()) }
+        fn output_drop_sequences(&self) -> Result<(), GenerationError> { Ok(()) }
     }
 
     fn make_generator(
@@ -268,14 +329,11 @@ mod tests {
         let log = CallLog::default();
         let schema = SchemaBuilder::new(None::<&str>).build();
         let model = DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema]);
-        let mut options = GenerateOptions::new(
-            Rc::new(model),
-            Rc::new(RefCell::new(PrintWriter::new(Box::new(Vec::<u8>::new())))),
-        );
+        let mut options = GenerateOptions::new(Arc::new(model));
         options.foreign_key_mode = foreign_key_mode;
         options.output_mode = output_mode;
         let settings = SqlGeneratorSettings::new(DatabaseType::Postgresql, &options);
-        let writer = SqlWriter::new(options.writer.clone());
+        let writer = SqlWriter::new(Rc::new(RefCell::new(PrintWriter::new(Box::new(Vec::<u8>::new())))));
         let context = GeneratorContext::new(settings, writer);
 
         let generator = DefaultSqlGenerator::new(
@@ -288,6 +346,8 @@ mod tests {
             Box::new(FakeProcedureGenerator(log.clone())),
             Box::new(FakeTriggerGenerator(log.clone())),
             Box::new(FakeOtherSqlGenerator(log.clone())),
+            Box::new(FakeSequenceGenerator(log.clone())),
+            Box::new(FakeDropGenerator(log.clone())),
         );
         (generator, log)
     }
@@ -295,22 +355,22 @@ mod tests {
     #[test]
     fn output_sql_runs_full_pipeline_in_order_when_foreign_key_mode_is_relations() {
         let (generator, log) = make_generator(ForeignKeyMode::Relations, OutputMode::All);
-        generator.output_sql();
+        generator.output_sql().unwrap();
 
         assert_eq!(
             log.calls(),
-            vec!["other_sql_top", "tables", "relations", "triggers", "functions", "views", "procedures", "other_sql_bottom"]
+            vec!["other_sql_top", "sequences", "tables", "relations", "triggers", "functions", "views", "procedures", "other_sql_bottom"]
         );
     }
 
     #[test]
     fn output_sql_skips_relations_when_foreign_key_mode_is_not_relations() {
         let (generator, log) = make_generator(ForeignKeyMode::Triggers, OutputMode::All);
-        generator.output_sql();
+        generator.output_sql().unwrap();
 
         assert_eq!(
             log.calls(),
-            vec!["other_sql_top", "tables", "triggers", "functions", "views", "procedures", "other_sql_bottom"]
+            vec!["other_sql_top", "sequences", "tables", "triggers", "functions", "views", "procedures", "other_sql_bottom"]
         );
         assert!(!log.calls().contains(&"relations"));
     }
@@ -318,7 +378,7 @@ mod tests {
     #[test]
     fn output_sql_only_runs_indexes_when_output_mode_is_indexes_only() {
         let (generator, log) = make_generator(ForeignKeyMode::Relations, OutputMode::IndexesOnly);
-        generator.output_sql();
+        generator.output_sql().unwrap();
 
         assert_eq!(log.calls(), vec!["indexes"]);
     }
@@ -326,8 +386,16 @@ mod tests {
     #[test]
     fn output_sql_only_runs_triggers_when_output_mode_is_triggers_only() {
         let (generator, log) = make_generator(ForeignKeyMode::Relations, OutputMode::TriggersOnly);
-        generator.output_sql();
+        generator.output_sql().unwrap();
 
         assert_eq!(log.calls(), vec!["triggers"]);
     }
+
+    #[test]
+    fn output_sql_only_runs_drops_when_output_mode_is_drop() {
+        let (generator, log) = make_generator(ForeignKeyMode::Relations, OutputMode::Drop);
+        generator.output_sql().unwrap();
+
+        assert_eq!(log.calls(), vec!["drops"]);
+    }
 }
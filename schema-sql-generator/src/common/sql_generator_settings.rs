@@ -1,20 +1,29 @@
+use crate::common::constraint_naming::ConstraintNaming;
 use crate::common::generate_options::GenerateOptions;
 use crate::common::output_mode::OutputMode;
+use schema_model::model::constraint_naming::ConstraintNamer;
 use schema_model::model::database_model::DatabaseModel;
-use schema_model::model::types::{BooleanMode, DatabaseType, ForeignKeyMode};
-use std::rc::Rc;
+use schema_model::model::naming::NamingConvention;
+use schema_model::model::types::{BooleanMode, DatabaseType, ForeignKeyMode, IdentifierQuoting, IdentityMode};
+use std::sync::Arc;
 
 #[derive(Debug, Clone)]
 pub struct SqlGeneratorSettings {
     database_type: DatabaseType,
-    database_model: Rc<DatabaseModel>,
+    database_model: Arc<DatabaseModel>,
     statement_separator: String,
     foreign_key_mode: ForeignKeyMode,
     boolean_mode: BooleanMode,
     output_mode: OutputMode,
     target_postgres_version: u32,
+    target_mariadb: bool,
     emit_postgres_extensions: bool,
     extension_check_user: Option<String>,
+    identity_mode: IdentityMode,
+    idempotent: bool,
+    identifier_quoting: IdentifierQuoting,
+    naming_convention: NamingConvention,
+    constraint_namer: ConstraintNamer,
 }
 
 impl SqlGeneratorSettings {
@@ -27,8 +36,14 @@ impl SqlGeneratorSettings {
             boolean_mode: options.boolean_mode,
             output_mode: options.output_mode,
             target_postgres_version: options.target_postgres_version,
+            target_mariadb: options.target_mariadb,
             emit_postgres_extensions: options.emit_postgres_extensions,
             extension_check_user: options.extension_check_user.clone(),
+            identity_mode: options.identity_mode,
+            idempotent: options.idempotent,
+            identifier_quoting: options.identifier_quoting,
+            naming_convention: options.naming_convention.clone(),
+            constraint_namer: ConstraintNamer::new(options.constraint_naming.clone(), options.naming_convention.clone(), database_type),
         }
     }
 
@@ -36,7 +51,7 @@ impl SqlGeneratorSettings {
         self.database_type
     }
 
-    pub fn database_model(&self) -> &Rc<DatabaseModel> {
+    pub fn database_model(&self) -> &Arc<DatabaseModel> {
         &self.database_model
     }
 
@@ -60,6 +75,10 @@ impl SqlGeneratorSettings {
         self.target_postgres_version
     }
 
+    pub fn target_mariadb(&self) -> bool {
+        self.target_mariadb
+    }
+
     pub fn emit_postgres_extensions(&self) -> bool {
         self.emit_postgres_extensions
     }
@@ -67,26 +86,94 @@ impl SqlGeneratorSettings {
     pub fn extension_check_user(&self) -> Option<&String> {
         self.extension_check_user.as_ref()
     }
+
+    /// How PostgreSQL sequence columns are generated unless a column says otherwise.
+    pub fn identity_mode(&self) -> IdentityMode {
+        self.identity_mode
+    }
+
+    /// Whether creates are guarded by existence checks, so running the script against a
+    /// database that already has the objects leaves them, and their data, in place.
+    pub fn idempotent(&self) -> bool {
+        self.idempotent
+    }
+
+    pub fn identifier_quoting(&self) -> IdentifierQuoting {
+        self.identifier_quoting
+    }
+
+    pub fn naming_convention(&self) -> &NamingConvention {
+        &self.naming_convention
+    }
+
+    pub fn constraint_naming(&self) -> &ConstraintNaming {
+        self.constraint_namer.naming()
+    }
+
+    /// The database spelling of the model name `name`, unquoted.
+    pub fn database_name(&self, name: &str) -> String {
+        self.naming_convention.to_database(name)
+    }
+
+    /// The model name `name` as the database spells it, quoted for this dialect as the
+    /// quoting policy asks.
+    pub fn quote(&self, name: &str) -> String {
+        self.quote_generated(&self.database_name(name))
+    }
+
+    /// A name the generator built, such as a constraint or trigger name, quoted. Its parts
+    /// already went through the naming convention, so it isn't applied again.
+    pub fn quote_generated(&self, name: &str) -> String {
+        self.database_type.quote_identifier(name, self.identifier_quoting)
+    }
+
+    /// Each of `names` quoted and joined with commas, as in a key's column list.
+    pub fn quote_list(&self, names: &[&str]) -> String {
+        names.iter().map(|name| self.quote(name)).collect::<Vec<_>>().join(",")
+    }
+
+    /// The schema-qualified model name `name`, with the schema and name quoted separately.
+    /// Schema names are not renamed by the naming convention.
+    pub fn qualified_name(&self, schema_name: Option<&str>, name: &str) -> String {
+        self.qualified_generated_name(schema_name, &self.database_name(name))
+    }
+
+    /// `qualified_name` for a name the generator built.
+    pub fn qualified_generated_name(&self, schema_name: Option<&str>, name: &str) -> String {
+        self.database_type.quoted_qualified_name(schema_name, name, self.identifier_quoting)
+    }
+
+    /// Names keys, indexes and foreign keys the way migrations also name them.
+    pub fn constraint_namer(&self) -> &ConstraintNamer {
+        &self.constraint_namer
+    }
+
+    /// A table or column name as part of a generated name; see
+    /// `ConstraintNamer::generated_name_part`.
+    pub fn generated_name_part(&self, name: &str) -> String {
+        self.constraint_namer.generated_name_part(name)
+    }
+
+    /// The name `template` (one of the `constraint_naming` templates) gives a constraint or
+    /// index on `table_name` over `column_names`, kept within the dialect's key name length.
+    pub fn constraint_name(&self, template: &str, table_name: &str, column_names: &[&str], position: Option<usize>) -> String {
+        self.constraint_namer.constraint_name(template, table_name, column_names, position)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::common::print_writer::PrintWriter;
     use schema_model::builder::SchemaBuilder;
-    use schema_model::model::database_model::DatabaseModel;
+use schema_model::model::database_model::DatabaseModel;
     use schema_model::model::types::{BooleanMode, ForeignKeyMode};
-    use std::cell::RefCell;
 
     #[test]
     fn statement_separator_matches_database_type_per_dialect() {
         // Postgres and SQLite batch statements with ";"; SQL Server uses "GO" batches.
         let schema = SchemaBuilder::new(None::<&str>).build();
         let model = DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema]);
-        let options = GenerateOptions::new(
-            std::rc::Rc::new(model),
-            std::rc::Rc::new(RefCell::new(PrintWriter::new(Box::new(Vec::<u8>::new())))),
-        );
+        let options = GenerateOptions::new(Arc::new(model));
 
         let postgres_settings = SqlGeneratorSettings::new(DatabaseType::Postgresql, &options);
         let sqlite_settings = SqlGeneratorSettings::new(DatabaseType::Sqlite, &options);
@@ -97,4 +184,43 @@ mod tests {
         assert_eq!(sqlserver_settings.statement_separator(), "\nGO");
         assert_eq!(sqlserver_settings.statement_separator(), DatabaseType::SqlServer.statement_separator());
     }
+
+    #[test]
+    fn qualified_name_quotes_reserved_words_for_the_dialect() {
+        let schema = SchemaBuilder::new(None::<&str>).build();
+        let model = DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema]);
+        let mut options = GenerateOptions::new(Arc::new(model));
+
+        let postgres_settings = SqlGeneratorSettings::new(DatabaseType::Postgresql, &options);
+        let sqlserver_settings = SqlGeneratorSettings::new(DatabaseType::SqlServer, &options);
+        assert_eq!(postgres_settings.qualified_name(None, "order"), "public.\"order\"");
+        assert_eq!(sqlserver_settings.qualified_name(None, "order"), "dbo.[order]");
+        assert_eq!(postgres_settings.quote_list(&["id", "user"]), "id,\"user\"");
+
+        options.identifier_quoting = IdentifierQuoting::Never;
+        let unquoted_settings = SqlGeneratorSettings::new(DatabaseType::Postgresql, &options);
+        assert_eq!(unquoted_settings.qualified_name(None, "order"), "public.order");
+    }
+
+    #[test]
+    fn naming_convention_renames_model_names_and_generated_name_parts() {
+        let schema = SchemaBuilder::new(None::<&str>).build();
+        let model = DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema]);
+        let mut options = GenerateOptions::new(Arc::new(model));
+
+        let preserving = SqlGeneratorSettings::new(DatabaseType::Postgresql, &options);
+        assert_eq!(preserving.qualified_name(Some("App"), "ParentTable"), "App.ParentTable");
+        assert_eq!(preserving.constraint_name("pk_{table}", "ParentTable", &["Id"], None), "pk_parenttable");
+
+        options.naming_convention = NamingConvention::SnakeCase;
+        options.constraint_naming.foreign_key = "fk_{table}_{column}".to_string();
+        let snake = SqlGeneratorSettings::new(DatabaseType::Postgresql, &options);
+        assert_eq!(snake.qualified_name(None, "ParentTable"), "public.parent_table");
+        assert_eq!(snake.quote_list(&["ParentId", "Name"]), "parent_id,name");
+        assert_eq!(snake.quote_generated("pk_parent_table"), "pk_parent_table");
+        assert_eq!(
+            snake.constraint_name(&snake.constraint_naming().foreign_key, "ChildTable", &["ParentId"], Some(1)),
+            "fk_child_table_parent_id"
+        );
+    }
 }
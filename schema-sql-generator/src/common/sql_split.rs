@@ -1,15 +1,32 @@
-use schema_sql_generator::common::generator_type::GeneratorType;
+use schema_model::model::types::DatabaseType;
 
 /// Splits a SQL script into individual statements, respecting quoting and
 /// comment rules so that semicolons (or, for SQL Server, `GO` batch
-/// separators) embedded inside string/identifier/dollar-quoted literals or
-/// comments are not treated as statement boundaries.
+/// separators, for MySQL the current `delimiter`) embedded inside
+/// string/identifier/dollar-quoted literals or comments are not treated as
+/// statement boundaries.
 ///
 /// Returns trimmed, non-empty statements only.
-pub(crate) fn split_sql_statements(sql: &str, database_type: &GeneratorType) -> Vec<String> {
+pub fn split_sql_statements(sql: &str, database_type: DatabaseType) -> Vec<String> {
     match database_type {
-        GeneratorType::SqlServer => split_on_go_batches(sql),
-        _ => split_on_semicolons(sql),
+        DatabaseType::SqlServer => split_on_go_batches(sql),
+        DatabaseType::MySql => split_on_mysql_delimiters(sql),
+        DatabaseType::Sqlite => split_on_semicolons(sql, true),
+        _ => split_on_semicolons(sql, false),
+    }
+}
+
+/// `sql` after the comments it starts with.
+pub fn skip_leading_comments(sql: &str) -> &str {
+    let mut rest = sql.trim_start();
+    loop {
+        if let Some(comment) = rest.strip_prefix("--") {
+            rest = comment.split_once('\n').map_or("", |(_, after)| after).trim_start();
+        } else if let Some(comment) = rest.strip_prefix("/*") {
+            rest = comment.split_once("*/").map_or("", |(_, after)| after).trim_start();
+        } else {
+            return rest;
+        }
     }
 }
 
@@ -22,8 +39,10 @@ enum State {
     BlockComment,
 }
 
-/// Context-aware `;`-splitter for Postgres/SQLite dialects.
-fn split_on_semicolons(sql: &str) -> Vec<String> {
+/// Context-aware `;`-splitter for Postgres/SQLite dialects. With `trigger_blocks`, the
+/// `;`s between a SQLite `create trigger`'s `begin` and its matching `end` stay inside the
+/// statement.
+fn split_on_semicolons(sql: &str, trigger_blocks: bool) -> Vec<String> {
     let chars: Vec<char> = sql.chars().collect();
     let len = chars.len();
 
@@ -31,6 +50,9 @@ fn split_on_semicolons(sql: &str) -> Vec<String> {
     let mut current_start = 0usize;
     let mut state = State::Normal;
     let mut dollar_tag = String::new();
+    let mut previous_word = String::new();
+    let mut in_trigger = false;
+    let mut block_depth = 0usize;
 
     let mut i = 0usize;
     while i < len {
@@ -59,9 +81,27 @@ fn split_on_semicolons(sql: &str) -> Vec<String> {
                         i += 1;
                     }
                 } else if c == ';' {
-                    statements.push(chars[current_start..i].iter().collect::<String>());
-                    current_start = i + 1;
+                    if block_depth == 0 {
+                        statements.push(chars[current_start..i].iter().collect::<String>());
+                        current_start = i + 1;
+                        previous_word.clear();
+                        in_trigger = false;
+                    }
                     i += 1;
+                } else if trigger_blocks && is_word_start(&chars, i) {
+                    let start = i;
+                    while i < len && (chars[i].is_ascii_alphanumeric() || chars[i] == '_') {
+                        i += 1;
+                    }
+                    let word = chars[start..i].iter().collect::<String>().to_ascii_lowercase();
+                    match word.as_str() {
+                        "trigger" if matches!(previous_word.as_str(), "create" | "temp" | "temporary") => in_trigger = true,
+                        "begin" if in_trigger => block_depth += 1,
+                        "case" if block_depth > 0 => block_depth += 1,
+                        "end" if block_depth > 0 => block_depth -= 1,
+                        _ => {}
+                    }
+                    previous_word = word;
                 } else {
                     i += 1;
                 }
@@ -131,6 +171,12 @@ fn split_on_semicolons(sql: &str) -> Vec<String> {
         .collect()
 }
 
+/// Whether a word starts at `chars[pos]`, rather than continuing an identifier or number.
+fn is_word_start(chars: &[char], pos: usize) -> bool {
+    (chars[pos].is_ascii_alphabetic() || chars[pos] == '_')
+        && (pos == 0 || !(chars[pos - 1].is_ascii_alphanumeric() || chars[pos - 1] == '_' || chars[pos - 1] == '$'))
+}
+
 /// If `chars[pos]` starts a dollar-quote opening tag (`$`, then zero or more
 /// identifier chars, then `$`), returns the tag text and the index just past
 /// the opening `$...$`.
@@ -308,6 +354,122 @@ fn split_on_go_batches(sql: &str) -> Vec<String> {
         .collect()
 }
 
+enum MySqlState {
+    Normal,
+    Quote(char),
+    LineComment,
+    BlockComment,
+}
+
+/// MySQL splitter: statements end at the current delimiter, `;` until a `delimiter X`
+/// line (a command of the `mysql` client, not SQL, so it is dropped) changes it. Strings
+/// may escape a quote with a backslash as well as by doubling it, identifiers are quoted
+/// with backticks, and `#` starts a line comment too.
+fn split_on_mysql_delimiters(sql: &str) -> Vec<String> {
+    let chars: Vec<char> = sql.chars().collect();
+    let len = chars.len();
+
+    let mut statements = Vec::new();
+    let mut current = String::new();
+    let mut delimiter: Vec<char> = vec![';'];
+    let mut state = MySqlState::Normal;
+    let mut at_line_start = true;
+
+    let mut i = 0usize;
+    while i < len {
+        let c = chars[i];
+
+        match state {
+            MySqlState::Normal => {
+                if at_line_start {
+                    at_line_start = false;
+                    let line_end = chars[i..].iter().position(|&ch| ch == '\n').map_or(len, |end| i + end);
+                    let line: String = chars[i..line_end].iter().collect();
+                    if let Some(new_delimiter) = delimiter_command(&line) {
+                        delimiter = new_delimiter.chars().collect();
+                        i = line_end;
+                        continue;
+                    }
+                }
+
+                if chars[i..].starts_with(&delimiter) {
+                    statements.push(std::mem::take(&mut current));
+                    i += delimiter.len();
+                } else if c == '\'' || c == '"' || c == '`' {
+                    state = MySqlState::Quote(c);
+                    current.push(c);
+                    i += 1;
+                } else if c == '#' || (c == '-' && i + 1 < len && chars[i + 1] == '-') {
+                    state = MySqlState::LineComment;
+                    current.push(c);
+                    i += 1;
+                } else if c == '/' && i + 1 < len && chars[i + 1] == '*' {
+                    state = MySqlState::BlockComment;
+                    current.push_str("/*");
+                    i += 2;
+                } else {
+                    at_line_start = c == '\n';
+                    current.push(c);
+                    i += 1;
+                }
+            }
+            MySqlState::Quote(quote) => {
+                if c == '\\' && quote != '`' && i + 1 < len {
+                    current.push(c);
+                    current.push(chars[i + 1]);
+                    i += 2;
+                } else if c == quote && i + 1 < len && chars[i + 1] == quote {
+                    current.push(c);
+                    current.push(c);
+                    i += 2;
+                } else {
+                    if c == quote {
+                        state = MySqlState::Normal;
+                    }
+                    current.push(c);
+                    i += 1;
+                }
+            }
+            MySqlState::LineComment => {
+                if c == '\n' {
+                    state = MySqlState::Normal;
+                    at_line_start = true;
+                }
+                current.push(c);
+                i += 1;
+            }
+            MySqlState::BlockComment => {
+                if c == '*' && i + 1 < len && chars[i + 1] == '/' {
+                    state = MySqlState::Normal;
+                    current.push_str("*/");
+                    i += 2;
+                } else {
+                    current.push(c);
+                    i += 1;
+                }
+            }
+        }
+    }
+    statements.push(current);
+
+    statements
+        .into_iter()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+/// The new delimiter, if `line` is a `delimiter X` command.
+fn delimiter_command(line: &str) -> Option<&str> {
+    let line = line.trim();
+    let (command, delimiter) = line.split_once(char::is_whitespace)?;
+    if command.eq_ignore_ascii_case("delimiter") && !delimiter.trim().is_empty() {
+        Some(delimiter.trim())
+    } else {
+        None
+    }
+}
+
 /// A line is a `GO` batch separator iff, once trimmed of surrounding
 /// whitespace, it is exactly `GO` in any letter case.
 fn is_go_batch_separator(line: &str) -> bool {
@@ -321,7 +483,7 @@ mod tests {
     #[test]
     fn splits_simple_postgres_statements() {
         let sql = "CREATE TABLE t1 (id INT); CREATE TABLE t2 (id INT);";
-        let result = split_sql_statements(sql, &GeneratorType::Postgresql);
+        let result = split_sql_statements(sql, DatabaseType::Postgresql);
         assert_eq!(result.len(), 2);
         assert_eq!(result[0], "CREATE TABLE t1 (id INT)");
         assert_eq!(result[1], "CREATE TABLE t2 (id INT)");
@@ -330,7 +492,7 @@ mod tests {
     #[test]
     fn splits_simple_sqlserver_go_batches() {
         let sql = "CREATE TABLE t1 (id INT)\nGO\nCREATE TABLE t2 (id INT)\nGO";
-        let result = split_sql_statements(sql, &GeneratorType::SqlServer);
+        let result = split_sql_statements(sql, DatabaseType::SqlServer);
         assert_eq!(result.len(), 2);
         assert_eq!(result[0], "CREATE TABLE t1 (id INT)");
         assert_eq!(result[1], "CREATE TABLE t2 (id INT)");
@@ -339,7 +501,7 @@ mod tests {
     #[test]
     fn sqlserver_go_inside_identifier_is_not_a_separator() {
         let sql = "CREATE TABLE t1 (EGO INT)\nGO";
-        let result = split_sql_statements(sql, &GeneratorType::SqlServer);
+        let result = split_sql_statements(sql, DatabaseType::SqlServer);
         assert_eq!(result.len(), 1);
         assert!(result[0].contains("EGO"));
     }
@@ -347,7 +509,7 @@ mod tests {
     #[test]
     fn does_not_split_inside_single_quoted_string_containing_semicolon() {
         let sql = "INSERT INTO t1 (name) VALUES ('a;b'); INSERT INTO t1 (name) VALUES ('c');";
-        let result = split_sql_statements(sql, &GeneratorType::Postgresql);
+        let result = split_sql_statements(sql, DatabaseType::Postgresql);
         assert_eq!(result.len(), 2);
         assert!(result[0].contains("'a;b'"));
     }
@@ -355,7 +517,7 @@ mod tests {
     #[test]
     fn handles_escaped_single_quotes() {
         let sql = "INSERT INTO t1 (name) VALUES ('it''s; fine'); SELECT 1;";
-        let result = split_sql_statements(sql, &GeneratorType::Postgresql);
+        let result = split_sql_statements(sql, DatabaseType::Postgresql);
         assert_eq!(result.len(), 2);
         assert!(result[0].contains("it''s; fine"));
     }
@@ -363,7 +525,7 @@ mod tests {
     #[test]
     fn handles_double_quoted_identifiers_with_semicolon_and_escaped_quote() {
         let sql = r#"SELECT * FROM "weird;table" WHERE "a""b" = 1; SELECT 2;"#;
-        let result = split_sql_statements(sql, &GeneratorType::Postgresql);
+        let result = split_sql_statements(sql, DatabaseType::Postgresql);
         assert_eq!(result.len(), 2);
         assert!(result[0].contains(r#""weird;table""#));
     }
@@ -371,21 +533,21 @@ mod tests {
     #[test]
     fn does_not_split_inside_line_comment() {
         let sql = "SELECT 1; -- comment with a ; semicolon\nSELECT 2;";
-        let result = split_sql_statements(sql, &GeneratorType::Postgresql);
+        let result = split_sql_statements(sql, DatabaseType::Postgresql);
         assert_eq!(result.len(), 2);
     }
 
     #[test]
     fn does_not_split_inside_block_comment() {
         let sql = "SELECT 1; /* comment ; with ; semicolons */ SELECT 2;";
-        let result = split_sql_statements(sql, &GeneratorType::Postgresql);
+        let result = split_sql_statements(sql, DatabaseType::Postgresql);
         assert_eq!(result.len(), 2);
     }
 
     #[test]
     fn does_not_split_inside_bare_dollar_quote() {
         let sql = "CREATE FUNCTION f() RETURNS void LANGUAGE plpgsql AS $$\nBEGIN\n  PERFORM 1; PERFORM 2;\nEND;\n$$; SELECT 1;";
-        let result = split_sql_statements(sql, &GeneratorType::Postgresql);
+        let result = split_sql_statements(sql, DatabaseType::Postgresql);
         assert_eq!(result.len(), 2);
         assert!(result[0].contains("PERFORM 1; PERFORM 2;"));
     }
@@ -393,7 +555,7 @@ mod tests {
     #[test]
     fn does_not_split_inside_tagged_dollar_quote() {
         let sql = "do $createextensions$\nbegin\n  create extension if not exists pgcrypto;\nend\n$createextensions$;\nSELECT 1;";
-        let result = split_sql_statements(sql, &GeneratorType::Postgresql);
+        let result = split_sql_statements(sql, DatabaseType::Postgresql);
         assert_eq!(result.len(), 2);
         assert!(result[0].contains("create extension if not exists pgcrypto;"));
     }
@@ -401,14 +563,14 @@ mod tests {
     #[test]
     fn different_dollar_tags_do_not_close_each_other() {
         let sql = "SELECT $foo$ this has $$ inside it $foo$ as literal;";
-        let result = split_sql_statements(sql, &GeneratorType::Postgresql);
+        let result = split_sql_statements(sql, DatabaseType::Postgresql);
         assert_eq!(result.len(), 1);
     }
 
     #[test]
     fn dollar_sign_not_forming_a_quote_is_left_alone() {
         let sql = "INSERT INTO prices (amount) VALUES ('$5'); SELECT 1;";
-        let result = split_sql_statements(sql, &GeneratorType::Postgresql);
+        let result = split_sql_statements(sql, DatabaseType::Postgresql);
         assert_eq!(result.len(), 2);
         assert!(result[0].contains("'$5'"));
     }
@@ -440,7 +602,7 @@ $createextensions$;
 
 insert into books (id, title) values (generate_uuid(), 'Book; With Semicolon');
 "#;
-        let result = split_sql_statements(sql, &GeneratorType::Postgresql);
+        let result = split_sql_statements(sql, DatabaseType::Postgresql);
         assert_eq!(result.len(), 4);
         assert!(result[0].starts_with("create table books"));
         assert!(result[1].starts_with("create or replace function generate_uuid"));
@@ -454,7 +616,7 @@ insert into books (id, title) values (generate_uuid(), 'Book; With Semicolon');
     #[test]
     fn empty_and_whitespace_only_statements_are_filtered() {
         let sql = "  ;\nCREATE TABLE t1 (id INT);\n\n  ;  ";
-        let result = split_sql_statements(sql, &GeneratorType::Postgresql);
+        let result = split_sql_statements(sql, DatabaseType::Postgresql);
         assert_eq!(result.len(), 1);
         assert_eq!(result[0], "CREATE TABLE t1 (id INT)");
     }
@@ -462,8 +624,17 @@ insert into books (id, title) values (generate_uuid(), 'Book; With Semicolon');
     #[test]
     fn sqlite_uses_semicolon_splitting_like_postgres() {
         let sql = "CREATE TABLE t1 (id INT); CREATE TABLE t2 (id INT);";
-        let result = split_sql_statements(sql, &GeneratorType::Sqlite);
+        let result = split_sql_statements(sql, DatabaseType::Sqlite);
+        assert_eq!(result.len(), 2);
+    }
+
+    #[test]
+    fn sqlite_keeps_a_trigger_body_in_one_statement() {
+        let sql = "create trigger t_delete before delete on t\nbegin\n   update c set v = case when 1 then 2 end;\n   delete from d;\nend;\ndrop table x;";
+        let result = split_sql_statements(sql, DatabaseType::Sqlite);
         assert_eq!(result.len(), 2);
+        assert!(result[0].ends_with("delete from d;\nend"));
+        assert_eq!(result[1], "drop table x");
     }
 
     #[test]
@@ -471,7 +642,7 @@ insert into books (id, title) values (generate_uuid(), 'Book; With Semicolon');
         // Regression test: a GO-only line inside a multi-line string literal must not be
         // mistaken for a real batch separator.
         let sql = "INSERT INTO notes (body) VALUES ('line one\nGO\nline three')\nGO";
-        let result = split_sql_statements(sql, &GeneratorType::SqlServer);
+        let result = split_sql_statements(sql, DatabaseType::SqlServer);
         assert_eq!(result.len(), 1);
         assert!(result[0].contains("line one\nGO\nline three"));
     }
@@ -481,7 +652,7 @@ insert into books (id, title) values (generate_uuid(), 'Book; With Semicolon');
         // Regression test: a GO-only line inside a multi-line block comment must not be
         // mistaken for a real batch separator.
         let sql = "CREATE TABLE t1 (id INT)\n/* a comment\nGO\nstill a comment */\nGO";
-        let result = split_sql_statements(sql, &GeneratorType::SqlServer);
+        let result = split_sql_statements(sql, DatabaseType::SqlServer);
         assert_eq!(result.len(), 1);
         assert!(result[0].contains("still a comment"));
     }
@@ -491,7 +662,7 @@ insert into books (id, title) values (generate_uuid(), 'Book; With Semicolon');
         // The batch containing the multi-line string must still end at the real,
         // top-level GO that follows it, and a second batch must start cleanly after.
         let sql = "INSERT INTO notes (body) VALUES ('a\nb')\nGO\nCREATE TABLE t2 (id INT)\nGO";
-        let result = split_sql_statements(sql, &GeneratorType::SqlServer);
+        let result = split_sql_statements(sql, DatabaseType::SqlServer);
         assert_eq!(result.len(), 2);
         assert!(result[0].contains("'a\nb'"));
         assert!(result[1].starts_with("CREATE TABLE t2"));
@@ -500,9 +671,28 @@ insert into books (id, title) values (generate_uuid(), 'Book; With Semicolon');
     #[test]
     fn sqlserver_go_after_line_comment_on_same_line_still_splits_correctly() {
         let sql = "CREATE TABLE t1 (id INT) -- trailing comment\nGO\nCREATE TABLE t2 (id INT)\nGO";
-        let result = split_sql_statements(sql, &GeneratorType::SqlServer);
+        let result = split_sql_statements(sql, DatabaseType::SqlServer);
         assert_eq!(result.len(), 2);
         assert!(result[0].starts_with("CREATE TABLE t1"));
         assert!(result[1].starts_with("CREATE TABLE t2"));
     }
+
+    #[test]
+    fn mysql_splits_on_the_current_delimiter_and_drops_delimiter_commands() {
+        let sql = "drop trigger if exists t_insert;\ndelimiter //\ncreate trigger t_insert after insert on t for each row\nbegin\n   set @a = 1;\nend//\ndelimiter ;\nselect 1;";
+        let result = split_sql_statements(sql, DatabaseType::MySql);
+        assert_eq!(result.len(), 3);
+        assert_eq!(result[0], "drop trigger if exists t_insert");
+        assert_eq!(result[1], "create trigger t_insert after insert on t for each row\nbegin\n   set @a = 1;\nend");
+        assert_eq!(result[2], "select 1");
+    }
+
+    #[test]
+    fn mysql_does_not_split_inside_backslash_escaped_strings_backticks_or_hash_comments() {
+        let sql = "insert into `a;b` values ('it\\'s; fine'); # a ; comment\nselect 2;";
+        let result = split_sql_statements(sql, DatabaseType::MySql);
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0], "insert into `a;b` values ('it\\'s; fine')");
+        assert!(result[1].ends_with("select 2"));
+    }
 }
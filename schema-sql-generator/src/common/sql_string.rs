@@ -9,6 +9,12 @@ pub fn escape_sql_literal(value: &str) -> String {
     value.replace('\'', "''")
 }
 
+/// `escape_sql_literal` for MySQL, which also reads a backslash in a string literal as
+/// the start of an escape sequence, so `\'` would end the literal early.
+pub fn escape_mysql_literal(value: &str) -> String {
+    escape_sql_literal(&value.replace('\\', "\\\\"))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -23,4 +29,9 @@ mod tests {
         assert_eq!(escape_sql_literal("O'Brien"), "O''Brien");
         assert_eq!(escape_sql_literal("it's a 'test'"), "it''s a ''test''");
     }
+
+    #[test]
+    fn mysql_literals_also_double_backslashes() {
+        assert_eq!(escape_mysql_literal(r"C:\temp\' or 1=1"), r"C:\\temp\\'' or 1=1");
+    }
 }
@@ -1,55 +1,76 @@
+use crate::common::generated_statement::{ObjectMarker, StatementKind};
 use crate::common::print_writer::PrintWriter;
 use std::cell::RefCell;
+use std::io;
 use std::rc::Rc;
 
 #[derive(Clone)]
 pub struct SqlWriter {
     print_writer: Rc<RefCell<PrintWriter>>,
+    markers: Vec<ObjectMarker>,
 }
 
 impl SqlWriter {
     pub fn new(writer: Rc<RefCell<PrintWriter>>) -> Self {
         Self {
             print_writer: writer,
+            markers: Vec::new(),
         }
     }
 
-    pub fn print(&mut self, text: &str) {
+    /// Marks everything written from here on, up to the next mark, as the SQL for one
+    /// object. The marks are what let a script be handed back as typed statements.
+    pub fn begin_object(&mut self, kind: StatementKind, object_name: Option<&str>) {
+        self.markers.push(ObjectMarker {
+            position: self.print_writer.borrow().position(),
+            kind,
+            object_name: object_name.map(str::to_string),
+        });
+    }
+
+    pub(crate) fn markers(&self) -> &[ObjectMarker] {
+        &self.markers
+    }
+
+    pub fn print(&mut self, text: &str) -> io::Result<()> {
         self.print_writer.borrow_mut().print(text)
     }
 
-    pub fn println(&mut self, text: &str) {
+    pub fn println(&mut self, text: &str) -> io::Result<()> {
         self.print_writer.borrow_mut().println(text)
     }
 
-    pub fn printf(&mut self, args: std::fmt::Arguments) {
+    pub fn printf(&mut self, args: std::fmt::Arguments) -> io::Result<()> {
         self.print_writer.borrow_mut().printf(args)
     }
 
-    pub fn newline(&mut self) {
+    pub fn newline(&mut self) -> io::Result<()> {
         self.print_writer.borrow_mut().newline()
     }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.print_writer.borrow_mut().flush()
+    }
 }
 
 #[macro_export]
 macro_rules! sql_print {
     ($writer:expr, $($arg:tt)*) => {
-        $writer.printf(format_args!($($arg)*));
+        $writer.printf(format_args!($($arg)*))
     };
 }
 
 #[macro_export]
 macro_rules! sql_println {
     ($writer:expr, $($arg:tt)*) => {
-        $writer.printf(format_args!($($arg)*));
-        $writer.newline();
+        $writer.printf(format_args!($($arg)*)).and_then(|_| $writer.newline())
     };
 }
 
 #[macro_export]
 macro_rules! sql_newline {
     ($writer:expr) => {
-        $writer.newline();
+        $writer.newline()
     };
 }
 
@@ -67,13 +88,13 @@ mod tests {
     // `print`/`printf` don't auto-flush (only println/newline do; see PrintWriter), so an
     // explicit flush is needed to observe their output through the underlying buffered writer.
     fn flush(writer: &SqlWriter) {
-        writer.print_writer.borrow_mut().flush();
+        writer.print_writer.borrow_mut().flush().unwrap();
     }
 
     #[test]
     fn print_writes_without_trailing_newline() {
         let (mut writer, buffer) = make_writer();
-        writer.print("hello");
+        writer.print("hello").unwrap();
         flush(&writer);
         assert_eq!(buffer.contents(), "hello");
     }
@@ -81,16 +102,16 @@ mod tests {
     #[test]
     fn println_appends_a_newline() {
         let (mut writer, buffer) = make_writer();
-        writer.println("hello");
+        writer.println("hello").unwrap();
         assert_eq!(buffer.contents(), "hello\n");
     }
 
     #[test]
     fn newline_writes_a_bare_newline() {
         let (mut writer, buffer) = make_writer();
-        writer.print("a");
-        writer.newline();
-        writer.print("b");
+        writer.print("a").unwrap();
+        writer.newline().unwrap();
+        writer.print("b").unwrap();
         flush(&writer);
         assert_eq!(buffer.contents(), "a\nb");
     }
@@ -98,7 +119,7 @@ mod tests {
     #[test]
     fn printf_formats_arguments() {
         let (mut writer, buffer) = make_writer();
-        writer.printf(format_args!("count = {}", 42));
+        writer.printf(format_args!("count = {}", 42)).unwrap();
         flush(&writer);
         assert_eq!(buffer.contents(), "count = 42");
     }
@@ -106,7 +127,7 @@ mod tests {
     #[test]
     fn sql_print_macro_writes_without_newline() {
         let (mut writer, buffer) = make_writer();
-        sql_print!(writer, "value {}", 1);
+        sql_print!(writer, "value {}", 1).unwrap();
         flush(&writer);
         assert_eq!(buffer.contents(), "value 1");
     }
@@ -114,16 +135,16 @@ mod tests {
     #[test]
     fn sql_println_macro_appends_newline() {
         let (mut writer, buffer) = make_writer();
-        sql_println!(writer, "value {}", 1);
+        sql_println!(writer, "value {}", 1).unwrap();
         assert_eq!(buffer.contents(), "value 1\n");
     }
 
     #[test]
     fn sql_newline_macro_writes_bare_newline() {
         let (mut writer, buffer) = make_writer();
-        sql_print!(writer, "a");
-        sql_newline!(writer);
-        sql_print!(writer, "b");
+        sql_print!(writer, "a").unwrap();
+        sql_newline!(writer).unwrap();
+        sql_print!(writer, "b").unwrap();
         flush(&writer);
         assert_eq!(buffer.contents(), "a\nb");
     }
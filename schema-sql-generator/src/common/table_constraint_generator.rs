@@ -1,9 +1,10 @@
+use crate::common::generation_error::GenerationError;
 use crate::common::generator_context::GeneratorContext;
 use schema_model::model::constraint::Constraint;
 use schema_model::model::table::Table;
 
 pub trait TableConstraintGenerator {
-    fn table_check_constraints(&self, table: &Table) -> Vec<String>;
+    fn table_check_constraints(&self, table: &Table) -> Result<Vec<String>, GenerationError>;
 }
 
 pub struct DefaultTableConstraintGenerator {
@@ -23,15 +24,15 @@ impl DefaultTableConstraintGenerator {
 
     fn generator_constraint(&self, constraint: &Constraint) -> String {
         format!("   constraint {} {}",
-               constraint.name(),
+               self.context.settings().quote(constraint.name()),
                constraint.sql())
     }
 }
 
 impl TableConstraintGenerator for DefaultTableConstraintGenerator {
-    fn table_check_constraints(&self, table: &Table) -> Vec<String> {
-        table.constraints().iter().map(|constraint| {
+    fn table_check_constraints(&self, table: &Table) -> Result<Vec<String>, GenerationError> {
+        Ok(table.constraints().iter().map(|constraint| {
             self.generator_constraint(constraint)
-        }).collect()
+        }).collect())
     }
 }
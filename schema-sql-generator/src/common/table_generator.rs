@@ -1,21 +1,25 @@
 use crate::common::column_constraint_generator::ColumnConstraintGenerator;
 use crate::common::column_generator::ColumnGenerator;
+use crate::common::generated_statement::StatementKind;
+use crate::common::generation_error::GenerationError;
 use crate::common::generator_context::GeneratorContext;
 use crate::common::index_generator::IndexGenerator;
 use crate::common::key_generator::KeyGenerator;
+use crate::common::sql_string::escape_sql_literal;
 use crate::common::table_constraint_generator::TableConstraintGenerator;
 use crate::{sql_newline, sql_print, sql_println};
 use schema_model::model::table::Table;
 use schema_model::model::types::DatabaseType;
 
 pub trait TableGenerator {
-    fn output_tables(&self);
-    fn output_table(&self, table: &Table);
-    fn output_table_header(&self, table: &Table);
-    fn output_table_definition(&self, table: &Table);
-    fn output_table_footer(&self, table: &Table);
-    fn output_indexes(&self, table: &Table);
-    fn output_initial_data(&self, table: &Table);
+    fn output_tables(&self) -> Result<(), GenerationError>;
+    fn output_table(&self, table: &Table) -> Result<(), GenerationError>;
+    fn output_table_header(&self, table: &Table) -> Result<(), GenerationError>;
+    fn output_table_definition(&self, table: &Table) -> Result<(), GenerationError>;
+    fn output_table_footer(&self, table: &Table) -> Result<(), GenerationError>;
+    fn output_table_descriptions(&self, table: &Table) -> Result<(), GenerationError>;
+    fn output_indexes(&self, table: &Table) -> Result<(), GenerationError>;
+    fn output_initial_data(&self, table: &Table) -> Result<(), GenerationError>;
 }
 
 pub struct DefaultTableGenerator {
@@ -49,104 +53,171 @@ impl DefaultTableGenerator {
     /// Same as `output_table_definition`, but appends `extra` definition clauses
     /// (e.g. dialect-specific inline foreign key constraints) to the comma-separated
     /// list before printing.
-    pub fn output_table_definition_with_extra(&self, table: &Table, extra: Vec<String>) {
-        let table_definitions: Vec<String> = self.column_generator.column_definitions(table)
+    pub fn output_table_definition_with_extra(&self, table: &Table, extra: Vec<String>) -> Result<(), GenerationError> {
+        let table_definitions: Vec<String> = self.column_generator.column_definitions(table)?
             .into_iter()
-            .chain(self.key_generator.key_constraints(table))
-            .chain(self.column_constraint_generator.column_check_constraints(table))
-            .chain(self.table_constraint_generator.table_check_constraints(table))
+            .chain(self.key_generator.key_constraints(table)?)
+            .chain(self.column_constraint_generator.column_check_constraints(table)?)
+            .chain(self.table_constraint_generator.table_check_constraints(table)?)
             .chain(extra)
             .collect();
 
         self.context.with_writer(|writer| {
             for (i, sql) in table_definitions.iter().enumerate() {
-                sql_print!(writer, "{}", sql);
+                sql_print!(writer, "{}", sql)?;
 
                 if i < table_definitions.len() - 1 {
-                    sql_print!(writer, ",");
+                    sql_print!(writer, ",")?;
                 }
 
-                sql_newline!(writer);
+                sql_newline!(writer)?;
             }
-        });
+            Ok(())
+        })
     }
 }
 
 impl TableGenerator for DefaultTableGenerator {
-    fn output_tables(&self) {
+    fn output_tables(&self) -> Result<(), GenerationError> {
         for schema in self.context.settings().database_model().schemas() {
             for table in schema.tables() {
-                self.output_table(table);
+                self.output_table(table)?;
             };
         }
+        Ok(())
     }
 
 
-    fn output_table(&self, table: &Table) {
-        self.output_table_header(table);
-        self.output_table_definition(table);
-        self.output_table_footer(table);
-        self.output_indexes(table);
-        self.output_initial_data(table);
+    fn output_table(&self, table: &Table) -> Result<(), GenerationError> {
+        output_table_steps(self, table)
     }
 
-    fn output_table_header(&self, table: &Table) {
+    fn output_table_header(&self, table: &Table) -> Result<(), GenerationError> {
         let is_postgres = self.context.settings().database_type() == DatabaseType::Postgresql;
         let cascade_suffix = if is_postgres { " cascade" } else { "" };
         let separator = self.context.settings().statement_separator().to_string();
-        let fully_qualified_table_name = table.fully_qualified_table_name(self.context.settings().database_type());
+        let fully_qualified_table_name = self.context.settings().qualified_name(table.schema_name(), table.name());
+
+        let idempotent = self.context.settings().idempotent();
 
         self.context.with_writer(|writer| {
-            if is_postgres {
-                sql_println!(writer, "/* {} */", fully_qualified_table_name);
-                sql_println!(writer, "drop table if exists {}{}{}", fully_qualified_table_name, cascade_suffix, separator);
-                sql_println!(writer, "");
-                sql_println!(writer, "create table {}", fully_qualified_table_name);
-                sql_println!(writer, "(");
+            writer.begin_object(StatementKind::Table, Some(&table.fully_qualified_table_name(self.context.settings().database_type())));
+            if idempotent {
+                // Keeps an existing table and its rows rather than dropping it.
+                sql_println!(writer, "/* {} */", fully_qualified_table_name)?;
+                sql_println!(writer, "create table if not exists {}", fully_qualified_table_name)?;
+                sql_println!(writer, "(")?;
+            } else if is_postgres {
+                sql_println!(writer, "/* {} */", fully_qualified_table_name)?;
+                sql_println!(writer, "drop table if exists {}{}{}", fully_qualified_table_name, cascade_suffix, separator)?;
+                sql_println!(writer, "")?;
+                sql_println!(writer, "create table {}", fully_qualified_table_name)?;
+                sql_println!(writer, "(")?;
             } else {
-                sql_println!(writer, "drop table if exists {}{}{}", fully_qualified_table_name, cascade_suffix, separator);
-                sql_println!(writer, "/* {} */", fully_qualified_table_name);
-                sql_println!(writer, "create table {}", fully_qualified_table_name);
-                sql_println!(writer, "(");
+                sql_println!(writer, "drop table if exists {}{}{}", fully_qualified_table_name, cascade_suffix, separator)?;
+                sql_println!(writer, "/* {} */", fully_qualified_table_name)?;
+                sql_println!(writer, "create table {}", fully_qualified_table_name)?;
+                sql_println!(writer, "(")?;
             }
-        });
+            Ok(())
+        })
     }
 
-    fn output_table_definition(&self, table: &Table) {
-        self.output_table_definition_with_extra(table, Vec::new());
+    fn output_table_definition(&self, table: &Table) -> Result<(), GenerationError> {
+        self.output_table_definition_with_extra(table, Vec::new())
     }
 
-    fn output_table_footer(&self, _table: &Table) {
+    fn output_table_footer(&self, _table: &Table) -> Result<(), GenerationError> {
         self.context.with_writer(|writer| {
-            sql_println!(writer, "){}", self.context.settings().statement_separator());
-            sql_newline!(writer);
-        });
+            sql_println!(writer, "){}", self.context.settings().statement_separator())?;
+            sql_newline!(writer)?;
+            Ok(())
+        })
+    }
+
+    fn output_table_descriptions(&self, table: &Table) -> Result<(), GenerationError> {
+        let database_type = self.context.settings().database_type();
+        let separator = self.context.settings().statement_separator().to_string();
+        let fully_qualified_table_name = self.context.settings().qualified_name(table.schema_name(), table.name());
+        let column_descriptions: Vec<(String, &str)> = table.columns()
+            .iter()
+            .filter_map(|column| column.description().map(|description| (self.context.settings().quote(column.name()), description)))
+            .collect();
+
+        if table.description().is_none() && column_descriptions.is_empty() {
+            return Ok(());
+        }
+
+        self.context.with_writer(|writer| {
+            writer.begin_object(StatementKind::Table, Some(&table.fully_qualified_table_name(database_type)));
+            if matches!(database_type, DatabaseType::Postgresql | DatabaseType::DuckDb) {
+                if let Some(description) = table.description() {
+                    sql_println!(writer, "comment on table {} is '{}'{}", fully_qualified_table_name, escape_sql_literal(description), separator)?;
+                }
+                for (column_name, description) in &column_descriptions {
+                    sql_println!(writer, "comment on column {}.{} is '{}'{}", fully_qualified_table_name, column_name, escape_sql_literal(description), separator)?;
+                }
+            } else {
+                // SQLite has no catalog for comments, so the descriptions are kept as SQL
+                // comments in the script.
+                if let Some(description) = table.description() {
+                    sql_println!(writer, "-- {}: {}", fully_qualified_table_name, sql_line_comment_text(description))?;
+                }
+                for (column_name, description) in &column_descriptions {
+                    sql_println!(writer, "-- {}.{}: {}", fully_qualified_table_name, column_name, sql_line_comment_text(description))?;
+                }
+            }
+            sql_newline!(writer)?;
+            Ok(())
+        })
     }
 
-    fn output_indexes(&self, table: &Table) {
+    fn output_indexes(&self, table: &Table) -> Result<(), GenerationError> {
         self.context.with_writer(|writer| {
-            self.index_generator.output_indexes_for_table(writer, table);
-        });
+            self.index_generator.output_indexes_for_table(writer, table)
+        })
     }
 
-    fn output_initial_data(&self, table: &Table) {
+    fn output_initial_data(&self, table: &Table) -> Result<(), GenerationError> {
         let initial_data = table.initial_data()
             .iter()
             .filter(|it| { it.database_type().is_none() || it.database_type().unwrap() == self.context.settings().database_type() })
             .collect::<Vec<_>>();
 
         if !initial_data.is_empty() {
+            let fully_qualified_table_name = table.fully_qualified_table_name(self.context.settings().database_type());
             self.context.with_writer(|writer| {
-                initial_data.iter().for_each(|it| {
-                    sql_println!(writer, "{}{}", it.sql(), self.context.settings().statement_separator());
-                });
+                writer.begin_object(StatementKind::Table, Some(&fully_qualified_table_name));
+                for it in &initial_data {
+                    sql_println!(writer, "{}{}", it.sql(), self.context.settings().statement_separator())?;
+                }
 
-                writer.newline();
-            });
+                writer.newline()
+            })?;
         }
+        Ok(())
     }
 }
 
+/// Runs every step of generating `table`, naming the table in any I/O error. Dialect
+/// wrappers call this with themselves so their overrides of the individual steps apply.
+pub fn output_table_steps(generator: &dyn TableGenerator, table: &Table) -> Result<(), GenerationError> {
+    let steps = || {
+        generator.output_table_header(table)?;
+        generator.output_table_definition(table)?;
+        generator.output_table_footer(table)?;
+        generator.output_table_descriptions(table)?;
+        generator.output_indexes(table)?;
+        generator.output_initial_data(table)
+    };
+    steps().map_err(|e| e.within(|| format!("table {}", table.name())))
+}
+
+/// Continues each line of a multi-line description as a `--` comment.
+fn sql_line_comment_text(description: &str) -> String {
+    description.lines().map(str::trim_end).collect::<Vec<_>>().join("\n--   ")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -154,8 +225,9 @@ mod tests {
     use crate::common::index_generator::IndexGenerator;
     use crate::common::key_generator::KeyGenerator;
     use crate::common::table_constraint_generator::TableConstraintGenerator;
-    use crate::common::test_support::make_context;
-    use schema_model::builder::{SchemaBuilder, TableBuilder};
+    use crate::common::test_support::{make_context, make_idempotent_context};
+    use schema_model::builder::{ColumnBuilder, SchemaBuilder, TableBuilder};
+    use schema_model::model::column_type::ColumnType;
     use schema_model::model::column::Column;
     use schema_model::model::database_model::DatabaseModel;
     use schema_model::model::initial_data::InitialData;
@@ -164,32 +236,32 @@ mod tests {
 
     struct NoopColumnGenerator;
     impl ColumnGenerator for NoopColumnGenerator {
-        fn column_definitions(&self, _table: &Table) -> Vec<String> { Vec::new() }
-        fn column_sql(&self, _table: &Table, _column: &Column) -> String { String::new() }
-        fn column_options(&self, _table: &Table, _column: &Column) -> String { String::new() }
-        fn default_value(&self, _table: &Table, _column: &Column) -> Option<String> { None }
+        fn column_definitions(&self, _table: &Table) -> Result<Vec<String>, GenerationError> { Ok(Vec::new()) }
+        fn column_sql(&self, _table: &Table, _column: &Column) -> Result<String, GenerationError> { Ok(String::new()) }
+        fn column_options(&self, _table: &Table, _column: &Column) -> Result<String, GenerationError> { Ok(String::new()) }
+        fn default_value(&self, _table: &Table, _column: &Column) -> Result<Option<String>, GenerationError> { Ok(None) }
     }
 
     struct NoopKeyGenerator;
     impl KeyGenerator for NoopKeyGenerator {
-        fn key_constraints(&self, _table: &Table) -> Vec<String> { Vec::new() }
+        fn key_constraints(&self, _table: &Table) -> Result<Vec<String>, GenerationError> { Ok(Vec::new()) }
     }
 
     struct NoopColumnConstraintGenerator;
     impl ColumnConstraintGenerator for NoopColumnConstraintGenerator {
-        fn column_check_constraints(&self, _table: &Table) -> Vec<String> { Vec::new() }
+        fn column_check_constraints(&self, _table: &Table) -> Result<Vec<String>, GenerationError> { Ok(Vec::new()) }
     }
 
     struct NoopTableConstraintGenerator;
     impl TableConstraintGenerator for NoopTableConstraintGenerator {
-        fn table_check_constraints(&self, _table: &Table) -> Vec<String> { Vec::new() }
+        fn table_check_constraints(&self, _table: &Table) -> Result<Vec<String>, GenerationError> { Ok(Vec::new()) }
     }
 
     struct NoopIndexGenerator;
     impl IndexGenerator for NoopIndexGenerator {
-        fn output_indexes(&self) {}
-        fn output_indexes_for_table(&self, _writer: &mut crate::common::sql_writer::SqlWriter, _table: &Table) {}
-        fn output_index(&self, _writer: &mut crate::common::sql_writer::SqlWriter, _statement_separator: &str, _table: &Table, _key_name: &str, _key: &Key) {}
+        fn output_indexes(&self) -> Result<(), GenerationError> { Ok(()) }
+        fn output_indexes_for_table(&self, _writer: &mut crate::common::sql_writer::SqlWriter, _table: &Table) -> Result<(), GenerationError> { Ok(()) }
+        fn output_index(&self, _writer: &mut crate::common::sql_writer::SqlWriter, _statement_separator: &str, _table: &Table, _key_name: &str, _key: &Key) -> Result<(), GenerationError> { Ok(()) }
         fn index_options(&self, _key: &Key) -> Option<String> { None }
     }
 
@@ -216,7 +288,7 @@ mod tests {
         let (ctx, buffer) = make_context(model, DatabaseType::Sqlite);
 
         let generator = make_generator(ctx);
-        generator.output_initial_data(&table);
+        generator.output_initial_data(&table).unwrap();
 
         let output = buffer.contents();
         assert!(output.contains("insert into users values (1)"));
@@ -232,7 +304,7 @@ mod tests {
         let (ctx, buffer) = make_context(model, DatabaseType::Sqlite);
 
         let generator = make_generator(ctx);
-        generator.output_initial_data(&table);
+        generator.output_initial_data(&table).unwrap();
 
         assert_eq!(buffer.contents(), "");
     }
@@ -247,7 +319,7 @@ mod tests {
         let (ctx, buffer) = make_context(model, DatabaseType::Sqlite);
 
         let generator = make_generator(ctx);
-        generator.output_table(&table);
+        generator.output_table(&table).unwrap();
 
         let output = buffer.contents();
         let header_pos = output.find("create table users").unwrap();
@@ -256,4 +328,50 @@ mod tests {
         assert!(header_pos < footer_pos);
         assert!(footer_pos < data_pos);
     }
+
+    #[test]
+    fn output_table_descriptions_writes_sql_comments_for_sqlite() {
+        let table = TableBuilder::new(None::<&str>, "users")
+            .description(Some("Application users\nOne row per login".to_string()))
+            .add_column(ColumnBuilder::new(None::<&str>, "name", ColumnType::Varchar).description(Some("Display name".to_string())).build())
+            .build();
+        let schema = SchemaBuilder::new(None::<&str>).add_table(table.clone()).build();
+        let model = DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema]);
+        let (ctx, buffer) = make_context(model, DatabaseType::Sqlite);
+
+        let generator = make_generator(ctx);
+        generator.output_table_descriptions(&table).unwrap();
+
+        let output = buffer.contents();
+        assert!(output.contains("-- users: Application users\n--   One row per login\n"));
+        assert!(output.contains("-- users.name: Display name\n"));
+    }
+
+    #[test]
+    fn output_table_descriptions_writes_nothing_without_descriptions() {
+        let table = TableBuilder::new(None::<&str>, "users").build();
+        let schema = SchemaBuilder::new(None::<&str>).add_table(table.clone()).build();
+        let model = DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema]);
+        let (ctx, buffer) = make_context(model, DatabaseType::Postgresql);
+
+        let generator = make_generator(ctx);
+        generator.output_table_descriptions(&table).unwrap();
+
+        assert!(buffer.contents().is_empty());
+    }
+
+    #[test]
+    fn output_table_header_keeps_an_existing_table_when_idempotent() {
+        let table = TableBuilder::new(None::<&str>, "users").build();
+        let schema = SchemaBuilder::new(None::<&str>).add_table(table.clone()).build();
+        let model = DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema]);
+        let (ctx, buffer) = make_idempotent_context(model, DatabaseType::Postgresql);
+
+        let generator = make_generator(ctx);
+        generator.output_table_header(&table).unwrap();
+
+        let output = buffer.contents();
+        assert!(output.contains("create table if not exists public.users\n("));
+        assert!(!output.contains("drop table"));
+    }
 }
@@ -6,6 +6,7 @@ use crate::common::print_writer::PrintWriter;
 use crate::common::sql_generator_settings::SqlGeneratorSettings;
 use crate::common::sql_writer::SqlWriter;
 use schema_model::model::database_model::DatabaseModel;
+use schema_model::model::naming::NamingConvention;
 use schema_model::model::types::{DatabaseType, ForeignKeyMode};
 use std::cell::RefCell;
 use std::io::Write;
@@ -37,12 +38,9 @@ impl Write for SharedBuffer {
 
 pub fn make_context(model: DatabaseModel, database_type: DatabaseType) -> (GeneratorContext, SharedBuffer) {
     let buffer = SharedBuffer::new();
-    let options = GenerateOptions::new(
-        Rc::new(model),
-        Rc::new(RefCell::new(PrintWriter::new_auto_flush(Box::new(buffer.clone())))),
-    );
+    let options = GenerateOptions::new(Arc::new(model));
     let settings = SqlGeneratorSettings::new(database_type, &options);
-    let writer = SqlWriter::new(options.writer.clone());
+    let writer = SqlWriter::new(Rc::new(RefCell::new(PrintWriter::new_auto_flush(Box::new(buffer.clone())))));
     (GeneratorContext::new(settings, writer), buffer)
 }
 
@@ -52,12 +50,31 @@ pub fn make_context_with_fk_mode(
     foreign_key_mode: ForeignKeyMode,
 ) -> (GeneratorContext, SharedBuffer) {
     let buffer = SharedBuffer::new();
-    let mut options = GenerateOptions::new(
-        Rc::new(model),
-        Rc::new(RefCell::new(PrintWriter::new_auto_flush(Box::new(buffer.clone())))),
-    );
+    let mut options = GenerateOptions::new(Arc::new(model));
     options.foreign_key_mode = foreign_key_mode;
     let settings = SqlGeneratorSettings::new(database_type, &options);
-    let writer = SqlWriter::new(options.writer.clone());
+    let writer = SqlWriter::new(Rc::new(RefCell::new(PrintWriter::new_auto_flush(Box::new(buffer.clone())))));
+    (GeneratorContext::new(settings, writer), buffer)
+}
+
+pub fn make_idempotent_context(model: DatabaseModel, database_type: DatabaseType) -> (GeneratorContext, SharedBuffer) {
+    let buffer = SharedBuffer::new();
+    let mut options = GenerateOptions::new(Arc::new(model));
+    options.idempotent = true;
+    let settings = SqlGeneratorSettings::new(database_type, &options);
+    let writer = SqlWriter::new(Rc::new(RefCell::new(PrintWriter::new_auto_flush(Box::new(buffer.clone())))));
+    (GeneratorContext::new(settings, writer), buffer)
+}
+
+pub fn make_context_with_naming(
+    model: DatabaseModel,
+    database_type: DatabaseType,
+    naming_convention: NamingConvention,
+) -> (GeneratorContext, SharedBuffer) {
+    let buffer = SharedBuffer::new();
+    let mut options = GenerateOptions::new(Arc::new(model));
+    options.naming_convention = naming_convention;
+    let settings = SqlGeneratorSettings::new(database_type, &options);
+    let writer = SqlWriter::new(Rc::new(RefCell::new(PrintWriter::new_auto_flush(Box::new(buffer.clone())))));
     (GeneratorContext::new(settings, writer), buffer)
 }
@@ -1,7 +1,32 @@
+use crate::common::generated_statement::GeneratedStatement;
+use crate::common::generation_error::GenerationError;
 use crate::common::generator_context::GeneratorContext;
+use schema_model::model::table::Table;
 
 pub trait TriggerGenerator {
-    fn output_triggers(&self);
+    /// Writes the triggers of every table in the model.
+    fn output_triggers(&self) -> Result<(), GenerationError>;
+
+    /// Writes the triggers `table` needs, dropping any earlier version of each first.
+    fn output_table_triggers(&self, table: &Table) -> Result<(), GenerationError>;
+
+    /// The unqualified names of the triggers `output_table_triggers` creates on `table`.
+    fn trigger_names(&self, table: &Table) -> Vec<String>;
+
+    /// The unqualified names of every trigger this generator can create on a table named
+    /// `table_name`, whether or not the table needs them.
+    fn possible_trigger_names(&self, table_name: &str) -> Vec<String>;
+}
+
+/// What a migration runs to bring the triggers of one table up to date with the model.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableTriggers {
+    /// The statements creating the triggers the table needs, each replacing any earlier
+    /// version, as the create script writes them.
+    pub statements: Vec<GeneratedStatement>,
+    /// The unqualified names of the other triggers the create script can give the table,
+    /// which must be dropped if the table has them.
+    pub unneeded_names: Vec<String>,
 }
 
 pub struct DefaultTriggerGenerator {
@@ -21,6 +46,19 @@ impl DefaultTriggerGenerator {
 }
 
 impl TriggerGenerator for DefaultTriggerGenerator {
-    fn output_triggers(&self) {
+    fn output_triggers(&self) -> Result<(), GenerationError> {
+        Ok(())
+    }
+
+    fn output_table_triggers(&self, _table: &Table) -> Result<(), GenerationError> {
+        Ok(())
+    }
+
+    fn trigger_names(&self, _table: &Table) -> Vec<String> {
+        Vec::new()
     }
-}
\ No newline at end of file
+
+    fn possible_trigger_names(&self, _table_name: &str) -> Vec<String> {
+        Vec::new()
+    }
+}
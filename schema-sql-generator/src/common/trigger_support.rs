@@ -0,0 +1,121 @@
+//! Dialect-independent pieces of the generated triggers. A table gets a delete trigger
+//! and an update trigger (split into insert and update on dialects whose triggers fire for
+//! a single event) when it has custom trigger code for the dialect, keeps its relations by
+//! triggers under `ForeignKeyMode::Triggers`, or maintains aggregations.
+
+use crate::common::sql_generator_settings::SqlGeneratorSettings;
+use schema_model::model::relation::Relation;
+use schema_model::model::table::Table;
+use schema_model::model::types::{ForeignKeyMode, TriggerType};
+
+/// Whether `table` has custom trigger code of `trigger_type` for the dialect being generated.
+pub fn has_custom_trigger(settings: &SqlGeneratorSettings, table: &Table, trigger_type: TriggerType) -> bool {
+    table
+        .triggers()
+        .iter()
+        .any(|t| t.trigger_type() == trigger_type && t.database_type() == settings.database_type())
+}
+
+/// Whether `relations` are kept by triggers rather than foreign keys.
+pub fn has_relations_kept_by_triggers(settings: &SqlGeneratorSettings, relations: &[Relation]) -> bool {
+    !relations.is_empty() && settings.foreign_key_mode() == ForeignKeyMode::Triggers
+}
+
+/// Whether `table` needs a delete trigger: for its custom delete code, to cascade to or
+/// guard the rows referencing it, or to shrink the summary rows it aggregates into.
+pub fn should_output_delete_trigger(settings: &SqlGeneratorSettings, table: &Table) -> bool {
+    has_custom_trigger(settings, table, TriggerType::Delete)
+        || has_relations_kept_by_triggers(settings, table.reverse_relations())
+        || !table.aggregations().is_empty()
+}
+
+/// Whether `table` needs an update trigger: for its custom update code, to check the rows
+/// it references exist, or to keep the summary rows it aggregates into up to date.
+pub fn should_output_update_trigger(settings: &SqlGeneratorSettings, table: &Table) -> bool {
+    has_custom_trigger(settings, table, TriggerType::Update)
+        || has_relations_kept_by_triggers(settings, table.relations())
+        || !table.aggregations().is_empty()
+}
+
+/// Whether the delete trigger `should_output_delete_trigger` asks for is written: the
+/// relation actions need the table's key to find its rows, while summary rows must shrink
+/// on delete whether or not the source table has a key.
+pub fn outputs_delete_trigger(settings: &SqlGeneratorSettings, table: &Table) -> bool {
+    let has_delete_target = table.primary_key().is_some() || !table.aggregations().is_empty();
+    should_output_delete_trigger(settings, table) && has_delete_target
+}
+
+/// `child_col = ROW.parent_col [and ...]` for every column pair of the relation, matching
+/// the child rows that reference the parent row held in the trigger's `ROW` record.
+pub fn child_match_condition(settings: &SqlGeneratorSettings, relation: &Relation, row: &str) -> String {
+    relation
+        .columns()
+        .iter()
+        .map(|c| format!("{} = {}.{}", settings.quote(c.from_column_name()), row, settings.quote(c.to_column_name())))
+        .collect::<Vec<_>>()
+        .join(" and ")
+}
+
+/// `parent_col = ROW.child_col [and ...]` for every column pair of the relation, matching
+/// the parent row referenced by the child row held in the trigger's `ROW` record.
+pub fn parent_match_condition(settings: &SqlGeneratorSettings, relation: &Relation, row: &str) -> String {
+    relation
+        .columns()
+        .iter()
+        .map(|c| format!("{} = {}.{}", settings.quote(c.to_column_name()), row, settings.quote(c.from_column_name())))
+        .collect::<Vec<_>>()
+        .join(" and ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::generate_options::GenerateOptions;
+    use schema_model::builder::{KeyBuilder, TableBuilder};
+    use schema_model::model::database_model::DatabaseModel;
+    use schema_model::model::relation::RelationColumn;
+    use schema_model::model::trigger::Trigger;
+    use schema_model::model::types::{BooleanMode, DatabaseType, KeyType, RelationType};
+    use std::sync::Arc;
+
+    fn make_settings(database_type: DatabaseType, foreign_key_mode: ForeignKeyMode) -> SqlGeneratorSettings {
+        let model = DatabaseModel::new(BooleanMode::Native, foreign_key_mode, vec![]);
+        SqlGeneratorSettings::new(database_type, &GenerateOptions { foreign_key_mode, ..GenerateOptions::new(Arc::new(model)) })
+    }
+
+    #[test]
+    fn only_custom_code_for_the_dialect_asks_for_a_trigger() {
+        let table = TableBuilder::new(None::<&str>, "orders")
+            .add_key(KeyBuilder::new(KeyType::Primary).add_column("id").build())
+            .add_trigger(Trigger::new("delete from audit;", TriggerType::Delete, DatabaseType::Sqlite))
+            .build();
+
+        assert!(outputs_delete_trigger(&make_settings(DatabaseType::Sqlite, ForeignKeyMode::Relations), &table));
+        assert!(!outputs_delete_trigger(&make_settings(DatabaseType::Postgresql, ForeignKeyMode::Relations), &table));
+        assert!(!should_output_update_trigger(&make_settings(DatabaseType::Sqlite, ForeignKeyMode::Relations), &table));
+    }
+
+    #[test]
+    fn relations_ask_for_triggers_only_when_kept_by_triggers() {
+        let relation = Relation::new("customer", "id", "invoice", "customer_id", RelationType::Cascade, false);
+        let table = TableBuilder::new(None::<&str>, "invoice").add_relation(relation).build();
+
+        assert!(should_output_update_trigger(&make_settings(DatabaseType::MySql, ForeignKeyMode::Triggers), &table));
+        assert!(!should_output_update_trigger(&make_settings(DatabaseType::MySql, ForeignKeyMode::Relations), &table));
+    }
+
+    #[test]
+    fn match_conditions_pair_every_column_of_the_relation() {
+        let relation = Relation::new_composite(
+            "customer",
+            "invoice",
+            vec![RelationColumn::new("tenant_id", "tenant_id"), RelationColumn::new("customer_id", "id")],
+            RelationType::Cascade,
+            false,
+        );
+        let settings = make_settings(DatabaseType::Postgresql, ForeignKeyMode::Triggers);
+
+        assert_eq!(child_match_condition(&settings, &relation, "OLD"), "tenant_id = OLD.tenant_id and customer_id = OLD.id");
+        assert_eq!(parent_match_condition(&settings, &relation, "new"), "tenant_id = new.tenant_id and id = new.customer_id");
+    }
+}
@@ -1,7 +1,8 @@
+use crate::common::generation_error::GenerationError;
 use crate::common::generator_context::GeneratorContext;
 
 pub trait ViewGenerator {
-    fn output_views(&self);
+    fn output_views(&self) -> Result<(), GenerationError>;
 }
 
 pub struct DefaultViewGenerator {
@@ -21,6 +22,7 @@ impl DefaultViewGenerator {
 }
 
 impl ViewGenerator for DefaultViewGenerator {
-    fn output_views(&self) {
+    fn output_views(&self) -> Result<(), GenerationError> {
+        Ok(())
     }
 }
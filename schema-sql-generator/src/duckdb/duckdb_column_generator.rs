@@ -0,0 +1,40 @@
+use crate::common::column_generator::{ColumnGenerator, DefaultColumnGenerator, DefaultConstraintNaming};
+use crate::common::generation_error::GenerationError;
+use crate::common::generator_context::GeneratorContext;
+use crate::duckdb::duckdb_column_type_generator::DuckDbColumnTypeGenerator;
+use schema_model::model::column::Column;
+use schema_model::model::table::Table;
+
+pub struct DuckDbColumnGenerator {
+    column_generator: DefaultColumnGenerator,
+}
+
+impl DuckDbColumnGenerator {
+    pub fn new(context: GeneratorContext) -> Self {
+        Self {
+            column_generator: DefaultColumnGenerator::new(
+                context.clone(),
+                Box::new(DuckDbColumnTypeGenerator::new(context.clone())),
+                DefaultConstraintNaming::Unnamed,
+            ),
+        }
+    }
+}
+
+impl ColumnGenerator for DuckDbColumnGenerator {
+    fn column_definitions(&self, table: &Table) -> Result<Vec<String>, GenerationError> {
+        self.column_generator.column_definitions(table)
+    }
+
+    fn column_sql(&self, table: &Table, column: &Column) -> Result<String, GenerationError> {
+        self.column_generator.column_sql(table, column)
+    }
+
+    fn column_options(&self, table: &Table, column: &Column) -> Result<String, GenerationError> {
+        self.column_generator.column_options(table, column)
+    }
+
+    fn default_value(&self, table: &Table, column: &Column) -> Result<Option<String>, GenerationError> {
+        self.column_generator.default_value(table, column)
+    }
+}
@@ -0,0 +1,225 @@
+use crate::common::column_type_generator::ColumnTypeGenerator;
+use crate::common::generation_error::GenerationError;
+use crate::common::generator_context::GeneratorContext;
+use crate::common::sql_string::escape_sql_literal;
+use schema_model::model::column::Column;
+use schema_model::model::column_type::ColumnType;
+use schema_model::model::schema::Schema;
+use schema_model::model::sequence::Sequence;
+use schema_model::model::table::Table;
+
+pub struct DuckDbColumnTypeGenerator {
+    context: GeneratorContext
+}
+
+impl DuckDbColumnTypeGenerator {
+    pub fn new(context: GeneratorContext) -> Self {
+        Self {
+            context
+        }
+    }
+
+    /// DuckDB has no serial or identity columns, so an auto-increment column draws its
+    /// values from a sequence of its own, created ahead of the table.
+    fn auto_increment_sql(&self, table: &Table, column: &Column, base_type: &str) -> String {
+        let sequence_name = self.context.settings().qualified_name(table.schema_name(), &column_sequence_name(table, column));
+        format!("{} default nextval('{}')", base_type, escape_sql_literal(&sequence_name))
+    }
+
+    /// The type of a scalar column, or of the elements of an array; `None` for the column
+    /// types that can't be a list element.
+    fn element_type_sql(&self, column: &Column, column_type: ColumnType) -> Option<String> {
+        Some(match column_type {
+            ColumnType::Byte => self.byte_sql(),
+            ColumnType::Short => self.short_sql(),
+            ColumnType::Int => self.int_sql(),
+            ColumnType::Long => self.long_sql(),
+            ColumnType::Float => self.float_sql(),
+            ColumnType::Double => self.double_sql(),
+            ColumnType::Decimal => self.decimal_sql(column),
+            ColumnType::Boolean => self.boolean_sql(),
+            ColumnType::Date => self.date_sql(),
+            ColumnType::DateTime | ColumnType::Timestamp => self.date_time_sql(),
+            ColumnType::Time => self.time_sql(),
+            ColumnType::TimestampTz => self.timestamp_tz_sql(),
+            ColumnType::Char => self.char_sql(column),
+            ColumnType::Varchar => self.varchar_sql(column),
+            ColumnType::Text => self.text_sql(column),
+            ColumnType::CiText => self.citext_sql(),
+            ColumnType::CsText => self.cstext_sql(),
+            ColumnType::Binary => self.binary_sql(),
+            ColumnType::Uuid => self.uuid_sql(column),
+            ColumnType::Json => self.json_sql(column),
+            ColumnType::Sequence | ColumnType::LongSequence | ColumnType::Enum | ColumnType::Array => return None,
+        })
+    }
+}
+
+/// The name of the sequence behind the auto-increment `column` of `table`.
+pub(crate) fn column_sequence_name(table: &Table, column: &Column) -> String {
+    format!("{}_{}_seq", table.name(), column.name())
+}
+
+impl ColumnTypeGenerator for DuckDbColumnTypeGenerator {
+    fn context(&self) -> &GeneratorContext {
+        &self.context
+    }
+
+    fn column_type_sql(&self, table: &Table, column: &Column) -> Result<String, GenerationError> {
+        Ok(match column.column_type() {
+            ColumnType::Sequence => self.auto_increment_sql(table, column, "integer"),
+            ColumnType::LongSequence => self.auto_increment_sql(table, column, "bigint"),
+            ColumnType::Array => self.array_sql(table, column)?,
+            ColumnType::Enum => self.enum_sql(table, column)?,
+            column_type => self.element_type_sql(column, column_type).ok_or_else(|| {
+                GenerationError::unsupported(
+                    format!("column {}.{}", table.name(), column.name()),
+                    format!("{} columns are not supported", column_type.name()),
+                )
+            })?,
+        })
+    }
+
+    fn sequence_sql(&self, _column: &Column) -> String {
+        "integer".to_string()
+    }
+
+    fn long_sequence_sql(&self, _column: &Column) -> String {
+        "bigint".to_string()
+    }
+
+    fn text_sql(&self, _column: &Column) -> String {
+        "varchar".to_string()
+    }
+
+    fn citext_sql(&self) -> String {
+        "varchar collate nocase".to_string()
+    }
+
+    fn cstext_sql(&self) -> String {
+        "varchar".to_string()
+    }
+
+    fn binary_sql(&self) -> String {
+        "blob".to_string()
+    }
+
+    fn uuid_default_value_sql(&self, _schema: &Schema) -> String {
+        "uuid()".to_string()
+    }
+
+    fn sequence_default_value_sql(&self, sequence: &Sequence) -> Option<String> {
+        Some(format!(
+            "nextval('{}')",
+            escape_sql_literal(&self.context.settings().qualified_name(sequence.schema_name(), sequence.name()))
+        ))
+    }
+
+    /// An array is a native `list` of its element type, written `{type}[]`.
+    fn array_sql(&self, table: &Table, column: &Column) -> Result<String, GenerationError> {
+        let object = || format!("column {}.{}", table.name(), column.name());
+        let element_type_name = column
+            .element_type()
+            .ok_or_else(|| GenerationError::unsupported(object(), "an array column needs an elementType"))?;
+        let element_type = ColumnType::from_type_name(element_type_name)
+            .map_err(|e| GenerationError::unsupported(object(), format!("invalid elementType: {}", e)))?;
+
+        self.element_type_sql(column, element_type)
+            .map(|sql| sql + "[]")
+            .ok_or_else(|| GenerationError::unsupported(object(), format!("arrays of {} are not supported", element_type.name())))
+    }
+
+    fn double_sql(&self) -> String {
+        "double".to_string()
+    }
+
+    fn timestamp_tz_sql(&self) -> String {
+        "timestamptz".to_string()
+    }
+
+    /// DuckDB keeps every string as `varchar`; a declared length is only checked, never padded.
+    fn char_sql(&self, column: &Column) -> String {
+        format!("varchar({})", column.length())
+    }
+
+    fn uuid_sql(&self, _column: &Column) -> String {
+        "uuid".to_string()
+    }
+
+    /// A json column declares no fields to build a `struct` from, so its documents are kept
+    /// in the `json` type and read back with the json functions.
+    fn json_sql(&self, _column: &Column) -> String {
+        "json".to_string()
+    }
+
+    fn enum_sql(&self, table: &Table, column: &Column) -> Result<String, GenerationError> {
+        let enum_type_name = column.enum_type().ok_or_else(|| {
+            GenerationError::unsupported(
+                format!("column {}.{}", table.name(), column.name()),
+                "an enum column needs an enum type",
+            )
+        })?;
+
+        Ok(self.context.settings().quote(enum_type_name))
+    }
+
+    fn native_boolean_sql(&self) -> String {
+        "boolean".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::test_support::make_context;
+    use schema_model::builder::{ColumnBuilder, SchemaBuilder, TableBuilder};
+    use schema_model::model::database_model::DatabaseModel;
+    use schema_model::model::types::{BooleanMode, DatabaseType, ForeignKeyMode};
+
+    fn column_type_sql(column: Column) -> Result<String, GenerationError> {
+        let model = DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![SchemaBuilder::new(None::<&str>).build()]);
+        let (ctx, _buffer) = make_context(model, DatabaseType::DuckDb);
+        let table = TableBuilder::new(None::<&str>, "test").build();
+        DuckDbColumnTypeGenerator::new(ctx).column_type_sql(&table, &column)
+    }
+
+    fn assert_type(column_type: ColumnType, expected: &str) {
+        let column = ColumnBuilder::new(None::<&str>, "col", column_type).build();
+        assert_eq!(column_type_sql(column).unwrap(), expected);
+    }
+
+    fn array_of(element_type: &str) -> Result<String, GenerationError> {
+        let column = ColumnBuilder::new(None::<&str>, "col", ColumnType::Array)
+            .element_type(Some(element_type.to_string()))
+            .build();
+        column_type_sql(column)
+    }
+
+    #[test]
+    fn sequence_columns_draw_from_their_own_sequence() {
+        assert_type(ColumnType::Sequence, "integer default nextval('main.test_col_seq')");
+        assert_type(ColumnType::LongSequence, "bigint default nextval('main.test_col_seq')");
+    }
+
+    #[test]
+    fn scalar_types() {
+        assert_type(ColumnType::Byte, "tinyint");
+        assert_type(ColumnType::Double, "double");
+        assert_type(ColumnType::Timestamp, "timestamp");
+        assert_type(ColumnType::TimestampTz, "timestamptz");
+        assert_type(ColumnType::Text, "varchar");
+        assert_type(ColumnType::CiText, "varchar collate nocase");
+        assert_type(ColumnType::Binary, "blob");
+        assert_type(ColumnType::Uuid, "uuid");
+        assert_type(ColumnType::Json, "json");
+        assert_type(ColumnType::Boolean, "boolean");
+    }
+
+    #[test]
+    fn arrays_are_lists_of_any_scalar_type() {
+        assert_eq!(array_of("int").unwrap(), "integer[]");
+        assert_eq!(array_of("timestamp").unwrap(), "timestamp[]");
+        assert_eq!(array_of("json").unwrap(), "json[]");
+        assert!(array_of("sequence").unwrap_err().to_string().contains("arrays of SEQUENCE are not supported"));
+    }
+}
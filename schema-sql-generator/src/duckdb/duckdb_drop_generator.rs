@@ -0,0 +1,123 @@
+use crate::common::drop_generator::{output_drop_steps, DefaultDropGenerator, DropGenerator};
+use crate::common::generated_statement::StatementKind;
+use crate::common::generation_error::GenerationError;
+use crate::common::generator_context::GeneratorContext;
+use crate::duckdb::duckdb_column_type_generator::column_sequence_name;
+use crate::duckdb::duckdb_sequence_generator::auto_increment_columns;
+use crate::sql_println;
+
+pub struct DuckDbDropGenerator {
+    context: GeneratorContext,
+    drop_generator: DefaultDropGenerator,
+}
+
+impl DuckDbDropGenerator {
+    pub fn new(context: GeneratorContext) -> Self {
+        Self {
+            context: context.clone(),
+            drop_generator: DefaultDropGenerator::new(context),
+        }
+    }
+}
+
+impl DropGenerator for DuckDbDropGenerator {
+    fn output_drops(&self) -> Result<(), GenerationError> {
+        output_drop_steps(self, self.context.settings().foreign_key_mode())
+    }
+
+    fn output_drop_procedures(&self) -> Result<(), GenerationError> {
+        self.drop_generator.output_drop_procedures()
+    }
+
+    fn output_drop_views(&self) -> Result<(), GenerationError> {
+        self.drop_generator.output_drop_views()
+    }
+
+    fn output_drop_functions(&self) -> Result<(), GenerationError> {
+        self.drop_generator.output_drop_functions()
+    }
+
+    fn output_drop_triggers(&self) -> Result<(), GenerationError> {
+        Ok(())
+    }
+
+    /// A foreign key can't be dropped from a DuckDB table; it goes with the table, and the
+    /// tables are dropped referencing ones first.
+    fn output_drop_relations(&self) -> Result<(), GenerationError> {
+        Ok(())
+    }
+
+    fn output_drop_tables(&self) -> Result<(), GenerationError> {
+        self.drop_generator.output_drop_tables()
+    }
+
+    fn output_drop_enum_types(&self) -> Result<(), GenerationError> {
+        let settings = self.context.settings();
+        let separator = settings.statement_separator();
+
+        self.context.with_writer(|writer| {
+            for schema in settings.database_model().schemas().iter().rev() {
+                let mut enum_types: Vec<_> = schema.enum_types().collect();
+                enum_types.reverse();
+                for enum_type in enum_types {
+                    writer.begin_object(StatementKind::Type, Some(enum_type.name()));
+                    sql_println!(writer, "drop type if exists {}{}", settings.quote(enum_type.name()), separator)?;
+                }
+            }
+            Ok(())
+        })
+    }
+
+    /// The sequences behind the auto-increment columns, then the model's own.
+    fn output_drop_sequences(&self) -> Result<(), GenerationError> {
+        let settings = self.context.settings();
+        let separator = settings.statement_separator();
+
+        self.context.with_writer(|writer| -> Result<(), GenerationError> {
+            for (table, column) in auto_increment_columns(settings.database_model()).into_iter().rev() {
+                let name = column_sequence_name(table, column);
+                writer.begin_object(StatementKind::Sequence, Some(&settings.database_type().qualified_name(table.schema_name(), &name)));
+                sql_println!(writer, "drop sequence if exists {}{}", settings.qualified_name(table.schema_name(), &name), separator)?;
+            }
+            Ok(())
+        })?;
+        self.drop_generator.output_drop_sequences()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::test_support::make_context;
+    use schema_model::builder::{ColumnBuilder, SchemaBuilder, TableBuilder};
+    use schema_model::model::column_type::ColumnType;
+    use schema_model::model::database_model::DatabaseModel;
+    use schema_model::model::enum_type::{EnumType, EnumValue};
+    use schema_model::model::relation::Relation;
+    use schema_model::model::types::{BooleanMode, DatabaseType, ForeignKeyMode, RelationType};
+
+    #[test]
+    fn output_drops_leaves_foreign_keys_to_their_tables_and_drops_column_sequences() {
+        let customer = TableBuilder::new(None::<&str>, "customer")
+            .add_column(ColumnBuilder::new(None::<&str>, "id", ColumnType::Sequence).required(true).build())
+            .build();
+        let order = TableBuilder::new(None::<&str>, "orders")
+            .add_column(ColumnBuilder::new(None::<&str>, "customer_id", ColumnType::Int).build())
+            .add_relation(Relation::new("customer", "id", "orders", "customer_id", RelationType::Enforce, false))
+            .build();
+        let status = EnumType::new("status_type", vec![EnumValue::new("ACTIVE", Some("A"))]);
+        let schema = SchemaBuilder::new(None::<&str>).add_table(customer).add_table(order).add_enum_type(status).build();
+        let model = DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema]);
+        let (ctx, buffer) = make_context(model, DatabaseType::DuckDb);
+
+        DuckDbDropGenerator::new(ctx).output_drops().unwrap();
+
+        assert_eq!(
+            buffer.contents(),
+            "drop table if exists main.orders;\n\
+             drop table if exists main.customer;\n\
+             drop type if exists status_type;\n\
+             drop sequence if exists main.customer_id_seq;\n"
+        );
+    }
+}
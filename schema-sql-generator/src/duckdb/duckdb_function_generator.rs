@@ -0,0 +1,27 @@
+use crate::common::function_generator::{DefaultFunctionGenerator, FunctionGenerator};
+use crate::common::generation_error::GenerationError;
+use crate::common::generator_context::GeneratorContext;
+use crate::common::sql_writer::SqlWriter;
+use schema_model::model::function::Function;
+
+pub struct DuckDbFunctionGenerator {
+    function_generator: DefaultFunctionGenerator
+}
+
+impl DuckDbFunctionGenerator {
+    pub fn new(context: GeneratorContext) -> Self {
+        Self {
+            function_generator: DefaultFunctionGenerator::new(context),
+        }
+    }
+}
+
+impl FunctionGenerator for DuckDbFunctionGenerator {
+    fn output_functions(&self) -> Result<(), GenerationError> {
+        self.function_generator.output_functions()
+    }
+
+    fn output_function(&self, writer: &mut SqlWriter, statement_separator: &str, function: &Function) -> Result<(), GenerationError> {
+        self.function_generator.output_function(writer, statement_separator, function)
+    }
+}
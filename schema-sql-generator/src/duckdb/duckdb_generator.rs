@@ -0,0 +1,185 @@
+use crate::common::generated_statement::StatementKind;
+use crate::common::generation_error::GenerationError;
+use crate::common::generator_context::GeneratorContext;
+use crate::common::sql_generator::{DefaultSqlGenerator, SqlGenerator};
+use crate::common::sql_string::escape_sql_literal;
+use crate::duckdb::duckdb_drop_generator::DuckDbDropGenerator;
+use crate::duckdb::duckdb_function_generator::DuckDbFunctionGenerator;
+use crate::duckdb::duckdb_index_generator::DuckDbIndexGenerator;
+use crate::duckdb::duckdb_other_sql_generator::DuckDbOtherSqlGenerator;
+use crate::duckdb::duckdb_procedure_generator::DuckDbProcedureGenerator;
+use crate::duckdb::duckdb_relation_generator::DuckDbRelationGenerator;
+use crate::duckdb::duckdb_sequence_generator::DuckDbSequenceGenerator;
+use crate::duckdb::duckdb_table_generator::DuckDbTableGenerator;
+use crate::duckdb::duckdb_trigger_generator::DuckDbTriggerGenerator;
+use crate::duckdb::duckdb_view_generator::DuckDbViewGenerator;
+use crate::sql_println;
+
+pub struct DuckDbGenerator {
+    context: GeneratorContext,
+    sql_generator: DefaultSqlGenerator,
+}
+
+impl DuckDbGenerator {
+    pub fn new(context: GeneratorContext) -> Self {
+        let sql_generator = DefaultSqlGenerator::new(
+            context.clone(),
+            Box::new(DuckDbTableGenerator::new(context.clone())),
+            Box::new(DuckDbRelationGenerator::new(context.clone())),
+            Box::new(DuckDbIndexGenerator::new(context.clone())),
+            Box::new(DuckDbFunctionGenerator::new(context.clone())),
+            Box::new(DuckDbViewGenerator::new(context.clone())),
+            Box::new(DuckDbProcedureGenerator::new(context.clone())),
+            Box::new(DuckDbTriggerGenerator::new(context.clone())),
+            Box::new(DuckDbOtherSqlGenerator::new(context.clone())),
+            Box::new(DuckDbSequenceGenerator::new(context.clone())),
+            Box::new(DuckDbDropGenerator::new(context.clone())),
+        );
+
+        Self {
+            context,
+            sql_generator,
+        }
+    }
+
+    /// A new database file only has the `main` schema, so the others are created first.
+    fn create_schemas(&self) -> Result<(), GenerationError> {
+        let separator = self.context.settings().statement_separator();
+        let database_model = self.context.settings().database_model();
+
+        self.context.with_writer(|writer| {
+            for schema_name in database_model.schemas().iter().filter_map(|schema| schema.schema_name()) {
+                // `public` is written as `main`.
+                if !schema_name.eq_ignore_ascii_case("main") && !schema_name.eq_ignore_ascii_case("public") {
+                    writer.begin_object(StatementKind::Schema, Some(schema_name));
+                    sql_println!(writer, "create schema if not exists {}{}", self.context.settings().quote(schema_name), separator)?;
+                    sql_println!(writer, "")?;
+                }
+            }
+            Ok(())
+        })
+    }
+
+    fn create_enum_types(&self) -> Result<(), GenerationError> {
+        let settings = self.context.settings();
+        let separator = settings.statement_separator();
+
+        self.context.with_writer(|writer| {
+            for enum_type in settings.database_model().schemas().iter().flat_map(|schema| schema.enum_types()) {
+                let enum_type_name = settings.quote(enum_type.name());
+                let values = enum_type
+                    .values()
+                    .iter()
+                    .map(|v| format!("'{}'", escape_sql_literal(v.code())))
+                    .collect::<Vec<_>>()
+                    .join(",");
+
+                writer.begin_object(StatementKind::Type, Some(enum_type.name()));
+                if settings.idempotent() {
+                    sql_println!(writer, "create type if not exists {} as enum ({}){}", enum_type_name, values, separator)?;
+                } else {
+                    sql_println!(writer, "drop type if exists {}{}", enum_type_name, separator)?;
+                    sql_println!(writer, "create type {} as enum ({}){}", enum_type_name, values, separator)?;
+                }
+                sql_println!(writer, "")?;
+            }
+            Ok(())
+        })
+    }
+}
+
+impl SqlGenerator for DuckDbGenerator {
+    fn context(&self) -> &GeneratorContext {
+        &self.context
+    }
+
+    fn output_header(&self) -> Result<(), GenerationError> {
+        self.create_schemas()?;
+        self.create_enum_types()
+    }
+
+    fn output_sequences(&self) -> Result<(), GenerationError> {
+        self.sql_generator.output_sequences()
+    }
+
+    fn output_tables(&self) -> Result<(), GenerationError> {
+        self.sql_generator.output_tables()
+    }
+
+    fn output_relations(&self) -> Result<(), GenerationError> {
+        self.sql_generator.output_relations()
+    }
+
+    fn output_indexes(&self) -> Result<(), GenerationError> {
+        self.sql_generator.output_indexes()
+    }
+
+    fn output_triggers(&self) -> Result<(), GenerationError> {
+        self.sql_generator.output_triggers()
+    }
+
+    fn output_functions(&self) -> Result<(), GenerationError> {
+        self.sql_generator.output_functions()
+    }
+
+    fn output_views(&self) -> Result<(), GenerationError> {
+        self.sql_generator.output_views()
+    }
+
+    fn output_procedures(&self) -> Result<(), GenerationError> {
+        self.sql_generator.output_procedures()
+    }
+
+    fn output_other_sql_top(&self) -> Result<(), GenerationError> {
+        self.sql_generator.output_other_sql_top()
+    }
+
+    fn output_other_sql_bottom(&self) -> Result<(), GenerationError> {
+        self.sql_generator.output_other_sql_bottom()
+    }
+
+    fn output_drops(&self) -> Result<(), GenerationError> {
+        self.sql_generator.output_drops()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::test_support::{make_context, make_idempotent_context};
+    use schema_model::builder::SchemaBuilder;
+    use schema_model::model::database_model::DatabaseModel;
+    use schema_model::model::enum_type::{EnumType, EnumValue};
+    use schema_model::model::types::{BooleanMode, DatabaseType, ForeignKeyMode};
+
+    fn make_model() -> DatabaseModel {
+        let enum_type = EnumType::new("status_type", vec![EnumValue::new("ACTIVE", Some("A")), EnumValue::new("HELD", Some("O'H"))]);
+        let main = SchemaBuilder::new(Some("public")).add_enum_type(enum_type).build();
+        let sales = SchemaBuilder::new(Some("sales")).build();
+        DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![main, sales])
+    }
+
+    #[test]
+    fn output_header_creates_schemas_and_enum_types() {
+        let (ctx, buffer) = make_context(make_model(), DatabaseType::DuckDb);
+
+        DuckDbGenerator::new(ctx).output_header().unwrap();
+
+        assert_eq!(
+            buffer.contents(),
+            "create schema if not exists sales;\n\n\
+             drop type if exists status_type;\n\
+             create type status_type as enum ('A','O''H');\n\n"
+        );
+    }
+
+    #[test]
+    fn output_header_keeps_existing_enum_types_when_idempotent() {
+        let (ctx, buffer) = make_idempotent_context(make_model(), DatabaseType::DuckDb);
+
+        DuckDbGenerator::new(ctx).output_header().unwrap();
+
+        assert!(buffer.contents().contains("create type if not exists status_type as enum ('A','O''H');"));
+        assert!(!buffer.contents().contains("drop type"));
+    }
+}
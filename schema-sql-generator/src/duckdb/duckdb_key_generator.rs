@@ -0,0 +1,22 @@
+use crate::common::generation_error::GenerationError;
+use crate::common::generator_context::GeneratorContext;
+use crate::common::key_generator::{DefaultKeyGenerator, KeyGenerator};
+use schema_model::model::table::Table;
+
+pub struct DuckDbKeyGenerator {
+    key_generator: DefaultKeyGenerator,
+}
+
+impl DuckDbKeyGenerator {
+    pub fn new(context: GeneratorContext) -> Self {
+        Self {
+            key_generator: DefaultKeyGenerator::new(context),
+        }
+    }
+}
+
+impl KeyGenerator for DuckDbKeyGenerator {
+    fn key_constraints(&self, table: &Table) -> Result<Vec<String>, GenerationError> {
+        self.key_generator.key_constraints(table)
+    }
+}
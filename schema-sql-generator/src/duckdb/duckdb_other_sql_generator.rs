@@ -0,0 +1,30 @@
+use crate::common::generation_error::GenerationError;
+use crate::common::generator_context::GeneratorContext;
+use crate::common::other_sql_generator::{DefaultOtherSqlGenerator, OtherSqlGenerator};
+use crate::common::sql_writer::SqlWriter;
+
+pub struct DuckDbOtherSqlGenerator {
+    other_sql_generator: DefaultOtherSqlGenerator
+}
+
+impl DuckDbOtherSqlGenerator {
+    pub fn new(context: GeneratorContext) -> Self {
+        Self {
+            other_sql_generator: DefaultOtherSqlGenerator::new(context),
+        }
+    }
+}
+
+impl OtherSqlGenerator for DuckDbOtherSqlGenerator {
+    fn output_other_sql_top(&self) -> Result<(), GenerationError> {
+        self.other_sql_generator.output_other_sql_top()
+    }
+
+    fn output_other_sql_bottom(&self) -> Result<(), GenerationError> {
+        self.other_sql_generator.output_other_sql_bottom()
+    }
+
+    fn output_other_sql(&self, writer: &mut SqlWriter, statement_separator: &str, sql: &str) -> Result<(), GenerationError> {
+        self.other_sql_generator.output_other_sql(writer, statement_separator, sql)
+    }
+}
@@ -0,0 +1,64 @@
+use crate::common::generation_error::GenerationError;
+use crate::common::generator_context::GeneratorContext;
+use crate::common::procedure_generator::ProcedureGenerator;
+use crate::common::sql_writer::SqlWriter;
+use schema_model::model::procedure::Procedure;
+
+pub struct DuckDbProcedureGenerator {
+    context: GeneratorContext,
+}
+
+impl DuckDbProcedureGenerator {
+    pub fn new(context: GeneratorContext) -> Self {
+        Self { context }
+    }
+}
+
+impl ProcedureGenerator for DuckDbProcedureGenerator {
+    fn output_procedures(&self) -> Result<(), GenerationError> {
+        let database_type = self.context.settings().database_type();
+        let database_model = self.context.settings().database_model();
+
+        match database_model
+            .schemas()
+            .iter()
+            .flat_map(|schema| schema.procedures())
+            .find(|procedure| procedure.database_type() == database_type)
+        {
+            Some(procedure) => Err(unsupported_procedure(procedure)),
+            None => Ok(()),
+        }
+    }
+
+    fn output_procedure(&self, _writer: &mut SqlWriter, _statement_separator: &str, procedure: &Procedure) -> Result<(), GenerationError> {
+        Err(unsupported_procedure(procedure))
+    }
+}
+
+fn unsupported_procedure(procedure: &Procedure) -> GenerationError {
+    GenerationError::unsupported(
+        format!("procedure {}", procedure.name()),
+        "DuckDB does not support stored procedures",
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::test_support::make_context;
+    use schema_model::builder::SchemaBuilder;
+    use schema_model::model::database_model::DatabaseModel;
+    use schema_model::model::types::{BooleanMode, DatabaseType, ForeignKeyMode};
+
+    #[test]
+    fn output_procedures_fails_when_a_duckdb_procedure_exists() {
+        let procedure = Procedure::new(None::<&str>, "proc", DatabaseType::DuckDb, "select 1");
+        let schema = SchemaBuilder::new(None::<&str>).add_procedures(vec![procedure]).build();
+        let model = DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema]);
+        let (ctx, _buffer) = make_context(model, DatabaseType::DuckDb);
+
+        let error = DuckDbProcedureGenerator::new(ctx).output_procedures().unwrap_err();
+
+        assert!(error.to_string().contains("DuckDB does not support stored procedures"));
+    }
+}
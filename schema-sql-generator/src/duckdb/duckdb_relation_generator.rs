@@ -0,0 +1,89 @@
+use crate::common::generation_error::{resolve_table, GenerationError};
+use crate::common::generator_context::GeneratorContext;
+use crate::common::relation_generator::{DefaultRelationGenerator, RelationGenerator};
+use schema_model::model::table::Table;
+use schema_model::model::types::ForeignKeyMode;
+
+pub struct DuckDbRelationGenerator {
+    relation_generator: DefaultRelationGenerator,
+}
+
+impl DuckDbRelationGenerator {
+    pub fn new(context: GeneratorContext) -> Self {
+        Self {
+            relation_generator: DefaultRelationGenerator::new(context),
+        }
+    }
+
+    /// DuckDB can't add a foreign key to an existing table, so, as on SQLite, the
+    /// constraints are declared in the `create table`. It only checks that a referenced row
+    /// exists and has no `on delete` actions, so a cascading or nulling relation also just
+    /// stops the parent row from being deleted while children point at it.
+    pub fn inline_foreign_key_constraints(&self, table: &Table) -> Result<Vec<String>, GenerationError> {
+        let settings = self.relation_generator.context().settings();
+
+        if settings.foreign_key_mode() != ForeignKeyMode::Relations {
+            return Ok(Vec::new());
+        }
+
+        table
+            .relations()
+            .iter()
+            .enumerate()
+            .map(|(relation_index, relation)| {
+                let constraint_name = self.relation_generator.relation_constraint_name(table, relation_index);
+                let to_table = resolve_table(settings.database_model(), relation.to_table_name(), || {
+                    format!("relation {}", constraint_name)
+                })?;
+
+                Ok(format!(
+                    "   constraint {} foreign key ({}) references {}({})",
+                    settings.quote_generated(&constraint_name),
+                    settings.quote_list(&relation.from_column_names()),
+                    settings.qualified_name(to_table.schema_name(), to_table.name()),
+                    settings.quote_list(&relation.to_column_names()),
+                ))
+            })
+            .collect()
+    }
+}
+
+impl RelationGenerator for DuckDbRelationGenerator {
+    fn output_relations(&self) -> Result<(), GenerationError> {
+        // No-op: the foreign keys are part of each `create table` (see
+        // `inline_foreign_key_constraints`).
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::test_support::make_context;
+    use schema_model::builder::{ColumnBuilder, SchemaBuilder, TableBuilder};
+    use schema_model::model::column_type::ColumnType;
+    use schema_model::model::database_model::DatabaseModel;
+    use schema_model::model::relation::Relation;
+    use schema_model::model::types::{BooleanMode, DatabaseType, RelationType};
+
+    #[test]
+    fn foreign_keys_are_inline_and_have_no_delete_action() {
+        let parent = TableBuilder::new(None::<&str>, "parent")
+            .add_column(ColumnBuilder::new(None::<&str>, "id", ColumnType::Int).required(true).build())
+            .build();
+        let child = TableBuilder::new(None::<&str>, "child")
+            .add_column(ColumnBuilder::new(None::<&str>, "parent_id", ColumnType::Int).required(true).build())
+            .add_relation(Relation::new("parent", "id", "child", "parent_id", RelationType::Cascade, false))
+            .build();
+        let schema = SchemaBuilder::new(None::<&str>).add_table(parent).add_table(child.clone()).build();
+        let model = DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema]);
+        let (ctx, buffer) = make_context(model, DatabaseType::DuckDb);
+        let generator = DuckDbRelationGenerator::new(ctx);
+
+        let clauses = generator.inline_foreign_key_constraints(&child).unwrap();
+        generator.output_relations().unwrap();
+
+        assert_eq!(clauses, vec!["   constraint fk_child1 foreign key (parent_id) references main.parent(id)"]);
+        assert_eq!(buffer.contents(), "");
+    }
+}
@@ -0,0 +1,116 @@
+use crate::common::generated_statement::StatementKind;
+use crate::common::generation_error::GenerationError;
+use crate::common::generator_context::GeneratorContext;
+use crate::common::sequence_generator::SequenceGenerator;
+use crate::duckdb::duckdb_column_type_generator::column_sequence_name;
+use crate::sql_println;
+use schema_model::model::column::Column;
+use schema_model::model::column_type::ColumnType;
+use schema_model::model::database_model::DatabaseModel;
+use schema_model::model::table::Table;
+
+pub struct DuckDbSequenceGenerator {
+    context: GeneratorContext,
+}
+
+impl DuckDbSequenceGenerator {
+    pub fn new(context: GeneratorContext) -> Self {
+        Self { context }
+    }
+}
+
+/// Every auto-increment column in `database_model`, with its table.
+pub(crate) fn auto_increment_columns(database_model: &DatabaseModel) -> Vec<(&Table, &Column)> {
+    database_model
+        .all_tables()
+        .into_iter()
+        .flat_map(|table| table.columns().iter().map(move |column| (table, column)))
+        .filter(|(_, column)| matches!(column.column_type(), ColumnType::Sequence | ColumnType::LongSequence))
+        .collect()
+}
+
+impl SequenceGenerator for DuckDbSequenceGenerator {
+    /// The model's sequences, then the one behind each auto-increment column. An idempotent
+    /// script keeps every existing sequence; otherwise each is dropped with `cascade`, taking
+    /// the tables drawing from it, which the script recreates after it.
+    fn output_sequences(&self) -> Result<(), GenerationError> {
+        let settings = self.context.settings();
+        let database_model = settings.database_model();
+        let database_type = settings.database_type();
+        let separator = settings.statement_separator();
+        let idempotent = settings.idempotent();
+        let if_not_exists = if idempotent { "if not exists " } else { "" };
+
+        self.context.with_writer(|writer| {
+            for sequence in database_model.schemas().iter().flat_map(|schema| schema.sequences()) {
+                let sequence_name = settings.qualified_name(sequence.schema_name(), sequence.name());
+                writer.begin_object(StatementKind::Sequence, Some(&sequence.fully_qualified_sequence_name(database_type)));
+                if !idempotent {
+                    sql_println!(writer, "drop sequence if exists {} cascade{}", sequence_name, separator)?;
+                }
+                sql_println!(writer, "create sequence {}{}{}{}", if_not_exists, sequence_name, sequence.options_sql(), separator)?;
+                sql_println!(writer, "")?;
+            }
+            for (table, column) in auto_increment_columns(database_model) {
+                let name = column_sequence_name(table, column);
+                let sequence_name = settings.qualified_name(table.schema_name(), &name);
+                writer.begin_object(StatementKind::Sequence, Some(&database_type.qualified_name(table.schema_name(), &name)));
+                if !idempotent {
+                    sql_println!(writer, "drop sequence if exists {} cascade{}", sequence_name, separator)?;
+                }
+                sql_println!(writer, "create sequence {}{}{}", if_not_exists, sequence_name, separator)?;
+                sql_println!(writer, "")?;
+            }
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::test_support::{make_context, make_idempotent_context};
+    use schema_model::builder::{ColumnBuilder, SchemaBuilder, SequenceBuilder, TableBuilder};
+    use schema_model::model::types::{BooleanMode, DatabaseType, ForeignKeyMode};
+
+    #[test]
+    fn output_sequences_creates_model_sequences_and_one_per_auto_increment_column() {
+        let sequence = SequenceBuilder::new(Some("sales"), "document_no").start(Some(1000)).build();
+        let table = TableBuilder::new(Some("sales"), "invoice")
+            .add_column(ColumnBuilder::new(Some("sales"), "id", ColumnType::Sequence).required(true).build())
+            .add_column(ColumnBuilder::new(Some("sales"), "total", ColumnType::Decimal).build())
+            .build();
+        let schema = SchemaBuilder::new(Some("sales")).add_sequence(sequence).add_table(table).build();
+        let model = DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema]);
+        let (ctx, buffer) = make_context(model, DatabaseType::DuckDb);
+
+        DuckDbSequenceGenerator::new(ctx).output_sequences().unwrap();
+
+        assert_eq!(
+            buffer.contents(),
+            "drop sequence if exists sales.document_no cascade;\n\
+             create sequence sales.document_no start with 1000;\n\n\
+             drop sequence if exists sales.invoice_id_seq cascade;\n\
+             create sequence sales.invoice_id_seq;\n\n"
+        );
+    }
+
+    #[test]
+    fn output_sequences_keeps_every_existing_sequence_when_idempotent() {
+        let sequence = SequenceBuilder::new(Some("sales"), "document_no").start(Some(1000)).build();
+        let table = TableBuilder::new(Some("sales"), "invoice")
+            .add_column(ColumnBuilder::new(Some("sales"), "id", ColumnType::Sequence).required(true).build())
+            .build();
+        let schema = SchemaBuilder::new(Some("sales")).add_sequence(sequence).add_table(table).build();
+        let model = DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema]);
+        let (ctx, buffer) = make_idempotent_context(model, DatabaseType::DuckDb);
+
+        DuckDbSequenceGenerator::new(ctx).output_sequences().unwrap();
+
+        assert_eq!(
+            buffer.contents(),
+            "create sequence if not exists sales.document_no start with 1000;\n\n\
+             create sequence if not exists sales.invoice_id_seq;\n\n"
+        );
+    }
+}
@@ -0,0 +1,112 @@
+use crate::common::drop_generator::tables_in_drop_order;
+use crate::common::generation_error::GenerationError;
+use crate::common::generator_context::GeneratorContext;
+use crate::common::table_generator::{output_table_steps, DefaultTableGenerator, TableGenerator};
+use crate::duckdb::duckdb_column_constraint_generator::DuckDbColumnConstraintGenerator;
+use crate::duckdb::duckdb_column_generator::DuckDbColumnGenerator;
+use crate::duckdb::duckdb_index_generator::DuckDbIndexGenerator;
+use crate::duckdb::duckdb_key_generator::DuckDbKeyGenerator;
+use crate::duckdb::duckdb_relation_generator::DuckDbRelationGenerator;
+use crate::duckdb::duckdb_table_constraint_generator::DuckDbTableConstraintGenerator;
+use schema_model::model::table::Table;
+
+pub struct DuckDbTableGenerator {
+    context: GeneratorContext,
+    table_generator: DefaultTableGenerator,
+    relation_generator: DuckDbRelationGenerator,
+}
+
+impl DuckDbTableGenerator {
+    pub fn new(context: GeneratorContext) -> Self {
+        Self {
+            context: context.clone(),
+            table_generator: DefaultTableGenerator::new(
+                context.clone(),
+                Box::new(DuckDbColumnGenerator::new(context.clone())),
+                Box::new(DuckDbKeyGenerator::new(context.clone())),
+                Box::new(DuckDbColumnConstraintGenerator::new(context.clone())),
+                Box::new(DuckDbTableConstraintGenerator::new(context.clone())),
+                Box::new(DuckDbIndexGenerator::new(context.clone())),
+            ),
+            relation_generator: DuckDbRelationGenerator::new(context),
+        }
+    }
+}
+
+impl TableGenerator for DuckDbTableGenerator {
+    /// A foreign key can only name a table that already exists, so each table is created
+    /// after the tables it references.
+    fn output_tables(&self) -> Result<(), GenerationError> {
+        for table in tables_in_drop_order(self.context.settings().database_model()).into_iter().rev() {
+            self.output_table(table)?;
+        }
+        Ok(())
+    }
+
+    fn output_table(&self, table: &Table) -> Result<(), GenerationError> {
+        output_table_steps(self, table)
+    }
+
+    fn output_table_header(&self, table: &Table) -> Result<(), GenerationError> {
+        self.table_generator.output_table_header(table)
+    }
+
+    fn output_table_definition(&self, table: &Table) -> Result<(), GenerationError> {
+        let inline_foreign_keys = self.relation_generator.inline_foreign_key_constraints(table)?;
+        self.table_generator.output_table_definition_with_extra(table, inline_foreign_keys)
+    }
+
+    fn output_table_footer(&self, table: &Table) -> Result<(), GenerationError> {
+        self.table_generator.output_table_footer(table)
+    }
+
+    fn output_table_descriptions(&self, table: &Table) -> Result<(), GenerationError> {
+        self.table_generator.output_table_descriptions(table)
+    }
+
+    fn output_indexes(&self, table: &Table) -> Result<(), GenerationError> {
+        self.table_generator.output_indexes(table)
+    }
+
+    fn output_initial_data(&self, table: &Table) -> Result<(), GenerationError> {
+        self.table_generator.output_initial_data(table)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::test_support::make_context;
+    use schema_model::builder::{ColumnBuilder, SchemaBuilder, TableBuilder};
+    use schema_model::model::column_type::ColumnType;
+    use schema_model::model::database_model::DatabaseModel;
+    use schema_model::model::key::{Key, KeyColumn};
+    use schema_model::model::relation::Relation;
+    use schema_model::model::types::{BooleanMode, DatabaseType, ForeignKeyMode, KeyType, RelationType};
+
+    #[test]
+    fn referenced_tables_are_created_first_with_the_foreign_keys_inline() {
+        let order = TableBuilder::new(None::<&str>, "order")
+            .add_column(ColumnBuilder::new(None::<&str>, "id", ColumnType::LongSequence).required(true).build())
+            .add_column(ColumnBuilder::new(None::<&str>, "customer_id", ColumnType::Int).required(true).build())
+            .add_key(Key::new(KeyType::Primary, vec![KeyColumn::new("id")]))
+            .add_relation(Relation::new("customer", "id", "order", "customer_id", RelationType::Enforce, false))
+            .build();
+        let customer = TableBuilder::new(None::<&str>, "customer")
+            .add_column(ColumnBuilder::new(None::<&str>, "id", ColumnType::Int).required(true).build())
+            .add_key(Key::new(KeyType::Primary, vec![KeyColumn::new("id")]))
+            .build();
+        let schema = SchemaBuilder::new(None::<&str>).add_table(order).add_table(customer).build();
+        let model = DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema]);
+        let (ctx, buffer) = make_context(model, DatabaseType::DuckDb);
+
+        DuckDbTableGenerator::new(ctx).output_tables().unwrap();
+
+        let output = buffer.contents();
+        let customer_at = output.find("create table main.customer").unwrap();
+        let order_at = output.find("create table main.\"order\"").unwrap();
+        assert!(customer_at < order_at);
+        assert!(output.contains("   id bigint default nextval('main.order_id_seq') not null,"));
+        assert!(output.contains("   constraint fk_order1 foreign key (customer_id) references main.customer(id)\n);"));
+    }
+}
@@ -0,0 +1,91 @@
+use crate::common::generation_error::GenerationError;
+use crate::common::generator_context::GeneratorContext;
+use crate::common::trigger_generator::{DefaultTriggerGenerator, TriggerGenerator};
+use schema_model::model::table::Table;
+use schema_model::model::types::ForeignKeyMode;
+
+pub struct DuckDbTriggerGenerator {
+    trigger_generator: DefaultTriggerGenerator,
+}
+
+impl DuckDbTriggerGenerator {
+    pub fn new(context: GeneratorContext) -> Self {
+        Self {
+            trigger_generator: DefaultTriggerGenerator::new(context),
+        }
+    }
+}
+
+impl TriggerGenerator for DuckDbTriggerGenerator {
+    fn output_triggers(&self) -> Result<(), GenerationError> {
+        for table in self.trigger_generator.context().settings().database_model().all_tables() {
+            self.output_table_triggers(table)?;
+        }
+
+        Ok(())
+    }
+
+    /// DuckDB has no triggers, so a table that needs one - to enforce its relations or
+    /// to keep an aggregation up to date - can't be generated for it.
+    fn output_table_triggers(&self, table: &Table) -> Result<(), GenerationError> {
+        let settings = self.trigger_generator.context().settings();
+        let reason = if settings.foreign_key_mode() == ForeignKeyMode::Triggers && !table.relations().is_empty() {
+            "DuckDB has no triggers to enforce relations with; use the relations foreign key mode"
+        } else if !table.aggregations().is_empty() {
+            "DuckDB has no triggers to maintain aggregations with"
+        } else {
+            return Ok(());
+        };
+        Err(GenerationError::unsupported(format!("table {}", table.name()), reason))
+    }
+
+    fn trigger_names(&self, table: &Table) -> Vec<String> {
+        self.trigger_generator.trigger_names(table)
+    }
+
+    fn possible_trigger_names(&self, table_name: &str) -> Vec<String> {
+        self.trigger_generator.possible_trigger_names(table_name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::test_support::{make_context, make_context_with_fk_mode};
+    use schema_model::builder::{ColumnBuilder, SchemaBuilder, TableBuilder};
+    use schema_model::model::column_type::ColumnType;
+    use schema_model::model::database_model::DatabaseModel;
+    use schema_model::model::relation::Relation;
+    use schema_model::model::types::{BooleanMode, DatabaseType, RelationType};
+
+    fn make_model() -> DatabaseModel {
+        let parent = TableBuilder::new(None::<&str>, "parent")
+            .add_column(ColumnBuilder::new(None::<&str>, "id", ColumnType::Int).required(true).build())
+            .build();
+        let child = TableBuilder::new(None::<&str>, "child")
+            .add_column(ColumnBuilder::new(None::<&str>, "parent_id", ColumnType::Int).build())
+            .add_relation(Relation::new("parent", "id", "child", "parent_id", RelationType::Enforce, false))
+            .build();
+        let schema = SchemaBuilder::new(None::<&str>).add_table(parent).add_table(child).build();
+        DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema])
+    }
+
+    #[test]
+    fn output_triggers_writes_nothing_when_no_trigger_is_needed() {
+        let (ctx, buffer) = make_context(make_model(), DatabaseType::DuckDb);
+
+        DuckDbTriggerGenerator::new(ctx).output_triggers().unwrap();
+
+        assert_eq!(buffer.contents(), "");
+    }
+
+    #[test]
+    fn output_triggers_rejects_relations_enforced_by_triggers() {
+        let (ctx, _buffer) = make_context_with_fk_mode(make_model(), DatabaseType::DuckDb, ForeignKeyMode::Triggers);
+
+        let error = DuckDbTriggerGenerator::new(ctx).output_triggers().unwrap_err();
+
+        assert_eq!(error.object(), Some("table child"));
+        assert!(error.to_string().contains("DuckDB has no triggers to enforce relations with"));
+    }
+}
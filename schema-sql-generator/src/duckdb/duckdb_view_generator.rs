@@ -0,0 +1,113 @@
+use crate::common::generated_statement::StatementKind;
+use crate::common::generation_error::GenerationError;
+use crate::common::generator_context::GeneratorContext;
+use crate::common::index_generator::{index_columns_sql, index_name};
+use crate::common::sql_writer::SqlWriter;
+use crate::common::view_generator::ViewGenerator;
+use crate::sql_println;
+use schema_model::model::view::View;
+
+pub struct DuckDbViewGenerator {
+    context: GeneratorContext,
+}
+
+impl DuckDbViewGenerator {
+    pub fn new(context: GeneratorContext) -> Self {
+        Self { context }
+    }
+
+    /// DuckDB has no materialized views, but it can build a table from a query in one
+    /// statement, and running that statement again is the refresh.
+    fn output_materialized_view(&self, writer: &mut SqlWriter, view: &View, view_name: &str, separator: &str) -> Result<(), GenerationError> {
+        let settings = self.context.settings();
+        sql_println!(writer, "/* {}: materialized view emulated by a table */", view_name)?;
+        sql_println!(writer, "create or replace table {} as", view_name)?;
+        sql_println!(writer, "   {}{}", view.sql(), separator)?;
+        for (position, key) in view.indexes().iter().enumerate() {
+            if let Some(filter) = key.filter() {
+                return Err(GenerationError::unsupported(
+                    format!("index on {}", view.name()),
+                    format!("DuckDB has no partial indexes, so the filter '{}' can't be applied", filter),
+                ));
+            }
+            sql_println!(
+                writer,
+                "create {}index {} on {} ({}){}",
+                if key.is_unique() { "unique " } else { "" },
+                settings.quote_generated(&index_name(settings, view.name(), key, position + 1)),
+                view_name,
+                index_columns_sql(settings, view.name(), key)?,
+                separator
+            )?;
+        }
+        sql_println!(writer, "")?;
+        Ok(())
+    }
+}
+
+impl ViewGenerator for DuckDbViewGenerator {
+    fn output_views(&self) -> Result<(), GenerationError> {
+        let database_model = self.context.settings().database_model();
+        let database_type = self.context.settings().database_type();
+        let separator = self.context.settings().statement_separator();
+
+        let views: Vec<View> = database_model
+            .schemas()
+            .iter()
+            .flat_map(|schema| schema.views(database_type))
+            .collect();
+
+        self.context.with_writer(|writer| -> Result<(), GenerationError> {
+            for view in views {
+                let view_name = self.context.settings().qualified_name(view.schema_name(), view.name());
+                writer.begin_object(StatementKind::View, Some(&view.fully_qualified_view_name(database_type)));
+                if view.is_materialized() {
+                    self.output_materialized_view(writer, &view, &view_name, separator)?;
+                    continue;
+                }
+                sql_println!(writer, "/* {} */", view_name)?;
+                sql_println!(writer, "create or replace view {} as", view_name)?;
+                sql_println!(writer, "   {}{}", view.sql(), separator)?;
+                sql_println!(writer, "")?;
+            }
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::test_support::make_context;
+    use schema_model::builder::SchemaBuilder;
+    use schema_model::model::database_model::DatabaseModel;
+    use schema_model::model::key::{Key, KeyColumn};
+    use schema_model::model::types::{BooleanMode, DatabaseType, ForeignKeyMode, KeyType, RefreshPolicy};
+
+    #[test]
+    fn output_views_replaces_plain_views_and_builds_tables_for_materialized_ones() {
+        let unique = Key::new_full(KeyType::Index, vec![KeyColumn::new("customer_id")], false, false, true, None::<String>);
+        let schema = SchemaBuilder::new(None::<&str>)
+            .add_view(View::new(None::<&str>, "active_users", "select * from users where active", None))
+            .add_view(View::new_materialized(
+                None,
+                "order_totals",
+                "select customer_id, sum(amount) as total from orders group by customer_id",
+                None,
+                RefreshPolicy::Manual,
+                vec![unique],
+            ))
+            .build();
+        let model = DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema]);
+        let (ctx, buffer) = make_context(model, DatabaseType::DuckDb);
+
+        DuckDbViewGenerator::new(ctx).output_views().unwrap();
+
+        let output = buffer.contents();
+        assert!(output.contains("create or replace view main.active_users as\n   select * from users where active;"));
+        assert!(output.contains(
+            "create or replace table main.order_totals as\n   select customer_id, sum(amount) as total from orders group by customer_id;\n\
+             create unique index ix_order_totals1 on main.order_totals (customer_id);"
+        ));
+    }
+}
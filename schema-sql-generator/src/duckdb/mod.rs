@@ -0,0 +1,16 @@
+pub mod duckdb_generator;
+mod duckdb_column_constraint_generator;
+mod duckdb_column_generator;
+mod duckdb_column_type_generator;
+mod duckdb_drop_generator;
+mod duckdb_function_generator;
+mod duckdb_index_generator;
+mod duckdb_key_generator;
+mod duckdb_other_sql_generator;
+mod duckdb_procedure_generator;
+mod duckdb_relation_generator;
+mod duckdb_sequence_generator;
+mod duckdb_table_constraint_generator;
+mod duckdb_table_generator;
+pub mod duckdb_trigger_generator;
+mod duckdb_view_generator;
@@ -1,4 +1,6 @@
 pub mod common;
+mod duckdb;
+mod mysql;
 mod postgresql;
 mod sqlite;
 mod sqlserver;
@@ -1,15 +1,15 @@
 use clap::{Arg, ArgAction, Command};
 use schema_model::model::database_model::DatabaseModel;
-use schema_model::model::types::{BooleanMode, ForeignKeyMode};
+use schema_model::model::naming::NamingConvention;
+use schema_model::model::types::{BooleanMode, DatabaseType, ForeignKeyMode, IdentifierQuoting, IdentityMode};
 use schema_parser::parse_database_xml;
+use schema_sql_generator::common::constraint_naming::ConstraintNaming;
 use schema_sql_generator::common::generate_options::GenerateOptions;
 use schema_sql_generator::common::generator_type::GeneratorType;
 use schema_sql_generator::common::output_mode::OutputMode;
-use schema_sql_generator::common::print_writer::PrintWriter;
-use std::cell::RefCell;
-use std::fs::{self, File};
+use std::fs;
 use std::path::Path;
-use std::rc::Rc;
+use std::sync::Arc;
 
 const EMPTY_SCHEMA_TEMPLATE: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
 <database xmlns="http://stano.com/database"
@@ -26,7 +26,7 @@ pub fn main() {
         .arg(Arg::new("database-type")
             .long("database-type")
             .value_name("TYPE")
-            .value_parser(["postgresql", "sqlite", "sqlserver"])
+            .value_parser(["postgresql", "sqlite", "sqlserver", "mysql", "mariadb", "duckdb"])
             .required(false)
             .num_args(1)
             .ignore_case(true)
@@ -49,8 +49,12 @@ pub fn main() {
         .arg(Arg::new("output-mode")
             .long("output-mode")
             .value_name("MODE")
-            .value_parser(["all", "indexes-only", "triggers-only"])
+            .value_parser(["all", "indexes-only", "triggers-only", "drop"])
             .help("Sets the output mode"))
+        .arg(Arg::new("drop")
+            .long("drop")
+            .action(ArgAction::SetTrue)
+            .help("Generate a teardown script that drops every object in the schema (same as --output-mode drop)"))
         .arg(Arg::new("postgresql-version")
             .long("postgresql-version")
             .value_name("VERSION")
@@ -63,6 +67,30 @@ pub fn main() {
             .long("extension-check-user")
             .value_name("USER")
             .help("Postgres role to check for superuser privilege in the create-extension block (default: CURRENT_USER)"))
+        .arg(Arg::new("identity-mode")
+            .long("identity-mode")
+            .value_name("MODE")
+            .value_parser(["serial", "always", "by-default"])
+            .help("How PostgreSQL sequence columns are generated: serial (default) or an identity column generated always or by default"))
+        .arg(Arg::new("idempotent")
+            .long("idempotent")
+            .action(ArgAction::SetTrue)
+            .help("Guard every create with an existence check so the script can be run again"))
+        .arg(Arg::new("quote-identifiers")
+            .long("quote-identifiers")
+            .value_name("POLICY")
+            .value_parser(["always", "when-needed", "never"])
+            .help("When to quote table, column and other names: always, when-needed (default; reserved words and names with characters only valid quoted) or never"))
+        .arg(Arg::new("naming")
+            .long("naming")
+            .value_name("CONVENTION")
+            .value_parser(["preserve", "snake-case", "lower", "upper"])
+            .help("How model names are spelled in the database: preserve (default), snake-case, lower or upper"))
+        .arg(Arg::new("name-template")
+            .long("name-template")
+            .value_name("KIND=TEMPLATE")
+            .action(ArgAction::Append)
+            .help("Overrides a generated name, e.g. fk=fk_{table}_{column}; KIND is pk, ak, fk, ix, ck or df"))
         .arg(Arg::new("new-schema")
             .long("new-schema")
             .action(ArgAction::SetTrue)
@@ -107,39 +135,77 @@ pub fn main() {
     let foreign_key_mode = arguments.get_one::<String>("foreign-key-mode").unwrap_or(&empty);
     let boolean_mode = arguments.get_one::<String>("boolean-mode").unwrap_or(&empty);
     let output_mode = arguments.get_one::<String>("output-mode").unwrap_or(&empty);
+    let identity_mode = arguments.get_one::<String>("identity-mode").unwrap_or(&empty);
+    let identifier_quoting = arguments.get_one::<String>("quote-identifiers").unwrap_or(&empty);
+    let naming_convention = arguments.get_one::<String>("naming").unwrap_or(&empty);
+    let mut constraint_naming = ConstraintNaming::default();
+    for spec in arguments.get_many::<String>("name-template").unwrap_or_default() {
+        if let Err(error) = set_name_template(&mut constraint_naming, spec) {
+            eprintln!("Error: {}", error);
+            std::process::exit(1);
+        }
+    }
     let target_postgres_version: u32 = arguments
         .get_one::<String>("postgresql-version")
         .and_then(|v| v.parse().ok())
         .unwrap_or(0);
+    let output_mode = if arguments.get_flag("drop") {
+        OutputMode::Drop
+    } else {
+        output_mode.parse().unwrap_or(OutputMode::All)
+    };
     let schema_path = Path::new(schema_file);
-    let output_path = build_output_path(schema_path, database_type.to_string().to_lowercase());
-    let output_file = File::create(output_path).expect("");
-    let print_writer = PrintWriter::new(Box::new(output_file));
+    let output_suffix = match output_mode {
+        OutputMode::Drop => format!("{}-drop", database_type.to_lowercase()),
+        _ => database_type.to_string().to_lowercase(),
+    };
+    let output_path = build_output_path(schema_path, output_suffix);
     let generator_type: GeneratorType = database_type.parse().unwrap();
-    let database_model = load_schema(schema_path);
+    let database_model = load_schema(schema_path, generator_type.database_type());
     let options = GenerateOptions {
-        database_model: Rc::new(database_model),
-        writer: Rc::new(RefCell::new(print_writer)),
+        database_model: Arc::new(database_model),
         boolean_mode: boolean_mode.parse().unwrap_or(BooleanMode::Native),
         foreign_key_mode: foreign_key_mode.parse().unwrap_or(ForeignKeyMode::Relations),
-        output_mode: output_mode.parse().unwrap_or(OutputMode::All),
+        output_mode,
         target_postgres_version,
+        target_mariadb: database_type.eq_ignore_ascii_case("mariadb"),
         emit_postgres_extensions: !arguments.get_flag("no-postgres-extensions"),
         extension_check_user: arguments.get_one::<String>("extension-check-user").cloned(),
+        identity_mode: identity_mode.parse().unwrap_or(IdentityMode::Serial),
+        idempotent: arguments.get_flag("idempotent"),
+        identifier_quoting: identifier_quoting.parse().unwrap_or(IdentifierQuoting::WhenNeeded),
+        naming_convention: naming_convention.parse().unwrap_or(NamingConvention::Preserve),
+        constraint_naming,
     };
 
-    generator_type.generate(options);
+    // Generated in memory, so a failure leaves any script from an earlier run in place
+    // rather than a truncated one.
+    let script = match generator_type.generate_script(&options) {
+        Ok(script) => script,
+        Err(error) => {
+            eprintln!("Error: {}", error);
+            std::process::exit(1);
+        }
+    };
+    if let Err(error) = fs::write(&output_path, script) {
+        eprintln!("Error: can't write {}: {}", output_path, error);
+        std::process::exit(1);
+    }
 }
 
-fn load_schema(schema_path: &Path) -> DatabaseModel {
-    let contents = fs::read_to_string(schema_path).expect("failed to read the schema file");
-    let database_model = parse_database_xml(contents.as_str()).expect("failed to parse the schema");
+fn load_schema(schema_path: &Path, database_type: DatabaseType) -> DatabaseModel {
+    let contents = fs::read_to_string(schema_path).unwrap_or_else(|error| {
+        eprintln!("Error: can't read the schema file {}: {}", schema_path.display(), error);
+        std::process::exit(1);
+    });
+    let database_model = parse_database_xml(contents.as_str()).unwrap_or_else(|error| {
+        eprintln!("Error: can't parse the schema file {}: {}", schema_path.display(), error);
+        std::process::exit(1);
+    });
 
-    // Catches dangling relation targets and enum-type references up front, with a clear
-    // message - several generator code paths (`find_enum_type`, `find_table_by_qualified_name`,
-    // ...) panic on a reference that doesn't resolve, on the assumption the model was
-    // already validated.
-    let errors = database_model.validate();
+    // Catches dangling relation targets and enum-type references up front, reporting every
+    // problem at once instead of only the first one generation would stop at.
+    let errors = database_model.validate_for(database_type);
     if !errors.is_empty() {
         eprintln!("Error: the schema file is invalid:");
         for error in &errors {
@@ -158,3 +224,20 @@ fn build_output_path(path: &Path, database_type: String) -> String {
 
     parent.join(new_filename).to_string_lossy().to_string()
 }
+
+fn set_name_template(constraint_naming: &mut ConstraintNaming, spec: &str) -> Result<(), String> {
+    let (kind, template) = spec
+        .split_once('=')
+        .ok_or_else(|| format!("name template '{}' is not KIND=TEMPLATE", spec))?;
+    let slot = match kind {
+        "pk" => &mut constraint_naming.primary_key,
+        "ak" => &mut constraint_naming.unique_key,
+        "fk" => &mut constraint_naming.foreign_key,
+        "ix" => &mut constraint_naming.index,
+        "ck" => &mut constraint_naming.check,
+        "df" => &mut constraint_naming.default,
+        _ => return Err(format!("unknown name template kind '{}'", kind)),
+    };
+    *slot = template.to_string();
+    Ok(())
+}
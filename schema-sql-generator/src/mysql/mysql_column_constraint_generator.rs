@@ -0,0 +1,56 @@
+use crate::common::column_constraint_generator::{ColumnConstraintGenerator, DefaultColumnConstraintGenerator};
+use crate::common::generation_error::GenerationError;
+use crate::common::generator_context::GeneratorContext;
+use schema_model::model::column_type::ColumnType;
+use schema_model::model::table::Table;
+
+pub struct MySqlColumnConstraintGenerator {
+    column_constraint_generator: DefaultColumnConstraintGenerator,
+}
+
+impl MySqlColumnConstraintGenerator {
+    pub fn new(context: GeneratorContext) -> Self {
+        Self {
+            column_constraint_generator: DefaultColumnConstraintGenerator::new(context),
+        }
+    }
+}
+
+impl ColumnConstraintGenerator for MySqlColumnConstraintGenerator {
+    /// An enum column needs no check: its native `enum(...)` type only admits the values.
+    fn column_check_constraints(&self, table: &Table) -> Result<Vec<String>, GenerationError> {
+        let boolean_mode = self.column_constraint_generator.context().settings().boolean_mode();
+        table.columns_with_check_constraints(boolean_mode)
+            .iter()
+            .filter(|col| col.column_type() != ColumnType::Enum)
+            .map(|col| self.column_constraint_generator.generate_constraint(table, col))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::test_support::make_context;
+    use schema_model::builder::{ColumnBuilder, SchemaBuilder, TableBuilder};
+    use schema_model::model::database_model::DatabaseModel;
+    use schema_model::model::enum_type::{EnumType, EnumValue};
+    use schema_model::model::types::{BooleanMode, DatabaseType, ForeignKeyMode};
+
+    #[test]
+    fn enum_columns_rely_on_the_native_enum_type() {
+        let enum_type = EnumType::new("status", vec![EnumValue::new("ACTIVE", Some("A"))]);
+        let table = TableBuilder::new(None::<&str>, "accounts")
+            .add_column(ColumnBuilder::new(None::<&str>, "status", ColumnType::Enum).enum_type(Some("status".to_string())).build())
+            .add_column(ColumnBuilder::new(None::<&str>, "balance", ColumnType::Int).min_value(Some(0.0)).build())
+            .build();
+        let schema = SchemaBuilder::new(None::<&str>).add_enum_type(enum_type).add_table(table.clone()).build();
+        let model = DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema]);
+        let (ctx, _buffer) = make_context(model, DatabaseType::MySql);
+
+        let constraints = MySqlColumnConstraintGenerator::new(ctx).column_check_constraints(&table).unwrap();
+
+        assert_eq!(constraints.len(), 1);
+        assert!(constraints[0].ends_with("check(balance >= 0)"));
+    }
+}
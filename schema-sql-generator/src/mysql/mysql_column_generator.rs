@@ -0,0 +1,84 @@
+use crate::common::column_generator::{ColumnGenerator, DefaultColumnGenerator, DefaultConstraintNaming};
+use crate::common::generation_error::GenerationError;
+use crate::common::generator_context::GeneratorContext;
+use crate::common::sql_string::escape_mysql_literal;
+use crate::mysql::mysql_column_type_generator::MySqlColumnTypeGenerator;
+use schema_model::model::column::Column;
+use schema_model::model::table::Table;
+
+pub struct MySqlColumnGenerator {
+    column_generator: DefaultColumnGenerator,
+}
+
+impl MySqlColumnGenerator {
+    pub fn new(context: GeneratorContext) -> Self {
+        Self {
+            column_generator: DefaultColumnGenerator::new(
+                context.clone(),
+                Box::new(MySqlColumnTypeGenerator::new(context.clone())),
+                DefaultConstraintNaming::Unnamed,
+            ),
+        }
+    }
+}
+
+impl ColumnGenerator for MySqlColumnGenerator {
+    fn column_definitions(&self, table: &Table) -> Result<Vec<String>, GenerationError> {
+        table
+            .columns()
+            .iter()
+            .map(|column| self.column_sql(table, column))
+            .collect()
+    }
+
+    /// MySQL keeps a column's description in the column definition itself.
+    fn column_sql(&self, table: &Table, column: &Column) -> Result<String, GenerationError> {
+        let column_sql = self.column_generator.column_sql(table, column)?;
+        match column.description() {
+            Some(description) => Ok(format!("{} comment '{}'", column_sql, escape_mysql_literal(description))),
+            None => Ok(column_sql),
+        }
+    }
+
+    fn column_options(&self, table: &Table, column: &Column) -> Result<String, GenerationError> {
+        self.column_generator.column_options(table, column)
+    }
+
+    fn default_value(&self, table: &Table, column: &Column) -> Result<Option<String>, GenerationError> {
+        self.column_generator.default_value(table, column)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::test_support::make_context;
+    use schema_model::builder::{ColumnBuilder, SchemaBuilder, TableBuilder};
+    use schema_model::model::column_type::ColumnType;
+    use schema_model::model::database_model::DatabaseModel;
+    use schema_model::model::key::{Key, KeyColumn};
+    use schema_model::model::types::{BooleanMode, DatabaseType, ForeignKeyMode, KeyType};
+
+    #[test]
+    fn column_sql_renders_defaults_and_inline_comments() {
+        let table = TableBuilder::new(None::<&str>, "users")
+            .add_column(ColumnBuilder::new(None::<&str>, "id", ColumnType::Uuid).required(true).build())
+            .add_column(
+                ColumnBuilder::new(None::<&str>, "active", ColumnType::Boolean)
+                    .required(true)
+                    .default_constraint(Some("true".to_string()))
+                    .description(Some("Whether the user can log in".to_string()))
+                    .build(),
+            )
+            .add_key(Key::new(KeyType::Primary, vec![KeyColumn::new("id")]))
+            .build();
+        let schema = SchemaBuilder::new(None::<&str>).add_table(table.clone()).build();
+        let model = DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema]);
+        let (ctx, _buffer) = make_context(model, DatabaseType::MySql);
+
+        let definitions = MySqlColumnGenerator::new(ctx).column_definitions(&table).unwrap();
+
+        assert_eq!(definitions[0], "   id char(36) not null default (uuid())");
+        assert_eq!(definitions[1], "   active boolean not null default true comment 'Whether the user can log in'");
+    }
+}
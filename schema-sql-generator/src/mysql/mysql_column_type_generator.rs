@@ -0,0 +1,205 @@
+use crate::common::column_type_generator::{find_enum_type, ColumnTypeGenerator};
+use crate::common::generation_error::GenerationError;
+use crate::common::generator_context::GeneratorContext;
+use crate::common::sql_string::escape_mysql_literal;
+use schema_model::model::column::Column;
+use schema_model::model::schema::Schema;
+use schema_model::model::sequence::Sequence;
+use schema_model::model::table::Table;
+
+pub struct MySqlColumnTypeGenerator {
+    context: GeneratorContext
+}
+
+impl MySqlColumnTypeGenerator {
+    pub fn new(context: GeneratorContext) -> Self {
+        Self {
+            context
+        }
+    }
+}
+
+impl ColumnTypeGenerator for MySqlColumnTypeGenerator {
+    fn context(&self) -> &GeneratorContext {
+        &self.context
+    }
+
+    /// MySQL only auto-increments a column that is (the first column of) a key.
+    fn sequence_sql(&self, _column: &Column) -> String {
+        "integer auto_increment".to_string()
+    }
+
+    fn long_sequence_sql(&self, _column: &Column) -> String {
+        "bigint auto_increment".to_string()
+    }
+
+    /// `text` stops at 64KB, so the unbounded text types map to `longtext`.
+    fn text_sql(&self, _column: &Column) -> String {
+        "longtext".to_string()
+    }
+
+    fn citext_sql(&self) -> String {
+        "longtext collate utf8mb4_unicode_ci".to_string()
+    }
+
+    fn cstext_sql(&self) -> String {
+        "longtext collate utf8mb4_bin".to_string()
+    }
+
+    fn binary_sql(&self) -> String {
+        "longblob".to_string()
+    }
+
+    fn uuid_default_value_sql(&self, _schema: &Schema) -> String {
+        "(uuid())".to_string()
+    }
+
+    /// Sequences are MariaDB's; MySQL itself has none to draw from.
+    fn sequence_default_value_sql(&self, sequence: &Sequence) -> Option<String> {
+        Some(format!(
+            "nextval({})",
+            self.context.settings().qualified_name(sequence.schema_name(), sequence.name())
+        ))
+    }
+
+    fn array_sql(&self, table: &Table, column: &Column) -> Result<String, GenerationError> {
+        Err(GenerationError::unsupported(
+            format!("column {}.{}", table.name(), column.name()),
+            "MySQL does not support arrays",
+        ))
+    }
+
+    fn float_sql(&self) -> String {
+        "float".to_string()
+    }
+
+    fn double_sql(&self) -> String {
+        "double".to_string()
+    }
+
+    /// `timestamp` only reaches 2038 and converts to the session time zone, so plain
+    /// timestamps are `datetime`; a time-zone aware one is the `timestamp` stored as UTC.
+    fn date_time_sql(&self) -> String {
+        "datetime".to_string()
+    }
+
+    fn timestamp_tz_sql(&self) -> String {
+        "timestamp".to_string()
+    }
+
+    fn uuid_sql(&self, _column: &Column) -> String {
+        "char(36)".to_string()
+    }
+
+    fn json_sql(&self, _column: &Column) -> String {
+        "json".to_string()
+    }
+
+    fn enum_sql(&self, table: &Table, column: &Column) -> Result<String, GenerationError> {
+        let enum_type = find_enum_type(self.context(), table, column)?;
+        let values = enum_type
+            .values()
+            .iter()
+            .map(|value| format!("'{}'", escape_mysql_literal(value.code())))
+            .collect::<Vec<_>>()
+            .join(",");
+
+        Ok(format!("enum({})", values))
+    }
+
+    fn native_boolean_sql(&self) -> String {
+        "boolean".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::test_support::make_context;
+    use schema_model::builder::{ColumnBuilder, SchemaBuilder, TableBuilder};
+    use schema_model::model::column_type::ColumnType;
+    use schema_model::model::database_model::DatabaseModel;
+    use schema_model::model::enum_type::{EnumType, EnumValue};
+    use schema_model::model::types::{BooleanMode, DatabaseType, ForeignKeyMode};
+
+    fn make_model() -> DatabaseModel {
+        let enum_type = EnumType::new(
+            "status_type",
+            vec![EnumValue::new("ACTIVE", Some("A")), EnumValue::new("ON_HOLD", Some("O'H"))],
+        );
+        let schema = SchemaBuilder::new(None::<&str>).add_enum_type(enum_type).build();
+        DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema])
+    }
+
+    fn column_type_sql(column: Column) -> Result<String, GenerationError> {
+        let (ctx, _buffer) = make_context(make_model(), DatabaseType::MySql);
+        let table = TableBuilder::new(None::<&str>, "test").build();
+        MySqlColumnTypeGenerator::new(ctx).column_type_sql(&table, &column)
+    }
+
+    fn assert_type(column_type: ColumnType, expected: &str) {
+        let column = ColumnBuilder::new(None::<&str>, "col", column_type).build();
+        assert_eq!(column_type_sql(column).unwrap(), expected);
+    }
+
+    #[test]
+    fn sequence_types() {
+        assert_type(ColumnType::Sequence, "integer auto_increment");
+        assert_type(ColumnType::LongSequence, "bigint auto_increment");
+    }
+
+    #[test]
+    fn numeric_types() {
+        assert_type(ColumnType::Byte, "tinyint");
+        assert_type(ColumnType::Short, "smallint");
+        assert_type(ColumnType::Int, "integer");
+        assert_type(ColumnType::Long, "bigint");
+        assert_type(ColumnType::Float, "float");
+        assert_type(ColumnType::Double, "double");
+        assert_type(ColumnType::Decimal, "decimal");
+    }
+
+    #[test]
+    fn temporal_types() {
+        assert_type(ColumnType::Date, "date");
+        assert_type(ColumnType::DateTime, "datetime");
+        assert_type(ColumnType::Time, "time");
+        assert_type(ColumnType::Timestamp, "datetime");
+        assert_type(ColumnType::TimestampTz, "timestamp");
+    }
+
+    #[test]
+    fn text_types() {
+        assert_type(ColumnType::Varchar, "varchar(0)");
+        assert_type(ColumnType::Char, "char(0)");
+        assert_type(ColumnType::Text, "longtext");
+        assert_type(ColumnType::CiText, "longtext collate utf8mb4_unicode_ci");
+        assert_type(ColumnType::CsText, "longtext collate utf8mb4_bin");
+        assert_type(ColumnType::Json, "json");
+        assert_type(ColumnType::Uuid, "char(36)");
+    }
+
+    #[test]
+    fn other_types() {
+        assert_type(ColumnType::Boolean, "boolean");
+        assert_type(ColumnType::Binary, "longblob");
+    }
+
+    #[test]
+    fn enum_columns_use_the_native_enum_type() {
+        let column = ColumnBuilder::new(None::<&str>, "status", ColumnType::Enum)
+            .enum_type(Some("status_type".to_string()))
+            .build();
+
+        assert_eq!(column_type_sql(column).unwrap(), "enum('A','O''H')");
+    }
+
+    #[test]
+    fn array_sql_is_unsupported() {
+        let column = ColumnBuilder::new(None::<&str>, "col", ColumnType::Array).build();
+
+        let error = column_type_sql(column).unwrap_err();
+
+        assert!(error.to_string().contains("MySQL does not support arrays"));
+    }
+}
@@ -0,0 +1,139 @@
+use crate::common::drop_generator::{output_drop_steps, tables_in_drop_order, DefaultDropGenerator, DropGenerator};
+use crate::common::generated_statement::StatementKind;
+use crate::common::generation_error::GenerationError;
+use crate::common::generator_context::GeneratorContext;
+use crate::common::relation_generator::DefaultRelationGenerator;
+use crate::common::trigger_generator::TriggerGenerator;
+use crate::mysql::mysql_trigger_generator::MySqlTriggerGenerator;
+use crate::sql_println;
+
+pub struct MySqlDropGenerator {
+    context: GeneratorContext,
+    drop_generator: DefaultDropGenerator,
+    trigger_generator: MySqlTriggerGenerator,
+}
+
+impl MySqlDropGenerator {
+    pub fn new(context: GeneratorContext) -> Self {
+        Self {
+            context: context.clone(),
+            drop_generator: DefaultDropGenerator::new(context.clone()),
+            trigger_generator: MySqlTriggerGenerator::new(context),
+        }
+    }
+}
+
+impl DropGenerator for MySqlDropGenerator {
+    fn output_drops(&self) -> Result<(), GenerationError> {
+        output_drop_steps(self, self.context.settings().foreign_key_mode())
+    }
+
+    fn output_drop_procedures(&self) -> Result<(), GenerationError> {
+        self.drop_generator.output_drop_procedures()
+    }
+
+    fn output_drop_views(&self) -> Result<(), GenerationError> {
+        self.drop_generator.output_drop_views()
+    }
+
+    fn output_drop_functions(&self) -> Result<(), GenerationError> {
+        self.drop_generator.output_drop_functions()
+    }
+
+    fn output_drop_triggers(&self) -> Result<(), GenerationError> {
+        let settings = self.context.settings();
+        let separator = settings.statement_separator();
+
+        self.context.with_writer(|writer| {
+            for table in settings.database_model().all_tables().into_iter().rev() {
+                for trigger_name in self.trigger_generator.trigger_names(table) {
+                    writer.begin_object(StatementKind::Trigger, Some(&settings.database_type().qualified_name(table.schema_name(), &trigger_name)));
+                    sql_println!(writer, "drop trigger if exists {}{}", settings.qualified_generated_name(table.schema_name(), &trigger_name), separator)?;
+                }
+            }
+            Ok(())
+        })
+    }
+
+    /// A foreign key is dropped with `drop foreign key` rather than `drop constraint`.
+    fn output_drop_relations(&self) -> Result<(), GenerationError> {
+        let settings = self.context.settings();
+        let separator = settings.statement_separator();
+        let relation_generator = DefaultRelationGenerator::new(self.context.clone());
+
+        self.context.with_writer(|writer| {
+            for table in tables_in_drop_order(settings.database_model()) {
+                let table_name = settings.qualified_name(table.schema_name(), table.name());
+                for relation_index in 0..table.relations().len() {
+                    let relation_name = relation_generator.relation_constraint_name(table, relation_index);
+                    writer.begin_object(StatementKind::Relation, Some(&relation_name));
+                    sql_println!(writer, "alter table if exists {} drop foreign key if exists {}{}", table_name, settings.quote_generated(&relation_name), separator)?;
+                }
+            }
+            Ok(())
+        })
+    }
+
+    fn output_drop_tables(&self) -> Result<(), GenerationError> {
+        self.drop_generator.output_drop_tables()
+    }
+
+    fn output_drop_enum_types(&self) -> Result<(), GenerationError> {
+        // Enums are column types, so they go with their tables.
+        Ok(())
+    }
+
+    fn output_drop_sequences(&self) -> Result<(), GenerationError> {
+        self.drop_generator.output_drop_sequences()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::test_support::make_context_with_fk_mode;
+    use schema_model::builder::{ColumnBuilder, KeyBuilder, SchemaBuilder, TableBuilder};
+    use schema_model::model::column_type::ColumnType;
+    use schema_model::model::database_model::DatabaseModel;
+    use schema_model::model::relation::Relation;
+    use schema_model::model::types::{BooleanMode, DatabaseType, ForeignKeyMode, KeyType, RelationType};
+
+    fn make_model() -> DatabaseModel {
+        let mut parent = TableBuilder::new(None::<&str>, "parent")
+            .add_column(ColumnBuilder::new(None::<&str>, "id", ColumnType::Int).required(true).build())
+            .add_key(KeyBuilder::new(KeyType::Primary).add_column("id").build())
+            .build();
+        parent.add_reverse_relation(Relation::new("parent", "id", "child", "parent_id", RelationType::Cascade, false));
+        let child = TableBuilder::new(None::<&str>, "child")
+            .add_column(ColumnBuilder::new(None::<&str>, "parent_id", ColumnType::Int).build())
+            .add_relation(Relation::new("parent", "id", "child", "parent_id", RelationType::Cascade, false))
+            .build();
+        let schema = SchemaBuilder::new(None::<&str>).add_table(child).add_table(parent).build();
+        DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Triggers, vec![schema])
+    }
+
+    #[test]
+    fn output_drops_with_relations_drops_foreign_keys_before_tables() {
+        let (ctx, buffer) = make_context_with_fk_mode(make_model(), DatabaseType::MySql, ForeignKeyMode::Relations);
+
+        MySqlDropGenerator::new(ctx).output_drops().unwrap();
+
+        let output = buffer.contents();
+        assert!(output.contains("alter table if exists child drop foreign key if exists fk_child1;"));
+        assert!(!output.contains("drop trigger"));
+        assert!(output.find("drop table if exists child;").unwrap() < output.find("drop table if exists parent;").unwrap());
+    }
+
+    #[test]
+    fn output_drops_with_triggers_drops_every_generated_trigger() {
+        let (ctx, buffer) = make_context_with_fk_mode(make_model(), DatabaseType::MySql, ForeignKeyMode::Triggers);
+
+        MySqlDropGenerator::new(ctx).output_drops().unwrap();
+
+        let output = buffer.contents();
+        assert!(output.contains("drop trigger if exists parent_delete;"));
+        assert!(output.contains("drop trigger if exists child_insert;"));
+        assert!(output.contains("drop trigger if exists child_update;"));
+        assert!(!output.contains("drop foreign key"));
+    }
+}
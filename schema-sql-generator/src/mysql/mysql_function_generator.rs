@@ -0,0 +1,68 @@
+use crate::common::function_generator::{DefaultFunctionGenerator, FunctionGenerator};
+use crate::common::generation_error::GenerationError;
+use crate::common::generator_context::GeneratorContext;
+use crate::common::sql_writer::SqlWriter;
+use crate::mysql::mysql_util::output_stored_program;
+use crate::sql_println;
+use schema_model::model::function::Function;
+
+pub struct MySqlFunctionGenerator {
+    function_generator: DefaultFunctionGenerator
+}
+
+impl MySqlFunctionGenerator {
+    pub fn new(context: GeneratorContext) -> Self {
+        Self {
+            function_generator: DefaultFunctionGenerator::new(context),
+        }
+    }
+}
+
+impl FunctionGenerator for MySqlFunctionGenerator {
+    fn output_functions(&self) -> Result<(), GenerationError> {
+        self.function_generator.output_functions_via(self)
+    }
+
+    /// MySQL has no `create or replace function`, so an existing function is dropped first.
+    fn output_function(&self, writer: &mut SqlWriter, statement_separator: &str, function: &Function) -> Result<(), GenerationError> {
+        let settings = self.function_generator.context().settings();
+        let function_name = settings.qualified_generated_name(function.schema_name(), function.name());
+
+        sql_println!(writer, "drop function if exists {}{}", function_name, statement_separator)?;
+        output_stored_program(writer, function.sql())?;
+        writer.newline()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::test_support::make_context;
+    use schema_model::builder::SchemaBuilder;
+    use schema_model::model::database_model::DatabaseModel;
+    use schema_model::model::types::{BooleanMode, DatabaseType, ForeignKeyMode};
+
+    #[test]
+    fn output_functions_replaces_the_function_between_delimiters() {
+        let function = Function::new(
+            None::<&str>,
+            "add_one",
+            DatabaseType::MySql,
+            "create function add_one(x int) returns int deterministic\nbegin\n   return x + 1;\nend;",
+        );
+        let schema = SchemaBuilder::new(None::<&str>).add_functions(vec![function]).build();
+        let model = DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema]);
+        let (ctx, buffer) = make_context(model, DatabaseType::MySql);
+
+        MySqlFunctionGenerator::new(ctx).output_functions().unwrap();
+
+        assert_eq!(
+            buffer.contents(),
+            "drop function if exists add_one;\n\
+             delimiter //\n\
+             create function add_one(x int) returns int deterministic\nbegin\n   return x + 1;\nend//\n\
+             delimiter ;\n\n"
+        );
+    }
+}
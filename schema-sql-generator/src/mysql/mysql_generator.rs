@@ -0,0 +1,101 @@
+use crate::common::generation_error::GenerationError;
+use crate::common::generator_context::GeneratorContext;
+use crate::common::sql_generator::{DefaultSqlGenerator, SqlGenerator};
+use crate::mysql::mysql_drop_generator::MySqlDropGenerator;
+use crate::mysql::mysql_function_generator::MySqlFunctionGenerator;
+use crate::mysql::mysql_index_generator::MySqlIndexGenerator;
+use crate::mysql::mysql_other_sql_generator::MySqlOtherSqlGenerator;
+use crate::mysql::mysql_procedure_generator::MySqlProcedureGenerator;
+use crate::mysql::mysql_relation_generator::MySqlRelationGenerator;
+use crate::mysql::mysql_sequence_generator::MySqlSequenceGenerator;
+use crate::mysql::mysql_table_generator::MySqlTableGenerator;
+use crate::mysql::mysql_trigger_generator::MySqlTriggerGenerator;
+use crate::mysql::mysql_view_generator::MySqlViewGenerator;
+
+pub struct MySqlGenerator {
+    sql_generator: DefaultSqlGenerator,
+}
+
+impl MySqlGenerator {
+    pub fn new(context: GeneratorContext) -> Self {
+        let sql_generator = DefaultSqlGenerator::new(
+            context.clone(),
+            Box::new(MySqlTableGenerator::new(context.clone())),
+            Box::new(MySqlRelationGenerator::new(context.clone())),
+            Box::new(MySqlIndexGenerator::new(context.clone())),
+            Box::new(MySqlFunctionGenerator::new(context.clone())),
+            Box::new(MySqlViewGenerator::new(context.clone())),
+            Box::new(MySqlProcedureGenerator::new(context.clone())),
+            Box::new(MySqlTriggerGenerator::new(context.clone())),
+            Box::new(MySqlOtherSqlGenerator::new(context.clone())),
+            Box::new(MySqlSequenceGenerator::new(context.clone())),
+            Box::new(MySqlDropGenerator::new(context.clone())),
+        );
+
+        Self {
+            sql_generator,
+        }
+    }
+}
+
+impl SqlGenerator for MySqlGenerator {
+    fn context(&self) -> &GeneratorContext {
+        self.sql_generator.context()
+    }
+
+    fn generate(&self) -> Result<(), GenerationError> {
+        self.sql_generator.generate()
+    }
+
+    fn output_sql(&self) -> Result<(), GenerationError> {
+        self.sql_generator.output_sql()
+    }
+
+    fn output_header(&self) -> Result<(), GenerationError> {
+        self.sql_generator.output_header()
+    }
+
+    fn output_sequences(&self) -> Result<(), GenerationError> {
+        self.sql_generator.output_sequences()
+    }
+
+    fn output_tables(&self) -> Result<(), GenerationError> {
+        self.sql_generator.output_tables()
+    }
+
+    fn output_relations(&self) -> Result<(), GenerationError> {
+        self.sql_generator.output_relations()
+    }
+
+    fn output_indexes(&self) -> Result<(), GenerationError> {
+        self.sql_generator.output_indexes()
+    }
+
+    fn output_triggers(&self) -> Result<(), GenerationError> {
+        self.sql_generator.output_triggers()
+    }
+
+    fn output_functions(&self) -> Result<(), GenerationError> {
+        self.sql_generator.output_functions()
+    }
+
+    fn output_views(&self) -> Result<(), GenerationError> {
+        self.sql_generator.output_views()
+    }
+
+    fn output_procedures(&self) -> Result<(), GenerationError> {
+        self.sql_generator.output_procedures()
+    }
+
+    fn output_other_sql_top(&self) -> Result<(), GenerationError> {
+        self.sql_generator.output_other_sql_top()
+    }
+
+    fn output_other_sql_bottom(&self) -> Result<(), GenerationError> {
+        self.sql_generator.output_other_sql_bottom()
+    }
+
+    fn output_drops(&self) -> Result<(), GenerationError> {
+        self.sql_generator.output_drops()
+    }
+}
@@ -0,0 +1,96 @@
+use crate::common::generation_error::GenerationError;
+use crate::common::generator_context::GeneratorContext;
+use crate::common::index_generator::{DefaultIndexGenerator, IndexGenerator};
+use crate::common::sql_writer::SqlWriter;
+use schema_model::model::key::Key;
+use schema_model::model::table::Table;
+
+pub struct MySqlIndexGenerator {
+    index_generator: DefaultIndexGenerator,
+}
+
+impl MySqlIndexGenerator {
+    pub fn new(context: GeneratorContext) -> Self {
+        Self {
+            index_generator: DefaultIndexGenerator::new(context),
+        }
+    }
+}
+
+impl IndexGenerator for MySqlIndexGenerator {
+    fn output_indexes(&self) -> Result<(), GenerationError> {
+        self.index_generator.context().with_writer(|writer| {
+            for table in self.index_generator.context().settings().database_model().all_tables() {
+                self.output_indexes_for_table(writer, table)?;
+            }
+            Ok(())
+        })
+    }
+
+    fn output_indexes_for_table(&self, writer: &mut SqlWriter, table: &Table) -> Result<(), GenerationError> {
+        if let Some(key) = table.indexes().iter().find(|key| key.filter().is_some()) {
+            return Err(GenerationError::unsupported(
+                format!("index on {}", table.name()),
+                format!("MySQL has no partial indexes, so the filter '{}' can't be applied", key.filter().unwrap_or_default()),
+            ));
+        }
+        self.index_generator.output_indexes_for_table(writer, table)
+    }
+
+    fn output_index(&self, writer: &mut SqlWriter, statement_separator: &str, table: &Table, key_name: &str, key: &Key) -> Result<(), GenerationError> {
+        self.index_generator.output_index(writer, statement_separator, table, key_name, key)
+    }
+
+    fn index_options(&self, key: &Key) -> Option<String> {
+        self.index_generator.index_options(key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::test_support::make_context;
+    use schema_model::builder::{KeyBuilder, SchemaBuilder, TableBuilder};
+    use schema_model::model::database_model::DatabaseModel;
+    use schema_model::model::key::KeyColumn;
+    use schema_model::model::types::{BooleanMode, DatabaseType, ForeignKeyMode, IndexMethod, KeyType, NullsOrder, SortOrder};
+
+    fn output_indexes(table: Table) -> Result<String, GenerationError> {
+        let schema = SchemaBuilder::new(None::<&str>).add_table(table).build();
+        let model = DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema]);
+        let (ctx, buffer) = make_context(model, DatabaseType::MySql);
+
+        MySqlIndexGenerator::new(ctx).output_indexes()?;
+        Ok(buffer.contents())
+    }
+
+    #[test]
+    fn output_index_renders_method_and_drops_nulls_order() {
+        let index = KeyBuilder::new(KeyType::Index)
+            .add_key_column(KeyColumn::new("created_at").with_sort_order(Some(SortOrder::Desc)).with_nulls_order(Some(NullsOrder::Last)))
+            .method(IndexMethod::BTree)
+            .unique(true)
+            .build();
+        let table = TableBuilder::new(None::<&str>, "users").add_index(index).build();
+
+        let output = output_indexes(table).unwrap();
+
+        assert!(output.contains("create unique index ix_users1 on users (created_at desc) using btree;"));
+    }
+
+    #[test]
+    fn filtered_and_expression_indexes_are_unsupported() {
+        let filtered = KeyBuilder::new(KeyType::Index)
+            .add_key_column(KeyColumn::new("email"))
+            .filter("deleted_at is null")
+            .build();
+        let error = output_indexes(TableBuilder::new(None::<&str>, "users").add_index(filtered).build()).unwrap_err();
+        assert!(error.to_string().contains("MySQL has no partial indexes"));
+
+        let expression = KeyBuilder::new(KeyType::Index)
+            .add_key_column(KeyColumn::new_expression("lower(email)"))
+            .build();
+        let error = output_indexes(TableBuilder::new(None::<&str>, "users").add_index(expression).build()).unwrap_err();
+        assert!(error.to_string().contains("add a generated column instead"));
+    }
+}
@@ -0,0 +1,63 @@
+use crate::common::generation_error::GenerationError;
+use crate::common::generator_context::GeneratorContext;
+use crate::common::procedure_generator::{DefaultProcedureGenerator, ProcedureGenerator};
+use crate::common::sql_writer::SqlWriter;
+use crate::mysql::mysql_util::output_stored_program;
+use crate::sql_println;
+use schema_model::model::procedure::Procedure;
+
+pub struct MySqlProcedureGenerator {
+    procedure_generator: DefaultProcedureGenerator,
+}
+
+impl MySqlProcedureGenerator {
+    pub fn new(context: GeneratorContext) -> Self {
+        Self {
+            procedure_generator: DefaultProcedureGenerator::new(context),
+        }
+    }
+}
+
+impl ProcedureGenerator for MySqlProcedureGenerator {
+    fn output_procedures(&self) -> Result<(), GenerationError> {
+        self.procedure_generator.output_procedures_via(self)
+    }
+
+    fn output_procedure(&self, writer: &mut SqlWriter, statement_separator: &str, procedure: &Procedure) -> Result<(), GenerationError> {
+        let settings = self.procedure_generator.context().settings();
+        let procedure_name = settings.qualified_generated_name(procedure.schema_name(), procedure.name());
+
+        sql_println!(writer, "drop procedure if exists {}{}", procedure_name, statement_separator)?;
+        output_stored_program(writer, procedure.sql())?;
+        writer.newline()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::test_support::make_context;
+    use schema_model::builder::SchemaBuilder;
+    use schema_model::model::database_model::DatabaseModel;
+    use schema_model::model::types::{BooleanMode, DatabaseType, ForeignKeyMode};
+
+    #[test]
+    fn output_procedures_only_writes_mysql_procedures() {
+        let schema = SchemaBuilder::new(Some("sales"))
+            .add_procedures(vec![
+                Procedure::new(Some("sales"), "archive", DatabaseType::MySql, "create procedure sales.archive()\nbegin\n   delete from orders;\nend"),
+                Procedure::new(Some("sales"), "archive", DatabaseType::SqlServer, "create procedure sales.archive as delete from orders"),
+            ])
+            .build();
+        let model = DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema]);
+        let (ctx, buffer) = make_context(model, DatabaseType::MySql);
+
+        MySqlProcedureGenerator::new(ctx).output_procedures().unwrap();
+
+        let output = buffer.contents();
+        assert!(output.starts_with("drop procedure if exists sales.archive;\ndelimiter //\ncreate procedure sales.archive()"));
+        assert!(output.contains("end//\ndelimiter ;\n"));
+        assert!(!output.contains(" as delete"));
+    }
+}
@@ -0,0 +1,110 @@
+use crate::common::generated_statement::StatementKind;
+use crate::common::generation_error::GenerationError;
+use crate::common::generator_context::GeneratorContext;
+use crate::common::sequence_generator::SequenceGenerator;
+use crate::sql_println;
+
+/// Sequences are MariaDB's (10.3 and later), which takes Postgres's options; MySQL itself
+/// has no sequences, so a model with any is only generated for MariaDB.
+pub struct MySqlSequenceGenerator {
+    context: GeneratorContext,
+}
+
+impl MySqlSequenceGenerator {
+    pub fn new(context: GeneratorContext) -> Self {
+        Self { context }
+    }
+}
+
+impl SequenceGenerator for MySqlSequenceGenerator {
+    fn output_sequences(&self) -> Result<(), GenerationError> {
+        let database_model = self.context.settings().database_model();
+        let database_type = self.context.settings().database_type();
+        let separator = self.context.settings().statement_separator();
+        let idempotent = self.context.settings().idempotent();
+        let if_not_exists = if idempotent { "if not exists " } else { "" };
+
+        self.context.with_writer(|writer| {
+            for sequence in database_model.schemas().iter().flat_map(|schema| schema.sequences()) {
+                let sequence_name = self.context.settings().qualified_name(sequence.schema_name(), sequence.name());
+                if !self.context.settings().target_mariadb() {
+                    return Err(GenerationError::unsupported(
+                        format!("sequence {}", sequence_name),
+                        "MySQL has no sequences; generate for MariaDB, or make the columns drawing from it auto-increment",
+                    ));
+                }
+                writer.begin_object(StatementKind::Sequence, Some(&sequence.fully_qualified_sequence_name(database_type)));
+                if !idempotent {
+                    sql_println!(writer, "drop sequence if exists {}{}", sequence_name, separator)?;
+                }
+                sql_println!(writer, "create sequence {}{}{}{}", if_not_exists, sequence_name, sequence.options_sql(), separator)?;
+                sql_println!(writer, "")?;
+            }
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::generate_options::GenerateOptions;
+    use crate::common::print_writer::PrintWriter;
+    use crate::common::sql_generator_settings::SqlGeneratorSettings;
+    use crate::common::sql_writer::SqlWriter;
+    use crate::common::table_generator::TableGenerator;
+    use crate::common::test_support::SharedBuffer;
+    use crate::mysql::mysql_table_generator::MySqlTableGenerator;
+    use schema_model::builder::{ColumnBuilder, SchemaBuilder, SequenceBuilder, TableBuilder};
+    use schema_model::model::column_type::ColumnType;
+    use schema_model::model::database_model::DatabaseModel;
+    use schema_model::model::table::Table;
+    use schema_model::model::types::{BooleanMode, DatabaseType, ForeignKeyMode};
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use std::sync::Arc;
+
+    fn invoice_table() -> Table {
+        TableBuilder::new(None::<&str>, "invoice")
+            .add_column(
+                ColumnBuilder::new(None::<&str>, "invoice_no", ColumnType::Long)
+                    .required(true)
+                    .sequence(Some("document_no".to_string()))
+                    .build(),
+            )
+            .build()
+    }
+
+    fn make_context(target_mariadb: bool) -> (GeneratorContext, SharedBuffer) {
+        let sequence = SequenceBuilder::new(None, "document_no").start(Some(1000)).increment(Some(10)).build();
+        let schema = SchemaBuilder::new(None::<&str>).add_sequence(sequence).add_table(invoice_table()).build();
+        let model = DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema]);
+        let options = GenerateOptions { target_mariadb, ..GenerateOptions::new(Arc::new(model)) };
+        let buffer = SharedBuffer::new();
+        let writer = SqlWriter::new(Rc::new(RefCell::new(PrintWriter::new_auto_flush(Box::new(buffer.clone())))));
+        (GeneratorContext::new(SqlGeneratorSettings::new(DatabaseType::MySql, &options), writer), buffer)
+    }
+
+    #[test]
+    fn sequences_are_created_and_drawn_from_with_nextval() {
+        let (ctx, buffer) = make_context(true);
+
+        MySqlSequenceGenerator::new(ctx.clone()).output_sequences().unwrap();
+        MySqlTableGenerator::new(ctx).output_table_definition(&invoice_table()).unwrap();
+
+        let output = buffer.contents();
+        assert!(output.starts_with("drop sequence if exists document_no;\ncreate sequence document_no start with 1000 increment by 10;\n"));
+        assert!(output.contains("invoice_no bigint not null default nextval(document_no)"));
+    }
+
+    #[test]
+    fn sequences_are_rejected_unless_generating_for_mariadb() {
+        let (ctx, buffer) = make_context(false);
+
+        let error = MySqlSequenceGenerator::new(ctx).output_sequences().unwrap_err();
+
+        assert_eq!(error.object(), Some("sequence document_no"));
+        assert!(error.to_string().contains("MySQL has no sequences"));
+        assert!(buffer.contents().is_empty());
+    }
+}
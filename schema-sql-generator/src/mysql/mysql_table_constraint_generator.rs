@@ -0,0 +1,23 @@
+use crate::common::generation_error::GenerationError;
+use crate::common::generator_context::GeneratorContext;
+use crate::common::table_constraint_generator::{DefaultTableConstraintGenerator, TableConstraintGenerator};
+use schema_model::model::table::Table;
+
+pub struct MySqlTableConstraintGenerator {
+    table_constraint_generator: DefaultTableConstraintGenerator,
+}
+
+impl MySqlTableConstraintGenerator {
+    pub fn new(context: GeneratorContext) -> Self {
+        Self {
+            table_constraint_generator: DefaultTableConstraintGenerator::new(context),
+        }
+    }
+}
+
+impl TableConstraintGenerator for MySqlTableConstraintGenerator {
+    fn table_check_constraints(&self, table: &Table) -> Result<Vec<String>, GenerationError> {
+        self.table_constraint_generator.table_check_constraints(table)
+    }
+}
+
@@ -0,0 +1,121 @@
+use crate::common::generation_error::GenerationError;
+use crate::common::generator_context::GeneratorContext;
+use crate::common::sql_string::escape_mysql_literal;
+use crate::common::table_generator::{output_table_steps, DefaultTableGenerator, TableGenerator};
+use crate::mysql::mysql_column_constraint_generator::MySqlColumnConstraintGenerator;
+use crate::mysql::mysql_column_generator::MySqlColumnGenerator;
+use crate::mysql::mysql_index_generator::MySqlIndexGenerator;
+use crate::mysql::mysql_key_generator::MySqlKeyGenerator;
+use crate::mysql::mysql_table_constraint_generator::MySqlTableConstraintGenerator;
+use crate::{sql_newline, sql_println};
+use schema_model::model::table::Table;
+
+pub struct MySqlTableGenerator {
+    context: GeneratorContext,
+    table_generator: DefaultTableGenerator,
+}
+
+impl MySqlTableGenerator {
+    pub fn new(context: GeneratorContext) -> Self {
+        Self {
+            context: context.clone(),
+            table_generator: DefaultTableGenerator::new(
+                context.clone(),
+                Box::new(MySqlColumnGenerator::new(context.clone())),
+                Box::new(MySqlKeyGenerator::new(context.clone())),
+                Box::new(MySqlColumnConstraintGenerator::new(context.clone())),
+                Box::new(MySqlTableConstraintGenerator::new(context.clone())),
+                Box::new(MySqlIndexGenerator::new(context.clone())),
+            ),
+        }
+    }
+}
+
+impl TableGenerator for MySqlTableGenerator {
+    fn output_tables(&self) -> Result<(), GenerationError> {
+        for schema in self.context.settings().database_model().schemas() {
+            for table in schema.tables() {
+                self.output_table(table)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn output_table(&self, table: &Table) -> Result<(), GenerationError> {
+        output_table_steps(self, table)
+    }
+
+    fn output_table_header(&self, table: &Table) -> Result<(), GenerationError> {
+        self.table_generator.output_table_header(table)
+    }
+
+    fn output_table_definition(&self, table: &Table) -> Result<(), GenerationError> {
+        self.table_generator.output_table_definition(table)
+    }
+
+    /// Every table is InnoDB, the only engine that enforces foreign keys, in full UTF-8.
+    fn output_table_footer(&self, table: &Table) -> Result<(), GenerationError> {
+        let comment = table
+            .description()
+            .map(|description| format!(" comment='{}'", escape_mysql_literal(description)))
+            .unwrap_or_default();
+
+        self.context.with_writer(|writer| {
+            sql_println!(writer, ") engine=InnoDB default charset=utf8mb4{}{}", comment, self.context.settings().statement_separator())?;
+            sql_newline!(writer)?;
+            Ok(())
+        })
+    }
+
+    fn output_table_descriptions(&self, _table: &Table) -> Result<(), GenerationError> {
+        // The table's description is in its footer and each column's in its definition.
+        Ok(())
+    }
+
+    fn output_indexes(&self, table: &Table) -> Result<(), GenerationError> {
+        self.table_generator.output_indexes(table)
+    }
+
+    fn output_initial_data(&self, table: &Table) -> Result<(), GenerationError> {
+        self.table_generator.output_initial_data(table)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::test_support::make_context;
+    use schema_model::builder::{ColumnBuilder, SchemaBuilder, TableBuilder};
+    use schema_model::model::column_type::ColumnType;
+    use schema_model::model::database_model::DatabaseModel;
+    use schema_model::model::key::{Key, KeyColumn};
+    use schema_model::model::types::{BooleanMode, DatabaseType, ForeignKeyMode, KeyType};
+
+    #[test]
+    fn output_table_renders_an_innodb_table_with_its_comment() {
+        let table = TableBuilder::new(None::<&str>, "order")
+            .description(Some("Customer's orders".to_string()))
+            .add_column(ColumnBuilder::new(None::<&str>, "id", ColumnType::LongSequence).required(true).build())
+            .add_column(ColumnBuilder::new(None::<&str>, "note", ColumnType::Varchar).length(200).build())
+            .add_key(Key::new(KeyType::Primary, vec![KeyColumn::new("id")]))
+            .build();
+        let schema = SchemaBuilder::new(None::<&str>).add_table(table.clone()).build();
+        let model = DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema]);
+        let (ctx, buffer) = make_context(model, DatabaseType::MySql);
+
+        MySqlTableGenerator::new(ctx).output_table(&table).unwrap();
+
+        assert_eq!(
+            buffer.contents(),
+            "drop table if exists `order`;\n\
+             /* `order` */\n\
+             create table `order`\n\
+             (\n\
+             \x20  id bigint auto_increment not null,\n\
+             \x20  note varchar(200),\n\
+             \x20  constraint pk_order primary key (id)\n\
+             ) engine=InnoDB default charset=utf8mb4 comment='Customer''s orders';\n\n"
+        );
+    }
+}
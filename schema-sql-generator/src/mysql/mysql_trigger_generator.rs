@@ -0,0 +1,428 @@
+use crate::common::aggregation_support::{aggregation_group_value, aggregation_row_value};
+use crate::common::generated_statement::StatementKind;
+use crate::common::generation_error::{resolve_table, GenerationError};
+use crate::common::generator_context::GeneratorContext;
+use crate::common::sql_string::escape_mysql_literal;
+use crate::common::trigger_generator::TriggerGenerator;
+use crate::common::trigger_support::{child_match_condition, outputs_delete_trigger, parent_match_condition, should_output_update_trigger};
+use crate::mysql::mysql_util::output_stored_program;
+use crate::sql_println;
+use schema_model::model::aggregation::AggregationFrequency;
+use schema_model::model::database_model::DatabaseModel;
+use schema_model::model::table::Table;
+use schema_model::model::types::{DatabaseType, ForeignKeyMode, RelationType, TriggerType};
+
+/// A MySQL trigger fires for a single event, so where Postgres has one `{table}_update`
+/// trigger for inserts and updates, this generator creates `{table}_insert` and
+/// `{table}_update` with the same checks.
+pub struct MySqlTriggerGenerator {
+    context: GeneratorContext,
+}
+
+impl MySqlTriggerGenerator {
+    pub fn new(context: GeneratorContext) -> Self {
+        Self { context }
+    }
+}
+
+impl TriggerGenerator for MySqlTriggerGenerator {
+    fn output_triggers(&self) -> Result<(), GenerationError> {
+        for table in self.context.settings().database_model().all_tables() {
+            self.output_table_triggers(table)?;
+        }
+
+        Ok(())
+    }
+
+    fn output_table_triggers(&self, table: &Table) -> Result<(), GenerationError> {
+        if outputs_delete_trigger(self.context.settings(), table) {
+            let body = self.delete_trigger_body(table)?;
+            self.output_trigger(table, "delete", &body)?;
+        }
+
+        if should_output_update_trigger(self.context.settings(), table) {
+            let body = self.update_trigger_body(table, false)?;
+            self.output_trigger(table, "insert", &body)?;
+            let body = self.update_trigger_body(table, true)?;
+            self.output_trigger(table, "update", &body)?;
+        }
+
+        Ok(())
+    }
+
+    fn trigger_names(&self, table: &Table) -> Vec<String> {
+        let table_name = self.context.settings().generated_name_part(table.name());
+        let mut names = Vec::new();
+        if outputs_delete_trigger(self.context.settings(), table) {
+            names.push(format!("{}_delete", table_name));
+        }
+        if should_output_update_trigger(self.context.settings(), table) {
+            names.push(format!("{}_insert", table_name));
+            names.push(format!("{}_update", table_name));
+        }
+        names
+    }
+
+    fn possible_trigger_names(&self, table_name: &str) -> Vec<String> {
+        let table_name = self.context.settings().generated_name_part(table_name);
+        ["delete", "insert", "update"].iter().map(|event| format!("{}_{}", table_name, event)).collect()
+    }
+}
+
+impl MySqlTriggerGenerator {
+    /// Writes the trigger firing after `event` on `table`, dropping any earlier version first.
+    fn output_trigger(&self, table: &Table, event: &str, body: &[String]) -> Result<(), GenerationError> {
+        let settings = self.context.settings();
+        let trigger_name = format!("{}_{}", settings.generated_name_part(table.name()), event);
+        let quoted_trigger = settings.qualified_generated_name(table.schema_name(), &trigger_name);
+        let sql = format!(
+            "create trigger {} after {} on {} for each row\nbegin\n{}\nend",
+            quoted_trigger,
+            event,
+            settings.qualified_name(table.schema_name(), table.name()),
+            body.join("\n")
+        );
+
+        self.context.with_writer(|writer| {
+            writer.begin_object(
+                StatementKind::Trigger,
+                Some(&settings.database_type().qualified_name(table.schema_name(), &trigger_name)),
+            );
+            sql_println!(writer, "/* {} */", trigger_name)?;
+            sql_println!(writer, "drop trigger if exists {}{}", quoted_trigger, settings.statement_separator())?;
+            output_stored_program(writer, &sql)?;
+            writer.newline()?;
+            Ok(())
+        })
+    }
+
+    fn delete_trigger_body(&self, table: &Table) -> Result<Vec<String>, GenerationError> {
+        let settings = self.context.settings();
+        let database_type = settings.database_type();
+        let object = || format!("trigger {}_delete", table.name());
+        let mut lines = Vec::new();
+
+        if settings.foreign_key_mode() == ForeignKeyMode::Triggers {
+            // A reverse relation keeps the original relation's fields: `from_table_name` is
+            // the child table, `to_table_name` this one.
+            for relation in table.reverse_relations() {
+                if relation.relation_type() == RelationType::DoNothing {
+                    continue;
+                }
+                let child_table = resolve_table(self.database_model(), relation.from_table_name(), object)?;
+                let child_name = settings.qualified_name(child_table.schema_name(), child_table.name());
+                let condition = child_match_condition(settings, relation, "old");
+
+                match relation.relation_type() {
+                    RelationType::Enforce => {
+                        lines.push(format!("   if (select count(*) from {} where {}) > 0 then", child_name, condition));
+                        lines.push(format!(
+                            "      signal sqlstate '45000' set message_text = 'The row in {} cannot be deleted. It is being used by a row in the {} table.';",
+                            escape_mysql_literal(&table.fully_qualified_table_name(database_type)),
+                            escape_mysql_literal(&child_table.fully_qualified_table_name(database_type))
+                        ));
+                        lines.push("   end if;".to_string());
+                    }
+                    RelationType::SetNull => {
+                        let assignments = relation
+                            .from_column_names()
+                            .iter()
+                            .map(|c| format!("{} = null", settings.quote(c)))
+                            .collect::<Vec<_>>()
+                            .join(", ");
+                        lines.push(format!("   update {} set {} where {};", child_name, assignments, condition));
+                    }
+                    RelationType::Cascade => {
+                        lines.push(format!("   delete from {} where {};", child_name, condition));
+                    }
+                    RelationType::DoNothing => {}
+                }
+            }
+        }
+
+        self.aggregation_lines(&mut lines, table, "old", false)?;
+        self.custom_trigger_lines(&mut lines, table, TriggerType::Delete);
+        Ok(lines)
+    }
+
+    /// The body of the insert trigger, or with `is_update` of the update trigger, which also
+    /// has to take the old row out of its aggregation buckets.
+    fn update_trigger_body(&self, table: &Table, is_update: bool) -> Result<Vec<String>, GenerationError> {
+        let settings = self.context.settings();
+        let database_type = settings.database_type();
+        let object = || format!("trigger {}_{}", table.name(), if is_update { "update" } else { "insert" });
+        let mut lines = Vec::new();
+
+        if settings.foreign_key_mode() == ForeignKeyMode::Triggers {
+            for relation in table.relations() {
+                if relation.relation_type() == RelationType::DoNothing {
+                    continue;
+                }
+                let to_table = resolve_table(self.database_model(), relation.to_table_name(), object)?;
+                lines.push(format!(
+                    "   if {} then",
+                    relation
+                        .from_column_names()
+                        .iter()
+                        .map(|c| format!("new.{} is not null", settings.quote(c)))
+                        .collect::<Vec<_>>()
+                        .join(" and ")
+                ));
+                lines.push(format!(
+                    "      if (select count(*) from {} where {}) = 0 then",
+                    settings.qualified_name(to_table.schema_name(), to_table.name()),
+                    parent_match_condition(settings, relation, "new")
+                ));
+                lines.push(format!(
+                    "         signal sqlstate '45000' set message_text = 'The value of {} was not found in the {} table.';",
+                    escape_mysql_literal(&relation.from_columns_as_string()),
+                    escape_mysql_literal(&to_table.fully_qualified_table_name(database_type))
+                ));
+                lines.push("      end if;".to_string());
+                lines.push("   end if;".to_string());
+            }
+        }
+
+        if is_update {
+            self.aggregation_lines(&mut lines, table, "old", false)?;
+        }
+        self.aggregation_lines(&mut lines, table, "new", true)?;
+        self.custom_trigger_lines(&mut lines, table, TriggerType::Update);
+        Ok(lines)
+    }
+
+    /// Adds (`add`) or removes the contribution of the `row` record (`new`/`old`) to the
+    /// summary table of every aggregation on `table`, creating a missing bucket when adding.
+    fn aggregation_lines(&self, lines: &mut Vec<String>, table: &Table, row: &str, add: bool) -> Result<(), GenerationError> {
+        let settings = self.context.settings();
+        let operator = if add { "+" } else { "-" };
+
+        for aggregation in table.aggregations() {
+            let object = || format!("aggregation on {}", table.name());
+            if let Some(criteria) = aggregation.criteria() {
+                // MySQL can't select from a trigger's row record, which is how the other
+                // dialects let the criteria refer to the table's columns.
+                return Err(GenerationError::unsupported(
+                    object(),
+                    format!("MySQL triggers can't apply the criteria '{}' to the row", criteria),
+                ));
+            }
+
+            let destination = resolve_table(self.database_model(), aggregation.destination_table(), object)?;
+            let destination_table = settings.qualified_name(destination.schema_name(), destination.name());
+            let date_column = settings.quote(aggregation.date_column());
+            let time_stamp_column = settings.quote(aggregation.time_stamp_column());
+            let bucket = mysql_date_bucket(aggregation.aggregation_frequency(), &format!("{}.{}", row, date_column));
+
+            let group_values: Vec<String> = aggregation
+                .aggregation_groups()
+                .iter()
+                .map(|group| aggregation_group_value(settings, table, group, row))
+                .collect::<Result<_, _>>()?;
+
+            let mut assignments: Vec<String> = aggregation
+                .aggregation_columns()
+                .iter()
+                .map(|column| {
+                    let destination_column = settings.quote(column.destination_column());
+                    format!(
+                        "{} = {} {} {}",
+                        destination_column,
+                        destination_column,
+                        operator,
+                        aggregation_row_value(settings, column, row)
+                    )
+                })
+                .collect();
+            assignments.push(format!("{} = current_timestamp", time_stamp_column));
+
+            // `<=>` is MySQL's null-safe equality, so a null group value finds its bucket.
+            let mut matches = vec![format!("{} = {}", date_column, bucket)];
+            for (group, value) in aggregation.aggregation_groups().iter().zip(group_values.iter()) {
+                matches.push(format!("{} <=> {}", settings.quote(group.destination()), value));
+            }
+            let update = format!("update {} set {} where {};", destination_table, assignments.join(", "), matches.join(" and "));
+
+            lines.push(format!("   if {}.{} is not null then", row, date_column));
+            if add {
+                let mut columns = vec![date_column.clone()];
+                let mut values = vec![bucket.clone()];
+                for (group, value) in aggregation.aggregation_groups().iter().zip(group_values.iter()) {
+                    columns.push(settings.quote(group.destination()));
+                    values.push(value.clone());
+                }
+                for column in aggregation.aggregation_columns() {
+                    columns.push(settings.quote(column.destination_column()));
+                    values.push(aggregation_row_value(settings, column, row));
+                }
+                columns.push(time_stamp_column.clone());
+                values.push("current_timestamp".to_string());
+
+                lines.push(format!("      if (select count(*) from {} where {}) = 0 then", destination_table, matches.join(" and ")));
+                lines.push(format!("         insert into {} ({})", destination_table, columns.join(", ")));
+                lines.push(format!("            values ({});", values.join(", ")));
+                lines.push("      else".to_string());
+                lines.push(format!("         {}", update));
+                lines.push("      end if;".to_string());
+            } else {
+                lines.push(format!("      {}", update));
+            }
+            lines.push("   end if;".to_string());
+        }
+
+        Ok(())
+    }
+
+    fn custom_trigger_lines(&self, lines: &mut Vec<String>, table: &Table, trigger_type: TriggerType) {
+        for custom_trigger in table.triggers() {
+            if custom_trigger.trigger_type() == trigger_type && custom_trigger.database_type() == DatabaseType::MySql {
+                lines.push(custom_trigger.trigger_text().to_string());
+            }
+        }
+    }
+
+    fn database_model(&self) -> &DatabaseModel {
+        self.context.settings().database_model()
+    }
+}
+
+/// The first day of the bucket `value` falls into (weeks start on Monday).
+fn mysql_date_bucket(frequency: AggregationFrequency, value: &str) -> String {
+    match frequency {
+        AggregationFrequency::Daily => format!("date({})", value),
+        AggregationFrequency::Weekly => format!("date({}) - interval weekday({}) day", value, value),
+        AggregationFrequency::Monthly => format!("date({}) - interval (dayofmonth({}) - 1) day", value, value),
+        AggregationFrequency::Yearly => format!("makedate(year({}), 1)", value),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::test_support::make_context_with_fk_mode;
+    use schema_model::builder::{ColumnBuilder, KeyBuilder, SchemaBuilder, TableBuilder};
+    use schema_model::model::aggregation::{Aggregation, AggregationColumn, AggregationGroup, AggregationType};
+    use schema_model::model::column_type::ColumnType;
+    use schema_model::model::relation::Relation;
+    use schema_model::model::types::{BooleanMode, KeyType};
+
+    fn build_model_with_reverse_relation(relation_type: RelationType) -> DatabaseModel {
+        let mut parent = TableBuilder::new(None::<&str>, "parent")
+            .add_column(ColumnBuilder::new(None::<&str>, "id", ColumnType::Sequence).required(true).build())
+            .add_key(KeyBuilder::new(KeyType::Primary).add_column("id").build())
+            .build();
+        parent.add_reverse_relation(Relation::new("parent", "id", "child", "parent_id", relation_type, false));
+        let child = TableBuilder::new(None::<&str>, "child")
+            .add_column(ColumnBuilder::new(None::<&str>, "parent_id", ColumnType::Int).build())
+            .add_relation(Relation::new("parent", "id", "child", "parent_id", relation_type, false))
+            .build();
+        let schema = SchemaBuilder::new(None::<&str>).add_table(parent).add_table(child).build();
+        DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema])
+    }
+
+    #[test]
+    fn output_triggers_enforce_relations_with_signals() {
+        let model = build_model_with_reverse_relation(RelationType::Enforce);
+        let (ctx, buffer) = make_context_with_fk_mode(model, DatabaseType::MySql, ForeignKeyMode::Triggers);
+
+        MySqlTriggerGenerator::new(ctx).output_triggers().unwrap();
+
+        let output = buffer.contents();
+        assert!(output.contains(
+            "drop trigger if exists parent_delete;\n\
+             delimiter //\n\
+             create trigger parent_delete after delete on parent for each row\n\
+             begin\n\
+             \x20  if (select count(*) from child where parent_id = old.id) > 0 then\n\
+             \x20     signal sqlstate '45000' set message_text = 'The row in parent cannot be deleted. It is being used by a row in the child table.';\n\
+             \x20  end if;\n\
+             end//\n\
+             delimiter ;\n"
+        ));
+        assert!(output.contains("create trigger child_insert after insert on child for each row"));
+        assert!(output.contains("create trigger child_update after update on child for each row"));
+        assert!(output.contains("      if (select count(*) from parent where id = new.parent_id) = 0 then"));
+    }
+
+    #[test]
+    fn output_triggers_cascade_and_set_null_act_on_the_child_table() {
+        let (ctx, buffer) = make_context_with_fk_mode(
+            build_model_with_reverse_relation(RelationType::Cascade),
+            DatabaseType::MySql,
+            ForeignKeyMode::Triggers,
+        );
+        MySqlTriggerGenerator::new(ctx).output_triggers().unwrap();
+        assert!(buffer.contents().contains("   delete from child where parent_id = old.id;"));
+
+        let (ctx, buffer) = make_context_with_fk_mode(
+            build_model_with_reverse_relation(RelationType::SetNull),
+            DatabaseType::MySql,
+            ForeignKeyMode::Triggers,
+        );
+        MySqlTriggerGenerator::new(ctx).output_triggers().unwrap();
+        assert!(buffer.contents().contains("   update child set parent_id = null where parent_id = old.id;"));
+    }
+
+    #[test]
+    fn output_triggers_does_nothing_when_relations_mode_is_not_triggers() {
+        let model = build_model_with_reverse_relation(RelationType::Enforce);
+        let (ctx, buffer) = make_context_with_fk_mode(model, DatabaseType::MySql, ForeignKeyMode::Relations);
+
+        MySqlTriggerGenerator::new(ctx).output_triggers().unwrap();
+
+        assert_eq!(buffer.contents(), "");
+    }
+
+    fn build_model_with_aggregation(criteria: Option<&str>) -> DatabaseModel {
+        let summary = TableBuilder::new(None::<&str>, "sale_summary")
+            .add_column(ColumnBuilder::new(None::<&str>, "sale_date", ColumnType::Date).required(true).build())
+            .add_column(ColumnBuilder::new(None::<&str>, "store_id", ColumnType::Int).build())
+            .add_column(ColumnBuilder::new(None::<&str>, "total", ColumnType::Decimal).build())
+            .add_column(ColumnBuilder::new(None::<&str>, "updated_at", ColumnType::Timestamp).build())
+            .build();
+        let sale = TableBuilder::new(None::<&str>, "sale")
+            .add_column(ColumnBuilder::new(None::<&str>, "sale_date", ColumnType::Date).required(true).build())
+            .add_column(ColumnBuilder::new(None::<&str>, "store_id", ColumnType::Int).build())
+            .add_column(ColumnBuilder::new(None::<&str>, "amount", ColumnType::Decimal).build())
+            .add_aggregation(Aggregation::new(
+                "sale_summary",
+                "sale_date",
+                criteria,
+                "updated_at",
+                AggregationFrequency::Monthly,
+                vec![AggregationColumn::new(AggregationType::Sum, "amount", "total")],
+                vec![AggregationGroup::new("store_id", "store_id", None)],
+            ))
+            .build();
+        let schema = SchemaBuilder::new(None::<&str>).add_table(summary).add_table(sale).build();
+        DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema])
+    }
+
+    #[test]
+    fn output_triggers_maintain_the_aggregation_summary_table() {
+        let (ctx, buffer) = make_context_with_fk_mode(build_model_with_aggregation(None), DatabaseType::MySql, ForeignKeyMode::Relations);
+
+        MySqlTriggerGenerator::new(ctx).output_triggers().unwrap();
+
+        let output = buffer.contents();
+        let bucket = "date(old.sale_date) - interval (dayofmonth(old.sale_date) - 1) day";
+        assert!(output.contains(&format!(
+            "      update sale_summary set total = total - coalesce(old.amount, 0), updated_at = current_timestamp where sale_date = {} and store_id <=> old.store_id;",
+            bucket
+        )));
+        assert!(output.contains("         insert into sale_summary (sale_date, store_id, total, updated_at)"));
+        assert!(output.contains("create trigger sale_update after update on sale for each row"));
+        // only the update trigger takes the old row out of its bucket
+        let insert_trigger = &output[output.find("sale_insert after").unwrap()..output.find("sale_update after").unwrap()];
+        assert!(!insert_trigger.contains("old."));
+    }
+
+    #[test]
+    fn output_triggers_reject_aggregation_criteria() {
+        let (ctx, _) = make_context_with_fk_mode(build_model_with_aggregation(Some("amount > 0")), DatabaseType::MySql, ForeignKeyMode::Relations);
+
+        let error = MySqlTriggerGenerator::new(ctx).output_triggers().unwrap_err();
+
+        assert!(error.to_string().contains("amount > 0"));
+    }
+}
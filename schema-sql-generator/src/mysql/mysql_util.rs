@@ -0,0 +1,15 @@
+use crate::common::generation_error::GenerationError;
+use crate::common::sql_writer::SqlWriter;
+use crate::sql_println;
+
+/// Ends a trigger, function or procedure. The `;`s inside its body would otherwise end it
+/// early, so the `mysql` client is switched to this delimiter for the program.
+pub const PROGRAM_DELIMITER: &str = "//";
+
+/// Writes the `create` statement of a stored program between `delimiter` commands.
+pub fn output_stored_program(writer: &mut SqlWriter, sql: &str) -> Result<(), GenerationError> {
+    sql_println!(writer, "delimiter {}", PROGRAM_DELIMITER)?;
+    sql_println!(writer, "{}{}", sql.trim_end().trim_end_matches(';'), PROGRAM_DELIMITER)?;
+    sql_println!(writer, "delimiter ;")?;
+    Ok(())
+}
@@ -4,8 +4,10 @@ mod postgres_relation_generator;
 mod postgres_index_generator;
 mod postgres_function_generator;
 mod postgres_view_generator;
+mod postgres_sequence_generator;
 mod postgres_procedure_generator;
-mod postgres_trigger_generator;
+pub mod postgres_trigger_generator;
+mod postgres_drop_generator;
 mod postgres_other_sql_generator;
 mod postgres_column_generator;
 mod postgres_column_type_generator;
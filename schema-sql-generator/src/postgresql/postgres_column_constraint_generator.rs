@@ -1,4 +1,5 @@
 use crate::common::column_constraint_generator::{ColumnConstraintGenerator, DefaultColumnConstraintGenerator};
+use crate::common::generation_error::GenerationError;
 use crate::common::generator_context::GeneratorContext;
 use schema_model::model::column_type::ColumnType;
 use schema_model::model::table::Table;
@@ -16,7 +17,7 @@ impl PostgresColumnConstraintGenerator {
 }
 
 impl ColumnConstraintGenerator for PostgresColumnConstraintGenerator {
-    fn column_check_constraints(&self, table: &Table) -> Vec<String> {
+    fn column_check_constraints(&self, table: &Table) -> Result<Vec<String>, GenerationError> {
         let boolean_mode = self.column_constraint_generator.context().settings().boolean_mode();
         table.columns_with_check_constraints(boolean_mode)
             .iter()
@@ -49,7 +50,7 @@ mod tests {
         let (ctx, _buffer) = make_context(model, DatabaseType::Postgresql);
 
         let generator = PostgresColumnConstraintGenerator::new(ctx);
-        let constraints = generator.column_check_constraints(&table);
+        let constraints = generator.column_check_constraints(&table).unwrap();
 
         assert_eq!(constraints.len(), 1);
         assert!(constraints[0].contains("check(price >= 0 and price <= 100)"));
@@ -71,6 +72,6 @@ mod tests {
         let (ctx, _buffer) = make_context(model, DatabaseType::Postgresql);
 
         let generator = PostgresColumnConstraintGenerator::new(ctx);
-        assert!(generator.column_check_constraints(&table).is_empty());
+        assert!(generator.column_check_constraints(&table).unwrap().is_empty());
     }
 }
@@ -1,11 +1,15 @@
 use crate::common::column_generator::{ColumnGenerator, DefaultColumnGenerator, DefaultConstraintNaming};
+use crate::common::column_type_generator::ColumnTypeGenerator;
+use crate::common::generation_error::GenerationError;
 use crate::common::generator_context::GeneratorContext;
 use crate::postgresql::postgres_column_type_generator::PostgresColumnTypeGenerator;
 use schema_model::model::column::Column;
+use schema_model::model::column_type::ColumnType;
 use schema_model::model::table::Table;
 
 pub struct PostgresColumnGenerator {
     column_generator: DefaultColumnGenerator,
+    column_type_generator: PostgresColumnTypeGenerator,
 }
 
 impl PostgresColumnGenerator {
@@ -16,24 +20,111 @@ impl PostgresColumnGenerator {
                 Box::new(PostgresColumnTypeGenerator::new(context.clone())),
                 DefaultConstraintNaming::Unnamed,
             ),
+            column_type_generator: PostgresColumnTypeGenerator::new(context.clone()),
         }
     }
 }
 
 impl ColumnGenerator for PostgresColumnGenerator {
-    fn column_definitions(&self, table: &Table) -> Vec<String> {
-        self.column_generator.column_definitions(table)
+    fn column_definitions(&self, table: &Table) -> Result<Vec<String>, GenerationError> {
+        table
+            .columns()
+            .iter()
+            .map(|column| self.column_sql(table, column))
+            .collect()
     }
 
-    fn column_sql(&self, table: &Table, column: &Column) -> String {
-        self.column_generator.column_sql(table, column)
+    /// A compressed table stores its TOASTable columns with lz4 rather than pglz; the
+    /// `compression` clause only exists from Postgres 14, so older targets keep the default.
+    fn column_sql(&self, table: &Table, column: &Column) -> Result<String, GenerationError> {
+        if table.compression().is_none()
+            || !is_toastable(column.column_type())
+            || self.column_generator.context().settings().target_postgres_version() < 14
+        {
+            return self.column_generator.column_sql(table, column);
+        }
+
+        let column_options = self.column_options(table, column)?;
+        let column_type = self.column_type_generator.column_type_sql(table, column)?;
+        let column_name = self.column_generator.context().settings().quote(column.name());
+
+        if column_options.is_empty() {
+            return Ok(format!("   {} {} compression lz4", column_name, column_type));
+        }
+
+        Ok(format!("   {} {} compression lz4 {}", column_name, column_type, column_options))
     }
 
-    fn column_options(&self, table: &Table, column: &Column) -> String {
+    fn column_options(&self, table: &Table, column: &Column) -> Result<String, GenerationError> {
         self.column_generator.column_options(table, column)
     }
 
-    fn default_value(&self, table: &Table, column: &Column) -> Option<String> {
+    fn default_value(&self, table: &Table, column: &Column) -> Result<Option<String>, GenerationError> {
         self.column_generator.default_value(table, column)
     }
 }
+
+fn is_toastable(column_type: ColumnType) -> bool {
+    matches!(
+        column_type,
+        ColumnType::Char
+            | ColumnType::Varchar
+            | ColumnType::Text
+            | ColumnType::CiText
+            | ColumnType::CsText
+            | ColumnType::Binary
+            | ColumnType::Json
+            | ColumnType::Array
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::generate_options::GenerateOptions;
+    use crate::common::print_writer::PrintWriter;
+    use crate::common::sql_generator_settings::SqlGeneratorSettings;
+    use crate::common::sql_writer::SqlWriter;
+    use schema_model::builder::{ColumnBuilder, SchemaBuilder, TableBuilder};
+    use schema_model::model::database_model::DatabaseModel;
+    use schema_model::model::types::{BooleanMode, CompressionLevel, DatabaseType, ForeignKeyMode, TableOption};
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use std::sync::Arc;
+
+    fn make_generator(target_postgres_version: u32) -> PostgresColumnGenerator {
+        let schema = SchemaBuilder::new(None::<&str>).build();
+        let model = DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema]);
+        let mut options = GenerateOptions::new(Arc::new(model));
+        options.target_postgres_version = target_postgres_version;
+        let settings = SqlGeneratorSettings::new(DatabaseType::Postgresql, &options);
+        let writer = SqlWriter::new(Rc::new(RefCell::new(PrintWriter::new(Box::new(Vec::<u8>::new())))));
+        PostgresColumnGenerator::new(GeneratorContext::new(settings, writer))
+    }
+
+    fn compressed_table() -> Table {
+        TableBuilder::new(None::<&str>, "documents")
+            .add_column(ColumnBuilder::new(None::<&str>, "id", ColumnType::Int).required(true).build())
+            .add_column(ColumnBuilder::new(None::<&str>, "body", ColumnType::Text).required(true).build())
+            .add_option(TableOption::Compress(CompressionLevel::Page))
+            .build()
+    }
+
+    #[test]
+    fn compressed_table_uses_lz4_for_toastable_columns() {
+        let table = compressed_table();
+
+        let definitions = make_generator(14).column_definitions(&table).unwrap();
+
+        assert_eq!(definitions, vec!["   id integer not null", "   body text compression lz4 not null"]);
+    }
+
+    #[test]
+    fn compression_is_left_out_before_postgres_14() {
+        let table = compressed_table();
+
+        let definitions = make_generator(13).column_definitions(&table).unwrap();
+
+        assert_eq!(definitions, vec!["   id integer not null", "   body text not null"]);
+    }
+}
@@ -1,9 +1,13 @@
 use crate::common::column_type_generator::ColumnTypeGenerator;
+use crate::common::generation_error::GenerationError;
 use crate::common::generator_context::GeneratorContext;
+use crate::common::sql_string::escape_sql_literal;
 use crate::postgresql::postgres_util::to_snake_case;
 use schema_model::model::column::Column;
 use schema_model::model::column_type::ColumnType;
 use schema_model::model::schema::Schema;
+use schema_model::model::sequence::Sequence;
+use schema_model::model::table::Table;
 
 pub struct PostgresColumnTypeGenerator {
     context: GeneratorContext
@@ -15,6 +19,25 @@ impl PostgresColumnTypeGenerator {
             context,
         }
     }
+
+    /// `{base_type} generated ... as identity`, with the column's start and increment, or
+    /// `None` when the column is to be a serial.
+    fn identity_sql(&self, column: &Column, base_type: &str) -> Option<String> {
+        let identity_mode = column.identity_mode().unwrap_or(self.context.settings().identity_mode());
+        let clause = identity_mode.sql()?;
+        let mut options = Vec::new();
+        if let Some(start) = column.identity_start() {
+            options.push(format!("start with {}", start));
+        }
+        if let Some(increment) = column.identity_increment() {
+            options.push(format!("increment by {}", increment));
+        }
+        if options.is_empty() {
+            Some(format!("{} {}", base_type, clause))
+        } else {
+            Some(format!("{} {} ({})", base_type, clause, options.join(" ")))
+        }
+    }
 }
 
 impl ColumnTypeGenerator for PostgresColumnTypeGenerator {
@@ -22,12 +45,12 @@ impl ColumnTypeGenerator for PostgresColumnTypeGenerator {
         &self.context
     }
 
-    fn sequence_sql(&self) -> String {
-        "serial".to_string()
+    fn sequence_sql(&self, column: &Column) -> String {
+        self.identity_sql(column, "integer").unwrap_or_else(|| "serial".to_string())
     }
 
-    fn long_sequence_sql(&self) -> String {
-        "bigserial".to_string()
+    fn long_sequence_sql(&self, column: &Column) -> String {
+        self.identity_sql(column, "bigint").unwrap_or_else(|| "bigserial".to_string())
     }
 
     fn byte_sql(&self) -> String {
@@ -36,8 +59,10 @@ impl ColumnTypeGenerator for PostgresColumnTypeGenerator {
 
     fn text_sql(&self, column: &Column) -> String {
         let database_model = self.context.settings().database_model();
-        let schema = database_model.find_schema(column.schema_name());
-        if !schema.case_sensitive_text() {
+        let case_sensitive = database_model
+            .find_schema_checked(column.schema_name())
+            .is_some_and(|schema| schema.case_sensitive_text());
+        if !case_sensitive {
             return "citext".to_string();
         }
         "text".to_string()
@@ -67,22 +92,22 @@ impl ColumnTypeGenerator for PostgresColumnTypeGenerator {
         }
     }
 
-    fn array_sql(&self, column: &Column) -> String {
-        let element_type_name = column.element_type().unwrap_or_else(|| {
-            panic!(
-                "Array column '{}' is missing an elementType.",
-                column.name()
-            )
-        });
-        let element_type = ColumnType::from_type_name(element_type_name).unwrap_or_else(|e| {
-            panic!(
-                "Array column '{}' has an invalid elementType: {}",
-                column.name(),
-                e
-            )
-        });
+    fn sequence_default_value_sql(&self, sequence: &Sequence) -> Option<String> {
+        Some(format!(
+            "nextval('{}')",
+            escape_sql_literal(&self.context.settings().qualified_name(sequence.schema_name(), sequence.name()))
+        ))
+    }
 
-        match element_type {
+    fn array_sql(&self, table: &Table, column: &Column) -> Result<String, GenerationError> {
+        let object = || format!("column {}.{}", table.name(), column.name());
+        let element_type_name = column
+            .element_type()
+            .ok_or_else(|| GenerationError::unsupported(object(), "an array column needs an elementType"))?;
+        let element_type = ColumnType::from_type_name(element_type_name)
+            .map_err(|e| GenerationError::unsupported(object(), format!("invalid elementType: {}", e)))?;
+
+        Ok(match element_type {
             ColumnType::Byte => self.byte_sql() + "[]",
             ColumnType::Short => self.short_sql() + "[]",
             ColumnType::Int => self.int_sql() + "[]",
@@ -91,8 +116,13 @@ impl ColumnTypeGenerator for PostgresColumnTypeGenerator {
             ColumnType::Char => self.char_sql(column) + "[]",
             ColumnType::Varchar => self.varchar_sql(column) + "[]",
             ColumnType::Text => self.text_sql(column) + "[]",
-            other => panic!("Unsupported array element type: {:?}", other),
-        }
+            other => {
+                return Err(GenerationError::unsupported(
+                    object(),
+                    format!("arrays of {} are not supported", other.name()),
+                ));
+            }
+        })
     }
 
     fn varchar_sql(&self, _column: &Column) -> String {
@@ -107,11 +137,15 @@ impl ColumnTypeGenerator for PostgresColumnTypeGenerator {
         "jsonb".to_string()
     }
 
-    fn enum_sql(&self, column: &Column) -> String {
-        let enum_type_opt = column.enum_type();
-        let enum_type_name = enum_type_opt.as_ref().unwrap();
+    fn enum_sql(&self, table: &Table, column: &Column) -> Result<String, GenerationError> {
+        let enum_type_name = column.enum_type().ok_or_else(|| {
+            GenerationError::unsupported(
+                format!("column {}.{}", table.name(), column.name()),
+                "an enum column needs an enum type",
+            )
+        })?;
 
-        to_snake_case(enum_type_name)
+        Ok(to_snake_case(enum_type_name))
     }
 
     fn native_boolean_sql(&self) -> String {
@@ -129,18 +163,16 @@ mod tests {
     use crate::common::sql_writer::SqlWriter;
     use schema_model::builder::{ColumnBuilder, SchemaBuilder, TableBuilder};
     use schema_model::model::database_model::DatabaseModel;
-    use schema_model::model::types::{BooleanMode, DatabaseType, ForeignKeyMode};
+    use schema_model::model::types::{BooleanMode, DatabaseType, ForeignKeyMode, IdentityMode};
     use std::cell::RefCell;
     use std::rc::Rc;
+    use std::sync::Arc;
 
     fn make_context(model: DatabaseModel) -> (GeneratorContext, TableBuilder) {
         let table = TableBuilder::new(None::<&str>, "test");
-        let options = GenerateOptions::new(
-            Rc::new(model),
-            Rc::new(RefCell::new(PrintWriter::new(Box::new(Vec::<u8>::new())))),
-        );
+        let options = GenerateOptions::new(Arc::new(model));
         let settings = SqlGeneratorSettings::new(DatabaseType::Postgresql, &options);
-        let writer = SqlWriter::new(options.writer.clone());
+        let writer = SqlWriter::new(Rc::new(RefCell::new(PrintWriter::new(Box::new(Vec::<u8>::new())))));
         let ctx = GeneratorContext::new(settings, writer);
         (ctx, table)
     }
@@ -156,7 +188,7 @@ mod tests {
         let generator = PostgresColumnTypeGenerator::new(ctx);
         let table = table_builder.build();
         let col = ColumnBuilder::new(None::<&str>, "col", column_type).build();
-        assert_eq!(generator.column_type_sql(&table, &col), expected);
+        assert_eq!(generator.column_type_sql(&table, &col).unwrap(), expected);
     }
 
     #[test]
@@ -165,6 +197,34 @@ mod tests {
         assert_type(ColumnType::LongSequence, "bigserial");
     }
 
+    #[test]
+    fn identity_columns_follow_the_option_unless_the_column_overrides_it() {
+        let mut options = GenerateOptions::new(Arc::new(make_model_default()));
+        options.identity_mode = IdentityMode::Always;
+        let settings = SqlGeneratorSettings::new(DatabaseType::Postgresql, &options);
+        let ctx = GeneratorContext::new(settings, SqlWriter::new(Rc::new(RefCell::new(PrintWriter::new(Box::new(Vec::<u8>::new()))))));
+        let generator = PostgresColumnTypeGenerator::new(ctx);
+        let table = TableBuilder::new(None::<&str>, "test").build();
+
+        let plain = ColumnBuilder::new(None::<&str>, "id", ColumnType::Sequence).build();
+        assert_eq!(generator.column_type_sql(&table, &plain).unwrap(), "integer generated always as identity");
+
+        let by_default = ColumnBuilder::new(None::<&str>, "id", ColumnType::LongSequence)
+            .identity_mode(Some(IdentityMode::ByDefault))
+            .identity_start(Some(1000))
+            .identity_increment(Some(10))
+            .build();
+        assert_eq!(
+            generator.column_type_sql(&table, &by_default).unwrap(),
+            "bigint generated by default as identity (start with 1000 increment by 10)"
+        );
+
+        let serial = ColumnBuilder::new(None::<&str>, "id", ColumnType::Sequence)
+            .identity_mode(Some(IdentityMode::Serial))
+            .build();
+        assert_eq!(generator.column_type_sql(&table, &serial).unwrap(), "serial");
+    }
+
     #[test]
     fn numeric_types() {
         assert_type(ColumnType::Byte, "smallint");
@@ -204,7 +264,7 @@ mod tests {
         let generator = PostgresColumnTypeGenerator::new(ctx);
         let table = table_builder.build();
         let col = ColumnBuilder::new(None::<&str>, "col", ColumnType::Text).build();
-        assert_eq!(generator.column_type_sql(&table, &col), "citext");
+        assert_eq!(generator.column_type_sql(&table, &col).unwrap(), "citext");
     }
 
     #[test]
@@ -222,17 +282,17 @@ mod tests {
         let col = ColumnBuilder::new(None::<&str>, "col", ColumnType::Array)
             .element_type(Some("int".to_string()))
             .build();
-        assert_eq!(generator.column_type_sql(&table, &col), "integer[]");
+        assert_eq!(generator.column_type_sql(&table, &col).unwrap(), "integer[]");
     }
 
     #[test]
-    #[should_panic(expected = "missing an elementType")]
-    fn array_type_without_element_type_panics() {
+    fn array_type_without_element_type_is_unsupported() {
         let model = make_model_default();
         let (ctx, table_builder) = make_context(model);
         let generator = PostgresColumnTypeGenerator::new(ctx);
         let table = table_builder.build();
         let col = ColumnBuilder::new(None::<&str>, "col", ColumnType::Array).build();
-        generator.column_type_sql(&table, &col);
+        let error = generator.column_type_sql(&table, &col).unwrap_err();
+        assert!(error.to_string().contains("needs an elementType"));
     }
 }
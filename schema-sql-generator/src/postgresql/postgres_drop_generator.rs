@@ -0,0 +1,148 @@
+use crate::common::drop_generator::{output_drop_steps, DefaultDropGenerator, DropGenerator};
+use crate::common::generated_statement::StatementKind;
+use crate::common::generation_error::GenerationError;
+use crate::common::generator_context::GeneratorContext;
+use crate::common::trigger_generator::TriggerGenerator;
+use crate::postgresql::postgres_trigger_generator::PostgresTriggerGenerator;
+use crate::postgresql::postgres_util::to_snake_case;
+use crate::sql_println;
+use schema_model::model::types::DatabaseType;
+
+pub struct PostgresDropGenerator {
+    context: GeneratorContext,
+    drop_generator: DefaultDropGenerator,
+    trigger_generator: PostgresTriggerGenerator,
+}
+
+impl PostgresDropGenerator {
+    pub fn new(context: GeneratorContext) -> Self {
+        Self {
+            context: context.clone(),
+            drop_generator: DefaultDropGenerator::new(context.clone()),
+            trigger_generator: PostgresTriggerGenerator::new(context),
+        }
+    }
+}
+
+impl DropGenerator for PostgresDropGenerator {
+    fn output_drops(&self) -> Result<(), GenerationError> {
+        output_drop_steps(self, self.context.settings().foreign_key_mode())
+    }
+
+    fn output_drop_procedures(&self) -> Result<(), GenerationError> {
+        self.drop_generator.output_drop_procedures()
+    }
+
+    fn output_drop_views(&self) -> Result<(), GenerationError> {
+        self.drop_generator.output_drop_views()
+    }
+
+    fn output_drop_functions(&self) -> Result<(), GenerationError> {
+        self.drop_generator.output_drop_functions()
+    }
+
+    fn output_drop_triggers(&self) -> Result<(), GenerationError> {
+        // Each trigger runs a function of the same name; dropping the function with
+        // `cascade` takes the trigger with it.
+        let settings = self.context.settings();
+        let separator = settings.statement_separator();
+        let database_model = settings.database_model();
+
+        self.context.with_writer(|writer| {
+            for table in database_model.all_tables().into_iter().rev() {
+                let schema_name = table.schema_name().unwrap_or(DatabaseType::Postgresql.default_schema().unwrap());
+                for trigger_name in self.trigger_generator.trigger_names(table) {
+                    let fully_qualified_fn = format!("{}.{}", schema_name, trigger_name);
+                    writer.begin_object(StatementKind::Trigger, Some(&fully_qualified_fn));
+                    sql_println!(writer, "drop function if exists {}() cascade{}", settings.qualified_generated_name(Some(schema_name), &trigger_name), separator)?;
+                }
+            }
+            Ok(())
+        })
+    }
+
+    fn output_drop_relations(&self) -> Result<(), GenerationError> {
+        self.drop_generator.output_drop_relations()
+    }
+
+    fn output_drop_tables(&self) -> Result<(), GenerationError> {
+        self.drop_generator.output_drop_tables()
+    }
+
+    fn output_drop_enum_types(&self) -> Result<(), GenerationError> {
+        let separator = self.context.settings().statement_separator();
+        let database_model = self.context.settings().database_model();
+
+        self.context.with_writer(|writer| {
+            for schema in database_model.schemas().iter().rev() {
+                let mut enum_types: Vec<_> = schema.enum_types().collect();
+                enum_types.reverse();
+                for enum_type in enum_types {
+                    let enum_type_name = to_snake_case(enum_type.name());
+                    writer.begin_object(StatementKind::Type, Some(&enum_type_name));
+                    sql_println!(writer, "drop type if exists {}{}", enum_type_name, separator)?;
+                }
+            }
+            Ok(())
+        })
+    }
+
+    fn output_drop_sequences(&self) -> Result<(), GenerationError> {
+        self.drop_generator.output_drop_sequences()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::test_support::make_context_with_fk_mode;
+    use schema_model::builder::{ColumnBuilder, KeyBuilder, SchemaBuilder, TableBuilder};
+    use schema_model::model::column_type::ColumnType;
+    use schema_model::model::database_model::DatabaseModel;
+    use schema_model::model::enum_type::{EnumType, EnumValue};
+    use schema_model::model::relation::Relation;
+    use schema_model::model::types::{BooleanMode, ForeignKeyMode, KeyType, RelationType};
+
+    fn make_model() -> DatabaseModel {
+        let mut parent = TableBuilder::new(Some("app"), "parent")
+            .add_column(ColumnBuilder::new(Some("app"), "id", ColumnType::Int).required(true).build())
+            .add_key(KeyBuilder::new(KeyType::Primary).add_column("id").build())
+            .build();
+        parent.add_reverse_relation(Relation::new("app.parent", "id", "app.child", "parent_id", RelationType::Cascade, false));
+        let child = TableBuilder::new(Some("app"), "child")
+            .add_column(ColumnBuilder::new(Some("app"), "parent_id", ColumnType::Int).build())
+            .add_relation(Relation::new("app.parent", "id", "app.child", "parent_id", RelationType::Cascade, false))
+            .build();
+        let schema = SchemaBuilder::new(Some("app"))
+            .add_table(child)
+            .add_table(parent)
+            .add_enum_type(EnumType::new("OrderStatus", vec![EnumValue::new("Open", None::<&str>)]))
+            .build();
+        DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Triggers, vec![schema])
+    }
+
+    #[test]
+    fn output_drops_with_triggers_drops_trigger_functions_instead_of_constraints() {
+        let (ctx, buffer) = make_context_with_fk_mode(make_model(), DatabaseType::Postgresql, ForeignKeyMode::Triggers);
+
+        PostgresDropGenerator::new(ctx).output_drops().unwrap();
+
+        let output = buffer.contents();
+        assert!(output.contains("drop function if exists app.parent_delete() cascade;"));
+        assert!(output.contains("drop function if exists app.child_update() cascade;"));
+        assert!(!output.contains("drop constraint"));
+        assert!(output.find("drop table if exists app.child cascade;").unwrap() < output.find("drop table if exists app.parent cascade;").unwrap());
+        assert!(output.contains("drop type if exists order_status;"));
+    }
+
+    #[test]
+    fn output_drops_with_relations_drops_constraints_and_no_trigger_functions() {
+        let (ctx, buffer) = make_context_with_fk_mode(make_model(), DatabaseType::Postgresql, ForeignKeyMode::Relations);
+
+        PostgresDropGenerator::new(ctx).output_drops().unwrap();
+
+        let output = buffer.contents();
+        assert!(output.contains("alter table if exists app.child drop constraint if exists fk_child1;"));
+        assert!(!output.contains("drop function"));
+    }
+}
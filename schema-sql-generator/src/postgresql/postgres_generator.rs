@@ -1,16 +1,21 @@
+use crate::common::generated_statement::StatementKind;
+use crate::common::generation_error::GenerationError;
 use crate::common::generator_context::GeneratorContext;
 use crate::common::sql_generator::{DefaultSqlGenerator, SqlGenerator};
 use crate::common::sql_string::escape_sql_literal;
+use crate::postgresql::postgres_drop_generator::PostgresDropGenerator;
 use crate::postgresql::postgres_function_generator::PostgresFunctionGenerator;
 use crate::postgresql::postgres_index_generator::PostgresIndexGenerator;
 use crate::postgresql::postgres_other_sql_generator::PostgresOtherSqlGenerator;
 use crate::postgresql::postgres_procedure_generator::PostgresProcedureGenerator;
 use crate::postgresql::postgres_relation_generator::PostgresRelationGenerator;
+use crate::postgresql::postgres_sequence_generator::PostgresSequenceGenerator;
 use crate::postgresql::postgres_table_generator::PostgresTableGenerator;
 use crate::postgresql::postgres_trigger_generator::PostgresTriggerGenerator;
 use crate::postgresql::postgres_util::to_snake_case;
 use crate::postgresql::postgres_view_generator::PostgresViewGenerator;
 use crate::sql_println;
+use schema_model::model::types::DatabaseType;
 
 pub struct PostgresGenerator {
     context: GeneratorContext,
@@ -29,6 +34,8 @@ impl PostgresGenerator {
             Box::new(PostgresProcedureGenerator::new(context.clone())),
             Box::new(PostgresTriggerGenerator::new(context.clone())),
             Box::new(PostgresOtherSqlGenerator::new(context.clone())),
+            Box::new(PostgresSequenceGenerator::new(context.clone())),
+            Box::new(PostgresDropGenerator::new(context.clone())),
         );
 
         Self {
@@ -37,34 +44,36 @@ impl PostgresGenerator {
         }
     }
 
-    fn create_uuid_generator_function(&self) {
+    fn create_uuid_generator_function(&self) -> Result<(), GenerationError> {
         let separator = self.context.settings().statement_separator().to_string();
 
         self.context.with_writer(|writer| {
-            sql_println!(writer, "create or replace function generate_uuid() returns uuid language plpgsql volatile parallel unsafe as $$");
-            sql_println!(writer, "declare");
-            sql_println!(writer, "   -- The current UNIX timestamp in milliseconds");
-            sql_println!(writer, "   unix_time_ms CONSTANT bigint NOT NULL DEFAULT (extract(epoch FROM clock_timestamp()) * 1000)::bigint;");
-            sql_println!(writer, "");
-            sql_println!(writer, "   -- The buffer used to create the UUID: the low 6 bytes (48 bits) of the timestamp, followed by 10 random bytes");
-            sql_println!(writer, "   buffer bytea not null default substring(int8send(unix_time_ms) from 3) || gen_random_bytes(10);");
-            sql_println!(writer, "begin");
-            sql_println!(writer, "   -- Set the version nibble of byte 6 to 0111 (UUID v7), keeping the last 4 bits unchanged");
-            sql_println!(writer, "   buffer = set_byte(buffer, 6, (get_byte(buffer, 6) & 15) | 112);");
-            sql_println!(writer, "");
-            sql_println!(writer, "   -- Set the top 2 bits of byte 8 to 10 (the UUID variant specified in RFC 4122), keeping the last 6 bits unchanged");
+            writer.begin_object(StatementKind::Function, Some("generate_uuid"));
+            sql_println!(writer, "create or replace function generate_uuid() returns uuid language plpgsql volatile parallel unsafe as $$")?;
+            sql_println!(writer, "declare")?;
+            sql_println!(writer, "   -- The current UNIX timestamp in milliseconds")?;
+            sql_println!(writer, "   unix_time_ms CONSTANT bigint NOT NULL DEFAULT (extract(epoch FROM clock_timestamp()) * 1000)::bigint;")?;
+            sql_println!(writer, "")?;
+            sql_println!(writer, "   -- The buffer used to create the UUID: the low 6 bytes (48 bits) of the timestamp, followed by 10 random bytes")?;
+            sql_println!(writer, "   buffer bytea not null default substring(int8send(unix_time_ms) from 3) || gen_random_bytes(10);")?;
+            sql_println!(writer, "begin")?;
+            sql_println!(writer, "   -- Set the version nibble of byte 6 to 0111 (UUID v7), keeping the last 4 bits unchanged")?;
+            sql_println!(writer, "   buffer = set_byte(buffer, 6, (get_byte(buffer, 6) & 15) | 112);")?;
+            sql_println!(writer, "")?;
+            sql_println!(writer, "   -- Set the top 2 bits of byte 8 to 10 (the UUID variant specified in RFC 4122), keeping the last 6 bits unchanged")?;
             sql_println!(writer,
                 "   buffer = set_byte(buffer, 8, (get_byte(buffer, 8) & 63) | 128);",
-            );
-            sql_println!(writer, "");
-            sql_println!(writer, "   return encode(buffer, 'hex')::uuid;");
-            sql_println!(writer, "end");
-            sql_println!(writer, "$${}", separator);
-            sql_println!(writer, "");
-        });
+            )?;
+            sql_println!(writer, "")?;
+            sql_println!(writer, "   return encode(buffer, 'hex')::uuid;")?;
+            sql_println!(writer, "end")?;
+            sql_println!(writer, "$${}", separator)?;
+            sql_println!(writer, "")?;
+            Ok(())
+        })
     }
 
-    fn create_extensions(&self) {
+    fn create_extensions(&self) -> Result<(), GenerationError> {
         let separator = self.context.settings().statement_separator().to_string();
         let check_user = match self.context.settings().extension_check_user() {
             Some(user) => format!("'{}'", escape_sql_literal(user)),
@@ -72,24 +81,43 @@ impl PostgresGenerator {
         };
 
         self.context.with_writer(|writer| {
-            sql_println!(writer, "do $$");
-            sql_println!(writer, "begin");
-            sql_println!(writer, "   if (select usesuper from pg_user where usename = {}) then", check_user);
-            sql_println!(writer, "      create extension if not exists \"citext\";");
-            sql_println!(writer, "      create extension if not exists \"btree_gist\";");
-            sql_println!(writer, "   else");
-            sql_println!(writer, "      raise notice 'Could not create extensions, user % does not have permission.', current_user;");
-            sql_println!(writer, "   end if;");
-            sql_println!(writer, "end;");
-            sql_println!(writer, "$${}", separator);
-            sql_println!(writer, "");
-        });
+            writer.begin_object(StatementKind::Extension, None);
+            sql_println!(writer, "do $$")?;
+            sql_println!(writer, "begin")?;
+            sql_println!(writer, "   if (select usesuper from pg_user where usename = {}) then", check_user)?;
+            sql_println!(writer, "      create extension if not exists \"citext\";")?;
+            sql_println!(writer, "      create extension if not exists \"btree_gist\";")?;
+            sql_println!(writer, "   else")?;
+            sql_println!(writer, "      raise notice 'Could not create extensions, user % does not have permission.', current_user;")?;
+            sql_println!(writer, "   end if;")?;
+            sql_println!(writer, "end;")?;
+            sql_println!(writer, "$${}", separator)?;
+            sql_println!(writer, "")?;
+            Ok(())
+        })
     }
 
-    fn create_enum_types(&self) {
+    fn create_schemas(&self) -> Result<(), GenerationError> {
         let separator = self.context.settings().statement_separator().to_string();
         let database_model = self.context.settings().database_model();
 
+        self.context.with_writer(|writer| {
+            for schema_name in database_model.schemas().iter().filter_map(|schema| schema.schema_name()) {
+                if schema_name != DatabaseType::Postgresql.default_schema().unwrap() {
+                    writer.begin_object(StatementKind::Schema, Some(schema_name));
+                    sql_println!(writer, "create schema if not exists {}{}", schema_name, separator)?;
+                    sql_println!(writer, "")?;
+                }
+            }
+            Ok(())
+        })
+    }
+
+    fn create_enum_types(&self) -> Result<(), GenerationError> {
+        let separator = self.context.settings().statement_separator().to_string();
+        let database_model = self.context.settings().database_model();
+        let idempotent = self.context.settings().idempotent();
+
         for schema in database_model.schemas() {
             for enum_type in schema.enum_types() {
                 let enum_type_name = to_snake_case(enum_type.name());
@@ -100,13 +128,28 @@ impl PostgresGenerator {
                     .collect::<Vec<_>>()
                     .join(",");
 
-                self.context.with_writer(|writer| {
-                    sql_println!(writer, "drop type if exists {} cascade{}", enum_type_name, separator);
-                    sql_println!(writer, "create type {} as enum ({}){}", enum_type_name, values, separator);
-                    sql_println!(writer, "");
-                });
+                self.context.with_writer(|writer| -> Result<(), GenerationError> {
+                    writer.begin_object(StatementKind::Type, Some(&enum_type_name));
+                    if idempotent {
+                        // Dropping with `cascade` would take every column of the type with it.
+                        sql_println!(writer, "do $$")?;
+                        sql_println!(writer, "begin")?;
+                        sql_println!(writer, "   if not exists (select 1 from pg_type where typname = '{}') then", escape_sql_literal(&enum_type_name))?;
+                        sql_println!(writer, "      create type {} as enum ({});", enum_type_name, values)?;
+                        sql_println!(writer, "   end if;")?;
+                        sql_println!(writer, "end")?;
+                        sql_println!(writer, "$${}", separator)?;
+                    } else {
+                        sql_println!(writer, "drop type if exists {} cascade{}", enum_type_name, separator)?;
+                        sql_println!(writer, "create type {} as enum ({}){}", enum_type_name, values, separator)?;
+                    }
+                    sql_println!(writer, "")?;
+                    Ok(())
+                })?;
             }
         }
+
+        Ok(())
     }
 }
 
@@ -115,50 +158,61 @@ impl SqlGenerator for PostgresGenerator {
         &self.context
     }
 
-    fn output_header(&self) {
+    fn output_header(&self) -> Result<(), GenerationError> {
         if self.context.settings().target_postgres_version() < 18 {
-            self.create_uuid_generator_function();
+            self.create_uuid_generator_function()?;
         }
         if self.context.settings().emit_postgres_extensions() {
-            self.create_extensions();
+            self.create_extensions()?;
+        }
+        if self.context.settings().idempotent() {
+            self.create_schemas()?;
         }
-        self.create_enum_types();
+        self.create_enum_types()
+    }
+
+    fn output_sequences(&self) -> Result<(), GenerationError> {
+        self.sql_generator.output_sequences()
+    }
+
+    fn output_tables(&self) -> Result<(), GenerationError> {
+        self.sql_generator.output_tables()
     }
 
-    fn output_tables(&self) {
-        self.sql_generator.output_tables();
+    fn output_relations(&self) -> Result<(), GenerationError> {
+        self.sql_generator.output_relations()
     }
 
-    fn output_relations(&self) {
-        self.sql_generator.output_relations();
+    fn output_indexes(&self) -> Result<(), GenerationError> {
+        self.sql_generator.output_indexes()
     }
 
-    fn output_indexes(&self) {
-        self.sql_generator.output_indexes();
+    fn output_triggers(&self) -> Result<(), GenerationError> {
+        self.sql_generator.output_triggers()
     }
 
-    fn output_triggers(&self) {
-        self.sql_generator.output_triggers();
+    fn output_functions(&self) -> Result<(), GenerationError> {
+        self.sql_generator.output_functions()
     }
 
-    fn output_functions(&self) {
-        self.sql_generator.output_functions();
+    fn output_views(&self) -> Result<(), GenerationError> {
+        self.sql_generator.output_views()
     }
 
-    fn output_views(&self) {
-        self.sql_generator.output_views();
+    fn output_procedures(&self) -> Result<(), GenerationError> {
+        self.sql_generator.output_procedures()
     }
 
-    fn output_procedures(&self) {
-        self.sql_generator.output_procedures();
+    fn output_other_sql_top(&self) -> Result<(), GenerationError> {
+        self.sql_generator.output_other_sql_top()
     }
 
-    fn output_other_sql_top(&self) {
-        self.sql_generator.output_other_sql_top();
+    fn output_other_sql_bottom(&self) -> Result<(), GenerationError> {
+        self.sql_generator.output_other_sql_bottom()
     }
 
-    fn output_other_sql_bottom(&self) {
-        self.sql_generator.output_other_sql_bottom();
+    fn output_drops(&self) -> Result<(), GenerationError> {
+        self.sql_generator.output_drops()
     }
 }
 
@@ -173,9 +227,10 @@ mod tests {
     use schema_model::builder::SchemaBuilder;
     use schema_model::model::database_model::DatabaseModel;
     use schema_model::model::enum_type::{EnumType, EnumValue};
-    use schema_model::model::types::{BooleanMode, DatabaseType, ForeignKeyMode};
+    use schema_model::model::types::{BooleanMode, ForeignKeyMode};
     use std::cell::RefCell;
     use std::rc::Rc;
+    use std::sync::Arc;
 
     fn make_context_with_version(model: DatabaseModel, target_postgres_version: u32) -> (GeneratorContext, SharedBuffer) {
         make_context(model, target_postgres_version, true)
@@ -183,14 +238,11 @@ mod tests {
 
     fn make_context(model: DatabaseModel, target_postgres_version: u32, emit_postgres_extensions: bool) -> (GeneratorContext, SharedBuffer) {
         let buffer = SharedBuffer::new();
-        let mut options = GenerateOptions::new(
-            Rc::new(model),
-            Rc::new(RefCell::new(PrintWriter::new_auto_flush(Box::new(buffer.clone())))),
-        );
+        let mut options = GenerateOptions::new(Arc::new(model));
         options.target_postgres_version = target_postgres_version;
         options.emit_postgres_extensions = emit_postgres_extensions;
         let settings = SqlGeneratorSettings::new(DatabaseType::Postgresql, &options);
-        let writer = SqlWriter::new(options.writer.clone());
+        let writer = SqlWriter::new(Rc::new(RefCell::new(PrintWriter::new_auto_flush(Box::new(buffer.clone())))));
         (GeneratorContext::new(settings, writer), buffer)
     }
 
@@ -201,7 +253,7 @@ mod tests {
         let (ctx, buffer) = make_context_with_version(model, 17);
 
         let generator = PostgresGenerator::new(ctx);
-        generator.output_header();
+        generator.output_header().unwrap();
 
         let output = buffer.contents();
         assert!(output.contains("create or replace function generate_uuid()"));
@@ -214,7 +266,7 @@ mod tests {
         let (ctx, buffer) = make_context_with_version(model, 18);
 
         let generator = PostgresGenerator::new(ctx);
-        generator.output_header();
+        generator.output_header().unwrap();
 
         let output = buffer.contents();
         assert!(!output.contains("create or replace function generate_uuid()"));
@@ -234,7 +286,7 @@ mod tests {
         let (ctx, buffer) = make_context_with_version(model, 18);
 
         let generator = PostgresGenerator::new(ctx);
-        generator.output_header();
+        generator.output_header().unwrap();
 
         let output = buffer.contents();
         assert!(output.contains("create type status_type as enum ('A','I')"));
@@ -247,7 +299,7 @@ mod tests {
         let (ctx, buffer) = make_context_with_version(model, 18);
 
         let generator = PostgresGenerator::new(ctx);
-        generator.output_header();
+        generator.output_header().unwrap();
 
         let output = buffer.contents();
         assert!(!output.contains("create type"));
@@ -260,7 +312,7 @@ mod tests {
         let (ctx, buffer) = make_context_with_version(model, 18);
 
         let generator = PostgresGenerator::new(ctx);
-        generator.output_header();
+        generator.output_header().unwrap();
 
         let output = buffer.contents();
         assert!(output.contains("create extension if not exists \"citext\""));
@@ -274,7 +326,7 @@ mod tests {
         let (ctx, buffer) = make_context(model, 18, false);
 
         let generator = PostgresGenerator::new(ctx);
-        generator.output_header();
+        generator.output_header().unwrap();
 
         let output = buffer.contents();
         assert!(!output.contains("create extension"));
@@ -287,7 +339,7 @@ mod tests {
         let (ctx, buffer) = make_context_with_version(model, 18);
 
         let generator = PostgresGenerator::new(ctx);
-        generator.output_header();
+        generator.output_header().unwrap();
 
         let output = buffer.contents();
         assert!(output.contains("where usename = CURRENT_USER"));
@@ -298,18 +350,15 @@ mod tests {
         let schema = SchemaBuilder::new(None::<&str>).build();
         let model = DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema]);
         let buffer = SharedBuffer::new();
-        let mut options = GenerateOptions::new(
-            Rc::new(model),
-            Rc::new(RefCell::new(PrintWriter::new_auto_flush(Box::new(buffer.clone())))),
-        );
+        let mut options = GenerateOptions::new(Arc::new(model));
         options.target_postgres_version = 18;
         options.extension_check_user = Some("schema_admin".to_string());
         let settings = SqlGeneratorSettings::new(DatabaseType::Postgresql, &options);
-        let writer = SqlWriter::new(options.writer.clone());
+        let writer = SqlWriter::new(Rc::new(RefCell::new(PrintWriter::new_auto_flush(Box::new(buffer.clone())))));
         let ctx = GeneratorContext::new(settings, writer);
 
         let generator = PostgresGenerator::new(ctx);
-        generator.output_header();
+        generator.output_header().unwrap();
 
         let output = buffer.contents();
         assert!(output.contains("where usename = 'schema_admin'"));
@@ -323,18 +372,15 @@ mod tests {
         let schema = SchemaBuilder::new(None::<&str>).build();
         let model = DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema]);
         let buffer = SharedBuffer::new();
-        let mut options = GenerateOptions::new(
-            Rc::new(model),
-            Rc::new(RefCell::new(PrintWriter::new_auto_flush(Box::new(buffer.clone())))),
-        );
+        let mut options = GenerateOptions::new(Arc::new(model));
         options.target_postgres_version = 18;
         options.extension_check_user = Some("o'brien".to_string());
         let settings = SqlGeneratorSettings::new(DatabaseType::Postgresql, &options);
-        let writer = SqlWriter::new(options.writer.clone());
+        let writer = SqlWriter::new(Rc::new(RefCell::new(PrintWriter::new_auto_flush(Box::new(buffer.clone())))));
         let ctx = GeneratorContext::new(settings, writer);
 
         let generator = PostgresGenerator::new(ctx);
-        generator.output_header();
+        generator.output_header().unwrap();
 
         let output = buffer.contents();
         assert!(output.contains("where usename = 'o''brien'"));
@@ -353,9 +399,30 @@ mod tests {
         let (ctx, buffer) = make_context_with_version(model, 18);
 
         let generator = PostgresGenerator::new(ctx);
-        generator.output_header();
+        generator.output_header().unwrap();
 
         let output = buffer.contents();
         assert!(output.contains("create type status_type as enum ('O''Brien')"));
     }
+
+    #[test]
+    fn output_header_creates_schemas_and_guards_enum_types_when_idempotent() {
+        let enum_type = EnumType::new("status_type", vec![EnumValue::new("ACTIVE", Some("A".to_string()))]);
+        let schema = SchemaBuilder::new(Some("app")).add_enum_type(enum_type).build();
+        let model = DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema]);
+        let buffer = SharedBuffer::new();
+        let mut options = GenerateOptions::new(Arc::new(model));
+        options.target_postgres_version = 18;
+        options.emit_postgres_extensions = false;
+        options.idempotent = true;
+        let settings = SqlGeneratorSettings::new(DatabaseType::Postgresql, &options);
+        let writer = SqlWriter::new(Rc::new(RefCell::new(PrintWriter::new_auto_flush(Box::new(buffer.clone())))));
+
+        PostgresGenerator::new(GeneratorContext::new(settings, writer)).output_header().unwrap();
+
+        let output = buffer.contents();
+        assert!(output.contains("create schema if not exists app;"));
+        assert!(output.contains("   if not exists (select 1 from pg_type where typname = 'status_type') then\n      create type status_type as enum ('A');\n   end if;"));
+        assert!(!output.contains("drop type"));
+    }
 }
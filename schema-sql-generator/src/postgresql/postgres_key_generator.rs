@@ -1,4 +1,5 @@
 use schema_model::model::table::Table;
+use crate::common::generation_error::GenerationError;
 use crate::common::generator_context::GeneratorContext;
 use crate::common::key_generator::{DefaultKeyGenerator, KeyGenerator};
 
@@ -15,7 +16,7 @@ impl PostgresKeyGenerator {
 }
 
 impl KeyGenerator for PostgresKeyGenerator {
-    fn key_constraints(&self, table: &Table) -> Vec<String> {
+    fn key_constraints(&self, table: &Table) -> Result<Vec<String>, GenerationError> {
         self.key_generator.key_constraints(table)
     }
 }
@@ -1,3 +1,4 @@
+use crate::common::generation_error::GenerationError;
 use crate::common::generator_context::GeneratorContext;
 use crate::common::procedure_generator::{DefaultProcedureGenerator, ProcedureGenerator};
 use crate::common::sql_writer::SqlWriter;
@@ -16,12 +17,12 @@ impl PostgresProcedureGenerator {
 }
 
 impl ProcedureGenerator for PostgresProcedureGenerator {
-    fn output_procedures(&self) {
-        self.procedure_generator.output_procedures();
+    fn output_procedures(&self) -> Result<(), GenerationError> {
+        self.procedure_generator.output_procedures()
     }
 
-    fn output_procedure(&self, writer: &mut SqlWriter, statement_separator: &str, procedure: &Procedure) {
-        self.procedure_generator.output_procedure(writer, statement_separator, procedure);
+    fn output_procedure(&self, writer: &mut SqlWriter, statement_separator: &str, procedure: &Procedure) -> Result<(), GenerationError> {
+        self.procedure_generator.output_procedure(writer, statement_separator, procedure)
     }
 }
 
@@ -45,7 +46,7 @@ mod tests {
         let (ctx, buffer) = make_context(model, DatabaseType::Postgresql);
 
         let generator = PostgresProcedureGenerator::new(ctx);
-        generator.output_procedures();
+        generator.output_procedures().unwrap();
 
         let output = buffer.contents();
         assert!(output.contains("create procedure pg_only()"));
@@ -0,0 +1,94 @@
+use crate::common::generated_statement::StatementKind;
+use crate::common::generation_error::GenerationError;
+use crate::common::generator_context::GeneratorContext;
+use crate::common::sequence_generator::SequenceGenerator;
+use crate::sql_println;
+
+pub struct PostgresSequenceGenerator {
+    context: GeneratorContext,
+}
+
+impl PostgresSequenceGenerator {
+    pub fn new(context: GeneratorContext) -> Self {
+        Self { context }
+    }
+}
+
+impl SequenceGenerator for PostgresSequenceGenerator {
+    fn output_sequences(&self) -> Result<(), GenerationError> {
+        let database_model = self.context.settings().database_model();
+        let database_type = self.context.settings().database_type();
+        let separator = self.context.settings().statement_separator();
+        let idempotent = self.context.settings().idempotent();
+        let if_not_exists = if idempotent { "if not exists " } else { "" };
+
+        self.context.with_writer(|writer| {
+            for sequence in database_model.schemas().iter().flat_map(|schema| schema.sequences()) {
+                let sequence_name = self.context.settings().qualified_name(sequence.schema_name(), sequence.name());
+                writer.begin_object(StatementKind::Sequence, Some(&sequence.fully_qualified_sequence_name(database_type)));
+                if !idempotent {
+                    // `cascade` only takes the column defaults drawing from the sequence,
+                    // and the tables are recreated after it.
+                    sql_println!(writer, "drop sequence if exists {} cascade{}", sequence_name, separator)?;
+                }
+                sql_println!(writer, "create sequence {}{}{}{}", if_not_exists, sequence_name, sequence.options_sql(), separator)?;
+                sql_println!(writer, "")?;
+            }
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::table_generator::TableGenerator;
+    use crate::common::test_support::make_context;
+    use crate::postgresql::postgres_table_generator::PostgresTableGenerator;
+    use schema_model::builder::{ColumnBuilder, SchemaBuilder, SequenceBuilder, TableBuilder};
+    use schema_model::model::column_type::ColumnType;
+    use schema_model::model::database_model::DatabaseModel;
+    use schema_model::model::types::{BooleanMode, DatabaseType, ForeignKeyMode};
+
+    fn model() -> DatabaseModel {
+        let sequence = SequenceBuilder::new(Some("sales"), "document_no")
+            .start(Some(1000))
+            .increment(Some(10))
+            .cache(Some(20))
+            .build();
+        let table = TableBuilder::new(Some("sales"), "invoice")
+            .add_column(
+                ColumnBuilder::new(Some("sales"), "invoice_no", ColumnType::Long)
+                    .required(true)
+                    .sequence(Some("document_no".to_string()))
+                    .build(),
+            )
+            .build();
+        let schema = SchemaBuilder::new(Some("sales")).add_sequence(sequence).add_table(table).build();
+        DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema])
+    }
+
+    #[test]
+    fn output_sequences_renders_create_sequence_with_options() {
+        let (ctx, buffer) = make_context(model(), DatabaseType::Postgresql);
+
+        PostgresSequenceGenerator::new(ctx).output_sequences().unwrap();
+
+        assert_eq!(
+            buffer.contents(),
+            "drop sequence if exists sales.document_no cascade;\n\
+             create sequence sales.document_no start with 1000 increment by 10 cache 20;\n\n"
+        );
+    }
+
+    #[test]
+    fn column_drawing_from_a_sequence_defaults_to_nextval() {
+        let model = model();
+        let table = model.schemas()[0].get_table("invoice").clone();
+        let (ctx, buffer) = make_context(model, DatabaseType::Postgresql);
+
+        PostgresTableGenerator::new(ctx).output_table_definition(&table).unwrap();
+
+        assert!(buffer.contents().contains("invoice_no bigint not null default nextval('sales.document_no')"));
+    }
+}
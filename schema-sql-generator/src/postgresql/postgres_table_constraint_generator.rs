@@ -1,3 +1,4 @@
+use crate::common::generation_error::GenerationError;
 use crate::common::generator_context::GeneratorContext;
 use crate::common::table_constraint_generator::{DefaultTableConstraintGenerator, TableConstraintGenerator};
 use schema_model::model::table::Table;
@@ -15,7 +16,7 @@ impl PostgresTableConstraintGenerator {
 }
 
 impl TableConstraintGenerator for PostgresTableConstraintGenerator {
-    fn table_check_constraints(&self, table: &Table) -> Vec<String> {
+    fn table_check_constraints(&self, table: &Table) -> Result<Vec<String>, GenerationError> {
         self.table_constraint_generator.table_check_constraints(table)
     }
 }
@@ -39,7 +40,7 @@ mod tests {
         let (ctx, _buffer) = make_context(model, DatabaseType::Postgresql);
 
         let generator = PostgresTableConstraintGenerator::new(ctx);
-        let constraints = generator.table_check_constraints(&table);
+        let constraints = generator.table_check_constraints(&table).unwrap();
 
         assert_eq!(constraints.len(), 1);
         assert!(constraints[0].contains("constraint ck_total_positive check (total > 0)"));
@@ -53,6 +54,6 @@ mod tests {
         let (ctx, _buffer) = make_context(model, DatabaseType::Postgresql);
 
         let generator = PostgresTableConstraintGenerator::new(ctx);
-        assert!(generator.table_check_constraints(&table).is_empty());
+        assert!(generator.table_check_constraints(&table).unwrap().is_empty());
     }
 }
@@ -1,3 +1,5 @@
+use crate::common::generated_statement::StatementKind;
+use crate::common::generation_error::GenerationError;
 use crate::common::generator_context::GeneratorContext;
 use crate::common::table_generator::{DefaultTableGenerator, TableGenerator};
 use crate::postgresql::postgres_column_constraint_generator::PostgresColumnConstraintGenerator;
@@ -5,15 +7,19 @@ use crate::postgresql::postgres_column_generator::PostgresColumnGenerator;
 use crate::postgresql::postgres_index_generator::PostgresIndexGenerator;
 use crate::postgresql::postgres_key_generator::PostgresKeyGenerator;
 use crate::postgresql::postgres_table_constraint_generator::PostgresTableConstraintGenerator;
+use crate::postgresql::postgres_util::partition_bound_sql;
+use crate::{sql_newline, sql_println};
 use schema_model::model::table::Table;
 
 pub struct PostgresTableGenerator {
+    context: GeneratorContext,
     table_generator: DefaultTableGenerator,
 }
 
 impl PostgresTableGenerator {
     pub fn new(context: GeneratorContext) -> Self {
         Self {
+            context: context.clone(),
             table_generator: DefaultTableGenerator::new(
                 context.clone(),
                 Box::new(PostgresColumnGenerator::new(context.clone())),
@@ -24,35 +30,99 @@ impl PostgresTableGenerator {
             ),
         }
     }
+
+    /// Creates each partition of a partitioned table as a `partition of` child table in the
+    /// parent's schema.
+    fn output_partitions(&self, table: &Table) -> Result<(), GenerationError> {
+        let Some(partitioning) = table.partitioning() else {
+            return Ok(());
+        };
+        let database_type = self.context.settings().database_type();
+        let separator = self.context.settings().statement_separator();
+        let fully_qualified_table_name = self.context.settings().qualified_name(table.schema_name(), table.name());
+        let if_not_exists = if self.context.settings().idempotent() { "if not exists " } else { "" };
+
+        self.context.with_writer(|writer| {
+            for partition in partitioning.all_partitions(table.name()) {
+                let partition_name = database_type.qualified_name(table.schema_name(), partition.name());
+                writer.begin_object(StatementKind::Table, Some(&partition_name));
+                sql_println!(
+                    writer,
+                    "create table {}{} partition of {} {}{}",
+                    if_not_exists,
+                    self.context.settings().qualified_name(table.schema_name(), partition.name()),
+                    fully_qualified_table_name,
+                    partition_bound_sql(partition.bound()),
+                    separator
+                )?;
+            }
+            sql_newline!(writer)?;
+            Ok(())
+        })
+    }
 }
 
 impl TableGenerator for PostgresTableGenerator {
-    fn output_tables(&self) {
-        self.table_generator.output_tables();
+    fn output_tables(&self) -> Result<(), GenerationError> {
+        for schema in self.context.settings().database_model().schemas() {
+            for table in schema.tables() {
+                self.output_table(table)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn output_table(&self, table: &Table) -> Result<(), GenerationError> {
+        let steps = || {
+            self.output_table_header(table)?;
+            self.output_table_definition(table)?;
+            self.output_table_footer(table)?;
+            self.output_partitions(table)?;
+            self.output_table_descriptions(table)?;
+            self.output_indexes(table)?;
+            self.output_initial_data(table)
+        };
+        steps().map_err(|e| e.within(|| format!("table {}", table.name())))
     }
 
-    fn output_table(&self, table: &Table) {
-        self.table_generator.output_table_header(table);
+    fn output_table_header(&self, table: &Table) -> Result<(), GenerationError> {
+        self.table_generator.output_table_header(table)
     }
 
-    fn output_table_header(&self, table: &Table) {
-        self.table_generator.output_table_header(table);
+    fn output_table_definition(&self, table: &Table) -> Result<(), GenerationError> {
+        self.table_generator.output_table_definition(table)
     }
 
-    fn output_table_definition(&self, table: &Table) {
-        self.table_generator.output_table_definition(table);
+    fn output_table_footer(&self, table: &Table) -> Result<(), GenerationError> {
+        match table.partitioning() {
+            Some(partitioning) => self.context.with_writer(|writer| {
+                let settings = self.context.settings();
+                let columns = partitioning.columns().iter().map(|column| settings.quote(column)).collect::<Vec<_>>();
+                sql_println!(
+                    writer,
+                    ") partition by {} ({}){}",
+                    partitioning.strategy().sql(),
+                    columns.join(", "),
+                    settings.statement_separator()
+                )?;
+                sql_newline!(writer)?;
+                Ok(())
+            }),
+            None => self.table_generator.output_table_footer(table),
+        }
     }
 
-    fn output_table_footer(&self, table: &Table) {
-        self.table_generator.output_table_footer(table);
+    fn output_table_descriptions(&self, table: &Table) -> Result<(), GenerationError> {
+        self.table_generator.output_table_descriptions(table)
     }
 
-    fn output_indexes(&self, table: &Table) {
-        self.table_generator.output_indexes(table);
+    fn output_indexes(&self, table: &Table) -> Result<(), GenerationError> {
+        self.table_generator.output_indexes(table)
     }
 
-    fn output_initial_data(&self, table: &Table) {
-        self.table_generator.output_initial_data(table);
+    fn output_initial_data(&self, table: &Table) -> Result<(), GenerationError> {
+        self.table_generator.output_initial_data(table)
     }
 }
 
@@ -63,7 +133,8 @@ mod tests {
     use schema_model::builder::{ColumnBuilder, SchemaBuilder, TableBuilder};
     use schema_model::model::column_type::ColumnType;
     use schema_model::model::database_model::DatabaseModel;
-    use schema_model::model::types::{BooleanMode, DatabaseType, ForeignKeyMode};
+    use schema_model::model::partition::{Partition, PartitionBound, PartitionTemplate, Partitioning};
+    use schema_model::model::types::{BooleanMode, DatabaseType, ForeignKeyMode, PartitionInterval, PartitionStrategy};
 
     #[test]
     fn output_table_renders_header_and_columns() {
@@ -76,9 +147,9 @@ mod tests {
         let (ctx, buffer) = make_context(model, DatabaseType::Postgresql);
 
         let generator = PostgresTableGenerator::new(ctx);
-        generator.output_table_header(&table);
-        generator.output_table_definition(&table);
-        generator.output_table_footer(&table);
+        generator.output_table_header(&table).unwrap();
+        generator.output_table_definition(&table).unwrap();
+        generator.output_table_footer(&table).unwrap();
 
         let output = buffer.contents();
         assert!(output.contains("create table public.users"));
@@ -86,4 +157,60 @@ mod tests {
         assert!(output.contains("name text"));
         assert!(output.contains(");"));
     }
+
+    #[test]
+    fn output_table_partitions_by_range_and_creates_each_partition() {
+        let partitioning = Partitioning::new(
+            PartitionStrategy::Range,
+            vec!["created_at".to_string()],
+            vec![Partition::new("events_default", PartitionBound::Default)],
+            Some(PartitionTemplate::Range {
+                interval: PartitionInterval::Month,
+                start: "2024-01-01".to_string(),
+                count: 2,
+            }),
+        );
+        let table = TableBuilder::new(Some("app"), "events")
+            .add_column(ColumnBuilder::new(Some("app"), "id", ColumnType::Long).required(true).build())
+            .add_column(ColumnBuilder::new(Some("app"), "created_at", ColumnType::Timestamp).required(true).build())
+            .partitioning(Some(partitioning))
+            .build();
+        let schema = SchemaBuilder::new(Some("app")).add_table(table.clone()).build();
+        let model = DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema]);
+        let (ctx, buffer) = make_context(model, DatabaseType::Postgresql);
+
+        let generator = PostgresTableGenerator::new(ctx);
+        generator.output_tables().unwrap();
+
+        let output = buffer.contents();
+        assert!(output.contains("create table app.events\n("));
+        assert!(output.contains(") partition by range (created_at);"));
+        assert!(output.contains("create table app.events_default partition of app.events default;"));
+        assert!(output.contains(
+            "create table app.events_p2024_01 partition of app.events for values from ('2024-01-01') to ('2024-02-01');"
+        ));
+        assert!(output.contains(
+            "create table app.events_p2024_02 partition of app.events for values from ('2024-02-01') to ('2024-03-01');"
+        ));
+    }
+
+    #[test]
+    fn output_table_descriptions_emits_comment_on_statements() {
+        let table = TableBuilder::new(None::<&str>, "users")
+            .description(Some("Application users".to_string()))
+            .add_column(ColumnBuilder::new(None::<&str>, "id", ColumnType::Int).build())
+            .add_column(ColumnBuilder::new(None::<&str>, "name", ColumnType::Varchar).description(Some("User's display name".to_string())).build())
+            .build();
+        let schema = SchemaBuilder::new(None::<&str>).add_table(table.clone()).build();
+        let model = DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema]);
+        let (ctx, buffer) = make_context(model, DatabaseType::Postgresql);
+
+        let generator = PostgresTableGenerator::new(ctx);
+        generator.output_table_descriptions(&table).unwrap();
+
+        let output = buffer.contents();
+        assert!(output.contains("comment on table public.users is 'Application users';"));
+        assert!(output.contains("comment on column public.users.name is 'User''s display name';"));
+        assert!(!output.contains("public.users.id"));
+    }
 }
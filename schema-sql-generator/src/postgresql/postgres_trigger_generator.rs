@@ -1,6 +1,12 @@
+use crate::common::aggregation_support::{aggregation_group_value, aggregation_row_value};
+use crate::common::generated_statement::StatementKind;
+use crate::common::generation_error::{resolve_table, GenerationError};
 use crate::common::generator_context::GeneratorContext;
+use crate::common::sql_writer::SqlWriter;
 use crate::common::trigger_generator::TriggerGenerator;
+use crate::common::trigger_support::{child_match_condition, outputs_delete_trigger, parent_match_condition, should_output_update_trigger};
 use crate::sql_println;
+use schema_model::model::aggregation::AggregationFrequency;
 use schema_model::model::table::Table;
 use schema_model::model::types::{DatabaseType, ForeignKeyMode, RelationType, TriggerType};
 
@@ -12,58 +18,53 @@ impl PostgresTriggerGenerator {
     pub fn new(context: GeneratorContext) -> Self {
         Self { context }
     }
+}
 
-    fn should_output_delete_trigger(&self, table: &Table) -> bool {
-        let has_delete_triggers = table
-            .triggers()
-            .iter()
-            .any(|t| t.trigger_type() == TriggerType::Delete);
-
-        let has_reverse_relations_with_triggers = !table.reverse_relations().is_empty()
-            && self.context.settings().foreign_key_mode() == ForeignKeyMode::Triggers;
-
-        let has_aggregations = !table.aggregations().is_empty();
+impl TriggerGenerator for PostgresTriggerGenerator {
+    fn output_triggers(&self) -> Result<(), GenerationError> {
+        for table in self.context.settings().database_model().all_tables() {
+            self.output_table_triggers(table)?;
+        }
 
-        has_delete_triggers || has_reverse_relations_with_triggers || has_aggregations
+        Ok(())
     }
 
-    fn should_output_update_trigger(&self, table: &Table) -> bool {
-        let has_update_triggers = table
-            .triggers()
-            .iter()
-            .any(|t| t.trigger_type() == TriggerType::Update);
+    fn output_table_triggers(&self, table: &Table) -> Result<(), GenerationError> {
+        let separator = self.context.settings().statement_separator();
 
-        let has_relations_with_triggers = !table.relations().is_empty()
-            && self.context.settings().foreign_key_mode() == ForeignKeyMode::Triggers;
+        if outputs_delete_trigger(self.context.settings(), table) {
+            self.output_delete_trigger(table, separator)?;
+        }
 
-        let has_aggregations = !table.aggregations().is_empty();
+        if should_output_update_trigger(self.context.settings(), table) {
+            self.output_update_trigger(table, separator)?;
+        }
 
-        has_update_triggers || has_relations_with_triggers || has_aggregations
+        Ok(())
     }
 
-}
-
-impl TriggerGenerator for PostgresTriggerGenerator {
-    fn output_triggers(&self) {
-        let database_model = self.context.settings().database_model();
-        let separator = self.context.settings().statement_separator();
-
-        for table in database_model.all_tables() {
-            if self.should_output_delete_trigger(table) && table.primary_key().is_some() {
-                self.output_delete_trigger(table, separator);
-            }
-
-            if self.should_output_update_trigger(table) {
-                self.output_update_trigger(table, separator);
-            }
+    fn trigger_names(&self, table: &Table) -> Vec<String> {
+        let table_name = self.context.settings().generated_name_part(table.name());
+        let mut names = Vec::new();
+        if outputs_delete_trigger(self.context.settings(), table) {
+            names.push(format!("{}_delete", table_name));
+        }
+        if should_output_update_trigger(self.context.settings(), table) {
+            names.push(format!("{}_update", table_name));
         }
+        names
+    }
+
+    fn possible_trigger_names(&self, table_name: &str) -> Vec<String> {
+        let table_name = self.context.settings().generated_name_part(table_name);
+        vec![format!("{}_delete", table_name), format!("{}_update", table_name)]
     }
 }
 
 impl PostgresTriggerGenerator {
-    fn output_delete_trigger(&self, table: &Table, separator: &str) {
+    fn output_delete_trigger(&self, table: &Table, separator: &str) -> Result<(), GenerationError> {
         let database_type = self.context.settings().database_type();
-        let table_name = table.name().to_lowercase();
+        let table_name = self.context.settings().generated_name_part(table.name());
         let fn_name = format!("{}_delete", table_name);
         let fully_qualified_table = table.fully_qualified_table_name(database_type);
         let fully_qualified_fn = format!(
@@ -71,16 +72,20 @@ impl PostgresTriggerGenerator {
             table.schema_name().unwrap_or(DatabaseType::Postgresql.default_schema().unwrap()),
             fn_name
         );
+        let settings = self.context.settings();
+        let quoted_table = settings.qualified_name(table.schema_name(), table.name());
+        let quoted_fn = settings.qualified_generated_name(table.schema_name(), &fn_name);
 
         self.context.with_writer(|writer| {
-            sql_println!(writer, "/* {} */", fully_qualified_fn);
+            writer.begin_object(StatementKind::Trigger, Some(&fully_qualified_fn));
+            sql_println!(writer, "/* {} */", fully_qualified_fn)?;
             sql_println!(
                 writer,
                 "create or replace function {}() returns trigger as $BODY$"
             ,
-                fully_qualified_fn
-            );
-            sql_println!(writer, "begin");
+                quoted_fn
+            )?;
+            sql_println!(writer, "begin")?;
 
             if self.context.settings().foreign_key_mode() == ForeignKeyMode::Triggers {
                 // A reverse relation carries the *original* relation's fields unchanged
@@ -93,148 +98,258 @@ impl PostgresTriggerGenerator {
                 for relation in table.reverse_relations() {
                     match relation.relation_type() {
                         RelationType::Enforce => {
-                            let child_table = self.database_model().find_table_by_qualified_name(relation.from_table_name());
+                            let child_table = resolve_table(self.database_model(), relation.from_table_name(), || format!("trigger {}", fully_qualified_fn))?;
                             sql_println!(
                                 writer,
-                                "   if (select count(*) from {} where {} = OLD.{}) > 0 then",
-                                child_table.fully_qualified_table_name(database_type),
-                                relation.from_column_name(),
-                                relation.to_column_name()
-                            );
+                                "   if (select count(*) from {} where {}) > 0 then",
+                                settings.qualified_name(child_table.schema_name(), child_table.name()),
+                                child_match_condition(settings, relation, "OLD")
+                            )?;
                             sql_println!(
                                 writer,
                                 "      raise exception 'The row in {} cannot be deleted. It is being used by a row in the {} table.';",
                                 fully_qualified_table,
                                 child_table.fully_qualified_table_name(database_type)
-                            );
-                            sql_println!(writer, "   end if;");
+                            )?;
+                            sql_println!(writer, "   end if;")?;
                         }
                         RelationType::SetNull => {
-                            let child_table = self.database_model().find_table_by_qualified_name(relation.from_table_name());
+                            let child_table = resolve_table(self.database_model(), relation.from_table_name(), || format!("trigger {}", fully_qualified_fn))?;
                             sql_println!(
                                 writer,
-                                "   update {} set {} = null where {} = OLD.{};",
-                                child_table.fully_qualified_table_name(database_type),
-                                relation.from_column_name(),
-                                relation.from_column_name(),
-                                relation.to_column_name()
-                            );
+                                "   update {} set {} where {};",
+                                settings.qualified_name(child_table.schema_name(), child_table.name()),
+                                relation
+                                    .from_column_names()
+                                    .iter()
+                                    .map(|c| format!("{} = null", settings.quote(c)))
+                                    .collect::<Vec<_>>()
+                                    .join(", "),
+                                child_match_condition(settings, relation, "OLD")
+                            )?;
                         }
                         RelationType::Cascade => {
-                            let child_table = self.database_model().find_table_by_qualified_name(relation.from_table_name());
+                            let child_table = resolve_table(self.database_model(), relation.from_table_name(), || format!("trigger {}", fully_qualified_fn))?;
                             sql_println!(
                                 writer,
-                                "   delete from {} where {} = OLD.{};",
-                                child_table.fully_qualified_table_name(database_type),
-                                relation.from_column_name(),
-                                relation.to_column_name()
-                            );
+                                "   delete from {} where {};",
+                                settings.qualified_name(child_table.schema_name(), child_table.name()),
+                                child_match_condition(settings, relation, "OLD")
+                            )?;
                         }
                         RelationType::DoNothing => {}
                     }
                 }
             }
 
+            self.output_aggregations(writer, table, "OLD", false, "   ")?;
+
             for custom_trigger in table.triggers() {
                 if custom_trigger.trigger_type() == TriggerType::Delete
                     && custom_trigger.database_type() == DatabaseType::Postgresql
                 {
-                    sql_println!(writer, "{}", custom_trigger.trigger_text());
+                    sql_println!(writer, "{}", custom_trigger.trigger_text())?;
                 }
             }
 
-            sql_println!(writer, "   return null;");
-            sql_println!(writer, "end;");
-            sql_println!(writer, "$BODY$ language plpgsql{}", separator);
-            sql_println!(writer, "");
+            sql_println!(writer, "   return null;")?;
+            sql_println!(writer, "end;")?;
+            sql_println!(writer, "$BODY$ language plpgsql{}", separator)?;
+            sql_println!(writer, "")?;
 
-            sql_println!(writer, "drop trigger if exists {} on {} cascade{}", table_name, fully_qualified_table, separator);
+            sql_println!(writer, "drop trigger if exists {} on {} cascade{}", settings.quote_generated(&fn_name), quoted_table, separator)?;
             sql_println!(
                 writer,
                 "create trigger {} after delete on {}",
-                table_name,
-                fully_qualified_table
-            );
-            sql_println!(writer, "   for each row execute procedure {}(){}", fully_qualified_fn, separator);
-            sql_println!(writer, "");
-        });
+                settings.quote_generated(&fn_name),
+                quoted_table
+            )?;
+            sql_println!(writer, "   for each row execute procedure {}(){}", quoted_fn, separator)?;
+            sql_println!(writer, "")?;
+            Ok(())
+        })
     }
 
-    fn output_update_trigger(&self, table: &Table, separator: &str) {
+    fn output_update_trigger(&self, table: &Table, separator: &str) -> Result<(), GenerationError> {
         let database_type = self.context.settings().database_type();
-        let table_name = table.name().to_lowercase();
+        let table_name = self.context.settings().generated_name_part(table.name());
         let fn_name = format!("{}_update", table_name);
-        let fully_qualified_table = table.fully_qualified_table_name(database_type);
         let fully_qualified_fn = format!(
             "{}.{}",
             table.schema_name().unwrap_or(DatabaseType::Postgresql.default_schema().unwrap()),
             fn_name
         );
+        let settings = self.context.settings();
+        let quoted_table = settings.qualified_name(table.schema_name(), table.name());
+        let quoted_fn = settings.qualified_generated_name(table.schema_name(), &fn_name);
 
         self.context.with_writer(|writer| {
-            sql_println!(writer, "/* {} */", fully_qualified_fn);
+            writer.begin_object(StatementKind::Trigger, Some(&fully_qualified_fn));
+            sql_println!(writer, "/* {} */", fully_qualified_fn)?;
             sql_println!(
                 writer,
                 "create or replace function {}() returns trigger as $BODY$"
             ,
-                fully_qualified_fn
-            );
-            sql_println!(writer, "begin");
+                quoted_fn
+            )?;
+            sql_println!(writer, "begin")?;
 
             if self.context.settings().foreign_key_mode() == ForeignKeyMode::Triggers {
                 for relation in table.relations() {
                     match relation.relation_type() {
                         RelationType::Enforce | RelationType::SetNull | RelationType::Cascade => {
-                            let to_table = self.database_model().find_table_by_qualified_name(relation.to_table_name());
+                            let to_table = resolve_table(self.database_model(), relation.to_table_name(), || format!("trigger {}", fully_qualified_fn))?;
                             sql_println!(
                                 writer,
-                                "   if new.{} is not null then",
-                                relation.from_column_name()
-                            );
+                                "   if {} then",
+                                relation
+                                    .from_column_names()
+                                    .iter()
+                                    .map(|c| format!("new.{} is not null", settings.quote(c)))
+                                    .collect::<Vec<_>>()
+                                    .join(" and ")
+                            )?;
                             sql_println!(
                                 writer,
-                                "      if (select count(*) from {} where {} = new.{}) = 0 then",
-                                to_table.fully_qualified_table_name(database_type),
-                                relation.to_column_name(),
-                                relation.from_column_name()
-                            );
+                                "      if (select count(*) from {} where {}) = 0 then",
+                                settings.qualified_name(to_table.schema_name(), to_table.name()),
+                                parent_match_condition(settings, relation, "new")
+                            )?;
                             sql_println!(
                                 writer,
                                 "         raise exception 'The value of {} was not found in the {} table.';",
-                                relation.from_column_name(),
+                                relation.from_columns_as_string(),
                                 to_table.fully_qualified_table_name(database_type)
-                            );
-                            sql_println!(writer, "      end if;");
-                            sql_println!(writer, "   end if;");
+                            )?;
+                            sql_println!(writer, "      end if;")?;
+                            sql_println!(writer, "   end if;")?;
                         }
                         RelationType::DoNothing => {}
                     }
                 }
             }
 
+            if !table.aggregations().is_empty() {
+                // An update moves the row's contribution: take the old values out of
+                // their bucket before adding the new ones.
+                sql_println!(writer, "   if TG_OP = 'UPDATE' then")?;
+                self.output_aggregations(writer, table, "OLD", false, "      ")?;
+                sql_println!(writer, "   end if;")?;
+                self.output_aggregations(writer, table, "NEW", true, "   ")?;
+            }
+
             for custom_trigger in table.triggers() {
                 if custom_trigger.trigger_type() == TriggerType::Update
                     && custom_trigger.database_type() == DatabaseType::Postgresql
                 {
-                    sql_println!(writer, "{}", custom_trigger.trigger_text());
+                    sql_println!(writer, "{}", custom_trigger.trigger_text())?;
                 }
             }
 
-            sql_println!(writer, "   return new;");
-            sql_println!(writer, "end;");
-            sql_println!(writer, "$BODY$ language plpgsql{}", separator);
-            sql_println!(writer, "");
+            sql_println!(writer, "   return new;")?;
+            sql_println!(writer, "end;")?;
+            sql_println!(writer, "$BODY$ language plpgsql{}", separator)?;
+            sql_println!(writer, "")?;
 
-            sql_println!(writer, "drop trigger if exists {} on {} cascade{}", table_name, fully_qualified_table, separator);
+            sql_println!(writer, "drop trigger if exists {} on {} cascade{}", settings.quote_generated(&fn_name), quoted_table, separator)?;
             sql_println!(
                 writer,
                 "create trigger {} after insert or update on {}",
-                table_name,
-                fully_qualified_table
+                settings.quote_generated(&fn_name),
+                quoted_table
+            )?;
+            sql_println!(writer, "   for each row execute procedure {}(){}", quoted_fn, separator)?;
+            sql_println!(writer, "")?;
+            Ok(())
+        })
+    }
+
+    /// Adds (`add`) or removes the contribution of the `row` record (`NEW`/`OLD`) to the
+    /// summary table of every aggregation on `table`. A bucket that doesn't exist yet is
+    /// created when adding; removing only ever touches an existing one.
+    fn output_aggregations(&self, writer: &mut SqlWriter, table: &Table, row: &str, add: bool, indent: &str) -> Result<(), GenerationError> {
+        let settings = self.context.settings();
+        let operator = if add { "+" } else { "-" };
+
+        for aggregation in table.aggregations() {
+            let destination = resolve_table(self.database_model(), aggregation.destination_table(), || {
+                format!("aggregation on {}", table.name())
+            })?;
+            let destination_table = settings.qualified_name(destination.schema_name(), destination.name());
+            let date_column = settings.quote(aggregation.date_column());
+            let time_stamp_column = settings.quote(aggregation.time_stamp_column());
+            let bucket = postgres_date_bucket(
+                aggregation.aggregation_frequency(),
+                &format!("{}.{}", row, date_column),
             );
-            sql_println!(writer, "   for each row execute procedure {}(){}", fully_qualified_fn, separator);
-            sql_println!(writer, "");
-        });
+
+            let mut condition = format!("{}.{} is not null", row, date_column);
+            if let Some(criteria) = aggregation.criteria() {
+                // Selecting the record as a one-row relation named after the table lets the
+                // criteria refer to the table's columns the same way it would in a query.
+                condition.push_str(&format!(
+                    " and exists (select 1 from (select {}.*) as {} where {})",
+                    row,
+                    settings.quote(table.name()),
+                    criteria
+                ));
+            }
+
+            let group_values: Vec<String> = aggregation
+                .aggregation_groups()
+                .iter()
+                .map(|group| aggregation_group_value(settings, table, group, row))
+                .collect::<Result<_, _>>()?;
+
+            let mut assignments: Vec<String> = aggregation
+                .aggregation_columns()
+                .iter()
+                .map(|column| {
+                    let destination_column = settings.quote(column.destination_column());
+                    format!(
+                        "{} = {} {} {}",
+                        destination_column,
+                        destination_column,
+                        operator,
+                        aggregation_row_value(settings, column, row)
+                    )
+                })
+                .collect();
+            assignments.push(format!("{} = current_timestamp", time_stamp_column));
+
+            let mut matches = vec![format!("{} = {}", date_column, bucket)];
+            for (group, value) in aggregation.aggregation_groups().iter().zip(group_values.iter()) {
+                matches.push(format!("{} is not distinct from {}", settings.quote(group.destination()), value));
+            }
+
+            sql_println!(writer, "{}if {} then", indent, condition)?;
+            sql_println!(writer, "{}   update {} set {}", indent, destination_table, assignments.join(", "))?;
+            sql_println!(writer, "{}      where {};", indent, matches.join(" and "))?;
+
+            if add {
+                let mut columns = vec![date_column.clone()];
+                let mut values = vec![bucket.clone()];
+                for (group, value) in aggregation.aggregation_groups().iter().zip(group_values.iter()) {
+                    columns.push(settings.quote(group.destination()));
+                    values.push(value.clone());
+                }
+                for column in aggregation.aggregation_columns() {
+                    columns.push(settings.quote(column.destination_column()));
+                    values.push(aggregation_row_value(settings, column, row));
+                }
+                columns.push(time_stamp_column.clone());
+                values.push("current_timestamp".to_string());
+
+                sql_println!(writer, "{}   if not found then", indent)?;
+                sql_println!(writer, "{}      insert into {} ({})", indent, destination_table, columns.join(", "))?;
+                sql_println!(writer, "{}         values ({});", indent, values.join(", "))?;
+                sql_println!(writer, "{}   end if;", indent)?;
+            }
+
+            sql_println!(writer, "{}end if;", indent)?;
+        }
+
+        Ok(())
     }
 
     fn database_model(&self) -> &schema_model::model::database_model::DatabaseModel {
@@ -242,14 +357,28 @@ impl PostgresTriggerGenerator {
     }
 }
 
+/// The first day of the bucket `value` falls into (weeks start on Monday).
+fn postgres_date_bucket(frequency: AggregationFrequency, value: &str) -> String {
+    let field = match frequency {
+        AggregationFrequency::Daily => "day",
+        AggregationFrequency::Weekly => "week",
+        AggregationFrequency::Monthly => "month",
+        AggregationFrequency::Yearly => "year",
+    };
+    format!("date_trunc('{}', {})::date", field, value)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::common::test_support::make_context_with_fk_mode;
+    use schema_model::model::aggregation::{
+        Aggregation, AggregationColumn, AggregationFrequency, AggregationGroup, AggregationType,
+    };
     use schema_model::builder::{ColumnBuilder, SchemaBuilder, TableBuilder};
     use schema_model::model::column_type::ColumnType;
     use schema_model::model::database_model::DatabaseModel;
-    use schema_model::model::relation::Relation;
+    use schema_model::model::relation::{Relation, RelationColumn};
     use schema_model::model::types::{BooleanMode, DatabaseType, ForeignKeyMode, RelationType};
 
     fn build_model_with_relation() -> DatabaseModel {
@@ -288,12 +417,12 @@ mod tests {
         let (ctx, buffer) = make_context_with_fk_mode(model, DatabaseType::Postgresql, ForeignKeyMode::Triggers);
 
         let generator = PostgresTriggerGenerator::new(ctx);
-        generator.output_triggers();
+        generator.output_triggers().unwrap();
 
         let output = buffer.contents();
         assert!(output.contains("create or replace function app.child_update() returns trigger"));
         assert!(output.contains("was not found in the app.parent table"));
-        assert!(output.contains("create trigger child after insert or update on app.child"));
+        assert!(output.contains("create trigger child_update after insert or update on app.child"));
     }
 
     #[test]
@@ -302,19 +431,42 @@ mod tests {
         let (ctx, buffer) = make_context_with_fk_mode(model, DatabaseType::Postgresql, ForeignKeyMode::Triggers);
 
         let generator = PostgresTriggerGenerator::new(ctx);
-        generator.output_triggers();
+        generator.output_triggers().unwrap();
 
         let output = buffer.contents();
         assert!(output.contains("was not found in the public.parent table"));
     }
 
+    #[test]
+    fn output_triggers_quotes_reserved_table_and_column_names() {
+        let user = TableBuilder::new(None::<&str>, "user")
+            .add_column(ColumnBuilder::new(None::<&str>, "id", ColumnType::Sequence).required(true).build())
+            .build();
+        let order = TableBuilder::new(None::<&str>, "order")
+            .add_column(ColumnBuilder::new(None::<&str>, "user", ColumnType::Int).build())
+            .add_relation(Relation::new("user", "id", "order", "user", RelationType::Enforce, false))
+            .build();
+        let schema = SchemaBuilder::new(None::<&str>).add_table(user).add_table(order).build();
+        let model = DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema]);
+        let (ctx, buffer) = make_context_with_fk_mode(model, DatabaseType::Postgresql, ForeignKeyMode::Triggers);
+
+        let generator = PostgresTriggerGenerator::new(ctx);
+        generator.output_triggers().unwrap();
+
+        let output = buffer.contents();
+        assert!(output.contains("create trigger order_update after insert or update on public.\"order\""));
+        assert!(output.contains("from public.\"user\""));
+        assert!(output.contains("new.\"user\""));
+        assert!(output.contains("was not found in the public.user table"));
+    }
+
     #[test]
     fn output_triggers_does_nothing_when_relations_mode_is_not_triggers() {
         let model = build_model_with_relation();
         let (ctx, buffer) = make_context_with_fk_mode(model, DatabaseType::Postgresql, ForeignKeyMode::Relations);
 
         let generator = PostgresTriggerGenerator::new(ctx);
-        generator.output_triggers();
+        generator.output_triggers().unwrap();
 
         assert_eq!(buffer.contents(), "");
     }
@@ -347,7 +499,7 @@ mod tests {
         let (ctx, buffer) = make_context_with_fk_mode(model, DatabaseType::Postgresql, ForeignKeyMode::Triggers);
 
         let generator = PostgresTriggerGenerator::new(ctx);
-        generator.output_triggers();
+        generator.output_triggers().unwrap();
 
         let output = buffer.contents();
         assert!(output.contains("create or replace function app.parent_delete"));
@@ -361,7 +513,7 @@ mod tests {
         let (ctx, buffer) = make_context_with_fk_mode(model, DatabaseType::Postgresql, ForeignKeyMode::Triggers);
 
         let generator = PostgresTriggerGenerator::new(ctx);
-        generator.output_triggers();
+        generator.output_triggers().unwrap();
 
         let output = buffer.contents();
         assert!(output.contains("update app.child set parent_id = null where parent_id = OLD.id;"));
@@ -373,7 +525,7 @@ mod tests {
         let (ctx, buffer) = make_context_with_fk_mode(model, DatabaseType::Postgresql, ForeignKeyMode::Triggers);
 
         let generator = PostgresTriggerGenerator::new(ctx);
-        generator.output_triggers();
+        generator.output_triggers().unwrap();
 
         let output = buffer.contents();
         assert!(output.contains("delete from app.child where parent_id = OLD.id;"));
@@ -407,11 +559,127 @@ mod tests {
         let (ctx, buffer) = make_context_with_fk_mode(model, DatabaseType::Postgresql, ForeignKeyMode::Triggers);
 
         let generator = PostgresTriggerGenerator::new(ctx);
-        generator.output_triggers();
+        generator.output_triggers().unwrap();
 
         let output = buffer.contents();
         assert!(output.contains("delete from app.child where parent_id = OLD.id;"));
     }
-}
 
+    #[test]
+    fn output_triggers_match_on_every_column_of_a_composite_relation() {
+        let columns = vec![
+            RelationColumn::new("tenant_id", "tenant_id"),
+            RelationColumn::new("parent_id", "id"),
+        ];
+        let mut parent = TableBuilder::new(Some("app"), "parent")
+            .add_column(ColumnBuilder::new(None::<&str>, "tenant_id", ColumnType::Int).required(true).build())
+            .add_column(ColumnBuilder::new(None::<&str>, "id", ColumnType::Int).required(true).build())
+            .add_key(
+                schema_model::builder::KeyBuilder::new(schema_model::model::types::KeyType::Primary)
+                    .add_column("tenant_id")
+                    .add_column("id")
+                    .build(),
+            )
+            .build();
+        parent.add_reverse_relation(Relation::new_composite("app.parent", "app.child", columns.clone(), RelationType::SetNull, false));
+
+        let child = TableBuilder::new(Some("app"), "child")
+            .add_column(ColumnBuilder::new(None::<&str>, "tenant_id", ColumnType::Int).build())
+            .add_column(ColumnBuilder::new(None::<&str>, "parent_id", ColumnType::Int).build())
+            .add_relation(Relation::new_composite("app.parent", "child", columns, RelationType::SetNull, false))
+            .build();
+        let schema = SchemaBuilder::new(Some("app"))
+            .add_table(parent)
+            .add_table(child)
+            .build();
+        let model = DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema]);
+        let (ctx, buffer) = make_context_with_fk_mode(model, DatabaseType::Postgresql, ForeignKeyMode::Triggers);
+
+        let generator = PostgresTriggerGenerator::new(ctx);
+        generator.output_triggers().unwrap();
+
+        let output = buffer.contents();
+        assert!(output.contains(
+            "update app.child set tenant_id = null, parent_id = null where tenant_id = OLD.tenant_id and parent_id = OLD.id;"
+        ));
+        assert!(output.contains("if new.tenant_id is not null and new.parent_id is not null then"));
+        assert!(output.contains("if (select count(*) from app.parent where tenant_id = new.tenant_id and id = new.parent_id) = 0 then"));
+    }
+
+    fn build_model_with_aggregation(criteria: Option<&str>) -> DatabaseModel {
+        let summary = TableBuilder::new(Some("app"), "sale_summary")
+            .add_column(ColumnBuilder::new(None::<&str>, "sale_date", ColumnType::Date).required(true).build())
+            .add_column(ColumnBuilder::new(None::<&str>, "store_id", ColumnType::Int).build())
+            .add_column(ColumnBuilder::new(None::<&str>, "total", ColumnType::Decimal).build())
+            .add_column(ColumnBuilder::new(None::<&str>, "sale_count", ColumnType::Int).build())
+            .add_column(ColumnBuilder::new(None::<&str>, "updated_at", ColumnType::Timestamp).build())
+            .build();
+        let sale = TableBuilder::new(Some("app"), "sale")
+            .add_column(ColumnBuilder::new(None::<&str>, "sale_date", ColumnType::Date).required(true).build())
+            .add_column(ColumnBuilder::new(None::<&str>, "store_id", ColumnType::Int).build())
+            .add_column(ColumnBuilder::new(None::<&str>, "amount", ColumnType::Decimal).build())
+            .add_aggregation(Aggregation::new(
+                "app.sale_summary",
+                "sale_date",
+                criteria,
+                "updated_at",
+                AggregationFrequency::Monthly,
+                vec![
+                    AggregationColumn::new(AggregationType::Sum, "amount", "total"),
+                    AggregationColumn::new(AggregationType::Count, "", "sale_count"),
+                ],
+                vec![AggregationGroup::new("store_id", "store_id", None)],
+            ))
+            .build();
+        let schema = SchemaBuilder::new(Some("app"))
+            .add_table(summary)
+            .add_table(sale)
+            .build();
+        DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema])
+    }
+
+    #[test]
+    fn output_triggers_maintain_the_aggregation_summary_table() {
+        let model = build_model_with_aggregation(None);
+        let (ctx, buffer) = make_context_with_fk_mode(model, DatabaseType::Postgresql, ForeignKeyMode::Relations);
+
+        let generator = PostgresTriggerGenerator::new(ctx);
+        generator.output_triggers().unwrap();
 
+        let output = buffer.contents();
+        // delete: the old row's contribution is removed from its bucket
+        assert!(output.contains("create or replace function app.sale_delete()"));
+        assert!(output.contains(
+            "update app.sale_summary set total = total - coalesce(OLD.amount, 0), sale_count = sale_count - 1, updated_at = current_timestamp"
+        ));
+        assert!(output.contains(
+            "where sale_date = date_trunc('month', OLD.sale_date)::date and store_id is not distinct from OLD.store_id;"
+        ));
+        // insert/update: the old contribution is only removed on update, then the new one is added
+        assert!(output.contains("if TG_OP = 'UPDATE' then"));
+        assert!(output.contains(
+            "update app.sale_summary set total = total + coalesce(NEW.amount, 0), sale_count = sale_count + 1, updated_at = current_timestamp"
+        ));
+        assert!(output.contains("insert into app.sale_summary (sale_date, store_id, total, sale_count, updated_at)"));
+        assert!(output.contains(
+            "values (date_trunc('month', NEW.sale_date)::date, NEW.store_id, coalesce(NEW.amount, 0), 1, current_timestamp);"
+        ));
+        // the delete and update triggers must not share a name
+        assert!(output.contains("create trigger sale_delete after delete on app.sale"));
+        assert!(output.contains("create trigger sale_update after insert or update on app.sale"));
+    }
+
+    #[test]
+    fn output_triggers_apply_aggregation_criteria_to_the_row() {
+        let model = build_model_with_aggregation(Some("amount > 0"));
+        let (ctx, buffer) = make_context_with_fk_mode(model, DatabaseType::Postgresql, ForeignKeyMode::Relations);
+
+        let generator = PostgresTriggerGenerator::new(ctx);
+        generator.output_triggers().unwrap();
+
+        let output = buffer.contents();
+        assert!(output.contains(
+            "if NEW.sale_date is not null and exists (select 1 from (select NEW.*) as sale where amount > 0) then"
+        ));
+    }
+}
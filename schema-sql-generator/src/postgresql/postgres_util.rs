@@ -1,3 +1,6 @@
+use crate::common::sql_string::escape_sql_literal;
+use schema_model::model::partition::PartitionBound;
+
 pub fn to_snake_case(s: &str) -> String {
     let mut result = String::new();
     let mut prev_underscore = false;
@@ -13,10 +16,55 @@ pub fn to_snake_case(s: &str) -> String {
     result
 }
 
+/// The `for values ...` (or `default`) clause of a `partition of` table.
+pub fn partition_bound_sql(bound: &PartitionBound) -> String {
+    match bound {
+        PartitionBound::Range { from, to } => format!(
+            "for values from ({}) to ({})",
+            bound_values_sql(from),
+            bound_values_sql(to)
+        ),
+        PartitionBound::List(values) => format!("for values in ({})", bound_values_sql(values)),
+        PartitionBound::Hash { modulus, remainder } => {
+            format!("for values with (modulus {}, remainder {})", modulus, remainder)
+        }
+        PartitionBound::Default => "default".to_string(),
+    }
+}
+
+/// Numbers and `minvalue`/`maxvalue` are written as they are; anything else is quoted and
+/// left for Postgres to cast to the partition key's type.
+fn bound_values_sql(values: &[String]) -> String {
+    values
+        .iter()
+        .map(|value| {
+            if value.parse::<f64>().is_ok() || value.eq_ignore_ascii_case("minvalue") || value.eq_ignore_ascii_case("maxvalue") {
+                value.to_lowercase()
+            } else {
+                format!("'{}'", escape_sql_literal(value))
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn partition_bound_sql_quotes_only_non_numeric_values() {
+        let range = PartitionBound::Range {
+            from: vec!["MINVALUE".to_string()],
+            to: vec!["2024-01-01".to_string()],
+        };
+        assert_eq!(partition_bound_sql(&range), "for values from (minvalue) to ('2024-01-01')");
+        let list = PartitionBound::List(vec!["us".to_string(), "o'hare".to_string(), "7".to_string()]);
+        assert_eq!(partition_bound_sql(&list), "for values in ('us', 'o''hare', 7)");
+        let hash = PartitionBound::Hash { modulus: 4, remainder: 1 };
+        assert_eq!(partition_bound_sql(&hash), "for values with (modulus 4, remainder 1)");
+    }
+
     #[test]
     fn test_to_snake_case() {
         assert_eq!(to_snake_case("GenderType"), "gender_type");
@@ -1,6 +1,10 @@
+use crate::common::generated_statement::StatementKind;
+use crate::common::generation_error::GenerationError;
 use crate::common::generator_context::GeneratorContext;
+use crate::common::index_generator::{index_columns_sql, index_name};
 use crate::common::view_generator::ViewGenerator;
 use crate::sql_println;
+use schema_model::model::types::RefreshPolicy;
 use schema_model::model::view::View;
 
 pub struct PostgresViewGenerator {
@@ -14,7 +18,7 @@ impl PostgresViewGenerator {
 }
 
 impl ViewGenerator for PostgresViewGenerator {
-    fn output_views(&self) {
+    fn output_views(&self) -> Result<(), GenerationError> {
         let database_model = self.context.settings().database_model();
         let database_type = self.context.settings().database_type();
         let separator = self.context.settings().statement_separator();
@@ -26,16 +30,49 @@ impl ViewGenerator for PostgresViewGenerator {
             .collect();
 
         if !views.is_empty() {
-            self.context.with_writer(|writer| {
+            self.context.with_writer(|writer| -> Result<(), GenerationError> {
                 for view in views {
-                    let view_name = view.fully_qualified_view_name(database_type);
-                    sql_println!(writer, "/* {} */", view_name);
-                    sql_println!(writer, "create or replace view {} as", view_name);
-                    sql_println!(writer, "   {}{}", view.sql(), separator);
-                    sql_println!(writer, "");
+                    let view_name = self.context.settings().qualified_name(view.schema_name(), view.name());
+                    writer.begin_object(StatementKind::View, Some(&view.fully_qualified_view_name(database_type)));
+                    sql_println!(writer, "/* {} */", view_name)?;
+                    if view.is_materialized() {
+                        // There is no `create or replace` for a materialized view, so it is
+                        // dropped (taking its indexes with it) and built again.
+                        sql_println!(writer, "drop materialized view if exists {}{}", view_name, separator)?;
+                        sql_println!(writer, "create materialized view {} as", view_name)?;
+                        sql_println!(writer, "   {}{}", view.sql(), separator)?;
+                        for (position, key) in view.indexes().iter().enumerate() {
+                            sql_println!(
+                                writer,
+                                "create {}index {} on {}{} ({}){}{}",
+                                if key.is_unique() { "unique " } else { "" },
+                                self.context.settings().quote_generated(&index_name(self.context.settings(), view.name(), key, position + 1)),
+                                view_name,
+                                key.method().map(|method| format!(" using {}", method.sql())).unwrap_or_default(),
+                                index_columns_sql(self.context.settings(), view.name(), key)?,
+                                key.filter().map(|filter| format!(" where {}", filter)).unwrap_or_default(),
+                                separator
+                            )?;
+                        }
+                        sql_println!(writer, "/* refresh with: {}{} */", refresh_sql(&view, &view_name), separator)?;
+                    } else {
+                        sql_println!(writer, "create or replace view {} as", view_name)?;
+                        sql_println!(writer, "   {}{}", view.sql(), separator)?;
+                    }
+                    sql_println!(writer, "")?;
                 }
-            });
+                Ok(())
+            })?;
         }
+
+        Ok(())
+    }
+}
+
+fn refresh_sql(view: &View, view_name: &str) -> String {
+    match view.refresh_policy() {
+        RefreshPolicy::Manual => format!("refresh materialized view {}", view_name),
+        RefreshPolicy::Concurrent => format!("refresh materialized view concurrently {}", view_name),
     }
 }
 
@@ -46,7 +83,8 @@ mod tests {
     use schema_model::builder::SchemaBuilder;
     use schema_model::model::database_model::DatabaseModel;
     use schema_model::model::types::DatabaseType as ModelDatabaseType;
-    use schema_model::model::types::{BooleanMode, ForeignKeyMode};
+    use schema_model::model::key::{Key, KeyColumn};
+    use schema_model::model::types::{BooleanMode, ForeignKeyMode, KeyType, SortOrder};
 
     #[test]
     fn output_views_renders_create_or_replace_view() {
@@ -56,7 +94,7 @@ mod tests {
         let (ctx, buffer) = make_context(model, ModelDatabaseType::Postgresql);
 
         let generator = PostgresViewGenerator::new(ctx);
-        generator.output_views();
+        generator.output_views().unwrap();
 
         let output = buffer.contents();
         assert!(output.contains("create or replace view app.active_users as"));
@@ -71,11 +109,39 @@ mod tests {
         let (ctx, buffer) = make_context(model, ModelDatabaseType::Postgresql);
 
         let generator = PostgresViewGenerator::new(ctx);
-        generator.output_views();
+        generator.output_views().unwrap();
 
         assert!(buffer.contents().contains("create or replace view public.active_users as"));
     }
 
+    #[test]
+    fn output_views_renders_materialized_view_with_indexes_and_refresh() {
+        let unique = Key::new_full(KeyType::Index, vec![KeyColumn::new("customer_id")], false, false, true, None::<String>);
+        let by_total = Key::new(KeyType::Index, vec![KeyColumn::new("total").with_sort_order(Some(SortOrder::Desc))]);
+        let view = View::new_materialized(
+            Some("app"),
+            "order_totals",
+            "select customer_id, sum(amount) as total from orders group by customer_id",
+            None,
+            RefreshPolicy::Concurrent,
+            vec![unique, by_total],
+        );
+        let schema = SchemaBuilder::new(Some("app")).add_view(view).build();
+        let model = DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema]);
+        let (ctx, buffer) = make_context(model, ModelDatabaseType::Postgresql);
+
+        let generator = PostgresViewGenerator::new(ctx);
+        generator.output_views().unwrap();
+
+        let output = buffer.contents();
+        assert!(output.contains("drop materialized view if exists app.order_totals;"));
+        assert!(output.contains("create materialized view app.order_totals as"));
+        assert!(output.contains("create unique index ix_order_totals1 on app.order_totals (customer_id);"));
+        assert!(output.contains("create index ix_order_totals2 on app.order_totals (total desc);"));
+        assert!(output.contains("/* refresh with: refresh materialized view concurrently app.order_totals; */"));
+        assert!(!output.contains("create or replace view"));
+    }
+
     #[test]
     fn output_views_does_nothing_when_no_views() {
         let schema = SchemaBuilder::new(None::<&str>).build();
@@ -83,7 +149,7 @@ mod tests {
         let (ctx, buffer) = make_context(model, ModelDatabaseType::Postgresql);
 
         let generator = PostgresViewGenerator::new(ctx);
-        generator.output_views();
+        generator.output_views().unwrap();
 
         assert_eq!(buffer.contents(), "");
     }
@@ -8,7 +8,9 @@ mod sqlite_key_generator;
 mod sqlite_other_sql_generator;
 mod sqlite_procedure_generator;
 mod sqlite_relation_generator;
+mod sqlite_sequence_generator;
 mod sqlite_table_constraint_generator;
 mod sqlite_table_generator;
-mod sqlite_trigger_generator;
+pub mod sqlite_trigger_generator;
+mod sqlite_drop_generator;
 mod sqlite_view_generator;
@@ -1,4 +1,5 @@
 use crate::common::column_constraint_generator::{ColumnConstraintGenerator, DefaultColumnConstraintGenerator};
+use crate::common::generation_error::GenerationError;
 use crate::common::generator_context::GeneratorContext;
 use schema_model::model::table::Table;
 
@@ -15,7 +16,7 @@ impl SqliteColumnConstraintGenerator {
 }
 
 impl ColumnConstraintGenerator for SqliteColumnConstraintGenerator {
-    fn column_check_constraints(&self, table: &Table) -> Vec<String> {
+    fn column_check_constraints(&self, table: &Table) -> Result<Vec<String>, GenerationError> {
         self.column_constraint_generator.column_check_constraints(table)
     }
 }
@@ -44,7 +45,7 @@ mod tests {
         let (ctx, _buffer) = make_context(model, DatabaseType::Sqlite);
 
         let generator = SqliteColumnConstraintGenerator::new(ctx);
-        let constraints = generator.column_check_constraints(&table);
+        let constraints = generator.column_check_constraints(&table).unwrap();
 
         assert_eq!(constraints.len(), 1);
         assert!(constraints[0].contains("check(price >= 0 and price <= 100)"));
@@ -60,7 +61,7 @@ mod tests {
         let (ctx, _buffer) = make_context(model, DatabaseType::Sqlite);
 
         let generator = SqliteColumnConstraintGenerator::new(ctx);
-        let constraints = generator.column_check_constraints(&table);
+        let constraints = generator.column_check_constraints(&table).unwrap();
 
         assert!(constraints.is_empty());
     }
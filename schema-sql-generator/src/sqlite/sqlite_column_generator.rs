@@ -1,4 +1,5 @@
 use crate::common::column_generator::{ColumnGenerator, DefaultColumnGenerator, DefaultConstraintNaming};
+use crate::common::generation_error::GenerationError;
 use crate::common::generator_context::GeneratorContext;
 use crate::sqlite::sqlite_column_type_generator::SqliteColumnTypeGenerator;
 use schema_model::model::column::Column;
@@ -21,19 +22,38 @@ impl SqliteColumnGenerator {
 }
 
 impl ColumnGenerator for SqliteColumnGenerator {
-    fn column_definitions(&self, table: &Table) -> Vec<String> {
-        self.column_generator.column_definitions(table)
+    fn column_definitions(&self, table: &Table) -> Result<Vec<String>, GenerationError> {
+        table
+            .columns()
+            .iter()
+            .map(|column| self.column_sql(table, column))
+            .collect()
     }
 
-    fn column_sql(&self, table: &Table, column: &Column) -> String {
-        self.column_generator.column_sql(table, column)
+    /// A sequence column that is the whole primary key becomes an alias for the rowid,
+    /// which is the only column SQLite will auto-increment. The alias must be declared as
+    /// exactly `integer primary key`; `autoincrement` keeps deleted ids from being reused.
+    fn column_sql(&self, table: &Table, column: &Column) -> Result<String, GenerationError> {
+        let is_rowid_alias = table
+            .rowid_alias_column()
+            .is_some_and(|alias| alias.name() == column.name());
+        if !is_rowid_alias {
+            return self.column_generator.column_sql(table, column);
+        }
+
+        let column_options = self.column_options(table, column)?;
+        let column_name = self.column_generator.context().settings().quote(column.name());
+        if column_options.is_empty() {
+            return Ok(format!("   {} integer primary key autoincrement", column_name));
+        }
+        Ok(format!("   {} integer primary key autoincrement {}", column_name, column_options))
     }
 
-    fn column_options(&self, table: &Table, column: &Column) -> String {
+    fn column_options(&self, table: &Table, column: &Column) -> Result<String, GenerationError> {
         self.column_generator.column_options(table, column)
     }
 
-    fn default_value(&self, table: &Table, column: &Column) -> Option<String> {
+    fn default_value(&self, table: &Table, column: &Column) -> Result<Option<String>, GenerationError> {
         self.column_generator.default_value(table, column)
     }
 }
@@ -1,7 +1,12 @@
-use crate::common::column_type_generator::ColumnTypeGenerator;
+use crate::common::column_type_generator::{standard_column_type_sql, ColumnTypeGenerator};
+use crate::common::generation_error::GenerationError;
 use crate::common::generator_context::GeneratorContext;
 use schema_model::model::column::Column;
+use schema_model::model::column_type::ColumnType;
 use schema_model::model::schema::Schema;
+use schema_model::model::sequence::Sequence;
+use schema_model::model::table::Table;
+use schema_model::model::types::{BooleanMode, TableOption};
 
 pub struct SqliteColumnTypeGenerator {
     context: GeneratorContext
@@ -20,12 +25,36 @@ impl ColumnTypeGenerator for SqliteColumnTypeGenerator {
         &self.context
     }
 
-    fn sequence_sql(&self) -> String {
-        "integer auto_increment".to_string()
+    /// A strict table only accepts the type names `int`, `integer`, `real`, `text`, `blob`
+    /// and `any`, so each column type is mapped onto the one holding its values.
+    fn column_type_sql(&self, table: &Table, column: &Column) -> Result<String, GenerationError> {
+        if !table.has_option(TableOption::Strict) {
+            return standard_column_type_sql(self, table, column);
+        }
+
+        Ok(match column.column_type() {
+            ColumnType::Sequence
+            | ColumnType::LongSequence
+            | ColumnType::Byte
+            | ColumnType::Short
+            | ColumnType::Int
+            | ColumnType::Long => "integer".to_string(),
+            ColumnType::Float | ColumnType::Double | ColumnType::Decimal => "real".to_string(),
+            ColumnType::Boolean if self.context.settings().boolean_mode() == BooleanMode::Native => "integer".to_string(),
+            ColumnType::Binary => self.binary_sql(),
+            ColumnType::Array => self.array_sql(table, column)?,
+            _ => "text".to_string(),
+        })
+    }
+
+    /// SQLite integers are all 64-bit; the column only auto-increments as the rowid alias
+    /// `SqliteColumnGenerator` declares for a sequence primary key.
+    fn sequence_sql(&self, _column: &Column) -> String {
+        "integer".to_string()
     }
 
-    fn long_sequence_sql(&self) -> String {
-        "integer auto_increment".to_string()
+    fn long_sequence_sql(&self, _column: &Column) -> String {
+        "integer".to_string()
     }
 
     fn text_sql(&self, _column: &Column) -> String {
@@ -48,8 +77,17 @@ impl ColumnTypeGenerator for SqliteColumnTypeGenerator {
         "uuidv4()".to_string()
     }
 
-    fn array_sql(&self, _column: &Column) -> String {
-        panic!("SQLite does not support arrays")
+    /// SQLite defaults cannot have side effects, so a column drawing from an (emulated)
+    /// sequence gets no default; the application takes the value from the sequence table.
+    fn sequence_default_value_sql(&self, _sequence: &Sequence) -> Option<String> {
+        None
+    }
+
+    fn array_sql(&self, table: &Table, column: &Column) -> Result<String, GenerationError> {
+        Err(GenerationError::unsupported(
+            format!("column {}.{}", table.name(), column.name()),
+            "SQLite does not support arrays",
+        ))
     }
 
     fn date_sql(&self) -> String {
@@ -92,15 +130,13 @@ mod tests {
     use schema_model::model::types::{BooleanMode, DatabaseType, ForeignKeyMode};
     use std::cell::RefCell;
     use std::rc::Rc;
+    use std::sync::Arc;
 
     fn make_context(model: DatabaseModel) -> (GeneratorContext, TableBuilder) {
         let table = TableBuilder::new(None::<&str>, "test");
-        let options = GenerateOptions::new(
-            Rc::new(model),
-            Rc::new(RefCell::new(PrintWriter::new(Box::new(Vec::<u8>::new())))),
-        );
+        let options = GenerateOptions::new(Arc::new(model));
         let settings = SqlGeneratorSettings::new(DatabaseType::Sqlite, &options);
-        let writer = SqlWriter::new(options.writer.clone());
+        let writer = SqlWriter::new(Rc::new(RefCell::new(PrintWriter::new(Box::new(Vec::<u8>::new())))));
         let ctx = GeneratorContext::new(settings, writer);
         (ctx, table)
     }
@@ -116,13 +152,13 @@ mod tests {
         let generator = SqliteColumnTypeGenerator::new(ctx);
         let table = table_builder.build();
         let col = ColumnBuilder::new(None::<&str>, "col", column_type).build();
-        assert_eq!(generator.column_type_sql(&table, &col), expected);
+        assert_eq!(generator.column_type_sql(&table, &col).unwrap(), expected);
     }
 
     #[test]
     fn sequence_types() {
-        assert_type(ColumnType::Sequence, "integer auto_increment");
-        assert_type(ColumnType::LongSequence, "integer auto_increment");
+        assert_type(ColumnType::Sequence, "integer");
+        assert_type(ColumnType::LongSequence, "integer");
     }
 
     #[test]
@@ -162,15 +198,35 @@ mod tests {
     }
 
     #[test]
-    fn array_sql_panics() {
+    fn strict_tables_use_strict_type_names() {
+        let model = make_model_default();
+        let (ctx, table_builder) = make_context(model);
+        let generator = SqliteColumnTypeGenerator::new(ctx);
+        let table = table_builder.add_option(TableOption::Strict).build();
+        let strict_type = |column_type: ColumnType| {
+            let col = ColumnBuilder::new(None::<&str>, "col", column_type).length(20).build();
+            generator.column_type_sql(&table, &col).unwrap()
+        };
+
+        assert_eq!(strict_type(ColumnType::Sequence), "integer");
+        assert_eq!(strict_type(ColumnType::Short), "integer");
+        assert_eq!(strict_type(ColumnType::Boolean), "integer");
+        assert_eq!(strict_type(ColumnType::Decimal), "real");
+        assert_eq!(strict_type(ColumnType::Double), "real");
+        assert_eq!(strict_type(ColumnType::Varchar), "text");
+        assert_eq!(strict_type(ColumnType::Timestamp), "text");
+        assert_eq!(strict_type(ColumnType::Uuid), "text");
+        assert_eq!(strict_type(ColumnType::Binary), "blob");
+    }
+
+    #[test]
+    fn array_sql_is_unsupported() {
         let model = make_model_default();
         let (ctx, table_builder) = make_context(model);
         let generator = SqliteColumnTypeGenerator::new(ctx);
         let table = table_builder.build();
         let col = ColumnBuilder::new(None::<&str>, "col", ColumnType::Array).build();
-        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
-            generator.column_type_sql(&table, &col)
-        }));
-        assert!(result.is_err());
+        let error = generator.column_type_sql(&table, &col).unwrap_err();
+        assert!(error.to_string().contains("SQLite does not support arrays"));
     }
 }
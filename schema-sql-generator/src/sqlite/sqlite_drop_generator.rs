@@ -0,0 +1,141 @@
+use crate::common::drop_generator::{output_drop_steps, DefaultDropGenerator, DropGenerator};
+use crate::common::generated_statement::StatementKind;
+use crate::common::generation_error::GenerationError;
+use crate::common::generator_context::GeneratorContext;
+use crate::common::trigger_generator::TriggerGenerator;
+use crate::sql_println;
+use crate::sqlite::sqlite_trigger_generator::SqliteTriggerGenerator;
+
+pub struct SqliteDropGenerator {
+    context: GeneratorContext,
+    drop_generator: DefaultDropGenerator,
+    trigger_generator: SqliteTriggerGenerator,
+}
+
+impl SqliteDropGenerator {
+    pub fn new(context: GeneratorContext) -> Self {
+        Self {
+            context: context.clone(),
+            drop_generator: DefaultDropGenerator::new(context.clone()),
+            trigger_generator: SqliteTriggerGenerator::new(context),
+        }
+    }
+}
+
+impl DropGenerator for SqliteDropGenerator {
+    fn output_drops(&self) -> Result<(), GenerationError> {
+        output_drop_steps(self, self.context.settings().foreign_key_mode())
+    }
+
+    fn output_drop_procedures(&self) -> Result<(), GenerationError> {
+        // SQLite has no stored procedures or functions to drop.
+        Ok(())
+    }
+
+    fn output_drop_views(&self) -> Result<(), GenerationError> {
+        self.drop_generator.output_drop_views()
+    }
+
+    fn output_drop_functions(&self) -> Result<(), GenerationError> {
+        Ok(())
+    }
+
+    fn output_drop_triggers(&self) -> Result<(), GenerationError> {
+        let settings = self.context.settings();
+        let separator = settings.statement_separator();
+
+        self.context.with_writer(|writer| {
+            for table in settings.database_model().all_tables().into_iter().rev() {
+                for trigger_name in self.trigger_generator.trigger_names(table) {
+                    writer.begin_object(StatementKind::Trigger, Some(&settings.database_type().qualified_name(table.schema_name(), &trigger_name)));
+                    sql_println!(writer, "drop trigger if exists {}{}", settings.qualified_generated_name(table.schema_name(), &trigger_name), separator)?;
+                }
+            }
+            Ok(())
+        })
+    }
+
+    fn output_drop_relations(&self) -> Result<(), GenerationError> {
+        // Foreign keys are declared inside `create table` and go with the table; SQLite
+        // cannot drop a constraint on its own.
+        Ok(())
+    }
+
+    fn output_drop_tables(&self) -> Result<(), GenerationError> {
+        self.drop_generator.output_drop_tables()
+    }
+
+    fn output_drop_enum_types(&self) -> Result<(), GenerationError> {
+        self.drop_generator.output_drop_enum_types()
+    }
+
+    fn output_drop_sequences(&self) -> Result<(), GenerationError> {
+        self.drop_generator.output_drop_sequences()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::test_support::{make_context, make_context_with_fk_mode};
+    use schema_model::builder::{ColumnBuilder, SchemaBuilder, TableBuilder};
+    use schema_model::model::column_type::ColumnType;
+    use schema_model::model::database_model::DatabaseModel;
+    use schema_model::model::relation::Relation;
+    use schema_model::model::sequence::Sequence;
+    use schema_model::model::types::{BooleanMode, DatabaseType, ForeignKeyMode, RelationType};
+    use schema_model::model::view::View;
+
+    #[test]
+    fn output_drops_drops_children_first_without_altering_constraints() {
+        let parent = TableBuilder::new(None::<&str>, "parent")
+            .add_column(ColumnBuilder::new(None::<&str>, "id", ColumnType::Int).required(true).build())
+            .build();
+        let child = TableBuilder::new(None::<&str>, "child")
+            .add_column(ColumnBuilder::new(None::<&str>, "parent_id", ColumnType::Int).build())
+            .add_relation(Relation::new("parent", "id", "child", "parent_id", RelationType::Cascade, false))
+            .build();
+        let schema = SchemaBuilder::new(None::<&str>)
+            .add_table(child)
+            .add_table(parent)
+            .add_view(View::new(None::<&str>, "parents", "select * from parent", None))
+            .add_sequence(Sequence::new(None, "invoice_number", None, None, None, None, false, None))
+            .build();
+        let model = DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema]);
+        let (ctx, buffer) = make_context(model, DatabaseType::Sqlite);
+
+        SqliteDropGenerator::new(ctx).output_drops().unwrap();
+
+        assert_eq!(
+            buffer.contents(),
+            "drop view if exists parents;\n\
+             drop table if exists child;\n\
+             drop table if exists parent;\n\
+             drop table if exists invoice_number;\n"
+        );
+    }
+
+    #[test]
+    fn output_drops_drops_the_foreign_key_triggers_before_the_tables() {
+        let parent = TableBuilder::new(None::<&str>, "parent")
+            .add_column(ColumnBuilder::new(None::<&str>, "id", ColumnType::Int).required(true).build())
+            .build();
+        let child = TableBuilder::new(None::<&str>, "child")
+            .add_column(ColumnBuilder::new(None::<&str>, "parent_id", ColumnType::Int).build())
+            .add_relation(Relation::new("parent", "id", "child", "parent_id", RelationType::Enforce, false))
+            .build();
+        let schema = SchemaBuilder::new(None::<&str>).add_table(parent).add_table(child).build();
+        let model = DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Triggers, vec![schema]);
+        let (ctx, buffer) = make_context_with_fk_mode(model, DatabaseType::Sqlite, ForeignKeyMode::Triggers);
+
+        SqliteDropGenerator::new(ctx).output_drops().unwrap();
+
+        assert_eq!(
+            buffer.contents(),
+            "drop trigger if exists child_insert;\n\
+             drop trigger if exists child_update;\n\
+             drop table if exists child;\n\
+             drop table if exists parent;\n"
+        );
+    }
+}
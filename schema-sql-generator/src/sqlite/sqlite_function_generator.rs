@@ -1,4 +1,5 @@
 use crate::common::function_generator::{DefaultFunctionGenerator, FunctionGenerator};
+use crate::common::generation_error::GenerationError;
 use crate::common::generator_context::GeneratorContext;
 use crate::common::sql_writer::SqlWriter;
 use schema_model::model::function::Function;
@@ -16,11 +17,11 @@ impl SqliteFunctionGenerator {
 }
 
 impl FunctionGenerator for SqliteFunctionGenerator {
-    fn output_functions(&self) {
-        self.function_generator.output_functions();
+    fn output_functions(&self) -> Result<(), GenerationError> {
+        self.function_generator.output_functions()
     }
 
-    fn output_function(&self, writer: &mut SqlWriter, statement_separator: &str, function: &Function) {
-        self.function_generator.output_function(writer, statement_separator, function);
+    fn output_function(&self, writer: &mut SqlWriter, statement_separator: &str, function: &Function) -> Result<(), GenerationError> {
+        self.function_generator.output_function(writer, statement_separator, function)
     }
 }
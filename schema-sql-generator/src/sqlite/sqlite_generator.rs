@@ -1,10 +1,13 @@
+use crate::common::generation_error::GenerationError;
 use crate::common::generator_context::GeneratorContext;
 use crate::common::sql_generator::{DefaultSqlGenerator, SqlGenerator};
+use crate::sqlite::sqlite_drop_generator::SqliteDropGenerator;
 use crate::sqlite::sqlite_function_generator::SqliteFunctionGenerator;
 use crate::sqlite::sqlite_index_generator::SqliteIndexGenerator;
 use crate::sqlite::sqlite_other_sql_generator::SqliteOtherSqlGenerator;
 use crate::sqlite::sqlite_procedure_generator::SqliteProcedureGenerator;
 use crate::sqlite::sqlite_relation_generator::SqliteRelationGenerator;
+use crate::sqlite::sqlite_sequence_generator::SqliteSequenceGenerator;
 use crate::sqlite::sqlite_table_generator::SqliteTableGenerator;
 use crate::sqlite::sqlite_trigger_generator::SqliteTriggerGenerator;
 use crate::sqlite::sqlite_view_generator::SqliteViewGenerator;
@@ -25,6 +28,8 @@ impl SqliteGenerator {
             Box::new(SqliteProcedureGenerator::new(context.clone())),
             Box::new(SqliteTriggerGenerator::new(context.clone())),
             Box::new(SqliteOtherSqlGenerator::new(context.clone())),
+            Box::new(SqliteSequenceGenerator::new(context.clone())),
+            Box::new(SqliteDropGenerator::new(context.clone())),
         );
 
         Self {
@@ -38,51 +43,59 @@ impl SqlGenerator for SqliteGenerator {
         self.sql_generator.context()
     }
 
-    fn generate(&self) {
+    fn generate(&self) -> Result<(), GenerationError> {
         self.sql_generator.generate()
     }
 
-    fn output_sql(&self) {
-        self.sql_generator.output_sql();
+    fn output_sql(&self) -> Result<(), GenerationError> {
+        self.sql_generator.output_sql()
     }
 
-    fn output_header(&self) {
-        self.sql_generator.output_header();
+    fn output_header(&self) -> Result<(), GenerationError> {
+        self.sql_generator.output_header()
     }
 
-    fn output_tables(&self) {
-        self.sql_generator.output_tables();
+    fn output_sequences(&self) -> Result<(), GenerationError> {
+        self.sql_generator.output_sequences()
     }
 
-    fn output_relations(&self) {
-        self.sql_generator.output_relations();
+    fn output_tables(&self) -> Result<(), GenerationError> {
+        self.sql_generator.output_tables()
     }
 
-    fn output_indexes(&self) {
-        self.sql_generator.output_indexes();
+    fn output_relations(&self) -> Result<(), GenerationError> {
+        self.sql_generator.output_relations()
     }
 
-    fn output_triggers(&self) {
-        self.sql_generator.output_triggers();
+    fn output_indexes(&self) -> Result<(), GenerationError> {
+        self.sql_generator.output_indexes()
     }
 
-    fn output_functions(&self) {
-        self.sql_generator.output_functions();
+    fn output_triggers(&self) -> Result<(), GenerationError> {
+        self.sql_generator.output_triggers()
     }
 
-    fn output_views(&self) {
-        self.sql_generator.output_views();
+    fn output_functions(&self) -> Result<(), GenerationError> {
+        self.sql_generator.output_functions()
     }
 
-    fn output_procedures(&self) {
-        self.sql_generator.output_procedures();
+    fn output_views(&self) -> Result<(), GenerationError> {
+        self.sql_generator.output_views()
     }
 
-    fn output_other_sql_top(&self) {
-        self.sql_generator.output_other_sql_top();
+    fn output_procedures(&self) -> Result<(), GenerationError> {
+        self.sql_generator.output_procedures()
     }
 
-    fn output_other_sql_bottom(&self) {
-        self.sql_generator.output_other_sql_bottom();
+    fn output_other_sql_top(&self) -> Result<(), GenerationError> {
+        self.sql_generator.output_other_sql_top()
+    }
+
+    fn output_other_sql_bottom(&self) -> Result<(), GenerationError> {
+        self.sql_generator.output_other_sql_bottom()
+    }
+
+    fn output_drops(&self) -> Result<(), GenerationError> {
+        self.sql_generator.output_drops()
     }
 }
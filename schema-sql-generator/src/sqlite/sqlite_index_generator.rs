@@ -1,3 +1,4 @@
+use crate::common::generation_error::GenerationError;
 use crate::common::generator_context::GeneratorContext;
 use crate::common::index_generator::{DefaultIndexGenerator, IndexGenerator};
 use crate::common::sql_writer::SqlWriter;
@@ -17,16 +18,16 @@ impl SqliteIndexGenerator {
 }
 
 impl IndexGenerator for SqliteIndexGenerator {
-    fn output_indexes(&self) {
-        self.index_generator.output_indexes();
+    fn output_indexes(&self) -> Result<(), GenerationError> {
+        self.index_generator.output_indexes()
     }
 
-    fn output_indexes_for_table(&self, writer: &mut SqlWriter, table: &Table) {
-        self.index_generator.output_indexes_for_table(writer, table);
+    fn output_indexes_for_table(&self, writer: &mut SqlWriter, table: &Table) -> Result<(), GenerationError> {
+        self.index_generator.output_indexes_for_table(writer, table)
     }
 
-    fn output_index(&self, writer: &mut SqlWriter, statement_separator: &str, table: &Table, key_name: &str, key: &Key) {
-        self.index_generator.output_index(writer, statement_separator, table, key_name, key);
+    fn output_index(&self, writer: &mut SqlWriter, statement_separator: &str, table: &Table, key_name: &str, key: &Key) -> Result<(), GenerationError> {
+        self.index_generator.output_index(writer, statement_separator, table, key_name, key)
     }
 
     fn index_options(&self, key: &Key) -> Option<String> {
@@ -38,10 +39,10 @@ impl IndexGenerator for SqliteIndexGenerator {
 mod tests {
     use super::*;
     use crate::common::test_support::make_context;
-    use schema_model::builder::{SchemaBuilder, TableBuilder};
+    use schema_model::builder::{KeyBuilder, SchemaBuilder, TableBuilder};
     use schema_model::model::database_model::DatabaseModel;
     use schema_model::model::key::KeyColumn;
-    use schema_model::model::types::{BooleanMode, DatabaseType, ForeignKeyMode, KeyType};
+    use schema_model::model::types::{BooleanMode, DatabaseType, ForeignKeyMode, IndexMethod, KeyType, NullsOrder, SortOrder};
 
     #[test]
     fn output_indexes_for_table_renders_unique_index() {
@@ -53,13 +54,34 @@ mod tests {
 
         let generator = SqliteIndexGenerator::new(ctx.clone());
         ctx.with_writer(|writer| {
-            generator.output_indexes_for_table(writer, &table);
+            generator.output_indexes_for_table(writer, &table).unwrap();
         });
 
         let output = buffer.contents();
         assert!(output.contains("create unique index ix_users1 on users (email);"));
     }
 
+    #[test]
+    fn output_index_renders_partial_expression_index() {
+        let index = KeyBuilder::new(KeyType::Index)
+            .add_key_column(KeyColumn::new_expression("lower(email)"))
+            .add_key_column(KeyColumn::new("created_at").with_sort_order(Some(SortOrder::Asc)).with_nulls_order(Some(NullsOrder::First)))
+            .method(IndexMethod::Gin)
+            .filter("deleted_at is null")
+            .build();
+        let table = TableBuilder::new(None::<&str>, "users").add_index(index).build();
+        let schema = SchemaBuilder::new(None::<&str>).add_table(table.clone()).build();
+        let model = DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema]);
+        let (ctx, buffer) = make_context(model, DatabaseType::Sqlite);
+
+        let generator = SqliteIndexGenerator::new(ctx.clone());
+        ctx.with_writer(|writer| {
+            generator.output_indexes_for_table(writer, &table).unwrap();
+        });
+
+        assert!(buffer.contents().contains("create index ix_users1 on users ((lower(email)), created_at asc nulls first) where deleted_at is null;"));
+    }
+
     #[test]
     fn output_indexes_for_table_skips_when_no_indexes() {
         let table = TableBuilder::new(None::<&str>, "solo").build();
@@ -69,7 +91,7 @@ mod tests {
 
         let generator = SqliteIndexGenerator::new(ctx.clone());
         ctx.with_writer(|writer| {
-            generator.output_indexes_for_table(writer, &table);
+            generator.output_indexes_for_table(writer, &table).unwrap();
         });
 
         assert_eq!(buffer.contents(), "");
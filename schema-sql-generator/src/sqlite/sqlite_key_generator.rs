@@ -1,4 +1,5 @@
 use schema_model::model::table::Table;
+use crate::common::generation_error::GenerationError;
 use crate::common::generator_context::GeneratorContext;
 use crate::common::key_generator::{DefaultKeyGenerator, KeyGenerator};
 
@@ -15,7 +16,11 @@ impl SqliteKeyGenerator {
 }
 
 impl KeyGenerator for SqliteKeyGenerator {
-    fn key_constraints(&self, table: &Table) -> Vec<String> {
+    fn key_constraints(&self, table: &Table) -> Result<Vec<String>, GenerationError> {
+        // A sequence primary key is declared on its column as the rowid alias.
+        if table.rowid_alias_column().is_some() {
+            return Ok(self.key_generator.key_constraints_without_primary_key(table));
+        }
         self.key_generator.key_constraints(table)
     }
 }
@@ -1,3 +1,4 @@
+use crate::common::generation_error::GenerationError;
 use crate::common::generator_context::GeneratorContext;
 use crate::common::other_sql_generator::{DefaultOtherSqlGenerator, OtherSqlGenerator};
 use crate::common::sql_writer::SqlWriter;
@@ -15,16 +16,16 @@ impl SqliteOtherSqlGenerator {
 }
 
 impl OtherSqlGenerator for SqliteOtherSqlGenerator {
-    fn output_other_sql_top(&self) {
-        self.other_sql_generator.output_other_sql_top();
+    fn output_other_sql_top(&self) -> Result<(), GenerationError> {
+        self.other_sql_generator.output_other_sql_top()
     }
 
-    fn output_other_sql_bottom(&self) {
-        self.other_sql_generator.output_other_sql_bottom();
+    fn output_other_sql_bottom(&self) -> Result<(), GenerationError> {
+        self.other_sql_generator.output_other_sql_bottom()
     }
 
-    fn output_other_sql(&self, writer: &mut SqlWriter, statement_separator: &str, sql: &str) {
-        self.other_sql_generator.output_other_sql(writer, statement_separator, sql);
+    fn output_other_sql(&self, writer: &mut SqlWriter, statement_separator: &str, sql: &str) -> Result<(), GenerationError> {
+        self.other_sql_generator.output_other_sql(writer, statement_separator, sql)
     }
 }
 
@@ -48,7 +49,7 @@ mod tests {
         let (ctx, buffer) = make_context(model, DatabaseType::Sqlite);
 
         let generator = SqliteOtherSqlGenerator::new(ctx);
-        generator.output_other_sql_top();
+        generator.output_other_sql_top().unwrap();
 
         let output = buffer.contents();
         assert!(output.contains("pragma foreign_keys = on;"));
@@ -66,7 +67,7 @@ mod tests {
         let (ctx, buffer) = make_context(model, DatabaseType::Sqlite);
 
         let generator = SqliteOtherSqlGenerator::new(ctx);
-        generator.output_other_sql_bottom();
+        generator.output_other_sql_bottom().unwrap();
 
         let output = buffer.contents();
         assert!(output.contains("vacuum;"));
@@ -1,3 +1,4 @@
+use crate::common::generation_error::GenerationError;
 use crate::common::generator_context::GeneratorContext;
 use crate::common::procedure_generator::ProcedureGenerator;
 use crate::common::sql_writer::SqlWriter;
@@ -14,25 +15,33 @@ impl SqliteProcedureGenerator {
 }
 
 impl ProcedureGenerator for SqliteProcedureGenerator {
-    fn output_procedures(&self) {
+    fn output_procedures(&self) -> Result<(), GenerationError> {
         let database_type = self.context.settings().database_type();
         let database_model = self.context.settings().database_model();
 
-        let has_procedures = database_model
+        match database_model
             .schemas()
             .iter()
-            .any(|schema| schema.procedures().iter().any(|p| p.database_type() == database_type));
-
-        if has_procedures {
-            panic!("SQLite does not support stored procedures.");
+            .flat_map(|schema| schema.procedures())
+            .find(|procedure| procedure.database_type() == database_type)
+        {
+            Some(procedure) => Err(unsupported_procedure(procedure)),
+            None => Ok(()),
         }
     }
 
-    fn output_procedure(&self, _writer: &mut SqlWriter, _statement_separator: &str, _procedure: &Procedure) {
-        panic!("SQLite does not support stored procedures.");
+    fn output_procedure(&self, _writer: &mut SqlWriter, _statement_separator: &str, procedure: &Procedure) -> Result<(), GenerationError> {
+        Err(unsupported_procedure(procedure))
     }
 }
 
+fn unsupported_procedure(procedure: &Procedure) -> GenerationError {
+    GenerationError::unsupported(
+        format!("procedure {}", procedure.name()),
+        "SQLite does not support stored procedures",
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -49,14 +58,13 @@ mod tests {
         let (ctx, buffer) = make_context(model, DatabaseType::Sqlite);
 
         let generator = SqliteProcedureGenerator::new(ctx);
-        generator.output_procedures();
+        generator.output_procedures().unwrap();
 
         assert_eq!(buffer.contents(), "");
     }
 
     #[test]
-    #[should_panic(expected = "SQLite does not support stored procedures.")]
-    fn output_procedures_panics_when_a_sqlite_procedure_exists() {
+    fn output_procedures_fails_when_a_sqlite_procedure_exists() {
         let procedure = Procedure::new(None::<&str>, "proc", DatabaseType::Sqlite, "select 1");
         let schema = SchemaBuilder::new(None::<&str>)
             .add_procedures(vec![procedure])
@@ -65,6 +73,8 @@ mod tests {
         let (ctx, _buffer) = make_context(model, DatabaseType::Sqlite);
 
         let generator = SqliteProcedureGenerator::new(ctx);
-        generator.output_procedures();
+        let error = generator.output_procedures().unwrap_err();
+        assert_eq!(error.object(), Some("procedure proc"));
+        assert!(error.to_string().contains("SQLite does not support stored procedures"));
     }
 }
@@ -1,3 +1,4 @@
+use crate::common::generation_error::{resolve_table, GenerationError};
 use crate::common::generator_context::GeneratorContext;
 use crate::common::relation_generator::{DefaultRelationGenerator, RelationGenerator};
 use schema_model::model::table::Table;
@@ -18,15 +19,15 @@ impl SqliteRelationGenerator {
     /// added after the table is created (unlike Postgres/SQL Server). Instead this returns
     /// the `constraint ... foreign key (...) references ...` clauses meant to be embedded
     /// directly inside the table's `CREATE TABLE (...)` definition.
-    pub fn inline_foreign_key_constraints(&self, table: &Table) -> Vec<String> {
+    pub fn inline_foreign_key_constraints(&self, table: &Table) -> Result<Vec<String>, GenerationError> {
         let context = self.relation_generator.context();
 
         if context.settings().foreign_key_mode() != ForeignKeyMode::Relations {
-            return Vec::new();
+            return Ok(Vec::new());
         }
 
-        let database_type = context.settings().database_type();
-        let database_model = context.settings().database_model();
+        let settings = context.settings();
+        let database_model = settings.database_model();
 
         table
             .relations()
@@ -34,27 +35,32 @@ impl SqliteRelationGenerator {
             .enumerate()
             .map(|(relation_index, relation)| {
                 let constraint_name = self.relation_generator.relation_constraint_name(table, relation_index);
-                let to_table = database_model.find_table_by_qualified_name(relation.to_table_name());
+                let to_table = resolve_table(database_model, relation.to_table_name(), || {
+                    format!("relation {}", constraint_name)
+                })?;
                 let operation = self.relation_generator.relation_operation_type(relation.relation_type());
 
-                format!(
+                // SQLite looks the parent table up in the child's own database and doesn't
+                // accept a qualified name in `references`.
+                Ok(format!(
                     "   constraint {} foreign key ({}) references {}({}) on delete {}",
-                    constraint_name,
-                    relation.from_column_name(),
-                    to_table.fully_qualified_table_name(database_type),
-                    relation.to_column_name(),
+                    settings.quote_generated(&constraint_name),
+                    settings.quote_list(&relation.from_column_names()),
+                    settings.quote(to_table.name()),
+                    settings.quote_list(&relation.to_column_names()),
                     operation
-                )
+                ))
             })
             .collect()
     }
 }
 
 impl RelationGenerator for SqliteRelationGenerator {
-    fn output_relations(&self) {
+    fn output_relations(&self) -> Result<(), GenerationError> {
         // No-op: SQLite foreign keys are emitted inline in the `CREATE TABLE` statement
         // (see `inline_foreign_key_constraints`) since SQLite doesn't support adding
         // foreign key constraints via `ALTER TABLE` after the fact.
+        Ok(())
     }
 }
 
@@ -65,7 +71,7 @@ mod tests {
     use schema_model::builder::{ColumnBuilder, SchemaBuilder, TableBuilder};
     use schema_model::model::column_type::ColumnType;
     use schema_model::model::database_model::DatabaseModel;
-    use schema_model::model::relation::Relation;
+    use schema_model::model::relation::{Relation, RelationColumn};
     use schema_model::model::types::{BooleanMode, DatabaseType, RelationType};
 
     fn build_model_with_relation() -> (DatabaseModel, Table) {
@@ -90,7 +96,7 @@ mod tests {
         let (ctx, buffer) = make_context(model, DatabaseType::Sqlite);
 
         let generator = SqliteRelationGenerator::new(ctx);
-        generator.output_relations();
+        generator.output_relations().unwrap();
 
         assert_eq!(buffer.contents(), "");
     }
@@ -101,7 +107,7 @@ mod tests {
         let (ctx, _buffer) = make_context(model, DatabaseType::Sqlite);
         let generator = SqliteRelationGenerator::new(ctx);
 
-        let clauses = generator.inline_foreign_key_constraints(&child);
+        let clauses = generator.inline_foreign_key_constraints(&child).unwrap();
 
         assert_eq!(clauses.len(), 1);
         assert!(clauses[0].contains("foreign key (parent_id) references parent(id)"));
@@ -109,6 +115,40 @@ mod tests {
         assert!(!clauses[0].contains("alter table"));
     }
 
+    #[test]
+    fn inline_foreign_key_constraints_renders_composite_relation() {
+        let parent = TableBuilder::new(None::<&str>, "parent")
+            .add_column(ColumnBuilder::new(None::<&str>, "tenant_id", ColumnType::Int).required(true).build())
+            .add_column(ColumnBuilder::new(None::<&str>, "id", ColumnType::Int).required(true).build())
+            .build();
+        let child = TableBuilder::new(None::<&str>, "child")
+            .add_column(ColumnBuilder::new(None::<&str>, "tenant_id", ColumnType::Int).required(true).build())
+            .add_column(ColumnBuilder::new(None::<&str>, "parent_id", ColumnType::Int).required(true).build())
+            .add_relation(Relation::new_composite(
+                "parent",
+                "child",
+                vec![
+                    RelationColumn::new("tenant_id", "tenant_id"),
+                    RelationColumn::new("parent_id", "id"),
+                ],
+                RelationType::Cascade,
+                false,
+            ))
+            .build();
+        let schema = SchemaBuilder::new(None::<&str>)
+            .add_table(parent)
+            .add_table(child.clone())
+            .build();
+        let model = DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema]);
+        let (ctx, _buffer) = make_context(model, DatabaseType::Sqlite);
+        let generator = SqliteRelationGenerator::new(ctx);
+
+        let clauses = generator.inline_foreign_key_constraints(&child).unwrap();
+
+        assert_eq!(clauses.len(), 1);
+        assert!(clauses[0].contains("foreign key (tenant_id,parent_id) references parent(tenant_id,id)"));
+    }
+
     #[test]
     fn inline_foreign_key_constraints_empty_when_table_has_no_relations() {
         let table = TableBuilder::new(None::<&str>, "solo").build();
@@ -118,7 +158,7 @@ mod tests {
 
         let generator = SqliteRelationGenerator::new(ctx);
 
-        assert!(generator.inline_foreign_key_constraints(&table).is_empty());
+        assert!(generator.inline_foreign_key_constraints(&table).unwrap().is_empty());
     }
 
     #[test]
@@ -139,6 +179,27 @@ mod tests {
 
         let generator = SqliteRelationGenerator::new(ctx);
 
-        assert!(generator.inline_foreign_key_constraints(&child).is_empty());
+        assert!(generator.inline_foreign_key_constraints(&child).unwrap().is_empty());
+    }
+
+    #[test]
+    fn inline_foreign_key_constraints_name_the_parent_table_unqualified() {
+        let parent = TableBuilder::new(Some("app"), "parent")
+            .add_column(ColumnBuilder::new(Some("app"), "id", ColumnType::Sequence).required(true).build())
+            .build();
+        let child = TableBuilder::new(Some("app"), "child")
+            .add_column(ColumnBuilder::new(Some("app"), "parent_id", ColumnType::Int).required(true).build())
+            .add_relation(Relation::new("app.parent", "id", "app.child", "parent_id", RelationType::Cascade, false))
+            .build();
+        let schema = SchemaBuilder::new(Some("app"))
+            .add_table(parent)
+            .add_table(child.clone())
+            .build();
+        let model = DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema]);
+        let (ctx, _buffer) = make_context(model, DatabaseType::Sqlite);
+
+        let clauses = SqliteRelationGenerator::new(ctx).inline_foreign_key_constraints(&child).unwrap();
+
+        assert!(clauses[0].contains("references parent(id)"));
     }
 }
@@ -0,0 +1,92 @@
+use crate::common::generated_statement::StatementKind;
+use crate::common::generation_error::GenerationError;
+use crate::common::generator_context::GeneratorContext;
+use crate::common::sequence_generator::SequenceGenerator;
+use crate::sql_println;
+
+/// SQLite has no sequence objects. Each sequence becomes a one-row table holding the last
+/// value handed out, which the application advances itself:
+///
+/// `update document_no set value = value + 10 returning value;`
+///
+/// Columns drawing from a sequence get no default, and `minValue`, `maxValue`, `cycle`
+/// and `cache` are not enforced.
+pub struct SqliteSequenceGenerator {
+    context: GeneratorContext,
+}
+
+impl SqliteSequenceGenerator {
+    pub fn new(context: GeneratorContext) -> Self {
+        Self { context }
+    }
+}
+
+impl SequenceGenerator for SqliteSequenceGenerator {
+    fn output_sequences(&self) -> Result<(), GenerationError> {
+        let database_model = self.context.settings().database_model();
+        let database_type = self.context.settings().database_type();
+        let separator = self.context.settings().statement_separator();
+        let idempotent = self.context.settings().idempotent();
+
+        self.context.with_writer(|writer| {
+            for sequence in database_model.schemas().iter().flat_map(|schema| schema.sequences()) {
+                let sequence_name = self.context.settings().qualified_name(sequence.schema_name(), sequence.name());
+                writer.begin_object(StatementKind::Sequence, Some(&sequence.fully_qualified_sequence_name(database_type)));
+                let increment = sequence.increment().unwrap_or(1);
+                let start = sequence.start().or(sequence.min_value()).unwrap_or(1);
+                sql_println!(writer, "/* {}: sequence emulated by a single-row table */", sequence_name)?;
+                if idempotent {
+                    // The row is only primed once, so a rerun keeps the current value.
+                    sql_println!(writer, "create table if not exists {} (value integer not null){}", sequence_name, separator)?;
+                    sql_println!(writer, "insert into {} (value) select {} where not exists (select 1 from {}){}", sequence_name, start - increment, sequence_name, separator)?;
+                } else {
+                    sql_println!(writer, "drop table if exists {}{}", sequence_name, separator)?;
+                    sql_println!(writer, "create table {} (value integer not null){}", sequence_name, separator)?;
+                    sql_println!(writer, "insert into {} (value) values ({}){}", sequence_name, start - increment, separator)?;
+                }
+                sql_println!(writer, "")?;
+            }
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::test_support::{make_context, make_idempotent_context};
+    use schema_model::builder::{SchemaBuilder, SequenceBuilder};
+    use schema_model::model::database_model::DatabaseModel;
+    use schema_model::model::types::{BooleanMode, DatabaseType, ForeignKeyMode};
+
+    #[test]
+    fn output_sequences_emulates_each_sequence_with_a_table_primed_one_step_before_start() {
+        let schema = SchemaBuilder::new(None::<&str>)
+            .add_sequence(SequenceBuilder::new(None, "document_no").start(Some(1000)).increment(Some(10)).build())
+            .build();
+        let model = DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema]);
+        let (ctx, buffer) = make_context(model, DatabaseType::Sqlite);
+
+        SqliteSequenceGenerator::new(ctx).output_sequences().unwrap();
+
+        let output = buffer.contents();
+        assert!(output.contains("drop table if exists document_no;\ncreate table document_no (value integer not null);"));
+        assert!(output.contains("insert into document_no (value) values (990);"));
+    }
+
+    #[test]
+    fn output_sequences_primes_the_table_only_once_when_idempotent() {
+        let schema = SchemaBuilder::new(None::<&str>)
+            .add_sequence(SequenceBuilder::new(None, "document_no").build())
+            .build();
+        let model = DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema]);
+        let (ctx, buffer) = make_idempotent_context(model, DatabaseType::Sqlite);
+
+        SqliteSequenceGenerator::new(ctx).output_sequences().unwrap();
+
+        let output = buffer.contents();
+        assert!(output.contains("create table if not exists document_no (value integer not null);"));
+        assert!(!output.contains("drop table"));
+        assert!(output.contains("insert into document_no (value) select 0 where not exists (select 1 from document_no);"));
+    }
+}
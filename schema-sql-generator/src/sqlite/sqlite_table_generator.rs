@@ -1,14 +1,18 @@
+use crate::common::generation_error::GenerationError;
 use crate::common::generator_context::GeneratorContext;
-use crate::common::table_generator::{DefaultTableGenerator, TableGenerator};
+use crate::common::table_generator::{output_table_steps, DefaultTableGenerator, TableGenerator};
 use crate::sqlite::sqlite_column_constraint_generator::SqliteColumnConstraintGenerator;
 use crate::sqlite::sqlite_column_generator::SqliteColumnGenerator;
 use crate::sqlite::sqlite_index_generator::SqliteIndexGenerator;
 use crate::sqlite::sqlite_key_generator::SqliteKeyGenerator;
 use crate::sqlite::sqlite_relation_generator::SqliteRelationGenerator;
 use crate::sqlite::sqlite_table_constraint_generator::SqliteTableConstraintGenerator;
+use crate::{sql_newline, sql_println};
 use schema_model::model::table::Table;
+use schema_model::model::types::TableOption;
 
 pub struct SqliteTableGenerator {
+    context: GeneratorContext,
     table_generator: DefaultTableGenerator,
     relation_generator: SqliteRelationGenerator,
 }
@@ -16,6 +20,7 @@ pub struct SqliteTableGenerator {
 impl SqliteTableGenerator {
     pub fn new(context: GeneratorContext) -> Self {
         Self {
+            context: context.clone(),
             table_generator: DefaultTableGenerator::new(
                 context.clone(),
                 Box::new(SqliteColumnGenerator::new(context.clone())),
@@ -30,36 +35,62 @@ impl SqliteTableGenerator {
 }
 
 impl TableGenerator for SqliteTableGenerator {
-    fn output_tables(&self) {
-        self.table_generator.output_tables();
+    /// Walks the tables itself rather than handing them to `DefaultTableGenerator`, whose
+    /// walk would write each table with its own definition and footer, leaving out the
+    /// inline foreign keys and the `strict`/`without rowid` options.
+    fn output_tables(&self) -> Result<(), GenerationError> {
+        for schema in self.context.settings().database_model().schemas() {
+            for table in schema.tables() {
+                self.output_table(table)?;
+            }
+        }
+
+        Ok(())
     }
 
-    fn output_table(&self, table: &Table) {
-        self.table_generator.output_table_header(table);
+    fn output_table(&self, table: &Table) -> Result<(), GenerationError> {
+        output_table_steps(self, table)
     }
 
-    fn output_table_header(&self, table: &Table) {
-        self.table_generator.output_table_header(table);
+    fn output_table_header(&self, table: &Table) -> Result<(), GenerationError> {
+        self.table_generator.output_table_header(table)
     }
 
-    fn output_table_definition(&self, table: &Table) {
+    fn output_table_definition(&self, table: &Table) -> Result<(), GenerationError> {
         // SQLite has no `ALTER TABLE ... ADD CONSTRAINT` support, so foreign keys
         // must be declared inline in the `CREATE TABLE` statement rather than as
         // separate statements from the relation generator.
-        let inline_foreign_keys = self.relation_generator.inline_foreign_key_constraints(table);
-        self.table_generator.output_table_definition_with_extra(table, inline_foreign_keys);
+        let inline_foreign_keys = self.relation_generator.inline_foreign_key_constraints(table)?;
+        self.table_generator.output_table_definition_with_extra(table, inline_foreign_keys)
     }
 
-    fn output_table_footer(&self, table: &Table) {
-        self.table_generator.output_table_footer(table);
+    fn output_table_footer(&self, table: &Table) -> Result<(), GenerationError> {
+        let table_options: Vec<&str> = [(TableOption::Strict, "strict"), (TableOption::WithoutRowid, "without rowid")]
+            .into_iter()
+            .filter(|(option, _)| table.has_option(option.clone()))
+            .map(|(_, sql)| sql)
+            .collect();
+        if table_options.is_empty() {
+            return self.table_generator.output_table_footer(table);
+        }
+
+        self.context.with_writer(|writer| {
+            sql_println!(writer, ") {}{}", table_options.join(", "), self.context.settings().statement_separator())?;
+            sql_newline!(writer)?;
+            Ok(())
+        })
     }
 
-    fn output_indexes(&self, table: &Table) {
-        self.table_generator.output_indexes(table);
+    fn output_table_descriptions(&self, table: &Table) -> Result<(), GenerationError> {
+        self.table_generator.output_table_descriptions(table)
     }
 
-    fn output_initial_data(&self, table: &Table) {
-        self.table_generator.output_initial_data(table);
+    fn output_indexes(&self, table: &Table) -> Result<(), GenerationError> {
+        self.table_generator.output_indexes(table)
+    }
+
+    fn output_initial_data(&self, table: &Table) -> Result<(), GenerationError> {
+        self.table_generator.output_initial_data(table)
     }
 }
 
@@ -70,12 +101,15 @@ mod tests {
     use schema_model::builder::{ColumnBuilder, SchemaBuilder, TableBuilder};
     use schema_model::model::column_type::ColumnType;
     use schema_model::model::database_model::DatabaseModel;
-    use schema_model::model::types::{BooleanMode, DatabaseType, ForeignKeyMode};
+    use schema_model::model::key::{Key, KeyColumn};
+    use schema_model::model::relation::Relation;
+    use schema_model::model::types::{BooleanMode, DatabaseType, ForeignKeyMode, KeyType, RelationType};
 
     #[test]
     fn output_table_renders_header_and_columns() {
         let table = TableBuilder::new(None::<&str>, "users")
             .add_column(ColumnBuilder::new(None::<&str>, "id", ColumnType::Sequence).required(true).build())
+            .add_key(Key::new(KeyType::Primary, vec![KeyColumn::new("id")]))
             .add_column(ColumnBuilder::new(None::<&str>, "name", ColumnType::Varchar).length(50).required(true).build())
             .build();
         let schema = SchemaBuilder::new(None::<&str>).add_table(table.clone()).build();
@@ -83,14 +117,74 @@ mod tests {
         let (ctx, buffer) = make_context(model, DatabaseType::Sqlite);
 
         let generator = SqliteTableGenerator::new(ctx);
-        generator.output_table_header(&table);
-        generator.output_table_definition(&table);
-        generator.output_table_footer(&table);
+        generator.output_table_header(&table).unwrap();
+        generator.output_table_definition(&table).unwrap();
+        generator.output_table_footer(&table).unwrap();
 
         let output = buffer.contents();
         assert!(output.contains("create table users"));
-        assert!(output.contains("id integer auto_increment"));
+        assert!(output.contains("id integer primary key autoincrement not null"));
+        assert!(!output.contains("constraint pk_users"));
         assert!(output.contains("name varchar(50)"));
         assert!(output.contains(");"));
     }
+
+    #[test]
+    fn output_table_footer_appends_strict_and_without_rowid() {
+        let table = TableBuilder::new(None::<&str>, "order_line")
+            .add_column(ColumnBuilder::new(None::<&str>, "order_id", ColumnType::Long).required(true).build())
+            .add_column(ColumnBuilder::new(None::<&str>, "line_no", ColumnType::Int).required(true).build())
+            .add_column(ColumnBuilder::new(None::<&str>, "sku", ColumnType::Varchar).length(20).build())
+            .add_key(Key::new(KeyType::Primary, vec![KeyColumn::new("order_id"), KeyColumn::new("line_no")]))
+            .add_option(TableOption::Strict)
+            .add_option(TableOption::WithoutRowid)
+            .build();
+        let schema = SchemaBuilder::new(None::<&str>).add_table(table.clone()).build();
+        let model = DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema]);
+        let (ctx, buffer) = make_context(model, DatabaseType::Sqlite);
+
+        SqliteTableGenerator::new(ctx).output_tables().unwrap();
+
+        let output = buffer.contents();
+        assert!(output.contains("order_id integer not null"));
+        assert!(output.contains("sku text"));
+        assert!(output.contains(") strict, without rowid;\n"));
+    }
+
+    #[test]
+    fn composite_primary_key_stays_a_table_constraint() {
+        let table = TableBuilder::new(None::<&str>, "order_line")
+            .add_column(ColumnBuilder::new(None::<&str>, "order_id", ColumnType::Long).required(true).build())
+            .add_column(ColumnBuilder::new(None::<&str>, "line_no", ColumnType::Int).required(true).build())
+            .add_key(Key::new(KeyType::Primary, vec![KeyColumn::new("order_id"), KeyColumn::new("line_no")]))
+            .build();
+        let schema = SchemaBuilder::new(None::<&str>).add_table(table.clone()).build();
+        let model = DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema]);
+        let (ctx, buffer) = make_context(model, DatabaseType::Sqlite);
+
+        SqliteTableGenerator::new(ctx).output_table(&table).unwrap();
+
+        let output = buffer.contents();
+        assert!(output.contains("order_id bigint not null"));
+        assert!(output.contains("constraint pk_order_line primary key (order_id,line_no)"));
+    }
+
+    #[test]
+    fn output_tables_declares_foreign_keys_inline() {
+        let parent = TableBuilder::new(None::<&str>, "parent")
+            .add_column(ColumnBuilder::new(None::<&str>, "id", ColumnType::Sequence).required(true).build())
+            .add_key(Key::new(KeyType::Primary, vec![KeyColumn::new("id")]))
+            .build();
+        let child = TableBuilder::new(None::<&str>, "child")
+            .add_column(ColumnBuilder::new(None::<&str>, "parent_id", ColumnType::Int).required(true).build())
+            .add_relation(Relation::new("parent", "id", "child", "parent_id", RelationType::Cascade, false))
+            .build();
+        let schema = SchemaBuilder::new(None::<&str>).add_table(parent).add_table(child).build();
+        let model = DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema]);
+        let (ctx, buffer) = make_context(model, DatabaseType::Sqlite);
+
+        SqliteTableGenerator::new(ctx).output_tables().unwrap();
+
+        assert!(buffer.contents().contains("foreign key (parent_id) references parent(id) on delete cascade"));
+    }
 }
@@ -1,44 +1,314 @@
+use crate::common::generated_statement::StatementKind;
+use crate::common::generation_error::{resolve_table, GenerationError};
 use crate::common::generator_context::GeneratorContext;
-use crate::common::trigger_generator::{DefaultTriggerGenerator, TriggerGenerator};
+use crate::common::sql_string::escape_sql_literal;
+use crate::common::trigger_generator::TriggerGenerator;
+use crate::common::trigger_support::{child_match_condition, has_custom_trigger, has_relations_kept_by_triggers, parent_match_condition};
+use crate::sql_println;
+use schema_model::model::database_model::DatabaseModel;
+use schema_model::model::table::Table;
+use schema_model::model::types::{DatabaseType, ForeignKeyMode, RelationType, TriggerType};
 
+/// A SQLite trigger fires for a single event, so like MySQL a table gets `{table}_insert`
+/// and `{table}_update` triggers with the same checks. They run before the change, so a
+/// failed check aborts the statement before any row is written.
+///
+/// A trigger can only refer to tables in its own database, and its statements can't
+/// qualify them, so the tables in a trigger are named without their schema.
 pub struct SqliteTriggerGenerator {
-    trigger_generator: DefaultTriggerGenerator,
+    context: GeneratorContext,
 }
 
 impl SqliteTriggerGenerator {
     pub fn new(context: GeneratorContext) -> Self {
-        Self {
-            trigger_generator: DefaultTriggerGenerator::new(context),
-        }
+        Self { context }
+    }
+
+    /// SQLite triggers don't maintain aggregations, so only custom code and relations kept
+    /// by triggers ask for one.
+    fn should_output_delete_trigger(&self, table: &Table) -> bool {
+        let settings = self.context.settings();
+        has_custom_trigger(settings, table, TriggerType::Delete)
+            || has_relations_kept_by_triggers(settings, table.reverse_relations())
+    }
+
+    fn should_output_update_triggers(&self, table: &Table) -> bool {
+        let settings = self.context.settings();
+        has_custom_trigger(settings, table, TriggerType::Update) || has_relations_kept_by_triggers(settings, table.relations())
     }
 }
 
 impl TriggerGenerator for SqliteTriggerGenerator {
-    fn output_triggers(&self) {
-        self.trigger_generator.output_triggers();
+    fn output_triggers(&self) -> Result<(), GenerationError> {
+        for table in self.context.settings().database_model().all_tables() {
+            self.output_table_triggers(table)?;
+        }
+
+        Ok(())
+    }
+
+    fn output_table_triggers(&self, table: &Table) -> Result<(), GenerationError> {
+        if self.should_output_delete_trigger(table) {
+            let body = self.delete_trigger_body(table)?;
+            self.output_trigger(table, "delete", &body)?;
+        }
+
+        if self.should_output_update_triggers(table) {
+            let body = self.update_trigger_body(table, "insert")?;
+            self.output_trigger(table, "insert", &body)?;
+            let body = self.update_trigger_body(table, "update")?;
+            self.output_trigger(table, "update", &body)?;
+        }
+
+        Ok(())
+    }
+
+    fn trigger_names(&self, table: &Table) -> Vec<String> {
+        let table_name = self.context.settings().generated_name_part(table.name());
+        let mut names = Vec::new();
+        if self.should_output_delete_trigger(table) {
+            names.push(format!("{}_delete", table_name));
+        }
+        if self.should_output_update_triggers(table) {
+            names.push(format!("{}_insert", table_name));
+            names.push(format!("{}_update", table_name));
+        }
+        names
+    }
+
+    fn possible_trigger_names(&self, table_name: &str) -> Vec<String> {
+        let table_name = self.context.settings().generated_name_part(table_name);
+        ["delete", "insert", "update"].iter().map(|event| format!("{}_{}", table_name, event)).collect()
+    }
+}
+
+impl SqliteTriggerGenerator {
+    /// Writes the trigger firing before `event` on `table`, dropping any earlier version first.
+    fn output_trigger(&self, table: &Table, event: &str, body: &[String]) -> Result<(), GenerationError> {
+        let settings = self.context.settings();
+        let separator = settings.statement_separator();
+        let trigger_name = format!("{}_{}", settings.generated_name_part(table.name()), event);
+        let quoted_trigger = settings.qualified_generated_name(table.schema_name(), &trigger_name);
+
+        self.context.with_writer(|writer| {
+            writer.begin_object(
+                StatementKind::Trigger,
+                Some(&settings.database_type().qualified_name(table.schema_name(), &trigger_name)),
+            );
+            sql_println!(writer, "/* {} */", trigger_name)?;
+            sql_println!(writer, "drop trigger if exists {}{}", quoted_trigger, separator)?;
+            sql_println!(
+                writer,
+                "create trigger {} before {} on {} for each row",
+                quoted_trigger,
+                event,
+                settings.quote(table.name())
+            )?;
+            sql_println!(writer, "begin")?;
+            for line in body {
+                sql_println!(writer, "{}", line)?;
+            }
+            sql_println!(writer, "end{}", separator)?;
+            sql_println!(writer, "")?;
+            Ok(())
+        })
+    }
+
+    fn delete_trigger_body(&self, table: &Table) -> Result<Vec<String>, GenerationError> {
+        let settings = self.context.settings();
+        let database_type = settings.database_type();
+        let object = || format!("trigger {}_delete", table.name());
+        let mut lines = Vec::new();
+
+        if settings.foreign_key_mode() == ForeignKeyMode::Triggers {
+            // A reverse relation keeps the original relation's fields: `from_table_name` is
+            // the child table, `to_table_name` this one.
+            for relation in table.reverse_relations() {
+                if relation.relation_type() == RelationType::DoNothing {
+                    continue;
+                }
+                let child_table = resolve_table(self.database_model(), relation.from_table_name(), object)?;
+                let child_name = settings.quote(child_table.name());
+                let condition = child_match_condition(settings, relation, "old");
+
+                match relation.relation_type() {
+                    RelationType::Enforce => {
+                        lines.push(format!(
+                            "   select raise(abort, 'The row in {} cannot be deleted. It is being used by a row in the {} table.')",
+                            escape_sql_literal(&table.fully_qualified_table_name(database_type)),
+                            escape_sql_literal(&child_table.fully_qualified_table_name(database_type))
+                        ));
+                        lines.push(format!("      where exists (select 1 from {} where {});", child_name, condition));
+                    }
+                    RelationType::SetNull => {
+                        let assignments = relation
+                            .from_column_names()
+                            .iter()
+                            .map(|c| format!("{} = null", settings.quote(c)))
+                            .collect::<Vec<_>>()
+                            .join(", ");
+                        lines.push(format!("   update {} set {} where {};", child_name, assignments, condition));
+                    }
+                    RelationType::Cascade => {
+                        lines.push(format!("   delete from {} where {};", child_name, condition));
+                    }
+                    RelationType::DoNothing => {}
+                }
+            }
+        }
+
+        self.custom_trigger_lines(&mut lines, table, TriggerType::Delete);
+        Ok(lines)
+    }
+
+    /// The body of the insert or update trigger: both check every reference the new row makes.
+    fn update_trigger_body(&self, table: &Table, event: &str) -> Result<Vec<String>, GenerationError> {
+        let settings = self.context.settings();
+        let database_type = settings.database_type();
+        let object = || format!("trigger {}_{}", table.name(), event);
+        let mut lines = Vec::new();
+
+        if settings.foreign_key_mode() == ForeignKeyMode::Triggers {
+            for relation in table.relations() {
+                if relation.relation_type() == RelationType::DoNothing {
+                    continue;
+                }
+                let to_table = resolve_table(self.database_model(), relation.to_table_name(), object)?;
+                let not_null = relation
+                    .from_column_names()
+                    .iter()
+                    .map(|c| format!("new.{} is not null", settings.quote(c)))
+                    .collect::<Vec<_>>()
+                    .join(" and ");
+                lines.push(format!(
+                    "   select raise(abort, 'The value of {} was not found in the {} table.')",
+                    escape_sql_literal(&relation.from_columns_as_string()),
+                    escape_sql_literal(&to_table.fully_qualified_table_name(database_type))
+                ));
+                lines.push(format!(
+                    "      where {} and not exists (select 1 from {} where {});",
+                    not_null,
+                    settings.quote(to_table.name()),
+                    parent_match_condition(settings, relation, "new")
+                ));
+            }
+        }
+
+        self.custom_trigger_lines(&mut lines, table, TriggerType::Update);
+        Ok(lines)
+    }
+
+    fn custom_trigger_lines(&self, lines: &mut Vec<String>, table: &Table, trigger_type: TriggerType) {
+        for custom_trigger in table.triggers() {
+            if custom_trigger.trigger_type() == trigger_type && custom_trigger.database_type() == DatabaseType::Sqlite {
+                lines.push(custom_trigger.trigger_text().to_string());
+            }
+        }
+    }
+
+    fn database_model(&self) -> &DatabaseModel {
+        self.context.settings().database_model()
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::common::test_support::make_context;
-    use schema_model::builder::{SchemaBuilder, TableBuilder};
-    use schema_model::model::database_model::DatabaseModel;
-    use schema_model::model::types::{BooleanMode, DatabaseType, ForeignKeyMode};
+    use crate::common::test_support::make_context_with_fk_mode;
+    use schema_model::builder::{ColumnBuilder, KeyBuilder, SchemaBuilder, TableBuilder};
+    use schema_model::model::column_type::ColumnType;
+    use schema_model::model::relation::Relation;
+    use schema_model::model::trigger::Trigger;
+    use schema_model::model::types::{BooleanMode, KeyType};
+
+    fn build_model_with_reverse_relation(relation_type: RelationType) -> DatabaseModel {
+        let mut parent = TableBuilder::new(None::<&str>, "parent")
+            .add_column(ColumnBuilder::new(None::<&str>, "id", ColumnType::Sequence).required(true).build())
+            .add_key(KeyBuilder::new(KeyType::Primary).add_column("id").build())
+            .build();
+        parent.add_reverse_relation(Relation::new("parent", "id", "child", "parent_id", relation_type, false));
+        let child = TableBuilder::new(None::<&str>, "child")
+            .add_column(ColumnBuilder::new(None::<&str>, "parent_id", ColumnType::Int).build())
+            .add_relation(Relation::new("parent", "id", "child", "parent_id", relation_type, false))
+            .build();
+        let schema = SchemaBuilder::new(None::<&str>).add_table(parent).add_table(child).build();
+        DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema])
+    }
 
     #[test]
-    fn output_triggers_is_a_no_op_for_sqlite() {
-        // SQLite has no trigger support in this generator; DefaultTriggerGenerator is
-        // intentionally a no-op here (unlike postgres/sqlserver which override it).
-        let table = TableBuilder::new(None::<&str>, "users").build();
-        let schema = SchemaBuilder::new(None::<&str>).add_table(table).build();
-        let model = DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Triggers, vec![schema]);
-        let (ctx, buffer) = make_context(model, DatabaseType::Sqlite);
+    fn output_triggers_enforce_relations_by_aborting() {
+        let model = build_model_with_reverse_relation(RelationType::Enforce);
+        let (ctx, buffer) = make_context_with_fk_mode(model, DatabaseType::Sqlite, ForeignKeyMode::Triggers);
 
-        let generator = SqliteTriggerGenerator::new(ctx);
-        generator.output_triggers();
+        SqliteTriggerGenerator::new(ctx).output_triggers().unwrap();
+
+        let output = buffer.contents();
+        assert!(output.contains(
+            "drop trigger if exists parent_delete;\n\
+             create trigger parent_delete before delete on parent for each row\n\
+             begin\n\
+             \x20  select raise(abort, 'The row in parent cannot be deleted. It is being used by a row in the child table.')\n\
+             \x20     where exists (select 1 from child where parent_id = old.id);\n\
+             end;\n"
+        ));
+        assert!(output.contains("create trigger child_insert before insert on child for each row"));
+        assert!(output.contains("create trigger child_update before update on child for each row"));
+        assert!(output.contains(
+            "      where new.parent_id is not null and not exists (select 1 from parent where id = new.parent_id);"
+        ));
+    }
+
+    #[test]
+    fn output_triggers_cascade_and_set_null_act_on_the_child_table() {
+        let (ctx, buffer) = make_context_with_fk_mode(
+            build_model_with_reverse_relation(RelationType::Cascade),
+            DatabaseType::Sqlite,
+            ForeignKeyMode::Triggers,
+        );
+        SqliteTriggerGenerator::new(ctx).output_triggers().unwrap();
+        assert!(buffer.contents().contains("   delete from child where parent_id = old.id;"));
+
+        let (ctx, buffer) = make_context_with_fk_mode(
+            build_model_with_reverse_relation(RelationType::SetNull),
+            DatabaseType::Sqlite,
+            ForeignKeyMode::Triggers,
+        );
+        SqliteTriggerGenerator::new(ctx).output_triggers().unwrap();
+        assert!(buffer.contents().contains("   update child set parent_id = null where parent_id = old.id;"));
+    }
+
+    #[test]
+    fn output_triggers_does_nothing_when_relations_mode_is_not_triggers() {
+        let model = build_model_with_reverse_relation(RelationType::Enforce);
+        let (ctx, buffer) = make_context_with_fk_mode(model, DatabaseType::Sqlite, ForeignKeyMode::Relations);
+
+        SqliteTriggerGenerator::new(ctx).output_triggers().unwrap();
 
         assert_eq!(buffer.contents(), "");
     }
+
+    #[test]
+    fn output_triggers_include_only_the_custom_triggers_for_sqlite() {
+        let table = TableBuilder::new(None::<&str>, "users")
+            .add_column(ColumnBuilder::new(None::<&str>, "id", ColumnType::Int).build())
+            .add_trigger(Trigger::new("   insert into audit (user_id) values (old.id);", TriggerType::Delete, DatabaseType::Sqlite))
+            .add_trigger(Trigger::new("   perform audit(OLD.id);", TriggerType::Delete, DatabaseType::Postgresql))
+            .add_trigger(Trigger::new("   perform touch(NEW.id);", TriggerType::Update, DatabaseType::Postgresql))
+            .build();
+        let schema = SchemaBuilder::new(None::<&str>).add_table(table).build();
+        let model = DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema]);
+        let (ctx, buffer) = make_context_with_fk_mode(model, DatabaseType::Sqlite, ForeignKeyMode::Relations);
+
+        SqliteTriggerGenerator::new(ctx).output_triggers().unwrap();
+
+        let output = buffer.contents();
+        assert!(output.contains(
+            "create trigger users_delete before delete on users for each row\n\
+             begin\n\
+             \x20  insert into audit (user_id) values (old.id);\n\
+             end;\n"
+        ));
+        assert!(!output.contains("perform"));
+        assert!(!output.contains("users_update"));
+    }
 }
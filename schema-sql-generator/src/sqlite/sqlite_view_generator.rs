@@ -1,4 +1,8 @@
+use crate::common::generated_statement::StatementKind;
+use crate::common::generation_error::GenerationError;
 use crate::common::generator_context::GeneratorContext;
+use crate::common::index_generator::{index_columns_sql, index_name};
+use crate::common::sql_writer::SqlWriter;
 use crate::common::view_generator::ViewGenerator;
 use crate::sql_println;
 use schema_model::model::view::View;
@@ -11,10 +15,38 @@ impl SqliteViewGenerator {
     pub fn new(context: GeneratorContext) -> Self {
         Self { context }
     }
+
+    /// SQLite has no materialized views, so the result is kept in an ordinary table. The
+    /// table is created empty with the query's shape, and the delete/insert pair that follows
+    /// both fills it now and is the script to rerun whenever it needs refreshing.
+    fn output_materialized_view(&self, writer: &mut SqlWriter, view: &View, view_name: &str, separator: &str) -> Result<(), GenerationError> {
+        sql_println!(writer, "/* {}: materialized view emulated by a table */", view_name)?;
+        sql_println!(writer, "drop table if exists {}{}", view_name, separator)?;
+        sql_println!(writer, "create table {} as", view_name)?;
+        sql_println!(writer, "   select * from ({}) limit 0{}", view.sql(), separator)?;
+        for (position, key) in view.indexes().iter().enumerate() {
+            sql_println!(
+                writer,
+                "create {}index {} on {} ({}){}{}",
+                if key.is_unique() { "unique " } else { "" },
+                self.context.settings().quote_generated(&index_name(self.context.settings(), view.name(), key, position + 1)),
+                view_name,
+                index_columns_sql(self.context.settings(), view.name(), key)?,
+                key.filter().map(|filter| format!(" where {}", filter)).unwrap_or_default(),
+                separator
+            )?;
+        }
+        sql_println!(writer, "/* refresh {} */", view_name)?;
+        sql_println!(writer, "delete from {}{}", view_name, separator)?;
+        sql_println!(writer, "insert into {}", view_name)?;
+        sql_println!(writer, "   {}{}", view.sql(), separator)?;
+        sql_println!(writer, "")?;
+        Ok(())
+    }
 }
 
 impl ViewGenerator for SqliteViewGenerator {
-    fn output_views(&self) {
+    fn output_views(&self) -> Result<(), GenerationError> {
         let database_model = self.context.settings().database_model();
         let database_type = self.context.settings().database_type();
         let separator = self.context.settings().statement_separator();
@@ -26,22 +58,30 @@ impl ViewGenerator for SqliteViewGenerator {
             .collect();
 
         if !views.is_empty() {
-            self.context.with_writer(|writer| {
+            self.context.with_writer(|writer| -> Result<(), GenerationError> {
                 for view in views {
-                    let view_name = view.fully_qualified_view_name(database_type);
-                    sql_println!(writer, "/* {} */", view_name);
+                    let view_name = self.context.settings().qualified_name(view.schema_name(), view.name());
+                    writer.begin_object(StatementKind::View, Some(&view.fully_qualified_view_name(database_type)));
+                    if view.is_materialized() {
+                        self.output_materialized_view(writer, &view, &view_name, separator)?;
+                        continue;
+                    }
+                    sql_println!(writer, "/* {} */", view_name)?;
                     // Unlike Postgres's `create or replace view` and SQL Server's
                     // explicit existence-check guard, SQLite has no "replace" form for
                     // views - `drop view if exists` first keeps re-running the
                     // generated script idempotent (and, unlike `create view if not
                     // exists`, still picks up a changed view definition).
-                    sql_println!(writer, "drop view if exists {}{}", view_name, separator);
-                    sql_println!(writer, "create view {} as", view_name);
-                    sql_println!(writer, "   {}{}", view.sql(), separator);
-                    sql_println!(writer, "");
+                    sql_println!(writer, "drop view if exists {}{}", view_name, separator)?;
+                    sql_println!(writer, "create view {} as", view_name)?;
+                    sql_println!(writer, "   {}{}", view.sql(), separator)?;
+                    sql_println!(writer, "")?;
                 }
-            });
+                Ok(())
+            })?;
         }
+
+        Ok(())
     }
 }
 
@@ -62,7 +102,7 @@ mod tests {
         let (ctx, buffer) = make_context(model, ModelDatabaseType::Sqlite);
 
         let generator = SqliteViewGenerator::new(ctx);
-        generator.output_views();
+        generator.output_views().unwrap();
 
         let output = buffer.contents();
         assert!(output.contains("drop view if exists active_users;"));
@@ -78,7 +118,7 @@ mod tests {
         let (ctx, buffer) = make_context(model, ModelDatabaseType::Sqlite);
 
         let generator = SqliteViewGenerator::new(ctx);
-        generator.output_views();
+        generator.output_views().unwrap();
 
         assert!(buffer.contents().contains("create view app.active_users as"));
     }
@@ -93,7 +133,7 @@ mod tests {
         let (ctx, buffer) = make_context(model, ModelDatabaseType::Sqlite);
 
         let generator = SqliteViewGenerator::new(ctx);
-        generator.output_views();
+        generator.output_views().unwrap();
 
         let output = buffer.contents();
         let drop_pos = output.find("drop view if exists active_users").unwrap();
@@ -101,6 +141,37 @@ mod tests {
         assert!(drop_pos < create_pos, "the drop guard must come before the create statement");
     }
 
+    #[test]
+    fn output_views_renders_materialized_view_as_table_with_refresh_script() {
+        use schema_model::model::key::{Key, KeyColumn};
+        use schema_model::model::types::{KeyType, RefreshPolicy};
+
+        let unique = Key::new_full(KeyType::Index, vec![KeyColumn::new("customer_id")], false, false, true, None::<String>);
+        let view = View::new_materialized(
+            None,
+            "order_totals",
+            "select customer_id, sum(amount) as total from orders group by customer_id",
+            None,
+            RefreshPolicy::Manual,
+            vec![unique],
+        );
+        let schema = SchemaBuilder::new(None::<&str>).add_view(view).build();
+        let model = DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema]);
+        let (ctx, buffer) = make_context(model, ModelDatabaseType::Sqlite);
+
+        let generator = SqliteViewGenerator::new(ctx);
+        generator.output_views().unwrap();
+
+        let output = buffer.contents();
+        assert!(output.contains("drop table if exists order_totals;"));
+        assert!(output.contains(
+            "create table order_totals as\n   select * from (select customer_id, sum(amount) as total from orders group by customer_id) limit 0;"
+        ));
+        assert!(output.contains("create unique index ix_order_totals1 on order_totals (customer_id);"));
+        assert!(output.contains("/* refresh order_totals */\ndelete from order_totals;\ninsert into order_totals\n"));
+        assert!(!output.contains("create view"));
+    }
+
     #[test]
     fn output_views_does_nothing_when_no_views() {
         let schema = SchemaBuilder::new(None::<&str>).build();
@@ -108,7 +179,7 @@ mod tests {
         let (ctx, buffer) = make_context(model, ModelDatabaseType::Sqlite);
 
         let generator = SqliteViewGenerator::new(ctx);
-        generator.output_views();
+        generator.output_views().unwrap();
 
         assert_eq!(buffer.contents(), "");
     }
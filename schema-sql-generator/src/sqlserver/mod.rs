@@ -8,7 +8,9 @@ mod sqlserver_key_generator;
 mod sqlserver_other_sql_generator;
 mod sqlserver_procedure_generator;
 mod sqlserver_relation_generator;
+mod sqlserver_sequence_generator;
 mod sqlserver_table_constraint_generator;
 mod sqlserver_table_generator;
-mod sqlserver_trigger_generator;
+pub mod sqlserver_trigger_generator;
+mod sqlserver_drop_generator;
 mod sqlserver_view_generator;
@@ -1,4 +1,5 @@
 use crate::common::column_generator::{ColumnGenerator, DefaultColumnGenerator, DefaultConstraintNaming};
+use crate::common::generation_error::GenerationError;
 use crate::common::generator_context::GeneratorContext;
 use crate::sqlserver::sqlserver_column_type_generator::SqlServerColumnTypeGenerator;
 use schema_model::model::column::Column;
@@ -21,19 +22,19 @@ impl SqlServerColumnGenerator {
 }
 
 impl ColumnGenerator for SqlServerColumnGenerator {
-    fn column_definitions(&self, table: &Table) -> Vec<String> {
+    fn column_definitions(&self, table: &Table) -> Result<Vec<String>, GenerationError> {
         self.column_generator.column_definitions(table)
     }
 
-    fn column_sql(&self, table: &Table, column: &Column) -> String {
+    fn column_sql(&self, table: &Table, column: &Column) -> Result<String, GenerationError> {
         self.column_generator.column_sql(table, column)
     }
 
-    fn column_options(&self, table: &Table, column: &Column) -> String {
+    fn column_options(&self, table: &Table, column: &Column) -> Result<String, GenerationError> {
         self.column_generator.column_options(table, column)
     }
 
-    fn default_value(&self, table: &Table, column: &Column) -> Option<String> {
+    fn default_value(&self, table: &Table, column: &Column) -> Result<Option<String>, GenerationError> {
         self.column_generator.default_value(table, column)
     }
 }
@@ -1,8 +1,11 @@
-use crate::common::column_type_generator::ColumnTypeGenerator;
+use crate::common::column_type_generator::{find_enum_type, ColumnTypeGenerator};
+use crate::common::generation_error::GenerationError;
 use crate::common::generator_context::GeneratorContext;
 use schema_model::model::column::Column;
 use schema_model::model::enum_type::EnumType;
 use schema_model::model::schema::Schema;
+use schema_model::model::sequence::Sequence;
+use schema_model::model::table::Table;
 use schema_model::model::types::BooleanMode;
 use std::cmp;
 
@@ -23,12 +26,12 @@ impl ColumnTypeGenerator for SqlServerColumnTypeGenerator {
         &self.context
     }
 
-    fn sequence_sql(&self) -> String {
-        "integer identity(1,1)".to_string()
+    fn sequence_sql(&self, column: &Column) -> String {
+        format!("integer {}", identity_sql(column))
     }
 
-    fn long_sequence_sql(&self) -> String {
-        "bigint identity(1,1)".to_string()
+    fn long_sequence_sql(&self, column: &Column) -> String {
+        format!("bigint {}", identity_sql(column))
     }
 
     fn text_sql(&self, _column: &Column) -> String {
@@ -55,8 +58,18 @@ impl ColumnTypeGenerator for SqlServerColumnTypeGenerator {
         "newid()".to_string()
     }
 
-    fn array_sql(&self, _column: &Column) -> String {
-        panic!("Sql Server does not support arrays")
+    fn sequence_default_value_sql(&self, sequence: &Sequence) -> Option<String> {
+        Some(format!(
+            "next value for {}",
+            self.context.settings().qualified_name(sequence.schema_name(), sequence.name())
+        ))
+    }
+
+    fn array_sql(&self, table: &Table, column: &Column) -> Result<String, GenerationError> {
+        Err(GenerationError::unsupported(
+            format!("column {}.{}", table.name(), column.name()),
+            "Sql Server does not support arrays",
+        ))
     }
 
     fn boolean_sql(&self) -> String {
@@ -110,9 +123,8 @@ impl ColumnTypeGenerator for SqlServerColumnTypeGenerator {
         }
     }
 
-    fn enum_sql(&self, column: &Column) -> String {
-        let database_model = self.context().settings().database_model();
-        let enum_type: &EnumType = database_model.find_enum_type(column.schema_name(), column.enum_type().as_ref().unwrap());
+    fn enum_sql(&self, table: &Table, column: &Column) -> Result<String, GenerationError> {
+        let enum_type: &EnumType = find_enum_type(self.context(), table, column)?;
 
         let mut min_length = usize::MAX;
         let mut max_length = 0;
@@ -125,13 +137,23 @@ impl ColumnTypeGenerator for SqlServerColumnTypeGenerator {
         });
 
         if min_length != max_length {
-            return format!("nvarchar({})", max_length);
+            return Ok(format!("nvarchar({})", max_length));
         }
 
-        format!("nchar({})", max_length)
+        Ok(format!("nchar({})", max_length))
     }
 }
 
+/// `identity(start,increment)`, defaulting both to 1. Whether the column is an identity is
+/// not a choice on SQL Server, so the column's identity mode doesn't apply.
+fn identity_sql(column: &Column) -> String {
+    format!(
+        "identity({},{})",
+        column.identity_start().unwrap_or(1),
+        column.identity_increment().unwrap_or(1)
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -146,17 +168,15 @@ mod tests {
     use schema_model::model::types::{BooleanMode, DatabaseType, ForeignKeyMode};
     use std::cell::RefCell;
     use std::rc::Rc;
+    use std::sync::Arc;
 
     fn make_context() -> (GeneratorContext, TableBuilder) {
         let schema = SchemaBuilder::new(None::<&str>).build();
         let model = DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema]);
         let table = TableBuilder::new(None::<&str>, "test");
-        let options = GenerateOptions::new(
-            Rc::new(model),
-            Rc::new(RefCell::new(PrintWriter::new(Box::new(Vec::<u8>::new())))),
-        );
+        let options = GenerateOptions::new(Arc::new(model));
         let settings = SqlGeneratorSettings::new(DatabaseType::SqlServer, &options);
-        let writer = SqlWriter::new(options.writer.clone());
+        let writer = SqlWriter::new(Rc::new(RefCell::new(PrintWriter::new(Box::new(Vec::<u8>::new())))));
         let ctx = GeneratorContext::new(settings, writer);
         (ctx, table)
     }
@@ -166,13 +186,21 @@ mod tests {
         let generator = SqlServerColumnTypeGenerator::new(ctx);
         let table = table_builder.build();
         let col = ColumnBuilder::new(None::<&str>, "col", column_type).build();
-        assert_eq!(generator.column_type_sql(&table, &col), expected);
+        assert_eq!(generator.column_type_sql(&table, &col).unwrap(), expected);
     }
 
     #[test]
     fn sequence_types() {
         assert_type(ColumnType::Sequence, "integer identity(1,1)");
         assert_type(ColumnType::LongSequence, "bigint identity(1,1)");
+
+        let (ctx, table_builder) = make_context();
+        let generator = SqlServerColumnTypeGenerator::new(ctx);
+        let col = ColumnBuilder::new(None::<&str>, "col", ColumnType::Sequence)
+            .identity_start(Some(1000))
+            .identity_increment(Some(5))
+            .build();
+        assert_eq!(generator.column_type_sql(&table_builder.build(), &col).unwrap(), "integer identity(1000,5)");
     }
 
     #[test]
@@ -232,12 +260,9 @@ mod tests {
         );
         let schema = SchemaBuilder::new(None::<&str>).add_enum_type(enum_type).build();
         let model = DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema]);
-        let options = GenerateOptions::new(
-            Rc::new(model),
-            Rc::new(RefCell::new(PrintWriter::new(Box::new(Vec::<u8>::new())))),
-        );
+        let options = GenerateOptions::new(Arc::new(model));
         let settings = SqlGeneratorSettings::new(DatabaseType::SqlServer, &options);
-        let writer = SqlWriter::new(options.writer.clone());
+        let writer = SqlWriter::new(Rc::new(RefCell::new(PrintWriter::new(Box::new(Vec::<u8>::new())))));
         let ctx = GeneratorContext::new(settings, writer);
 
         let generator = SqlServerColumnTypeGenerator::new(ctx);
@@ -246,7 +271,7 @@ mod tests {
             .enum_type(Some("status_type".to_string()))
             .build();
 
-        assert_eq!(generator.column_type_sql(&table, &col), "nvarchar(3)");
+        assert_eq!(generator.column_type_sql(&table, &col).unwrap(), "nvarchar(3)");
     }
 
     #[test]
@@ -262,12 +287,9 @@ mod tests {
         );
         let schema = SchemaBuilder::new(None::<&str>).add_enum_type(enum_type).build();
         let model = DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema]);
-        let options = GenerateOptions::new(
-            Rc::new(model),
-            Rc::new(RefCell::new(PrintWriter::new(Box::new(Vec::<u8>::new())))),
-        );
+        let options = GenerateOptions::new(Arc::new(model));
         let settings = SqlGeneratorSettings::new(DatabaseType::SqlServer, &options);
-        let writer = SqlWriter::new(options.writer.clone());
+        let writer = SqlWriter::new(Rc::new(RefCell::new(PrintWriter::new(Box::new(Vec::<u8>::new())))));
         let ctx = GeneratorContext::new(settings, writer);
 
         let generator = SqlServerColumnTypeGenerator::new(ctx);
@@ -276,6 +298,6 @@ mod tests {
             .enum_type(Some("gender_type".to_string()))
             .build();
 
-        assert_eq!(generator.column_type_sql(&table, &col), "nchar(1)");
+        assert_eq!(generator.column_type_sql(&table, &col).unwrap(), "nchar(1)");
     }
 }
@@ -0,0 +1,191 @@
+use crate::common::drop_generator::{output_drop_steps, tables_in_drop_order, DropGenerator};
+use crate::common::generated_statement::StatementKind;
+use crate::common::generation_error::GenerationError;
+use crate::common::generator_context::GeneratorContext;
+use crate::common::relation_generator::DefaultRelationGenerator;
+use crate::common::sql_string::escape_sql_literal;
+use crate::common::sql_writer::SqlWriter;
+use crate::common::trigger_generator::TriggerGenerator;
+use crate::sql_println;
+use crate::sqlserver::sqlserver_trigger_generator::SqlServerTriggerGenerator;
+use schema_model::model::view::View;
+
+/// Drops with the same `dbo.sysobjects` existence checks the create script uses, rather
+/// than `drop ... if exists`.
+pub struct SqlServerDropGenerator {
+    context: GeneratorContext,
+    trigger_generator: SqlServerTriggerGenerator,
+}
+
+impl SqlServerDropGenerator {
+    pub fn new(context: GeneratorContext) -> Self {
+        Self {
+            context: context.clone(),
+            trigger_generator: SqlServerTriggerGenerator::new(context),
+        }
+    }
+
+    /// Drops `schema_name.name` if `dbo.sysobjects` has an object called `name` of one of
+    /// the given `types`.
+    fn output_drop(&self, writer: &mut SqlWriter, kind: StatementKind, object_type: &str, types: &[&str], schema_name: Option<&str>, name: &str) -> Result<(), GenerationError> {
+        let settings = self.context.settings();
+        let types = types.iter().map(|object_type| format!("'{}'", object_type)).collect::<Vec<_>>().join(", ");
+        writer.begin_object(kind, Some(&settings.database_type().qualified_name(schema_name, name)));
+        // Trigger names are already built by the generator; functions and procedures keep
+        // the name their SQL gives them.
+        let database_name = match kind {
+            StatementKind::Trigger | StatementKind::Function | StatementKind::Procedure => name.to_string(),
+            _ => settings.database_name(name),
+        };
+        sql_println!(writer, "if exists (select name from dbo.sysobjects where name = '{}' and type in ({}))", escape_sql_literal(&database_name), types)?;
+        sql_println!(writer, "   drop {} {}{}", object_type, settings.qualified_generated_name(schema_name, &database_name), settings.statement_separator())?;
+        Ok(())
+    }
+}
+
+impl DropGenerator for SqlServerDropGenerator {
+    fn output_drops(&self) -> Result<(), GenerationError> {
+        output_drop_steps(self, self.context.settings().foreign_key_mode())
+    }
+
+    fn output_drop_procedures(&self) -> Result<(), GenerationError> {
+        let database_type = self.context.settings().database_type();
+        let database_model = self.context.settings().database_model();
+
+        self.context.with_writer(|writer| {
+            for schema in database_model.schemas().iter().rev() {
+                for procedure in schema.procedures().iter().rev().filter(|procedure| procedure.database_type() == database_type) {
+                    self.output_drop(writer, StatementKind::Procedure, "procedure", &["P"], procedure.schema_name(), procedure.name())?;
+                }
+            }
+            Ok(())
+        })
+    }
+
+    fn output_drop_views(&self) -> Result<(), GenerationError> {
+        let database_type = self.context.settings().database_type();
+        let mut views: Vec<View> = self.context.settings().database_model()
+            .schemas()
+            .iter()
+            .flat_map(|schema| schema.views(database_type))
+            .collect();
+        views.reverse();
+
+        self.context.with_writer(|writer| {
+            for view in views {
+                self.output_drop(writer, StatementKind::View, "view", &["V"], view.schema_name(), view.name())?;
+            }
+            Ok(())
+        })
+    }
+
+    fn output_drop_functions(&self) -> Result<(), GenerationError> {
+        let database_type = self.context.settings().database_type();
+        let database_model = self.context.settings().database_model();
+
+        self.context.with_writer(|writer| {
+            for schema in database_model.schemas().iter().rev() {
+                for function in schema.functions().iter().rev().filter(|function| function.database_type() == database_type) {
+                    // Scalar, inline table-valued and multi-statement table-valued functions.
+                    self.output_drop(writer, StatementKind::Function, "function", &["FN", "IF", "TF"], function.schema_name(), function.name())?;
+                }
+            }
+            Ok(())
+        })
+    }
+
+    fn output_drop_triggers(&self) -> Result<(), GenerationError> {
+        let database_model = self.context.settings().database_model();
+
+        self.context.with_writer(|writer| {
+            for table in database_model.all_tables().into_iter().rev() {
+                for trigger_name in self.trigger_generator.trigger_names(table) {
+                    self.output_drop(writer, StatementKind::Trigger, "trigger", &["TR"], table.schema_name(), &trigger_name)?;
+                }
+            }
+            Ok(())
+        })
+    }
+
+    fn output_drop_relations(&self) -> Result<(), GenerationError> {
+        let settings = self.context.settings();
+        let separator = settings.statement_separator();
+        let relation_generator = DefaultRelationGenerator::new(self.context.clone());
+
+        self.context.with_writer(|writer| {
+            for table in tables_in_drop_order(settings.database_model()) {
+                let table_name = settings.qualified_name(table.schema_name(), table.name());
+                for relation_index in 0..table.relations().len() {
+                    let relation_name = relation_generator.relation_constraint_name(table, relation_index);
+                    writer.begin_object(StatementKind::Relation, Some(&relation_name));
+                    sql_println!(writer, "if exists (select name from dbo.sysobjects where name = '{}' and type = 'F')", escape_sql_literal(&relation_name))?;
+                    sql_println!(writer, "   alter table {} drop constraint {}{}", table_name, settings.quote_generated(&relation_name), separator)?;
+                }
+            }
+            Ok(())
+        })
+    }
+
+    fn output_drop_tables(&self) -> Result<(), GenerationError> {
+        self.context.with_writer(|writer| {
+            for table in tables_in_drop_order(self.context.settings().database_model()) {
+                self.output_drop(writer, StatementKind::Table, "table", &["U"], table.schema_name(), table.name())?;
+            }
+            Ok(())
+        })
+    }
+
+    fn output_drop_enum_types(&self) -> Result<(), GenerationError> {
+        // Enums become check constraints on SQL Server, so there is no type to drop.
+        Ok(())
+    }
+
+    fn output_drop_sequences(&self) -> Result<(), GenerationError> {
+        let database_model = self.context.settings().database_model();
+
+        self.context.with_writer(|writer| {
+            for sequence in database_model.schemas().iter().rev().flat_map(|schema| schema.sequences().iter().rev()) {
+                self.output_drop(writer, StatementKind::Sequence, "sequence", &["SO"], sequence.schema_name(), sequence.name())?;
+            }
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::test_support::make_context;
+    use schema_model::builder::{ColumnBuilder, SchemaBuilder, TableBuilder};
+    use schema_model::model::column_type::ColumnType;
+    use schema_model::model::database_model::DatabaseModel;
+    use schema_model::model::procedure::Procedure;
+    use schema_model::model::relation::Relation;
+    use schema_model::model::types::{BooleanMode, DatabaseType, ForeignKeyMode, RelationType};
+
+    #[test]
+    fn output_drops_checks_sysobjects_and_maps_public_to_dbo() {
+        let parent = TableBuilder::new(Some("public"), "parent")
+            .add_column(ColumnBuilder::new(Some("public"), "id", ColumnType::Int).required(true).build())
+            .build();
+        let child = TableBuilder::new(Some("public"), "child")
+            .add_column(ColumnBuilder::new(Some("public"), "parent_id", ColumnType::Int).build())
+            .add_relation(Relation::new("public.parent", "id", "public.child", "parent_id", RelationType::Enforce, false))
+            .build();
+        let schema = SchemaBuilder::new(Some("public"))
+            .add_table(child)
+            .add_table(parent)
+            .add_procedures(vec![Procedure::new(Some("public"), "purge", DatabaseType::SqlServer, "create procedure purge as select 1")])
+            .build();
+        let model = DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema]);
+        let (ctx, buffer) = make_context(model, DatabaseType::SqlServer);
+
+        SqlServerDropGenerator::new(ctx).output_drops().unwrap();
+
+        let output = buffer.contents();
+        assert!(output.contains("if exists (select name from dbo.sysobjects where name = 'purge' and type in ('P'))\n   drop procedure dbo.purge\nGO"));
+        assert!(output.contains("if exists (select name from dbo.sysobjects where name = 'fk_child1' and type = 'F')\n   alter table dbo.child drop constraint fk_child1\nGO"));
+        assert!(output.find("drop table dbo.child").unwrap() < output.find("drop table dbo.parent").unwrap());
+        assert!(!output.contains("public."));
+    }
+}
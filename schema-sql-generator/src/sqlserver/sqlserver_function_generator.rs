@@ -1,4 +1,5 @@
 use crate::common::function_generator::{DefaultFunctionGenerator, FunctionGenerator};
+use crate::common::generation_error::GenerationError;
 use crate::common::generator_context::GeneratorContext;
 use crate::common::sql_string::escape_sql_literal;
 use crate::common::sql_writer::SqlWriter;
@@ -17,15 +18,15 @@ impl SqlServerFunctionGenerator {
 }
 
 impl FunctionGenerator for SqlServerFunctionGenerator {
-    fn output_functions(&self) {
+    fn output_functions(&self) -> Result<(), GenerationError> {
         // Route through `output_functions_via(self)` rather than
         // `self.function_generator.output_functions()`: the latter dispatches each
         // function statically on the inner `DefaultFunctionGenerator`, which never sees
         // this struct's `output_function` override (the drop-if-exists guard below).
-        self.function_generator.output_functions_via(self);
+        self.function_generator.output_functions_via(self)
     }
 
-    fn output_function(&self, writer: &mut SqlWriter, statement_separator: &str, function: &Function) {
+    fn output_function(&self, writer: &mut SqlWriter, statement_separator: &str, function: &Function) -> Result<(), GenerationError> {
         let function_name = function.name();
         let schema_name = match function.schema_name() {
             Some(s) if s.eq_ignore_ascii_case("public") => "dbo",
@@ -38,12 +39,13 @@ impl FunctionGenerator for SqlServerFunctionGenerator {
             "if exists (select * from dbo.sysobjects where id = object_id(N'[{}].[{}]') and objectproperty(id, N'IsScalarFunction') = 1)",
             escape_sql_literal(schema_name),
             escape_sql_literal(function_name)
-        ).as_str());
-        writer.print(format!("drop function {}", fully_qualified_name).as_str());
-        writer.println(statement_separator);
-        writer.print(function.sql());
-        writer.println(statement_separator);
-        writer.newline();
+        ).as_str())?;
+        writer.print(format!("drop function {}", fully_qualified_name).as_str())?;
+        writer.println(statement_separator)?;
+        writer.print(function.sql())?;
+        writer.println(statement_separator)?;
+        writer.newline()?;
+        Ok(())
     }
 }
 
@@ -69,7 +71,7 @@ mod tests {
 
         let generator = SqlServerFunctionGenerator::new(ctx.clone());
         ctx.with_writer(|writer| {
-            generator.output_function(writer, ";", &function);
+            generator.output_function(writer, ";", &function).unwrap();
         });
 
         let output = buffer.contents();
@@ -92,7 +94,7 @@ mod tests {
 
         let generator = SqlServerFunctionGenerator::new(ctx.clone());
         ctx.with_writer(|writer| {
-            generator.output_function(writer, ";", &function);
+            generator.output_function(writer, ";", &function).unwrap();
         });
 
         let output = buffer.contents();
@@ -115,7 +117,7 @@ mod tests {
 
         let generator = SqlServerFunctionGenerator::new(ctx.clone());
         ctx.with_writer(|writer| {
-            generator.output_function(writer, ";", &function);
+            generator.output_function(writer, ";", &function).unwrap();
         });
 
         let output = buffer.contents();
@@ -124,7 +126,7 @@ mod tests {
 
     #[test]
     fn output_functions_applies_the_drop_if_exists_override_through_the_real_pipeline() {
-        // Regression test: output_functions() (the real pipeline entry point) must route
+        // Regression test: output_functions().unwrap() (the real pipeline entry point) must route
         // through SqlServerFunctionGenerator's output_function override, not silently fall
         // back to DefaultFunctionGenerator's plain rendering via static dispatch.
         let schema = SchemaBuilder::new(None::<&str>)
@@ -139,7 +141,7 @@ mod tests {
         let (ctx, buffer) = make_context(model, DatabaseType::SqlServer);
 
         let generator = SqlServerFunctionGenerator::new(ctx);
-        generator.output_functions();
+        generator.output_functions().unwrap();
 
         let output = buffer.contents();
         assert!(output.contains("create function dbo.mssql_fn() returns int as begin return 1 end"));
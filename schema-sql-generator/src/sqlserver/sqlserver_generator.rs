@@ -1,13 +1,20 @@
+use crate::common::generated_statement::StatementKind;
+use crate::common::generation_error::GenerationError;
 use crate::common::generator_context::GeneratorContext;
 use crate::common::sql_generator::{DefaultSqlGenerator, SqlGenerator};
+use crate::common::sql_string::escape_sql_literal;
+use crate::sqlserver::sqlserver_drop_generator::SqlServerDropGenerator;
 use crate::sqlserver::sqlserver_function_generator::SqlServerFunctionGenerator;
 use crate::sqlserver::sqlserver_index_generator::SqlServerIndexGenerator;
 use crate::sqlserver::sqlserver_other_sql_generator::SqlServerOtherSqlGenerator;
 use crate::sqlserver::sqlserver_procedure_generator::SqlServerProcedureGenerator;
 use crate::sqlserver::sqlserver_relation_generator::SqlServerRelationGenerator;
+use crate::sqlserver::sqlserver_sequence_generator::SqlServerSequenceGenerator;
 use crate::sqlserver::sqlserver_table_generator::SqlServerTableGenerator;
 use crate::sqlserver::sqlserver_trigger_generator::SqlServerTriggerGenerator;
 use crate::sqlserver::sqlserver_view_generator::SqlServerViewGenerator;
+use crate::sql_println;
+use schema_model::model::types::DatabaseType;
 
 pub struct SqlServerGenerator {
     sql_generator: DefaultSqlGenerator,
@@ -25,6 +32,8 @@ impl SqlServerGenerator {
             Box::new(SqlServerProcedureGenerator::new(context.clone())),
             Box::new(SqlServerTriggerGenerator::new(context.clone())),
             Box::new(SqlServerOtherSqlGenerator::new(context.clone())),
+            Box::new(SqlServerSequenceGenerator::new(context.clone())),
+            Box::new(SqlServerDropGenerator::new(context.clone())),
         );
 
         Self {
@@ -38,51 +47,72 @@ impl SqlGenerator for SqlServerGenerator {
         self.sql_generator.context()
     }
 
-    fn generate(&self) {
-        self.sql_generator.generate()
+    fn output_header(&self) -> Result<(), GenerationError> {
+        if !self.context().settings().idempotent() {
+            return self.sql_generator.output_header();
+        }
+
+        // `create schema` has to be the only statement in its batch, hence `exec`.
+        let separator = self.context().settings().statement_separator();
+        let database_model = self.context().settings().database_model();
+        self.context().with_writer(|writer| {
+            // `public` is written as `dbo`, which always exists.
+            for schema_name in database_model
+                .schemas()
+                .iter()
+                .filter_map(|schema| schema.schema_name())
+                .filter(|name| !name.eq_ignore_ascii_case("public") && *name != DatabaseType::SqlServer.default_schema().unwrap())
+            {
+                writer.begin_object(StatementKind::Schema, Some(schema_name));
+                sql_println!(writer, "if schema_id('{}') is null", escape_sql_literal(schema_name))?;
+                sql_println!(writer, "   exec('create schema {}'){}", schema_name, separator)?;
+                sql_println!(writer, "")?;
+            }
+            Ok(())
+        })
     }
 
-    fn output_sql(&self) {
-        self.sql_generator.output_sql();
+    fn output_sequences(&self) -> Result<(), GenerationError> {
+        self.sql_generator.output_sequences()
     }
 
-    fn output_header(&self) {
-        self.sql_generator.output_header();
+    fn output_tables(&self) -> Result<(), GenerationError> {
+        self.sql_generator.output_tables()
     }
 
-    fn output_tables(&self) {
-        self.sql_generator.output_tables();
+    fn output_relations(&self) -> Result<(), GenerationError> {
+        self.sql_generator.output_relations()
     }
 
-    fn output_relations(&self) {
-        self.sql_generator.output_relations();
+    fn output_indexes(&self) -> Result<(), GenerationError> {
+        self.sql_generator.output_indexes()
     }
 
-    fn output_indexes(&self) {
-        self.sql_generator.output_indexes();
+    fn output_triggers(&self) -> Result<(), GenerationError> {
+        self.sql_generator.output_triggers()
     }
 
-    fn output_triggers(&self) {
-        self.sql_generator.output_triggers();
+    fn output_functions(&self) -> Result<(), GenerationError> {
+        self.sql_generator.output_functions()
     }
 
-    fn output_functions(&self) {
-        self.sql_generator.output_functions();
+    fn output_views(&self) -> Result<(), GenerationError> {
+        self.sql_generator.output_views()
     }
 
-    fn output_views(&self) {
-        self.sql_generator.output_views();
+    fn output_procedures(&self) -> Result<(), GenerationError> {
+        self.sql_generator.output_procedures()
     }
 
-    fn output_procedures(&self) {
-        self.sql_generator.output_procedures();
+    fn output_other_sql_top(&self) -> Result<(), GenerationError> {
+        self.sql_generator.output_other_sql_top()
     }
 
-    fn output_other_sql_top(&self) {
-        self.sql_generator.output_other_sql_top();
+    fn output_other_sql_bottom(&self) -> Result<(), GenerationError> {
+        self.sql_generator.output_other_sql_bottom()
     }
 
-    fn output_other_sql_bottom(&self) {
-        self.sql_generator.output_other_sql_bottom();
+    fn output_drops(&self) -> Result<(), GenerationError> {
+        self.sql_generator.output_drops()
     }
 }
@@ -1,3 +1,4 @@
+use crate::common::generation_error::GenerationError;
 use crate::common::generator_context::GeneratorContext;
 use crate::common::index_generator::{DefaultIndexGenerator, IndexGenerator};
 use crate::common::sql_writer::SqlWriter;
@@ -17,16 +18,16 @@ impl SqlServerIndexGenerator {
 }
 
 impl IndexGenerator for SqlServerIndexGenerator {
-    fn output_indexes(&self) {
-        self.index_generator.output_indexes();
+    fn output_indexes(&self) -> Result<(), GenerationError> {
+        self.index_generator.output_indexes()
     }
 
-    fn output_indexes_for_table(&self, writer: &mut SqlWriter, table: &Table) {
-        self.index_generator.output_indexes_for_table(writer, table);
+    fn output_indexes_for_table(&self, writer: &mut SqlWriter, table: &Table) -> Result<(), GenerationError> {
+        self.index_generator.output_indexes_for_table(writer, table)
     }
 
-    fn output_index(&self, writer: &mut SqlWriter, statement_separator: &str, table: &Table, key_name: &str, key: &Key) {
-        self.index_generator.output_index(writer, statement_separator, table, key_name, key);
+    fn output_index(&self, writer: &mut SqlWriter, statement_separator: &str, table: &Table, key_name: &str, key: &Key) -> Result<(), GenerationError> {
+        self.index_generator.output_index(writer, statement_separator, table, key_name, key)
     }
 
     fn index_options(&self, key: &Key) -> Option<String> {
@@ -38,10 +39,10 @@ impl IndexGenerator for SqlServerIndexGenerator {
 mod tests {
     use super::*;
     use crate::common::test_support::make_context;
-    use schema_model::builder::{SchemaBuilder, TableBuilder};
+    use schema_model::builder::{KeyBuilder, SchemaBuilder, TableBuilder};
     use schema_model::model::database_model::DatabaseModel;
     use schema_model::model::key::KeyColumn;
-    use schema_model::model::types::{BooleanMode, DatabaseType, ForeignKeyMode, KeyType};
+    use schema_model::model::types::{BooleanMode, DatabaseType, ForeignKeyMode, IndexMethod, KeyType, NullsOrder, SortOrder};
 
     #[test]
     fn output_indexes_for_table_renders_unique_index() {
@@ -53,13 +54,48 @@ mod tests {
 
         let generator = SqlServerIndexGenerator::new(ctx.clone());
         ctx.with_writer(|writer| {
-            generator.output_indexes_for_table(writer, &table);
+            generator.output_indexes_for_table(writer, &table).unwrap();
         });
 
         let output = buffer.contents();
         assert!(output.contains("create unique index ix_users1 on dbo.users (email)"));
     }
 
+    #[test]
+    fn output_index_renders_filtered_index_without_nulls_ordering_or_method() {
+        let index = KeyBuilder::new(KeyType::Index)
+            .add_key_column(KeyColumn::new("created_at").with_sort_order(Some(SortOrder::Desc)).with_nulls_order(Some(NullsOrder::Last)))
+            .method(IndexMethod::Hash)
+            .filter("deleted_at is null")
+            .build();
+        let table = TableBuilder::new(None::<&str>, "users").add_index(index).build();
+        let schema = SchemaBuilder::new(None::<&str>).add_table(table.clone()).build();
+        let model = DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema]);
+        let (ctx, buffer) = make_context(model, DatabaseType::SqlServer);
+
+        let generator = SqlServerIndexGenerator::new(ctx.clone());
+        ctx.with_writer(|writer| {
+            generator.output_indexes_for_table(writer, &table).unwrap();
+        });
+
+        assert!(buffer.contents().contains("create index ix_users1 on dbo.users (created_at desc) where deleted_at is null\nGO"));
+    }
+
+    #[test]
+    #[should_panic(expected = "lower(email)")]
+    fn output_index_rejects_expression_columns() {
+        let index = KeyBuilder::new(KeyType::Index).add_key_column(KeyColumn::new_expression("lower(email)")).build();
+        let table = TableBuilder::new(None::<&str>, "users").add_index(index).build();
+        let schema = SchemaBuilder::new(None::<&str>).add_table(table.clone()).build();
+        let model = DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema]);
+        let (ctx, _buffer) = make_context(model, DatabaseType::SqlServer);
+
+        let generator = SqlServerIndexGenerator::new(ctx.clone());
+        ctx.with_writer(|writer| {
+            generator.output_indexes_for_table(writer, &table).unwrap();
+        });
+    }
+
     #[test]
     fn output_indexes_for_table_skips_when_no_indexes() {
         let table = TableBuilder::new(None::<&str>, "solo").build();
@@ -69,7 +105,7 @@ mod tests {
 
         let generator = SqlServerIndexGenerator::new(ctx.clone());
         ctx.with_writer(|writer| {
-            generator.output_indexes_for_table(writer, &table);
+            generator.output_indexes_for_table(writer, &table).unwrap();
         });
 
         assert_eq!(buffer.contents(), "");
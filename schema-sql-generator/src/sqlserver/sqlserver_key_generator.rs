@@ -1,3 +1,4 @@
+use crate::common::generation_error::GenerationError;
 use crate::common::generator_context::GeneratorContext;
 use crate::common::key_generator::{DefaultKeyGenerator, KeyGenerator};
 use schema_model::model::table::Table;
@@ -9,13 +10,13 @@ pub struct SqlServerKeyGenerator {
 impl SqlServerKeyGenerator {
     pub fn new(context: GeneratorContext) -> Self {
         Self {
-            key_generator: DefaultKeyGenerator::new(context).with_nonclustered_primary_key(true),
+            key_generator: DefaultKeyGenerator::new(context).with_nonclustered_primary_key(true).with_data_compression(true),
         }
     }
 }
 
 impl KeyGenerator for SqlServerKeyGenerator {
-    fn key_constraints(&self, table: &Table) -> Vec<String> {
+    fn key_constraints(&self, table: &Table) -> Result<Vec<String>, GenerationError> {
         self.key_generator.key_constraints(table)
     }
 }
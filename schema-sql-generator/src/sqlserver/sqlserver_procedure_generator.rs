@@ -1,3 +1,4 @@
+use crate::common::generation_error::GenerationError;
 use crate::common::generator_context::GeneratorContext;
 use crate::common::procedure_generator::{DefaultProcedureGenerator, ProcedureGenerator};
 use crate::common::sql_string::escape_sql_literal;
@@ -17,16 +18,16 @@ impl SqlServerProcedureGenerator {
 }
 
 impl ProcedureGenerator for SqlServerProcedureGenerator {
-    fn output_procedures(&self) {
+    fn output_procedures(&self) -> Result<(), GenerationError> {
         // Route through `output_procedures_via(self)` rather than
         // `self.procedure_generator.output_procedures()`: the latter dispatches each
         // procedure statically on the inner `DefaultProcedureGenerator`, which would
         // never see this struct's `output_procedure` override (the drop-if-exists guard
         // below) - the same static-dispatch pitfall fixed for functions.
-        self.procedure_generator.output_procedures_via(self);
+        self.procedure_generator.output_procedures_via(self)
     }
 
-    fn output_procedure(&self, writer: &mut SqlWriter, statement_separator: &str, procedure: &Procedure) {
+    fn output_procedure(&self, writer: &mut SqlWriter, statement_separator: &str, procedure: &Procedure) -> Result<(), GenerationError> {
         let procedure_name = procedure.name();
         let schema_name = match procedure.schema_name() {
             Some(s) if s.eq_ignore_ascii_case("public") => "dbo",
@@ -39,12 +40,13 @@ impl ProcedureGenerator for SqlServerProcedureGenerator {
             "if exists (select * from dbo.sysobjects where id = object_id(N'[{}].[{}]') and objectproperty(id, N'IsProcedure') = 1)",
             escape_sql_literal(schema_name),
             escape_sql_literal(procedure_name)
-        ).as_str());
-        writer.print(format!("drop procedure {}", fully_qualified_name).as_str());
-        writer.println(statement_separator);
-        writer.print(procedure.sql());
-        writer.println(statement_separator);
-        writer.newline();
+        ).as_str())?;
+        writer.print(format!("drop procedure {}", fully_qualified_name).as_str())?;
+        writer.println(statement_separator)?;
+        writer.print(procedure.sql())?;
+        writer.println(statement_separator)?;
+        writer.newline()?;
+        Ok(())
     }
 }
 
@@ -68,7 +70,7 @@ mod tests {
         let (ctx, buffer) = make_context(model, DatabaseType::SqlServer);
 
         let generator = SqlServerProcedureGenerator::new(ctx);
-        generator.output_procedures();
+        generator.output_procedures().unwrap();
 
         let output = buffer.contents();
         assert!(output.contains("create procedure mssql_only as begin end"));
@@ -77,7 +79,7 @@ mod tests {
 
     #[test]
     fn output_procedures_applies_the_drop_if_exists_guard_through_the_real_pipeline() {
-        // Regression test: output_procedures() (the real pipeline entry point) must
+        // Regression test: output_procedures().unwrap() (the real pipeline entry point) must
         // route through the drop-if-exists override, not silently fall back to
         // DefaultProcedureGenerator's plain rendering via static dispatch. Re-running
         // generated SQL against a database that already has the procedure must not fail
@@ -94,7 +96,7 @@ mod tests {
         let (ctx, buffer) = make_context(model, DatabaseType::SqlServer);
 
         let generator = SqlServerProcedureGenerator::new(ctx);
-        generator.output_procedures();
+        generator.output_procedures().unwrap();
 
         let output = buffer.contents();
         assert!(output.contains("if exists (select * from dbo.sysobjects where id = object_id(N'[dbo].[mssql_proc]') and objectproperty(id, N'IsProcedure') = 1)"));
@@ -116,7 +118,7 @@ mod tests {
 
         let generator = SqlServerProcedureGenerator::new(ctx.clone());
         ctx.with_writer(|writer| {
-            generator.output_procedure(writer, ";", &procedure);
+            generator.output_procedure(writer, ";", &procedure).unwrap();
         });
 
         let output = buffer.contents();
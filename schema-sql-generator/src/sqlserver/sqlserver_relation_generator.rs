@@ -1,3 +1,4 @@
+use crate::common::generation_error::GenerationError;
 use crate::common::generator_context::GeneratorContext;
 use crate::common::relation_generator::{DefaultRelationGenerator, RelationGenerator};
 
@@ -14,8 +15,8 @@ impl SqlServerRelationGenerator {
 }
 
 impl RelationGenerator for SqlServerRelationGenerator {
-    fn output_relations(&self) {
-        self.relation_generator.output_relations();
+    fn output_relations(&self) -> Result<(), GenerationError> {
+        self.relation_generator.output_relations()
     }
 }
 
@@ -46,7 +47,7 @@ mod tests {
         let (ctx, buffer) = make_context(model, DatabaseType::SqlServer);
 
         let generator = SqlServerRelationGenerator::new(ctx);
-        generator.output_relations();
+        generator.output_relations().unwrap();
 
         let output = buffer.contents();
         assert!(output.contains("alter table dbo.child"));
@@ -62,7 +63,7 @@ mod tests {
         let (ctx, buffer) = make_context(model, DatabaseType::SqlServer);
 
         let generator = SqlServerRelationGenerator::new(ctx);
-        generator.output_relations();
+        generator.output_relations().unwrap();
 
         assert_eq!(buffer.contents(), "");
     }
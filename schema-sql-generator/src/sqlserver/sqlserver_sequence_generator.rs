@@ -0,0 +1,78 @@
+use crate::common::generated_statement::StatementKind;
+use crate::common::generation_error::GenerationError;
+use crate::common::generator_context::GeneratorContext;
+use crate::common::sequence_generator::SequenceGenerator;
+use crate::common::sql_string::escape_sql_literal;
+use crate::sql_println;
+
+pub struct SqlServerSequenceGenerator {
+    context: GeneratorContext,
+}
+
+impl SqlServerSequenceGenerator {
+    pub fn new(context: GeneratorContext) -> Self {
+        Self { context }
+    }
+}
+
+impl SequenceGenerator for SqlServerSequenceGenerator {
+    fn output_sequences(&self) -> Result<(), GenerationError> {
+        let database_model = self.context.settings().database_model();
+        let database_type = self.context.settings().database_type();
+        let separator = self.context.settings().statement_separator();
+
+        self.context.with_writer(|writer| {
+            for sequence in database_model.schemas().iter().flat_map(|schema| schema.sequences()) {
+                let sequence_name = self.context.settings().qualified_name(sequence.schema_name(), sequence.name());
+                writer.begin_object(StatementKind::Sequence, Some(&sequence.fully_qualified_sequence_name(database_type)));
+                // A SQL Server sequence without a start value begins at the type's minimum
+                // (-2^63 for bigint) rather than at 1 as in Postgres.
+                let start = if sequence.start().is_none() && sequence.min_value().is_none() {
+                    " start with 1"
+                } else {
+                    ""
+                };
+                // Unlike a table, the sequence is kept when it exists already even when the
+                // script isn't idempotent: it can't be dropped while a column's default
+                // still draws from it, and the tables are only recreated after it.
+                sql_println!(writer, "if object_id('{}', 'SO') is null", escape_sql_literal(&sequence_name))?;
+                sql_println!(
+                    writer,
+                    "create sequence {} as bigint{}{}{}",
+                    sequence_name,
+                    start,
+                    sequence.options_sql(),
+                    separator
+                )?;
+                sql_println!(writer, "")?;
+            }
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::test_support::make_context;
+    use schema_model::builder::{SchemaBuilder, SequenceBuilder};
+    use schema_model::model::database_model::DatabaseModel;
+    use schema_model::model::types::{BooleanMode, DatabaseType, ForeignKeyMode};
+
+    #[test]
+    fn output_sequences_renders_bigint_sequence_starting_at_one_by_default() {
+        let schema = SchemaBuilder::new(None::<&str>)
+            .add_sequence(SequenceBuilder::new(None, "document_no").increment(Some(10)).cycle(true).build())
+            .build();
+        let model = DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema]);
+        let (ctx, buffer) = make_context(model, DatabaseType::SqlServer);
+
+        SqlServerSequenceGenerator::new(ctx).output_sequences().unwrap();
+
+        assert_eq!(
+            buffer.contents(),
+            "if object_id('dbo.document_no', 'SO') is null\n\
+             create sequence dbo.document_no as bigint start with 1 increment by 10 cycle\nGO\n\n"
+        );
+    }
+}
@@ -1,7 +1,9 @@
+use crate::common::generated_statement::StatementKind;
+use crate::common::generation_error::GenerationError;
 use crate::common::generator_context::GeneratorContext;
 use crate::common::sql_string::escape_sql_literal;
-use crate::common::table_generator::{DefaultTableGenerator, TableGenerator};
-use crate::sql_println;
+use crate::common::table_generator::{output_table_steps, DefaultTableGenerator, TableGenerator};
+use crate::{sql_newline, sql_println};
 use crate::sqlserver::sqlserver_column_constraint_generator::SqlServerColumnConstraintGenerator;
 use crate::sqlserver::sqlserver_column_generator::SqlServerColumnGenerator;
 use crate::sqlserver::sqlserver_index_generator::SqlServerIndexGenerator;
@@ -32,24 +34,22 @@ impl SqlServerTableGenerator {
 }
 
 impl TableGenerator for SqlServerTableGenerator {
-    fn output_tables(&self) {
+    fn output_tables(&self) -> Result<(), GenerationError> {
         let database_model = self.context.settings().database_model();
         for schema in database_model.schemas() {
             for table in schema.tables() {
-                self.output_table(table);
+                self.output_table(table)?;
             }
         }
+
+        Ok(())
     }
 
-    fn output_table(&self, table: &Table) {
-        self.output_table_header(table);
-        self.output_table_definition(table);
-        self.output_table_footer(table);
-        self.output_indexes(table);
-        self.output_initial_data(table);
+    fn output_table(&self, table: &Table) -> Result<(), GenerationError> {
+        output_table_steps(self, table)
     }
 
-    fn output_table_header(&self, table: &Table) {
+    fn output_table_header(&self, table: &Table) -> Result<(), GenerationError> {
         // The legacy Java codegen tool's SQL Server output uses an existence-checked drop
         // (`if exists (select ... from dbo.sysobjects ...)`) and `GO` batches rather than the
         // `drop table if exists ...;` form the other dialects share, so this is a full override
@@ -57,24 +57,40 @@ impl TableGenerator for SqlServerTableGenerator {
         let database_type = self.context.settings().database_type();
         let separator = self.context.settings().statement_separator().to_string();
         let fully_qualified_table_name = table.fully_qualified_table_name(database_type);
+        let quoted_table_name = self.context.settings().qualified_name(table.schema_name(), table.name());
         let table_name = table.name();
 
+        let idempotent = self.context.settings().idempotent();
+
         self.context.with_writer(|writer| {
-            sql_println!(writer, "/* {} */", table_name);
-            sql_println!(writer, "if exists (select name from dbo.sysobjects where name = '{}' and type = 'U')", escape_sql_literal(table_name));
-            sql_println!(writer, "drop table {}{}", fully_qualified_table_name, separator);
-            sql_println!(writer, "");
-            sql_println!(writer, "create table {}", fully_qualified_table_name);
-            sql_println!(writer, "(");
-        });
+            writer.begin_object(StatementKind::Table, Some(&fully_qualified_table_name));
+            sql_println!(writer, "/* {} */", table_name)?;
+            if idempotent {
+                sql_println!(writer, "if object_id('{}', 'U') is null", escape_sql_literal(&quoted_table_name))?;
+            } else {
+                sql_println!(writer, "if exists (select name from dbo.sysobjects where name = '{}' and type = 'U')", escape_sql_literal(&self.context.settings().database_name(table_name)))?;
+                sql_println!(writer, "drop table {}{}", quoted_table_name, separator)?;
+                sql_println!(writer, "")?;
+            }
+            sql_println!(writer, "create table {}", quoted_table_name)?;
+            sql_println!(writer, "(")?;
+            Ok(())
+        })
     }
 
-    fn output_table_definition(&self, table: &Table) {
-        self.table_generator.output_table_definition(table);
+    fn output_table_definition(&self, table: &Table) -> Result<(), GenerationError> {
+        self.table_generator.output_table_definition(table)
     }
 
-    fn output_table_footer(&self, table: &Table) {
-        self.table_generator.output_table_footer(table);
+    fn output_table_footer(&self, table: &Table) -> Result<(), GenerationError> {
+        match table.compression() {
+            Some(level) => self.context.with_writer(|writer| -> Result<(), GenerationError> {
+                sql_println!(writer, ") with (data_compression = {}){}", level.sql(), self.context.settings().statement_separator())?;
+                sql_newline!(writer)?;
+                Ok(())
+            })?,
+            None => self.table_generator.output_table_footer(table)?,
+        }
 
         // Unlike Postgres/SQLite, the legacy Java tool always emits the lock_escalation clause,
         // including for the default `Auto` setting.
@@ -84,7 +100,7 @@ impl TableGenerator for SqlServerTableGenerator {
             LockEscalation::Table => "table",
         };
         let separator = self.context.settings().statement_separator();
-        let fully_qualified_table_name = table.fully_qualified_table_name(self.context.settings().database_type());
+        let fully_qualified_table_name = self.context.settings().qualified_name(table.schema_name(), table.name());
         self.context.with_writer(|writer| {
             sql_println!(
                 writer,
@@ -92,28 +108,94 @@ impl TableGenerator for SqlServerTableGenerator {
                 fully_qualified_table_name,
                 lock_escalation_value,
                 separator
-            );
-            sql_println!(writer, "");
-        });
+            )?;
+            sql_println!(writer, "")?;
+            Ok(())
+        })
     }
 
-    fn output_indexes(&self, table: &Table) {
-        self.table_generator.output_indexes(table);
+    fn output_table_descriptions(&self, table: &Table) -> Result<(), GenerationError> {
+        // SQL Server keeps descriptions as `MS_Description` extended properties (the
+        // property SSMS shows as a table/column description) rather than `comment on`.
+        let database_type = self.context.settings().database_type();
+        let separator = self.context.settings().statement_separator();
+        let schema_name = table.schema_name().or(database_type.default_schema()).unwrap_or_default();
+        let settings = self.context.settings();
+        let table_name = settings.database_name(table.name());
+        let column_descriptions: Vec<(String, &str)> = table.columns()
+            .iter()
+            .filter_map(|column| column.description().map(|description| (settings.database_name(column.name()), description)))
+            .collect();
+
+        if table.description().is_none() && column_descriptions.is_empty() {
+            return Ok(());
+        }
+
+        let fully_qualified_table_name = table.fully_qualified_table_name(database_type);
+        // `sp_addextendedproperty` fails on a property that is already there.
+        let idempotent = self.context.settings().idempotent();
+        self.context.with_writer(|writer| {
+            writer.begin_object(StatementKind::Table, Some(&fully_qualified_table_name));
+            if let Some(description) = table.description() {
+                if idempotent {
+                    sql_println!(
+                        writer,
+                        "if not exists (select 1 from fn_listextendedproperty(N'MS_Description', N'SCHEMA', N'{}', N'TABLE', N'{}', default, default))",
+                        escape_sql_literal(schema_name),
+                        escape_sql_literal(&table_name)
+                    )?;
+                }
+                sql_println!(
+                    writer,
+                    "exec sp_addextendedproperty @name = N'MS_Description', @value = N'{}', @level0type = N'SCHEMA', @level0name = N'{}', @level1type = N'TABLE', @level1name = N'{}'{}",
+                    escape_sql_literal(description),
+                    escape_sql_literal(schema_name),
+                    escape_sql_literal(&table_name),
+                    separator
+                )?;
+            }
+            for (column_name, description) in &column_descriptions {
+                if idempotent {
+                    sql_println!(
+                        writer,
+                        "if not exists (select 1 from fn_listextendedproperty(N'MS_Description', N'SCHEMA', N'{}', N'TABLE', N'{}', N'COLUMN', N'{}'))",
+                        escape_sql_literal(schema_name),
+                        escape_sql_literal(&table_name),
+                        escape_sql_literal(column_name)
+                    )?;
+                }
+                sql_println!(
+                    writer,
+                    "exec sp_addextendedproperty @name = N'MS_Description', @value = N'{}', @level0type = N'SCHEMA', @level0name = N'{}', @level1type = N'TABLE', @level1name = N'{}', @level2type = N'COLUMN', @level2name = N'{}'{}",
+                    escape_sql_literal(description),
+                    escape_sql_literal(schema_name),
+                    escape_sql_literal(&table_name),
+                    escape_sql_literal(column_name),
+                    separator
+                )?;
+            }
+            sql_println!(writer, "")?;
+            Ok(())
+        })
     }
 
-    fn output_initial_data(&self, table: &Table) {
-        self.table_generator.output_initial_data(table);
+    fn output_indexes(&self, table: &Table) -> Result<(), GenerationError> {
+        self.table_generator.output_indexes(table)
+    }
+
+    fn output_initial_data(&self, table: &Table) -> Result<(), GenerationError> {
+        self.table_generator.output_initial_data(table)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::common::test_support::make_context;
-    use schema_model::builder::{ColumnBuilder, SchemaBuilder, TableBuilder};
+    use crate::common::test_support::{make_context, make_idempotent_context};
+    use schema_model::builder::{ColumnBuilder, KeyBuilder, SchemaBuilder, TableBuilder};
     use schema_model::model::column_type::ColumnType;
     use schema_model::model::database_model::DatabaseModel;
-    use schema_model::model::types::{BooleanMode, DatabaseType, ForeignKeyMode};
+    use schema_model::model::types::{BooleanMode, CompressionLevel, DatabaseType, ForeignKeyMode, KeyType, TableOption};
 
     #[test]
     fn output_table_renders_header_and_columns() {
@@ -126,9 +208,9 @@ mod tests {
         let (ctx, buffer) = make_context(model, DatabaseType::SqlServer);
 
         let generator = SqlServerTableGenerator::new(ctx);
-        generator.output_table_header(&table);
-        generator.output_table_definition(&table);
-        generator.output_table_footer(&table);
+        generator.output_table_header(&table).unwrap();
+        generator.output_table_definition(&table).unwrap();
+        generator.output_table_footer(&table).unwrap();
 
         let output = buffer.contents();
         assert!(output.contains("/* users */"));
@@ -139,6 +221,26 @@ mod tests {
         assert!(output.contains("name nvarchar(50)"));
     }
 
+    #[test]
+    fn output_table_descriptions_adds_ms_description_extended_properties() {
+        let table = TableBuilder::new(Some("sales"), "customer")
+            .description(Some("People who buy things".to_string()))
+            .add_column(ColumnBuilder::new(Some("sales"), "id", ColumnType::Int).build())
+            .add_column(ColumnBuilder::new(Some("sales"), "name", ColumnType::Varchar).description(Some("Customer's name".to_string())).build())
+            .build();
+        let schema = SchemaBuilder::new(Some("sales")).add_table(table.clone()).build();
+        let model = DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema]);
+        let (ctx, buffer) = make_context(model, DatabaseType::SqlServer);
+
+        let generator = SqlServerTableGenerator::new(ctx);
+        generator.output_table_descriptions(&table).unwrap();
+
+        let output = buffer.contents();
+        assert!(output.contains("exec sp_addextendedproperty @name = N'MS_Description', @value = N'People who buy things', @level0type = N'SCHEMA', @level0name = N'sales', @level1type = N'TABLE', @level1name = N'customer'\nGO"));
+        assert!(output.contains("@value = N'Customer''s name', @level0type = N'SCHEMA', @level0name = N'sales', @level1type = N'TABLE', @level1name = N'customer', @level2type = N'COLUMN', @level2name = N'name'\nGO"));
+        assert!(!output.contains("@level2name = N'id'"));
+    }
+
     #[test]
     fn output_table_header_escapes_single_quote_in_table_name() {
         // Regression test: an unescaped embedded quote would break the generated
@@ -149,7 +251,7 @@ mod tests {
         let (ctx, buffer) = make_context(model, DatabaseType::SqlServer);
 
         let generator = SqlServerTableGenerator::new(ctx);
-        generator.output_table_header(&table);
+        generator.output_table_header(&table).unwrap();
 
         let output = buffer.contents();
         assert!(output.contains("where name = 'o''brien' and type = 'U'"));
@@ -165,11 +267,30 @@ mod tests {
         let (ctx, buffer) = make_context(model, DatabaseType::SqlServer);
 
         let generator = SqlServerTableGenerator::new(ctx);
-        generator.output_table_footer(&table);
+        generator.output_table_footer(&table).unwrap();
 
         assert!(buffer.contents().contains("alter table dbo.users set (lock_escalation = table)\nGO"));
     }
 
+    #[test]
+    fn output_table_compresses_the_table_and_its_compressed_keys() {
+        let table = TableBuilder::new(None::<&str>, "ledger")
+            .add_column(ColumnBuilder::new(None::<&str>, "id", ColumnType::Int).required(true).build())
+            .add_key(KeyBuilder::new(KeyType::Primary).add_column("id").compression(CompressionLevel::Row).build())
+            .add_option(TableOption::Compress(CompressionLevel::Page))
+            .build();
+        let schema = SchemaBuilder::new(None::<&str>).add_table(table.clone()).build();
+        let model = DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema]);
+        let (ctx, buffer) = make_context(model, DatabaseType::SqlServer);
+
+        SqlServerTableGenerator::new(ctx).output_table(&table).unwrap();
+
+        let output = buffer.contents();
+        assert!(output.contains("   constraint pk_ledger primary key nonclustered (id) with (data_compression = row)\n"));
+        assert!(output.contains(") with (data_compression = page)\nGO\n"));
+        assert!(output.contains("alter table dbo.ledger set (lock_escalation = auto)\nGO"));
+    }
+
     #[test]
     fn output_table_footer_emits_lock_escalation_when_auto() {
         let table = TableBuilder::new(None::<&str>, "users")
@@ -180,7 +301,7 @@ mod tests {
         let (ctx, buffer) = make_context(model, DatabaseType::SqlServer);
 
         let generator = SqlServerTableGenerator::new(ctx);
-        generator.output_table_footer(&table);
+        generator.output_table_footer(&table).unwrap();
 
         assert!(buffer.contents().contains("alter table dbo.users set (lock_escalation = auto)\nGO"));
     }
@@ -195,7 +316,7 @@ mod tests {
         let (ctx, buffer) = make_context(model, DatabaseType::SqlServer);
 
         let generator = SqlServerTableGenerator::new(ctx);
-        generator.output_table_footer(&table);
+        generator.output_table_footer(&table).unwrap();
 
         assert!(buffer.contents().contains("alter table dbo.users set (lock_escalation = disable)\nGO"));
     }
@@ -215,8 +336,22 @@ mod tests {
         let (ctx, buffer) = make_context(model, DatabaseType::SqlServer);
 
         let generator = SqlServerTableGenerator::new(ctx);
-        generator.output_table_footer(&table);
+        generator.output_table_footer(&table).unwrap();
 
         assert!(buffer.contents().contains("alter table app.orders set (lock_escalation = table)\nGO"));
     }
+
+    #[test]
+    fn output_table_header_checks_object_id_instead_of_dropping_when_idempotent() {
+        let table = TableBuilder::new(Some("sales"), "orders").build();
+        let schema = SchemaBuilder::new(Some("sales")).add_table(table.clone()).build();
+        let model = DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema]);
+        let (ctx, buffer) = make_idempotent_context(model, DatabaseType::SqlServer);
+
+        SqlServerTableGenerator::new(ctx).output_table_header(&table).unwrap();
+
+        let output = buffer.contents();
+        assert!(output.contains("if object_id('sales.orders', 'U') is null\ncreate table sales.orders\n("));
+        assert!(!output.contains("drop table"));
+    }
 }
@@ -1,7 +1,15 @@
+use crate::common::aggregation_support::{aggregation_group_value, aggregation_row_value};
+use crate::common::generated_statement::StatementKind;
+use crate::common::generation_error::{resolve_table, GenerationError};
 use crate::common::generator_context::GeneratorContext;
+use crate::common::sql_generator_settings::SqlGeneratorSettings;
 use crate::common::sql_string::escape_sql_literal;
+use crate::common::sql_writer::SqlWriter;
 use crate::common::trigger_generator::TriggerGenerator;
+use crate::common::trigger_support::{outputs_delete_trigger, should_output_update_trigger};
 use crate::sql_println;
+use schema_model::model::aggregation::{AggregationFrequency, AggregationType};
+use schema_model::model::relation::Relation;
 use schema_model::model::table::Table;
 use schema_model::model::types::{DatabaseType, ForeignKeyMode, RelationType, TriggerType};
 
@@ -13,73 +21,72 @@ impl SqlServerTriggerGenerator {
     pub fn new(context: GeneratorContext) -> Self {
         Self { context }
     }
+}
 
-    fn should_output_delete_trigger(&self, table: &Table) -> bool {
-        let has_delete_triggers = table
-            .triggers()
-            .iter()
-            .any(|t| t.trigger_type() == TriggerType::Delete);
-
-        let has_reverse_relations_with_triggers = !table.reverse_relations().is_empty()
-            && self.context.settings().foreign_key_mode() == ForeignKeyMode::Triggers;
-
-        let has_aggregations = !table.aggregations().is_empty();
+impl TriggerGenerator for SqlServerTriggerGenerator {
+    fn output_triggers(&self) -> Result<(), GenerationError> {
+        for table in self.context.settings().database_model().all_tables() {
+            self.output_table_triggers(table)?;
+        }
 
-        has_delete_triggers || has_reverse_relations_with_triggers || has_aggregations
+        Ok(())
     }
 
-    fn should_output_update_trigger(&self, table: &Table) -> bool {
-        let has_update_triggers = table
-            .triggers()
-            .iter()
-            .any(|t| t.trigger_type() == TriggerType::Update);
+    fn output_table_triggers(&self, table: &Table) -> Result<(), GenerationError> {
+        let separator = self.context.settings().statement_separator();
 
-        let has_relations_with_triggers = !table.relations().is_empty()
-            && self.context.settings().foreign_key_mode() == ForeignKeyMode::Triggers;
+        if outputs_delete_trigger(self.context.settings(), table) {
+            self.output_delete_trigger(table, separator)?;
+        }
 
-        let has_aggregations = !table.aggregations().is_empty();
+        if should_output_update_trigger(self.context.settings(), table) {
+            self.output_update_trigger(table, separator)?;
+        }
 
-        has_update_triggers || has_relations_with_triggers || has_aggregations
+        Ok(())
     }
 
-}
-
-impl TriggerGenerator for SqlServerTriggerGenerator {
-    fn output_triggers(&self) {
-        let database_model = self.context.settings().database_model();
-        let separator = self.context.settings().statement_separator();
-
-        for table in database_model.all_tables() {
-            if self.should_output_delete_trigger(table) && table.primary_key().is_some() {
-                self.output_delete_trigger(table, separator);
-            }
-
-            if self.should_output_update_trigger(table) {
-                self.output_update_trigger(table, separator);
-            }
+    fn trigger_names(&self, table: &Table) -> Vec<String> {
+        let table_name = self.context.settings().generated_name_part(table.name());
+        let mut names = Vec::new();
+        if outputs_delete_trigger(self.context.settings(), table) {
+            names.push(format!("{}_delete", table_name));
+        }
+        if should_output_update_trigger(self.context.settings(), table) {
+            names.push(format!("{}_update", table_name));
         }
+        names
+    }
+
+    fn possible_trigger_names(&self, table_name: &str) -> Vec<String> {
+        let table_name = self.context.settings().generated_name_part(table_name);
+        vec![format!("{}_delete", table_name), format!("{}_update", table_name)]
     }
 }
 
 impl SqlServerTriggerGenerator {
-    fn output_delete_trigger(&self, table: &Table, separator: &str) {
+    fn output_delete_trigger(&self, table: &Table, separator: &str) -> Result<(), GenerationError> {
         let database_type = self.context.settings().database_type();
-        let table_name = table.name().to_lowercase();
+        let table_name = self.context.settings().generated_name_part(table.name());
         let fully_qualified_table = table.fully_qualified_table_name(database_type);
         let fully_qualified_trigger = database_type.qualified_name(table.schema_name(), &format!("{}_delete", table_name));
+        let settings = self.context.settings();
+        let quoted_table = settings.qualified_name(table.schema_name(), table.name());
+        let quoted_trigger = settings.qualified_generated_name(table.schema_name(), &format!("{}_delete", table_name));
 
         self.context.with_writer(|writer| {
-            sql_println!(writer, "/* {}_delete */", table_name);
+            writer.begin_object(StatementKind::Trigger, Some(&fully_qualified_trigger));
+            sql_println!(writer, "/* {}_delete */", table_name)?;
             sql_println!(
                 writer,
                 "if exists (select name from dbo.sysobjects where name = '{}_delete' and type = 'TR')",
                 escape_sql_literal(&table_name)
-            );
-            sql_println!(writer, "   drop trigger {}{}", fully_qualified_trigger, separator);
-            sql_println!(writer, "");
-            sql_println!(writer, "create trigger {}_delete on {} for delete as", table_name, fully_qualified_table);
-            sql_println!(writer, "if (select count(*) from deleted) > 0");
-            sql_println!(writer, "BEGIN");
+            )?;
+            sql_println!(writer, "   drop trigger {}{}", quoted_trigger, separator)?;
+            sql_println!(writer, "")?;
+            sql_println!(writer, "create trigger {} on {} for delete as", settings.quote_generated(&format!("{}_delete", table_name)), quoted_table)?;
+            sql_println!(writer, "if (select count(*) from deleted) > 0")?;
+            sql_println!(writer, "BEGIN")?;
 
             // A reverse relation carries the *original* relation's fields unchanged
             // (attached to the parent for convenient lookup): `from_table_name`/
@@ -93,135 +100,261 @@ impl SqlServerTriggerGenerator {
                 for relation in table.reverse_relations() {
                     if matches!(relation.relation_type(), RelationType::Enforce) {
                         if first_enforce {
-                            sql_println!(writer, "   declare @msg varchar(2000)");
+                            sql_println!(writer, "   declare @msg varchar(2000)")?;
                             first_enforce = false;
                         }
-                        let child_table = self.database_model().find_table_by_qualified_name(relation.from_table_name());
+                        let child_table = resolve_table(self.database_model(), relation.from_table_name(), || format!("trigger {}", fully_qualified_trigger))?;
+                        let quoted_child_table = settings.qualified_name(child_table.schema_name(), child_table.name());
                         sql_println!(
                             writer,
-                            "   if (select count(*) from {} where {} in (select {} from deleted)) > 0",
-                            child_table.fully_qualified_table_name(database_type),
-                            relation.from_column_name(),
-                            relation.to_column_name()
-                        );
-                        sql_println!(writer, "   begin");
+                            "   if (select count(*) from {} where {}) > 0",
+                            quoted_child_table,
+                            child_references_deleted(settings, relation, &quoted_child_table)
+                        )?;
+                        sql_println!(writer, "   begin")?;
                         sql_println!(
                             writer,
-                            "      select @msg = 'The {} ' + (select top 1 convert(varchar, {}) from deleted where {} in (select {} from {})) + ' cannot be deleted. It is being used by a row in the {} table.'",
+                            "      select @msg = 'The {} ' + (select top 1 {} from deleted where {}) + ' cannot be deleted. It is being used by a row in the {} table.'",
                             fully_qualified_table,
-                            relation.to_column_name(),
-                            relation.to_column_name(),
-                            relation.from_column_name(),
-                            child_table.fully_qualified_table_name(database_type),
+                            deleted_key_text(settings, relation),
+                            deleted_referenced_by_child(settings, relation, &quoted_child_table),
                             child_table.fully_qualified_table_name(database_type)
-                        );
-                        sql_println!(writer, "      rollback transaction");
-                        sql_println!(writer, "      raiserror (@msg, 16, 1)");
-                        sql_println!(writer, "      return");
-                        sql_println!(writer, "   end;");
+                        )?;
+                        sql_println!(writer, "      rollback transaction")?;
+                        sql_println!(writer, "      raiserror (@msg, 16, 1)")?;
+                        sql_println!(writer, "      return")?;
+                        sql_println!(writer, "   end;")?;
                     }
                 }
 
                 for relation in table.reverse_relations() {
                     if matches!(relation.relation_type(), RelationType::SetNull) {
-                        let child_table = self.database_model().find_table_by_qualified_name(relation.from_table_name());
+                        let child_table = resolve_table(self.database_model(), relation.from_table_name(), || format!("trigger {}", fully_qualified_trigger))?;
+                        let quoted_child_table = settings.qualified_name(child_table.schema_name(), child_table.name());
                         sql_println!(
                             writer,
-                            "   update {} set {} = null where {} in (select {} from deleted);",
-                            child_table.fully_qualified_table_name(database_type),
-                            relation.from_column_name(),
-                            relation.from_column_name(),
-                            relation.to_column_name()
-                        );
+                            "   update {} set {} where {};",
+                            quoted_child_table,
+                            relation
+                                .from_column_names()
+                                .iter()
+                                .map(|c| format!("{} = null", settings.quote(c)))
+                                .collect::<Vec<_>>()
+                                .join(", "),
+                            child_references_deleted(settings, relation, &quoted_child_table)
+                        )?;
                     }
                 }
 
                 for relation in table.reverse_relations() {
                     if matches!(relation.relation_type(), RelationType::Cascade) {
-                        let child_table = self.database_model().find_table_by_qualified_name(relation.from_table_name());
+                        let child_table = resolve_table(self.database_model(), relation.from_table_name(), || format!("trigger {}", fully_qualified_trigger))?;
+                        let quoted_child_table = settings.qualified_name(child_table.schema_name(), child_table.name());
                         sql_println!(
                             writer,
-                            "   delete from {} where {} in (select {} from deleted);",
-                            child_table.fully_qualified_table_name(database_type),
-                            relation.from_column_name(),
-                            relation.to_column_name()
-                        );
+                            "   delete from {} where {};",
+                            quoted_child_table,
+                            child_references_deleted(settings, relation, &quoted_child_table)
+                        )?;
                     }
                 }
             }
 
+            self.output_aggregations(writer, table, "deleted", false)?;
+
             for custom_trigger in table.triggers() {
                 if custom_trigger.trigger_type() == TriggerType::Delete
                     && custom_trigger.database_type() == DatabaseType::SqlServer
                 {
-                    sql_println!(writer, "{}", custom_trigger.trigger_text());
+                    sql_println!(writer, "{}", custom_trigger.trigger_text())?;
                 }
             }
 
-            sql_println!(writer, "END{}", separator);
-            sql_println!(writer, "");
-        });
+            sql_println!(writer, "END{}", separator)?;
+            sql_println!(writer, "")?;
+            Ok(())
+        })
     }
 
-    fn output_update_trigger(&self, table: &Table, separator: &str) {
+    fn output_update_trigger(&self, table: &Table, separator: &str) -> Result<(), GenerationError> {
         let database_type = self.context.settings().database_type();
-        let table_name = table.name().to_lowercase();
-        let fully_qualified_table = table.fully_qualified_table_name(database_type);
+        let table_name = self.context.settings().generated_name_part(table.name());
         let fully_qualified_trigger = database_type.qualified_name(table.schema_name(), &format!("{}_update", table_name));
+        let settings = self.context.settings();
+        let quoted_table = settings.qualified_name(table.schema_name(), table.name());
+        let quoted_trigger = settings.qualified_generated_name(table.schema_name(), &format!("{}_update", table_name));
 
         self.context.with_writer(|writer| {
-            sql_println!(writer, "/* {}_update */", table_name);
+            writer.begin_object(StatementKind::Trigger, Some(&fully_qualified_trigger));
+            sql_println!(writer, "/* {}_update */", table_name)?;
             sql_println!(
                 writer,
                 "if exists (select name from dbo.sysobjects where name = '{}_update' and type = 'TR')",
                 escape_sql_literal(&table_name)
-            );
-            sql_println!(writer, "   drop trigger {}{}", fully_qualified_trigger, separator);
-            sql_println!(writer, "");
-            sql_println!(writer, "create trigger {}_update on {} for insert, update as", table_name, fully_qualified_table);
-            sql_println!(writer, "if (select count(*) from inserted) > 0");
-            sql_println!(writer, "BEGIN");
+            )?;
+            sql_println!(writer, "   drop trigger {}{}", quoted_trigger, separator)?;
+            sql_println!(writer, "")?;
+            sql_println!(writer, "create trigger {} on {} for insert, update as", settings.quote_generated(&format!("{}_update", table_name)), quoted_table)?;
+            sql_println!(writer, "if (select count(*) from inserted) > 0")?;
+            sql_println!(writer, "BEGIN")?;
 
             if self.context.settings().foreign_key_mode() == ForeignKeyMode::Triggers {
                 for relation in table.relations() {
                     match relation.relation_type() {
                         RelationType::Enforce | RelationType::SetNull | RelationType::Cascade => {
-                            let to_table = self.database_model().find_table_by_qualified_name(relation.to_table_name());
+                            let to_table = resolve_table(self.database_model(), relation.to_table_name(), || format!("trigger {}", fully_qualified_trigger))?;
                             sql_println!(
                                 writer,
-                                "   if (select count(*) from inserted where {} is not null and {} not in (select {} from {})) > 0",
-                                relation.from_column_name(),
-                                relation.from_column_name(),
-                                relation.to_column_name(),
-                                to_table.fully_qualified_table_name(database_type)
-                            );
-                            sql_println!(writer, "   begin");
+                                "   if (select count(*) from inserted where {}) > 0",
+                                inserted_missing_parent(settings, relation, &settings.qualified_name(to_table.schema_name(), to_table.name()))
+                            )?;
+                            sql_println!(writer, "   begin")?;
                             sql_println!(
                                 writer,
                                 "      raiserror ('The value of {} was not found in the {} table.', 16, 1)",
-                                relation.from_column_name(),
+                                relation.from_columns_as_string(),
                                 to_table.fully_qualified_table_name(database_type)
-                            );
-                            sql_println!(writer, "      rollback transaction");
-                            sql_println!(writer, "      return");
-                            sql_println!(writer, "   end;");
+                            )?;
+                            sql_println!(writer, "      rollback transaction")?;
+                            sql_println!(writer, "      return")?;
+                            sql_println!(writer, "   end;")?;
                         }
                         RelationType::DoNothing => {}
                     }
                 }
             }
 
+            // `deleted` holds the previous values of updated rows (and is empty on insert),
+            // so their contribution is taken out before the new values are added.
+            self.output_aggregations(writer, table, "deleted", false)?;
+            self.output_aggregations(writer, table, "inserted", true)?;
+
             for custom_trigger in table.triggers() {
                 if custom_trigger.trigger_type() == TriggerType::Update
                     && custom_trigger.database_type() == DatabaseType::SqlServer
                 {
-                    sql_println!(writer, "{}", custom_trigger.trigger_text());
+                    sql_println!(writer, "{}", custom_trigger.trigger_text())?;
                 }
             }
 
-            sql_println!(writer, "END{}", separator);
-            sql_println!(writer, "");
-        });
+            sql_println!(writer, "END{}", separator)?;
+            sql_println!(writer, "")?;
+            Ok(())
+        })
+    }
+
+    /// Adds (`add`) or removes the contribution of the `rows` pseudo table (`inserted`/
+    /// `deleted`) to the summary table of every aggregation on `table`. The rows are first
+    /// totalled per bucket, so a multi-row statement touches each summary row once.
+    fn output_aggregations(&self, writer: &mut SqlWriter, table: &Table, rows: &str, add: bool) -> Result<(), GenerationError> {
+        let settings = self.context.settings();
+        let operator = if add { "+" } else { "-" };
+        // Reading the pseudo table under the table's name lets the criteria refer to the
+        // table's columns the same way it would in a query, as it does in Postgres.
+        let row = settings.quote(table.name());
+
+        for aggregation in table.aggregations() {
+            let destination = resolve_table(self.database_model(), aggregation.destination_table(), || {
+                format!("aggregation on {}", table.name())
+            })?;
+            let destination_table = settings.qualified_name(destination.schema_name(), destination.name());
+            let date_column = settings.quote(aggregation.date_column());
+            let time_stamp_column = settings.quote(aggregation.time_stamp_column());
+            let group_columns: Vec<String> = aggregation.aggregation_groups().iter().map(|group| settings.quote(group.destination())).collect();
+
+            let mut row_columns = vec![format!(
+                "{} as {}",
+                sqlserver_date_bucket(aggregation.aggregation_frequency(), &format!("{}.{}", row, date_column)),
+                date_column
+            )];
+            let mut bucket_columns = vec![date_column.clone()];
+            for (group, group_column) in aggregation.aggregation_groups().iter().zip(&group_columns) {
+                row_columns.push(format!(
+                    "{} as {}",
+                    aggregation_group_value(settings, table, group, &row)?,
+                    group_column
+                ));
+                bucket_columns.push(group_column.clone());
+            }
+
+            let mut totals = bucket_columns.clone();
+            for column in aggregation.aggregation_columns() {
+                let destination_column = settings.quote(column.destination_column());
+                match column.aggregation_type() {
+                    AggregationType::Sum => {
+                        row_columns.push(format!("{} as {}", aggregation_row_value(settings, column, &row), destination_column));
+                        totals.push(format!("sum({}) as {}", destination_column, destination_column));
+                    }
+                    AggregationType::Count => {
+                        totals.push(format!("count(*) as {}", destination_column));
+                    }
+                }
+            }
+
+            let mut condition = format!("{}.{} is not null", row, date_column);
+            if let Some(criteria) = aggregation.criteria() {
+                condition.push_str(&format!(" and ({})", criteria));
+            }
+
+            let bucket_totals = format!(
+                "(select {} from (select {} from {} as {} where {}) r group by {})",
+                totals.join(", "),
+                row_columns.join(", "),
+                rows,
+                row,
+                condition,
+                bucket_columns.join(", ")
+            );
+
+            let mut matches = vec![format!("d.{} = s.{}", date_column, date_column)];
+            for group_column in &group_columns {
+                matches.push(format!(
+                    "(d.{} = s.{} or (d.{} is null and s.{} is null))",
+                    group_column,
+                    group_column,
+                    group_column,
+                    group_column
+                ));
+            }
+
+            let mut assignments: Vec<String> = aggregation
+                .aggregation_columns()
+                .iter()
+                .map(|column| {
+                    let destination_column = settings.quote(column.destination_column());
+                    format!(
+                        "{} = d.{} {} s.{}",
+                        destination_column,
+                        destination_column,
+                        operator,
+                        destination_column
+                    )
+                })
+                .collect();
+            assignments.push(format!("{} = getdate()", time_stamp_column));
+
+            sql_println!(writer, "   update d set {}", assignments.join(", "))?;
+            sql_println!(writer, "      from {} d", destination_table)?;
+            sql_println!(writer, "      join {} s on {};", bucket_totals, matches.join(" and "))?;
+
+            if add {
+                let mut columns = bucket_columns.clone();
+                columns.extend(aggregation.aggregation_columns().iter().map(|c| settings.quote(c.destination_column())));
+                let values: Vec<String> = columns.iter().map(|c| format!("s.{}", c)).collect();
+                columns.push(time_stamp_column.clone());
+
+                sql_println!(writer, "   insert into {} ({})", destination_table, columns.join(", "))?;
+                sql_println!(writer, "      select {}, getdate() from {} s", values.join(", "), bucket_totals)?;
+                sql_println!(
+                    writer,
+                    "      where not exists (select 1 from {} d where {});",
+                    destination_table,
+                    matches.join(" and ")
+                )?;
+            }
+        }
+
+        Ok(())
     }
 
     fn database_model(&self) -> &schema_model::model::database_model::DatabaseModel {
@@ -229,14 +362,102 @@ impl SqlServerTriggerGenerator {
     }
 }
 
+/// The first day of the bucket `value` falls into. Weeks start on Monday, independent of
+/// the session's `datefirst` setting (1900-01-01, day zero, was a Monday).
+fn sqlserver_date_bucket(frequency: AggregationFrequency, value: &str) -> String {
+    match frequency {
+        AggregationFrequency::Daily => format!("cast({} as date)", value),
+        AggregationFrequency::Weekly => format!(
+            "dateadd(day, -(datediff(day, '19000101', {}) % 7), cast({} as date))",
+            value, value
+        ),
+        AggregationFrequency::Monthly => format!("datefromparts(year({}), month({}), 1)", value, value),
+        AggregationFrequency::Yearly => format!("datefromparts(year({}), 1, 1)", value),
+    }
+}
+
+// T-SQL has no row-value `(a, b) in (select ...)`, so a composite relation is matched with a
+// correlated `exists` instead; single-column relations keep the simpler `in` form.
+
+/// Condition on the child table selecting the rows that reference a row in `deleted`.
+fn child_references_deleted(settings: &SqlGeneratorSettings, relation: &Relation, child_table: &str) -> String {
+    if let [column] = relation.columns() {
+        format!("{} in (select {} from deleted)", settings.quote(column.from_column_name()), settings.quote(column.to_column_name()))
+    } else {
+        let pairs = relation
+            .columns()
+            .iter()
+            .map(|c| format!("deleted.{} = {}.{}", settings.quote(c.to_column_name()), child_table, settings.quote(c.from_column_name())))
+            .collect::<Vec<_>>()
+            .join(" and ");
+        format!("exists (select 1 from deleted where {})", pairs)
+    }
+}
+
+/// Condition on `deleted` selecting the rows still referenced by the child table.
+fn deleted_referenced_by_child(settings: &SqlGeneratorSettings, relation: &Relation, child_table: &str) -> String {
+    if let [column] = relation.columns() {
+        format!("{} in (select {} from {})", settings.quote(column.to_column_name()), settings.quote(column.from_column_name()), child_table)
+    } else {
+        let pairs = relation
+            .columns()
+            .iter()
+            .map(|c| format!("{}.{} = deleted.{}", child_table, settings.quote(c.from_column_name()), settings.quote(c.to_column_name())))
+            .collect::<Vec<_>>()
+            .join(" and ");
+        format!("exists (select 1 from {} where {})", child_table, pairs)
+    }
+}
+
+/// The referenced key of a `deleted` row as text, for the error message.
+fn deleted_key_text(settings: &SqlGeneratorSettings, relation: &Relation) -> String {
+    relation
+        .to_column_names()
+        .iter()
+        .map(|c| format!("convert(varchar, {})", settings.quote(c)))
+        .collect::<Vec<_>>()
+        .join(" + ',' + ")
+}
+
+/// Condition on `inserted` selecting fully populated foreign keys with no matching parent row.
+fn inserted_missing_parent(settings: &SqlGeneratorSettings, relation: &Relation, parent_table: &str) -> String {
+    let not_null = relation
+        .from_column_names()
+        .iter()
+        .map(|c| format!("{} is not null", settings.quote(c)))
+        .collect::<Vec<_>>()
+        .join(" and ");
+
+    if let [column] = relation.columns() {
+        format!(
+            "{} and {} not in (select {} from {})",
+            not_null,
+            settings.quote(column.from_column_name()),
+            settings.quote(column.to_column_name()),
+            parent_table
+        )
+    } else {
+        let pairs = relation
+            .columns()
+            .iter()
+            .map(|c| format!("{}.{} = inserted.{}", parent_table, settings.quote(c.to_column_name()), settings.quote(c.from_column_name())))
+            .collect::<Vec<_>>()
+            .join(" and ");
+        format!("{} and not exists (select 1 from {} where {})", not_null, parent_table, pairs)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::common::test_support::make_context_with_fk_mode;
+    use schema_model::model::aggregation::{
+        Aggregation, AggregationColumn, AggregationFrequency, AggregationGroup, AggregationType,
+    };
     use schema_model::builder::{ColumnBuilder, SchemaBuilder, TableBuilder};
     use schema_model::model::column_type::ColumnType;
     use schema_model::model::database_model::DatabaseModel;
-    use schema_model::model::relation::Relation;
+    use schema_model::model::relation::{Relation, RelationColumn};
     use schema_model::model::types::{BooleanMode, DatabaseType, ForeignKeyMode, RelationType};
 
     fn build_model_with_relation() -> DatabaseModel {
@@ -263,7 +484,7 @@ mod tests {
         let (ctx, buffer) = make_context_with_fk_mode(model, DatabaseType::SqlServer, ForeignKeyMode::Triggers);
 
         let generator = SqlServerTriggerGenerator::new(ctx);
-        generator.output_triggers();
+        generator.output_triggers().unwrap();
 
         let output = buffer.contents();
         assert!(output.contains("create trigger child_update on app.child for insert, update as"));
@@ -278,7 +499,7 @@ mod tests {
         let (ctx, buffer) = make_context_with_fk_mode(model, DatabaseType::SqlServer, ForeignKeyMode::Triggers);
 
         let generator = SqlServerTriggerGenerator::new(ctx);
-        generator.output_triggers();
+        generator.output_triggers().unwrap();
 
         let output = buffer.contents();
         assert!(output.contains("drop trigger app.child_update"));
@@ -299,7 +520,7 @@ mod tests {
         let (ctx, buffer) = make_context_with_fk_mode(model, DatabaseType::SqlServer, ForeignKeyMode::Relations);
 
         let generator = SqlServerTriggerGenerator::new(ctx);
-        generator.output_triggers();
+        generator.output_triggers().unwrap();
 
         let output = buffer.contents();
         assert!(output.contains("drop trigger dbo.widget_update"));
@@ -322,7 +543,7 @@ mod tests {
         let (ctx, buffer) = make_context_with_fk_mode(model, DatabaseType::SqlServer, ForeignKeyMode::Relations);
 
         let generator = SqlServerTriggerGenerator::new(ctx);
-        generator.output_triggers();
+        generator.output_triggers().unwrap();
 
         let output = buffer.contents();
         assert!(output.contains("where name = 'o''brien_update' and type = 'TR'"));
@@ -334,7 +555,7 @@ mod tests {
         let (ctx, buffer) = make_context_with_fk_mode(model, DatabaseType::SqlServer, ForeignKeyMode::Relations);
 
         let generator = SqlServerTriggerGenerator::new(ctx);
-        generator.output_triggers();
+        generator.output_triggers().unwrap();
 
         assert_eq!(buffer.contents(), "");
     }
@@ -367,7 +588,7 @@ mod tests {
         let (ctx, buffer) = make_context_with_fk_mode(model, DatabaseType::SqlServer, ForeignKeyMode::Triggers);
 
         let generator = SqlServerTriggerGenerator::new(ctx);
-        generator.output_triggers();
+        generator.output_triggers().unwrap();
 
         let output = buffer.contents();
         assert!(output.contains("create trigger parent_delete on app.parent for delete as"));
@@ -381,7 +602,7 @@ mod tests {
         let (ctx, buffer) = make_context_with_fk_mode(model, DatabaseType::SqlServer, ForeignKeyMode::Triggers);
 
         let generator = SqlServerTriggerGenerator::new(ctx);
-        generator.output_triggers();
+        generator.output_triggers().unwrap();
 
         let output = buffer.contents();
         assert!(output.contains("update app.child set parent_id = null where parent_id in (select id from deleted);"));
@@ -393,7 +614,7 @@ mod tests {
         let (ctx, buffer) = make_context_with_fk_mode(model, DatabaseType::SqlServer, ForeignKeyMode::Triggers);
 
         let generator = SqlServerTriggerGenerator::new(ctx);
-        generator.output_triggers();
+        generator.output_triggers().unwrap();
 
         let output = buffer.contents();
         assert!(output.contains("delete from app.child where parent_id in (select id from deleted);"));
@@ -427,9 +648,120 @@ mod tests {
         let (ctx, buffer) = make_context_with_fk_mode(model, DatabaseType::SqlServer, ForeignKeyMode::Triggers);
 
         let generator = SqlServerTriggerGenerator::new(ctx);
-        generator.output_triggers();
+        generator.output_triggers().unwrap();
 
         let output = buffer.contents();
         assert!(output.contains("delete from app.child where parent_id in (select id from deleted);"));
     }
+
+    #[test]
+    fn output_triggers_use_exists_for_a_composite_relation() {
+        let columns = vec![
+            RelationColumn::new("tenant_id", "tenant_id"),
+            RelationColumn::new("parent_id", "id"),
+        ];
+        let mut parent = TableBuilder::new(Some("app"), "parent")
+            .add_column(ColumnBuilder::new(None::<&str>, "tenant_id", ColumnType::Int).required(true).build())
+            .add_column(ColumnBuilder::new(None::<&str>, "id", ColumnType::Int).required(true).build())
+            .add_key(
+                schema_model::builder::KeyBuilder::new(schema_model::model::types::KeyType::Primary)
+                    .add_column("tenant_id")
+                    .add_column("id")
+                    .build(),
+            )
+            .build();
+        parent.add_reverse_relation(Relation::new_composite("app.parent", "app.child", columns.clone(), RelationType::Cascade, false));
+
+        let child = TableBuilder::new(Some("app"), "child")
+            .add_column(ColumnBuilder::new(None::<&str>, "tenant_id", ColumnType::Int).build())
+            .add_column(ColumnBuilder::new(None::<&str>, "parent_id", ColumnType::Int).build())
+            .add_relation(Relation::new_composite("app.parent", "child", columns, RelationType::Cascade, false))
+            .build();
+        let schema = SchemaBuilder::new(Some("app"))
+            .add_table(parent)
+            .add_table(child)
+            .build();
+        let model = DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema]);
+        let (ctx, buffer) = make_context_with_fk_mode(model, DatabaseType::SqlServer, ForeignKeyMode::Triggers);
+
+        let generator = SqlServerTriggerGenerator::new(ctx);
+        generator.output_triggers().unwrap();
+
+        let output = buffer.contents();
+        assert!(output.contains(
+            "delete from app.child where exists (select 1 from deleted where deleted.tenant_id = app.child.tenant_id and deleted.id = app.child.parent_id);"
+        ));
+        assert!(output.contains(
+            "where tenant_id is not null and parent_id is not null and not exists (select 1 from app.parent where app.parent.tenant_id = inserted.tenant_id and app.parent.id = inserted.parent_id)"
+        ));
+    }
+
+    fn build_model_with_aggregation(criteria: Option<&str>) -> DatabaseModel {
+        let summary = TableBuilder::new(Some("app"), "sale_summary")
+            .add_column(ColumnBuilder::new(None::<&str>, "sale_date", ColumnType::Date).required(true).build())
+            .add_column(ColumnBuilder::new(None::<&str>, "store_id", ColumnType::Int).build())
+            .add_column(ColumnBuilder::new(None::<&str>, "total", ColumnType::Decimal).build())
+            .add_column(ColumnBuilder::new(None::<&str>, "sale_count", ColumnType::Int).build())
+            .add_column(ColumnBuilder::new(None::<&str>, "updated_at", ColumnType::Timestamp).build())
+            .build();
+        let sale = TableBuilder::new(Some("app"), "sale")
+            .add_column(ColumnBuilder::new(None::<&str>, "sale_date", ColumnType::Date).required(true).build())
+            .add_column(ColumnBuilder::new(None::<&str>, "store_id", ColumnType::Int).build())
+            .add_column(ColumnBuilder::new(None::<&str>, "amount", ColumnType::Decimal).build())
+            .add_aggregation(Aggregation::new(
+                "app.sale_summary",
+                "sale_date",
+                criteria,
+                "updated_at",
+                AggregationFrequency::Monthly,
+                vec![
+                    AggregationColumn::new(AggregationType::Sum, "amount", "total"),
+                    AggregationColumn::new(AggregationType::Count, "", "sale_count"),
+                ],
+                vec![AggregationGroup::new("store_id", "store_id", None)],
+            ))
+            .build();
+        let schema = SchemaBuilder::new(Some("app"))
+            .add_table(summary)
+            .add_table(sale)
+            .build();
+        DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema])
+    }
+
+    #[test]
+    fn output_triggers_maintain_the_aggregation_summary_table() {
+        let model = build_model_with_aggregation(Some("amount > 0"));
+        let (ctx, buffer) = make_context_with_fk_mode(model, DatabaseType::SqlServer, ForeignKeyMode::Relations);
+
+        let generator = SqlServerTriggerGenerator::new(ctx);
+        generator.output_triggers().unwrap();
+
+        let output = buffer.contents();
+        let inserted_totals = "(select sale_date, store_id, sum(total) as total, count(*) as sale_count from (select datefromparts(year(sale.sale_date), month(sale.sale_date), 1) as sale_date, sale.store_id as store_id, coalesce(sale.amount, 0) as total from inserted as sale where sale.sale_date is not null and (amount > 0)) r group by sale_date, store_id)";
+        assert!(output.contains("create trigger sale_delete on app.sale for delete as"));
+        assert!(output.contains("update d set total = d.total - s.total, sale_count = d.sale_count - s.sale_count, updated_at = getdate()"));
+        assert!(output.contains("update d set total = d.total + s.total, sale_count = d.sale_count + s.sale_count, updated_at = getdate()"));
+        assert!(output.contains(&format!(
+            "join {} s on d.sale_date = s.sale_date and (d.store_id = s.store_id or (d.store_id is null and s.store_id is null));",
+            inserted_totals
+        )));
+        assert!(output.contains("insert into app.sale_summary (sale_date, store_id, total, sale_count, updated_at)"));
+        assert!(output.contains(&format!(
+            "select s.sale_date, s.store_id, s.total, s.sale_count, getdate() from {} s",
+            inserted_totals
+        )));
+    }
+
+    #[test]
+    fn output_triggers_apply_aggregation_criteria_to_the_row_under_the_table_name() {
+        let model = build_model_with_aggregation(Some("sale.amount > 0"));
+        let (ctx, buffer) = make_context_with_fk_mode(model, DatabaseType::SqlServer, ForeignKeyMode::Relations);
+
+        let generator = SqlServerTriggerGenerator::new(ctx);
+        generator.output_triggers().unwrap();
+
+        let output = buffer.contents();
+        assert!(output.contains("from deleted as sale where sale.sale_date is not null and (sale.amount > 0)"));
+        assert!(output.contains("from inserted as sale where sale.sale_date is not null and (sale.amount > 0)"));
+    }
 }
@@ -1,7 +1,12 @@
+use crate::common::generated_statement::StatementKind;
+use crate::common::generation_error::GenerationError;
 use crate::common::generator_context::GeneratorContext;
+use crate::common::index_generator::{index_columns_sql, index_name};
 use crate::common::sql_string::escape_sql_literal;
+use crate::common::sql_writer::SqlWriter;
 use crate::common::view_generator::ViewGenerator;
 use crate::sql_println;
+use schema_model::model::key::Key;
 use schema_model::model::view::View;
 
 pub struct SqlServerViewGenerator {
@@ -13,10 +18,45 @@ impl SqlServerViewGenerator {
         Self { context }
     }
 
+    /// SQL Server materializes a view through an index: the view is schema-bound and its
+    /// first unique index becomes the unique clustered index that stores the rows. The
+    /// remaining indexes can only be created after that one, so without a unique index the
+    /// view is left unindexed.
+    fn output_indexed_view(&self, writer: &mut SqlWriter, view: &View, view_name: &str, separator: &str) -> Result<(), GenerationError> {
+        sql_println!(writer, "create view {} with schemabinding as", view_name)?;
+        sql_println!(writer, "   {}{}", view.sql(), separator)?;
+
+        let mut indexes: Vec<(usize, &Key)> = view.indexes().iter().enumerate().collect();
+        let Some(clustered) = indexes.iter().position(|(_, key)| key.is_unique()) else {
+            sql_println!(
+                writer,
+                "/* {} has no unique index, so SQL Server keeps it as a plain schema-bound view */",
+                view_name
+            )?;
+            return Ok(());
+        };
+        let clustered = indexes.remove(clustered);
+        indexes.insert(0, clustered);
+
+        for (order, (position, key)) in indexes.into_iter().enumerate() {
+            sql_println!(
+                writer,
+                "create {}{}index {} on {} ({}){}{}",
+                if key.is_unique() { "unique " } else { "" },
+                if order == 0 { "clustered " } else { "" },
+                self.context.settings().quote_generated(&index_name(self.context.settings(), view.name(), key, position + 1)),
+                view_name,
+                index_columns_sql(self.context.settings(), view.name(), key)?,
+                key.filter().map(|filter| format!(" where {}", filter)).unwrap_or_default(),
+                separator
+            )?;
+        }
+        Ok(())
+    }
 }
 
 impl ViewGenerator for SqlServerViewGenerator {
-    fn output_views(&self) {
+    fn output_views(&self) -> Result<(), GenerationError> {
         let database_model = self.context.settings().database_model();
         let database_type = self.context.settings().database_type();
         let separator = self.context.settings().statement_separator();
@@ -28,18 +68,26 @@ impl ViewGenerator for SqlServerViewGenerator {
             .collect();
 
         if !views.is_empty() {
-            self.context.with_writer(|writer| {
+            self.context.with_writer(|writer| -> Result<(), GenerationError> {
                 for view in views {
-                    let view_name = view.fully_qualified_view_name(database_type);
-                    sql_println!(writer, "/* {} */", view_name);
-                    sql_println!(writer, "if exists (select name from dbo.sysobjects where name = '{}' and type = 'V')", escape_sql_literal(view.name()));
-                    sql_println!(writer, "   drop view {}{}", view_name, separator);
-                    sql_println!(writer, "create view {} as", view_name);
-                    sql_println!(writer, "   {}{}", view.sql(), separator);
-                    sql_println!(writer, "");
+                    let view_name = self.context.settings().qualified_name(view.schema_name(), view.name());
+                    writer.begin_object(StatementKind::View, Some(&view.fully_qualified_view_name(database_type)));
+                    sql_println!(writer, "/* {} */", view_name)?;
+                    sql_println!(writer, "if exists (select name from dbo.sysobjects where name = '{}' and type = 'V')", escape_sql_literal(&self.context.settings().database_name(view.name())))?;
+                    sql_println!(writer, "   drop view {}{}", view_name, separator)?;
+                    if view.is_materialized() {
+                        self.output_indexed_view(writer, &view, &view_name, separator)?;
+                    } else {
+                        sql_println!(writer, "create view {} as", view_name)?;
+                        sql_println!(writer, "   {}{}", view.sql(), separator)?;
+                    }
+                    sql_println!(writer, "")?;
                 }
-            });
+                Ok(())
+            })?;
         }
+
+        Ok(())
     }
 }
 
@@ -59,7 +107,7 @@ mod tests {
         let (ctx, buffer) = make_context(model, DatabaseType::SqlServer);
 
         let generator = SqlServerViewGenerator::new(ctx);
-        generator.output_views();
+        generator.output_views().unwrap();
 
         let output = buffer.contents();
         assert!(output.contains("dbo.active_users"));
@@ -76,7 +124,7 @@ mod tests {
         let (ctx, buffer) = make_context(model, DatabaseType::SqlServer);
 
         let generator = SqlServerViewGenerator::new(ctx);
-        generator.output_views();
+        generator.output_views().unwrap();
 
         assert!(buffer.contents().contains("create view app.active_users as"));
     }
@@ -89,7 +137,7 @@ mod tests {
         let (ctx, buffer) = make_context(model, DatabaseType::SqlServer);
 
         let generator = SqlServerViewGenerator::new(ctx);
-        generator.output_views();
+        generator.output_views().unwrap();
 
         assert!(buffer.contents().contains("create view dbo.active_users as"));
     }
@@ -104,11 +152,44 @@ mod tests {
         let (ctx, buffer) = make_context(model, DatabaseType::SqlServer);
 
         let generator = SqlServerViewGenerator::new(ctx);
-        generator.output_views();
+        generator.output_views().unwrap();
 
         assert!(buffer.contents().contains("where name = 'o''brien' and type = 'V'"));
     }
 
+    #[test]
+    fn output_views_renders_materialized_view_as_indexed_view() {
+        use schema_model::model::key::KeyColumn;
+        use schema_model::model::types::{KeyType, RefreshPolicy};
+
+        let by_total = Key::new(KeyType::Index, vec![KeyColumn::new("total")]);
+        let unique = Key::new_full(KeyType::Index, vec![KeyColumn::new("customer_id")], false, false, true, None::<String>);
+        let view = View::new_materialized(
+            None,
+            "order_totals",
+            "select customer_id, sum(amount) as total, count_big(*) as orders from dbo.orders group by customer_id",
+            None,
+            RefreshPolicy::Manual,
+            vec![by_total, unique],
+        );
+        let schema = SchemaBuilder::new(None::<&str>).add_view(view).build();
+        let model = DatabaseModel::new(BooleanMode::Native, ForeignKeyMode::Relations, vec![schema]);
+        let (ctx, buffer) = make_context(model, DatabaseType::SqlServer);
+
+        let generator = SqlServerViewGenerator::new(ctx);
+        generator.output_views().unwrap();
+
+        let output = buffer.contents();
+        assert!(output.contains("create view dbo.order_totals with schemabinding as"));
+        let clustered = output
+            .find("create unique clustered index ix_order_totals2 on dbo.order_totals (customer_id)\nGO")
+            .expect("the unique index is clustered");
+        let secondary = output
+            .find("create index ix_order_totals1 on dbo.order_totals (total)\nGO")
+            .expect("the other index follows");
+        assert!(clustered < secondary, "the clustered index must exist before any other index on the view");
+    }
+
     #[test]
     fn output_views_does_nothing_when_no_views() {
         let schema = SchemaBuilder::new(None::<&str>).build();
@@ -116,7 +197,7 @@ mod tests {
         let (ctx, buffer) = make_context(model, DatabaseType::SqlServer);
 
         let generator = SqlServerViewGenerator::new(ctx);
-        generator.output_views();
+        generator.output_views().unwrap();
 
         assert_eq!(buffer.contents(), "");
     }